@@ -4,16 +4,20 @@ use cf_utilities::{
 };
 use chainflip_api::{
 	self, clean_foreign_chain_address,
-	primitives::{AccountRole, Asset, BasisPoints, BlockNumber, CcmChannelMetadata, ChannelId},
+	primitives::{
+		AccountRole, Affiliate, Asset, BasisPoints, BlockNumber, CcmChannelMetadata, ChannelId,
+		EgressId, ForeignChain, MAX_AFFILIATES,
+	},
+	rpc_server::{self, RpcServerOptions},
 	settings::StateChain,
-	BrokerApi, OperatorApi, StateChainApi,
+	AccountId32, BrokerApi, OperatorApi, StateChainApi,
 };
 use clap::Parser;
+use custom_rpc::{CustomApiClient, RpcBrokerChannel, RpcBrokerEarnings};
 use futures::FutureExt;
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
-	server::ServerBuilder,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -41,6 +45,68 @@ impl From<chainflip_api::SwapDepositAddress> for BrokerSwapDepositAddress {
 	}
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BrokerFeeWithdrawal {
+	pub egress_id: EgressId,
+	pub egress_amount: NumberOrHex,
+	pub egress_fee: NumberOrHex,
+	pub destination_address: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BrokerSkippedWithdrawal {
+	pub asset: Asset,
+	pub amount: NumberOrHex,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BrokerFeeWithdrawals {
+	pub withdrawn: Vec<BrokerFeeWithdrawal>,
+	pub skipped: Vec<BrokerSkippedWithdrawal>,
+}
+
+impl From<chainflip_api::BrokerFeeWithdrawals> for BrokerFeeWithdrawals {
+	fn from(value: chainflip_api::BrokerFeeWithdrawals) -> Self {
+		Self {
+			withdrawn: value
+				.withdrawn
+				.into_iter()
+				.map(|withdrawal| BrokerFeeWithdrawal {
+					egress_id: withdrawal.egress_id,
+					egress_amount: withdrawal.egress_amount.into(),
+					egress_fee: withdrawal.egress_fee.into(),
+					destination_address: withdrawal.destination_address,
+				})
+				.collect(),
+			skipped: value
+				.skipped
+				.into_iter()
+				.map(|(asset, amount)| BrokerSkippedWithdrawal { asset, amount: amount.into() })
+				.collect(),
+		}
+	}
+}
+
+/// Methods that submit signed extrinsics, and are therefore recorded in the audit log.
+const EXTRINSIC_SUBMITTING_METHODS: &[&str] = &[
+	"broker_register_account",
+	"broker_registerAccount",
+	"broker_request_swap_deposit_address",
+	"broker_requestSwapDepositAddress",
+	"broker_register_withdrawal_address",
+	"broker_registerWithdrawalAddress",
+	"broker_withdraw_all_fees",
+	"broker_withdrawAllFees",
+	"broker_close_channel",
+	"broker_closeChannel",
+	"broker_extend_channel",
+	"broker_extendChannel",
+	"broker_make_channel_sticky",
+	"broker_makeChannelSticky",
+	"broker_update_sticky_channel_destination",
+	"broker_updateStickyChannelDestination",
+];
+
 #[rpc(server, client, namespace = "broker")]
 pub trait Rpc {
 	#[method(name = "register_account", aliases = ["broker_registerAccount"])]
@@ -55,7 +121,69 @@ pub trait Rpc {
 		broker_commission_bps: BasisPoints,
 		channel_metadata: Option<CcmChannelMetadata>,
 		boost_fee: Option<BasisPoints>,
+		affiliates: Option<Vec<Affiliate<AccountId32>>>,
 	) -> RpcResult<BrokerSwapDepositAddress>;
+
+	#[method(name = "encode_btc_vault_swap_payload", aliases = ["broker_encodeBtcVaultSwapPayload"])]
+	async fn encode_btc_vault_swap_payload(
+		&self,
+		destination_asset: Asset,
+		destination_address: String,
+		min_output_amount: NumberOrHex,
+		broker_commission_bps: BasisPoints,
+	) -> RpcResult<String>;
+
+	#[method(
+		name = "encode_exact_output_swap_cf_parameters",
+		aliases = ["broker_encodeExactOutputSwapCfParameters"]
+	)]
+	async fn encode_exact_output_swap_cf_parameters(
+		&self,
+		output_amount: NumberOrHex,
+	) -> RpcResult<String>;
+
+	#[method(name = "register_withdrawal_address", aliases = ["broker_registerWithdrawalAddress"])]
+	async fn register_withdrawal_address(
+		&self,
+		chain: ForeignChain,
+		address: String,
+	) -> RpcResult<String>;
+
+	#[method(name = "withdraw_all_fees", aliases = ["broker_withdrawAllFees"])]
+	async fn withdraw_all_fees(&self) -> RpcResult<BrokerFeeWithdrawals>;
+
+	#[method(name = "earnings", aliases = ["broker_earnings"])]
+	async fn earnings(&self) -> RpcResult<RpcBrokerEarnings>;
+
+	#[method(name = "channels", aliases = ["broker_channels"])]
+	async fn channels(&self) -> RpcResult<Vec<RpcBrokerChannel>>;
+
+	#[method(name = "close_channel", aliases = ["broker_closeChannel"])]
+	async fn close_channel(&self, chain: ForeignChain, channel_id: ChannelId) -> RpcResult<String>;
+
+	#[method(name = "extend_channel", aliases = ["broker_extendChannel"])]
+	async fn extend_channel(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+		extension: u64,
+	) -> RpcResult<String>;
+
+	#[method(name = "make_channel_sticky", aliases = ["broker_makeChannelSticky"])]
+	async fn make_channel_sticky(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+	) -> RpcResult<String>;
+
+	#[method(name = "update_sticky_channel_destination", aliases = ["broker_updateStickyChannelDestination"])]
+	async fn update_sticky_channel_destination(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+		destination_asset: Asset,
+		destination_address: String,
+	) -> RpcResult<String>;
 }
 
 pub struct RpcServerImpl {
@@ -93,21 +221,165 @@ impl RpcServer for RpcServerImpl {
 		broker_commission_bps: BasisPoints,
 		channel_metadata: Option<CcmChannelMetadata>,
 		boost_fee: Option<BasisPoints>,
+		affiliates: Option<Vec<Affiliate<AccountId32>>>,
 	) -> RpcResult<BrokerSwapDepositAddress> {
 		Ok(self
 			.api
 			.broker_api()
-			.request_swap_deposit_address(
+			.request_swap_deposit_address_with_affiliates(
 				source_asset,
 				destination_asset,
 				clean_foreign_chain_address(destination_asset.into(), &destination_address)?,
 				broker_commission_bps,
+				affiliates.unwrap_or_default().try_into().map_err(|_| {
+					anyhow::anyhow!("Too many affiliates, the maximum is {MAX_AFFILIATES}")
+				})?,
 				channel_metadata,
 				boost_fee,
 			)
 			.await
 			.map(BrokerSwapDepositAddress::from)?)
 	}
+
+	async fn encode_btc_vault_swap_payload(
+		&self,
+		destination_asset: Asset,
+		destination_address: String,
+		min_output_amount: NumberOrHex,
+		broker_commission_bps: BasisPoints,
+	) -> RpcResult<String> {
+		Ok(self
+			.api
+			.broker_api()
+			.encode_btc_vault_swap_payload(
+				destination_asset,
+				clean_foreign_chain_address(destination_asset.into(), &destination_address)?,
+				min_output_amount.try_into().map_err(|_| {
+					anyhow::anyhow!("Minimum output amount is too large: {min_output_amount:?}")
+				})?,
+				broker_commission_bps,
+			)
+			.map(|payload| format!("0x{}", hex::encode(payload)))?)
+	}
+
+	async fn encode_exact_output_swap_cf_parameters(
+		&self,
+		output_amount: NumberOrHex,
+	) -> RpcResult<String> {
+		Ok(format!(
+			"0x{}",
+			hex::encode(chainflip_api::encode_exact_output_swap_cf_parameters(
+				output_amount.try_into().map_err(|_| {
+					anyhow::anyhow!("Output amount is too large: {output_amount:?}")
+				})?,
+			))
+		))
+	}
+
+	async fn register_withdrawal_address(
+		&self,
+		chain: ForeignChain,
+		address: String,
+	) -> RpcResult<String> {
+		Ok(self
+			.api
+			.broker_api()
+			.register_withdrawal_address(clean_foreign_chain_address(chain, &address)?)
+			.await
+			.map(|tx_hash| format!("{tx_hash:#x}"))?)
+	}
+
+	async fn withdraw_all_fees(&self) -> RpcResult<BrokerFeeWithdrawals> {
+		Ok(self
+			.api
+			.broker_api()
+			.withdraw_all_fees()
+			.await
+			.map(BrokerFeeWithdrawals::from)?)
+	}
+
+	async fn earnings(&self) -> RpcResult<RpcBrokerEarnings> {
+		Ok(self
+			.api
+			.state_chain_client
+			.base_rpc_client
+			.raw_rpc_client
+			.cf_broker_earnings(
+				self.api.state_chain_client.account_id(),
+				Some(self.api.state_chain_client.latest_finalized_block().hash),
+			)
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("The account is not registered as a broker"))?)
+	}
+
+	async fn channels(&self) -> RpcResult<Vec<RpcBrokerChannel>> {
+		Ok(self
+			.api
+			.state_chain_client
+			.base_rpc_client
+			.raw_rpc_client
+			.cf_broker_channels(
+				self.api.state_chain_client.account_id(),
+				Some(self.api.state_chain_client.latest_finalized_block().hash),
+			)
+			.await?)
+	}
+
+	async fn close_channel(&self, chain: ForeignChain, channel_id: ChannelId) -> RpcResult<String> {
+		Ok(self
+			.api
+			.broker_api()
+			.close_deposit_channel(chain, channel_id)
+			.await
+			.map(|tx_hash| format!("{tx_hash:#x}"))?)
+	}
+
+	async fn extend_channel(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+		extension: u64,
+	) -> RpcResult<String> {
+		Ok(self
+			.api
+			.broker_api()
+			.extend_deposit_channel(chain, channel_id, extension)
+			.await
+			.map(|tx_hash| format!("{tx_hash:#x}"))?)
+	}
+
+	async fn make_channel_sticky(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+	) -> RpcResult<String> {
+		Ok(self
+			.api
+			.broker_api()
+			.make_channel_sticky(chain, channel_id)
+			.await
+			.map(|tx_hash| format!("{tx_hash:#x}"))?)
+	}
+
+	async fn update_sticky_channel_destination(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+		destination_asset: Asset,
+		destination_address: String,
+	) -> RpcResult<String> {
+		Ok(self
+			.api
+			.broker_api()
+			.update_sticky_channel_destination(
+				chain,
+				channel_id,
+				destination_asset,
+				clean_foreign_chain_address(destination_asset.into(), &destination_address)?,
+			)
+			.await
+			.map(|tx_hash| format!("{tx_hash:#x}"))?)
+	}
 }
 
 #[derive(Parser, Debug, Clone, Default)]
@@ -131,6 +403,8 @@ pub struct BrokerOptions {
 		help = "A path to a file that contains the broker's secret key for signing extrinsics."
 	)]
 	pub signing_key_file: PathBuf,
+	#[clap(flatten)]
+	pub server: RpcServerOptions,
 }
 
 #[tokio::main]
@@ -144,9 +418,15 @@ async fn main() -> anyhow::Result<()> {
 
 	task_scope(|scope| {
 		async move {
-			let server = ServerBuilder::default().build(format!("0.0.0.0:{}", opts.port)).await?;
-			let server_addr = server.local_addr()?;
-			let server = server.start(RpcServerImpl::new(scope, opts).await?.into_rpc())?;
+			let server_options = opts.server.clone();
+			let (server_addr, server) = rpc_server::start_server(
+				scope,
+				opts.port,
+				&server_options,
+				EXTRINSIC_SUBMITTING_METHODS,
+				RpcServerImpl::new(scope, opts).await?.into_rpc(),
+			)
+			.await?;
 
 			log::info!("🎙 Server is listening on {server_addr}.");
 
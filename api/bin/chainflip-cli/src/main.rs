@@ -96,8 +96,45 @@ async fn run_cli() -> Result<()> {
 						api.lp_api().register_liquidity_refund_address(lra_address).await?;
 					println!("Liquidity Refund address registered. Tx hash: {tx_hash}");
 				},
-				Redeem { amount, eth_address, executor_address } => {
-					request_redemption(api, amount, eth_address, executor_address).await?;
+				LiquidityProvider(LiquidityProviderSubcommands::InternalSwap {
+					source_asset,
+					destination_asset,
+					amount,
+					min_output,
+				}) => {
+					let swap_id = api
+						.lp_api()
+						.internal_swap(
+							amount,
+							source_asset,
+							destination_asset,
+							min_output,
+							api::WaitFor::InBlock,
+						)
+						.await?
+						.unwrap_details();
+					println!("Internal swap scheduled. Swap id: {swap_id}");
+				},
+				Redeem {
+					amount,
+					eth_address,
+					executor_address,
+					on_retirement,
+					recurring_floor,
+					interval,
+				} => {
+					let schedule = match (on_retirement, recurring_floor.zip(interval)) {
+						(true, _) => RedemptionSchedule::OnRetirement,
+						(false, Some((floor, interval))) =>
+							RedemptionSchedule::Recurring { floor, interval },
+						(false, None) => RedemptionSchedule::Now,
+					};
+					request_redemption(api, amount, eth_address, executor_address, schedule)
+						.await?;
+				},
+				CancelScheduledRedemptions {} => {
+					let tx_hash = api.operator_api().cancel_scheduled_redemptions().await?;
+					println!("Scheduled redemptions cancelled at tx {tx_hash:#x}.");
 				},
 				BindRedeemAddress { eth_address } => {
 					bind_redeem_address(api.operator_api(), &eth_address).await?;
@@ -150,17 +187,25 @@ async fn run_cli() -> Result<()> {
 	.await
 }
 
-/// Turns the amount of FLIP into a RedemptionAmount in Flipperinos.
-fn flip_to_redemption_amount(amount: Option<f64>) -> RedemptionAmount {
+/// When a redemption should be requested.
+enum RedemptionSchedule {
+	Now,
+	OnRetirement,
+	Recurring { floor: f64, interval: u32 },
+}
+
+/// Turns an amount of FLIP into Flipperinos.
+fn flip_to_flipperinos(amount: f64) -> u128 {
 	// Using a set number of decimal places of accuracy to avoid floating point rounding errors
 	const MAX_DECIMAL_PLACES: u32 = 6;
+	((round_f64(amount, MAX_DECIMAL_PLACES) * 10_f64.powi(MAX_DECIMAL_PLACES as i32)) as u128) *
+		10_u128.pow(FLIP_DECIMALS - MAX_DECIMAL_PLACES)
+}
+
+/// Turns the amount of FLIP into a RedemptionAmount in Flipperinos.
+fn flip_to_redemption_amount(amount: Option<f64>) -> RedemptionAmount {
 	match amount {
-		Some(amount_float) => {
-			let atomic_amount = ((round_f64(amount_float, MAX_DECIMAL_PLACES) *
-				10_f64.powi(MAX_DECIMAL_PLACES as i32)) as u128) *
-				10_u128.pow(FLIP_DECIMALS - MAX_DECIMAL_PLACES);
-			RedemptionAmount::Exact(atomic_amount)
-		},
+		Some(amount_float) => RedemptionAmount::Exact(flip_to_flipperinos(amount_float)),
 		None => RedemptionAmount::Max,
 	}
 }
@@ -170,6 +215,7 @@ async fn request_redemption(
 	amount: Option<f64>,
 	supplied_redeem_address: String,
 	supplied_executor_address: Option<String>,
+	schedule: RedemptionSchedule,
 ) -> Result<()> {
 	// Check validity of the redeem address
 	let redeem_address = EthereumAddress::from(
@@ -186,8 +232,27 @@ async fn request_redemption(
 		None
 	};
 
+	if let RedemptionSchedule::Recurring { floor, interval } = schedule {
+		let atomic_floor = flip_to_flipperinos(floor);
+		println!(
+			"Scheduling a redemption of all FLIP above `{floor}` FLIP (`{atomic_floor}` Flipperinos) to ETH address `{redeem_address:?}` every {interval} blocks."
+		);
+		if !confirm_submit() {
+			return Ok(())
+		}
+		let tx_hash = api
+			.operator_api()
+			.schedule_recurring_redemption(atomic_floor, interval, redeem_address, executor_address)
+			.await?;
+		println!("Recurring redemption scheduled at tx {tx_hash:#x}.");
+		return Ok(())
+	}
+
 	// Calculate the redemption amount
 	let redeem_amount = flip_to_redemption_amount(amount);
+	if matches!(schedule, RedemptionSchedule::OnRetirement) {
+		println!("The redemption will be requested once your account is no longer an authority.");
+	}
 	match redeem_amount {
 		RedemptionAmount::Exact(atomic_amount) => {
 			println!(
@@ -203,6 +268,15 @@ async fn request_redemption(
 		return Ok(())
 	}
 
+	if matches!(schedule, RedemptionSchedule::OnRetirement) {
+		let tx_hash = api
+			.operator_api()
+			.schedule_retirement_redemption(redeem_amount, redeem_address, executor_address)
+			.await?;
+		println!("Retirement redemption scheduled at tx {tx_hash:#x}.");
+		return Ok(())
+	}
+
 	let tx_hash = api
 		.operator_api()
 		.request_redemption(redeem_amount, redeem_address, executor_address)
@@ -94,6 +94,18 @@ pub enum LiquidityProviderSubcommands {
 	/// Register a Liquidity Refund Address for the given chain. An address must be
 	/// registered to request a deposit address for the given chain.
 	RegisterLiquidityRefundAddress { chain: ForeignChain, address: String },
+	/// Swap part of your free balance into another asset. The output is credited back to your
+	/// free balance.
+	InternalSwap {
+		/// Source asset ("ETH"|"DOT")
+		source_asset: Asset,
+		/// Destination asset ("ETH"|"DOT")
+		destination_asset: Asset,
+		/// Amount of the source asset to swap, in the asset's smallest unit
+		amount: u128,
+		/// Minimum amount of the destination asset to receive, otherwise the swap is refunded
+		min_output: u128,
+	},
 }
 
 #[derive(Parser, Clone, Debug)]
@@ -119,7 +131,28 @@ pub enum CliCommand {
 			help = "Optional executor address. If specified, only this address will be able to execute the redemption."
 		)]
 		executor_address: Option<String>,
+		#[clap(
+			help = "Schedule the redemption to be requested automatically once your account is no longer an authority.",
+			long = "on-retirement",
+			conflicts_with = "recurring_floor"
+		)]
+		on_retirement: bool,
+		#[clap(
+			help = "Schedule a recurring redemption of all FLIP above this balance. Up to 6 decimal places, any more are rounded.",
+			long = "recurring-floor",
+			requires = "interval",
+			conflicts_with = "amount"
+		)]
+		recurring_floor: Option<f64>,
+		#[clap(
+			help = "The number of blocks between recurring redemptions.",
+			long = "interval",
+			requires = "recurring_floor"
+		)]
+		interval: Option<u32>,
 	},
+	#[clap(about = "Cancel any scheduled retirement or recurring redemptions")]
+	CancelScheduledRedemptions {},
 	#[clap(
 		about = "Irreversible action that restricts your account to only be able to redeem to the specified address"
 	)]
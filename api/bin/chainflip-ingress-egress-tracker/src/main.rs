@@ -9,6 +9,7 @@ use utilities::task_scope;
 mod settings;
 mod store;
 mod utils;
+mod webhooks;
 mod witnessing;
 
 async fn start(
@@ -23,6 +24,12 @@ async fn start(
 	let client = redis::Client::open(settings.redis_url.clone()).unwrap();
 	let store = RedisStore::new(client.get_multiplexed_tokio_connection().await?);
 
+	let subscribers = match &settings.webhooks_file {
+		Some(path) => webhooks::load_subscribers(path)?,
+		None => vec![],
+	};
+	webhooks::start(scope, client.get_multiplexed_tokio_connection().await?, subscribers);
+
 	let btc_network = witnessing::start(scope, settings.clone(), store.clone())
 		.await?
 		.chainflip_network
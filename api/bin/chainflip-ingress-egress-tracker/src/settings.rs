@@ -27,6 +27,8 @@ pub struct TrackerOptions {
 	btc_password: Option<String>,
 	#[clap(long = "redis_url")]
 	redis_url: Option<String>,
+	#[clap(long = "webhooks_file")]
+	webhooks_file: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -36,6 +38,8 @@ pub struct DepositTrackerSettings {
 	pub state_chain_ws_endpoint: String,
 	pub btc: HttpBasicAuthEndpoint,
 	pub redis_url: String,
+	/// Path to a JSON file listing the webhook subscribers.
+	pub webhooks_file: Option<String>,
 }
 
 impl CfSettings for DepositTrackerSettings {
@@ -99,6 +103,7 @@ impl Source for TrackerOptions {
 		insert_command_line_option(&mut map, "btc.basic_auth_user", &self.btc_username);
 		insert_command_line_option(&mut map, "btc.basic_auth_password", &self.btc_password);
 		insert_command_line_option(&mut map, "redis_url", &self.redis_url);
+		insert_command_line_option(&mut map, "webhooks_file", &self.webhooks_file);
 
 		Ok(map)
 	}
@@ -1,3 +1,4 @@
+use crate::webhooks::{Notification, NOTIFICATION_QUEUE_KEY};
 use async_trait::async_trait;
 use redis::{aio::MultiplexedConnection, AsyncCommands};
 use serde::Serialize;
@@ -9,6 +10,10 @@ pub trait Store: Sync + Send + 'static {
 
 	async fn save_to_array<S: Storable>(&mut self, storable: &S) -> anyhow::Result<Self::Output>;
 	async fn save_singleton<S: Storable>(&mut self, storable: &S) -> anyhow::Result<Self::Output>;
+	async fn push_notification(
+		&mut self,
+		notification: &Notification,
+	) -> anyhow::Result<Self::Output>;
 }
 
 #[derive(Clone)]
@@ -45,6 +50,14 @@ impl Store for RedisStore {
 
 		Ok(())
 	}
+
+	async fn push_notification(&mut self, notification: &Notification) -> anyhow::Result<()> {
+		self.con
+			.rpush(NOTIFICATION_QUEUE_KEY, serde_json::to_string(notification)?)
+			.await?;
+
+		Ok(())
+	}
 }
 
 pub trait Storable: Serialize + Sized + Sync + 'static {
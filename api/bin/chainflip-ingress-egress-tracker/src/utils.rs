@@ -1,9 +1,13 @@
+use cf_chains::Chain;
 use cf_primitives::BroadcastId;
 use chainflip_engine::state_chain_observer::client::{
 	chain_api::ChainApi, storage_api::StorageApi, STATE_CHAIN_CONNECTION,
 };
 use pallet_cf_broadcast::TransactionOutIdFor;
-use state_chain_runtime::PalletInstanceAlias;
+use pallet_cf_ingress_egress::{ChannelAction, DepositChannelDetails};
+use serde::de::DeserializeOwned;
+use sp_core::crypto::Ss58Codec;
+use state_chain_runtime::{constants::common::CHAINFLIP_SS58_PREFIX, PalletInstanceAlias};
 use tracing::log;
 
 pub fn hex_encode_bytes(bytes: &[u8]) -> String {
@@ -34,3 +38,56 @@ where
 
 	id
 }
+
+/// Returns the SS58 account id of the broker that opened the deposit channel, if the address is
+/// a swap deposit channel that is open at the given block.
+pub async fn get_channel_broker<C, StateChainClient>(
+	state_chain_client: &StateChainClient,
+	block_hash: state_chain_runtime::Hash,
+	deposit_address: &C::ChainAccount,
+) -> Option<String>
+where
+	state_chain_runtime::Runtime: pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
+	C: Chain + PalletInstanceAlias + 'static,
+	StateChainClient: StorageApi + 'static + Send + Sync,
+{
+	state_chain_client
+		.storage_map_entry::<pallet_cf_ingress_egress::DepositChannelLookup<
+			state_chain_runtime::Runtime,
+			C::Instance,
+		>>(block_hash, deposit_address)
+		.await
+		.expect(STATE_CHAIN_CONNECTION)
+		.and_then(|DepositChannelDetails { action, .. }| match action {
+			ChannelAction::Swap { broker_id, .. } =>
+				Some(broker_id.to_ss58check_with_version(CHAINFLIP_SS58_PREFIX.into())),
+			_ => None,
+		})
+}
+
+pub async fn json_rpc_request<T: DeserializeOwned>(
+	client: &reqwest::Client,
+	endpoint: &str,
+	method: &str,
+	params: serde_json::Value,
+) -> anyhow::Result<T> {
+	let mut response: serde_json::Value = client
+		.post(endpoint)
+		.json(&serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": method,
+			"params": params,
+		}))
+		.send()
+		.await?
+		.error_for_status()?
+		.json()
+		.await?;
+
+	if let Some(error) = response.get("error") {
+		anyhow::bail!("{method} failed: {error}");
+	}
+
+	Ok(serde_json::from_value(response["result"].take())?)
+}
@@ -0,0 +1,499 @@
+//! Webhook notifications for the progress of deposits, swaps and egresses.
+//!
+//! Witnessing code pushes [Notification]s onto a Redis list (see [Store::push_notification]). The
+//! dispatcher fans every notification out to the subscribers whose filter matches it, and keeps
+//! the resulting deliveries in a Redis sorted set scored by the time of their next attempt. Both
+//! the queue and the pending deliveries live in Redis, so restarting the tracker doesn't drop any
+//! notification. Delivery is at-least-once: subscribers should be prepared to receive duplicates.
+//!
+//! Every request is signed with the subscriber's secret. The `X-Chainflip-Signature` header holds
+//! the hex-encoded HMAC-SHA256 of `{timestamp}.{body}`, where `timestamp` is the value of the
+//! `X-Chainflip-Timestamp` header (seconds since the unix epoch).
+//!
+//! [Store::push_notification]: crate::store::Store::push_notification
+
+use cf_primitives::{Asset, BroadcastId, EgressId, ForeignChain, SwapId};
+use hmac::{Hmac, Mac};
+use redis::{aio::MultiplexedConnection, AsyncCommands, Direction};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+	collections::HashSet,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{error, warn};
+use utilities::{rpc::NumberOrHex, task_scope};
+
+pub const NOTIFICATION_QUEUE_KEY: &str = "webhook:notifications";
+const NOTIFICATION_PROCESSING_KEY: &str = "webhook:notifications:processing";
+const DELIVERIES_KEY: &str = "webhook:deliveries";
+const DELIVERY_ID_KEY: &str = "webhook:next_delivery_id";
+const DEAD_LETTER_KEY: &str = "webhook:dead_letter";
+
+pub const SIGNATURE_HEADER: &str = "X-Chainflip-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Chainflip-Timestamp";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const DELIVERY_BATCH_SIZE: isize = 100;
+const MAX_ATTEMPTS: u32 = 12;
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebhookEvent {
+	/// A transaction to an address has been seen in the mempool of an external chain, or has been
+	/// included in one of its latest blocks.
+	MempoolSighting {
+		asset: Asset,
+		address: String,
+		tx_hash: String,
+		amount: NumberOrHex,
+		confirmations: u32,
+	},
+	/// The tracker has witnessed a deposit, ahead of the deposit being confirmed on the State
+	/// Chain.
+	DepositPrewitnessed {
+		asset: Asset,
+		address: String,
+		amount: NumberOrHex,
+		deposit_chain_block_height: u64,
+	},
+	/// A deposit has been processed by the State Chain.
+	DepositWitnessed {
+		asset: Asset,
+		address: String,
+		amount: NumberOrHex,
+		ingress_fee: NumberOrHex,
+		state_chain_block_number: u32,
+	},
+	SwapExecuted {
+		swap_id: SwapId,
+		source_asset: Asset,
+		destination_asset: Asset,
+		swap_input: NumberOrHex,
+		swap_output: NumberOrHex,
+		state_chain_block_number: u32,
+	},
+	EgressScheduled {
+		swap_id: SwapId,
+		egress_id: EgressId,
+		asset: Asset,
+		amount: NumberOrHex,
+		fee: NumberOrHex,
+		state_chain_block_number: u32,
+	},
+	/// The broadcast carrying an egress has succeeded on the external chain.
+	BroadcastSuccess {
+		chain: ForeignChain,
+		broadcast_id: BroadcastId,
+		#[serde(skip_serializing_if = "Option::is_none", default)]
+		egress_id: Option<EgressId>,
+		tx_out_id: serde_json::Value,
+		state_chain_block_number: u32,
+	},
+}
+
+impl WebhookEvent {
+	fn assets(&self) -> Vec<Asset> {
+		match self {
+			Self::MempoolSighting { asset, .. } |
+			Self::DepositPrewitnessed { asset, .. } |
+			Self::DepositWitnessed { asset, .. } |
+			Self::EgressScheduled { asset, .. } => vec![*asset],
+			Self::SwapExecuted { source_asset, destination_asset, .. } =>
+				vec![*source_asset, *destination_asset],
+			Self::BroadcastSuccess { .. } => vec![],
+		}
+	}
+}
+
+/// The payload of a webhook request.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+	/// The SS58 account id of the broker that opened the deposit channel, if known.
+	pub broker: Option<String>,
+	/// The deposit and destination addresses involved.
+	pub addresses: Vec<String>,
+	#[serde(flatten)]
+	pub event: WebhookEvent,
+}
+
+impl Notification {
+	pub fn new(event: WebhookEvent, broker: Option<String>, addresses: Vec<String>) -> Self {
+		Self { broker, addresses, event }
+	}
+}
+
+/// A webhook subscriber, as configured in the webhooks file. Each non-empty filter list restricts
+/// the notifications the subscriber receives to those matching one of its entries. Notifications
+/// that can't be attributed to a broker are not sent to subscribers filtering by broker.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Subscriber {
+	/// Identifies the subscriber in pending deliveries, so its url can be changed without losing
+	/// them.
+	pub name: String,
+	pub url: String,
+	pub secret: String,
+	#[serde(default)]
+	pub brokers: Vec<String>,
+	#[serde(default)]
+	pub assets: Vec<Asset>,
+	#[serde(default)]
+	pub addresses: Vec<String>,
+}
+
+impl Subscriber {
+	pub fn matches(&self, notification: &Notification) -> bool {
+		(self.brokers.is_empty() ||
+			notification.broker.as_ref().is_some_and(|broker| self.brokers.contains(broker))) &&
+			(self.assets.is_empty() ||
+				notification.event.assets().iter().any(|asset| self.assets.contains(asset))) &&
+			(self.addresses.is_empty() ||
+				notification.addresses.iter().any(|address| {
+					self.addresses.iter().any(|filter| filter.eq_ignore_ascii_case(address))
+				}))
+	}
+}
+
+/// Reads the list of subscribers from a JSON file.
+pub fn load_subscribers(path: &str) -> anyhow::Result<Vec<Subscriber>> {
+	parse_subscribers(&std::fs::read_to_string(path)?)
+}
+
+/// Parses the list of subscribers, rejecting duplicate names or urls.
+fn parse_subscribers(json: &str) -> anyhow::Result<Vec<Subscriber>> {
+	let subscribers: Vec<Subscriber> = serde_json::from_str(json)?;
+
+	let mut names = HashSet::new();
+	let mut urls = HashSet::new();
+	for subscriber in &subscribers {
+		anyhow::ensure!(
+			names.insert(&subscriber.name),
+			"Duplicate webhook subscriber name {}",
+			subscriber.name
+		);
+		anyhow::ensure!(
+			urls.insert(&subscriber.url),
+			"Duplicate webhook subscriber url {}",
+			subscriber.url
+		);
+	}
+
+	Ok(subscribers)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct Delivery {
+	// Makes otherwise identical deliveries distinct members of the sorted set.
+	id: u64,
+	// The name of the subscriber.
+	subscriber: String,
+	attempts: u32,
+	notification: Notification,
+}
+
+/// Returns the hex-encoded HMAC-SHA256 signature of a request body.
+pub fn sign(secret: &str, timestamp: u64, body: &str) -> String {
+	let mut mac =
+		Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take a key of any size");
+	mac.update(format!("{timestamp}.{body}").as_bytes());
+	hex::encode(mac.finalize().into_bytes())
+}
+
+fn retry_delay(attempts: u32) -> Duration {
+	INITIAL_RETRY_DELAY
+		.saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+		.min(MAX_RETRY_DELAY)
+}
+
+fn unix_timestamp() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("system time is after the unix epoch")
+		.as_secs()
+}
+
+struct Dispatcher {
+	con: MultiplexedConnection,
+	subscribers: Vec<Subscriber>,
+	http_client: reqwest::Client,
+}
+
+impl Dispatcher {
+	/// Puts back notifications that were being fanned out when the tracker stopped.
+	async fn requeue_unprocessed(&mut self) -> anyhow::Result<()> {
+		loop {
+			let requeued: Option<String> = self
+				.con
+				.lmove(
+					NOTIFICATION_PROCESSING_KEY,
+					NOTIFICATION_QUEUE_KEY,
+					Direction::Right,
+					Direction::Left,
+				)
+				.await?;
+			if requeued.is_none() {
+				return Ok(())
+			}
+		}
+	}
+
+	async fn fan_out(&mut self) -> anyhow::Result<()> {
+		loop {
+			let raw: Option<String> = self
+				.con
+				.lmove(
+					NOTIFICATION_QUEUE_KEY,
+					NOTIFICATION_PROCESSING_KEY,
+					Direction::Left,
+					Direction::Right,
+				)
+				.await?;
+			let Some(raw) = raw else { return Ok(()) };
+
+			match serde_json::from_str::<Notification>(&raw) {
+				Ok(notification) =>
+					for subscriber in self.subscribers.iter().filter(|s| s.matches(&notification)) {
+						let id: u64 = self.con.incr(DELIVERY_ID_KEY, 1).await?;
+						let delivery = Delivery {
+							id,
+							subscriber: subscriber.name.clone(),
+							attempts: 0,
+							notification: notification.clone(),
+						};
+						let _: () = self
+							.con
+							.zadd(
+								DELIVERIES_KEY,
+								serde_json::to_string(&delivery)?,
+								unix_timestamp(),
+							)
+							.await?;
+					},
+				Err(err) => error!("Dropping malformed notification {raw}: {err}"),
+			}
+			let _: () = self.con.lrem(NOTIFICATION_PROCESSING_KEY, 1, raw).await?;
+		}
+	}
+
+	async fn deliver_due(&mut self) -> anyhow::Result<()> {
+		let due: Vec<String> = self
+			.con
+			.zrangebyscore_limit(DELIVERIES_KEY, "-inf", unix_timestamp(), 0, DELIVERY_BATCH_SIZE)
+			.await?;
+
+		for raw in due {
+			match serde_json::from_str::<Delivery>(&raw) {
+				Ok(delivery) => match self
+					.subscribers
+					.iter()
+					.find(|subscriber| subscriber.name == delivery.subscriber)
+				{
+					None => warn!(
+						"Dropping webhook delivery to {}, which is no longer a subscriber",
+						delivery.subscriber
+					),
+					Some(subscriber) =>
+						if let Err(err) = self.send(subscriber, &delivery).await {
+							let delivery = Delivery { attempts: delivery.attempts + 1, ..delivery };
+							if delivery.attempts >= MAX_ATTEMPTS {
+								warn!(
+									"Giving up on webhook delivery to {} after {} attempts: {err}",
+									delivery.subscriber, delivery.attempts
+								);
+								let _: () = self
+									.con
+									.rpush(DEAD_LETTER_KEY, serde_json::to_string(&delivery)?)
+									.await?;
+							} else {
+								warn!(
+									"Webhook delivery to {} failed, retrying in {:?}: {err}",
+									delivery.subscriber,
+									retry_delay(delivery.attempts)
+								);
+								let _: () = self
+									.con
+									.zadd(
+										DELIVERIES_KEY,
+										serde_json::to_string(&delivery)?,
+										unix_timestamp() + retry_delay(delivery.attempts).as_secs(),
+									)
+									.await?;
+							}
+						},
+				},
+				Err(err) => error!("Dropping malformed webhook delivery {raw}: {err}"),
+			}
+			let _: () = self.con.zrem(DELIVERIES_KEY, raw).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn send(&self, subscriber: &Subscriber, delivery: &Delivery) -> anyhow::Result<()> {
+		let body = serde_json::to_string(&delivery.notification)?;
+		let timestamp = unix_timestamp();
+
+		self.http_client
+			.post(&subscriber.url)
+			.header(reqwest::header::CONTENT_TYPE, "application/json")
+			.header(TIMESTAMP_HEADER, timestamp.to_string())
+			.header(SIGNATURE_HEADER, sign(&subscriber.secret, timestamp, &body))
+			.body(body)
+			.send()
+			.await?
+			.error_for_status()?;
+
+		Ok(())
+	}
+}
+
+pub fn start(
+	scope: &task_scope::Scope<'_, anyhow::Error>,
+	con: MultiplexedConnection,
+	subscribers: Vec<Subscriber>,
+) {
+	scope.spawn(async move {
+		let mut dispatcher = Dispatcher {
+			con,
+			subscribers,
+			http_client: reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build()?,
+		};
+
+		dispatcher.requeue_unprocessed().await?;
+
+		let mut interval = tokio::time::interval(POLL_INTERVAL);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+		loop {
+			interval.tick().await;
+			if let Err(err) = dispatcher.fan_out().await {
+				error!("Error when queueing webhook deliveries: {err}");
+			}
+			if let Err(err) = dispatcher.deliver_due().await {
+				error!("Error when delivering webhooks: {err}");
+			}
+		}
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cf_primitives::chains::assets::any;
+
+	fn subscriber() -> Subscriber {
+		Subscriber {
+			name: "backend".to_string(),
+			url: "http://localhost:8080".to_string(),
+			secret: "secret".to_string(),
+			brokers: vec![],
+			assets: vec![],
+			addresses: vec![],
+		}
+	}
+
+	fn swap_executed(broker: Option<&str>) -> Notification {
+		Notification::new(
+			WebhookEvent::SwapExecuted {
+				swap_id: 1,
+				source_asset: any::Asset::Eth,
+				destination_asset: any::Asset::Btc,
+				swap_input: 100u128.into(),
+				swap_output: 10u128.into(),
+				state_chain_block_number: 1,
+			},
+			broker.map(ToString::to_string),
+			vec!["0x541f563237a309b3a61e33bdf07a8930bdba8d99".to_string()],
+		)
+	}
+
+	#[test]
+	fn subscriber_filters() {
+		let notification = swap_executed(Some("cFbroker"));
+
+		assert!(subscriber().matches(&notification));
+
+		assert!(Subscriber { brokers: vec!["cFbroker".to_string()], ..subscriber() }
+			.matches(&notification));
+		assert!(!Subscriber { brokers: vec!["cFother".to_string()], ..subscriber() }
+			.matches(&notification));
+		assert!(!Subscriber { brokers: vec!["cFbroker".to_string()], ..subscriber() }
+			.matches(&swap_executed(None)));
+
+		assert!(Subscriber { assets: vec![any::Asset::Btc], ..subscriber() }.matches(&notification));
+		assert!(
+			!Subscriber { assets: vec![any::Asset::Dot], ..subscriber() }.matches(&notification)
+		);
+
+		assert!(Subscriber {
+			addresses: vec!["0x541F563237A309B3A61E33BDf07a8930Bdba8D99".to_string()],
+			..subscriber()
+		}
+		.matches(&notification));
+		assert!(!Subscriber {
+			addresses: vec!["0xa56A6be23b6Cf39D9448FF6e897C29c41c8fbDFF".to_string()],
+			brokers: vec!["cFbroker".to_string()],
+			..subscriber()
+		}
+		.matches(&notification));
+	}
+
+	#[test]
+	fn subscribers_must_have_unique_names_and_urls() {
+		let parse = |subscribers: &[(&str, &str)]| {
+			parse_subscribers(
+				&serde_json::to_string(
+					&subscribers
+						.iter()
+						.map(
+							|(name, url)| serde_json::json!({ "name": name, "url": url, "secret": "secret" }),
+						)
+						.collect::<Vec<_>>(),
+				)
+				.unwrap(),
+			)
+		};
+
+		assert_eq!(parse(&[("backend", "http://localhost:8080")]).unwrap(), vec![subscriber()]);
+		assert!(parse(&[("a", "http://localhost:8080"), ("b", "http://localhost:8081")]).is_ok());
+		assert!(parse(&[("a", "http://localhost:8080"), ("a", "http://localhost:8081")]).is_err());
+		assert!(parse(&[("a", "http://localhost:8080"), ("b", "http://localhost:8080")]).is_err());
+	}
+
+	#[test]
+	fn notification_serialization() {
+		let notification = swap_executed(Some("cFbroker"));
+		let json = serde_json::to_value(&notification).unwrap();
+
+		assert_eq!(json["type"], "swap_executed");
+		assert_eq!(json["broker"], "cFbroker");
+		assert_eq!(json["swap_id"], 1);
+		assert_eq!(
+			serde_json::from_value::<Notification>(json).unwrap().event.assets(),
+			vec![any::Asset::Eth, any::Asset::Btc]
+		);
+	}
+
+	#[test]
+	fn signature_is_hmac_sha256_of_timestamp_and_body() {
+		// echo -n '1700000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+		assert_eq!(
+			sign("secret", 1_700_000_000, r#"{"a":1}"#),
+			"49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+		);
+		assert_ne!(
+			sign("secret", 1_700_000_001, r#"{"a":1}"#),
+			sign("secret", 1_700_000_000, r#"{"a":1}"#)
+		);
+	}
+
+	#[test]
+	fn retry_delay_backs_off_exponentially() {
+		assert_eq!(retry_delay(1), INITIAL_RETRY_DELAY);
+		assert_eq!(retry_delay(2), INITIAL_RETRY_DELAY * 2);
+		assert_eq!(retry_delay(3), INITIAL_RETRY_DELAY * 4);
+		assert_eq!(retry_delay(MAX_ATTEMPTS), MAX_RETRY_DELAY);
+	}
+}
@@ -1,8 +1,12 @@
 mod btc;
 pub mod btc_mempool;
 mod dot;
+mod dot_mempool;
 mod eth;
+mod eth_mempool;
+mod mempool;
 pub mod state_chain;
+mod state_chain_events;
 
 use self::state_chain::handle_call;
 use crate::{settings::DepositTrackerSettings, store::RedisStore};
@@ -122,6 +126,27 @@ pub(super) async fn start(
 	let epoch_source =
 		EpochSource::builder(scope, state_chain_stream.clone(), state_chain_client.clone()).await;
 
+	state_chain_events::start(scope, state_chain_client.clone(), store.clone(), chainflip_network);
+
+	mempool::start::<cf_chains::Ethereum, _, _, _>(
+		scope,
+		eth_mempool::EthMempoolClient::new(
+			settings.eth.http_endpoint.clone(),
+			env_params.supported_erc20_tokens.clone(),
+		),
+		store.clone(),
+		state_chain_client.clone(),
+		chainflip_network,
+	);
+
+	mempool::start::<cf_chains::Polkadot, _, _, _>(
+		scope,
+		dot_mempool::DotMempoolClient::new(settings.dot.http_endpoint.clone()),
+		store.clone(),
+		state_chain_client.clone(),
+		chainflip_network,
+	);
+
 	let witness_call = {
 		let state_chain_client = state_chain_client.clone();
 		move |call: state_chain_runtime::RuntimeCall, _epoch_index| {
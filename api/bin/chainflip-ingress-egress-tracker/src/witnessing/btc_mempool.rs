@@ -1,7 +1,10 @@
-use crate::{Storable, Store};
+use crate::{
+	webhooks::{Notification, WebhookEvent},
+	Storable, Store,
+};
 use bitcoin::{BlockHash, Network, ScriptBuf, Transaction, Txid};
 use cf_chains::btc::BitcoinNetwork;
-use cf_primitives::ForeignChain;
+use cf_primitives::{chains::assets::any, ForeignChain};
 use chainflip_engine::{
 	btc::rpc::{BtcRpcApi, BtcRpcClient},
 	settings::HttpBasicAuthEndpoint,
@@ -46,6 +49,22 @@ impl Storable for QueryResult {
 	}
 }
 
+impl QueryResult {
+	fn to_notification(&self) -> Notification {
+		Notification::new(
+			WebhookEvent::MempoolSighting {
+				asset: any::Asset::Btc,
+				address: self.destination.clone(),
+				tx_hash: self.tx_hash.to_string(),
+				amount: self.value.into(),
+				confirmations: self.confirmations,
+			},
+			None,
+			vec![self.destination.clone()],
+		)
+	}
+}
+
 fn script_to_address(script: &ScriptBuf, btc_network: Network) -> Option<String> {
 	bitcoin::Address::from_script(script, btc_network)
 		.map(|addr| addr.to_string())
@@ -72,6 +91,23 @@ impl Default for Cache {
 	}
 }
 
+/// Returns the transactions that weren't in the previous cache, or whose number of confirmations
+/// has changed since.
+fn new_sightings<'a>(
+	previous_cache: &'a Cache,
+	cache: &'a Cache,
+) -> impl Iterator<Item = &'a QueryResult> + 'a {
+	cache.transactions.iter().filter_map(|(address, query_result)| {
+		match previous_cache.transactions.get(address) {
+			Some(previous)
+				if previous.tx_hash == query_result.tx_hash &&
+					previous.confirmations == query_result.confirmations =>
+				None,
+			_ => Some(query_result),
+		}
+	})
+}
+
 const SAFETY_MARGIN: u32 = 10;
 const REFRESH_INTERVAL: u64 = 10;
 
@@ -201,6 +237,10 @@ pub fn start<S: Store>(
 				interval.tick().await;
 				match update_cache(&client, cache.clone(), btc_network).await {
 					Ok(updated_cache) => {
+						for query_result in new_sightings(&cache, &updated_cache) {
+							store.push_notification(&query_result.to_notification()).await?;
+						}
+
 						cache = updated_cache;
 
 						for query_result in cache.transactions.values() {
@@ -498,6 +538,44 @@ mod tests {
 		assert_eq!(cache.transactions.get(&address1).unwrap().confirmations, 2);
 	}
 
+	#[tokio::test]
+	async fn only_new_sightings_are_notified() {
+		let address1 = "3KhCRZchNv46uHwBXUZo4ALCUCjGT1v7fd".to_string();
+		let address2 = "1F1tAaz5x1HUXrCNLbtMDqcw6o5GNn4xqX".to_string();
+
+		let a1_script = address::Address::from_str(&address1).unwrap().payload.script_pubkey();
+		let a2_script = address::Address::from_str(&address2).unwrap().payload.script_pubkey();
+
+		let mut btc = MockBtcRpc {
+			mempool: vec![tx_with_outs(vec![TxOut {
+				value: Amount::from_btc(0.8).unwrap().to_sat(),
+				script_pubkey: a1_script,
+			}])],
+			latest_block_hash: i_to_block_hash(15),
+			blocks: init_blocks(),
+		};
+		let empty_cache: Cache = Default::default();
+		let cache = update_cache(&btc, empty_cache.clone(), Network::Bitcoin).await.unwrap();
+		assert_eq!(
+			new_sightings(&empty_cache, &cache)
+				.map(|q| q.destination.clone())
+				.collect::<Vec<_>>(),
+			vec![address1.clone()]
+		);
+
+		btc.mempool.push(tx_with_outs(vec![TxOut {
+			value: Amount::from_btc(1.2).unwrap().to_sat(),
+			script_pubkey: a2_script,
+		}]));
+		let updated_cache = update_cache(&btc, cache.clone(), Network::Bitcoin).await.unwrap();
+		assert_eq!(
+			new_sightings(&cache, &updated_cache)
+				.map(|q| q.destination.clone())
+				.collect::<Vec<_>>(),
+			vec![address2]
+		);
+	}
+
 	#[tokio::test]
 	async fn report_oldest_tx_only() {
 		let address1 = "bc1qrtwkf6jdda74ngjv6zgmxvx4jkckxkl2dafpm3".to_string();
@@ -0,0 +1,188 @@
+use super::mempool::{Block, MempoolRpc, Transfer};
+use crate::utils::{hex_encode_bytes, json_rpc_request};
+use async_trait::async_trait;
+use cf_primitives::chains::assets::any;
+use codec::{Compact, Decode};
+use serde::Deserialize;
+use serde_json::json;
+use sp_core::{blake2_256, Bytes, H256, U256};
+use utilities::redact_endpoint_secret::SecretUrl;
+
+const BALANCES_PALLET_INDEX: u8 = 5;
+const TRANSFER_ALLOW_DEATH_CALL_INDEX: u8 = 0;
+const TRANSFER_KEEP_ALIVE_CALL_INDEX: u8 = 3;
+/// `MultiAddress::Id`
+const ACCOUNT_ID_ADDRESS_INDEX: u8 = 0;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Header {
+	parent_hash: H256,
+	number: U256,
+}
+
+#[derive(Deserialize)]
+struct SignedBlock {
+	block: DotBlock,
+}
+
+#[derive(Deserialize)]
+struct DotBlock {
+	header: Header,
+	extrinsics: Vec<Bytes>,
+}
+
+/// Finds the balance transfers in an encoded extrinsic. The extrinsic is scanned for encoded
+/// `Balances::transfer_allow_death` and `Balances::transfer_keep_alive` calls rather than fully
+/// decoded so that transfers nested in batches or proxy calls are picked up as well. Any false
+/// positive is discarded later on unless it happens to match an open deposit channel.
+fn transfers_in_extrinsic(extrinsic: &[u8]) -> Vec<Transfer> {
+	const PREFIX_LEN: usize = 3;
+	const ACCOUNT_ID_LEN: usize = 32;
+
+	let tx_hash = hex_encode_bytes(&blake2_256(extrinsic));
+
+	(0..extrinsic.len().saturating_sub(PREFIX_LEN + ACCOUNT_ID_LEN))
+		.filter_map(|i| {
+			let (prefix, rest) = extrinsic[i..].split_at(PREFIX_LEN);
+			if prefix[0] != BALANCES_PALLET_INDEX ||
+				![TRANSFER_ALLOW_DEATH_CALL_INDEX, TRANSFER_KEEP_ALIVE_CALL_INDEX]
+					.contains(&prefix[1]) ||
+				prefix[2] != ACCOUNT_ID_ADDRESS_INDEX
+			{
+				return None
+			}
+			let (to, mut value) = rest.split_at(ACCOUNT_ID_LEN);
+			let Compact(amount) = Compact::<u128>::decode(&mut value).ok()?;
+
+			Some(Transfer {
+				to: hex_encode_bytes(to),
+				asset: any::Asset::Dot,
+				amount,
+				tx_hash: tx_hash.clone(),
+			})
+		})
+		.collect()
+}
+
+pub struct DotMempoolClient {
+	client: reqwest::Client,
+	endpoint: SecretUrl,
+}
+
+impl DotMempoolClient {
+	pub fn new(endpoint: SecretUrl) -> Self {
+		Self { client: reqwest::Client::new(), endpoint }
+	}
+
+	async fn request<T: serde::de::DeserializeOwned>(
+		&self,
+		method: &str,
+		params: serde_json::Value,
+	) -> anyhow::Result<T> {
+		json_rpc_request(&self.client, self.endpoint.as_ref(), method, params).await
+	}
+}
+
+#[async_trait]
+impl MempoolRpc for DotMempoolClient {
+	// Polkadot has deterministic finality, so only a few blocks are worth reporting.
+	const SAFETY_MARGIN: usize = 5;
+
+	async fn pending_transfers(&self) -> anyhow::Result<Vec<Transfer>> {
+		let extrinsics: Vec<Bytes> = self.request("author_pendingExtrinsics", json!([])).await?;
+
+		Ok(extrinsics
+			.iter()
+			.flat_map(|extrinsic| transfers_in_extrinsic(extrinsic))
+			.collect())
+	}
+
+	async fn best_block_number(&self) -> anyhow::Result<u64> {
+		let header: Header = self.request("chain_getHeader", json!([])).await?;
+
+		Ok(header.number.as_u64())
+	}
+
+	async fn block(&self, block_number: u64) -> anyhow::Result<Block> {
+		let hash: H256 = self.request("chain_getBlockHash", json!([block_number])).await?;
+		let SignedBlock { block } = self.request("chain_getBlock", json!([hash])).await?;
+
+		Ok(Block {
+			number: block.header.number.as_u64(),
+			hash: hex_encode_bytes(hash.as_bytes()),
+			parent_hash: hex_encode_bytes(block.header.parent_hash.as_bytes()),
+			transfers: block
+				.extrinsics
+				.iter()
+				.flat_map(|extrinsic| transfers_in_extrinsic(extrinsic))
+				.collect(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+
+	const DESTINATION: [u8; 32] = [0xaa; 32];
+
+	fn transfer_call(call_index: u8, amount: u128) -> Vec<u8> {
+		let mut call = vec![BALANCES_PALLET_INDEX, call_index, ACCOUNT_ID_ADDRESS_INDEX];
+		call.extend(DESTINATION);
+		call.extend(Compact(amount).encode());
+		call
+	}
+
+	/// Wraps a call in something resembling a signed extrinsic.
+	fn extrinsic(calls: &[Vec<u8>]) -> Vec<u8> {
+		let mut body = vec![0x84, 0x00];
+		body.extend([0x11; 32]);
+		body.extend([0x01; 65]);
+		body.extend([0x00, 0x04, 0x00]);
+		calls.iter().for_each(|call| body.extend(call));
+		let mut extrinsic = Compact(body.len() as u32).encode();
+		extrinsic.extend(body);
+		extrinsic
+	}
+
+	#[test]
+	fn transfers_are_found_in_extrinsics() {
+		let bytes = extrinsic(&[transfer_call(TRANSFER_ALLOW_DEATH_CALL_INDEX, 10_000_000_000)]);
+		let tx_hash = hex_encode_bytes(&blake2_256(&bytes));
+
+		assert_eq!(
+			transfers_in_extrinsic(&bytes),
+			vec![Transfer {
+				to: hex_encode_bytes(&DESTINATION),
+				asset: any::Asset::Dot,
+				amount: 10_000_000_000,
+				tx_hash,
+			}]
+		);
+	}
+
+	#[test]
+	fn batched_transfers_are_found() {
+		let bytes = extrinsic(&[
+			transfer_call(TRANSFER_ALLOW_DEATH_CALL_INDEX, 1),
+			transfer_call(TRANSFER_KEEP_ALIVE_CALL_INDEX, u128::MAX),
+		]);
+
+		assert_eq!(
+			transfers_in_extrinsic(&bytes).into_iter().map(|t| t.amount).collect::<Vec<_>>(),
+			vec![1, u128::MAX]
+		);
+	}
+
+	#[test]
+	fn other_calls_are_ignored() {
+		// `Balances::transfer_all`
+		let mut call = vec![BALANCES_PALLET_INDEX, 4, ACCOUNT_ID_ADDRESS_INDEX];
+		call.extend(DESTINATION);
+		call.push(0);
+
+		assert!(transfers_in_extrinsic(&extrinsic(&[call])).is_empty());
+	}
+}
@@ -0,0 +1,186 @@
+use super::mempool::{Block, MempoolRpc, Transfer};
+use crate::utils::{hex_encode_bytes, json_rpc_request};
+use async_trait::async_trait;
+use cf_primitives::{chains::assets::any, Asset};
+use serde::Deserialize;
+use serde_json::json;
+use sp_core::{Bytes, H160, H256, U256};
+use std::collections::HashMap;
+use utilities::redact_endpoint_secret::SecretUrl;
+
+/// `transfer(address,uint256)`
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EthBlock {
+	number: U256,
+	hash: H256,
+	parent_hash: H256,
+	transactions: Vec<EthTransaction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingEthBlock {
+	transactions: Vec<EthTransaction>,
+}
+
+#[derive(Deserialize)]
+struct EthTransaction {
+	hash: H256,
+	to: Option<H160>,
+	value: U256,
+	input: Bytes,
+}
+
+impl EthTransaction {
+	/// Native ETH sent to an address, or an ERC-20 `transfer` of one of the supported tokens.
+	fn transfer(&self, erc20_tokens: &HashMap<H160, Asset>) -> Option<Transfer> {
+		let to = self.to?;
+		let tx_hash = hex_encode_bytes(self.hash.as_bytes());
+
+		match erc20_tokens.get(&to) {
+			Some(asset) => (self.input.len() == 68 && self.input[..4] == ERC20_TRANSFER_SELECTOR)
+				.then(|| {
+					Some(Transfer {
+						to: hex_encode_bytes(&self.input[16..36]),
+						asset: *asset,
+						amount: U256::from_big_endian(&self.input[36..68]).try_into().ok()?,
+						tx_hash,
+					})
+				})
+				.flatten(),
+			None => (!self.value.is_zero())
+				.then(|| {
+					Some(Transfer {
+						to: hex_encode_bytes(to.as_bytes()),
+						asset: any::Asset::Eth,
+						amount: self.value.try_into().ok()?,
+						tx_hash,
+					})
+				})
+				.flatten(),
+		}
+	}
+}
+
+pub struct EthMempoolClient {
+	client: reqwest::Client,
+	endpoint: SecretUrl,
+	erc20_tokens: HashMap<H160, Asset>,
+}
+
+impl EthMempoolClient {
+	pub fn new(endpoint: SecretUrl, erc20_tokens: HashMap<H160, Asset>) -> Self {
+		Self { client: reqwest::Client::new(), endpoint, erc20_tokens }
+	}
+
+	fn transfers(&self, transactions: &[EthTransaction]) -> Vec<Transfer> {
+		transactions.iter().filter_map(|tx| tx.transfer(&self.erc20_tokens)).collect()
+	}
+}
+
+#[async_trait]
+impl MempoolRpc for EthMempoolClient {
+	// In line with the safety margin used when witnessing Ethereum deposits.
+	const SAFETY_MARGIN: usize = 7;
+
+	async fn pending_transfers(&self) -> anyhow::Result<Vec<Transfer>> {
+		let block: PendingEthBlock = json_rpc_request(
+			&self.client,
+			self.endpoint.as_ref(),
+			"eth_getBlockByNumber",
+			json!(["pending", true]),
+		)
+		.await?;
+
+		Ok(self.transfers(&block.transactions))
+	}
+
+	async fn best_block_number(&self) -> anyhow::Result<u64> {
+		let number: U256 =
+			json_rpc_request(&self.client, self.endpoint.as_ref(), "eth_blockNumber", json!([]))
+				.await?;
+
+		Ok(number.as_u64())
+	}
+
+	async fn block(&self, block_number: u64) -> anyhow::Result<Block> {
+		let block: EthBlock = json_rpc_request(
+			&self.client,
+			self.endpoint.as_ref(),
+			"eth_getBlockByNumber",
+			json!([format!("0x{block_number:x}"), true]),
+		)
+		.await?;
+
+		Ok(Block {
+			number: block.number.as_u64(),
+			hash: hex_encode_bytes(block.hash.as_bytes()),
+			parent_hash: hex_encode_bytes(block.parent_hash.as_bytes()),
+			transfers: self.transfers(&block.transactions),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const DEPOSIT_ADDRESS: &str = "0x541f563237a309b3a61e33bdf07a8930bdba8d99";
+	const USDC_ADDRESS: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+	fn tx(to: &str, value: u64, input: &str) -> EthTransaction {
+		serde_json::from_value(json!({
+			"hash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+			"to": to,
+			"value": format!("0x{value:x}"),
+			"input": input,
+		}))
+		.unwrap()
+	}
+
+	fn erc20_tokens() -> HashMap<H160, Asset> {
+		HashMap::from([(USDC_ADDRESS.parse().unwrap(), any::Asset::Usdc)])
+	}
+
+	#[test]
+	fn native_eth_transfer() {
+		assert_eq!(
+			tx(DEPOSIT_ADDRESS, 1_000, "0x").transfer(&erc20_tokens()),
+			Some(Transfer {
+				to: DEPOSIT_ADDRESS.to_string(),
+				asset: any::Asset::Eth,
+				amount: 1_000,
+				tx_hash: format!("0x{}", "01".repeat(32)),
+			})
+		);
+		assert_eq!(tx(DEPOSIT_ADDRESS, 0, "0x").transfer(&erc20_tokens()), None);
+	}
+
+	#[test]
+	fn erc20_transfer() {
+		let input = format!(
+			"0xa9059cbb000000000000000000000000{}{:064x}",
+			&DEPOSIT_ADDRESS[2..],
+			2_000_000u128
+		);
+
+		assert_eq!(
+			tx(USDC_ADDRESS, 0, &input).transfer(&erc20_tokens()),
+			Some(Transfer {
+				to: DEPOSIT_ADDRESS.to_string(),
+				asset: any::Asset::Usdc,
+				amount: 2_000_000,
+				tx_hash: format!("0x{}", "01".repeat(32)),
+			})
+		);
+		// `approve(address,uint256)` has the same layout but isn't a transfer.
+		assert_eq!(
+			tx(USDC_ADDRESS, 0, &input.replace("0xa9059cbb", "0x095ea7b3"))
+				.transfer(&erc20_tokens()),
+			None
+		);
+	}
+}
@@ -0,0 +1,376 @@
+//! Pending deposit tracking for chains where the deposit channel addresses must be known up front
+//! to pick out the transfers of interest (Ethereum and Polkadot). The results are stored with the
+//! same schema as the Bitcoin mempool results.
+
+use crate::{
+	store::{Storable, Store},
+	webhooks::{Notification, WebhookEvent},
+};
+use async_trait::async_trait;
+use cf_chains::{address::to_encoded_address, Chain};
+use cf_primitives::{Asset, ForeignChain, NetworkEnvironment};
+use chainflip_engine::state_chain_observer::client::{
+	chain_api::ChainApi, storage_api::StorageApi, STATE_CHAIN_CONNECTION,
+};
+use pallet_cf_ingress_egress::{ChannelAction, DepositChannelDetails};
+use serde::Serialize;
+use state_chain_runtime::PalletInstanceAlias;
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Arc,
+	time::Duration,
+};
+use tracing::error;
+use utilities::task_scope;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(6);
+
+/// A transfer of an asset to an address, found in a pending transaction or in a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transfer {
+	pub to: String,
+	pub asset: Asset,
+	pub amount: u128,
+	pub tx_hash: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+	pub number: u64,
+	pub hash: String,
+	pub parent_hash: String,
+	pub transfers: Vec<Transfer>,
+}
+
+#[async_trait]
+pub trait MempoolRpc: Send + Sync + 'static {
+	/// The number of blocks for which we keep reporting the confirmations of a transfer.
+	const SAFETY_MARGIN: usize;
+
+	async fn pending_transfers(&self) -> anyhow::Result<Vec<Transfer>>;
+	async fn best_block_number(&self) -> anyhow::Result<u64>;
+	async fn block(&self, block_number: u64) -> anyhow::Result<Block>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingDeposit {
+	asset: Asset,
+	confirmations: u32,
+	destination: String,
+	value: u128,
+	tx_hash: String,
+}
+
+impl Serialize for PendingDeposit {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		use serde::ser::SerializeMap;
+
+		let mut map = serializer.serialize_map(Some(3))?;
+		map.serialize_entry("confirmations", &self.confirmations)?;
+		map.serialize_entry("tx_hash", &self.tx_hash)?;
+		map.serialize_entry("value", &format!("0x{:x}", self.value))?;
+		map.end()
+	}
+}
+
+impl Storable for PendingDeposit {
+	fn get_key(&self) -> String {
+		let chain = ForeignChain::from(self.asset).to_string();
+		format!("mempool:{chain}:{}", self.destination)
+	}
+
+	fn get_expiry_duration(&self) -> Duration {
+		Duration::from_secs(60)
+	}
+}
+
+impl PendingDeposit {
+	fn to_notification(&self) -> Notification {
+		Notification::new(
+			WebhookEvent::MempoolSighting {
+				asset: self.asset,
+				address: self.destination.clone(),
+				tx_hash: self.tx_hash.clone(),
+				amount: self.value.into(),
+				confirmations: self.confirmations,
+			},
+			None,
+			vec![self.destination.clone()],
+		)
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+struct Cache {
+	blocks: BTreeMap<u64, Block>,
+	deposits: HashMap<String, PendingDeposit>,
+}
+
+async fn update_cache<Rpc: MempoolRpc>(
+	rpc: &Rpc,
+	previous_cache: &Cache,
+	open_channels: &HashMap<String, Asset>,
+) -> anyhow::Result<Cache> {
+	// Walk back from the best block, reusing the blocks we already have unless they've been
+	// reorged out.
+	let mut blocks = BTreeMap::new();
+	let mut block = rpc.block(rpc.best_block_number().await?).await?;
+	loop {
+		let parent = block
+			.number
+			.checked_sub(1)
+			.map(|parent_number| (parent_number, block.parent_hash.clone()));
+		blocks.insert(block.number, block);
+		match parent {
+			Some((parent_number, parent_hash)) if blocks.len() < Rpc::SAFETY_MARGIN =>
+				block = match previous_cache.blocks.get(&parent_number) {
+					Some(cached_block) if cached_block.hash == parent_hash => cached_block.clone(),
+					_ => rpc.block(parent_number).await?,
+				},
+			_ => break,
+		}
+	}
+
+	let pending_transfers = rpc.pending_transfers().await?;
+
+	// Transfers in older blocks take precedence, so we report the oldest transfer to an address.
+	let mut deposits = HashMap::new();
+	for (confirmations, transfer) in pending_transfers.iter().map(|transfer| (0, transfer)).chain(
+		blocks.values().rev().enumerate().flat_map(|(depth, block)| {
+			block.transfers.iter().map(move |transfer| (depth as u32 + 1, transfer))
+		}),
+	) {
+		if open_channels.get(&transfer.to) == Some(&transfer.asset) {
+			deposits.insert(
+				transfer.to.clone(),
+				PendingDeposit {
+					asset: transfer.asset,
+					confirmations,
+					destination: transfer.to.clone(),
+					value: transfer.amount,
+					tx_hash: transfer.tx_hash.clone(),
+				},
+			);
+		}
+	}
+
+	Ok(Cache { blocks, deposits })
+}
+
+/// Returns the deposits that weren't in the previous cache, or whose number of confirmations has
+/// changed since.
+fn new_sightings<'a>(
+	previous_cache: &'a Cache,
+	cache: &'a Cache,
+) -> impl Iterator<Item = &'a PendingDeposit> + 'a {
+	cache.deposits.iter().filter_map(|(address, deposit)| {
+		match previous_cache.deposits.get(address) {
+			Some(previous) if previous == deposit => None,
+			_ => Some(deposit),
+		}
+	})
+}
+
+/// The addresses and assets of the open swap deposit channels, like `get_open_swap_channels` of
+/// the chainflip api.
+async fn open_swap_channels<C, StateChainClient>(
+	state_chain_client: &StateChainClient,
+	chainflip_network: NetworkEnvironment,
+) -> HashMap<String, Asset>
+where
+	C: Chain + PalletInstanceAlias + 'static,
+	state_chain_runtime::Runtime: pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+{
+	state_chain_client
+		.storage_map::<pallet_cf_ingress_egress::DepositChannelLookup<
+			state_chain_runtime::Runtime,
+			C::Instance,
+		>, Vec<_>>(state_chain_client.latest_finalized_block().hash)
+		.await
+		.expect(STATE_CHAIN_CONNECTION)
+		.into_iter()
+		.filter_map(|(_, DepositChannelDetails { deposit_channel, action, .. })| match action {
+			ChannelAction::Swap { .. } | ChannelAction::CcmTransfer { .. } => Some((
+				to_encoded_address(deposit_channel.address.into(), || chainflip_network).to_string(),
+				deposit_channel.asset.into(),
+			)),
+			ChannelAction::LiquidityProvision { .. } => None,
+		})
+		.collect()
+}
+
+pub fn start<C, Rpc, S, StateChainClient>(
+	scope: &task_scope::Scope<'_, anyhow::Error>,
+	rpc: Rpc,
+	mut store: S,
+	state_chain_client: Arc<StateChainClient>,
+	chainflip_network: NetworkEnvironment,
+) where
+	C: Chain + PalletInstanceAlias + 'static,
+	state_chain_runtime::Runtime: pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
+	Rpc: MempoolRpc,
+	S: Store,
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+{
+	scope.spawn(async move {
+		let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+		let mut cache = Cache::default();
+		loop {
+			interval.tick().await;
+			let open_channels =
+				open_swap_channels::<C, _>(state_chain_client.as_ref(), chainflip_network).await;
+			match update_cache(&rpc, &cache, &open_channels).await {
+				Ok(updated_cache) => {
+					for deposit in new_sightings(&cache, &updated_cache) {
+						store.push_notification(&deposit.to_notification()).await?;
+					}
+
+					cache = updated_cache;
+
+					for deposit in cache.deposits.values() {
+						store.save_singleton(deposit).await?;
+					}
+				},
+				Err(err) => {
+					error!("Error when querying {} pending transactions: {err}", C::NAME);
+				},
+			}
+		}
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::anyhow;
+	use cf_primitives::chains::assets::any;
+	use std::sync::Mutex;
+
+	const DEPOSIT_ADDRESS: &str = "0x541f563237a309b3a61e33bdf07a8930bdba8d99";
+	const OTHER_ADDRESS: &str = "0xa56a6be23b6cf39d9448ff6e897c29c41c8fbdff";
+
+	#[derive(Default)]
+	struct MockRpc {
+		pending: Vec<Transfer>,
+		blocks: BTreeMap<u64, Block>,
+		requested_blocks: Mutex<Vec<u64>>,
+	}
+
+	#[async_trait]
+	impl MempoolRpc for MockRpc {
+		const SAFETY_MARGIN: usize = 3;
+
+		async fn pending_transfers(&self) -> anyhow::Result<Vec<Transfer>> {
+			Ok(self.pending.clone())
+		}
+
+		async fn best_block_number(&self) -> anyhow::Result<u64> {
+			self.blocks.keys().last().copied().ok_or(anyhow!("No blocks"))
+		}
+
+		async fn block(&self, block_number: u64) -> anyhow::Result<Block> {
+			self.requested_blocks.lock().unwrap().push(block_number);
+			self.blocks.get(&block_number).cloned().ok_or(anyhow!("Block missing"))
+		}
+	}
+
+	fn transfer(to: &str, asset: Asset, tx_hash: &str) -> Transfer {
+		Transfer { to: to.to_string(), asset, amount: 1_000, tx_hash: tx_hash.to_string() }
+	}
+
+	fn block(number: u64, transfers: Vec<Transfer>) -> Block {
+		Block {
+			number,
+			hash: format!("hash{number}"),
+			parent_hash: format!("hash{}", number.saturating_sub(1)),
+			transfers,
+		}
+	}
+
+	fn open_channels() -> HashMap<String, Asset> {
+		HashMap::from([(DEPOSIT_ADDRESS.to_string(), any::Asset::Usdc)])
+	}
+
+	#[tokio::test]
+	async fn only_transfers_of_the_channel_asset_are_reported() {
+		let rpc = MockRpc {
+			pending: vec![
+				transfer(DEPOSIT_ADDRESS, any::Asset::Eth, "0x01"),
+				transfer(OTHER_ADDRESS, any::Asset::Usdc, "0x02"),
+				transfer(DEPOSIT_ADDRESS, any::Asset::Usdc, "0x03"),
+			],
+			blocks: BTreeMap::from([(0, block(0, vec![])), (1, block(1, vec![]))]),
+			..Default::default()
+		};
+
+		let cache = update_cache(&rpc, &Cache::default(), &open_channels()).await.unwrap();
+
+		assert_eq!(cache.deposits.len(), 1);
+		let deposit = cache.deposits.get(DEPOSIT_ADDRESS).unwrap();
+		assert_eq!(deposit.tx_hash, "0x03");
+		assert_eq!(deposit.confirmations, 0);
+		assert_eq!(deposit.get_key(), format!("mempool:Ethereum:{DEPOSIT_ADDRESS}"));
+	}
+
+	#[tokio::test]
+	async fn confirmations_are_counted_and_blocks_reused() {
+		let mut rpc = MockRpc {
+			pending: vec![transfer(DEPOSIT_ADDRESS, any::Asset::Usdc, "0x01")],
+			blocks: BTreeMap::from([
+				(0, block(0, vec![])),
+				(1, block(1, vec![])),
+				(2, block(2, vec![])),
+			]),
+			..Default::default()
+		};
+
+		let cache = update_cache(&rpc, &Cache::default(), &open_channels()).await.unwrap();
+		assert_eq!(cache.deposits.get(DEPOSIT_ADDRESS).unwrap().confirmations, 0);
+		assert_eq!(new_sightings(&Cache::default(), &cache).count(), 1);
+
+		rpc.pending = vec![];
+		rpc.blocks
+			.insert(3, block(3, vec![transfer(DEPOSIT_ADDRESS, any::Asset::Usdc, "0x01")]));
+		rpc.blocks.insert(4, block(4, vec![]));
+		rpc.requested_blocks.lock().unwrap().clear();
+
+		let updated_cache = update_cache(&rpc, &cache, &open_channels()).await.unwrap();
+		assert_eq!(updated_cache.deposits.get(DEPOSIT_ADDRESS).unwrap().confirmations, 2);
+		assert_eq!(updated_cache.blocks.keys().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+		// Block 2 was already known.
+		assert_eq!(*rpc.requested_blocks.lock().unwrap(), vec![4, 3]);
+		assert_eq!(new_sightings(&cache, &updated_cache).count(), 1);
+		assert_eq!(new_sightings(&updated_cache, &updated_cache).count(), 0);
+	}
+
+	#[tokio::test]
+	async fn reorged_blocks_are_fetched_again() {
+		let mut rpc = MockRpc {
+			blocks: BTreeMap::from([
+				(0, block(0, vec![])),
+				(1, block(1, vec![])),
+				(2, block(2, vec![])),
+			]),
+			..Default::default()
+		};
+
+		let cache = update_cache(&rpc, &Cache::default(), &open_channels()).await.unwrap();
+
+		rpc.blocks.insert(
+			2,
+			Block {
+				hash: "reorged".to_string(),
+				..block(2, vec![transfer(DEPOSIT_ADDRESS, any::Asset::Usdc, "0x01")])
+			},
+		);
+		rpc.blocks
+			.insert(3, Block { parent_hash: "reorged".to_string(), ..block(3, vec![]) });
+		rpc.requested_blocks.lock().unwrap().clear();
+
+		let updated_cache = update_cache(&rpc, &cache, &open_channels()).await.unwrap();
+		assert_eq!(*rpc.requested_blocks.lock().unwrap(), vec![3, 2]);
+		assert_eq!(updated_cache.deposits.get(DEPOSIT_ADDRESS).unwrap().confirmations, 2);
+	}
+}
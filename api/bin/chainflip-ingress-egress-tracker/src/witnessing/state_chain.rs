@@ -1,10 +1,11 @@
 use crate::{
 	store::{Storable, Store},
-	utils::{get_broadcast_id, hex_encode_bytes},
+	utils::{get_broadcast_id, get_channel_broker, hex_encode_bytes},
+	webhooks::{Notification, WebhookEvent},
 };
 use cf_chains::{
-	address::ToHumanreadableAddress, evm::SchnorrVerificationComponents, AnyChain, Bitcoin, Chain,
-	Ethereum, Polkadot,
+	address::ToHumanreadableAddress, evm::SchnorrVerificationComponents, hub::Assethub, AnyChain,
+	Bitcoin, Chain, Ethereum, Polkadot,
 };
 use cf_primitives::{BroadcastId, ForeignChain, NetworkEnvironment};
 use chainflip_engine::state_chain_observer::client::{
@@ -12,14 +13,16 @@ use chainflip_engine::state_chain_observer::client::{
 };
 use pallet_cf_ingress_egress::DepositWitness;
 use serde::Serialize;
+use state_chain_runtime::PalletInstanceAlias;
 use utilities::rpc::NumberOrHex;
 
 #[derive(Serialize)]
 #[serde(untagged)]
-enum TransactionId {
+pub(crate) enum TransactionId {
 	Bitcoin { hash: String },
 	Ethereum { signature: SchnorrVerificationComponents },
 	Polkadot { signature: String },
+	Assethub { signature: String },
 }
 
 #[derive(Serialize)]
@@ -47,6 +50,7 @@ impl WitnessInformation {
 				TransactionId::Bitcoin { .. } => ForeignChain::Bitcoin,
 				TransactionId::Ethereum { .. } => ForeignChain::Ethereum,
 				TransactionId::Polkadot { .. } => ForeignChain::Polkadot,
+				TransactionId::Assethub { .. } => ForeignChain::Assethub,
 			},
 		}
 	}
@@ -109,6 +113,68 @@ impl From<DepositInfo<Polkadot>> for WitnessInformation {
 	}
 }
 
+impl From<DepositInfo<Assethub>> for WitnessInformation {
+	fn from((value, height, _): DepositInfo<Assethub>) -> Self {
+		Self::Deposit {
+			deposit_chain_block_height: height as u64,
+			deposit_address: hex_encode_bytes(value.deposit_address.aliased_ref()),
+			amount: value.amount.into(),
+			asset: value.asset.into(),
+		}
+	}
+}
+
+async fn handle_deposit_witnesses<C, S, StateChainClient>(
+	deposit_witnesses: Vec<DepositWitness<C>>,
+	block_height: C::ChainBlockNumber,
+	store: &mut S,
+	chainflip_network: NetworkEnvironment,
+	state_chain_client: &StateChainClient,
+) -> anyhow::Result<()>
+where
+	C: Chain + PalletInstanceAlias + 'static,
+	state_chain_runtime::Runtime: pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
+	WitnessInformation: From<DepositInfo<C>>,
+	S: Store,
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+{
+	for witness in deposit_witnesses {
+		let broker = get_channel_broker::<C, _>(
+			state_chain_client,
+			state_chain_client.latest_unfinalized_block().hash,
+			&witness.deposit_address,
+		)
+		.await;
+
+		let witness_information =
+			WitnessInformation::from((witness, block_height, chainflip_network));
+		store.save_to_array(&witness_information).await?;
+
+		if let WitnessInformation::Deposit {
+			deposit_chain_block_height,
+			deposit_address,
+			amount,
+			asset,
+		} = witness_information
+		{
+			store
+				.push_notification(&Notification::new(
+					WebhookEvent::DepositPrewitnessed {
+						asset,
+						address: deposit_address.clone(),
+						amount,
+						deposit_chain_block_height,
+					},
+					broker,
+					vec![deposit_address],
+				))
+				.await?;
+		}
+	}
+
+	Ok(())
+}
+
 pub async fn handle_call<S, StateChainClient>(
 	call: state_chain_runtime::RuntimeCall,
 	store: &mut S,
@@ -128,41 +194,50 @@ where
 			deposit_witnesses,
 			block_height,
 		}) =>
-			for witness in deposit_witnesses as Vec<DepositWitness<Ethereum>> {
-				store
-					.save_to_array(&WitnessInformation::from((
-						witness,
-						block_height,
-						chainflip_network,
-					)))
-					.await?;
-			},
+			handle_deposit_witnesses::<Ethereum, _, _>(
+				deposit_witnesses,
+				block_height,
+				store,
+				chainflip_network,
+				state_chain_client,
+			)
+			.await?,
 		BitcoinIngressEgress(IngressEgressCall::process_deposits {
 			deposit_witnesses,
 			block_height,
 		}) =>
-			for witness in deposit_witnesses as Vec<DepositWitness<Bitcoin>> {
-				store
-					.save_to_array(&WitnessInformation::from((
-						witness,
-						block_height,
-						chainflip_network,
-					)))
-					.await?;
-			},
+			handle_deposit_witnesses::<Bitcoin, _, _>(
+				deposit_witnesses,
+				block_height,
+				store,
+				chainflip_network,
+				state_chain_client,
+			)
+			.await?,
 		PolkadotIngressEgress(IngressEgressCall::process_deposits {
 			deposit_witnesses,
 			block_height,
 		}) =>
-			for witness in deposit_witnesses as Vec<DepositWitness<Polkadot>> {
-				store
-					.save_to_array(&WitnessInformation::from((
-						witness,
-						block_height,
-						chainflip_network,
-					)))
-					.await?;
-			},
+			handle_deposit_witnesses::<Polkadot, _, _>(
+				deposit_witnesses,
+				block_height,
+				store,
+				chainflip_network,
+				state_chain_client,
+			)
+			.await?,
+		AssethubIngressEgress(IngressEgressCall::process_deposits {
+			deposit_witnesses,
+			block_height,
+		}) =>
+			handle_deposit_witnesses::<Assethub, _, _>(
+				deposit_witnesses,
+				block_height,
+				store,
+				chainflip_network,
+				state_chain_client,
+			)
+			.await?,
 		EthereumBroadcaster(BroadcastCall::transaction_succeeded { tx_out_id, .. }) => {
 			let broadcast_id =
 				get_broadcast_id::<Ethereum, StateChainClient>(state_chain_client, &tx_out_id)
@@ -208,10 +283,27 @@ where
 					.await?;
 			}
 		},
+		AssethubBroadcaster(BroadcastCall::transaction_succeeded { tx_out_id, .. }) => {
+			let broadcast_id =
+				get_broadcast_id::<Assethub, StateChainClient>(state_chain_client, &tx_out_id)
+					.await;
+
+			if let Some(broadcast_id) = broadcast_id {
+				store
+					.save_singleton(&WitnessInformation::Broadcast {
+						broadcast_id,
+						tx_out_id: TransactionId::Assethub {
+							signature: format!("0x{}", hex::encode(tx_out_id.aliased_ref())),
+						},
+					})
+					.await?;
+			}
+		},
 
 		EthereumIngressEgress(_) |
 		BitcoinIngressEgress(_) |
 		PolkadotIngressEgress(_) |
+		AssethubIngressEgress(_) |
 		System(_) |
 		Timestamp(_) |
 		Environment(_) |
@@ -229,15 +321,18 @@ where
 		EthereumChainTracking(_) |
 		BitcoinChainTracking(_) |
 		PolkadotChainTracking(_) |
+		AssethubChainTracking(_) |
 		EthereumVault(_) |
 		PolkadotVault(_) |
 		BitcoinVault(_) |
+		AssethubVault(_) |
 		EthereumThresholdSigner(_) |
 		PolkadotThresholdSigner(_) |
 		BitcoinThresholdSigner(_) |
 		EthereumBroadcaster(_) |
 		PolkadotBroadcaster(_) |
 		BitcoinBroadcaster(_) |
+		AssethubBroadcaster(_) |
 		Swapping(_) |
 		LiquidityProvider(_) |
 		LiquidityPools(_) => {},
@@ -247,7 +342,7 @@ where
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 	use super::*;
 	use anyhow::anyhow;
 	use async_trait::async_trait;
@@ -275,6 +370,7 @@ mod tests {
 	#[derive(Default)]
 	struct MockStore {
 		storage: HashMap<String, serde_json::Value>,
+		notifications: Vec<Notification>,
 	}
 
 	#[async_trait]
@@ -307,6 +403,15 @@ mod tests {
 
 			Ok(())
 		}
+
+		async fn push_notification(
+			&mut self,
+			notification: &Notification,
+		) -> anyhow::Result<Self::Output> {
+			self.notifications.push(notification.clone());
+
+			Ok(())
+		}
 	}
 
 	mock! {
@@ -421,7 +526,24 @@ mod tests {
 		let (eth_address2, eth_address_str2) =
 			parse_eth_address("0xa56A6be23b6Cf39D9448FF6e897C29c41c8fbDFF");
 
-		let client = MockStateChainClient::new();
+		let mut client = MockStateChainClient::new();
+		client
+			.expect_storage_map_entry::<pallet_cf_ingress_egress::DepositChannelLookup<
+				state_chain_runtime::Runtime,
+				state_chain_runtime::EthereumInstance,
+			>>()
+			.returning(|_, _| Ok(None));
+		client
+			.expect_storage_map_entry::<pallet_cf_ingress_egress::DepositChannelLookup<
+				state_chain_runtime::Runtime,
+				state_chain_runtime::PolkadotInstance,
+			>>()
+			.returning(|_, _| Ok(None));
+		client.expect_latest_unfinalized_block().returning(|| BlockInfo {
+			parent_hash: state_chain_runtime::Hash::default(),
+			hash: state_chain_runtime::Hash::default(),
+			number: 1,
+		});
 		let mut store = MockStore::default();
 		handle_call(
 			state_chain_runtime::RuntimeCall::EthereumIngressEgress(
@@ -522,6 +644,21 @@ mod tests {
 			.storage
 			.get(format!("deposit:Ethereum:{}", eth_address_str1.to_lowercase()).as_str())
 			.unwrap());
+
+		assert_eq!(store.notifications.len(), 4);
+		assert_eq!(
+			store.notifications[3],
+			Notification::new(
+				WebhookEvent::DepositPrewitnessed {
+					asset: cf_chains::assets::any::Asset::Eth,
+					address: eth_address_str1.to_lowercase(),
+					amount: 2_000_000u128.into(),
+					deposit_chain_block_height: 1,
+				},
+				None,
+				vec![eth_address_str1.to_lowercase()],
+			)
+		);
 	}
 
 	#[tokio::test]
@@ -0,0 +1,535 @@
+use crate::{
+	store::Store,
+	utils::get_channel_broker,
+	webhooks::{Notification, WebhookEvent},
+	witnessing::state_chain::TransactionId,
+};
+use cf_chains::{
+	address::to_encoded_address, hub::Assethub, Bitcoin, Chain, Ethereum, Polkadot, SwapOrigin,
+};
+use cf_primitives::{AssetAmount, BroadcastId, EgressId, ForeignChain, NetworkEnvironment, SwapId};
+use chainflip_engine::state_chain_observer::client::{
+	chain_api::ChainApi, storage_api::StorageApi, BlockInfo,
+};
+use futures::StreamExt;
+use pallet_cf_ingress_egress::DepositAction;
+use state_chain_runtime::{AccountId, PalletInstanceAlias, RuntimeEvent};
+use std::{collections::HashMap, sync::Arc};
+use utilities::task_scope;
+
+/// What we know about the origin of a swap, used to attribute its later events.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct SwapContext {
+	broker: Option<String>,
+	addresses: Vec<String>,
+}
+
+impl SwapContext {
+	fn add_address(&mut self, address: String) {
+		if !self.addresses.contains(&address) {
+			self.addresses.push(address);
+		}
+	}
+}
+
+/// Follows swaps from their deposit through to the broadcast of their egress, so that each
+/// notification can be attributed to the broker and addresses of the swap it belongs to. Entries
+/// are dropped as soon as the swap moves on to the next stage.
+#[derive(Default)]
+struct SwapProgress {
+	swaps: HashMap<SwapId, SwapContext>,
+	egresses: HashMap<EgressId, (SwapId, SwapContext)>,
+	broadcasts: HashMap<(ForeignChain, BroadcastId), Vec<(EgressId, SwapContext)>>,
+}
+
+impl SwapProgress {
+	#[allow(clippy::too_many_arguments)]
+	async fn deposit_received<C, StateChainClient>(
+		&mut self,
+		state_chain_client: &StateChainClient,
+		block: BlockInfo,
+		chainflip_network: NetworkEnvironment,
+		deposit_address: C::ChainAccount,
+		asset: C::ChainAsset,
+		amount: C::ChainAmount,
+		ingress_fee: C::ChainAmount,
+		action: DepositAction<AccountId>,
+	) -> Notification
+	where
+		C: Chain + PalletInstanceAlias + 'static,
+		state_chain_runtime::Runtime:
+			pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
+		StateChainClient: StorageApi + 'static + Send + Sync,
+	{
+		let broker =
+			get_channel_broker::<C, _>(state_chain_client, block.hash, &deposit_address).await;
+		let address = to_encoded_address(deposit_address.into(), || chainflip_network).to_string();
+
+		let swap_ids = match action {
+			DepositAction::Swap { swap_id } => vec![swap_id],
+			DepositAction::CcmTransfer { principal_swap_id, gas_swap_id } =>
+				principal_swap_id.into_iter().chain(gas_swap_id).collect(),
+			DepositAction::LiquidityProvision { .. } | DepositAction::NoAction => vec![],
+		};
+		for swap_id in swap_ids {
+			let context = self.swaps.entry(swap_id).or_default();
+			context.broker = broker.clone();
+			context.add_address(address.clone());
+		}
+
+		Notification::new(
+			WebhookEvent::DepositWitnessed {
+				asset: asset.into(),
+				address: address.clone(),
+				amount: Into::<AssetAmount>::into(amount).into(),
+				ingress_fee: Into::<AssetAmount>::into(ingress_fee).into(),
+				state_chain_block_number: block.number,
+			},
+			broker,
+			vec![address],
+		)
+	}
+
+	fn broadcast_requested(&mut self, broadcast_id: BroadcastId, egress_ids: Vec<EgressId>) {
+		for egress_id in egress_ids {
+			if let Some((_, context)) = self.egresses.remove(&egress_id) {
+				self.broadcasts
+					.entry((egress_id.0, broadcast_id))
+					.or_default()
+					.push((egress_id, context));
+			}
+		}
+	}
+
+	fn broadcast_success(
+		&mut self,
+		chain: ForeignChain,
+		broadcast_id: BroadcastId,
+		tx_out_id: TransactionId,
+		block: BlockInfo,
+	) -> Vec<Notification> {
+		let tx_out_id = serde_json::to_value(tx_out_id).expect("TransactionId is serializable");
+		let notification = |egress_id, context: SwapContext| {
+			Notification::new(
+				WebhookEvent::BroadcastSuccess {
+					chain,
+					broadcast_id,
+					egress_id,
+					tx_out_id: tx_out_id.clone(),
+					state_chain_block_number: block.number,
+				},
+				context.broker,
+				context.addresses,
+			)
+		};
+
+		match self.broadcasts.remove(&(chain, broadcast_id)) {
+			Some(egresses) => egresses
+				.into_iter()
+				.map(|(egress_id, context)| notification(Some(egress_id), context))
+				.collect(),
+			// The broadcast doesn't carry any swap egress we know of.
+			None => vec![notification(None, Default::default())],
+		}
+	}
+
+	async fn handle_event<StateChainClient>(
+		&mut self,
+		event: RuntimeEvent,
+		block: BlockInfo,
+		chainflip_network: NetworkEnvironment,
+		state_chain_client: &StateChainClient,
+	) -> Vec<Notification>
+	where
+		StateChainClient: StorageApi + 'static + Send + Sync,
+	{
+		use pallet_cf_broadcast::Event as BroadcastEvent;
+		use pallet_cf_ingress_egress::Event as IngressEgressEvent;
+		use pallet_cf_swapping::Event as SwappingEvent;
+
+		match event {
+			RuntimeEvent::EthereumIngressEgress(IngressEgressEvent::DepositReceived {
+				deposit_address,
+				asset,
+				amount,
+				ingress_fee,
+				action,
+				..
+			}) => vec![
+				self.deposit_received::<Ethereum, _>(
+					state_chain_client,
+					block,
+					chainflip_network,
+					deposit_address,
+					asset,
+					amount,
+					ingress_fee,
+					action,
+				)
+				.await,
+			],
+			RuntimeEvent::BitcoinIngressEgress(IngressEgressEvent::DepositReceived {
+				deposit_address,
+				asset,
+				amount,
+				ingress_fee,
+				action,
+				..
+			}) => vec![
+				self.deposit_received::<Bitcoin, _>(
+					state_chain_client,
+					block,
+					chainflip_network,
+					deposit_address,
+					asset,
+					amount,
+					ingress_fee,
+					action,
+				)
+				.await,
+			],
+			RuntimeEvent::PolkadotIngressEgress(IngressEgressEvent::DepositReceived {
+				deposit_address,
+				asset,
+				amount,
+				ingress_fee,
+				action,
+				..
+			}) => vec![
+				self.deposit_received::<Polkadot, _>(
+					state_chain_client,
+					block,
+					chainflip_network,
+					deposit_address,
+					asset,
+					amount,
+					ingress_fee,
+					action,
+				)
+				.await,
+			],
+			RuntimeEvent::AssethubIngressEgress(IngressEgressEvent::DepositReceived {
+				deposit_address,
+				asset,
+				amount,
+				ingress_fee,
+				action,
+				..
+			}) => vec![
+				self.deposit_received::<Assethub, _>(
+					state_chain_client,
+					block,
+					chainflip_network,
+					deposit_address,
+					asset,
+					amount,
+					ingress_fee,
+					action,
+				)
+				.await,
+			],
+			RuntimeEvent::Swapping(SwappingEvent::SwapScheduled {
+				swap_id,
+				destination_address,
+				origin,
+				..
+			}) => {
+				let context = self.swaps.entry(swap_id).or_default();
+				if let SwapOrigin::DepositChannel { deposit_address, .. } = origin {
+					context.add_address(deposit_address.to_string());
+				}
+				context.add_address(destination_address.to_string());
+				vec![]
+			},
+			RuntimeEvent::Swapping(SwappingEvent::SwapExecuted {
+				swap_id,
+				source_asset,
+				destination_asset,
+				swap_input,
+				swap_output,
+				..
+			}) => {
+				let context = self.swaps.get(&swap_id).cloned().unwrap_or_default();
+				vec![Notification::new(
+					WebhookEvent::SwapExecuted {
+						swap_id,
+						source_asset,
+						destination_asset,
+						swap_input: swap_input.into(),
+						swap_output: swap_output.into(),
+						state_chain_block_number: block.number,
+					},
+					context.broker,
+					context.addresses,
+				)]
+			},
+			RuntimeEvent::Swapping(SwappingEvent::SwapEgressScheduled {
+				swap_id,
+				egress_id,
+				asset,
+				amount,
+				fee,
+			}) => {
+				let context = self.swaps.remove(&swap_id).unwrap_or_default();
+				self.egresses.insert(egress_id, (swap_id, context.clone()));
+				vec![Notification::new(
+					WebhookEvent::EgressScheduled {
+						swap_id,
+						egress_id,
+						asset,
+						amount: amount.into(),
+						fee: fee.into(),
+						state_chain_block_number: block.number,
+					},
+					context.broker,
+					context.addresses,
+				)]
+			},
+			RuntimeEvent::EthereumIngressEgress(IngressEgressEvent::BatchBroadcastRequested {
+				broadcast_id,
+				egress_ids,
+			}) |
+			RuntimeEvent::BitcoinIngressEgress(IngressEgressEvent::BatchBroadcastRequested {
+				broadcast_id,
+				egress_ids,
+			}) |
+			RuntimeEvent::PolkadotIngressEgress(IngressEgressEvent::BatchBroadcastRequested {
+				broadcast_id,
+				egress_ids,
+			}) |
+			RuntimeEvent::AssethubIngressEgress(IngressEgressEvent::BatchBroadcastRequested {
+				broadcast_id,
+				egress_ids,
+			}) => {
+				self.broadcast_requested(broadcast_id, egress_ids);
+				vec![]
+			},
+			RuntimeEvent::EthereumBroadcaster(BroadcastEvent::BroadcastSuccess {
+				broadcast_id,
+				transaction_out_id,
+			}) => self.broadcast_success(
+				ForeignChain::Ethereum,
+				broadcast_id,
+				TransactionId::Ethereum { signature: transaction_out_id },
+				block,
+			),
+			RuntimeEvent::BitcoinBroadcaster(BroadcastEvent::BroadcastSuccess {
+				broadcast_id,
+				transaction_out_id,
+			}) => self.broadcast_success(
+				ForeignChain::Bitcoin,
+				broadcast_id,
+				TransactionId::Bitcoin { hash: format!("0x{}", hex::encode(transaction_out_id)) },
+				block,
+			),
+			RuntimeEvent::PolkadotBroadcaster(BroadcastEvent::BroadcastSuccess {
+				broadcast_id,
+				transaction_out_id,
+			}) => self.broadcast_success(
+				ForeignChain::Polkadot,
+				broadcast_id,
+				TransactionId::Polkadot {
+					signature: format!("0x{}", hex::encode(transaction_out_id.aliased_ref())),
+				},
+				block,
+			),
+			RuntimeEvent::AssethubBroadcaster(BroadcastEvent::BroadcastSuccess {
+				broadcast_id,
+				transaction_out_id,
+			}) => self.broadcast_success(
+				ForeignChain::Assethub,
+				broadcast_id,
+				TransactionId::Assethub {
+					signature: format!("0x{}", hex::encode(transaction_out_id.aliased_ref())),
+				},
+				block,
+			),
+			_ => vec![],
+		}
+	}
+}
+
+/// Pushes notifications for the deposit, swap and egress events of every finalized State Chain
+/// block.
+pub fn start<S, StateChainClient>(
+	scope: &task_scope::Scope<'_, anyhow::Error>,
+	state_chain_client: Arc<StateChainClient>,
+	mut store: S,
+	chainflip_network: NetworkEnvironment,
+) where
+	S: Store,
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+{
+	scope.spawn(async move {
+		let mut swap_progress = SwapProgress::default();
+		let mut finalized_block_stream = state_chain_client.finalized_block_stream().await;
+
+		while let Some(block) = finalized_block_stream.next().await {
+			let events = state_chain_client
+				.storage_value::<frame_system::Events<state_chain_runtime::Runtime>>(block.hash)
+				.await?;
+
+			for event_record in events {
+				for notification in swap_progress
+					.handle_event(
+						event_record.event,
+						block,
+						chainflip_network,
+						state_chain_client.as_ref(),
+					)
+					.await
+				{
+					store.push_notification(&notification).await?;
+				}
+			}
+		}
+
+		Ok(())
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::witnessing::state_chain::tests::MockStateChainClient;
+	use cf_chains::{
+		address::{EncodedAddress, ForeignChainAddress},
+		assets::eth,
+		evm::SchnorrVerificationComponents,
+		DepositChannel,
+	};
+	use cf_primitives::chains::assets::any;
+	use pallet_cf_ingress_egress::{ChannelAction, DepositChannelDetails};
+	use sp_core::{crypto::Ss58Codec, H160};
+	use state_chain_runtime::{constants::common::CHAINFLIP_SS58_PREFIX, EthereumInstance};
+
+	const BLOCK: BlockInfo = BlockInfo {
+		parent_hash: state_chain_runtime::Hash::zero(),
+		hash: state_chain_runtime::Hash::zero(),
+		number: 10,
+	};
+
+	#[tokio::test]
+	async fn swap_events_are_attributed_to_the_channel_broker() {
+		let deposit_address = H160::from([1; 20]);
+		let destination_address = EncodedAddress::Eth([2; 20]);
+		let broker_id = AccountId::new([3; 32]);
+		let broker = broker_id.to_ss58check_with_version(CHAINFLIP_SS58_PREFIX.into());
+		let egress_id = (ForeignChain::Ethereum, 1);
+
+		let mut client = MockStateChainClient::new();
+		client
+			.expect_storage_map_entry::<pallet_cf_ingress_egress::DepositChannelLookup<
+				state_chain_runtime::Runtime,
+				EthereumInstance,
+			>>()
+			.return_once(move |_, _| {
+				Ok(Some(DepositChannelDetails {
+					deposit_channel: DepositChannel {
+						channel_id: 1,
+						address: deposit_address,
+						asset: eth::Asset::Eth,
+						state: Default::default(),
+					},
+					opened_at: 1,
+					expires_at: 100,
+					action: ChannelAction::Swap {
+						destination_asset: any::Asset::Flip,
+						destination_address: ForeignChainAddress::Eth(H160::from([2; 20])),
+						broker_id,
+						broker_commission_bps: 0,
+						affiliates: Default::default(),
+					},
+					boost_fee: 0,
+				}))
+			});
+
+		let mut swap_progress = SwapProgress::default();
+		let mut notifications = vec![];
+		for event in [
+			RuntimeEvent::Swapping(pallet_cf_swapping::Event::SwapScheduled {
+				swap_id: 1,
+				source_asset: any::Asset::Eth,
+				deposit_amount: 1_000,
+				destination_asset: any::Asset::Flip,
+				destination_address: destination_address.clone(),
+				origin: SwapOrigin::DepositChannel {
+					deposit_address: EncodedAddress::Eth([1; 20]),
+					channel_id: 1,
+					deposit_block_height: 1,
+				},
+				swap_type: pallet_cf_swapping::SwapType::Swap(ForeignChainAddress::Eth(
+					H160::from([2; 20]),
+				)),
+				broker_commission: None,
+				execute_at: 11,
+			}),
+			RuntimeEvent::EthereumIngressEgress(pallet_cf_ingress_egress::Event::DepositReceived {
+				deposit_address,
+				asset: eth::Asset::Eth,
+				amount: 1_000,
+				deposit_details: (),
+				ingress_fee: 0,
+				action: DepositAction::Swap { swap_id: 1 },
+			}),
+			#[allow(deprecated)]
+			RuntimeEvent::Swapping(pallet_cf_swapping::Event::SwapExecuted {
+				swap_id: 1,
+				source_asset: any::Asset::Eth,
+				deposit_amount: 1_000,
+				swap_input: 1_000,
+				destination_asset: any::Asset::Flip,
+				egress_amount: 2_000,
+				swap_output: 2_000,
+				intermediate_amount: None,
+			}),
+			RuntimeEvent::Swapping(pallet_cf_swapping::Event::SwapEgressScheduled {
+				swap_id: 1,
+				egress_id,
+				asset: any::Asset::Flip,
+				amount: 1_900,
+				fee: 100,
+			}),
+			RuntimeEvent::EthereumIngressEgress(
+				pallet_cf_ingress_egress::Event::BatchBroadcastRequested {
+					broadcast_id: 7,
+					egress_ids: vec![egress_id],
+				},
+			),
+			RuntimeEvent::EthereumBroadcaster(pallet_cf_broadcast::Event::BroadcastSuccess {
+				broadcast_id: 7,
+				transaction_out_id: SchnorrVerificationComponents {
+					s: [0; 32],
+					k_times_g_address: [0; 20],
+				},
+			}),
+		] {
+			notifications.extend(
+				swap_progress
+					.handle_event(event, BLOCK, NetworkEnvironment::Testnet, &client)
+					.await,
+			);
+		}
+
+		assert_eq!(notifications.len(), 4);
+		assert!(matches!(notifications[0].event, WebhookEvent::DepositWitnessed { .. }));
+		assert!(matches!(notifications[1].event, WebhookEvent::SwapExecuted { .. }));
+		assert!(matches!(notifications[2].event, WebhookEvent::EgressScheduled { .. }));
+		assert!(matches!(
+			notifications[3].event,
+			WebhookEvent::BroadcastSuccess { broadcast_id: 7, egress_id: Some(_), .. }
+		));
+		for notification in &notifications[1..] {
+			assert_eq!(notification.broker, Some(broker.clone()));
+			assert_eq!(
+				notification.addresses,
+				vec![format!("0x{}", hex::encode([1; 20])), destination_address.to_string()]
+			);
+		}
+
+		// Everything about the swap has been forgotten once its egress has been broadcast.
+		assert!(swap_progress.swaps.is_empty());
+		assert!(swap_progress.egresses.is_empty());
+		assert!(swap_progress.broadcasts.is_empty());
+	}
+}
@@ -1,4 +1,4 @@
-use cf_primitives::{AccountId, BasisPoints, BlockNumber, EgressId};
+use cf_primitives::{AccountId, BasisPoints, BlockNumber, EgressId, SwapId};
 use cf_utilities::{
 	rpc::NumberOrHex,
 	task_scope::{task_scope, Scope},
@@ -7,13 +7,14 @@ use cf_utilities::{
 use chainflip_api::{
 	self,
 	lp::{
-		types::{LimitOrder, RangeOrder},
+		types::{LimitOrder, OrderBatch, RangeOrder},
 		ApiWaitForResult, LpApi, Order, Tick,
 	},
 	primitives::{
-		chains::{assets::any::OldAsset, Bitcoin, Ethereum, Polkadot},
+		chains::{assets::any::OldAsset, Assethub, Bitcoin, Ethereum, Polkadot},
 		AccountRole, Asset, ForeignChain, Hash, RedemptionAmount,
 	},
+	rpc_server::{self, RpcServerOptions},
 	settings::StateChain,
 	BlockInfo, BlockUpdate, ChainApi, EthereumAddress, OperatorApi, SignedExtrinsicApi,
 	StateChainApi, StorageApi, WaitFor,
@@ -24,12 +25,16 @@ use futures::{try_join, FutureExt, StreamExt};
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
 	proc_macros::rpc,
-	server::ServerBuilder,
 	types::SubscriptionResult,
 	SubscriptionSink,
 };
-use pallet_cf_pools::{AssetPair, AssetsMap, IncreaseOrDecrease, OrderId, RangeOrderSize};
-use rpc_types::{AssetBalance, OpenSwapChannels, OrderIdJson, RangeOrderSizeJson};
+use pallet_cf_pools::{
+	AskBidMap, AssetPair, AssetsMap, IncreaseOrDecrease, OrderId, OrderOperation, RangeOrderPolicy,
+	RangeOrderSize,
+};
+use rpc_types::{
+	AssetBalance, OpenSwapChannels, OrderIdJson, OrderOperationJson, RangeOrderSizeJson,
+};
 use sp_core::U256;
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
@@ -86,11 +91,117 @@ pub mod rpc_types {
 		}
 	}
 
+	#[derive(Clone, Debug, Serialize, Deserialize)]
+	#[serde(rename_all = "snake_case")]
+	pub enum OrderOperationJson {
+		UpdateRangeOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderIdJson,
+			tick_range: Option<Range<Tick>>,
+			size_change: IncreaseOrDecrease<RangeOrderSizeJson>,
+		},
+		SetRangeOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderIdJson,
+			tick_range: Option<Range<Tick>>,
+			size: RangeOrderSizeJson,
+		},
+		UpdateLimitOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Order,
+			id: OrderIdJson,
+			tick: Option<Tick>,
+			amount_change: IncreaseOrDecrease<NumberOrHex>,
+			expiry_block: Option<BlockNumber>,
+		},
+		SetLimitOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Order,
+			id: OrderIdJson,
+			tick: Option<Tick>,
+			sell_amount: NumberOrHex,
+			expiry_block: Option<BlockNumber>,
+		},
+	}
+	impl TryFrom<OrderOperationJson> for OrderOperation<BlockNumber> {
+		type Error = anyhow::Error;
+
+		fn try_from(value: OrderOperationJson) -> Result<Self, Self::Error> {
+			Ok(match value {
+				OrderOperationJson::UpdateRangeOrder {
+					base_asset,
+					quote_asset,
+					id,
+					tick_range,
+					size_change,
+				} => OrderOperation::UpdateRangeOrder {
+					base_asset,
+					quote_asset,
+					id: id.try_into()?,
+					option_tick_range: tick_range,
+					size_change: size_change.try_map(TryInto::try_into)?,
+				},
+				OrderOperationJson::SetRangeOrder {
+					base_asset,
+					quote_asset,
+					id,
+					tick_range,
+					size,
+				} => OrderOperation::SetRangeOrder {
+					base_asset,
+					quote_asset,
+					id: id.try_into()?,
+					option_tick_range: tick_range,
+					size: size.try_into()?,
+				},
+				OrderOperationJson::UpdateLimitOrder {
+					base_asset,
+					quote_asset,
+					side,
+					id,
+					tick,
+					amount_change,
+					expiry_block,
+				} => OrderOperation::UpdateLimitOrder {
+					base_asset,
+					quote_asset,
+					side,
+					id: id.try_into()?,
+					option_tick: tick,
+					amount_change: amount_change.try_map(try_parse_number_or_hex)?,
+					expiry_block,
+				},
+				OrderOperationJson::SetLimitOrder {
+					base_asset,
+					quote_asset,
+					side,
+					id,
+					tick,
+					sell_amount,
+					expiry_block,
+				} => OrderOperation::SetLimitOrder {
+					base_asset,
+					quote_asset,
+					side,
+					id: id.try_into()?,
+					option_tick: tick,
+					sell_amount: try_parse_number_or_hex(sell_amount)?,
+					expiry_block,
+				},
+			})
+		}
+	}
+
 	#[derive(Serialize, Deserialize, Clone)]
 	pub struct OpenSwapChannels {
 		pub ethereum: Vec<SwapChannelInfo<Ethereum>>,
 		pub bitcoin: Vec<SwapChannelInfo<Bitcoin>>,
 		pub polkadot: Vec<SwapChannelInfo<Polkadot>>,
+		pub assethub: Vec<SwapChannelInfo<Assethub>>,
 	}
 
 	#[derive(Serialize, Deserialize, Clone)]
@@ -100,6 +211,22 @@ pub mod rpc_types {
 	}
 }
 
+/// Methods that submit signed extrinsics, and are therefore recorded in the audit log.
+const EXTRINSIC_SUBMITTING_METHODS: &[&str] = &[
+	"lp_register_account",
+	"lp_liquidity_deposit",
+	"lp_register_liquidity_refund_address",
+	"lp_withdraw_asset",
+	"lp_internal_swap",
+	"lp_set_range_order_policy",
+	"lp_update_range_order",
+	"lp_set_range_order",
+	"lp_update_limit_order",
+	"lp_set_limit_order",
+	"lp_batch_orders",
+	"lp_request_redemption",
+];
+
 #[rpc(server, client, namespace = "lp")]
 pub trait Rpc {
 	#[method(name = "register_account")]
@@ -129,6 +256,25 @@ pub trait Rpc {
 		wait_for: Option<WaitFor>,
 	) -> RpcResult<ApiWaitForResult<EgressId>>;
 
+	#[method(name = "internal_swap")]
+	async fn internal_swap(
+		&self,
+		amount: NumberOrHex,
+		from: Asset,
+		to: Asset,
+		min_output: NumberOrHex,
+		wait_for: Option<WaitFor>,
+	) -> RpcResult<ApiWaitForResult<SwapId>>;
+
+	#[method(name = "set_range_order_policy")]
+	async fn set_range_order_policy(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		id: OrderIdJson,
+		policy: Option<RangeOrderPolicy>,
+	) -> RpcResult<Hash>;
+
 	#[method(name = "update_range_order")]
 	async fn update_range_order(
 		&self,
@@ -162,6 +308,7 @@ pub trait Rpc {
 		amount_change: IncreaseOrDecrease<NumberOrHex>,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: Option<WaitFor>,
+		expiry_block: Option<BlockNumber>,
 	) -> RpcResult<ApiWaitForResult<Vec<LimitOrder>>>;
 
 	#[method(name = "set_limit_order")]
@@ -175,8 +322,16 @@ pub trait Rpc {
 		sell_amount: NumberOrHex,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: Option<WaitFor>,
+		expiry_block: Option<BlockNumber>,
 	) -> RpcResult<ApiWaitForResult<Vec<LimitOrder>>>;
 
+	#[method(name = "batch_orders")]
+	async fn batch_orders(
+		&self,
+		orders: Vec<OrderOperationJson>,
+		wait_for: Option<WaitFor>,
+	) -> RpcResult<ApiWaitForResult<OrderBatch>>;
+
 	#[method(name = "asset_balances")]
 	async fn asset_balances(&self) -> RpcResult<BTreeMap<ForeignChain, Vec<AssetBalance>>>;
 
@@ -196,6 +351,9 @@ pub trait Rpc {
 
 	#[method(name = "order_fills")]
 	async fn order_fills(&self, at: Option<Hash>) -> RpcResult<BlockUpdate<OrderFills>>;
+
+	#[subscription(name = "subscribe_pool_state", item = BlockUpdate<PoolStateUpdate>)]
+	fn subscribe_pool_state(&self, base_asset: Asset, quote_asset: Asset);
 }
 
 pub struct RpcServerImpl {
@@ -245,6 +403,104 @@ pub enum OrderFilled {
 	},
 }
 
+/// A change to a pool's order book, range liquidity and price, sent once per finalized block by the
+/// pool state subscription.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct PoolStateUpdate {
+	/// Starts at zero and increases by one with each update of the subscription, so that missed
+	/// updates can be detected.
+	sequence_number: u64,
+	/// The first update is a full snapshot of the pool. Later updates only contain the ticks and
+	/// ranges whose amounts changed, where an amount of zero means there are no longer any orders
+	/// there.
+	is_snapshot: bool,
+	base_asset: OldAsset,
+	quote_asset: OldAsset,
+	/// The total amount sold by the limit orders at each tick.
+	limit_orders: AskBidMap<Vec<LimitOrderLevel>>,
+	/// The total liquidity of the range orders over each range.
+	range_orders: Vec<RangeOrderLevel>,
+	/// The pool's current price. Only present in the snapshot, and if the price has changed.
+	price: Option<pallet_cf_pools::PoolPriceV2>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct LimitOrderLevel {
+	tick: Tick,
+	amount: U256,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct RangeOrderLevel {
+	range: Range<Tick>,
+	liquidity: U256,
+}
+
+/// The aggregated orders and price of a pool at a block, from which [PoolStateUpdate]s are
+/// computed.
+#[derive(Default, Clone, PartialEq, Eq)]
+struct PoolSnapshot {
+	limit_orders: AskBidMap<BTreeMap<Tick, U256>>,
+	range_orders: BTreeMap<(Tick, Tick), U256>,
+	price: Option<pallet_cf_pools::PoolPriceV2>,
+}
+
+impl PoolSnapshot {
+	/// Returns the update from `previous` to this snapshot, or a full snapshot if there is no
+	/// previous snapshot.
+	fn update_since(
+		&self,
+		previous: Option<&PoolSnapshot>,
+		base_asset: Asset,
+		quote_asset: Asset,
+		sequence_number: u64,
+	) -> PoolStateUpdate {
+		fn changes<K: Ord + Copy>(
+			previous: &BTreeMap<K, U256>,
+			current: &BTreeMap<K, U256>,
+		) -> Vec<(K, U256)> {
+			current
+				.iter()
+				.filter(|(key, amount)| previous.get(key) != Some(amount))
+				.map(|(key, amount)| (*key, *amount))
+				.chain(
+					previous
+						.keys()
+						.filter(|key| !current.contains_key(key))
+						.map(|key| (*key, U256::zero())),
+				)
+				.collect()
+		}
+
+		let empty = PoolSnapshot::default();
+		let previous_snapshot = previous.unwrap_or(&empty);
+		PoolStateUpdate {
+			sequence_number,
+			is_snapshot: previous.is_none(),
+			base_asset: base_asset.into(),
+			quote_asset: quote_asset.into(),
+			limit_orders: AskBidMap {
+				asks: changes(&previous_snapshot.limit_orders.asks, &self.limit_orders.asks),
+				bids: changes(&previous_snapshot.limit_orders.bids, &self.limit_orders.bids),
+			}
+			.map(|levels| {
+				levels
+					.into_iter()
+					.map(|(tick, amount)| LimitOrderLevel { tick, amount })
+					.collect()
+			}),
+			range_orders: changes(&previous_snapshot.range_orders, &self.range_orders)
+				.into_iter()
+				.map(|((lower_tick, upper_tick), liquidity)| RangeOrderLevel {
+					range: lower_tick..upper_tick,
+					liquidity,
+				})
+				.collect(),
+			price: if previous_snapshot.price != self.price { self.price.clone() } else { None },
+		}
+	}
+}
+
 #[async_trait]
 impl RpcServer for RpcServerImpl {
 	/// Returns a deposit address
@@ -294,6 +550,29 @@ impl RpcServer for RpcServerImpl {
 			.await?)
 	}
 
+	/// Swaps part of the LP's free balance into another asset, crediting the output back to the
+	/// free balance.
+	async fn internal_swap(
+		&self,
+		amount: NumberOrHex,
+		from: Asset,
+		to: Asset,
+		min_output: NumberOrHex,
+		wait_for: Option<WaitFor>,
+	) -> RpcResult<ApiWaitForResult<SwapId>> {
+		Ok(self
+			.api
+			.lp_api()
+			.internal_swap(
+				try_parse_number_or_hex(amount)?,
+				from,
+				to,
+				try_parse_number_or_hex(min_output)?,
+				wait_for.unwrap_or_default(),
+			)
+			.await?)
+	}
+
 	/// Returns a list of all assets and their free balance in json format
 	async fn asset_balances(&self) -> RpcResult<BTreeMap<ForeignChain, Vec<AssetBalance>>> {
 		let cf_asset_balances = self
@@ -317,6 +596,20 @@ impl RpcServer for RpcServerImpl {
 		Ok(lp_asset_balances)
 	}
 
+	async fn set_range_order_policy(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		id: OrderIdJson,
+		policy: Option<RangeOrderPolicy>,
+	) -> RpcResult<Hash> {
+		Ok(self
+			.api
+			.lp_api()
+			.set_range_order_policy(base_asset, quote_asset, id.try_into()?, policy)
+			.await?)
+	}
+
 	async fn update_range_order(
 		&self,
 		base_asset: Asset,
@@ -373,6 +666,7 @@ impl RpcServer for RpcServerImpl {
 		amount_change: IncreaseOrDecrease<NumberOrHex>,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: Option<WaitFor>,
+		expiry_block: Option<BlockNumber>,
 	) -> RpcResult<ApiWaitForResult<Vec<LimitOrder>>> {
 		Ok(self
 			.api
@@ -385,6 +679,7 @@ impl RpcServer for RpcServerImpl {
 				tick,
 				amount_change.try_map(try_parse_number_or_hex)?,
 				dispatch_at,
+				expiry_block,
 				wait_for.unwrap_or_default(),
 			)
 			.await?)
@@ -400,6 +695,7 @@ impl RpcServer for RpcServerImpl {
 		sell_amount: NumberOrHex,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: Option<WaitFor>,
+		expiry_block: Option<BlockNumber>,
 	) -> RpcResult<ApiWaitForResult<Vec<LimitOrder>>> {
 		Ok(self
 			.api
@@ -412,6 +708,24 @@ impl RpcServer for RpcServerImpl {
 				tick,
 				try_parse_number_or_hex(sell_amount)?,
 				dispatch_at,
+				expiry_block,
+				wait_for.unwrap_or_default(),
+			)
+			.await?)
+	}
+
+	/// Applies all of the given order operations in a single extrinsic. Either all operations
+	/// succeed, or none are applied.
+	async fn batch_orders(
+		&self,
+		orders: Vec<OrderOperationJson>,
+		wait_for: Option<WaitFor>,
+	) -> RpcResult<ApiWaitForResult<OrderBatch>> {
+		Ok(self
+			.api
+			.lp_api()
+			.batch_orders(
+				orders.into_iter().map(TryInto::try_into).collect::<Result<Vec<_>, _>>()?,
 				wait_for.unwrap_or_default(),
 			)
 			.await?)
@@ -429,12 +743,13 @@ impl RpcServer for RpcServerImpl {
 	async fn get_open_swap_channels(&self) -> RpcResult<OpenSwapChannels> {
 		let api = self.api.query_api();
 
-		let (ethereum, bitcoin, polkadot) = tokio::try_join!(
+		let (ethereum, bitcoin, polkadot, assethub) = tokio::try_join!(
 			api.get_open_swap_channels::<Ethereum>(None),
 			api.get_open_swap_channels::<Bitcoin>(None),
 			api.get_open_swap_channels::<Polkadot>(None),
+			api.get_open_swap_channels::<Assethub>(None),
 		)?;
-		Ok(OpenSwapChannels { ethereum, bitcoin, polkadot })
+		Ok(OpenSwapChannels { ethereum, bitcoin, polkadot, assethub })
 	}
 
 	async fn request_redemption(
@@ -492,6 +807,90 @@ impl RpcServer for RpcServerImpl {
 
 		Ok(order_fills(state_chain_client.clone(), block).await?)
 	}
+
+	fn subscribe_pool_state(
+		&self,
+		mut sink: SubscriptionSink,
+		base_asset: Asset,
+		quote_asset: Asset,
+	) -> SubscriptionResult {
+		sink.accept()?;
+		let state_chain_client = self.api.state_chain_client.clone();
+		tokio::spawn(async move {
+			let mut finalized_block_stream = state_chain_client.finalized_block_stream().await;
+			let mut previous_snapshot = None;
+			let mut sequence_number = 0;
+			while let Some(block) = finalized_block_stream.next().await {
+				if let Err(option_error) = pool_snapshot(
+					&state_chain_client.base_rpc_client.raw_rpc_client,
+					base_asset,
+					quote_asset,
+					block.hash,
+				)
+				.await
+				.map_err(Some)
+				.and_then(|snapshot| {
+					let update = BlockUpdate::<PoolStateUpdate> {
+						block_hash: block.hash,
+						block_number: block.number,
+						data: snapshot.update_since(
+							previous_snapshot.as_ref(),
+							base_asset,
+							quote_asset,
+							sequence_number,
+						),
+					};
+					previous_snapshot = Some(snapshot);
+					sequence_number += 1;
+					match sink.send(&update) {
+						Ok(true) => Ok(()),
+						Ok(false) => Err(None),
+						Err(error) => Err(Some(jsonrpsee::core::Error::ParseError(error))),
+					}
+				}) {
+					if let Some(error) = option_error {
+						sink.close(error);
+					}
+					break
+				}
+			}
+		});
+
+		Ok(())
+	}
+}
+
+/// Aggregates the pool's orders, as returned by `cf_pool_orders`, and its price at the given block.
+async fn pool_snapshot<RawRpcClient: CustomApiClient + Send + Sync>(
+	raw_rpc_client: &RawRpcClient,
+	base_asset: Asset,
+	quote_asset: Asset,
+	block_hash: Hash,
+) -> Result<PoolSnapshot, jsonrpsee::core::Error> {
+	let (pool_orders, pool_price) = try_join!(
+		raw_rpc_client.cf_pool_orders(base_asset, quote_asset, None, Some(block_hash)),
+		raw_rpc_client.cf_pool_price_v2(base_asset, quote_asset, Some(block_hash)),
+	)?;
+
+	let mut snapshot = PoolSnapshot { price: Some(pool_price.price), ..Default::default() };
+	for (levels, limit_orders) in [
+		(&mut snapshot.limit_orders.asks, pool_orders.limit_orders.asks),
+		(&mut snapshot.limit_orders.bids, pool_orders.limit_orders.bids),
+	] {
+		for limit_order in limit_orders {
+			let amount = levels.entry(limit_order.tick).or_default();
+			*amount = amount.saturating_add(limit_order.sell_amount);
+		}
+	}
+	for range_order in pool_orders.range_orders {
+		let liquidity = snapshot
+			.range_orders
+			.entry((range_order.range.start, range_order.range.end))
+			.or_default();
+		*liquidity = liquidity.saturating_add(range_order.liquidity.into());
+	}
+
+	Ok(snapshot)
 }
 
 async fn order_fills<StateChainClient>(
@@ -685,6 +1084,8 @@ pub struct LPOptions {
 		help = "A path to a file that contains the LP's secret key for signing extrinsics."
 	)]
 	pub signing_key_file: PathBuf,
+	#[clap(flatten)]
+	pub server: RpcServerOptions,
 }
 
 #[tokio::main]
@@ -704,9 +1105,15 @@ async fn main() -> anyhow::Result<()> {
 
 	task_scope(|scope| {
 		async move {
-			let server = ServerBuilder::default().build(format!("0.0.0.0:{}", opts.port)).await?;
-			let server_addr = server.local_addr()?;
-			let server = server.start(RpcServerImpl::new(scope, opts).await?.into_rpc())?;
+			let server_options = opts.server.clone();
+			let (server_addr, server) = rpc_server::start_server(
+				scope,
+				opts.port,
+				&server_options,
+				EXTRINSIC_SUBMITTING_METHODS,
+				RpcServerImpl::new(scope, opts).await?.into_rpc(),
+			)
+			.await?;
 
 			log::info!("🎙 Server is listening on {server_addr}.");
 
@@ -3,10 +3,17 @@ use std::{str::FromStr, sync::Arc};
 use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
 use cf_chains::{
-	address::EncodedAddress, dot::PolkadotAccountId, evm::to_evm_address, AnyChain,
-	CcmChannelMetadata, ForeignChain,
+	address::EncodedAddress,
+	btc::vault_swap_encoding::{
+		decode_vault_swap_payload, encode_vault_swap_payload, VaultSwapParameters,
+	},
+	dot::PolkadotAccountId,
+	evm::{to_evm_address, VaultSwapCfParameters},
+	AnyChain, CcmChannelMetadata, ForeignChain,
+};
+use cf_primitives::{
+	AccountRole, Affiliates, Asset, AssetAmount, BasisPoints, ChannelId, EgressId, SemVer,
 };
-use cf_primitives::{AccountRole, Asset, BasisPoints, ChannelId, SemVer};
 use futures::FutureExt;
 use pallet_cf_governance::ExecutionMode;
 use pallet_cf_validator::MAX_LENGTH_FOR_VANITY_NAME;
@@ -16,7 +23,10 @@ use sp_consensus_grandpa::AuthorityId as GrandpaId;
 pub use sp_core::crypto::AccountId32;
 use sp_core::{ed25519::Public as EdPublic, sr25519::Public as SrPublic, Bytes, Pair, H256};
 pub use state_chain_runtime::chainflip::BlockUpdate;
-use state_chain_runtime::{opaque::SessionKeys, RuntimeCall};
+use state_chain_runtime::{
+	opaque::SessionKeys, AssethubInstance, BitcoinInstance, EthereumInstance, PolkadotInstance,
+	Runtime, RuntimeCall,
+};
 use zeroize::Zeroize;
 pub mod primitives {
 	pub use cf_primitives::*;
@@ -25,6 +35,7 @@ pub mod primitives {
 	pub type RedemptionAmount = pallet_cf_funding::RedemptionAmount<FlipBalance>;
 	pub use cf_chains::{
 		address::{EncodedAddress, ForeignChainAddress},
+		btc::vault_swap_encoding::VaultSwapParameters,
 		CcmChannelMetadata, CcmDepositMetadata,
 	};
 }
@@ -39,6 +50,7 @@ pub use chainflip_engine::state_chain_observer::client::{
 
 pub mod lp;
 pub mod queries;
+pub mod rpc_server;
 
 pub use chainflip_engine::settings;
 pub use chainflip_node::chain_spec::use_chainflip_account_id_encoding;
@@ -173,6 +185,56 @@ pub trait OperatorApi: SignedExtrinsicApi + RotateSessionKeysApi + AuctionPhaseA
 		Ok(tx_hash)
 	}
 
+	async fn schedule_retirement_redemption(
+		&self,
+		amount: primitives::RedemptionAmount,
+		address: EthereumAddress,
+		executor: Option<EthereumAddress>,
+	) -> Result<H256> {
+		let call = RuntimeCall::from(pallet_cf_funding::Call::schedule_retirement_redemption {
+			amount,
+			address,
+			executor,
+		});
+
+		let (tx_hash, ..) =
+			self.submit_signed_extrinsic_with_dry_run(call).await?.until_in_block().await?;
+
+		Ok(tx_hash)
+	}
+
+	async fn schedule_recurring_redemption(
+		&self,
+		floor: primitives::FlipBalance,
+		interval: primitives::BlockNumber,
+		address: EthereumAddress,
+		executor: Option<EthereumAddress>,
+	) -> Result<H256> {
+		let call = RuntimeCall::from(pallet_cf_funding::Call::schedule_recurring_redemption {
+			floor,
+			interval,
+			address,
+			executor,
+		});
+
+		let (tx_hash, ..) =
+			self.submit_signed_extrinsic_with_dry_run(call).await?.until_in_block().await?;
+
+		Ok(tx_hash)
+	}
+
+	async fn cancel_scheduled_redemptions(&self) -> Result<H256> {
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic_with_dry_run(RuntimeCall::from(
+				pallet_cf_funding::Call::cancel_scheduled_redemptions {},
+			))
+			.await?
+			.until_in_block()
+			.await?;
+
+		Ok(tx_hash)
+	}
+
 	async fn bind_redeem_address(&self, address: EthereumAddress) -> Result<H256> {
 		let (tx_hash, ..) = self
 			.submit_signed_extrinsic(pallet_cf_funding::Call::bind_redeem_address { address })
@@ -305,6 +367,19 @@ pub struct SwapDepositAddress {
 	pub source_chain_expiry_block: <AnyChain as cf_chains::Chain>::ChainBlockNumber,
 }
 
+pub struct BrokerFeeWithdrawal {
+	pub egress_id: EgressId,
+	pub egress_amount: AssetAmount,
+	pub egress_fee: AssetAmount,
+	pub destination_address: String,
+}
+
+/// The outcome of withdrawing all of a broker's fees. Skipped balances remain available.
+pub struct BrokerFeeWithdrawals {
+	pub withdrawn: Vec<BrokerFeeWithdrawal>,
+	pub skipped: Vec<(Asset, AssetAmount)>,
+}
+
 #[async_trait]
 pub trait BrokerApi: SignedExtrinsicApi {
 	async fn request_swap_deposit_address(
@@ -316,20 +391,52 @@ pub trait BrokerApi: SignedExtrinsicApi {
 		channel_metadata: Option<CcmChannelMetadata>,
 		boost_fee: Option<BasisPoints>,
 	) -> Result<SwapDepositAddress> {
-		let (_tx_hash, events, header, ..) = self
-			.submit_signed_extrinsic_with_dry_run(
-				pallet_cf_swapping::Call::request_swap_deposit_address {
+		self.request_swap_deposit_address_with_affiliates(
+			source_asset,
+			destination_asset,
+			destination_address,
+			broker_commission_bps,
+			Default::default(),
+			channel_metadata,
+			boost_fee,
+		)
+		.await
+	}
+	async fn request_swap_deposit_address_with_affiliates(
+		&self,
+		source_asset: Asset,
+		destination_asset: Asset,
+		destination_address: EncodedAddress,
+		broker_commission_bps: BasisPoints,
+		affiliates: Affiliates<AccountId32>,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+	) -> Result<SwapDepositAddress> {
+		let boost_fee = boost_fee.unwrap_or_default();
+		let call = if affiliates.is_empty() {
+			RuntimeCall::from(pallet_cf_swapping::Call::request_swap_deposit_address {
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission_bps,
+				channel_metadata,
+				boost_fee,
+			})
+		} else {
+			RuntimeCall::from(
+				pallet_cf_swapping::Call::request_swap_deposit_address_with_affiliates {
 					source_asset,
 					destination_asset,
 					destination_address,
 					broker_commission_bps,
+					affiliates,
 					channel_metadata,
-					boost_fee: boost_fee.unwrap_or_default(),
+					boost_fee,
 				},
 			)
-			.await?
-			.until_in_block()
-			.await?;
+		};
+		let (_tx_hash, events, header, ..) =
+			self.submit_signed_extrinsic_with_dry_run(call).await?.until_in_block().await?;
 
 		if let Some(state_chain_runtime::RuntimeEvent::Swapping(
 			pallet_cf_swapping::Event::SwapDepositAddressReady {
@@ -356,6 +463,248 @@ pub trait BrokerApi: SignedExtrinsicApi {
 			bail!("No SwapDepositAddressReady event was found");
 		}
 	}
+
+	/// Registers the address that the broker's fees on the address's chain are withdrawn to.
+	async fn register_withdrawal_address(&self, address: EncodedAddress) -> Result<H256> {
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic(pallet_cf_swapping::Call::register_withdrawal_address {
+				address,
+			})
+			.await
+			.until_in_block()
+			.await
+			.context("Registration of the withdrawal address failed.")?;
+		Ok(tx_hash)
+	}
+
+	/// Withdraws every fee balance that can be egressed to the registered withdrawal addresses.
+	async fn withdraw_all_fees(&self) -> Result<BrokerFeeWithdrawals> {
+		let (_tx_hash, events, ..) = self
+			.submit_signed_extrinsic_with_dry_run(pallet_cf_swapping::Call::withdraw_all {})
+			.await?
+			.until_in_block()
+			.await?;
+
+		let mut withdrawals = BrokerFeeWithdrawals { withdrawn: vec![], skipped: vec![] };
+		for event in events {
+			match event {
+				state_chain_runtime::RuntimeEvent::Swapping(
+					pallet_cf_swapping::Event::WithdrawalRequested {
+						egress_id,
+						egress_amount,
+						egress_fee,
+						destination_address,
+					},
+				) => withdrawals.withdrawn.push(BrokerFeeWithdrawal {
+					egress_id,
+					egress_amount,
+					egress_fee,
+					destination_address: destination_address.to_string(),
+				}),
+				state_chain_runtime::RuntimeEvent::Swapping(
+					pallet_cf_swapping::Event::WithdrawalSkipped { asset, amount, .. },
+				) => withdrawals.skipped.push((asset, amount)),
+				_ => {},
+			}
+		}
+		Ok(withdrawals)
+	}
+
+	/// Closes one of the broker's deposit channels before it expires.
+	async fn close_deposit_channel(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+	) -> Result<H256> {
+		let call = match chain {
+			ForeignChain::Ethereum => close_deposit_channel_call::<EthereumInstance>(channel_id),
+			ForeignChain::Polkadot => close_deposit_channel_call::<PolkadotInstance>(channel_id),
+			ForeignChain::Bitcoin => close_deposit_channel_call::<BitcoinInstance>(channel_id),
+			ForeignChain::Assethub => close_deposit_channel_call::<AssethubInstance>(channel_id),
+		};
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic_with_dry_run(call)
+			.await?
+			.until_in_block()
+			.await
+			.context("Closing the deposit channel failed.")?;
+		Ok(tx_hash)
+	}
+
+	/// Extends the lifetime of one of the broker's deposit channels by the given number of blocks
+	/// of its chain.
+	async fn extend_deposit_channel(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+		extension: u64,
+	) -> Result<H256> {
+		let call = match chain {
+			ForeignChain::Ethereum =>
+				extend_deposit_channel_call::<EthereumInstance>(channel_id, extension),
+			ForeignChain::Polkadot =>
+				extend_deposit_channel_call::<PolkadotInstance>(channel_id, extension),
+			ForeignChain::Bitcoin =>
+				extend_deposit_channel_call::<BitcoinInstance>(channel_id, extension),
+			ForeignChain::Assethub =>
+				extend_deposit_channel_call::<AssethubInstance>(channel_id, extension),
+		}?;
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic_with_dry_run(call)
+			.await?
+			.until_in_block()
+			.await
+			.context("Extending the deposit channel failed.")?;
+		Ok(tx_hash)
+	}
+
+	/// Makes one of the broker's swap channels sticky, so that it is renewed until it is closed.
+	async fn make_channel_sticky(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+	) -> Result<H256> {
+		let call = match chain {
+			ForeignChain::Ethereum => make_channel_sticky_call::<EthereumInstance>(channel_id),
+			ForeignChain::Polkadot => make_channel_sticky_call::<PolkadotInstance>(channel_id),
+			ForeignChain::Bitcoin => make_channel_sticky_call::<BitcoinInstance>(channel_id),
+			ForeignChain::Assethub => make_channel_sticky_call::<AssethubInstance>(channel_id),
+		};
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic_with_dry_run(call)
+			.await?
+			.until_in_block()
+			.await
+			.context("Making the deposit channel sticky failed.")?;
+		Ok(tx_hash)
+	}
+
+	/// Changes the destination of one of the broker's sticky channels.
+	async fn update_sticky_channel_destination(
+		&self,
+		chain: ForeignChain,
+		channel_id: ChannelId,
+		destination_asset: Asset,
+		destination_address: EncodedAddress,
+	) -> Result<H256> {
+		let call = match chain {
+			ForeignChain::Ethereum => update_sticky_channel_destination_call::<EthereumInstance>(
+				channel_id,
+				destination_asset,
+				destination_address,
+			),
+			ForeignChain::Polkadot => update_sticky_channel_destination_call::<PolkadotInstance>(
+				channel_id,
+				destination_asset,
+				destination_address,
+			),
+			ForeignChain::Bitcoin => update_sticky_channel_destination_call::<BitcoinInstance>(
+				channel_id,
+				destination_asset,
+				destination_address,
+			),
+			ForeignChain::Assethub => update_sticky_channel_destination_call::<AssethubInstance>(
+				channel_id,
+				destination_asset,
+				destination_address,
+			),
+		};
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic_with_dry_run(call)
+			.await?
+			.until_in_block()
+			.await
+			.context("Updating the sticky channel destination failed.")?;
+		Ok(tx_hash)
+	}
+
+	/// Encodes the parameters of a Bitcoin vault swap that is credited to this broker.
+	fn encode_btc_vault_swap_payload(
+		&self,
+		destination_asset: Asset,
+		destination_address: EncodedAddress,
+		min_output_amount: AssetAmount,
+		broker_commission_bps: BasisPoints,
+	) -> Result<Vec<u8>> {
+		encode_btc_vault_swap_payload(&VaultSwapParameters {
+			output_asset: destination_asset,
+			output_address: destination_address,
+			min_output_amount,
+			broker_id: self.account_id(),
+			broker_commission_bps,
+		})
+	}
+}
+
+fn close_deposit_channel_call<I: 'static>(channel_id: ChannelId) -> RuntimeCall
+where
+	Runtime: pallet_cf_ingress_egress::Config<I>,
+	RuntimeCall: From<pallet_cf_ingress_egress::Call<Runtime, I>>,
+{
+	pallet_cf_ingress_egress::Call::<Runtime, I>::close_deposit_channel { channel_id }.into()
+}
+
+fn extend_deposit_channel_call<I: 'static>(
+	channel_id: ChannelId,
+	extension: u64,
+) -> Result<RuntimeCall>
+where
+	Runtime: pallet_cf_ingress_egress::Config<I>,
+	RuntimeCall: From<pallet_cf_ingress_egress::Call<Runtime, I>>,
+{
+	Ok(pallet_cf_ingress_egress::Call::<Runtime, I>::extend_deposit_channel {
+		channel_id,
+		extension: extension
+			.try_into()
+			.map_err(|_| anyhow!("The extension of {extension} blocks is too long."))?,
+	}
+	.into())
+}
+
+fn make_channel_sticky_call<I: 'static>(channel_id: ChannelId) -> RuntimeCall
+where
+	Runtime: pallet_cf_ingress_egress::Config<I>,
+	RuntimeCall: From<pallet_cf_ingress_egress::Call<Runtime, I>>,
+{
+	pallet_cf_ingress_egress::Call::<Runtime, I>::make_channel_sticky { channel_id }.into()
+}
+
+fn update_sticky_channel_destination_call<I: 'static>(
+	channel_id: ChannelId,
+	destination_asset: Asset,
+	destination_address: EncodedAddress,
+) -> RuntimeCall
+where
+	Runtime: pallet_cf_ingress_egress::Config<I>,
+	RuntimeCall: From<pallet_cf_ingress_egress::Call<Runtime, I>>,
+{
+	pallet_cf_ingress_egress::Call::<Runtime, I>::update_sticky_channel_destination {
+		channel_id,
+		destination_asset,
+		destination_address,
+	}
+	.into()
+}
+
+/// Encodes the parameters of a Bitcoin vault swap into the data of the swap transaction's
+/// `OP_RETURN` output.
+pub fn encode_btc_vault_swap_payload(
+	parameters: &VaultSwapParameters<AccountId32>,
+) -> Result<Vec<u8>> {
+	encode_vault_swap_payload(parameters)
+		.map_err(|e| anyhow!("Unable to encode vault swap payload: {e:?}"))
+}
+
+/// Encodes the `cfParameters` to pass to the Vault contract's swap functions to buy exactly
+/// `output_amount` of the destination asset. The swapped amount is then the maximum input, and any
+/// unused input is refunded to the sender.
+pub fn encode_exact_output_swap_cf_parameters(output_amount: AssetAmount) -> Vec<u8> {
+	codec::Encode::encode(&VaultSwapCfParameters::ExactOutput { output_amount })
+}
+
+/// Decodes the data of a Bitcoin vault swap transaction's `OP_RETURN` output.
+pub fn decode_btc_vault_swap_payload(payload: &[u8]) -> Result<VaultSwapParameters<AccountId32>> {
+	decode_vault_swap_payload(payload).map_err(|e| anyhow!("Invalid vault swap payload: {e:?}"))
 }
 
 /// Sanitize the given address (hex or base58) and turn it into a EncodedAddress of the given
@@ -366,6 +715,8 @@ pub fn clean_foreign_chain_address(chain: ForeignChain, address: &str) -> Result
 		ForeignChain::Polkadot =>
 			EncodedAddress::Dot(PolkadotAccountId::from_str(address).map(|id| *id.aliased_ref())?),
 		ForeignChain::Bitcoin => EncodedAddress::Btc(address.as_bytes().to_vec()),
+		ForeignChain::Assethub =>
+			EncodedAddress::Hub(PolkadotAccountId::from_str(address).map(|id| *id.aliased_ref())?),
 	})
 }
 
@@ -558,4 +909,23 @@ mod tests {
 			);
 		}
 	}
+
+	#[test]
+	fn btc_vault_swap_payload_round_trips() {
+		let parameters = VaultSwapParameters {
+			output_asset: Asset::Dot,
+			output_address: clean_foreign_chain_address(
+				ForeignChain::Polkadot,
+				"126PaS7kDWTdtiojd556gD4ZPcxj7KbjrMJj7xZ5i6XKfARE",
+			)
+			.unwrap(),
+			min_output_amount: 10_000_000_000,
+			broker_id: AccountId32::new([0xbb; 32]),
+			broker_commission_bps: 100,
+		};
+
+		let payload = encode_btc_vault_swap_payload(&parameters).unwrap();
+		assert_eq!(decode_btc_vault_swap_payload(&payload).unwrap(), parameters);
+		assert!(decode_btc_vault_swap_payload(&payload[1..]).is_err());
+	}
 }
@@ -5,12 +5,14 @@ pub use cf_amm::{
 	range_orders::Liquidity,
 };
 use cf_chains::address::EncodedAddress;
-use cf_primitives::{Asset, AssetAmount, BasisPoints, BlockNumber, EgressId};
+use cf_primitives::{Asset, AssetAmount, BasisPoints, BlockNumber, EgressId, SwapId};
 use chainflip_engine::state_chain_observer::client::{
 	extrinsic_api::signed::{SignedExtrinsicApi, UntilInBlock, WaitFor, WaitForResult},
 	StateChainClient,
 };
-use pallet_cf_pools::{AssetsMap, IncreaseOrDecrease, OrderId, RangeOrderSize};
+use pallet_cf_pools::{
+	AssetsMap, IncreaseOrDecrease, OrderId, OrderOperation, RangeOrderPolicy, RangeOrderSize,
+};
 use serde::{Deserialize, Serialize};
 use sp_core::{H256, U256};
 use state_chain_runtime::RuntimeCall;
@@ -74,6 +76,12 @@ pub mod types {
 		pub bought_amount: U256,
 		pub sell_amount_change: Option<IncreaseOrDecrease<U256>>,
 	}
+
+	#[derive(Serialize, Deserialize, Clone)]
+	pub struct OrderBatch {
+		pub range_orders: Vec<RangeOrder>,
+		pub limit_orders: Vec<LimitOrder>,
+	}
 }
 
 fn collect_range_order_returns(
@@ -256,6 +264,67 @@ pub trait LpApi: SignedExtrinsicApi {
 		})
 	}
 
+	async fn internal_swap(
+		&self,
+		amount: AssetAmount,
+		from: Asset,
+		to: Asset,
+		min_output: AssetAmount,
+		wait_for: WaitFor,
+	) -> Result<ApiWaitForResult<SwapId>> {
+		if amount == 0 {
+			bail!("Swap amount must be greater than 0");
+		}
+
+		let wait_for_result = self
+			.submit_signed_extrinsic_wait_for(
+				pallet_cf_swapping::Call::internal_swap { from, to, amount, min_output },
+				wait_for,
+			)
+			.await?;
+
+		Ok(match wait_for_result {
+			WaitForResult::TransactionHash(tx_hash) => return Ok(ApiWaitForResult::TxHash(tx_hash)),
+			WaitForResult::Details(details) => {
+				let (tx_hash, events, ..) = details;
+				let swap_id = events
+					.into_iter()
+					.find_map(|event| match event {
+						state_chain_runtime::RuntimeEvent::Swapping(
+							pallet_cf_swapping::Event::InternalSwapScheduled { swap_id, .. },
+						) => Some(swap_id),
+						_ => None,
+					})
+					.ok_or_else(|| anyhow::anyhow!("No InternalSwapScheduled event was found"))?;
+
+				ApiWaitForResult::TxDetails { tx_hash, response: swap_id }
+			},
+		})
+	}
+
+	async fn set_range_order_policy(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		id: OrderId,
+		policy: Option<RangeOrderPolicy>,
+	) -> Result<H256> {
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic(RuntimeCall::from(
+				pallet_cf_pools::Call::set_range_order_policy {
+					base_asset,
+					quote_asset,
+					id,
+					policy,
+				},
+			))
+			.await
+			.until_in_block()
+			.await
+			.context("Setting the range order policy failed.")?;
+		Ok(tx_hash)
+	}
+
 	async fn update_range_order(
 		&self,
 		base_asset: Asset,
@@ -318,16 +387,28 @@ pub trait LpApi: SignedExtrinsicApi {
 		option_tick: Option<Tick>,
 		amount_change: IncreaseOrDecrease<AssetAmount>,
 		dispatch_at: Option<BlockNumber>,
+		expiry_block: Option<BlockNumber>,
 		wait_for: WaitFor,
 	) -> Result<ApiWaitForResult<Vec<types::LimitOrder>>> {
 		self.scheduled_or_immediate(
-			pallet_cf_pools::Call::update_limit_order {
-				base_asset,
-				quote_asset,
-				side,
-				id,
-				option_tick,
-				amount_change,
+			match expiry_block {
+				None => pallet_cf_pools::Call::update_limit_order {
+					base_asset,
+					quote_asset,
+					side,
+					id,
+					option_tick,
+					amount_change,
+				},
+				expiry_block => pallet_cf_pools::Call::update_limit_order_with_expiry {
+					base_asset,
+					quote_asset,
+					side,
+					id,
+					option_tick,
+					amount_change,
+					expiry_block,
+				},
 			},
 			dispatch_at,
 			wait_for,
@@ -345,16 +426,28 @@ pub trait LpApi: SignedExtrinsicApi {
 		option_tick: Option<Tick>,
 		sell_amount: AssetAmount,
 		dispatch_at: Option<BlockNumber>,
+		expiry_block: Option<BlockNumber>,
 		wait_for: WaitFor,
 	) -> Result<ApiWaitForResult<Vec<types::LimitOrder>>> {
 		self.scheduled_or_immediate(
-			pallet_cf_pools::Call::set_limit_order {
-				base_asset,
-				quote_asset,
-				side,
-				id,
-				option_tick,
-				sell_amount,
+			match expiry_block {
+				None => pallet_cf_pools::Call::set_limit_order {
+					base_asset,
+					quote_asset,
+					side,
+					id,
+					option_tick,
+					sell_amount,
+				},
+				expiry_block => pallet_cf_pools::Call::set_limit_order_with_expiry {
+					base_asset,
+					quote_asset,
+					side,
+					id,
+					option_tick,
+					sell_amount,
+					expiry_block,
+				},
 			},
 			dispatch_at,
 			wait_for,
@@ -362,6 +455,32 @@ pub trait LpApi: SignedExtrinsicApi {
 		.await
 	}
 
+	async fn batch_orders(
+		&self,
+		orders: Vec<OrderOperation<BlockNumber>>,
+		wait_for: WaitFor,
+	) -> Result<ApiWaitForResult<types::OrderBatch>> {
+		if orders.is_empty() {
+			bail!("At least one order must be given");
+		}
+
+		Ok(into_api_wait_for_result(
+			self.submit_signed_extrinsic_wait_for(
+				pallet_cf_pools::Call::batch_orders {
+					orders: orders
+						.try_into()
+						.map_err(|_| anyhow::anyhow!("Too many orders in a single batch"))?,
+				},
+				wait_for,
+			)
+			.await?,
+			|events| types::OrderBatch {
+				range_orders: collect_range_order_returns(events.clone()),
+				limit_orders: collect_limit_order_returns(events),
+			},
+		))
+	}
+
 	async fn scheduled_or_immediate(
 		&self,
 		call: pallet_cf_pools::Call<state_chain_runtime::Runtime>,
@@ -0,0 +1,624 @@
+//! Access control for the json-rpc servers run by the api binaries.
+//!
+//! When configured, every request must present an API key in an `Authorization: Bearer <key>` or
+//! an `x-api-key` header. Keys are stored in the config file as hex-encoded sha256 hashes, and
+//! each key can be restricted to a set of rpc methods and to a number of requests per minute.
+//! Calls to methods that submit signed extrinsics are written to an audit log together with the
+//! key that made them. TLS can be terminated by the server itself.
+
+use std::{
+	collections::{BTreeSet, HashMap},
+	error::Error as StdError,
+	fs::{File, OpenOptions},
+	future::Future,
+	io::{BufReader, Write},
+	net::SocketAddr,
+	path::{Path, PathBuf},
+	pin::Pin,
+	sync::{Arc, Mutex},
+	task::{Context as TaskContext, Poll},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use hyper::{header::HeaderValue, Body, Request, Response, StatusCode};
+use jsonrpsee::server::{RpcModule, ServerBuilder, ServerHandle};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::{
+	rustls::{Certificate, PrivateKey, ServerConfig},
+	TlsAcceptor,
+};
+use tower::{Layer, Service};
+use utilities::task_scope::Scope;
+
+const API_KEY_HEADER: &str = "x-api-key";
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// The target used for audit log entries emitted via `tracing`.
+pub const AUDIT_LOG_TARGET: &str = "rpc_audit";
+
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct RpcServerOptions {
+	#[clap(
+		long = "api_keys_file",
+		help = "A path to a toml file containing the hashed API keys that may access the server. If not set, the server accepts unauthenticated requests."
+	)]
+	pub api_keys_file: Option<PathBuf>,
+	#[clap(
+		long = "audit_log_file",
+		requires = "api_keys_file",
+		help = "A path to a file to which every extrinsic submitted through the server is appended, along with the API key that submitted it."
+	)]
+	pub audit_log_file: Option<PathBuf>,
+	#[clap(
+		long = "tls.certificate_file",
+		requires = "tls_private_key_file",
+		help = "A path to a PEM encoded certificate chain. If set, the server only accepts TLS connections."
+	)]
+	pub tls_certificate_file: Option<PathBuf>,
+	#[clap(
+		long = "tls.private_key_file",
+		requires = "tls_certificate_file",
+		help = "A path to the PEM encoded private key for the TLS certificate."
+	)]
+	pub tls_private_key_file: Option<PathBuf>,
+}
+
+/// The contents of the API keys file.
+///
+/// ```toml
+/// [[keys]]
+/// name = "market-maker"
+/// # echo -n "<key>" | sha256sum
+/// key_hash = "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+/// allowed_methods = ["lp_asset_balances", "lp_set_limit_order"]
+/// max_requests_per_minute = 600
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeysConfig {
+	pub keys: Vec<ApiKeyConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyConfig {
+	/// Identifies the key in logs. Never the key itself.
+	pub name: String,
+	/// The hex-encoded sha256 hash of the key.
+	pub key_hash: String,
+	/// The rpc methods the key may call, including the namespace. All methods are allowed if not
+	/// set.
+	#[serde(default)]
+	pub allowed_methods: Option<BTreeSet<String>>,
+	/// Unlimited if not set.
+	#[serde(default)]
+	pub max_requests_per_minute: Option<u32>,
+}
+
+impl ApiKeysConfig {
+	pub fn load(path: &Path) -> Result<Self> {
+		let config: Self = toml::from_str(
+			&std::fs::read_to_string(path)
+				.with_context(|| format!("Failed to read API keys file {}", path.display()))?,
+		)
+		.with_context(|| format!("Failed to parse API keys file {}", path.display()))?;
+		if config.keys.is_empty() {
+			return Err(anyhow!("API keys file {} doesn't contain any keys", path.display()))
+		}
+		Ok(config)
+	}
+}
+
+struct ApiKey {
+	config: ApiKeyConfig,
+	/// The start of the current rate limiting window, and the number of calls made in it.
+	window: Mutex<(Instant, u32)>,
+}
+
+impl ApiKey {
+	fn allows(&self, method: &str) -> bool {
+		self.config
+			.allowed_methods
+			.as_ref()
+			.map_or(true, |allowed_methods| allowed_methods.contains(method))
+	}
+
+	/// Method calls made over an open websocket bypass the http middleware, so they can't be
+	/// checked against the allow-list, counted towards the rate limit or audited. Only keys with
+	/// none of these restrictions may open websocket connections.
+	fn allows_websockets(&self, audited_methods: &BTreeSet<&'static str>) -> bool {
+		self.config.allowed_methods.is_none() &&
+			self.config.max_requests_per_minute.is_none() &&
+			!audited_methods.iter().any(|method| self.allows(method))
+	}
+
+	fn try_consume(&self, calls: u32) -> bool {
+		if let Some(limit) = self.config.max_requests_per_minute {
+			let mut window = self.window.lock().unwrap();
+			let now = Instant::now();
+			if now.duration_since(window.0) >= RATE_LIMIT_WINDOW {
+				*window = (now, 0);
+			}
+			if window.1.saturating_add(calls) > limit {
+				return false
+			}
+			window.1 += calls;
+		}
+		true
+	}
+}
+
+struct AuditLog {
+	file: Option<Mutex<File>>,
+}
+
+impl AuditLog {
+	fn record(&self, api_key: &str, method: &str, params: &Value, outcome: &Value) {
+		tracing::info!(
+			target: AUDIT_LOG_TARGET,
+			api_key,
+			method,
+			%params,
+			%outcome,
+			"Extrinsic submitted"
+		);
+		if let Some(file) = &self.file {
+			let entry = serde_json::json!({
+				"timestamp": SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+				"api_key": api_key,
+				"method": method,
+				"params": params,
+				"outcome": outcome,
+			});
+			if let Err(error) = writeln!(file.lock().unwrap(), "{entry}") {
+				tracing::error!("Failed to write to audit log: {error}");
+			}
+		}
+	}
+}
+
+struct Authenticator {
+	keys: HashMap<[u8; 32], ApiKey>,
+	audited_methods: BTreeSet<&'static str>,
+	audit_log: AuditLog,
+}
+
+impl Authenticator {
+	fn new(
+		config: ApiKeysConfig,
+		audited_methods: &[&'static str],
+		audit_log_file: Option<&Path>,
+	) -> Result<Self> {
+		let mut keys = HashMap::new();
+		for key in config.keys {
+			let hash: [u8; 32] = hex::decode(key.key_hash.trim_start_matches("0x"))
+				.ok()
+				.and_then(|hash| hash.try_into().ok())
+				.ok_or_else(|| anyhow!("Key hash of API key {} is not a sha256 hash", key.name))?;
+			if keys
+				.insert(hash, ApiKey { config: key, window: Mutex::new((Instant::now(), 0)) })
+				.is_some()
+			{
+				return Err(anyhow!("Duplicate API key hash in API keys file"))
+			}
+		}
+		Ok(Self {
+			keys,
+			audited_methods: audited_methods.iter().copied().collect(),
+			audit_log: AuditLog {
+				file: audit_log_file
+					.map(|path| {
+						OpenOptions::new().create(true).append(true).open(path).with_context(|| {
+							format!("Failed to open audit log file {}", path.display())
+						})
+					})
+					.transpose()?
+					.map(Mutex::new),
+			},
+		})
+	}
+
+	fn authenticate(&self, request: &Request<Body>) -> Option<&ApiKey> {
+		let headers = request.headers();
+		headers
+			.get(API_KEY_HEADER)
+			.and_then(|value| value.to_str().ok())
+			.or_else(|| {
+				headers
+					.get(hyper::header::AUTHORIZATION)
+					.and_then(|value| value.to_str().ok())
+					.and_then(|value| value.strip_prefix("Bearer "))
+			})
+			.and_then(|key| self.keys.get(&<[u8; 32]>::from(Sha256::digest(key.trim()))))
+	}
+}
+
+/// A json-rpc call extracted from a request body.
+struct Call {
+	id: Value,
+	method: String,
+	params: Value,
+}
+
+/// Returns the calls in a single or batch json-rpc request. Requests that can't be parsed yield no
+/// calls and are left for the server to reject.
+fn calls_in(body: &[u8]) -> Vec<Call> {
+	let into_call = |value: Value| -> Option<Call> {
+		match value {
+			Value::Object(mut object) => Some(Call {
+				id: object.remove("id").unwrap_or(Value::Null),
+				method: object.remove("method")?.as_str()?.to_owned(),
+				params: object.remove("params").unwrap_or(Value::Null),
+			}),
+			_ => None,
+		}
+	};
+	match serde_json::from_slice(body) {
+		Ok(Value::Array(values)) => values.into_iter().filter_map(into_call).collect(),
+		Ok(value) => into_call(value).into_iter().collect(),
+		Err(_) => Vec::new(),
+	}
+}
+
+/// Returns the `result` or `error` of each response in a single or batch json-rpc response, by id.
+fn outcomes_in(body: &[u8]) -> Vec<(Value, Value)> {
+	let into_outcome = |value: Value| -> Option<(Value, Value)> {
+		match value {
+			Value::Object(mut object) => Some((
+				object.remove("id")?,
+				object.remove("result").or_else(|| object.remove("error"))?,
+			)),
+			_ => None,
+		}
+	};
+	match serde_json::from_slice(body) {
+		Ok(Value::Array(values)) => values.into_iter().filter_map(into_outcome).collect(),
+		Ok(value) => into_outcome(value).into_iter().collect(),
+		Err(_) => Vec::new(),
+	}
+}
+
+fn is_websocket_upgrade(request: &Request<Body>) -> bool {
+	request
+		.headers()
+		.get(hyper::header::UPGRADE)
+		.is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"websocket"))
+}
+
+fn reject(status: StatusCode, reason: &'static str) -> Response<Body> {
+	let mut response = Response::new(Body::from(reason));
+	*response.status_mut() = status;
+	response
+		.headers_mut()
+		.insert(hyper::header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+	response
+}
+
+#[derive(Clone)]
+struct ApiKeyAuthLayer {
+	authenticator: Arc<Authenticator>,
+}
+
+impl<S> Layer<S> for ApiKeyAuthLayer {
+	type Service = ApiKeyAuth<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		ApiKeyAuth { authenticator: self.authenticator.clone(), inner }
+	}
+}
+
+#[derive(Clone)]
+struct ApiKeyAuth<S> {
+	authenticator: Arc<Authenticator>,
+	inner: S,
+}
+
+type BoxError = Box<dyn StdError + Send + Sync + 'static>;
+
+impl<S> Service<Request<Body>> for ApiKeyAuth<S>
+where
+	S: Service<Request<Body>, Response = Response<Body>, Error = BoxError> + Clone + Send + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = Response<Body>;
+	type Error = BoxError;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	fn call(&mut self, request: Request<Body>) -> Self::Future {
+		let authenticator = self.authenticator.clone();
+		// The inner service has been driven to readiness, so use it and leave the clone behind.
+		let clone = self.inner.clone();
+		let mut inner = std::mem::replace(&mut self.inner, clone);
+
+		Box::pin(async move {
+			let api_key = match authenticator.authenticate(&request) {
+				Some(api_key) => api_key,
+				None => return Ok(reject(StatusCode::UNAUTHORIZED, "Missing or unknown API key.")),
+			};
+
+			if is_websocket_upgrade(&request) {
+				if !api_key.allows_websockets(&authenticator.audited_methods) {
+					return Ok(reject(
+						StatusCode::FORBIDDEN,
+						"This API key is restricted, rate limited or audited and can only be used over http.",
+					))
+				}
+				tracing::debug!(api_key = %api_key.config.name, "Websocket connection opened");
+				return inner.call(request).await
+			}
+
+			let (parts, body) = request.into_parts();
+			let body = hyper::body::to_bytes(body).await?;
+			let calls = calls_in(&body);
+
+			if !calls.iter().all(|call| api_key.allows(&call.method)) {
+				return Ok(reject(
+					StatusCode::FORBIDDEN,
+					"This API key is not allowed to call the requested method.",
+				))
+			}
+			if !api_key.try_consume(calls.len().max(1) as u32) {
+				return Ok(reject(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded."))
+			}
+
+			let audited_calls = calls
+				.into_iter()
+				.filter(|call| authenticator.audited_methods.contains(call.method.as_str()))
+				.collect::<Vec<_>>();
+
+			let response = inner.call(Request::from_parts(parts, Body::from(body))).await?;
+
+			if audited_calls.is_empty() {
+				return Ok(response)
+			}
+
+			let (parts, body) = response.into_parts();
+			let body = hyper::body::to_bytes(body).await?;
+			let outcomes = outcomes_in(&body);
+			for call in audited_calls {
+				authenticator.audit_log.record(
+					&api_key.config.name,
+					&call.method,
+					&call.params,
+					outcomes
+						.iter()
+						.find(|(id, _)| *id == call.id)
+						.map(|(_, outcome)| outcome)
+						.unwrap_or(&Value::Null),
+				);
+			}
+			Ok(Response::from_parts(parts, Body::from(body)))
+		})
+	}
+}
+
+fn load_tls_acceptor(certificate_file: &Path, private_key_file: &Path) -> Result<TlsAcceptor> {
+	let certificates =
+		rustls_pemfile::certs(&mut BufReader::new(File::open(certificate_file).with_context(
+			|| format!("Failed to open TLS certificate file {}", certificate_file.display()),
+		)?))?
+		.into_iter()
+		.map(Certificate)
+		.collect::<Vec<_>>();
+
+	let private_key =
+		rustls_pemfile::read_all(&mut BufReader::new(File::open(private_key_file).with_context(
+			|| format!("Failed to open TLS private key file {}", private_key_file.display()),
+		)?))?
+		.into_iter()
+		.find_map(|item| match item {
+			rustls_pemfile::Item::PKCS8Key(key) |
+			rustls_pemfile::Item::RSAKey(key) |
+			rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+			_ => None,
+		})
+		.ok_or_else(|| anyhow!("No private key found in {}", private_key_file.display()))?;
+
+	Ok(TlsAcceptor::from(Arc::new(
+		ServerConfig::builder()
+			.with_safe_defaults()
+			.with_no_client_auth()
+			.with_single_cert(certificates, private_key)
+			.context("Invalid TLS certificate or private key")?,
+	)))
+}
+
+/// Accepts TLS connections and forwards the decrypted streams to the rpc server, which only
+/// listens on the loopback interface when TLS is enabled.
+async fn terminate_tls(
+	listener: TcpListener,
+	acceptor: TlsAcceptor,
+	server_addr: SocketAddr,
+) -> Result<()> {
+	loop {
+		let (stream, remote_addr) = listener.accept().await?;
+		let acceptor = acceptor.clone();
+		tokio::spawn(async move {
+			let result: Result<()> = async {
+				let mut tls_stream = acceptor.accept(stream).await?;
+				let mut server_stream = TcpStream::connect(server_addr).await?;
+				tokio::io::copy_bidirectional(&mut tls_stream, &mut server_stream).await?;
+				Ok(())
+			}
+			.await;
+			if let Err(error) = result {
+				tracing::debug!("TLS connection from {remote_addr} closed: {error}");
+			}
+		});
+	}
+}
+
+/// Starts an rpc server on the given port with the access controls set in `options`.
+/// `audited_methods` are the methods that submit signed extrinsics. Returns the address the
+/// server accepts connections on.
+pub async fn start_server<RpcContext: Send + Sync + 'static>(
+	scope: &Scope<'_, anyhow::Error>,
+	port: u16,
+	options: &RpcServerOptions,
+	audited_methods: &[&'static str],
+	module: RpcModule<RpcContext>,
+) -> Result<(SocketAddr, ServerHandle)> {
+	let auth_layer = options
+		.api_keys_file
+		.as_deref()
+		.map(|api_keys_file| {
+			Ok::<_, anyhow::Error>(ApiKeyAuthLayer {
+				authenticator: Arc::new(Authenticator::new(
+					ApiKeysConfig::load(api_keys_file)?,
+					audited_methods,
+					options.audit_log_file.as_deref(),
+				)?),
+			})
+		})
+		.transpose()?;
+
+	let tls_acceptor = match (&options.tls_certificate_file, &options.tls_private_key_file) {
+		(Some(certificate_file), Some(private_key_file)) =>
+			Some(load_tls_acceptor(certificate_file, private_key_file)?),
+		_ => None,
+	};
+
+	let server = ServerBuilder::default()
+		.set_middleware(tower::ServiceBuilder::new().option_layer(auth_layer))
+		.build(if tls_acceptor.is_some() {
+			"127.0.0.1:0".to_string()
+		} else {
+			format!("0.0.0.0:{port}")
+		})
+		.await?;
+	let server_addr = server.local_addr()?;
+	let handle = server.start(module)?;
+
+	if let Some(tls_acceptor) = tls_acceptor {
+		let listener = TcpListener::bind(format!("0.0.0.0:{port}")).await?;
+		let listen_addr = listener.local_addr()?;
+		scope.spawn(terminate_tls(listener, tls_acceptor, server_addr));
+		Ok((listen_addr, handle))
+	} else {
+		Ok((server_addr, handle))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const KEY: &str = "secret";
+
+	fn authenticator(
+		allowed_methods: Option<&[&str]>,
+		max_requests_per_minute: Option<u32>,
+	) -> Authenticator {
+		authenticator_auditing(allowed_methods, max_requests_per_minute, &["lp_set_limit_order"])
+	}
+
+	fn authenticator_auditing(
+		allowed_methods: Option<&[&str]>,
+		max_requests_per_minute: Option<u32>,
+		audited_methods: &[&'static str],
+	) -> Authenticator {
+		Authenticator::new(
+			ApiKeysConfig {
+				keys: vec![ApiKeyConfig {
+					name: "test".to_string(),
+					key_hash: hex::encode(Sha256::digest(KEY)),
+					allowed_methods: allowed_methods
+						.map(|methods| methods.iter().map(|method| method.to_string()).collect()),
+					max_requests_per_minute,
+				}],
+			},
+			audited_methods,
+			None,
+		)
+		.unwrap()
+	}
+
+	fn request_with_header(name: &str, value: &str) -> Request<Body> {
+		Request::builder().header(name, value).body(Body::empty()).unwrap()
+	}
+
+	#[test]
+	fn api_keys_are_matched_by_hash() {
+		let authenticator = authenticator(None, None);
+
+		assert!(authenticator.authenticate(&request_with_header(API_KEY_HEADER, KEY)).is_some());
+		assert!(authenticator
+			.authenticate(&request_with_header("authorization", &format!("Bearer {KEY}")))
+			.is_some());
+		assert!(authenticator
+			.authenticate(&request_with_header(API_KEY_HEADER, "wrong"))
+			.is_none());
+		assert!(authenticator.authenticate(&Request::new(Body::empty())).is_none());
+	}
+
+	#[test]
+	fn method_allow_lists_and_rate_limits_are_applied_per_key() {
+		let authenticator = authenticator(Some(&["lp_asset_balances"]), Some(3));
+		let api_key =
+			authenticator.authenticate(&request_with_header(API_KEY_HEADER, KEY)).unwrap();
+
+		assert!(api_key.allows("lp_asset_balances"));
+		assert!(!api_key.allows("lp_set_limit_order"));
+
+		assert!(api_key.try_consume(2));
+		assert!(!api_key.try_consume(2));
+		assert!(api_key.try_consume(1));
+		assert!(!api_key.try_consume(1));
+	}
+
+	#[test]
+	fn only_unrestricted_keys_can_open_websockets() {
+		let allows_websockets = |authenticator: Authenticator| {
+			authenticator
+				.authenticate(&request_with_header(API_KEY_HEADER, KEY))
+				.unwrap()
+				.allows_websockets(&authenticator.audited_methods)
+		};
+
+		assert!(allows_websockets(authenticator_auditing(None, None, &[])));
+		assert!(!allows_websockets(authenticator_auditing(None, Some(100), &[])));
+		assert!(!allows_websockets(authenticator_auditing(
+			Some(&["lp_asset_balances"]),
+			None,
+			&[]
+		)));
+		// Keys that may submit audited extrinsics can't use websockets.
+		assert!(!allows_websockets(authenticator(None, None)));
+		assert!(!allows_websockets(authenticator(Some(&["lp_asset_balances"]), None)));
+	}
+
+	#[test]
+	fn calls_and_outcomes_are_extracted_from_batches() {
+		let calls = calls_in(
+			br#"[
+				{"jsonrpc":"2.0","id":1,"method":"lp_asset_balances"},
+				{"jsonrpc":"2.0","id":2,"method":"lp_set_limit_order","params":["Eth"]}
+			]"#,
+		);
+		assert_eq!(
+			calls.iter().map(|call| call.method.as_str()).collect::<Vec<_>>(),
+			vec!["lp_asset_balances", "lp_set_limit_order"]
+		);
+		assert_eq!(calls[1].params, serde_json::json!(["Eth"]));
+		assert!(calls_in(b"not json").is_empty());
+
+		assert_eq!(
+			outcomes_in(
+				br#"[
+					{"jsonrpc":"2.0","id":1,"result":"0x1234"},
+					{"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"failed"}}
+				]"#
+			),
+			vec![
+				(serde_json::json!(1), serde_json::json!("0x1234")),
+				(serde_json::json!(2), serde_json::json!({"code":-32000,"message":"failed"})),
+			]
+		);
+	}
+}
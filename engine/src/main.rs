@@ -188,12 +188,24 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				);
 				DotRetryRpcClient::new(scope, settings.dot.nodes, expected_dot_genesis_hash)?
 			};
+			let hub_client = {
+				let expected_hub_genesis_hash = PolkadotHash::from(
+					state_chain_client
+						.storage_value::<pallet_cf_environment::AssethubGenesisHash<state_chain_runtime::Runtime>>(
+							state_chain_client.latest_finalized_block().hash,
+						)
+						.await
+						.expect(STATE_CHAIN_CONNECTION),
+				);
+				DotRetryRpcClient::new(scope, settings.hub.nodes, expected_hub_genesis_hash)?
+			};
 
 			witness::start::start(
 				scope,
 				eth_client.clone(),
 				btc_client.clone(),
 				dot_client.clone(),
+				hub_client.clone(),
 				state_chain_client.clone(),
 				state_chain_stream.clone(),
 				unfinalised_state_chain_stream.clone(),
@@ -207,6 +219,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				eth_client,
 				dot_client,
 				btc_client,
+				hub_client,
 				eth_multisig_client,
 				dot_multisig_client,
 				btc_multisig_client,
@@ -188,6 +188,8 @@ pub struct Settings {
 	pub eth: Eth,
 	pub dot: Dot,
 	pub btc: Btc,
+	// Asset Hub is a Polkadot parachain, so its nodes are configured exactly like Polkadot's.
+	pub hub: Dot,
 
 	pub health_check: Option<HealthCheck>,
 	pub prometheus: Option<Prometheus>,
@@ -232,6 +234,19 @@ pub struct DotOptions {
 	pub dot_backup_http_endpoint: Option<String>,
 }
 
+#[derive(Parser, Debug, Clone, Default)]
+pub struct HubOptions {
+	#[clap(long = "hub.rpc.ws_endpoint")]
+	pub hub_ws_endpoint: Option<String>,
+	#[clap(long = "hub.rpc.http_endpoint")]
+	pub hub_http_endpoint: Option<String>,
+
+	#[clap(long = "hub.backup_rpc.ws_endpoint")]
+	pub hub_backup_ws_endpoint: Option<String>,
+	#[clap(long = "hub.backup_rpc.http_endpoint")]
+	pub hub_backup_http_endpoint: Option<String>,
+}
+
 #[derive(Parser, Debug, Clone, Default)]
 pub struct BtcOptions {
 	#[clap(long = "btc.rpc.http_endpoint")]
@@ -283,6 +298,9 @@ pub struct CommandLineOptions {
 	#[clap(flatten)]
 	pub btc_opts: BtcOptions,
 
+	#[clap(flatten)]
+	pub hub_opts: HubOptions,
+
 	// Health Check Settings
 	#[clap(long = "health_check.hostname")]
 	pub health_check_hostname: Option<String>,
@@ -319,6 +337,7 @@ impl Default for CommandLineOptions {
 			eth_opts: EthOptions::default(),
 			dot_opts: DotOptions::default(),
 			btc_opts: BtcOptions::default(),
+			hub_opts: HubOptions::default(),
 			health_check_hostname: None,
 			health_check_port: None,
 			prometheus_hostname: None,
@@ -500,6 +519,8 @@ impl CfSettings for Settings {
 
 		self.btc.validate_settings()?;
 
+		self.hub.validate_settings()?;
+
 		self.state_chain.validate_settings()?;
 
 		is_valid_db_path(&self.signing.db_file).map_err(|e| ConfigError::Message(e.to_string()))?;
@@ -583,6 +604,8 @@ impl Source for CommandLineOptions {
 
 		self.btc_opts.insert_all(&mut map);
 
+		self.hub_opts.insert_all(&mut map);
+
 		insert_command_line_option(&mut map, "health_check.hostname", &self.health_check_hostname);
 		insert_command_line_option(&mut map, "health_check.port", &self.health_check_port);
 
@@ -716,6 +739,20 @@ impl DotOptions {
 	}
 }
 
+impl HubOptions {
+	pub fn insert_all(&self, map: &mut HashMap<String, Value>) {
+		insert_command_line_option(map, "hub.rpc.ws_endpoint", &self.hub_ws_endpoint);
+		insert_command_line_option(map, "hub.rpc.http_endpoint", &self.hub_http_endpoint);
+
+		insert_command_line_option(map, "hub.backup_rpc.ws_endpoint", &self.hub_backup_ws_endpoint);
+		insert_command_line_option(
+			map,
+			"hub.backup_rpc.http_endpoint",
+			&self.hub_backup_http_endpoint,
+		);
+	}
+}
+
 impl Settings {
 	/// New settings loaded from "$base_config_path/config/Settings.toml",
 	/// environment and `CommandLineOptions`
@@ -782,7 +819,8 @@ pub mod tests {
 		BTC_BACKUP_HTTP_ENDPOINT, BTC_BACKUP_RPC_PASSWORD, BTC_BACKUP_RPC_USER, BTC_HTTP_ENDPOINT,
 		BTC_RPC_PASSWORD, BTC_RPC_USER, DOT_BACKUP_HTTP_ENDPOINT, DOT_BACKUP_WS_ENDPOINT,
 		DOT_HTTP_ENDPOINT, DOT_WS_ENDPOINT, ETH_BACKUP_HTTP_ENDPOINT, ETH_BACKUP_WS_ENDPOINT,
-		ETH_HTTP_ENDPOINT, ETH_WS_ENDPOINT, NODE_P2P_IP_ADDRESS,
+		ETH_HTTP_ENDPOINT, ETH_WS_ENDPOINT, HUB_BACKUP_HTTP_ENDPOINT, HUB_BACKUP_WS_ENDPOINT,
+		HUB_HTTP_ENDPOINT, HUB_WS_ENDPOINT, NODE_P2P_IP_ADDRESS,
 	};
 
 	use super::*;
@@ -831,7 +869,14 @@ pub mod tests {
 		DOT_BACKUP_WS_ENDPOINT =>
 		"wss://second.my_fake_polkadot_rpc:443/<secret_key>",
 		DOT_BACKUP_HTTP_ENDPOINT =>
-		"https://second.my_fake_polkadot_rpc:443/<secret_key>"
+		"https://second.my_fake_polkadot_rpc:443/<secret_key>",
+
+		HUB_WS_ENDPOINT => "wss://my_fake_assethub_rpc:443/<secret_key>",
+		HUB_HTTP_ENDPOINT => "https://my_fake_assethub_rpc:443/<secret_key>",
+		HUB_BACKUP_WS_ENDPOINT =>
+		"wss://second.my_fake_assethub_rpc:443/<secret_key>",
+		HUB_BACKUP_HTTP_ENDPOINT =>
+		"https://second.my_fake_assethub_rpc:443/<secret_key>"
 	}
 
 	// We do them like this so they run sequentially, which is necessary so the environment doesn't
@@ -952,6 +997,13 @@ pub mod tests {
 				btc_backup_basic_auth_user: Some("second.my_username".to_owned()),
 				btc_backup_basic_auth_password: Some("second.my_password".to_owned()),
 			},
+			hub_opts: HubOptions {
+				hub_ws_endpoint: Some("ws://hub-endpoint:4321".to_owned()),
+				hub_http_endpoint: Some("http://hub-endpoint:4321".to_owned()),
+
+				hub_backup_ws_endpoint: Some("ws://second.hub-endpoint:4321".to_owned()),
+				hub_backup_http_endpoint: Some("http://second.hub-endpoint:4321".to_owned()),
+			},
 			health_check_hostname: Some("health_check_hostname".to_owned()),
 			health_check_port: Some(1337),
 			prometheus_hostname: Some(("prometheus_hostname").to_owned()),
@@ -1041,6 +1093,25 @@ pub mod tests {
 			btc_backup_node.basic_auth_password
 		);
 
+		assert_eq!(
+			opts.hub_opts.hub_ws_endpoint.unwrap(),
+			settings.hub.nodes.primary.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.hub_opts.hub_http_endpoint.unwrap(),
+			settings.hub.nodes.primary.http_endpoint.as_ref()
+		);
+
+		let hub_backup_node = settings.hub.nodes.backup.unwrap();
+		assert_eq!(
+			opts.hub_opts.hub_backup_ws_endpoint.unwrap(),
+			hub_backup_node.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.hub_opts.hub_backup_http_endpoint.unwrap(),
+			hub_backup_node.http_endpoint.as_ref()
+		);
+
 		assert_eq!(
 			opts.health_check_hostname.unwrap(),
 			settings.health_check.as_ref().unwrap().hostname
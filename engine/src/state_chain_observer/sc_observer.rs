@@ -221,6 +221,7 @@ pub async fn start<
 	EthRpc,
 	DotRpc,
 	BtcRpc,
+	HubRpc,
 	EthMultisigClient,
 	PolkadotMultisigClient,
 	BitcoinMultisigClient,
@@ -230,6 +231,7 @@ pub async fn start<
 	eth_rpc: EthRpc,
 	dot_rpc: DotRpc,
 	btc_rpc: BtcRpc,
+	hub_rpc: HubRpc,
 	eth_multisig_client: EthMultisigClient,
 	dot_multisig_client: PolkadotMultisigClient,
 	btc_multisig_client: BitcoinMultisigClient,
@@ -239,6 +241,7 @@ where
 	EthRpc: EthersRetrySigningRpcApi + Send + Sync + 'static,
 	DotRpc: DotRetryRpcApi + Send + Sync + 'static,
 	BtcRpc: BtcRetryRpcApi + Send + Sync + 'static,
+	HubRpc: DotRetryRpcApi + Send + Sync + 'static,
 	EthMultisigClient: MultisigClientApi<EvmCryptoScheme> + Send + Sync + 'static,
 	PolkadotMultisigClient: MultisigClientApi<PolkadotCryptoScheme> + Send + Sync + 'static,
 	BitcoinMultisigClient: MultisigClientApi<BtcCryptoScheme> + Send + Sync + 'static,
@@ -457,6 +460,29 @@ where
                                             });
                                         }
                                     }
+                                    CfeEvent::HubTxBroadcastRequest(TxBroadcastRequest::<Runtime, _> { broadcast_id, nominee, payload }) => {
+                                        if nominee == account_id {
+                                            let hub_rpc = hub_rpc.clone();
+                                            let state_chain_client = state_chain_client.clone();
+                                            scope.spawn(async move {
+                                                match hub_rpc.submit_raw_encoded_extrinsic(payload.encoded_extrinsic).await {
+                                                    Ok(tx_hash) => info!("Assethub TransactionBroadcastRequest {broadcast_id:?} success: tx_hash: {tx_hash:#x}"),
+                                                    Err(error) => {
+                                                        error!("Error on Assethub TransactionBroadcastRequest {broadcast_id:?}: {error:?}");
+                                                        state_chain_client.finalize_signed_extrinsic(
+                                                            RuntimeCall::AssethubBroadcaster(
+                                                                pallet_cf_broadcast::Call::transaction_failed {
+                                                                    broadcast_id,
+                                                                },
+                                                            ),
+                                                        )
+                                                        .await;
+                                                    }
+                                                }
+                                                Ok(())
+                                            });
+                                        }
+                                    }
                                     CfeEvent::EthTxBroadcastRequest(TxBroadcastRequest::<Runtime, _> { broadcast_id, nominee, payload }) => {
                                         if nominee == account_id {
                                             let eth_rpc = eth_rpc.clone();
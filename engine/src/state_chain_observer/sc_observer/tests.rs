@@ -54,6 +54,7 @@ async fn start_sc_observer<
 		eth_rpc,
 		MockDotHttpRpcClient::new(),
 		MockBtcRetryRpcClient::new(),
+		MockDotHttpRpcClient::new(),
 		MockMultisigClientApi::new(),
 		MockMultisigClientApi::new(),
 		MockMultisigClientApi::new(),
@@ -726,6 +727,7 @@ async fn run_the_sc_observer() {
 				MockEthRetryRpcClient::new(),
 				MockDotHttpRpcClient::new(),
 				MockBtcRetryRpcClient::new(),
+				MockDotHttpRpcClient::new(),
 				MockMultisigClientApi::new(),
 				MockMultisigClientApi::new(),
 				MockMultisigClientApi::new(),
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use cf_primitives::EpochIndex;
 use futures_core::Future;
 use itertools::Itertools;
-use pallet_cf_ingress_egress::{DepositChannelDetails, DepositWitness};
+use pallet_cf_ingress_egress::{DepositChannelDetails, DepositWitness, VaultSwapWitness};
 use secp256k1::hashes::Hash as secp256k1Hash;
 use state_chain_runtime::BitcoinInstance;
 
@@ -17,10 +17,13 @@ use crate::{
 		RuntimeHasChain,
 	},
 };
-use bitcoin::BlockHash;
+use bitcoin::{BlockHash, Script};
 use cf_chains::{
 	assets::btc,
-	btc::{ScriptPubkey, UtxoId},
+	btc::{
+		deposit_address::DepositAddress, vault_swap_encoding::decode_vault_swap_payload,
+		ScriptPubkey, UtxoId, CHANGE_ADDRESS_SALT,
+	},
 	Bitcoin,
 };
 
@@ -75,6 +78,20 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 					)
 					.await;
 				}
+
+				let vault_address =
+					DepositAddress::new(epoch.info.0.current, CHANGE_ADDRESS_SALT).script_pubkey();
+				for vault_swap in vault_swaps(&txs, &vault_address) {
+					process_call(
+						pallet_cf_ingress_egress::Call::<_, BitcoinInstance>::vault_swap_request {
+							vault_swap: Box::new(vault_swap),
+						}
+						.into(),
+						epoch.index,
+					)
+					.await;
+				}
+
 				txs
 			}
 		})
@@ -118,6 +135,77 @@ fn deposit_witnesses(
 		.collect()
 }
 
+/// Finds the transactions that pay into the vault and request a swap. See
+/// [cf_chains::btc::vault_swap_encoding] for the expected layout of these transactions.
+fn vault_swaps(
+	txs: &[VerboseTransaction],
+	vault_address: &ScriptPubkey,
+) -> Vec<VaultSwapWitness<Bitcoin, state_chain_runtime::AccountId>> {
+	let vault_script = vault_address.bytes();
+	txs.iter()
+		.filter_map(|tx| {
+			let [vault_output, payload_output, refund_output, ..] = &tx.vout[..] else {
+				return None
+			};
+			if vault_output.script_pubkey.as_bytes() != vault_script ||
+				vault_output.value.to_sat() == 0
+			{
+				return None
+			}
+			let parameters = decode_vault_swap_payload::<state_chain_runtime::AccountId>(
+				op_return_data(&payload_output.script_pubkey)?,
+			)
+			.ok()?;
+
+			Some(VaultSwapWitness {
+				vault_address: vault_address.clone(),
+				asset: btc::Asset::Btc,
+				amount: vault_output.value.to_sat(),
+				deposit_details: UtxoId { tx_id: tx.txid.as_raw_hash().to_byte_array(), vout: 0 },
+				destination_asset: parameters.output_asset,
+				destination_address: parameters.output_address,
+				min_output: parameters.min_output_amount,
+				refund_address: script_pubkey_from_script(&refund_output.script_pubkey)?,
+				broker_id: parameters.broker_id,
+				broker_commission_bps: parameters.broker_commission_bps,
+				tx_hash: tx.txid.as_raw_hash().to_byte_array(),
+			})
+		})
+		.collect()
+}
+
+/// Returns the data pushed by an `OP_RETURN` output.
+fn op_return_data(script: &Script) -> Option<&[u8]> {
+	use bitcoin::blockdata::script::Instruction;
+
+	if !script.is_op_return() {
+		return None
+	}
+	let mut instructions = script.instructions().skip(1);
+	match (instructions.next(), instructions.next()) {
+		(Some(Ok(Instruction::PushBytes(data))), None) => Some(data.as_bytes()),
+		_ => None,
+	}
+}
+
+/// Converts a standard output script into the state chain's representation of it.
+fn script_pubkey_from_script(script: &Script) -> Option<ScriptPubkey> {
+	let bytes = script.as_bytes();
+	if script.is_p2pkh() {
+		Some(ScriptPubkey::P2PKH(bytes[3..23].try_into().ok()?))
+	} else if script.is_p2sh() {
+		Some(ScriptPubkey::P2SH(bytes[2..22].try_into().ok()?))
+	} else if script.is_v0_p2wpkh() {
+		Some(ScriptPubkey::P2WPKH(bytes[2..22].try_into().ok()?))
+	} else if script.is_v0_p2wsh() {
+		Some(ScriptPubkey::P2WSH(bytes[2..34].try_into().ok()?))
+	} else if script.is_v1_p2tr() {
+		Some(ScriptPubkey::Taproot(bytes[2..34].try_into().ok()?))
+	} else {
+		None
+	}
+}
+
 fn script_addresses(
 	addresses: Vec<DepositChannelDetails<state_chain_runtime::Runtime, BitcoinInstance>>,
 ) -> HashMap<Vec<u8>, ScriptPubkey> {
@@ -139,12 +227,19 @@ pub mod tests {
 	use super::*;
 	use bitcoin::{
 		absolute::{Height, LockTime},
+		script::PushBytesBuf,
 		Amount, ScriptBuf, Txid,
 	};
 	use cf_chains::{
-		btc::{deposit_address::DepositAddress, ScriptPubkey},
+		address::EncodedAddress,
+		btc::{
+			deposit_address::DepositAddress,
+			vault_swap_encoding::{encode_vault_swap_payload, VaultSwapParameters},
+			ScriptPubkey,
+		},
 		DepositChannel,
 	};
+	use cf_primitives::Asset;
 	use pallet_cf_ingress_egress::ChannelAction;
 	use rand::{seq::SliceRandom, Rng, SeedableRng};
 	use sp_runtime::AccountId32;
@@ -389,4 +484,91 @@ pub mod tests {
 		assert_eq!(deposit_witnesses[0].amount, UTXO_WITNESSED_1);
 		assert_eq!(deposit_witnesses[1].amount, UTXO_WITNESSED_2);
 	}
+
+	#[test]
+	fn vault_swaps_are_witnessed() {
+		let vault_address: ScriptPubkey =
+			DepositAddress::new([0xaa; 32], CHANGE_ADDRESS_SALT).script_pubkey();
+		let refund_address = ScriptPubkey::P2WPKH([0xcc; 20]);
+		let parameters = VaultSwapParameters {
+			output_asset: Asset::Eth,
+			output_address: EncodedAddress::Eth([0xee; 20]),
+			min_output_amount: 1_000,
+			broker_id: AccountId32::new([0xbb; 32]),
+			broker_commission_bps: 10,
+		};
+		let op_return = ScriptBuf::new_op_return(
+			&PushBytesBuf::try_from(encode_vault_swap_payload(&parameters).unwrap()).unwrap(),
+		)
+		.into_bytes();
+
+		let vault_swap_tx = fake_transaction(
+			fake_verbose_vouts(vec![
+				(5_000, vault_address.bytes()),
+				(0, op_return.clone()),
+				(2_000, refund_address.bytes()),
+			]),
+			None,
+		);
+		let txs = vec![
+			vault_swap_tx.clone(),
+			// The payment into the vault must be the first output.
+			fake_transaction(
+				fake_verbose_vouts(vec![
+					(0, op_return.clone()),
+					(5_000, vault_address.bytes()),
+					(2_000, refund_address.bytes()),
+				]),
+				None,
+			),
+			// Payments into the vault without a refund output are not swaps.
+			fake_transaction(
+				fake_verbose_vouts(vec![(5_000, vault_address.bytes()), (0, op_return)]),
+				None,
+			),
+			// Change outputs of our own transactions are not swaps.
+			fake_transaction(
+				fake_verbose_vouts(vec![
+					(5_000, refund_address.bytes()),
+					(2_000, vault_address.bytes()),
+				]),
+				None,
+			),
+		];
+
+		let tx_hash = vault_swap_tx.txid.as_raw_hash().to_byte_array();
+		assert_eq!(
+			vault_swaps(&txs, &vault_address),
+			vec![VaultSwapWitness {
+				vault_address,
+				asset: btc::Asset::Btc,
+				amount: 5_000,
+				deposit_details: UtxoId { tx_id: tx_hash, vout: 0 },
+				destination_asset: parameters.output_asset,
+				destination_address: parameters.output_address,
+				min_output: parameters.min_output_amount,
+				refund_address,
+				broker_id: parameters.broker_id,
+				broker_commission_bps: parameters.broker_commission_bps,
+				tx_hash,
+			}]
+		);
+	}
+
+	#[test]
+	fn standard_scripts_are_converted() {
+		for script_pubkey in [
+			ScriptPubkey::P2PKH([0x01; 20]),
+			ScriptPubkey::P2SH([0x02; 20]),
+			ScriptPubkey::P2WPKH([0x03; 20]),
+			ScriptPubkey::P2WSH([0x04; 32]),
+			ScriptPubkey::Taproot([0x05; 32]),
+		] {
+			assert_eq!(
+				script_pubkey_from_script(&ScriptBuf::from(script_pubkey.bytes())),
+				Some(script_pubkey)
+			);
+		}
+		assert_eq!(script_pubkey_from_script(&ScriptBuf::from(vec![0x6a, 0x01, 0x00])), None);
+	}
 }
@@ -5,7 +5,7 @@ pub mod epoch_source;
 use cf_chains::Chain;
 use futures_core::{stream::BoxStream, Future, Stream};
 use futures_util::{stream, StreamExt};
-use state_chain_runtime::PalletInstanceAlias;
+use state_chain_runtime::{PalletInstanceAlias, ThresholdSignerInstanceAlias};
 
 use chain_source::ChainSource;
 
@@ -55,7 +55,7 @@ pub type BoxActiveAndFuture<'a, T> =
 pub trait RuntimeHasChain<TChain: ExternalChain>:
 	pallet_cf_vaults::Config<<TChain as PalletInstanceAlias>::Instance, Chain = TChain>
 	+ pallet_cf_threshold_signature::Config<
-		<TChain as PalletInstanceAlias>::Instance,
+		<TChain as ThresholdSignerInstanceAlias>::Instance,
 		TargetChainCrypto = TChain::ChainCrypto,
 	> + pallet_cf_chain_tracking::Config<
 		<TChain as PalletInstanceAlias>::Instance,
@@ -69,7 +69,7 @@ pub trait RuntimeHasChain<TChain: ExternalChain>:
 impl<TChain: ExternalChain> RuntimeHasChain<TChain> for state_chain_runtime::Runtime where
 	Self: pallet_cf_vaults::Config<<TChain as PalletInstanceAlias>::Instance, Chain = TChain>
 		+ pallet_cf_threshold_signature::Config<
-			<TChain as PalletInstanceAlias>::Instance,
+			<TChain as ThresholdSignerInstanceAlias>::Instance,
 			TargetChainCrypto = TChain::ChainCrypto,
 		> + pallet_cf_chain_tracking::Config<
 			<TChain as PalletInstanceAlias>::Instance,
@@ -107,8 +107,8 @@ where
 {
 }
 
-pub trait ExternalChain: Chain + PalletInstanceAlias {}
-impl<T: Chain + PalletInstanceAlias> ExternalChain for T {}
+pub trait ExternalChain: Chain + PalletInstanceAlias + ThresholdSignerInstanceAlias {}
+impl<T: Chain + PalletInstanceAlias + ThresholdSignerInstanceAlias> ExternalChain for T {}
 
 pub trait ExternalChainSource:
 	ChainSource<Index = <Self::Chain as Chain>::ChainBlockNumber>
@@ -16,7 +16,7 @@ use cf_primitives::{AccountId, EpochIndex};
 use futures::StreamExt;
 use futures_core::{Future, Stream};
 use futures_util::stream;
-use state_chain_runtime::PalletInstanceAlias;
+use state_chain_runtime::{PalletInstanceAlias, ThresholdSignerInstanceAlias};
 use utilities::{spmc, task_scope::Scope};
 
 use super::{ActiveAndFuture, ExternalChain, RuntimeHasChain};
@@ -432,7 +432,7 @@ impl<'a, 'env, StateChainClient: StorageApi + Send + Sync + 'static, Info, Histo
 						state_chain_client
 						.storage_map_entry::<pallet_cf_threshold_signature::Keys<
 							state_chain_runtime::Runtime,
-							<TChain as PalletInstanceAlias>::Instance,
+							<TChain as ThresholdSignerInstanceAlias>::Instance,
 						>>(block_hash, &epoch)
 						.await
 						.expect(STATE_CHAIN_CONNECTION)
@@ -447,7 +447,7 @@ impl<'a, 'env, StateChainClient: StorageApi + Send + Sync + 'static, Info, Histo
 					state_chain_client
 						.storage_map_entry::<pallet_cf_threshold_signature::Keys<
 							state_chain_runtime::Runtime,
-							<TChain as PalletInstanceAlias>::Instance,
+							<TChain as ThresholdSignerInstanceAlias>::Instance,
 						>>(block_hash, &(epoch + 1))
 						.await
 						.expect(STATE_CHAIN_CONNECTION)
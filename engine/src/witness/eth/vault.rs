@@ -17,10 +17,12 @@ use futures_core::Future;
 
 use anyhow::{anyhow, Result};
 use cf_chains::{
-	address::EncodedAddress, eth::Address as EthereumAddress, CcmChannelMetadata,
-	CcmDepositMetadata,
+	address::EncodedAddress, eth::Address as EthereumAddress, evm::VaultSwapCfParameters,
+	CcmChannelMetadata, CcmDepositMetadata,
+};
+use cf_primitives::{
+	chains::assets::eth::Asset as EthereumAsset, Asset, AssetAmount, ForeignChain, TransactionHash,
 };
-use cf_primitives::{chains::assets::eth::Asset as EthereumAsset, Asset, ForeignChain};
 use ethers::prelude::*;
 use state_chain_runtime::{EthereumInstance, Runtime, RuntimeCall};
 
@@ -49,44 +51,74 @@ pub fn call_from_event(
 		})
 	}
 
+	/// Swaps whose `cf_parameters` request an exact output are scheduled as such, with the swapped
+	/// amount as the maximum input, and any unused input refunded to the sender.
+	fn swap_call(
+		from: Asset,
+		to: Asset,
+		amount: AssetAmount,
+		destination_address: EncodedAddress,
+		sender: EncodedAddress,
+		cf_parameters: &[u8],
+		tx_hash: TransactionHash,
+	) -> RuntimeCall {
+		RuntimeCall::Swapping(match VaultSwapCfParameters::decode_from(cf_parameters) {
+			Some(VaultSwapCfParameters::ExactOutput { output_amount }) =>
+				pallet_cf_swapping::Call::schedule_exact_output_swap_from_contract {
+					from,
+					to,
+					max_input: amount,
+					output_amount,
+					destination_address,
+					refund_address: sender,
+					tx_hash,
+				},
+			None => pallet_cf_swapping::Call::schedule_swap_from_contract {
+				from,
+				to,
+				deposit_amount: amount,
+				destination_address,
+				tx_hash,
+			},
+		})
+	}
+
 	Ok(match event.event_parameters {
 		VaultEvents::SwapNativeFilter(SwapNativeFilter {
 			dst_chain,
 			dst_address,
 			dst_token,
 			amount,
-			sender: _,
-			cf_parameters: _,
-		}) => Some(RuntimeCall::Swapping(pallet_cf_swapping::Call::schedule_swap_from_contract {
-			from: native_asset,
-			to: try_into_primitive(dst_token)?,
-			deposit_amount: try_into_primitive(amount)?,
-			destination_address: try_into_encoded_address(
-				try_into_primitive(dst_chain)?,
-				dst_address.to_vec(),
-			)?,
-			tx_hash: event.tx_hash.into(),
-		})),
+			sender,
+			cf_parameters,
+		}) => Some(swap_call(
+			native_asset,
+			try_into_primitive(dst_token)?,
+			try_into_primitive(amount)?,
+			try_into_encoded_address(try_into_primitive(dst_chain)?, dst_address.to_vec())?,
+			try_into_encoded_address(source_chain, sender.0.to_vec())?,
+			&cf_parameters,
+			event.tx_hash.into(),
+		)),
 		VaultEvents::SwapTokenFilter(SwapTokenFilter {
 			dst_chain,
 			dst_address,
 			dst_token,
 			src_token,
 			amount,
-			sender: _,
-			cf_parameters: _,
-		}) => Some(RuntimeCall::Swapping(pallet_cf_swapping::Call::schedule_swap_from_contract {
-			from: *(supported_assets
+			sender,
+			cf_parameters,
+		}) => Some(swap_call(
+			*(supported_assets
 				.get(&src_token)
 				.ok_or(anyhow!("Source token {src_token:?} not found"))?),
-			to: try_into_primitive(dst_token)?,
-			deposit_amount: try_into_primitive(amount)?,
-			destination_address: try_into_encoded_address(
-				try_into_primitive(dst_chain)?,
-				dst_address.to_vec(),
-			)?,
-			tx_hash: event.tx_hash.into(),
-		})),
+			try_into_primitive(dst_token)?,
+			try_into_primitive(amount)?,
+			try_into_encoded_address(try_into_primitive(dst_chain)?, dst_address.to_vec())?,
+			try_into_encoded_address(source_chain, sender.0.to_vec())?,
+			&cf_parameters,
+			event.tx_hash.into(),
+		)),
 		VaultEvents::XcallNativeFilter(XcallNativeFilter {
 			dst_chain,
 			dst_address,
@@ -117,6 +149,10 @@ pub fn call_from_event(
 						cf_parameters: cf_parameters.0.to_vec().try_into().map_err(|_| {
 							anyhow!("Failed to deposit CCM: `cf_parameters` too long.")
 						})?,
+						// The Vault's xCall events don't carry a fallback, so CCMs sent to the
+						// Vault contract directly can't be refunded. Supporting this needs a new
+						// contract event, and is out of scope for now.
+						fallback: None,
 					},
 				},
 				tx_hash: event.tx_hash.into(),
@@ -154,6 +190,8 @@ pub fn call_from_event(
 						cf_parameters: cf_parameters.0.to_vec().try_into().map_err(|_| {
 							anyhow!("Failed to deposit CCM. cf_parameter too long.")
 						})?,
+						// See the comment on `XcallNativeFilter`.
+						fallback: None,
 					},
 				},
 				tx_hash: event.tx_hash.into(),
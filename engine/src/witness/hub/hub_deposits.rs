@@ -0,0 +1,213 @@
+use cf_primitives::{EpochIndex, PolkadotBlockNumber};
+use futures_core::Future;
+use pallet_cf_ingress_egress::{DepositChannelDetails, DepositWitness};
+use state_chain_runtime::AssethubInstance;
+
+use super::super::common::chunked_chain_source::chunked_by_vault::{
+	builder::ChunkedByVaultBuilder, ChunkedByVault,
+};
+use crate::witness::{
+	common::{
+		chunked_chain_source::chunked_by_vault::deposit_addresses::Addresses, RuntimeCallHasChain,
+		RuntimeHasChain,
+	},
+	hub::EventWrapper,
+};
+use cf_chains::{
+	assets::hub::Asset,
+	dot::PolkadotHash,
+	hub::{Assethub, AssethubAccountId, AssethubAssetId, USDC_ASSET_ID, USDT_ASSET_ID},
+};
+use subxt::events::Phase;
+
+impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
+	pub fn hub_deposits<ProcessCall, ProcessingFut>(
+		self,
+		process_call: ProcessCall,
+	) -> ChunkedByVaultBuilder<
+		impl ChunkedByVault<
+			Index = PolkadotBlockNumber,
+			Hash = PolkadotHash,
+			Data = Vec<(Phase, EventWrapper)>,
+			Chain = Assethub,
+			ExtraInfo = AssethubAccountId,
+			ExtraHistoricInfo = (),
+		>,
+	>
+	where
+		Inner: ChunkedByVault<
+			Index = PolkadotBlockNumber,
+			Hash = PolkadotHash,
+			Data = (Vec<(Phase, EventWrapper)>, Addresses<Inner>),
+			Chain = Assethub,
+			ExtraInfo = AssethubAccountId,
+			ExtraHistoricInfo = (),
+		>,
+		ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+			+ Send
+			+ Sync
+			+ Clone
+			+ 'static,
+		ProcessingFut: Future<Output = ()> + Send + 'static,
+		state_chain_runtime::Runtime: RuntimeHasChain<Inner::Chain>,
+		state_chain_runtime::RuntimeCall:
+			RuntimeCallHasChain<state_chain_runtime::Runtime, Inner::Chain>,
+	{
+		self.then(move |epoch, header| {
+			let process_call = process_call.clone();
+			async move {
+				let (events, addresses_and_details) = header.data;
+
+				let channels = address_and_details_to_channels(addresses_and_details);
+
+				let deposit_witnesses = deposit_witnesses(channels, &events);
+
+				if !deposit_witnesses.is_empty() {
+					process_call(
+						pallet_cf_ingress_egress::Call::<_, AssethubInstance>::process_deposits {
+							deposit_witnesses,
+							block_height: header.index,
+						}
+						.into(),
+						epoch.index,
+					)
+					.await
+				}
+
+				events
+			}
+		})
+	}
+}
+
+fn address_and_details_to_channels(
+	address_and_details: Vec<DepositChannelDetails<state_chain_runtime::Runtime, AssethubInstance>>,
+) -> Vec<(AssethubAccountId, Asset)> {
+	address_and_details
+		.into_iter()
+		.map(|deposit_channel_details| {
+			(
+				deposit_channel_details.deposit_channel.address,
+				deposit_channel_details.deposit_channel.asset,
+			)
+		})
+		.collect()
+}
+
+/// The asset corresponding to an id in the assets pallet, if it is one we support.
+fn asset_from_id(asset_id: AssethubAssetId) -> Option<Asset> {
+	match asset_id {
+		USDT_ASSET_ID => Some(Asset::HubUsdt),
+		USDC_ASSET_ID => Some(Asset::HubUsdc),
+		_ => None,
+	}
+}
+
+// DOT arrives through the balances pallet and every other asset through the assets pallet. A
+// transfer is only a deposit if it matches both the address and the asset of an open channel.
+fn deposit_witnesses(
+	monitored_channels: Vec<(AssethubAccountId, Asset)>,
+	events: &Vec<(Phase, EventWrapper)>,
+) -> Vec<DepositWitness<Assethub>> {
+	let mut deposit_witnesses = vec![];
+	for (phase, wrapped_event) in events {
+		if let Phase::ApplyExtrinsic(_extrinsic_index) = phase {
+			let (to, asset, amount) = match wrapped_event {
+				EventWrapper::Transfer { to, amount, from: _ } => (to, Asset::HubDot, *amount),
+				EventWrapper::AssetTransferred { asset_id, to, amount, from: _ } =>
+					match asset_from_id(*asset_id) {
+						Some(asset) => (to, asset, *amount),
+						None => continue,
+					},
+				_ => continue,
+			};
+			let deposit_address = AssethubAccountId::from_aliased(to.0);
+			if monitored_channels.contains(&(deposit_address, asset)) {
+				deposit_witnesses.push(DepositWitness {
+					deposit_address,
+					asset,
+					amount,
+					deposit_details: (),
+				});
+			}
+		}
+	}
+	deposit_witnesses
+}
+
+#[cfg(test)]
+mod test {
+	use cf_chains::dot::PolkadotBalance;
+
+	use crate::witness::hub::test::phase_and_events;
+
+	use super::*;
+
+	fn mock_transfer(
+		from: &AssethubAccountId,
+		to: &AssethubAccountId,
+		amount: PolkadotBalance,
+	) -> EventWrapper {
+		EventWrapper::Transfer {
+			from: from.aliased_ref().to_owned().into(),
+			to: to.aliased_ref().to_owned().into(),
+			amount,
+		}
+	}
+
+	fn mock_asset_transfer(
+		asset_id: AssethubAssetId,
+		from: &AssethubAccountId,
+		to: &AssethubAccountId,
+		amount: PolkadotBalance,
+	) -> EventWrapper {
+		EventWrapper::AssetTransferred {
+			asset_id,
+			from: from.aliased_ref().to_owned().into(),
+			to: to.aliased_ref().to_owned().into(),
+			amount,
+		}
+	}
+
+	#[test]
+	fn witness_deposits_for_channels_we_monitor() {
+		let sender = AssethubAccountId::from_aliased([7; 32]);
+		let dot_channel = AssethubAccountId::from_aliased([1; 32]);
+		let usdt_channel = AssethubAccountId::from_aliased([2; 32]);
+		let usdc_channel = AssethubAccountId::from_aliased([3; 32]);
+
+		let block_event_details = phase_and_events(vec![
+			(1, mock_transfer(&sender, &dot_channel, 10000)),
+			(2, mock_asset_transfer(USDT_ASSET_ID, &sender, &usdt_channel, 20000)),
+			(3, mock_asset_transfer(USDC_ASSET_ID, &sender, &usdc_channel, 30000)),
+			// Asset doesn't match the channel's asset.
+			(4, mock_asset_transfer(USDC_ASSET_ID, &sender, &usdt_channel, 40000)),
+			(5, mock_transfer(&sender, &usdc_channel, 50000)),
+			// Asset we don't support.
+			(6, mock_asset_transfer(1, &sender, &usdt_channel, 60000)),
+			// Not one of our channels.
+			(7, mock_asset_transfer(USDT_ASSET_ID, &sender, &sender, 70000)),
+		]);
+
+		let deposit_witnesses = deposit_witnesses(
+			vec![
+				(dot_channel, Asset::HubDot),
+				(usdt_channel, Asset::HubUsdt),
+				(usdc_channel, Asset::HubUsdc),
+			],
+			&block_event_details,
+		);
+
+		assert_eq!(
+			deposit_witnesses
+				.into_iter()
+				.map(|witness| (witness.deposit_address, witness.asset, witness.amount))
+				.collect::<Vec<_>>(),
+			vec![
+				(dot_channel, Asset::HubDot, 10000),
+				(usdt_channel, Asset::HubUsdt, 20000),
+				(usdc_channel, Asset::HubUsdc, 30000),
+			]
+		);
+	}
+}
@@ -0,0 +1,50 @@
+use cf_chains::hub::Assethub;
+
+use crate::witness::{
+	common::{
+		chain_source::{BoxChainStream, ChainSource},
+		ExternalChainSource,
+	},
+	dot::{DotFinalisedSource, DotUnfinalisedSource},
+};
+
+// Asset Hub nodes expose the same RPC interface as Polkadot nodes, so the Polkadot sources can be
+// reused as-is. These wrappers only change the chain the headers are attributed to.
+macro_rules! assethub_source {
+	($name:ident, $inner:ident) => {
+		pub struct $name<C>($inner<C>);
+
+		impl<C> $name<C> {
+			pub fn new(client: C) -> Self {
+				Self($inner::new(client))
+			}
+		}
+
+		#[async_trait::async_trait]
+		impl<C> ChainSource for $name<C>
+		where
+			$inner<C>: ChainSource,
+		{
+			type Index = <$inner<C> as ChainSource>::Index;
+			type Hash = <$inner<C> as ChainSource>::Hash;
+			type Data = <$inner<C> as ChainSource>::Data;
+			type Client = <$inner<C> as ChainSource>::Client;
+
+			async fn stream_and_client(
+				&self,
+			) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+				self.0.stream_and_client().await
+			}
+		}
+
+		impl<C> ExternalChainSource for $name<C>
+		where
+			$inner<C>: ExternalChainSource<Chain = cf_chains::Polkadot>,
+		{
+			type Chain = Assethub;
+		}
+	};
+}
+
+assethub_source!(HubUnfinalisedSource, DotUnfinalisedSource);
+assethub_source!(HubFinalisedSource, DotFinalisedSource);
@@ -30,6 +30,7 @@ pub async fn start<StateChainClient>(
 	eth_client: EthRetryRpcClient<EthRpcSigningClient>,
 	btc_client: BtcRetryRpcClient,
 	dot_client: DotRetryRpcClient,
+	hub_client: DotRetryRpcClient,
 	state_chain_client: Arc<StateChainClient>,
 	state_chain_stream: impl StreamApi<FINALIZED> + Clone,
 	unfinalised_state_chain_stream: impl StreamApi<UNFINALIZED> + Clone,
@@ -101,6 +102,16 @@ where
 	let start_dot = super::dot::start(
 		scope,
 		dot_client,
+		witness_call.clone(),
+		state_chain_client.clone(),
+		state_chain_stream.clone(),
+		epoch_source.clone(),
+		db.clone(),
+	);
+
+	let start_hub = super::hub::start(
+		scope,
+		hub_client,
 		witness_call,
 		state_chain_client,
 		state_chain_stream,
@@ -108,7 +119,7 @@ where
 		db,
 	);
 
-	futures::future::try_join3(start_eth, start_btc, start_dot).await?;
+	futures::future::try_join4(start_eth, start_btc, start_dot, start_hub).await?;
 
 	Ok(())
 }
@@ -28,6 +28,13 @@ pub struct PoolState<LiquidityProvider: Ord> {
 	range_orders: range_orders::PoolState<LiquidityProvider>,
 }
 
+/// The fees paid by a swap to limit and range orders, denominated in the sold asset.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapFees {
+	pub limit_orders: Amount,
+	pub range_orders: Amount,
+}
+
 pub enum NewError {
 	LimitOrders(limit_orders::NewError),
 	RangeOrders(range_orders::NewError),
@@ -171,6 +178,21 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		sold_amount: Amount,
 		sqrt_price_limit: Option<SqrtPriceQ64F96>,
 	) -> (Amount, Amount) {
+		let (output_amount, remaining_amount, _fees) =
+			self.swap_with_fees(order, sold_amount, sqrt_price_limit);
+		(output_amount, remaining_amount)
+	}
+
+	/// Performs a swap like [Self::swap], additionally returning the fees paid to limit and range
+	/// orders.
+	///
+	/// This function never panics.
+	pub fn swap_with_fees(
+		&mut self,
+		order: Order,
+		sold_amount: Amount,
+		sqrt_price_limit: Option<SqrtPriceQ64F96>,
+	) -> (Amount, Amount, SwapFees) {
 		match order.to_sold_side() {
 			Side::Zero => self.inner_swap::<ZeroToOne>(sold_amount, sqrt_price_limit),
 			Side::One => self.inner_swap::<OneToZero>(sold_amount, sqrt_price_limit),
@@ -183,11 +205,12 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		&mut self,
 		mut amount: Amount,
 		sqrt_price_limit: Option<SqrtPriceQ64F96>,
-	) -> (Amount, Amount) {
+	) -> (Amount, Amount, SwapFees) {
 		let mut total_output_amount = Amount::zero();
+		let mut fees = SwapFees::default();
 
 		while !amount.is_zero() {
-			let (output_amount, remaining_amount) = match (
+			let ((output_amount, remaining_amount, swap_fees), swapped_limit_orders) = match (
 				self.limit_orders.current_sqrt_price::<SD>().filter(|sqrt_price| {
 					sqrt_price_limit.map_or(true, |sqrt_price_limit| {
 						!SD::sqrt_price_op_more_than(*sqrt_price, sqrt_price_limit)
@@ -201,7 +224,7 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 			) {
 				(Some(limit_order_sqrt_price), Some(range_order_sqrt_price)) => {
 					if SD::sqrt_price_op_more_than(limit_order_sqrt_price, range_order_sqrt_price) {
-						self.range_orders.swap::<SD>(amount, Some(limit_order_sqrt_price))
+						(self.range_orders.swap::<SD>(amount, Some(limit_order_sqrt_price)), false)
 					} else {
 						// Note it is important that in the equal price case we prefer to swap limit
 						// orders as if we do a swap with range_orders where the sqrt_price_limit is
@@ -209,19 +232,121 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 						// price or use any of the input amount, therefore we would loop forever
 
 						// Also we prefer limit orders as they don't immediately incur slippage
-						self.limit_orders.swap::<SD>(amount, Some(range_order_sqrt_price))
+						(self.limit_orders.swap::<SD>(amount, Some(range_order_sqrt_price)), true)
 					}
 				},
-				(Some(_), None) => self.limit_orders.swap::<SD>(amount, sqrt_price_limit),
-				(None, Some(_)) => self.range_orders.swap::<SD>(amount, sqrt_price_limit),
+				(Some(_), None) => (self.limit_orders.swap::<SD>(amount, sqrt_price_limit), true),
+				(None, Some(_)) => (self.range_orders.swap::<SD>(amount, sqrt_price_limit), false),
 				(None, None) => break,
 			};
 
+			if swapped_limit_orders {
+				fees.limit_orders = fees.limit_orders.saturating_add(swap_fees);
+			} else {
+				fees.range_orders = fees.range_orders.saturating_add(swap_fees);
+			}
+
 			amount = remaining_amount;
 			total_output_amount = total_output_amount.saturating_add(output_amount);
 		}
 
-		(total_output_amount, amount)
+		(total_output_amount, amount, fees)
+	}
+
+	/// Performs a swap to buy an exact amount of either side/asset. Returns the amount of the sold
+	/// asset required, including fees, and the amount bought. Less than the requested amount is
+	/// bought if there is a lack of liquidity or sqrt_price_limit is reached. Slightly more may be
+	/// bought from limit orders, as the liquidity used from a partially filled limit order is
+	/// rounded up, and paid for.
+	///
+	/// This function never panics.
+	pub fn swap_exact_output(
+		&mut self,
+		order: Order,
+		bought_amount: Amount,
+		sqrt_price_limit: Option<SqrtPriceQ64F96>,
+	) -> (Amount, Amount) {
+		let (input_amount, output_amount, _fees) =
+			self.swap_exact_output_with_fees(order, bought_amount, sqrt_price_limit);
+		(input_amount, output_amount)
+	}
+
+	/// Performs a swap like [Self::swap_exact_output], additionally returning the fees paid to
+	/// limit and range orders.
+	///
+	/// This function never panics.
+	pub fn swap_exact_output_with_fees(
+		&mut self,
+		order: Order,
+		bought_amount: Amount,
+		sqrt_price_limit: Option<SqrtPriceQ64F96>,
+	) -> (Amount, Amount, SwapFees) {
+		match order.to_sold_side() {
+			Side::Zero =>
+				self.inner_swap_exact_output::<ZeroToOne>(bought_amount, sqrt_price_limit),
+			Side::One => self.inner_swap_exact_output::<OneToZero>(bought_amount, sqrt_price_limit),
+		}
+	}
+
+	fn inner_swap_exact_output<
+		SD: common::SwapDirection + limit_orders::SwapDirection + range_orders::SwapDirection,
+	>(
+		&mut self,
+		mut amount: Amount,
+		sqrt_price_limit: Option<SqrtPriceQ64F96>,
+	) -> (Amount, Amount, SwapFees) {
+		let mut total_input_amount = Amount::zero();
+		let mut total_output_amount = Amount::zero();
+		let mut fees = SwapFees::default();
+
+		while !amount.is_zero() {
+			let ((input_amount, output_amount, swap_fees), swapped_limit_orders) = match (
+				self.limit_orders.current_sqrt_price::<SD>().filter(|sqrt_price| {
+					sqrt_price_limit.map_or(true, |sqrt_price_limit| {
+						!SD::sqrt_price_op_more_than(*sqrt_price, sqrt_price_limit)
+					})
+				}),
+				self.range_orders.current_sqrt_price::<SD>().filter(|sqrt_price| {
+					sqrt_price_limit.map_or(true, |sqrt_price_limit| {
+						SD::sqrt_price_op_more_than(sqrt_price_limit, *sqrt_price)
+					})
+				}),
+			) {
+				(Some(limit_order_sqrt_price), Some(range_order_sqrt_price)) => {
+					// See `inner_swap` for why limit orders are preferred when prices are equal.
+					if SD::sqrt_price_op_more_than(limit_order_sqrt_price, range_order_sqrt_price) {
+						(
+							self.range_orders
+								.swap_exact_output::<SD>(amount, Some(limit_order_sqrt_price)),
+							false,
+						)
+					} else {
+						(
+							self.limit_orders
+								.swap_exact_output::<SD>(amount, Some(range_order_sqrt_price)),
+							true,
+						)
+					}
+				},
+				(Some(_), None) =>
+					(self.limit_orders.swap_exact_output::<SD>(amount, sqrt_price_limit), true),
+				(None, Some(_)) =>
+					(self.range_orders.swap_exact_output::<SD>(amount, sqrt_price_limit), false),
+				(None, None) => break,
+			};
+
+			if swapped_limit_orders {
+				fees.limit_orders = fees.limit_orders.saturating_add(swap_fees);
+			} else {
+				fees.range_orders = fees.range_orders.saturating_add(swap_fees);
+			}
+
+			amount = amount.saturating_sub(output_amount);
+			total_input_amount = total_input_amount.saturating_add(input_amount);
+			total_output_amount = total_output_amount.saturating_add(output_amount);
+		}
+
+		(total_input_amount, total_output_amount, fees)
 	}
 
 	pub fn collect_and_mint_limit_order(
@@ -387,6 +512,17 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		self.range_orders.liquidity()
 	}
 
+	/// Returns the range order liquidity that is in range at the current range order price.
+	pub fn current_range_order_liquidity(&self) -> Liquidity {
+		self.range_orders.current_liquidity()
+	}
+
+	/// Returns the current tick of the range orders. A range order is in range, and so earning
+	/// fees, if its range contains this tick.
+	pub fn current_range_order_tick(&self) -> Tick {
+		self.range_orders.current_tick()
+	}
+
 	pub fn limit_order_depth(
 		&mut self,
 		range: core::ops::Range<Tick>,
@@ -100,6 +100,17 @@ impl FloatBetweenZeroAndOne {
 	///
 	/// This function will panic if the numerator is zero, or if numerator > denominator
 	fn mul_div_ceil(&self, numerator: U256, denominator: U256) -> Self {
+		self.mul_div::<true>(numerator, denominator)
+	}
+
+	/// Returns the result of `self * numerator / denominator` with the result rounded down.
+	///
+	/// This function will panic if the numerator is zero, or if numerator > denominator
+	fn mul_div_floor(&self, numerator: U256, denominator: U256) -> Self {
+		self.mul_div::<false>(numerator, denominator)
+	}
+
+	fn mul_div<const ROUND_UP: bool>(&self, numerator: U256, denominator: U256) -> Self {
 		// We cannot use the `mul_div_ceil` function here (and then right-shift the result) to
 		// calculate the normalised_mantissa as the low zero bits (where we shifted) could be wrong.
 
@@ -124,14 +135,17 @@ impl FloatBetweenZeroAndOne {
 			let (d, div_remainder) =
 				U512::div_mod(mul_normalised_mantissa, U512::from(denominator)); // Note that d can never be zero as mul_normalised_mantissa always has at least one bit
 																 // set above the 256th bit.
-			let d = if div_remainder.is_zero() { d } else { d + U512::one() };
+			let d = if !ROUND_UP || div_remainder.is_zero() { d } else { d + U512::one() };
 			let normalise_shift = d.leading_zeros();
 			// We right shift and use the lower 256 bits for the mantissa
 			let shift_bits = 256 - normalise_shift;
 			let (d, shift_remainder) = Self::right_shift_mod(d, shift_bits.into());
 			let d = U256::try_from(d).unwrap();
 
-			(if shift_remainder.is_zero() { d } else { d + U256::one() }, normalise_shift)
+			(
+				if !ROUND_UP || shift_remainder.is_zero() { d } else { d + U256::one() },
+				normalise_shift,
+			)
 		};
 
 		assert!(!mul_div_normalised_mantissa.is_zero());
@@ -508,17 +522,18 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 	}
 
 	/// Swaps the specified Amount into the other currency until sqrt_price_limit is reached (If
-	/// Some), and returns the resulting Amount and the remaining input Amount. The direction of the
-	/// swap is controlled by the generic type parameter `SD`, by setting it to `ZeroToOne` or
-	/// `OneToZero`. Note sqrt_price_limit is inclusive.
+	/// Some), and returns the resulting Amount, the remaining input Amount and the fees paid to the
+	/// pool. The direction of the swap is controlled by the generic type parameter `SD`, by setting
+	/// it to `ZeroToOne` or `OneToZero`. Note sqrt_price_limit is inclusive.
 	///
 	/// This function never panics
 	pub(super) fn swap<SD: SwapDirection>(
 		&mut self,
 		mut amount: Amount,
 		sqrt_price_limit: Option<SqrtPriceQ64F96>,
-	) -> (Amount, Amount) {
+	) -> (Amount, Amount, Amount) {
 		let mut total_output_amount = U256::zero();
+		let mut total_fees = U256::zero();
 
 		while let Some((sqrt_price, mut fixed_pool_entry)) = (!amount.is_zero())
 			.then_some(())
@@ -601,12 +616,96 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 				self.total_fees_earned[SD::INPUT_SIDE].saturating_add(fees_taken);
 
 			total_output_amount = total_output_amount.saturating_add(output_amount);
+			total_fees = total_fees.saturating_add(fees_taken);
+		}
+
+		self.total_swap_outputs[!SD::INPUT_SIDE] =
+			self.total_swap_outputs[!SD::INPUT_SIDE].saturating_add(total_output_amount);
+
+		(total_output_amount, amount, total_fees)
+	}
+
+	/// Swaps the input currency until the specified output Amount has been bought or
+	/// sqrt_price_limit is reached (If Some), and returns the required input Amount (Including
+	/// fees), the output Amount bought and the fees paid to the pool. The output may slightly
+	/// exceed the specified Amount, as the liquidity used from a partially filled pool is rounded
+	/// up. The direction of the swap is controlled by the generic type parameter `SD`, by setting
+	/// it to `ZeroToOne` or `OneToZero`. Note sqrt_price_limit is inclusive.
+	///
+	/// This function never panics
+	pub(super) fn swap_exact_output<SD: SwapDirection>(
+		&mut self,
+		mut amount: Amount,
+		sqrt_price_limit: Option<SqrtPriceQ64F96>,
+	) -> (Amount, Amount, Amount) {
+		let mut total_input_amount = U256::zero();
+		let mut total_output_amount = U256::zero();
+		let mut total_fees = U256::zero();
+
+		while let Some((sqrt_price, mut fixed_pool_entry)) = (!amount.is_zero())
+			.then_some(())
+			.and_then(|()| SD::best_priced_fixed_pool(&mut self.fixed_pools[!SD::INPUT_SIDE]))
+			.map(|entry| (*entry.key(), entry))
+			.filter(|(sqrt_price, _)| {
+				sqrt_price_limit.map_or(true, |sqrt_price_limit| {
+					!SD::sqrt_price_op_more_than(*sqrt_price, sqrt_price_limit)
+				})
+			}) {
+			let fixed_pool = fixed_pool_entry.get_mut();
+			let price = sqrt_price_to_price(sqrt_price);
+
+			let output_amount = if amount >= fixed_pool.available {
+				fixed_pool_entry.remove().available
+			} else {
+				// We calculate next_percent_remaining so that it is an under-estimate of the
+				// remaining liquidity, so the liquidity used by the swap is at least the requested
+				// amount. The swapper pays for, and receives, all of the used liquidity.
+				let next_percent_remaining = FloatBetweenZeroAndOne::mul_div_floor(
+					&fixed_pool.percent_remaining,
+					/* Cannot underflow as amount < fixed_pool.available */
+					fixed_pool.available - amount,
+					fixed_pool.available,
+				);
+
+				let output_amount = fixed_pool.available -
+					FloatBetweenZeroAndOne::integer_mul_div(
+						fixed_pool.available,
+						&next_percent_remaining,
+						&fixed_pool.percent_remaining,
+					)
+					.1;
+
+				assert!(output_amount >= amount);
+
+				fixed_pool.percent_remaining = next_percent_remaining;
+				fixed_pool.available -= output_amount;
+
+				output_amount
+			};
+
+			let swapped_amount = SD::input_amount_ceil(output_amount, price);
+			let fees = mul_div_ceil(
+				swapped_amount,
+				U256::from(self.fee_hundredth_pips),
+				U256::from(ONE_IN_HUNDREDTH_PIPS - self.fee_hundredth_pips),
+			); /* Will not overflow as fee_hundredth_pips <= ONE_IN_HUNDREDTH_PIPS / 2 */
+
+			self.total_swap_inputs[SD::INPUT_SIDE] =
+				self.total_swap_inputs[SD::INPUT_SIDE].saturating_add(swapped_amount);
+			self.total_fees_earned[SD::INPUT_SIDE] =
+				self.total_fees_earned[SD::INPUT_SIDE].saturating_add(fees);
+
+			total_input_amount =
+				total_input_amount.saturating_add(swapped_amount).saturating_add(fees);
+			total_output_amount = total_output_amount.saturating_add(output_amount);
+			total_fees = total_fees.saturating_add(fees);
+			amount = amount.saturating_sub(output_amount);
 		}
 
 		self.total_swap_outputs[!SD::INPUT_SIDE] =
 			self.total_swap_outputs[!SD::INPUT_SIDE].saturating_add(total_output_amount);
 
-		(total_output_amount, amount)
+		(total_input_amount, total_output_amount, total_fees)
 	}
 
 	fn collect_from_position<SD: SwapDirection>(
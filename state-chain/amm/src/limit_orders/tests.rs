@@ -525,7 +525,7 @@ fn burn() {
 				)),
 				(Collected::default(), PositionInfo::new(amount))
 			);
-			assert_eq!(pool_state.swap::<SD>(amount, None), (amount, 0.into()));
+			assert_eq!(pool_state.swap::<SD>(amount, None), (amount, 0.into(), 0.into()));
 			assert_eq!(
 				assert_ok!(pool_state.collect_and_burn::<SD>(
 					&LiquidityProvider::from([0; 32]),
@@ -559,7 +559,7 @@ fn burn() {
 				)),
 				(Collected::default(), PositionInfo::new(amount))
 			);
-			assert_eq!(pool_state.swap::<SD>(swap, None), (expected_output, 0.into()));
+			assert_eq!(pool_state.swap::<SD>(swap, None), (expected_output, 0.into(), 0.into()));
 			assert_eq!(
 				assert_ok!(pool_state.collect_and_burn::<SD>(
 					&LiquidityProvider::from([0; 32]),
@@ -598,7 +598,7 @@ fn swap() {
 					0,
 					1000.into()
 				));
-				assert_eq!(pool_state.swap::<SD>(swap, None), (output, 0.into()));
+				assert_eq!(pool_state.swap::<SD>(swap, None), (output, 0.into(), 0.into()));
 			}
 			{
 				let mut pool_state = PoolState::new(0).unwrap();
@@ -613,14 +613,14 @@ fn swap() {
 					tick,
 					500.into()
 				));
-				assert_eq!(pool_state.swap::<SD>(swap, None), (output, 0.into()));
+				assert_eq!(pool_state.swap::<SD>(swap, None), (output, 0.into(), 0.into()));
 			}
 			{
 				let mut pool_state = PoolState::new(0).unwrap();
 				let tick = 0;
 				assert_ok!(pool_state.collect_and_mint::<SD>(&[1u8; 32].into(), tick, 500.into()));
 				assert_ok!(pool_state.collect_and_mint::<SD>(&[2u8; 32].into(), tick, 500.into()));
-				assert_eq!(pool_state.swap::<SD>(swap, None), (output, 0.into()));
+				assert_eq!(pool_state.swap::<SD>(swap, None), (output, 0.into(), 0.into()));
 			}
 		}
 		{
@@ -630,7 +630,10 @@ fn swap() {
 				0,
 				1000.into()
 			));
-			assert_eq!(pool_state.swap::<SD>(1000.into(), None), (900.into(), 0.into()));
+			assert_eq!(
+				pool_state.swap::<SD>(1000.into(), None),
+				(900.into(), 0.into(), 100.into())
+			);
 		}
 	}
 
@@ -656,7 +659,7 @@ fn swap() {
 					tick_at_sqrt_price(sqrt_price_at_tick(tick) * U256::from(4).integer_sqrt()),
 				100000000.into()
 			));
-			let (output, remaining) = pool_state.swap::<ZeroToOne>(75000000.into(), None);
+			let (output, remaining, _fees) = pool_state.swap::<ZeroToOne>(75000000.into(), None);
 			assert!(range.contains(&output));
 			assert_eq!(remaining, Amount::zero());
 		}
@@ -679,7 +682,7 @@ fn swap() {
 					tick_at_sqrt_price(sqrt_price_at_tick(tick) * U256::from(4).integer_sqrt()),
 				100000000.into()
 			));
-			let (output, remaining) = pool_state.swap::<OneToZero>(180000000.into(), None);
+			let (output, remaining, _fees) = pool_state.swap::<OneToZero>(180000000.into(), None);
 			assert!(range.contains(&output));
 			assert_eq!(remaining, Amount::zero());
 		}
@@ -699,7 +702,10 @@ fn swap() {
 			tick_at_sqrt_price(sqrt_price_at_tick(tick) * U256::from(4).integer_sqrt()),
 			100.into()
 		));
-		assert_eq!(pool_state.swap::<ZeroToOne>(150.into(), None), (200.into(), 24.into()));
+		assert_eq!(
+			pool_state.swap::<ZeroToOne>(150.into(), None),
+			(200.into(), 24.into(), 0.into())
+		);
 	}
 	{
 		let mut pool_state = PoolState::new(0).unwrap();
@@ -714,7 +720,10 @@ fn swap() {
 			tick_at_sqrt_price(sqrt_price_at_tick(tick) * U256::from(4).integer_sqrt()),
 			100.into()
 		));
-		assert_eq!(pool_state.swap::<OneToZero>(550.into(), None), (200.into(), 50.into()));
+		assert_eq!(
+			pool_state.swap::<OneToZero>(550.into(), None),
+			(200.into(), 50.into(), 0.into())
+		);
 	}
 }
 
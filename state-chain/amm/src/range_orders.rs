@@ -218,6 +218,14 @@ pub(super) trait SwapDirection: crate::common::SwapDirection {
 		amount: Amount,
 	) -> SqrtPriceQ64F96;
 
+	/// Calculates where the current price will be after a swap that outputs amount given the
+	/// current price and a specific amount of liquidity
+	fn next_sqrt_price_from_output_amount(
+		sqrt_price_current: SqrtPriceQ64F96,
+		liquidity: Liquidity,
+		amount: Amount,
+	) -> SqrtPriceQ64F96;
+
 	/// For a given tick calculates the change in current liquidity when that tick is crossed
 	fn liquidity_delta_on_crossing_tick(tick_liquidity: &TickDelta) -> i128;
 
@@ -285,6 +293,19 @@ impl SwapDirection for ZeroToOne {
 		)
 	}
 
+	fn next_sqrt_price_from_output_amount(
+		sqrt_price_current: SqrtPriceQ64F96,
+		liquidity: Liquidity,
+		amount: Amount,
+	) -> SqrtPriceQ64F96 {
+		assert!(0 < liquidity);
+
+		// Will not underflow as function is not called if amount >= amount_available_at_target,
+		// and the price delta is rounded up so the output amount is at least amount
+		sqrt_price_current -
+			mul_div_ceil(amount, U256::one() << SQRT_PRICE_FRACTIONAL_BITS, liquidity)
+	}
+
 	fn liquidity_delta_on_crossing_tick(tick_liquidity: &TickDelta) -> i128 {
 		-tick_liquidity.liquidity_delta
 	}
@@ -341,6 +362,26 @@ impl SwapDirection for OneToZero {
 			mul_div_floor(amount, U256::one() << SQRT_PRICE_FRACTIONAL_BITS, liquidity)
 	}
 
+	fn next_sqrt_price_from_output_amount(
+		sqrt_price_current: SqrtPriceQ64F96,
+		liquidity: Liquidity,
+		amount: Amount,
+	) -> SqrtPriceQ64F96 {
+		assert!(0 < liquidity);
+		assert!(SqrtPriceQ64F96::zero() < sqrt_price_current);
+
+		let liquidity = U256::from(liquidity) << SQRT_PRICE_FRACTIONAL_BITS;
+
+		// Subtraction will not underflow as function is not called if amount >=
+		// amount_available_at_target, which is less than liquidity / sqrt_price_current. The
+		// result is rounded up so the output amount is at least amount.
+		mul_div_ceil(
+			liquidity,
+			sqrt_price_current,
+			U512::from(liquidity) - U256::full_mul(amount, sqrt_price_current),
+		)
+	}
+
 	fn liquidity_delta_on_crossing_tick(tick_liquidity: &TickDelta) -> i128 {
 		tick_liquidity.liquidity_delta
 	}
@@ -520,6 +561,21 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		SD::further_liquidity(self.current_tick).then_some(self.current_sqrt_price)
 	}
 
+	/// Returns the total liquidity/depth at the current price.
+	///
+	/// This function never panics
+	pub(super) fn current_liquidity(&self) -> Liquidity {
+		self.current_liquidity
+	}
+
+	/// Returns the current tick. Positions with `lower_tick <= current_tick < upper_tick` are in
+	/// range.
+	///
+	/// This function never panics
+	pub(super) fn current_tick(&self) -> Tick {
+		self.current_tick
+	}
+
 	/// Calculates the fees owed to the specified position, resets the fees owed for that position
 	/// to zero, calls `try_debit` passing the Amounts required to add the `minted_liquidity` to the
 	/// position. If `try_debit` returns `Ok(t)` the position will be created if it didn't already
@@ -752,17 +808,18 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 	}
 
 	/// Swaps the specified Amount into the other currency until sqrt_price_limit is reached (If
-	/// Some), and returns the resulting Amount and the remaining input Amount. The direction of the
-	/// swap is controlled by the generic type parameter `SD`, by setting it to `ZeroToOne` or
-	/// `OneToZero`.
+	/// Some), and returns the resulting Amount, the remaining input Amount and the fees paid to the
+	/// pool. The direction of the swap is controlled by the generic type parameter `SD`, by setting
+	/// it to `ZeroToOne` or `OneToZero`.
 	///
 	/// This function never panics
 	pub(super) fn swap<SD: SwapDirection>(
 		&mut self,
 		mut amount: Amount,
 		sqrt_price_limit: Option<U256>,
-	) -> (Amount, Amount) {
+	) -> (Amount, Amount, Amount) {
 		let mut total_output_amount = Amount::zero();
+		let mut total_fees = Amount::zero();
 
 		// DIFF: This behaviour is different than Uniswap's. As Solidity doesn't have an ordered map
 		// container, there is a fixed limit to how far the price can move in one iteration of the
@@ -852,6 +909,7 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 
 				// TODO: Prove this does not underflow
 				amount -= amount_swapped + fees;
+				total_fees = total_fees.saturating_add(fees);
 
 				// DIFF: This behaviour is different to Uniswap's, we saturate instead of
 				// overflowing/bricking the pool. This means we just stop giving LPs fees, but
@@ -891,7 +949,130 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		self.total_swap_outputs[!SD::INPUT_SIDE] =
 			self.total_swap_outputs[!SD::INPUT_SIDE].saturating_add(total_output_amount);
 
-		(total_output_amount, amount)
+		(total_output_amount, amount, total_fees)
+	}
+
+	/// Swaps the input currency until the specified output Amount has been bought or
+	/// sqrt_price_limit is reached (If Some), and returns the required input Amount (Including
+	/// fees), the output Amount bought and the fees paid to the pool. The direction of the swap is
+	/// controlled by the generic type parameter `SD`, by setting it to `ZeroToOne` or `OneToZero`.
+	///
+	/// This function never panics
+	pub(super) fn swap_exact_output<SD: SwapDirection>(
+		&mut self,
+		mut amount: Amount,
+		sqrt_price_limit: Option<U256>,
+	) -> (Amount, Amount, Amount) {
+		let mut total_input_amount = Amount::zero();
+		let mut total_output_amount = Amount::zero();
+		let mut total_fees = Amount::zero();
+
+		while let Some((tick_at_delta, delta)) = (!amount.is_zero() &&
+			sqrt_price_limit.map_or(true, |sqrt_price_limit| {
+				SD::sqrt_price_op_more_than(sqrt_price_limit, self.current_sqrt_price)
+			}))
+		.then_some(())
+		.and_then(|()| SD::next_liquidity_delta(self.current_tick, &mut self.liquidity_map))
+		{
+			let sqrt_price_at_delta = sqrt_price_at_tick(*tick_at_delta);
+
+			let sqrt_price_target = if let Some(sqrt_price_limit) = sqrt_price_limit {
+				if SD::sqrt_price_op_more_than(sqrt_price_at_delta, sqrt_price_limit) {
+					sqrt_price_limit
+				} else {
+					sqrt_price_at_delta
+				}
+			} else {
+				sqrt_price_at_delta
+			};
+
+			let sqrt_price_next = if self.current_liquidity == 0 {
+				sqrt_price_target
+			} else {
+				let amount_available_at_target = SD::output_amount_delta_floor(
+					self.current_sqrt_price,
+					sqrt_price_target,
+					self.current_liquidity,
+				);
+
+				let sqrt_price_next = if amount >= amount_available_at_target {
+					sqrt_price_target
+				} else {
+					SD::next_sqrt_price_from_output_amount(
+						self.current_sqrt_price,
+						self.current_liquidity,
+						amount,
+					)
+				};
+
+				// The price is rounded in favour of the pool, so the output at sqrt_price_next may
+				// exceed the requested amount. Like Uniswap, we only output the requested amount.
+				let output_amount = core::cmp::min(
+					amount,
+					SD::output_amount_delta_floor(
+						self.current_sqrt_price,
+						sqrt_price_next,
+						self.current_liquidity,
+					),
+				);
+
+				let amount_swapped = SD::input_amount_delta_ceil(
+					self.current_sqrt_price,
+					sqrt_price_next,
+					self.current_liquidity,
+				);
+				/* Will not overflow as fee_hundredth_pips <= ONE_IN_HUNDREDTH_PIPS / 2 */
+				let fees = mul_div_ceil(
+					amount_swapped,
+					U256::from(self.fee_hundredth_pips),
+					U256::from(ONE_IN_HUNDREDTH_PIPS - self.fee_hundredth_pips),
+				);
+
+				self.total_swap_inputs[SD::INPUT_SIDE] =
+					self.total_swap_inputs[SD::INPUT_SIDE].saturating_add(amount_swapped);
+				self.total_fees_earned[SD::INPUT_SIDE] =
+					self.total_fees_earned[SD::INPUT_SIDE].saturating_add(fees);
+
+				total_input_amount =
+					total_input_amount.saturating_add(amount_swapped).saturating_add(fees);
+				total_output_amount += output_amount;
+				total_fees = total_fees.saturating_add(fees);
+				amount -= output_amount;
+
+				// See `swap` for why we saturate here.
+				self.global_fee_growth[SD::INPUT_SIDE] = self.global_fee_growth[SD::INPUT_SIDE]
+					.saturating_add(mul_div_floor(
+						fees,
+						U256::from(1) << 128u32,
+						self.current_liquidity,
+					));
+
+				sqrt_price_next
+			};
+
+			assert!(!SD::sqrt_price_op_more_than(sqrt_price_next, sqrt_price_at_delta));
+
+			if sqrt_price_next == sqrt_price_at_delta {
+				delta.fee_growth_outside = SideMap::default()
+					.map(|side, ()| self.global_fee_growth[side] - delta.fee_growth_outside[side]);
+				self.current_sqrt_price = sqrt_price_next;
+				self.current_tick = SD::current_tick_after_crossing_tick(*tick_at_delta);
+
+				// Addition is guaranteed to never overflow, see test `max_liquidity`
+				self.current_liquidity = self
+					.current_liquidity
+					.checked_add_signed(SD::liquidity_delta_on_crossing_tick(delta))
+					.unwrap();
+			} else if self.current_sqrt_price != sqrt_price_next {
+				self.current_sqrt_price = sqrt_price_next;
+				self.current_tick = tick_at_sqrt_price(sqrt_price_next);
+			}
+		}
+
+		self.total_swap_outputs[!SD::INPUT_SIDE] =
+			self.total_swap_outputs[!SD::INPUT_SIDE].saturating_add(total_output_amount);
+
+		(total_input_amount, total_output_amount, total_fees)
 	}
 
 	fn validate_position_range<T>(
@@ -98,7 +98,7 @@ fn maximum_liquidity_swap() {
 		})
 		.fold(Default::default(), |acc, x| acc + x);
 
-	let (output, _remaining) = pool_state.swap::<OneToZero>(Amount::MAX, None);
+	let (output, _remaining, _fees) = pool_state.swap::<OneToZero>(Amount::MAX, None);
 
 	assert!(((minted_amounts[Side::Zero] - (MAX_TICK - MIN_TICK) /* Maximum rounding down by one per swap iteration */)..minted_amounts[Side::Zero]).contains(&output));
 }
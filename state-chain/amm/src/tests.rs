@@ -208,6 +208,84 @@ fn test_basic_swaps() {
 	inner(Order::Buy);
 }
 
+#[test]
+fn test_exact_output_swaps() {
+	fn inner(order: Order) {
+		{
+			let mut pool_state = PoolState {
+				limit_orders: limit_orders::PoolState::new(0).unwrap(),
+				range_orders: range_orders::PoolState::new(0, MIN_SQRT_PRICE).unwrap(),
+			};
+
+			assert_eq!(pool_state.swap_exact_output(order, 0.into(), None), (0.into(), 0.into()));
+			assert_eq!(
+				pool_state.swap_exact_output(order, 1000.into(), None),
+				(0.into(), 0.into())
+			);
+		}
+
+		{
+			let mut pool_state = PoolState {
+				limit_orders: limit_orders::PoolState::new(0).unwrap(),
+				range_orders: range_orders::PoolState::new(0, MIN_SQRT_PRICE).unwrap(),
+			};
+
+			let amount: Amount = 1000.into();
+
+			assert_ok!(pool_state.collect_and_mint_limit_order(
+				&LiquidityProvider::from([0; 32]),
+				!order,
+				0,
+				amount
+			));
+
+			// At a price of 1 with no fees the required input equals the output.
+			assert_eq!(
+				pool_state.swap_exact_output(order, 400.into(), None),
+				(400.into(), 400.into())
+			);
+			assert_eq!(
+				pool_state.swap_exact_output(order, 1000.into(), None),
+				(600.into(), 600.into())
+			);
+			assert_eq!(pool_state.swap(order, Amount::MAX, None), (0.into(), Amount::MAX));
+		}
+
+		{
+			let mut pool_state = PoolState {
+				limit_orders: limit_orders::PoolState::new(3000).unwrap(),
+				range_orders: range_orders::PoolState::new(3000, SqrtPriceQ64F96::from(1) << 96)
+					.unwrap(),
+			};
+
+			assert_ok!(pool_state.collect_and_mint_range_order(
+				&LiquidityProvider::from([0; 32]),
+				-100..100,
+				range_orders::Size::Liquidity { liquidity: 1_000_000_000_000 },
+				Result::<_, Infallible>::Ok
+			));
+			assert_ok!(pool_state.collect_and_mint_limit_order(
+				&LiquidityProvider::from([0; 32]),
+				!order,
+				0,
+				1000.into()
+			));
+
+			// The output is bought from the limit order first, and then from the range order.
+			let output_amount: Amount = 1_000_000.into();
+			let (input_amount, bought_amount) =
+				pool_state.swap_exact_output(order, output_amount, None);
+			assert_eq!(bought_amount, output_amount);
+			assert!(input_amount > output_amount);
+			assert!(input_amount < output_amount * 101 / 100);
+			assert!(pool_state.limit_order_liquidity(!order).is_empty());
+		}
+	}
+
+	inner(Order::Sell);
+	inner(Order::Buy);
+}
+
 #[test]
 fn test_sqrt_price_to_price() {
 	assert_eq!(
@@ -16,10 +16,11 @@ use cf_chains::{
 	btc::{BitcoinFeeInfo, BitcoinTrackedData},
 	dot::{PolkadotTrackedData, RuntimeVersion},
 	eth::EthereumTrackedData,
-	Bitcoin, ChainState, Ethereum, Polkadot,
+	Assethub, Bitcoin, ChainState, Ethereum, Polkadot,
 };
 use state_chain_runtime::{
-	BitcoinChainTrackingConfig, EthereumChainTrackingConfig, PolkadotChainTrackingConfig,
+	AssethubChainTrackingConfig, BitcoinChainTrackingConfig, EthereumChainTrackingConfig,
+	PolkadotChainTrackingConfig,
 };
 
 pub const CURRENT_AUTHORITY_EMISSION_INFLATION_PERBILL: u32 = 28;
@@ -231,6 +232,18 @@ impl ExtBuilder {
 					tracked_data: BitcoinTrackedData { btc_fee_info: BitcoinFeeInfo::new(0) },
 				},
 			},
+			assethub_chain_tracking: AssethubChainTrackingConfig {
+				init_chain_state: ChainState::<Assethub> {
+					block_height: 0,
+					tracked_data: PolkadotTrackedData {
+						median_tip: 0,
+						runtime_version: RuntimeVersion {
+							spec_version: 17,
+							transaction_version: 17,
+						},
+					},
+				},
+			},
 			bitcoin_threshold_signer: Default::default(),
 			ethereum_threshold_signer: EthereumThresholdSignerConfig {
 				key: Some(ethereum_vault_key),
@@ -242,6 +255,7 @@ impl ExtBuilder {
 			polkadot_threshold_signer: Default::default(),
 			bitcoin_vault: Default::default(),
 			polkadot_vault: Default::default(),
+			assethub_vault: Default::default(),
 			environment: Default::default(),
 			liquidity_pools: Default::default(),
 			system: Default::default(),
@@ -249,6 +263,7 @@ impl ExtBuilder {
 			bitcoin_ingress_egress: Default::default(),
 			polkadot_ingress_egress: Default::default(),
 			ethereum_ingress_egress: Default::default(),
+			assethub_ingress_egress: Default::default(),
 		})
 	}
 }
@@ -327,6 +327,7 @@ fn can_process_ccm_via_swap_deposit_address() {
 			message: vec![0u8, 1u8, 2u8, 3u8, 4u8].try_into().unwrap(),
 			gas_budget,
 			cf_parameters: Default::default(),
+			fallback: None,
 		};
 
 		assert_ok!(Swapping::request_swap_deposit_address(
@@ -437,6 +438,7 @@ fn can_process_ccm_via_direct_deposit() {
 				message: vec![0u8, 1u8, 2u8, 3u8, 4u8].try_into().unwrap(),
 				gas_budget,
 				cf_parameters: Default::default(),
+				fallback: None,
 			},
 		};
 
@@ -787,6 +789,7 @@ fn can_resign_failed_ccm() {
 					message: vec![0u8, 1u8, 2u8, 3u8, 4u8].try_into().unwrap(),
 					gas_budget: 1_000,
 					cf_parameters: Default::default(),
+					fallback: None,
 				},
 			};
 
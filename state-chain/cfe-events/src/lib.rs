@@ -4,8 +4,8 @@
 mod tests;
 
 use cf_chains::{
-	btc::BitcoinCrypto, dot::PolkadotCrypto, evm::EvmCrypto, Bitcoin, Chain, ChainCrypto, Ethereum,
-	Polkadot,
+	btc::BitcoinCrypto, dot::PolkadotCrypto, evm::EvmCrypto, hub::Assethub, Bitcoin, Chain,
+	ChainCrypto, Ethereum, Polkadot,
 };
 use cf_primitives::{BroadcastId, CeremonyId, Ed25519PublicKey, EpochIndex, Ipv6Addr, Port};
 
@@ -66,4 +66,5 @@ pub enum CfeEvent<ValidatorId> {
 	BtcTxBroadcastRequest(TxBroadcastRequest<ValidatorId, Bitcoin>),
 	PeerIdRegistered { account_id: ValidatorId, pubkey: Ed25519PublicKey, port: Port, ip: Ipv6Addr },
 	PeerIdDeregistered { account_id: ValidatorId, pubkey: Ed25519PublicKey },
+	HubTxBroadcastRequest(TxBroadcastRequest<ValidatorId, Assethub>),
 }
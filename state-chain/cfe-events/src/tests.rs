@@ -150,6 +150,14 @@ fn event_decoding() {
 				nominee: AccountId::from([1; 32]),
 				payload: BitcoinTransactionData { encoded_transaction: vec![2, 0, 1, 7, 23, 241] },
 			}), "09010000000101010101010101010101010101010101010101010101010101010101010101180200010717f1");
+
+		check_encoding(CfeEvent::HubTxBroadcastRequest(TxBroadcastRequest {
+				broadcast_id: 1,
+				nominee: AccountId::from([1; 32]),
+				payload: PolkadotTransactionData {
+					encoded_extrinsic: vec![217, 7, 132, 0, 102, 145],
+				},
+			}), "0c01000000010101010101010101010101010101010101010101010101010101010101010118d90784006691");
 	}
 
 	// P2P registration/deregistration
@@ -1,6 +1,9 @@
 extern crate alloc;
 
-use crate::{btc::ScriptPubkey, dot::PolkadotAccountId, eth::Address as EthereumAddress, Chain};
+use crate::{
+	btc::ScriptPubkey, dot::PolkadotAccountId, eth::Address as EthereumAddress,
+	hub::AssethubAccountId, Chain,
+};
 use cf_primitives::{ChannelId, ForeignChain, NetworkEnvironment};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -14,6 +17,7 @@ use sp_std::{fmt::Debug, vec::Vec};
 pub enum AddressDerivationError {
 	MissingPolkadotVault,
 	MissingBitcoinVault,
+	MissingAssethubVault,
 	BitcoinChannelIdTooLarge,
 }
 
@@ -49,6 +53,7 @@ pub enum ForeignChainAddress {
 	Eth(EthereumAddress),
 	Dot(PolkadotAccountId),
 	Btc(ScriptPubkey),
+	Hub(AssethubAccountId),
 }
 
 impl ForeignChainAddress {
@@ -57,6 +62,7 @@ impl ForeignChainAddress {
 			ForeignChainAddress::Eth(_) => ForeignChain::Ethereum,
 			ForeignChainAddress::Dot(_) => ForeignChain::Polkadot,
 			ForeignChainAddress::Btc(_) => ForeignChain::Bitcoin,
+			ForeignChainAddress::Hub(_) => ForeignChain::Assethub,
 		}
 	}
 }
@@ -68,6 +74,7 @@ pub enum EncodedAddress {
 	Eth([u8; 20]),
 	Dot([u8; 32]),
 	Btc(Vec<u8>),
+	Hub([u8; 32]),
 }
 
 pub trait AddressConverter: Sized {
@@ -95,6 +102,9 @@ impl core::fmt::Display for EncodedAddress {
 						.unwrap_or("The address cant be decoded from the utf8 encoded bytes")
 				)
 			},
+			EncodedAddress::Hub(addr) => {
+				write!(f, "0x{}", hex::encode(&addr[..]))
+			},
 		}
 	}
 }
@@ -126,6 +136,17 @@ impl TryFrom<ForeignChainAddress> for PolkadotAccountId {
 	}
 }
 
+impl TryFrom<ForeignChainAddress> for AssethubAccountId {
+	type Error = AddressError;
+
+	fn try_from(address: ForeignChainAddress) -> Result<Self, Self::Error> {
+		match address {
+			ForeignChainAddress::Hub(addr) => Ok(addr),
+			_ => Err(AddressError::InvalidAddress),
+		}
+	}
+}
+
 impl TryFrom<ForeignChainAddress> for ScriptPubkey {
 	type Error = AddressError;
 
@@ -155,6 +176,12 @@ impl From<ScriptPubkey> for ForeignChainAddress {
 	}
 }
 
+impl From<AssethubAccountId> for ForeignChainAddress {
+	fn from(account_id: AssethubAccountId) -> ForeignChainAddress {
+		ForeignChainAddress::Hub(account_id)
+	}
+}
+
 impl EncodedAddress {
 	pub fn from_chain_bytes(chain: ForeignChain, bytes: Vec<u8>) -> Result<Self, &'static str> {
 		match chain {
@@ -175,6 +202,14 @@ impl EncodedAddress {
 				Ok(EncodedAddress::Dot(address))
 			},
 			ForeignChain::Bitcoin => Ok(EncodedAddress::Btc(bytes)),
+			ForeignChain::Assethub => {
+				if bytes.len() != 32 {
+					return Err("Invalid Assethub address length")
+				}
+				let mut address = [0u8; 32];
+				address.copy_from_slice(&bytes);
+				Ok(EncodedAddress::Hub(address))
+			},
 		}
 	}
 }
@@ -189,6 +224,7 @@ pub fn to_encoded_address<GetNetwork: FnOnce() -> NetworkEnvironment>(
 		ForeignChainAddress::Btc(script_pubkey) => EncodedAddress::Btc(
 			script_pubkey.to_address(&network_environment().into()).as_bytes().to_vec(),
 		),
+		ForeignChainAddress::Hub(address) => EncodedAddress::Hub(*address.aliased_ref()),
 	}
 }
 
@@ -208,6 +244,8 @@ pub fn try_from_encoded_address<GetNetwork: FnOnce() -> NetworkEnvironment>(
 			)
 			.map_err(|_| ())?,
 		)),
+		EncodedAddress::Hub(address_bytes) =>
+			Ok(ForeignChainAddress::Hub(AssethubAccountId::from_aliased(address_bytes))),
 	}
 }
 
@@ -250,6 +288,17 @@ impl ToHumanreadableAddress for PolkadotAccountId {
 	}
 }
 
+impl ToHumanreadableAddress for AssethubAccountId {
+	#[cfg(feature = "std")]
+	type Humanreadable = crate::dot::SubstrateNetworkAddress;
+
+	#[cfg(feature = "std")]
+	fn to_humanreadable(&self, _network_environment: NetworkEnvironment) -> Self::Humanreadable {
+		// Asset Hub uses the same SS58 prefix as the relay chain.
+		crate::dot::SubstrateNetworkAddress::polkadot(*self.aliased_ref())
+	}
+}
+
 #[cfg(feature = "std")]
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
@@ -262,6 +311,7 @@ pub enum ForeignChainAddressHumanreadable {
 	Eth(<EthereumAddress as ToHumanreadableAddress>::Humanreadable),
 	Dot(<PolkadotAccountId as ToHumanreadableAddress>::Humanreadable),
 	Btc(<ScriptPubkey as ToHumanreadableAddress>::Humanreadable),
+	Hub(<AssethubAccountId as ToHumanreadableAddress>::Humanreadable),
 }
 
 #[cfg(feature = "std")]
@@ -287,6 +337,8 @@ impl ToHumanreadableAddress for ForeignChainAddress {
 				ForeignChainAddressHumanreadable::Dot(address.to_humanreadable(network_environment)),
 			ForeignChainAddress::Btc(address) =>
 				ForeignChainAddressHumanreadable::Btc(address.to_humanreadable(network_environment)),
+			ForeignChainAddress::Hub(address) =>
+				ForeignChainAddressHumanreadable::Hub(address.to_humanreadable(network_environment)),
 		}
 	}
 }
@@ -1,6 +1,6 @@
 #[cfg(feature = "runtime-benchmarks")]
 use cf_primitives::{
-	chains::assets::{btc, dot, eth},
+	chains::assets::{btc, dot, eth, hub},
 	Asset,
 };
 
@@ -74,6 +74,13 @@ impl BenchmarkValue for dot::Asset {
 	}
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkValue for hub::Asset {
+	fn benchmark_value() -> Self {
+		hub::Asset::HubDot
+	}
+}
+
 // TODO: Look at deduplicating this by including it in the macro
 #[cfg(feature = "runtime-benchmarks")]
 impl BenchmarkValue for btc::Asset {
@@ -2,6 +2,7 @@ pub mod api;
 pub mod benchmarking;
 pub mod deposit_address;
 pub mod utxo_selection;
+pub mod vault_swap_encoding;
 
 extern crate alloc;
 use core::{cmp::max, mem::size_of};
@@ -0,0 +1,181 @@
+//! Encoding of the swap parameters carried by Bitcoin vault swaps.
+//!
+//! A vault swap is a Bitcoin transaction that pays the current vault address directly, without
+//! first opening a deposit channel. The transaction must be laid out as follows:
+//!
+//! - Output 0 pays the vault address. Its value is the amount to be swapped.
+//! - Output 1 is an `OP_RETURN` output whose data is the payload described below.
+//! - Output 2 pays the refund address. Its value is not used, so it is usually the change output.
+//!   If the swap can't be executed, or would return less than the minimum output, the deposit is
+//!   refunded to this address.
+//!
+//! The payload starts with a single version byte, followed by the SCALE encoding of the
+//! parameters for that version. Version 0 encodes a [VaultSwapParameters]:
+//!
+//! | Field               | Encoding                                        | Size        |
+//! |---------------------|-------------------------------------------------|-------------|
+//! | `output_asset`      | The asset's index as a single byte              | 1           |
+//! | `output_address`    | [EncodedAddress]: variant index and raw bytes   | 21 - 64     |
+//! | `min_output_amount` | Compact encoded `u128`                          | 1 - 17      |
+//! | `broker_id`         | The broker's 32 byte account id                 | 32          |
+//! | `broker_commission` | Compact encoded `u16` in basis points           | 1 - 3       |
+//!
+//! Fixed size addresses take 21 or 33 bytes. Bitcoin addresses are encoded as a compact length
+//! followed by the address string, so they take up to 64 bytes.
+//!
+//! The whole payload, including the version byte, must fit into the 80 bytes that nodes relay in
+//! an `OP_RETURN` output. This leaves at most 44 bytes for the output address, so the longer
+//! Bitcoin addresses, such as taproot addresses, can't be used as the output address.
+
+use crate::address::EncodedAddress;
+use cf_primitives::{Asset, AssetAmount, BasisPoints};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// The version of the payload that is produced by [encode_vault_swap_payload].
+pub const CURRENT_VAULT_SWAP_PAYLOAD_VERSION: u8 = 0;
+
+/// The maximum number of bytes of data relayed in an `OP_RETURN` output.
+pub const MAX_VAULT_SWAP_PAYLOAD_LENGTH: usize = 80;
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct VaultSwapParameters<AccountId> {
+	pub output_asset: Asset,
+	pub output_address: EncodedAddress,
+	#[codec(compact)]
+	pub min_output_amount: AssetAmount,
+	pub broker_id: AccountId,
+	#[codec(compact)]
+	pub broker_commission_bps: BasisPoints,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultSwapPayloadError {
+	/// The encoded payload doesn't fit into an `OP_RETURN` output.
+	PayloadTooLong,
+	/// The payload is empty or has a version this code doesn't know about.
+	UnsupportedVersion,
+	/// The payload could not be decoded into the parameters for its version.
+	InvalidPayload,
+}
+
+/// Encodes the parameters as a payload of the current version.
+pub fn encode_vault_swap_payload<AccountId: Encode>(
+	parameters: &VaultSwapParameters<AccountId>,
+) -> Result<Vec<u8>, VaultSwapPayloadError> {
+	let payload = (CURRENT_VAULT_SWAP_PAYLOAD_VERSION, parameters).encode();
+	if payload.len() > MAX_VAULT_SWAP_PAYLOAD_LENGTH {
+		return Err(VaultSwapPayloadError::PayloadTooLong)
+	}
+	Ok(payload)
+}
+
+/// Decodes a payload of any supported version. Trailing bytes are rejected so that each set of
+/// parameters has exactly one valid encoding.
+pub fn decode_vault_swap_payload<AccountId: Decode>(
+	payload: &[u8],
+) -> Result<VaultSwapParameters<AccountId>, VaultSwapPayloadError> {
+	match payload.split_first() {
+		Some((&CURRENT_VAULT_SWAP_PAYLOAD_VERSION, mut data)) => {
+			let parameters = VaultSwapParameters::decode(&mut data)
+				.map_err(|_| VaultSwapPayloadError::InvalidPayload)?;
+			if !data.is_empty() {
+				return Err(VaultSwapPayloadError::InvalidPayload)
+			}
+			Ok(parameters)
+		},
+		_ => Err(VaultSwapPayloadError::UnsupportedVersion),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use codec::Compact;
+
+	fn parameters() -> VaultSwapParameters<[u8; 32]> {
+		VaultSwapParameters {
+			output_asset: Asset::Dot,
+			output_address: EncodedAddress::Dot([0x01; 32]),
+			min_output_amount: 1_000_000_000_000_000_000_000,
+			broker_id: [0xbb; 32],
+			broker_commission_bps: 100,
+		}
+	}
+
+	#[test]
+	fn payload_round_trips() {
+		let payload = encode_vault_swap_payload(&parameters()).unwrap();
+
+		assert_eq!(payload[0], CURRENT_VAULT_SWAP_PAYLOAD_VERSION);
+		assert!(payload.len() <= MAX_VAULT_SWAP_PAYLOAD_LENGTH);
+		assert_eq!(
+			payload.len(),
+			1 + 1 +
+				33 + Compact(parameters().min_output_amount).encoded_size() +
+				32 + Compact(parameters().broker_commission_bps).encoded_size()
+		);
+		assert_eq!(decode_vault_swap_payload(&payload), Ok(parameters()));
+	}
+
+	#[test]
+	fn payload_layout_is_stable() {
+		let payload = encode_vault_swap_payload(&VaultSwapParameters {
+			output_asset: Asset::Eth,
+			output_address: EncodedAddress::Eth([0xee; 20]),
+			min_output_amount: 1,
+			broker_id: [0xbb; 32],
+			broker_commission_bps: 10,
+		})
+		.unwrap();
+
+		assert_eq!(
+			hex::encode(payload),
+			"000100eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee04bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb28"
+		);
+	}
+
+	#[test]
+	fn invalid_payloads_are_rejected() {
+		let payload = encode_vault_swap_payload(&parameters()).unwrap();
+
+		let mut wrong_version = payload.clone();
+		wrong_version[0] = CURRENT_VAULT_SWAP_PAYLOAD_VERSION + 1;
+		assert_eq!(
+			decode_vault_swap_payload::<[u8; 32]>(&wrong_version),
+			Err(VaultSwapPayloadError::UnsupportedVersion)
+		);
+		assert_eq!(
+			decode_vault_swap_payload::<[u8; 32]>(&[]),
+			Err(VaultSwapPayloadError::UnsupportedVersion)
+		);
+
+		let mut trailing_bytes = payload.clone();
+		trailing_bytes.push(0);
+		assert_eq!(
+			decode_vault_swap_payload::<[u8; 32]>(&trailing_bytes),
+			Err(VaultSwapPayloadError::InvalidPayload)
+		);
+		assert_eq!(
+			decode_vault_swap_payload::<[u8; 32]>(&payload[..payload.len() - 1]),
+			Err(VaultSwapPayloadError::InvalidPayload)
+		);
+	}
+
+	#[test]
+	fn payloads_that_do_not_fit_are_rejected() {
+		assert_eq!(
+			encode_vault_swap_payload(&VaultSwapParameters {
+				output_asset: Asset::Btc,
+				output_address: EncodedAddress::Btc(
+					b"bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297".to_vec()
+				),
+				min_output_amount: 1,
+				broker_id: [0xbb; 32],
+				broker_commission_bps: 0,
+			}),
+			Err(VaultSwapPayloadError::PayloadTooLong)
+		);
+	}
+}
@@ -497,7 +497,14 @@ pub enum PolkadotRuntimeCall {
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
-pub enum SystemCall {}
+pub enum SystemCall {
+	/// Make some on-chain remark and emit event.
+	#[codec(index = 7u8)]
+	remark_with_event {
+		#[allow(missing_docs)]
+		remark: Vec<u8>,
+	},
+}
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
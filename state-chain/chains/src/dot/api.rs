@@ -1,4 +1,5 @@
 pub mod batch_fetch_and_transfer;
+pub mod execute_x_swap_and_call;
 pub mod rotate_vault_proxy;
 
 use super::{
@@ -114,14 +115,21 @@ impl<E> ExecutexSwapAndCall<Polkadot> for PolkadotApi<E>
 where
 	E: PolkadotEnvironment + ReplayProtectionProvider<Polkadot>,
 {
+	/// Polkadot has no contracts to call, so the message is delivered as a memo alongside the
+	/// transfer. Messages to Polkadot can't carry a gas budget, so it is always zero.
 	fn new_unsigned(
-		_transfer_param: TransferAssetParams<Polkadot>,
+		transfer_param: TransferAssetParams<Polkadot>,
 		_source_chain: ForeignChain,
 		_source_address: Option<ForeignChainAddress>,
 		_gas_budget: <Polkadot as Chain>::ChainAmount,
-		_message: Vec<u8>,
+		message: Vec<u8>,
 	) -> Result<Self, DispatchError> {
-		Err(DispatchError::Other("Not implemented"))
+		Ok(Self::ExecuteXSwapAndCall(execute_x_swap_and_call::extrinsic_builder(
+			E::replay_protection(false),
+			transfer_param,
+			message,
+			E::try_vault_account().ok_or(DispatchError::Other("Vault account not set"))?,
+		)))
 	}
 }
 
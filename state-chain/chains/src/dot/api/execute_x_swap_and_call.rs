@@ -0,0 +1,92 @@
+use crate::{
+	dot::{
+		BalancesCall, Polkadot, PolkadotAccountId, PolkadotAccountIdLookup,
+		PolkadotExtrinsicBuilder, PolkadotProxyType, PolkadotReplayProtection, PolkadotRuntimeCall,
+		ProxyCall, SystemCall, UtilityCall,
+	},
+	TransferAssetParams,
+};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+/// Transfers the egress amount from the vault and carries the message as a memo in a
+/// `remark_with_event`. Both calls are batched atomically so the memo is only recorded if the
+/// transfer succeeds.
+pub fn extrinsic_builder(
+	replay_protection: PolkadotReplayProtection,
+	transfer_param: TransferAssetParams<Polkadot>,
+	message: Vec<u8>,
+	vault_account: PolkadotAccountId,
+) -> PolkadotExtrinsicBuilder {
+	PolkadotExtrinsicBuilder::new(
+		replay_protection,
+		PolkadotRuntimeCall::Proxy(ProxyCall::proxy {
+			real: PolkadotAccountIdLookup::from(vault_account),
+			force_proxy_type: Some(PolkadotProxyType::Any),
+			call: Box::new(PolkadotRuntimeCall::Utility(UtilityCall::batch_all {
+				calls: vec![
+					PolkadotRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+						dest: PolkadotAccountIdLookup::from(transfer_param.to),
+						value: transfer_param.amount,
+					}),
+					PolkadotRuntimeCall::System(SystemCall::remark_with_event { remark: message }),
+				],
+			})),
+		}),
+	)
+}
+
+#[cfg(test)]
+mod test_execute_x_swap_and_call {
+
+	use super::*;
+	use crate::dot::{PolkadotPair, NONCE_1, RAW_SEED_1, RAW_SEED_2, TEST_RUNTIME_VERSION};
+	use cf_primitives::chains::assets;
+
+	#[test]
+	fn transfer_and_memo_are_batched_atomically() {
+		let vault_account = PolkadotPair::from_seed(&RAW_SEED_1).public_key();
+		let keypair_proxy = PolkadotPair::from_seed(&RAW_SEED_2);
+		let destination = PolkadotAccountId::from_aliased([7u8; 32]);
+
+		let mut builder = super::extrinsic_builder(
+			PolkadotReplayProtection {
+				nonce: NONCE_1,
+				signer: keypair_proxy.public_key(),
+				genesis_hash: Default::default(),
+			},
+			TransferAssetParams::<Polkadot> {
+				to: destination,
+				amount: 1_000,
+				asset: assets::dot::Asset::Dot,
+			},
+			b"hello".to_vec(),
+			vault_account,
+		);
+
+		assert_eq!(
+			builder.extrinsic_call,
+			PolkadotRuntimeCall::Proxy(ProxyCall::proxy {
+				real: PolkadotAccountIdLookup::from(vault_account),
+				force_proxy_type: Some(PolkadotProxyType::Any),
+				call: Box::new(PolkadotRuntimeCall::Utility(UtilityCall::batch_all {
+					calls: vec![
+						PolkadotRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+							dest: PolkadotAccountIdLookup::from(destination),
+							value: 1_000,
+						}),
+						PolkadotRuntimeCall::System(SystemCall::remark_with_event {
+							remark: b"hello".to_vec()
+						}),
+					],
+				})),
+			})
+		);
+
+		let payload = builder.get_signature_payload(
+			TEST_RUNTIME_VERSION.spec_version,
+			TEST_RUNTIME_VERSION.transaction_version,
+		);
+		builder.insert_signature(keypair_proxy.sign(&payload));
+		assert!(builder.is_signed());
+	}
+}
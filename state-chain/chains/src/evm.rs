@@ -4,7 +4,7 @@ pub mod tokenizable;
 
 use crate::*;
 use cf_primitives::ChannelId;
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, DecodeAll, Encode, MaxEncodedLen};
 use ethabi::ParamType;
 pub use ethabi::{
 	ethereum_types::{H256, U256},
@@ -637,6 +637,23 @@ pub struct TransactionFee {
 	pub gas_used: u128,
 }
 
+/// Parameters that can be passed in the `cfParameters` of a swap made directly through the Vault
+/// contract. They are SCALE encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum VaultSwapCfParameters {
+	/// The swapped amount is the maximum input of an exact output swap that buys
+	/// `output_amount` of the destination asset. Any unused input is refunded to the sender.
+	ExactOutput { output_amount: AssetAmount },
+}
+
+impl VaultSwapCfParameters {
+	/// Returns the parameters if `cf_parameters` is exactly their encoding. Other values, such as
+	/// empty `cf_parameters`, are ignored.
+	pub fn decode_from(cf_parameters: &[u8]) -> Option<Self> {
+		Self::decode_all(&mut &cf_parameters[..]).ok()
+	}
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
 	use super::*;
@@ -650,6 +667,15 @@ pub(crate) mod tests {
 		res
 	}
 
+	#[test]
+	fn vault_swap_cf_parameters_are_only_decoded_from_their_exact_encoding() {
+		let parameters = VaultSwapCfParameters::ExactOutput { output_amount: 1_000 };
+		let encoded = parameters.encode();
+		assert_eq!(VaultSwapCfParameters::decode_from(&encoded), Some(parameters));
+		assert_eq!(VaultSwapCfParameters::decode_from(&[]), None);
+		assert_eq!(VaultSwapCfParameters::decode_from(&[&encoded[..], &[0]].concat()), None);
+	}
+
 	#[test]
 	fn test_agg_key_conversion() {
 		// 2 == even
@@ -0,0 +1,168 @@
+pub mod batch_fetch_and_transfer;
+pub mod rotate_vault_proxy;
+
+use super::{AssethubAccountId, AssethubExtrinsicBuilder};
+use crate::{
+	dot::{PolkadotCrypto, PolkadotPublicKey, RuntimeVersion},
+	hub::Assethub,
+	*,
+};
+use frame_support::{traits::Get, CloneNoBound, DebugNoBound, EqNoBound, Never, PartialEqNoBound};
+use sp_std::marker::PhantomData;
+
+/// Chainflip api calls available on Assethub.
+#[derive(CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo)]
+#[scale_info(skip_type_params(Environment))]
+pub enum AssethubApi<Environment: 'static> {
+	BatchFetchAndTransfer(AssethubExtrinsicBuilder),
+	RotateVaultProxy(AssethubExtrinsicBuilder),
+	#[doc(hidden)]
+	#[codec(skip)]
+	_Phantom(PhantomData<Environment>, Never),
+}
+
+pub trait AssethubEnvironment {
+	fn try_vault_account() -> Option<AssethubAccountId>;
+	fn vault_account() -> AssethubAccountId {
+		Self::try_vault_account().expect("Vault account must be set")
+	}
+
+	fn runtime_version() -> RuntimeVersion;
+}
+
+impl<T: ChainEnvironment<VaultAccount, AssethubAccountId> + Get<RuntimeVersion>> AssethubEnvironment
+	for T
+{
+	fn try_vault_account() -> Option<AssethubAccountId> {
+		Self::lookup(VaultAccount)
+	}
+
+	fn runtime_version() -> RuntimeVersion {
+		Self::get()
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct VaultAccount;
+
+impl<E> ConsolidateCall<Assethub> for AssethubApi<E>
+where
+	E: AssethubEnvironment + ReplayProtectionProvider<Assethub>,
+{
+	fn consolidate_utxos() -> Result<Self, ConsolidationError> {
+		Err(ConsolidationError::NotRequired)
+	}
+}
+
+impl<E> AllBatch<Assethub> for AssethubApi<E>
+where
+	E: AssethubEnvironment + ReplayProtectionProvider<Assethub>,
+{
+	fn new_unsigned(
+		fetch_params: Vec<FetchAssetParams<Assethub>>,
+		transfer_params: Vec<TransferAssetParams<Assethub>>,
+	) -> Result<Self, AllBatchError> {
+		Ok(Self::BatchFetchAndTransfer(batch_fetch_and_transfer::extrinsic_builder(
+			E::replay_protection(false),
+			fetch_params,
+			transfer_params,
+			E::try_vault_account().ok_or(AllBatchError::VaultAccountNotSet)?,
+		)))
+	}
+}
+
+impl<E> SetAggKeyWithAggKey<PolkadotCrypto> for AssethubApi<E>
+where
+	E: AssethubEnvironment + ReplayProtectionProvider<Assethub>,
+{
+	fn new_unsigned(
+		maybe_old_key: Option<PolkadotPublicKey>,
+		new_key: PolkadotPublicKey,
+	) -> Result<Self, SetAggKeyWithAggKeyError> {
+		// The Assethub vault is created by governance after the Polkadot vault already exists.
+		let vault = E::try_vault_account().ok_or(SetAggKeyWithAggKeyError::VaultNotCreated)?;
+
+		Ok(Self::RotateVaultProxy(rotate_vault_proxy::extrinsic_builder(
+			// we reset the proxy account nonce on a rotation tx
+			E::replay_protection(true),
+			maybe_old_key,
+			new_key,
+			vault,
+		)))
+	}
+}
+
+impl<E> ExecutexSwapAndCall<Assethub> for AssethubApi<E>
+where
+	E: AssethubEnvironment + ReplayProtectionProvider<Assethub>,
+{
+	fn new_unsigned(
+		_transfer_param: TransferAssetParams<Assethub>,
+		_source_chain: ForeignChain,
+		_source_address: Option<ForeignChainAddress>,
+		_gas_budget: <Assethub as Chain>::ChainAmount,
+		_message: Vec<u8>,
+	) -> Result<Self, DispatchError> {
+		Err(DispatchError::Other("Assethub's ExecutexSwapAndCall is not supported."))
+	}
+}
+
+impl<E> TransferFallback<Assethub> for AssethubApi<E>
+where
+	E: AssethubEnvironment + ReplayProtectionProvider<Assethub>,
+{
+	fn new_unsigned(_transfer_param: TransferAssetParams<Assethub>) -> Result<Self, DispatchError> {
+		Err(DispatchError::Other("Assethub's TransferFallback is not supported."))
+	}
+}
+
+macro_rules! map_over_api_variants {
+	( $self:expr, $var:pat_param, $var_method:expr $(,)* ) => {
+		match $self {
+			AssethubApi::BatchFetchAndTransfer($var) => $var_method,
+			AssethubApi::RotateVaultProxy($var) => $var_method,
+			AssethubApi::_Phantom(..) => unreachable!(),
+		}
+	};
+}
+
+impl<E: AssethubEnvironment> ApiCall<PolkadotCrypto> for AssethubApi<E> {
+	fn threshold_signature_payload(&self) -> <PolkadotCrypto as ChainCrypto>::Payload {
+		let RuntimeVersion { spec_version, transaction_version, .. } = E::runtime_version();
+		map_over_api_variants!(
+			self,
+			call,
+			call.get_signature_payload(spec_version, transaction_version)
+		)
+	}
+
+	fn signed(
+		mut self,
+		threshold_signature: &<PolkadotCrypto as ChainCrypto>::ThresholdSignature,
+	) -> Self {
+		map_over_api_variants!(
+			self,
+			ref mut call,
+			call.insert_signature(threshold_signature.clone())
+		);
+		self
+	}
+
+	fn chain_encoded(&self) -> Vec<u8> {
+		map_over_api_variants!(
+			self,
+			call,
+			call.get_signed_unchecked_extrinsic()
+				.expect("Must be called after `signed`")
+				.encode()
+		)
+	}
+
+	fn is_signed(&self) -> bool {
+		map_over_api_variants!(self, call, call.is_signed())
+	}
+
+	fn transaction_out_id(&self) -> <PolkadotCrypto as ChainCrypto>::TransactionOutId {
+		map_over_api_variants!(self, call, call.signature().unwrap())
+	}
+}
@@ -0,0 +1,221 @@
+use crate::{
+	dot::{BalancesCall, PolkadotAccountIdLookup, PolkadotReplayProtection},
+	hub::{
+		asset_id, Assethub, AssethubAccountId, AssethubExtrinsicBuilder, AssethubProxyType,
+		AssethubRuntimeCall, AssetsCall, ProxyCall, UtilityCall,
+	},
+	FetchAssetParams, TransferAssetParams,
+};
+use cf_primitives::{chains::assets, ChannelId};
+use cf_utilities::SliceToArray;
+use sp_std::{boxed::Box, vec::Vec};
+
+pub fn extrinsic_builder(
+	replay_protection: PolkadotReplayProtection,
+	fetch_params: Vec<FetchAssetParams<Assethub>>,
+	transfer_params: Vec<TransferAssetParams<Assethub>>,
+	vault_account: AssethubAccountId,
+) -> AssethubExtrinsicBuilder {
+	AssethubExtrinsicBuilder::new(
+		replay_protection,
+		AssethubRuntimeCall::Proxy(ProxyCall::proxy {
+			real: PolkadotAccountIdLookup::from(vault_account),
+			force_proxy_type: Some(AssethubProxyType::Any),
+			call: Box::new(AssethubRuntimeCall::Utility(UtilityCall::force_batch {
+				calls: [
+					fetch_params
+						.into_iter()
+						.map(|fetch_param| {
+							utility_fetch(
+								fetch_param.deposit_fetch_id,
+								fetch_param.asset,
+								vault_account,
+							)
+						})
+						.collect::<Vec<AssethubRuntimeCall>>(),
+					transfer_params
+						.into_iter()
+						.map(|transfer_param| {
+							asset_transfer(
+								transfer_param.asset,
+								transfer_param.to,
+								transfer_param.amount,
+							)
+						})
+						.collect::<Vec<AssethubRuntimeCall>>(),
+				]
+				.concat(),
+			})),
+		}),
+	)
+}
+
+/// Sweeps the full balance of `asset` from the derived deposit account back into the vault.
+///
+/// Deposit accounts are derived from the vault through nested `as_derivative` calls, one layer
+/// per non-zero 16-bit chunk of the channel id, exactly as on the relay chain.
+fn utility_fetch(
+	channel_id: ChannelId,
+	asset: assets::hub::Asset,
+	vault_account: AssethubAccountId,
+) -> AssethubRuntimeCall {
+	let layers = channel_id
+		.to_be_bytes()
+		.chunks(2)
+		.map(|chunk| u16::from_be_bytes(chunk.as_array::<2>()))
+		.skip_while(|layer| *layer == 0u16)
+		.collect::<Vec<u16>>();
+
+	let sweep = match asset_id(asset) {
+		None => AssethubRuntimeCall::Balances(BalancesCall::transfer_all {
+			dest: PolkadotAccountIdLookup::from(vault_account),
+			keep_alive: false,
+		}),
+		Some(id) => AssethubRuntimeCall::Assets(AssetsCall::transfer_all {
+			id,
+			dest: PolkadotAccountIdLookup::from(vault_account),
+			keep_alive: false,
+		}),
+	};
+
+	layers.into_iter().fold(sweep, |call, index| {
+		AssethubRuntimeCall::Utility(UtilityCall::as_derivative { index, call: Box::new(call) })
+	})
+}
+
+fn asset_transfer(
+	asset: assets::hub::Asset,
+	to: AssethubAccountId,
+	amount: u128,
+) -> AssethubRuntimeCall {
+	match asset_id(asset) {
+		None => AssethubRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+			dest: PolkadotAccountIdLookup::from(to),
+			value: amount,
+		}),
+		Some(id) => AssethubRuntimeCall::Assets(AssetsCall::transfer {
+			id,
+			target: PolkadotAccountIdLookup::from(to),
+			amount,
+		}),
+	}
+}
+
+#[cfg(test)]
+mod test_batch_fetch {
+
+	use super::*;
+	use crate::{
+		dot::{PolkadotPair, NONCE_1, RAW_SEED_1, RAW_SEED_2, TEST_RUNTIME_VERSION},
+		hub::USDT_ASSET_ID,
+	};
+
+	#[test]
+	fn create_test_api_call() {
+		let keypair_vault = PolkadotPair::from_seed(&RAW_SEED_1);
+		let account_id_vault = AssethubAccountId::from(keypair_vault.public_key());
+
+		let keypair_proxy = PolkadotPair::from_seed(&RAW_SEED_2);
+
+		let dummy_fetch_params: Vec<FetchAssetParams<Assethub>> = vec![
+			FetchAssetParams::<Assethub> { deposit_fetch_id: 1, asset: assets::hub::Asset::HubDot },
+			FetchAssetParams::<Assethub> {
+				deposit_fetch_id: 2,
+				asset: assets::hub::Asset::HubUsdt,
+			},
+			FetchAssetParams::<Assethub> {
+				deposit_fetch_id: 3,
+				asset: assets::hub::Asset::HubUsdc,
+			},
+		];
+
+		let dummy_transfer_params: Vec<TransferAssetParams<Assethub>> = vec![
+			TransferAssetParams::<Assethub> {
+				to: AssethubAccountId::from_aliased([7u8; 32]),
+				amount: 4,
+				asset: assets::hub::Asset::HubDot,
+			},
+			TransferAssetParams::<Assethub> {
+				to: AssethubAccountId::from_aliased([8u8; 32]),
+				amount: 5,
+				asset: assets::hub::Asset::HubUsdt,
+			},
+			TransferAssetParams::<Assethub> {
+				to: AssethubAccountId::from_aliased([9u8; 32]),
+				amount: 6,
+				asset: assets::hub::Asset::HubUsdc,
+			},
+		];
+
+		let mut builder = super::extrinsic_builder(
+			PolkadotReplayProtection {
+				nonce: NONCE_1,
+				signer: keypair_proxy.public_key(),
+				genesis_hash: Default::default(),
+			},
+			dummy_fetch_params,
+			dummy_transfer_params,
+			account_id_vault,
+		);
+
+		let payload = builder.get_signature_payload(
+			TEST_RUNTIME_VERSION.spec_version,
+			TEST_RUNTIME_VERSION.transaction_version,
+		);
+		builder.insert_signature(keypair_proxy.sign(&payload));
+		assert!(builder.is_signed());
+		assert!(builder.get_signed_unchecked_extrinsic().is_some());
+	}
+
+	#[test]
+	fn asset_fetch_uses_assets_pallet() {
+		let vault_account = AssethubAccountId::from_aliased([1u8; 32]);
+
+		assert_eq!(
+			utility_fetch(0x0001_0000, assets::hub::Asset::HubUsdt, vault_account),
+			AssethubRuntimeCall::Utility(UtilityCall::as_derivative {
+				index: 0x0000,
+				call: Box::new(AssethubRuntimeCall::Utility(UtilityCall::as_derivative {
+					index: 0x0001,
+					call: Box::new(AssethubRuntimeCall::Assets(AssetsCall::transfer_all {
+						id: USDT_ASSET_ID,
+						dest: PolkadotAccountIdLookup::from(vault_account),
+						keep_alive: false,
+					})),
+				})),
+			})
+		);
+
+		assert_eq!(
+			utility_fetch(1, assets::hub::Asset::HubDot, vault_account),
+			AssethubRuntimeCall::Utility(UtilityCall::as_derivative {
+				index: 1,
+				call: Box::new(AssethubRuntimeCall::Balances(BalancesCall::transfer_all {
+					dest: PolkadotAccountIdLookup::from(vault_account),
+					keep_alive: false,
+				})),
+			})
+		);
+	}
+
+	#[test]
+	fn transfers_select_pallet_by_asset() {
+		let to = AssethubAccountId::from_aliased([2u8; 32]);
+
+		assert_eq!(
+			asset_transfer(assets::hub::Asset::HubDot, to, 10),
+			AssethubRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+				dest: PolkadotAccountIdLookup::from(to),
+				value: 10,
+			})
+		);
+		assert_eq!(
+			asset_transfer(assets::hub::Asset::HubUsdt, to, 10),
+			AssethubRuntimeCall::Assets(AssetsCall::transfer {
+				id: USDT_ASSET_ID,
+				target: PolkadotAccountIdLookup::from(to),
+				amount: 10,
+			})
+		);
+	}
+}
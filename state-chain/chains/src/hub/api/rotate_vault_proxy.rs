@@ -0,0 +1,86 @@
+use sp_std::{boxed::Box, vec};
+
+use crate::{
+	dot::{BalancesCall, PolkadotAccountIdLookup, PolkadotPublicKey, PolkadotReplayProtection},
+	hub::{
+		AssethubAccountId, AssethubExtrinsicBuilder, AssethubProxyType, AssethubRuntimeCall,
+		ProxyCall, UtilityCall,
+	},
+};
+
+/// Rotates the proxy of the Asset Hub vault. The AggKey is shared with the Polkadot vault, so this
+/// is issued alongside the Polkadot rotation with the same old and new keys.
+pub fn extrinsic_builder(
+	replay_protection: PolkadotReplayProtection,
+	maybe_old_proxy: Option<PolkadotPublicKey>,
+	new_proxy: PolkadotPublicKey,
+	vault_account: AssethubAccountId,
+) -> AssethubExtrinsicBuilder {
+	AssethubExtrinsicBuilder::new(
+		replay_protection,
+		AssethubRuntimeCall::Utility(UtilityCall::batch_all {
+			calls: vec![
+				AssethubRuntimeCall::Proxy(ProxyCall::proxy {
+					real: PolkadotAccountIdLookup::from(vault_account),
+					force_proxy_type: Some(AssethubProxyType::Any),
+					call: Box::new(AssethubRuntimeCall::Utility(UtilityCall::batch_all {
+						calls: [
+							Some(AssethubRuntimeCall::Proxy(ProxyCall::add_proxy {
+								delegate: new_proxy.into(),
+								proxy_type: AssethubProxyType::Any,
+								delay: 0,
+							})),
+							maybe_old_proxy.map(|old_proxy| {
+								AssethubRuntimeCall::Proxy(ProxyCall::remove_proxy {
+									delegate: old_proxy.into(),
+									proxy_type: AssethubProxyType::Any,
+									delay: 0,
+								})
+							}),
+						]
+						.into_iter()
+						.flatten()
+						.collect(),
+					})),
+				}),
+				AssethubRuntimeCall::Balances(BalancesCall::transfer_all {
+					dest: new_proxy.into(),
+					keep_alive: false,
+				}),
+			],
+		}),
+	)
+}
+
+#[cfg(test)]
+mod test_rotate_vault_proxy {
+
+	use super::*;
+	use crate::dot::{PolkadotPair, NONCE_2, RAW_SEED_2, RAW_SEED_3, TEST_RUNTIME_VERSION};
+
+	#[test]
+	fn create_test_api_call() {
+		let keypair_old_proxy = PolkadotPair::from_seed(&RAW_SEED_2);
+		let keypair_new_proxy = PolkadotPair::from_seed(&RAW_SEED_3);
+
+		let mut builder = super::extrinsic_builder(
+			PolkadotReplayProtection {
+				nonce: NONCE_2,
+				signer: keypair_old_proxy.public_key(),
+				genesis_hash: Default::default(),
+			},
+			Some(keypair_old_proxy.public_key()),
+			keypair_new_proxy.public_key(),
+			AssethubAccountId::from_aliased(hex_literal::hex!(
+				"2c8e8fde289aa5739f1b5a390404a4bdbc6a0588dce3f329d16f8a0ef6fa6bb7"
+			)),
+		);
+
+		let payload = builder.get_signature_payload(
+			TEST_RUNTIME_VERSION.spec_version,
+			TEST_RUNTIME_VERSION.transaction_version,
+		);
+		builder.insert_signature(keypair_old_proxy.sign(&payload));
+		assert!(builder.is_signed());
+	}
+}
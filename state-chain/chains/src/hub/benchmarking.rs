@@ -0,0 +1,39 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{
+	benchmarking_value::{BenchmarkValue, BenchmarkValueExtended},
+	dot::PolkadotReplayProtection,
+};
+
+use super::{
+	api::{rotate_vault_proxy, AssethubApi},
+	AssethubAccountId,
+};
+
+impl BenchmarkValue for AssethubAccountId {
+	fn benchmark_value() -> Self {
+		Self::from_aliased(hex_literal::hex!(
+			"858c1ee915090a119d4cb0774b908fa585ef7882f4648c577606490cc94f6e15"
+		))
+	}
+}
+impl BenchmarkValueExtended for AssethubAccountId {
+	fn benchmark_value_by_id(id: u8) -> Self {
+		Self::from_aliased([id; 32])
+	}
+}
+
+impl<E> BenchmarkValue for AssethubApi<E> {
+	fn benchmark_value() -> Self {
+		AssethubApi::RotateVaultProxy(rotate_vault_proxy::extrinsic_builder(
+			PolkadotReplayProtection {
+				genesis_hash: Default::default(),
+				signer: BenchmarkValue::benchmark_value(),
+				nonce: Default::default(),
+			},
+			Some(Default::default()),
+			Default::default(),
+			Default::default(),
+		))
+	}
+}
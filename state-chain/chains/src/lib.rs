@@ -37,6 +37,7 @@ pub mod btc;
 pub mod dot;
 pub mod eth;
 pub mod evm;
+pub mod hub;
 pub mod none;
 
 pub mod address;
@@ -301,6 +302,8 @@ pub trait ChainEnvironment<
 pub enum SetAggKeyWithAggKeyError {
 	Failed,
 	NotRequired,
+	/// The vault has not been created on the external chain yet, so it has to be bootstrapped.
+	VaultNotCreated,
 }
 
 /// Constructs the `SetAggKeyWithAggKey` api call.
@@ -471,6 +474,22 @@ pub struct CcmChannelMetadata {
 		serde(with = "bounded_hex", default, skip_serializing_if = "Vec::is_empty")
 	)]
 	pub cf_parameters: CcmCfParameters,
+	/// Where the principal is sent if the message can't be delivered.
+	#[cfg_attr(feature = "std", serde(default, skip_serializing_if = "Option::is_none"))]
+	pub fallback: Option<CcmFailureFallback>,
+}
+
+/// Where the principal of a cross chain message is sent if it can't be delivered.
+///
+/// A failed message is kept available for the user to broadcast themselves, and the principal is
+/// only refunded once the failed call has expired: until then the signed call could still be
+/// executed, so refunding earlier could deliver the funds twice.
+#[derive(
+	Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize, MaxEncodedLen,
+)]
+pub struct CcmFailureFallback {
+	/// An address on the destination chain.
+	pub refund_address: ForeignChainAddress,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
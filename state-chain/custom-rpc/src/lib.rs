@@ -9,8 +9,8 @@ use cf_chains::{
 };
 use cf_primitives::{
 	chains::assets::any::{self, OldAsset},
-	AccountRole, Asset, AssetAmount, BroadcastId, ForeignChain, NetworkEnvironment, SemVer,
-	SwapOutput,
+	AccountRole, Asset, AssetAmount, BroadcastId, ChannelId, EpochIndex, ForeignChain,
+	NetworkEnvironment, SemVer, SwapOutput,
 };
 use cf_utilities::rpc::NumberOrHex;
 use core::ops::Range;
@@ -20,21 +20,22 @@ use jsonrpsee::{
 	types::error::{CallError, SubscriptionEmptyError},
 	SubscriptionSink,
 };
-use pallet_cf_governance::GovCallHash;
+use pallet_cf_governance::{GovCallHash, ProposalId};
 use pallet_cf_pools::{
-	AskBidMap, AssetsMap, PoolInfo, PoolLiquidity, PoolPriceV1, UnidirectionalPoolDepth,
+	AskBidMap, AssetsMap, LpPosition, PoolInfo, PoolLiquidity, PoolPriceV1, UnidirectionalPoolDepth,
 };
+use pallet_cf_tokenholder_governance::{Proposal as TokenholderProposal, Vote as TokenholderVote};
 use sc_client_api::{BlockchainEvents, HeaderBackend};
 use serde::{Deserialize, Serialize};
 use sp_api::{BlockT, HeaderT};
 use sp_core::U256;
 use sp_runtime::Permill;
 use state_chain_runtime::{
-	chainflip::{BlockUpdate, Offence},
+	chainflip::{BlockUpdate, GovernanceCallCategory, Offence},
 	constants::common::TX_FEE_MULTIPLIER,
 	runtime_apis::{
-		CustomRuntimeApi, DispatchErrorWithMessage, FailingWitnessValidators,
-		LiquidityProviderInfo, RuntimeApiAccountInfoV2,
+		BrokerChannel, BrokerEarnings, CustomRuntimeApi, DispatchErrorWithMessage,
+		FailingWitnessValidators, LiquidityProviderInfo, RuntimeApiAccountInfoV2,
 	},
 	NetworkFee,
 };
@@ -237,6 +238,137 @@ pub struct PoolPriceV2 {
 	pub price: pallet_cf_pools::PoolPriceV2,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcPoolTwap {
+	pub base_asset: OldAsset,
+	pub quote_asset: OldAsset,
+	pub window: u32,
+	pub tick: Tick,
+	pub price: U256,
+	pub harmonic_mean_liquidity: NumberOrHex,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcPoolStats {
+	pub base_asset: OldAsset,
+	pub quote_asset: OldAsset,
+	pub volume: AssetsMap<NumberOrHex>,
+	pub limit_order_fees: AssetsMap<NumberOrHex>,
+	pub range_order_fees: AssetsMap<NumberOrHex>,
+	pub network_fee: NumberOrHex,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcLpPositionStats {
+	pub base_asset: OldAsset,
+	pub quote_asset: OldAsset,
+	pub position: LpPosition,
+	pub fees_earned: AssetsMap<NumberOrHex>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcBrokerEarnings {
+	pub withdrawal_addresses: HashMap<ForeignChain, Option<ForeignChainAddressHumanreadable>>,
+	/// Commission that has been earned but not yet withdrawn.
+	pub balances: any::AssetMap<NumberOrHex>,
+	/// Commission earned over the broker's lifetime, including withdrawn amounts.
+	pub total_earnings: any::AssetMap<NumberOrHex>,
+	/// Commission earned from each deposit channel that has not been recycled yet.
+	pub channel_earnings: Vec<RpcBrokerChannelEarnings>,
+	/// Commission earned in each of the most recent epochs.
+	pub epoch_earnings: Vec<RpcBrokerEpochEarnings>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcBrokerChannelEarnings {
+	#[serde(flatten)]
+	pub asset: Asset,
+	pub channel_id: ChannelId,
+	pub earned: NumberOrHex,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcBrokerEpochEarnings {
+	pub epoch: EpochIndex,
+	#[serde(flatten)]
+	pub asset: Asset,
+	pub earned: NumberOrHex,
+}
+
+impl RpcBrokerEarnings {
+	fn new(earnings: BrokerEarnings, network: NetworkEnvironment) -> Self {
+		Self {
+			withdrawal_addresses: earnings
+				.withdrawal_addresses
+				.into_iter()
+				.map(|(chain, address)| (chain, address.map(|a| a.to_humanreadable(network))))
+				.collect(),
+			balances: any::AssetMap::try_from_iter(
+				earnings.balances.into_iter().map(|(asset, amount)| (asset, amount.into())),
+			)
+			.unwrap(),
+			total_earnings: any::AssetMap::try_from_iter(
+				earnings
+					.total_earnings
+					.into_iter()
+					.map(|(asset, amount)| (asset, amount.into())),
+			)
+			.unwrap(),
+			channel_earnings: earnings
+				.channel_earnings
+				.into_iter()
+				.map(|(asset, channel_id, earned)| RpcBrokerChannelEarnings {
+					asset,
+					channel_id,
+					earned: earned.into(),
+				})
+				.collect(),
+			epoch_earnings: earnings
+				.epoch_earnings
+				.into_iter()
+				.map(|(epoch, asset, earned)| RpcBrokerEpochEarnings {
+					epoch,
+					asset,
+					earned: earned.into(),
+				})
+				.collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcBrokerChannel {
+	pub chain: ForeignChain,
+	pub channel_id: ChannelId,
+	pub deposit_address: ForeignChainAddressHumanreadable,
+	pub source_asset: Asset,
+	pub destination_asset: Asset,
+	pub opened_at: u64,
+	pub expires_at: u64,
+	pub is_open: bool,
+	pub is_sticky: bool,
+	pub deposit_count: u32,
+	pub total_deposited: NumberOrHex,
+}
+
+impl RpcBrokerChannel {
+	fn new(channel: BrokerChannel, network: NetworkEnvironment) -> Self {
+		Self {
+			chain: channel.chain,
+			channel_id: channel.channel_id,
+			deposit_address: channel.deposit_address.to_humanreadable(network),
+			source_asset: channel.source_asset,
+			destination_asset: channel.destination_asset,
+			opened_at: channel.opened_at,
+			expires_at: channel.expires_at,
+			is_open: channel.is_open,
+			is_sticky: channel.is_sticky,
+			deposit_count: channel.deposit_count,
+			total_deposited: channel.total_deposited.into(),
+		}
+	}
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct RpcPrewitnessedSwap {
 	pub base_asset: OldAsset,
@@ -245,6 +377,45 @@ pub struct RpcPrewitnessedSwap {
 	pub amounts: Vec<U256>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct RpcTokenholderProposal {
+	pub proposal: TokenholderProposal,
+	pub resolution_block: u32,
+	pub total_funds_snapshot: NumberOrHex,
+	pub ayes: NumberOrHex,
+	pub nays: NumberOrHex,
+	pub abstentions: NumberOrHex,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcTokenholderVote {
+	pub proposal: TokenholderProposal,
+	pub vote: Option<TokenholderVote>,
+	pub voting_power: NumberOrHex,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcTimelockedGovernanceCall {
+	pub proposal_id: ProposalId,
+	/// Human-readable representation of the decoded call.
+	pub call: Option<String>,
+	pub category: GovernanceCallCategory,
+	pub execute_at: u64,
+	pub vetoed_by: Vec<state_chain_runtime::AccountId>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcValidatorEpochPerformance {
+	pub epoch_index: EpochIndex,
+	pub rewards: NumberOrHex,
+	pub slashes: NumberOrHex,
+	pub offences: Vec<(Offence, u32)>,
+	/// The offences the validator was suspended for, how many times, and the block at which the
+	/// latest suspension ends.
+	pub suspensions: Vec<(Offence, u32, u32)>,
+	pub reputation_at_epoch_end: Option<i32>,
+}
+
 #[rpc(server, client, namespace = "cf")]
 /// The custom RPC endpoints for the state chain node.
 pub trait CustomApi {
@@ -315,6 +486,18 @@ pub trait CustomApi {
 		account_id: state_chain_runtime::AccountId,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<RpcAccountInfoV2>;
+	#[method(name = "broker_earnings")]
+	fn cf_broker_earnings(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<RpcBrokerEarnings>>;
+	#[method(name = "broker_channels")]
+	fn cf_broker_channels(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcBrokerChannel>>;
 	#[method(name = "asset_balances")]
 	fn cf_asset_balances(
 		&self,
@@ -351,6 +534,27 @@ pub trait CustomApi {
 		quote_asset: Asset,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<PoolPriceV2>;
+	#[method(name = "pool_twap")]
+	fn cf_pool_twap(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		window: u32,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcPoolTwap>;
+	#[method(name = "pool_stats")]
+	fn cf_pool_stats(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcPoolStats>;
+	#[method(name = "lp_stats")]
+	fn cf_lp_stats(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcLpPositionStats>>;
 	#[method(name = "swap_rate")]
 	fn cf_pool_swap_rate(
 		&self,
@@ -473,6 +677,32 @@ pub trait CustomApi {
 		hash: state_chain_runtime::Hash,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Option<FailingWitnessValidators>>;
+
+	#[method(name = "tokenholder_proposals")]
+	fn cf_tokenholder_proposals(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcTokenholderProposal>>;
+
+	#[method(name = "tokenholder_votes")]
+	fn cf_tokenholder_votes(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcTokenholderVote>>;
+
+	#[method(name = "timelocked_governance_calls")]
+	fn cf_timelocked_governance_calls(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcTimelockedGovernanceCall>>;
+
+	#[method(name = "validator_history")]
+	fn cf_validator_history(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcValidatorEpochPerformance>>;
 }
 
 /// An RPC extension for the state chain node.
@@ -719,6 +949,38 @@ where
 		})
 	}
 
+	fn cf_broker_earnings(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<RpcBrokerEarnings>> {
+		let api = self.client.runtime_api();
+		let hash = self.unwrap_or_best(at);
+
+		let network = api.cf_network_environment(hash).map_err(to_rpc_error)?;
+		Ok(api
+			.cf_broker_earnings(hash, account_id)
+			.map_err(to_rpc_error)?
+			.map(|earnings| RpcBrokerEarnings::new(earnings, network)))
+	}
+
+	fn cf_broker_channels(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcBrokerChannel>> {
+		let api = self.client.runtime_api();
+		let hash = self.unwrap_or_best(at);
+
+		let network = api.cf_network_environment(hash).map_err(to_rpc_error)?;
+		Ok(api
+			.cf_broker_channels(hash, account_id)
+			.map_err(to_rpc_error)?
+			.into_iter()
+			.map(|channel| RpcBrokerChannel::new(channel, network))
+			.collect())
+	}
+
 	fn cf_asset_balances(
 		&self,
 		account_id: state_chain_runtime::AccountId,
@@ -821,6 +1083,71 @@ where
 		})
 	}
 
+	fn cf_pool_twap(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		window: u32,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcPoolTwap> {
+		let twap = self
+			.client
+			.runtime_api()
+			.cf_pool_twap(self.unwrap_or_best(at), base_asset, quote_asset, window)
+			.map_err(to_rpc_error)
+			.and_then(|result| result.map_err(map_dispatch_error))?;
+		Ok(RpcPoolTwap {
+			base_asset: base_asset.into(),
+			quote_asset: quote_asset.into(),
+			window,
+			tick: twap.tick,
+			price: twap.price,
+			harmonic_mean_liquidity: twap.harmonic_mean_liquidity.into(),
+		})
+	}
+
+	fn cf_pool_stats(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcPoolStats> {
+		let stats = self
+			.client
+			.runtime_api()
+			.cf_pool_stats(self.unwrap_or_best(at), base_asset, quote_asset)
+			.map_err(to_rpc_error)
+			.and_then(|result| result.map_err(map_dispatch_error))?;
+		Ok(RpcPoolStats {
+			base_asset: base_asset.into(),
+			quote_asset: quote_asset.into(),
+			volume: stats.volume.map(Into::into),
+			limit_order_fees: stats.limit_order_fees.map(Into::into),
+			range_order_fees: stats.range_order_fees.map(Into::into),
+			network_fee: stats.network_fee.into(),
+		})
+	}
+
+	fn cf_lp_stats(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcLpPositionStats>> {
+		Ok(self
+			.client
+			.runtime_api()
+			.cf_lp_stats(self.unwrap_or_best(at), lp)
+			.map_err(to_rpc_error)?
+			.into_iter()
+			.map(|stats| RpcLpPositionStats {
+				base_asset: stats.base_asset.into(),
+				quote_asset: stats.quote_asset.into(),
+				position: stats.position,
+				fees_earned: stats.fees_earned.map(Into::into),
+			})
+			.collect())
+	}
+
 	fn cf_pool_swap_rate(
 		&self,
 		from_asset: Asset,
@@ -1196,6 +1523,88 @@ where
 			.cf_witness_count(self.unwrap_or_best(at), pallet_cf_witnesser::CallHash(hash.into()))
 			.map_err(to_rpc_error)
 	}
+
+	fn cf_tokenholder_proposals(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcTokenholderProposal>> {
+		Ok(self
+			.client
+			.runtime_api()
+			.cf_tokenholder_proposals(self.unwrap_or_best(at))
+			.map_err(to_rpc_error)?
+			.into_iter()
+			.map(|info| RpcTokenholderProposal {
+				proposal: info.proposal,
+				resolution_block: info.resolution_block,
+				total_funds_snapshot: info.total_funds_snapshot.into(),
+				ayes: info.tally.ayes.into(),
+				nays: info.tally.nays.into(),
+				abstentions: info.tally.abstentions.into(),
+			})
+			.collect())
+	}
+
+	fn cf_tokenholder_votes(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcTokenholderVote>> {
+		Ok(self
+			.client
+			.runtime_api()
+			.cf_tokenholder_votes(self.unwrap_or_best(at), account_id)
+			.map_err(to_rpc_error)?
+			.into_iter()
+			.map(|info| RpcTokenholderVote {
+				proposal: info.proposal,
+				vote: info.vote,
+				voting_power: info.voting_power.into(),
+			})
+			.collect())
+	}
+
+	fn cf_timelocked_governance_calls(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcTimelockedGovernanceCall>> {
+		Ok(self
+			.client
+			.runtime_api()
+			.cf_timelocked_governance_calls(self.unwrap_or_best(at))
+			.map_err(to_rpc_error)?
+			.into_iter()
+			.map(|timelocked_call| RpcTimelockedGovernanceCall {
+				proposal_id: timelocked_call.proposal_id,
+				call: timelocked_call.call.map(|call| format!("{call:?}")),
+				category: timelocked_call.category,
+				execute_at: timelocked_call.execute_at,
+				vetoed_by: timelocked_call.vetoed_by,
+			})
+			.collect())
+	}
+
+	fn cf_validator_history(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcValidatorEpochPerformance>> {
+		Ok(self
+			.client
+			.runtime_api()
+			.cf_validator_history(self.unwrap_or_best(at), account_id)
+			.map_err(to_rpc_error)?
+			.into_iter()
+			.map(|performance| RpcValidatorEpochPerformance {
+				epoch_index: performance.epoch_index,
+				rewards: performance.rewards.into(),
+				slashes: performance.slashes.into(),
+				offences: performance.offences,
+				suspensions: performance.suspensions,
+				reputation_at_epoch_end: performance.reputation_at_epoch_end,
+			})
+			.collect())
+	}
 }
 
 impl<C, B> CustomRpc<C, B>
@@ -1283,7 +1692,7 @@ where
 mod test {
 	use super::*;
 	use cf_primitives::{
-		chains::assets::{any, btc, dot, eth},
+		chains::assets::{any, btc, dot, eth, hub},
 		FLIPPERINOS_PER_FLIP,
 	};
 	use sp_core::H160;
@@ -1323,6 +1732,7 @@ mod test {
 						Some(cf_chains::ForeignChainAddress::Dot(Default::default())),
 					),
 					(ForeignChain::Bitcoin, None),
+					(ForeignChain::Assethub, None),
 				],
 				balances: vec![
 					(Asset::Eth, u128::MAX),
@@ -1330,6 +1740,9 @@ mod test {
 					(Asset::Flip, u128::MAX / 2),
 					(Asset::Usdc, 0),
 					(Asset::Dot, 0),
+					(Asset::HubDot, 0),
+					(Asset::HubUsdt, 0),
+					(Asset::HubUsdc, 0),
 				],
 			},
 			cf_primitives::NetworkEnvironment::Mainnet,
@@ -1375,6 +1788,7 @@ mod test {
 					},
 					btc: btc::AssetMap { btc: Some(0u32.into()) },
 					dot: dot::AssetMap { dot: None },
+					hub: hub::AssetMap { hubdot: None, hubusdt: None, hubusdc: None },
 				},
 				network_fee_hundredth_pips: Permill::from_percent(100),
 			},
@@ -1387,6 +1801,11 @@ mod test {
 					},
 					btc: btc::AssetMap { btc: 0u32.into() },
 					dot: dot::AssetMap { dot: 0u32.into() },
+					hub: hub::AssetMap {
+						hubdot: 0u32.into(),
+						hubusdt: 0u32.into(),
+						hubusdc: 0u32.into(),
+					},
 				},
 				ingress_fees: any::AssetMap {
 					eth: eth::AssetMap {
@@ -1396,6 +1815,7 @@ mod test {
 					},
 					btc: btc::AssetMap { btc: Some(0u32.into()) },
 					dot: dot::AssetMap { dot: Some((u64::MAX / 2 - 1).into()) },
+					hub: hub::AssetMap { hubdot: Some(0u32.into()), hubusdt: None, hubusdc: None },
 				},
 				egress_fees: any::AssetMap {
 					eth: eth::AssetMap {
@@ -1405,11 +1825,13 @@ mod test {
 					},
 					btc: btc::AssetMap { btc: Some(0u32.into()) },
 					dot: dot::AssetMap { dot: Some((u64::MAX / 2 - 1).into()) },
+					hub: hub::AssetMap { hubdot: Some(0u32.into()), hubusdt: None, hubusdc: None },
 				},
 				witness_safety_margins: HashMap::from([
 					(ForeignChain::Bitcoin, Some(3u64)),
 					(ForeignChain::Ethereum, Some(3u64)),
 					(ForeignChain::Polkadot, None),
+					(ForeignChain::Assethub, None),
 				]),
 				egress_dust_limits: any::AssetMap {
 					eth: eth::AssetMap {
@@ -1419,11 +1841,17 @@ mod test {
 					},
 					btc: btc::AssetMap { btc: 0u32.into() },
 					dot: dot::AssetMap { dot: 0u32.into() },
+					hub: hub::AssetMap {
+						hubdot: 0u32.into(),
+						hubusdt: 0u32.into(),
+						hubusdc: 0u32.into(),
+					},
 				},
 				channel_opening_fees: HashMap::from([
 					(ForeignChain::Bitcoin, 0u32.into()),
 					(ForeignChain::Ethereum, 1000u32.into()),
 					(ForeignChain::Polkadot, 1000u32.into()),
+					(ForeignChain::Assethub, 1000u32.into()),
 				]),
 			},
 			funding: FundingEnvironment {
@@ -1439,6 +1867,7 @@ mod test {
 							PoolInfo {
 								limit_order_fee_hundredth_pips: 0,
 								range_order_fee_hundredth_pips: 100,
+								dynamic_fee: None,
 							}
 							.into(),
 						),
@@ -12,7 +12,7 @@ use cf_chains::{
 	btc::{BitcoinFeeInfo, BitcoinTrackedData},
 	dot::{PolkadotTrackedData, RuntimeVersion},
 	eth::EthereumTrackedData,
-	Bitcoin, Ethereum, Polkadot,
+	Assethub, Bitcoin, Ethereum, Polkadot,
 };
 use common::FLIPPERINOS_PER_FLIP;
 pub use sc_service::{ChainType, Properties};
@@ -24,7 +24,8 @@ use sp_core::{
 	Pair, Public,
 };
 use state_chain_runtime::{
-	chainflip::Offence, opaque::SessionKeys, AccountId, AccountRolesConfig, AuraConfig,
+	chainflip::Offence, opaque::SessionKeys, AccountId, AccountRolesConfig,
+	AssethubChainTrackingConfig, AssethubIngressEgressConfig, AssethubVaultConfig, AuraConfig,
 	BitcoinChainTrackingConfig, BitcoinIngressEgressConfig, BitcoinThresholdSignerConfig,
 	BitcoinVaultConfig, BlockNumber, EmissionsConfig, EnvironmentConfig,
 	EthereumChainTrackingConfig, EthereumIngressEgressConfig, EthereumThresholdSignerConfig,
@@ -99,6 +100,9 @@ pub struct StateChainEnvironment {
 	dot_genesis_hash: PolkadotHash,
 	dot_vault_account_id: Option<PolkadotAccountId>,
 	dot_runtime_version: RuntimeVersion,
+	hub_genesis_hash: PolkadotHash,
+	hub_vault_account_id: Option<PolkadotAccountId>,
+	hub_runtime_version: RuntimeVersion,
 }
 
 /// Get the values from the State Chain's environment variables. Else set them via the defaults
@@ -148,6 +152,24 @@ pub fn get_environment_or_defaults(defaults: StateChainEnvironment) -> StateChai
 		Err(_) => defaults.dot_runtime_version.transaction_version,
 	};
 
+	let hub_genesis_hash = match env::var("HUB_GENESIS_HASH") {
+		Ok(s) => hex_decode::<32>(&s).unwrap().into(),
+		Err(_) => defaults.hub_genesis_hash,
+	};
+	let hub_vault_account_id = match env::var("HUB_VAULT_ACCOUNT_ID") {
+		Ok(s) => Some(PolkadotAccountId::from_aliased(hex_decode::<32>(&s).unwrap())),
+		Err(_) => defaults.hub_vault_account_id,
+	};
+
+	let hub_spec_version: u32 = match env::var("HUB_SPEC_VERSION") {
+		Ok(s) => s.parse().unwrap(),
+		Err(_) => defaults.hub_runtime_version.spec_version,
+	};
+	let hub_transaction_version: u32 = match env::var("HUB_TRANSACTION_VERSION") {
+		Ok(s) => s.parse().unwrap(),
+		Err(_) => defaults.hub_runtime_version.transaction_version,
+	};
+
 	StateChainEnvironment {
 		flip_token_address,
 		eth_usdc_address,
@@ -166,6 +188,12 @@ pub fn get_environment_or_defaults(defaults: StateChainEnvironment) -> StateChai
 			spec_version: dot_spec_version,
 			transaction_version: dot_transaction_version,
 		},
+		hub_genesis_hash,
+		hub_vault_account_id,
+		hub_runtime_version: RuntimeVersion {
+			spec_version: hub_spec_version,
+			transaction_version: hub_transaction_version,
+		},
 	}
 }
 
@@ -220,6 +248,9 @@ pub fn inner_cf_development_config(
 		dot_genesis_hash,
 		dot_vault_account_id,
 		dot_runtime_version,
+		hub_genesis_hash,
+		hub_vault_account_id,
+		hub_runtime_version,
 	} = get_environment_or_defaults(testnet::ENV);
 	Ok(ChainSpec::from_genesis(
 		"CF Develop",
@@ -245,6 +276,8 @@ pub fn inner_cf_development_config(
 					ethereum_chain_id,
 					polkadot_genesis_hash: dot_genesis_hash,
 					polkadot_vault_account_id: dot_vault_account_id,
+					assethub_genesis_hash: hub_genesis_hash,
+					assethub_vault_account_id: hub_vault_account_id.map(Into::into),
 					network_environment: NetworkEnvironment::Development,
 					_config: PhantomData,
 				},
@@ -267,10 +300,12 @@ pub fn inner_cf_development_config(
 				devnet::KEYGEN_CEREMONY_TIMEOUT_BLOCKS,
 				devnet::THRESHOLD_SIGNATURE_CEREMONY_TIMEOUT_BLOCKS,
 				dot_runtime_version,
+				hub_runtime_version,
 				// Bitcoin block times on localnets are much faster, so we account for that here.
 				devnet::BITCOIN_EXPIRY_BLOCKS,
 				devnet::ETHEREUM_EXPIRY_BLOCKS,
 				devnet::POLKADOT_EXPIRY_BLOCKS,
+				devnet::ASSETHUB_EXPIRY_BLOCKS,
 				devnet::BITCOIN_SAFETY_MARGIN,
 				devnet::ETHEREUM_SAFETY_MARGIN,
 				devnet::AUCTION_BID_CUTOFF_PERCENTAGE,
@@ -321,6 +356,9 @@ macro_rules! network_spec {
 					dot_genesis_hash,
 					dot_vault_account_id,
 					dot_runtime_version,
+					hub_genesis_hash,
+					hub_vault_account_id,
+					hub_runtime_version,
 				} = env_override.unwrap_or(ENV);
 				let protocol_id = format!(
 					"{}-{}",
@@ -375,6 +413,8 @@ macro_rules! network_spec {
 								ethereum_chain_id,
 								polkadot_genesis_hash: dot_genesis_hash,
 								polkadot_vault_account_id: dot_vault_account_id.clone(),
+								assethub_genesis_hash: hub_genesis_hash,
+								assethub_vault_account_id: hub_vault_account_id.map(Into::into),
 								network_environment: NETWORK_ENVIRONMENT,
 								_config: PhantomData,
 							},
@@ -397,9 +437,11 @@ macro_rules! network_spec {
 							KEYGEN_CEREMONY_TIMEOUT_BLOCKS,
 							THRESHOLD_SIGNATURE_CEREMONY_TIMEOUT_BLOCKS,
 							dot_runtime_version,
+							hub_runtime_version,
 							BITCOIN_EXPIRY_BLOCKS,
 							ETHEREUM_EXPIRY_BLOCKS,
 							POLKADOT_EXPIRY_BLOCKS,
+							ASSETHUB_EXPIRY_BLOCKS,
 							BITCOIN_SAFETY_MARGIN,
 							ETHEREUM_SAFETY_MARGIN,
 							AUCTION_BID_CUTOFF_PERCENTAGE,
@@ -458,9 +500,11 @@ fn testnet_genesis(
 	keygen_ceremony_timeout_blocks: BlockNumber,
 	threshold_signature_ceremony_timeout_blocks: BlockNumber,
 	dot_runtime_version: RuntimeVersion,
+	hub_runtime_version: RuntimeVersion,
 	bitcoin_deposit_channel_lifetime: u32,
 	ethereum_deposit_channel_lifetime: u32,
 	polkadot_deposit_channel_lifetime: u32,
+	assethub_deposit_channel_lifetime: u32,
 	bitcoin_safety_margin: u64,
 	ethereum_safety_margin: u64,
 	auction_bid_cutoff_percentage: Percent,
@@ -608,6 +652,7 @@ fn testnet_genesis(
 		ethereum_vault: EthereumVaultConfig { deployment_block: Some(ethereum_deployment_block) },
 		polkadot_vault: PolkadotVaultConfig { deployment_block: None },
 		bitcoin_vault: BitcoinVaultConfig { deployment_block: None },
+		assethub_vault: AssethubVaultConfig { deployment_block: None },
 
 		ethereum_threshold_signer: EthereumThresholdSignerConfig {
 			key: Some(cf_chains::evm::AggKey::from_pubkey_compressed(eth_init_agg_key)),
@@ -662,6 +707,15 @@ fn testnet_genesis(
 				tracked_data: BitcoinTrackedData { btc_fee_info: BitcoinFeeInfo::new(1000) },
 			},
 		},
+		assethub_chain_tracking: AssethubChainTrackingConfig {
+			init_chain_state: ChainState::<Assethub> {
+				block_height: 0,
+				tracked_data: PolkadotTrackedData {
+					median_tip: 0,
+					runtime_version: hub_runtime_version,
+				},
+			},
+		},
 		transaction_payment: Default::default(),
 		liquidity_pools: Default::default(),
 		// Channel lifetimes are set to ~2 hours at average block times.
@@ -680,6 +734,11 @@ fn testnet_genesis(
 			witness_safety_margin: None,
 			dust_limits: Default::default(),
 		},
+		assethub_ingress_egress: AssethubIngressEgressConfig {
+			deposit_channel_lifetime: assethub_deposit_channel_lifetime,
+			witness_safety_margin: None,
+			dust_limits: Default::default(),
+		},
 	}
 }
 
@@ -17,6 +17,7 @@ pub const PROTOCOL_ID: &str = "flip-berghain";
 pub const BITCOIN_EXPIRY_BLOCKS: u32 = 24 * 60 / 10;
 pub const ETHEREUM_EXPIRY_BLOCKS: u32 = 24 * 3600 / 14;
 pub const POLKADOT_EXPIRY_BLOCKS: u32 = 24 * 3600 / 6;
+pub const ASSETHUB_EXPIRY_BLOCKS: u32 = 24 * 3600 / 12;
 
 pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	flip_token_address: hex_literal::hex!("826180541412D574cf1336d22c0C0a287822678A"),
@@ -37,6 +38,11 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	)),
 	dot_vault_account_id: None,
 	dot_runtime_version: RuntimeVersion { spec_version: 9431, transaction_version: 24 },
+	hub_genesis_hash: H256(hex_literal::hex!(
+		"68d56f15f85d3136970ec16946040bc1752654e906147f7e43e9d539d7c3de2f" // Polkadot Asset Hub
+	)),
+	hub_vault_account_id: None,
+	hub_runtime_version: RuntimeVersion { spec_version: 1000001, transaction_version: 13 },
 };
 
 pub const EPOCH_DURATION_BLOCKS: BlockNumber = 24 * HOURS;
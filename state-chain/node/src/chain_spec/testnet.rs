@@ -16,6 +16,7 @@ pub const PROTOCOL_ID: &str = "flip-test";
 pub const BITCOIN_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / (10 * 60);
 pub const ETHEREUM_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / 14;
 pub const POLKADOT_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / 6;
+pub const ASSETHUB_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / 12;
 
 pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	flip_token_address: hex_literal::hex!("Cf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9"),
@@ -36,6 +37,11 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	)),
 	dot_vault_account_id: None,
 	dot_runtime_version: RuntimeVersion { spec_version: 10000, transaction_version: 25 },
+	// There is no Asset Hub deployed for this network by default. Override with the
+	// HUB_GENESIS_HASH environment variable.
+	hub_genesis_hash: H256::zero(),
+	hub_vault_account_id: None,
+	hub_runtime_version: RuntimeVersion { spec_version: 10000, transaction_version: 13 },
 };
 
 pub const EPOCH_DURATION_BLOCKS: BlockNumber = 3 * HOURS;
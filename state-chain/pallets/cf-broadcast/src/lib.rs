@@ -296,6 +296,12 @@ pub mod pallet {
 	#[pallet::getter(fn aborted_broadcasts)]
 	pub type AbortedBroadcasts<T, I = ()> = StorageValue<_, Vec<BroadcastId>, ValueQuery>;
 
+	/// Broadcasts that are still witnessed after they have been aborted or re-signed, because
+	/// someone else may broadcast the signed call. Their success callback is kept on abort.
+	#[pallet::storage]
+	pub type WitnessedAfterAbort<T, I = ()> =
+		StorageMap<_, Twox64Concat, BroadcastId, (), OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -452,6 +458,8 @@ pub mod pallet {
 
 			let signed_api_call = api_call.signed(&signature);
 
+			let previous_transaction_out_id = ThresholdSignatureData::<T, I>::get(broadcast_id)
+				.map(|(previous_api_call, _)| previous_api_call.transaction_out_id());
 			ThresholdSignatureData::<T, I>::insert(
 				broadcast_id,
 				(signed_api_call.clone(), signature),
@@ -487,6 +495,16 @@ pub mod pallet {
 					Self::start_broadcast_attempt(broadcast_data);
 				}
 			} else {
+				// The re-signed call can still be broadcast by anyone, so it must be witnessed.
+				if WitnessedAfterAbort::<T, I>::contains_key(broadcast_id) {
+					if let Some(previous_transaction_out_id) = previous_transaction_out_id {
+						TransactionOutIdToBroadcastId::<T, I>::remove(previous_transaction_out_id);
+					}
+					TransactionOutIdToBroadcastId::<T, I>::insert(
+						signed_api_call.transaction_out_id(),
+						(broadcast_id, initiated_at),
+					);
+				}
 				Self::deposit_event(Event::<T, I>::CallResigned { broadcast_id });
 			}
 
@@ -637,6 +655,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		TransactionMetadata::<T, I>::remove(broadcast_id);
 		RequestSuccessCallbacks::<T, I>::remove(broadcast_id);
 		RequestFailureCallbacks::<T, I>::remove(broadcast_id);
+		WitnessedAfterAbort::<T, I>::remove(broadcast_id);
 		if let Some((api_call, _)) = ThresholdSignatureData::<T, I>::take(broadcast_id) {
 			TransactionOutIdToBroadcastId::<T, I>::remove(api_call.transaction_out_id());
 		}
@@ -904,7 +923,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				),
 			});
 		}
-		RequestSuccessCallbacks::<T, I>::remove(broadcast_id);
+		// If the broadcast is still witnessed, the success callback is kept: the signed call may
+		// still be broadcast by someone else, in which case it is dispatched when the transaction
+		// is witnessed.
+		if !WitnessedAfterAbort::<T, I>::contains_key(broadcast_id) {
+			RequestSuccessCallbacks::<T, I>::remove(broadcast_id);
+		}
 
 		Self::deposit_event(Event::<T, I>::BroadcastAborted { broadcast_id });
 		Self::remove_pending_broadcast(&broadcast_id);
@@ -948,6 +972,10 @@ impl<T: Config<I>, I: 'static> Broadcaster<T::TargetChain> for Pallet<T, I> {
 		Self::clean_up_broadcast_storage(broadcast_id);
 	}
 
+	fn witness_after_abort(broadcast_id: BroadcastId) {
+		WitnessedAfterAbort::<T, I>::insert(broadcast_id, ());
+	}
+
 	fn threshold_sign_and_broadcast_rotation_tx(api_call: Self::ApiCall) -> BroadcastId {
 		let broadcast_id = <Self as Broadcaster<_>>::threshold_sign_and_broadcast(api_call);
 
@@ -5,7 +5,7 @@ use crate::{
 	DelayedBroadcastRetryQueue, Error, Event as BroadcastEvent, FailedBroadcasters, Instance1,
 	PalletOffence, PendingBroadcasts, RequestFailureCallbacks, RequestSuccessCallbacks,
 	ThresholdSignatureData, Timeouts, TransactionFeeDeficit, TransactionMetadata,
-	TransactionOutIdToBroadcastId,
+	TransactionOutIdToBroadcastId, WitnessedAfterAbort,
 };
 use cf_chains::{
 	evm::SchnorrVerificationComponents,
@@ -995,6 +995,43 @@ fn aborted_broadcasts_can_still_succeed() {
 		});
 }
 
+#[test]
+fn success_callback_is_only_kept_on_abort_if_still_witnessed() {
+	new_test_ext().execute_with(|| {
+		let [witnessed_broadcast_id, unwitnessed_broadcast_id] = [1, 2].map(|i| {
+			let broadcast_id = Broadcaster::threshold_sign_and_broadcast(
+				api_call(i).1,
+				Some(MockCallback),
+				|_| None,
+			);
+			EthMockThresholdSigner::execute_signature_result_against_last_request(Ok(
+				ETH_DUMMY_SIG,
+			));
+			broadcast_id
+		});
+		<Broadcaster as BroadcasterTrait<_>>::witness_after_abort(witnessed_broadcast_id);
+
+		for broadcast_id in [witnessed_broadcast_id, unwitnessed_broadcast_id] {
+			let nominee = ready_to_abort_broadcast(broadcast_id);
+			assert_ok!(Broadcaster::transaction_failed(
+				RawOrigin::Signed(nominee).into(),
+				broadcast_id,
+			));
+			assert!(AbortedBroadcasts::<Test, Instance1>::get().contains(&broadcast_id));
+		}
+
+		assert_eq!(
+			RequestSuccessCallbacks::<Test, Instance1>::get(witnessed_broadcast_id),
+			Some(MockCallback)
+		);
+		assert!(RequestSuccessCallbacks::<Test, Instance1>::get(unwitnessed_broadcast_id).is_none());
+
+		<Broadcaster as BroadcasterTrait<_>>::clean_up_broadcast_storage(witnessed_broadcast_id);
+		assert!(RequestSuccessCallbacks::<Test, Instance1>::get(witnessed_broadcast_id).is_none());
+		assert!(!WitnessedAfterAbort::<Test, Instance1>::contains_key(witnessed_broadcast_id));
+	});
+}
+
 #[test]
 fn broadcast_retry_delay_works() {
 	let mut target = 0;
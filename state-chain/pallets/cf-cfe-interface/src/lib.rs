@@ -7,7 +7,8 @@ pub use weights::PalletWeight;
 use weights::WeightInfo;
 
 use cf_chains::{
-	btc::BitcoinCrypto, dot::PolkadotCrypto, evm::EvmCrypto, Bitcoin, Ethereum, Polkadot,
+	btc::BitcoinCrypto, dot::PolkadotCrypto, evm::EvmCrypto, hub::Assethub, Bitcoin, Ethereum,
+	Polkadot,
 };
 use cf_primitives::{Ed25519PublicKey, Ipv6Addr, Port};
 use cf_traits::{CfeBroadcastRequest, CfeMultisigRequest, CfePeerRegistration, Chainflip};
@@ -105,6 +106,12 @@ impl<T: Config> CfeBroadcastRequest<T, Polkadot> for Pallet<T> {
 	}
 }
 
+impl<T: Config> CfeBroadcastRequest<T, Assethub> for Pallet<T> {
+	fn tx_broadcast_request(req: TxBroadcastRequest<T, Assethub>) {
+		CfeEvents::<T>::append(CfeEvent::<T>::HubTxBroadcastRequest(req))
+	}
+}
+
 impl<T: Config> CfeBroadcastRequest<T, Bitcoin> for Pallet<T> {
 	fn tx_broadcast_request(req: TxBroadcastRequest<T, Bitcoin>) {
 		CfeEvents::<T>::append(CfeEvent::<T>::BtcTxBroadcastRequest(req))
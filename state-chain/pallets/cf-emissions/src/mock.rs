@@ -97,6 +97,7 @@ impl pallet_cf_flip::Config for Test {
 	type OnAccountFunded = MockOnAccountFunded;
 	type WeightInfo = ();
 	type WaivedFees = WaivedFeesMock;
+	type ValidatorPerformanceRecorder = ();
 }
 
 pub const EMISSION_RATE: u128 = 10;
@@ -197,6 +198,10 @@ impl Broadcaster<MockEthereum> for MockBroadcast {
 	fn clean_up_broadcast_storage(_broadcast_id: BroadcastId) {
 		unimplemented!()
 	}
+
+	fn witness_after_abort(_broadcast_id: BroadcastId) {
+		unimplemented!()
+	}
 }
 
 impl_mock_runtime_safe_mode! { emissions: PalletSafeMode }
@@ -11,6 +11,7 @@ use cf_chains::{
 	},
 	dot::{Polkadot, PolkadotAccountId, PolkadotHash, PolkadotIndex},
 	eth::Address as EthereumAddress,
+	hub::{Assethub, AssethubAccountId},
 };
 use cf_primitives::{chains::assets::eth::Asset as EthAsset, NetworkEnvironment, SemVer};
 use cf_traits::{CompatibleCfeVersions, GetBitcoinFeeInfo, NetworkEnvironmentProvider, SafeMode};
@@ -70,6 +71,8 @@ pub mod pallet {
 		type PolkadotVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Polkadot>;
 		/// On new key witnessed handler for Bitcoin
 		type BitcoinVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Bitcoin>;
+		/// On new key witnessed handler for Assethub
+		type AssethubVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Assethub>;
 
 		/// The runtime's safe mode is stored in this pallet.
 		type RuntimeSafeMode: cf_traits::SafeMode + Member + Parameter + Default;
@@ -147,6 +150,21 @@ pub mod pallet {
 	/// Current Nonce of the current Polkadot Proxy Account
 	pub type PolkadotProxyAccountNonce<T> = StorageValue<_, PolkadotIndex, ValueQuery>;
 
+	// ASSETHUB CHAIN RELATED ENVIRONMENT ITEMS
+
+	#[pallet::storage]
+	#[pallet::getter(fn assethub_genesis_hash)]
+	pub type AssethubGenesisHash<T> = StorageValue<_, PolkadotHash, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn assethub_vault_account)]
+	/// The Assethub Vault Anonymous Account
+	pub type AssethubVaultAccountId<T> = StorageValue<_, AssethubAccountId, OptionQuery>;
+
+	#[pallet::storage]
+	/// Current Nonce of the current Assethub Proxy Account
+	pub type AssethubProxyAccountNonce<T> = StorageValue<_, PolkadotIndex, ValueQuery>;
+
 	// BITCOIN CHAIN RELATED ENVIRONMENT ITEMS
 	#[pallet::storage]
 	/// The set of available UTXOs available in our Bitcoin Vault.
@@ -189,6 +207,8 @@ pub mod pallet {
 		RuntimeSafeModeUpdated { safe_mode: SafeModeUpdate<T> },
 		/// UTXO consolidation parameters has been updated
 		UtxoConsolidationParametersUpdated { params: cf_chains::btc::ConsolidationParameters },
+		/// Assethub Vault Account is successfully set
+		AssethubVaultAccountSet { assethub_vault_account_id: AssethubAccountId },
 	}
 
 	#[pallet::call]
@@ -306,6 +326,40 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Manually initiates Assethub vault key rotation completion steps so Epoch rotation can be
+		/// continued and sets the Assethub Pure Proxy Vault in environment pallet. The pure proxy
+		/// is created on Assethub with the current Polkadot AggKey as its delegate, in the same way
+		/// as the Polkadot vault.
+		///
+		/// ## Events
+		///
+		/// - [AssethubVaultAccountSet](Event::AssethubVaultAccountSet)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(5)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn witness_assethub_vault_creation(
+			origin: OriginFor<T>,
+			hub_pure_proxy_vault_key: AssethubAccountId,
+			tx_id: TxId,
+		) -> DispatchResultWithPostInfo {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			use cf_traits::VaultKeyWitnessedHandler;
+
+			AssethubVaultAccountId::<T>::put(hub_pure_proxy_vault_key);
+			Self::deposit_event(Event::<T>::AssethubVaultAccountSet {
+				assethub_vault_account_id: hub_pure_proxy_vault_key,
+			});
+
+			// Witness the agg_key rotation manually in the vaults pallet for assethub
+			T::AssethubVaultKeyWitnessedHandler::on_first_key_activated(tx_id.block_number)
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -320,6 +374,8 @@ pub mod pallet {
 		pub ethereum_chain_id: u64,
 		pub polkadot_genesis_hash: PolkadotHash,
 		pub polkadot_vault_account_id: Option<PolkadotAccountId>,
+		pub assethub_genesis_hash: PolkadotHash,
+		pub assethub_vault_account_id: Option<AssethubAccountId>,
 		pub network_environment: NetworkEnvironment,
 		pub _config: PhantomData<T>,
 	}
@@ -341,6 +397,10 @@ pub mod pallet {
 			PolkadotVaultAccountId::<T>::set(self.polkadot_vault_account_id);
 			PolkadotProxyAccountNonce::<T>::set(0);
 
+			AssethubGenesisHash::<T>::set(self.assethub_genesis_hash);
+			AssethubVaultAccountId::<T>::set(self.assethub_vault_account_id);
+			AssethubProxyAccountNonce::<T>::set(0);
+
 			BitcoinAvailableUtxos::<T>::set(vec![]);
 			ConsolidationParameters::<T>::set(INITIAL_CONSOLIDATION_PARAMETERS);
 
@@ -376,6 +436,19 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	pub fn next_assethub_proxy_account_nonce(reset_nonce: bool) -> PolkadotIndex {
+		AssethubProxyAccountNonce::<T>::mutate(|nonce| {
+			let current_nonce = *nonce;
+
+			if reset_nonce {
+				*nonce = 0;
+			} else {
+				*nonce += 1;
+			}
+			current_nonce
+		})
+	}
+
 	pub fn add_bitcoin_utxo_to_list(
 		amount: BtcAmount,
 		utxo_id: UtxoId,
@@ -220,3 +220,29 @@ fn update_safe_mode() {
 		));
 	});
 }
+
+#[test]
+fn witness_assethub_vault_creation() {
+	new_test_ext().execute_with(|| {
+		let vault_account = cf_chains::hub::AssethubAccountId::from_aliased([0xaa; 32]);
+		assert_eq!(Environment::assethub_vault_account(), None);
+
+		assert_ok!(Environment::witness_assethub_vault_creation(
+			OriginTrait::root(),
+			vault_account,
+			cf_primitives::TxId { block_number: 10, extrinsic_index: 1 },
+		));
+
+		assert_eq!(Environment::assethub_vault_account(), Some(vault_account));
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::AssethubVaultAccountSet {
+				assethub_vault_account_id: vault_account,
+			},
+		));
+
+		assert_eq!(Environment::next_assethub_proxy_account_nonce(false), 0);
+		assert_eq!(Environment::next_assethub_proxy_account_nonce(false), 1);
+		assert_eq!(Environment::next_assethub_proxy_account_nonce(true), 2);
+		assert_eq!(Environment::next_assethub_proxy_account_nonce(false), 0);
+	});
+}
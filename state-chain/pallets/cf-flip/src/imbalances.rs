@@ -94,6 +94,7 @@ impl<T: Config> Surplus<T> {
 		amount: T::Balance,
 		check_liquidity: bool,
 	) -> Option<Self> {
+		Flip::Pallet::<T>::record_balance_checkpoints(account_id);
 		Flip::Account::<T>::try_mutate_exists(account_id, |maybe_account| {
 			if let Some(account) = maybe_account.as_mut() {
 				if check_liquidity && account.liquid() < amount {
@@ -117,6 +118,7 @@ impl<T: Config> Surplus<T> {
 	/// *Warning:* if the account entry does not exist, it will be created as a side effect. Do not
 	/// expose this via  an extrinsic.
 	pub(super) fn from_acct(account_id: &T::AccountId, amount: T::Balance) -> Self {
+		Flip::Pallet::<T>::record_balance_checkpoints(account_id);
 		Flip::Account::<T>::mutate(account_id, |account| {
 			let deducted = account.balance.min(amount);
 			account.balance = account.balance.saturating_sub(deducted);
@@ -210,6 +212,7 @@ impl<T: Config> Deficit<T> {
 	/// *Warning:* if the accout does not exist, it will be created as a side effect. Do not expose
 	/// this via  an extrinsic.
 	pub(super) fn from_acct(account_id: &T::AccountId, amount: T::Balance) -> Self {
+		Flip::Pallet::<T>::record_balance_checkpoints(account_id);
 		Flip::Account::<T>::mutate(account_id, |account| {
 			let added = match account.balance.checked_add(&amount) {
 				Some(result) => {
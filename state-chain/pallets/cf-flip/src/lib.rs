@@ -15,7 +15,10 @@ use cf_primitives::FlipBalance;
 use scale_info::TypeInfo;
 pub use weights::WeightInfo;
 
-use cf_traits::{AccountInfo, Bonding, FeePayment, FundingInfo, OnAccountFunded, Slashing};
+use cf_traits::{
+	AccountInfo, BalanceCheckpointId, Bonding, FeePayment, FundingInfo, OnAccountFunded, Slashing,
+	ValidatorPerformanceRecorder,
+};
 pub use imbalances::{Deficit, ImbalanceSource, InternalSource, Surplus};
 pub use on_charge_transaction::FlipTransactionPayment;
 
@@ -28,7 +31,9 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	pallet_prelude::*,
 	sp_runtime::{
-		traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Saturating, Zero},
+		traits::{
+			AtLeast32BitUnsigned, MaybeSerializeDeserialize, Saturating, UniqueSaturatedInto, Zero,
+		},
 		DispatchError, Permill, RuntimeDebug,
 	},
 };
@@ -77,6 +82,12 @@ pub mod pallet {
 			AccountId = Self::AccountId,
 			RuntimeCall = <Self as frame_system::Config>::RuntimeCall,
 		>;
+
+		/// Keeps a record of the amounts slashed from validators.
+		type ValidatorPerformanceRecorder: ValidatorPerformanceRecorder<
+			Self::AccountId,
+			Self::Balance,
+		>;
 	}
 
 	#[pallet::pallet]
@@ -114,6 +125,29 @@ pub mod pallet {
 	#[pallet::getter(fn offchain_funds)]
 	pub type OffchainFunds<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
 
+	/// The id of the next balance checkpoint.
+	#[pallet::storage]
+	pub type NextBalanceCheckpointId<T: Config> = StorageValue<_, BalanceCheckpointId, ValueQuery>;
+
+	/// The balance checkpoints that are still recording balances.
+	#[pallet::storage]
+	pub type ActiveBalanceCheckpoints<T: Config> =
+		StorageValue<_, Vec<BalanceCheckpointId>, ValueQuery>;
+
+	/// The balance of each account at a checkpoint. Recorded the first time the balance changes
+	/// after the checkpoint was created, so accounts without a record still have the balance they
+	/// had at the checkpoint.
+	#[pallet::storage]
+	pub type BalanceCheckpoints<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BalanceCheckpointId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -126,6 +160,10 @@ pub mod pallet {
 			who: T::AccountId,
 			amount: T::Balance,
 		},
+		SlashRefunded {
+			who: T::AccountId,
+			amount: T::Balance,
+		},
 		AccountReaped {
 			who: T::AccountId,
 			dust_burned: T::Balance,
@@ -398,6 +436,21 @@ impl<T: Config> Pallet<T> {
 	pub fn deposit_pending_redemption(account_id: &T::AccountId, amount: T::Balance) -> Deficit<T> {
 		Deficit::from_pending_redemptions_reserve(account_id, amount)
 	}
+
+	/// Records the balance of an account for each active checkpoint that doesn't have a record of
+	/// it yet. Must be called before the balance of the account changes.
+	pub(crate) fn record_balance_checkpoints(account_id: &T::AccountId) {
+		let checkpoints = ActiveBalanceCheckpoints::<T>::get();
+		if checkpoints.is_empty() {
+			return
+		}
+		let balance = Self::total_balance_of(account_id);
+		for checkpoint in checkpoints {
+			if !BalanceCheckpoints::<T>::contains_key(checkpoint, account_id) {
+				BalanceCheckpoints::<T>::insert(checkpoint, account_id, balance);
+			}
+		}
+	}
 }
 
 impl<T: Config> FundingInfo for Pallet<T> {
@@ -411,6 +464,32 @@ impl<T: Config> FundingInfo for Pallet<T> {
 	fn total_onchain_funds() -> Self::Balance {
 		Self::onchain_funds()
 	}
+
+	fn create_balance_checkpoint() -> BalanceCheckpointId {
+		let checkpoint = NextBalanceCheckpointId::<T>::mutate(|id| {
+			let checkpoint = *id;
+			*id = id.wrapping_add(1);
+			checkpoint
+		});
+		ActiveBalanceCheckpoints::<T>::append(checkpoint);
+		checkpoint
+	}
+
+	fn balance_at_checkpoint(
+		checkpoint: BalanceCheckpointId,
+		account_id: &Self::AccountId,
+	) -> Self::Balance {
+		BalanceCheckpoints::<T>::get(checkpoint, account_id)
+			.unwrap_or_else(|| Self::total_balance_of(account_id))
+	}
+
+	fn release_balance_checkpoint(checkpoint: BalanceCheckpointId, limit: u32) -> (u32, bool) {
+		ActiveBalanceCheckpoints::<T>::mutate(|checkpoints| {
+			checkpoints.retain(|active| *active != checkpoint)
+		});
+		let result = BalanceCheckpoints::<T>::clear_prefix(checkpoint, limit, None);
+		(result.unique, result.maybe_cursor.is_none())
+	}
 }
 
 impl<T: Config> FeePayment for Pallet<T> {
@@ -433,6 +512,10 @@ impl<T: Config> FeePayment for Pallet<T> {
 			Err(Error::<T>::InsufficientLiquidity.into())
 		}
 	}
+
+	fn refund_fee(account_id: &Self::AccountId, amount: Self::Amount) {
+		Pallet::<T>::settle(account_id, Pallet::<T>::mint(amount).into());
+	}
 }
 
 pub struct Bonder<T>(PhantomData<T>);
@@ -551,17 +634,22 @@ impl<T: Config> OnKilledAccount<T::AccountId> for BurnFlipAccount<T> {
 pub struct FlipSlasher<T: Config>(PhantomData<T>);
 
 impl<T: Config> FlipSlasher<T> {
+	/// Returns the amount slashed, which is zero if the account could not be slashed.
 	fn attempt_slash(
 		account_id: &T::AccountId,
 		account: FlipAccount<T::Balance>,
 		slash_amount: T::Balance,
-	) {
+	) -> T::Balance {
 		if !slash_amount.is_zero() && account.can_be_slashed(slash_amount) {
 			Pallet::<T>::settle(account_id, Pallet::<T>::burn(slash_amount).into());
+			T::ValidatorPerformanceRecorder::record_slash(account_id, slash_amount);
 			Pallet::<T>::deposit_event(Event::<T>::SlashingPerformed {
 				who: account_id.clone(),
 				amount: slash_amount,
 			});
+			slash_amount
+		} else {
+			Zero::zero()
 		}
 	}
 }
@@ -580,9 +668,18 @@ where
 		Self::attempt_slash(account_id, account, slash_amount);
 	}
 
-	fn slash_balance(account_id: &Self::AccountId, slash_amount: FlipBalance) {
+	fn slash_balance(account_id: &Self::AccountId, slash_amount: FlipBalance) -> FlipBalance {
 		let account = Account::<T>::get(account_id);
-		Self::attempt_slash(account_id, account, slash_amount.into());
+		Self::attempt_slash(account_id, account, slash_amount.into()).unique_saturated_into()
+	}
+
+	fn refund_slash(account_id: &Self::AccountId, amount: FlipBalance) {
+		if amount.is_zero() {
+			return
+		}
+		let amount: T::Balance = amount.into();
+		Pallet::<T>::settle(account_id, Pallet::<T>::mint(amount).into());
+		Pallet::<T>::deposit_event(Event::<T>::SlashRefunded { who: account_id.clone(), amount });
 	}
 
 	fn calculate_slash_amount(
@@ -76,6 +76,7 @@ impl pallet_cf_flip::Config for Test {
 	type OnAccountFunded = MockOnAccountFunded;
 	type WeightInfo = ();
 	type WaivedFees = WaivedFeesMock;
+	type ValidatorPerformanceRecorder = ();
 }
 
 parameter_types! {
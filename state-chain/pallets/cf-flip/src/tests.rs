@@ -2,11 +2,11 @@
 
 use super::*;
 use crate::{
-	mock::*, Account, Bonder, Error, FlipIssuance, FlipSlasher, OffchainFunds, Reserve,
-	SlashingRate, TotalIssuance,
+	mock::*, Account, BalanceCheckpoints, Bonder, Error, FlipIssuance, FlipSlasher, OffchainFunds,
+	Reserve, SlashingRate, TotalIssuance,
 };
 use cf_primitives::FlipBalance;
-use cf_traits::{AccountInfo, Bonding, Funding, Issuance, Slashing};
+use cf_traits::{AccountInfo, Bonding, Funding, FundingInfo, Issuance, Slashing};
 use frame_support::{
 	assert_noop,
 	traits::{HandleLifetime, Imbalance},
@@ -466,6 +466,48 @@ fn test_try_debit_from_liquid_funds() {
 	});
 }
 
+#[test]
+fn slashed_funds_can_be_refunded() {
+	new_test_ext().execute_with(|| {
+		let issuance = TotalIssuance::<Test>::get();
+		Bonder::<Test>::update_bond(&ALICE, 50);
+		assert_eq!(FlipSlasher::<Test>::slash_balance(&ALICE, 10), 10);
+		assert_eq!(Flip::total_balance_of(&ALICE), 90);
+		assert_eq!(TotalIssuance::<Test>::get(), issuance - 10);
+
+		// Nothing is slashed if the account can't afford it.
+		assert_eq!(FlipSlasher::<Test>::slash_balance(&CHARLIE, 10), 0);
+
+		FlipSlasher::<Test>::refund_slash(&ALICE, 10);
+		assert_eq!(Flip::total_balance_of(&ALICE), 100);
+		assert_eq!(TotalIssuance::<Test>::get(), issuance);
+	});
+}
+
+#[test]
+fn balances_are_recorded_at_checkpoints() {
+	new_test_ext().execute_with(|| {
+		let checkpoint = <Flip as FundingInfo>::create_balance_checkpoint();
+		<Flip as Funding>::credit_funds(&ALICE, 100);
+		<Flip as Funding>::credit_funds(&ALICE, 100);
+		<Flip as Funding>::credit_funds(&CHARLIE, 100);
+		let later_checkpoint = <Flip as FundingInfo>::create_balance_checkpoint();
+		<Flip as Funding>::credit_funds(&ALICE, 100);
+
+		assert_eq!(Flip::balance_at_checkpoint(checkpoint, &ALICE), 100);
+		assert_eq!(Flip::balance_at_checkpoint(checkpoint, &BOB), 50);
+		assert_eq!(Flip::balance_at_checkpoint(checkpoint, &CHARLIE), 0);
+		assert_eq!(Flip::balance_at_checkpoint(later_checkpoint, &ALICE), 300);
+		assert_eq!(Flip::total_balance_of(&ALICE), 400);
+
+		assert_eq!(Flip::release_balance_checkpoint(checkpoint, 1), (1, false));
+		assert_eq!(Flip::release_balance_checkpoint(checkpoint, 10), (1, true));
+		<Flip as Funding>::credit_funds(&BOB, 100);
+		assert!(!BalanceCheckpoints::<Test>::contains_key(checkpoint, BOB));
+		assert_eq!(BalanceCheckpoints::<Test>::get(later_checkpoint, BOB), Some(50));
+	});
+}
+
 #[cfg(test)]
 mod test_issuance {
 	use super::*;
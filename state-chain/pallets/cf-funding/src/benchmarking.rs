@@ -266,5 +266,53 @@ mod benchmarks {
 		assert!(BoundExecutorAddress::<T>::contains_key(&caller));
 	}
 
+	#[benchmark]
+	fn schedule_retirement_redemption() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		schedule_retirement_redemption(
+			RawOrigin::Signed(caller.clone()),
+			RedemptionAmount::Max,
+			Default::default(),
+			Default::default(),
+		);
+
+		assert!(RetirementRedemptions::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn schedule_recurring_redemption() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		schedule_recurring_redemption(
+			RawOrigin::Signed(caller.clone()),
+			MinimumFunding::<T>::get(),
+			1u32.into(),
+			Default::default(),
+			Default::default(),
+		);
+
+		assert!(RecurringRedemptions::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn cancel_scheduled_redemptions() {
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(Pallet::<T>::schedule_recurring_redemption(
+			RawOrigin::Signed(caller.clone()).into(),
+			MinimumFunding::<T>::get(),
+			1u32.into(),
+			Default::default(),
+			Default::default(),
+		));
+
+		#[extrinsic_call]
+		cancel_scheduled_redemptions(RawOrigin::Signed(caller.clone()));
+
+		assert!(!RecurringRedemptions::<T>::contains_key(&caller));
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
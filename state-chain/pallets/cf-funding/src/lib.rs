@@ -16,23 +16,24 @@ pub use weights::WeightInfo;
 mod tests;
 
 use cf_chains::{eth::Address as EthereumAddress, RegisterRedemption};
-use cf_primitives::AccountRole;
+use cf_primitives::{AccountRole, EpochIndex};
 use cf_traits::{
 	impl_pallet_safe_mode, AccountInfo, AccountRoleRegistry, Bid, BidderProvider, Broadcaster,
-	Chainflip, EpochInfo, FeePayment, Funding,
+	Chainflip, EpochInfo, EpochTransitionHandler, FeePayment, Funding,
 };
 use codec::{Decode, Encode};
 use frame_support::{
-	dispatch::DispatchResultWithPostInfo,
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	ensure,
 	pallet_prelude::Weight,
 	sp_runtime::{
 		traits::{CheckedSub, UniqueSaturatedInto, Zero},
 		Saturating,
 	},
+	storage::with_storage_layer,
 	traits::{EnsureOrigin, HandleLifetime, IsType, OnKilledAccount, StorageVersion, UnixTime},
 };
-use frame_system::pallet_prelude::OriginFor;
+use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
 pub use pallet::*;
 use scale_info::TypeInfo;
 use sp_std::{
@@ -46,6 +47,9 @@ pub enum Pending {
 }
 pub const PALLET_VERSION: StorageVersion = StorageVersion::new(3);
 
+/// The maximum number of scheduled redemptions of each kind that are processed in a block.
+pub const MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK: u32 = 100;
+
 #[derive(Encode, Decode, PartialEq, Debug, TypeInfo)]
 pub struct PendingRedemptionInfo<FlipBalance> {
 	pub total: FlipBalance,
@@ -86,6 +90,27 @@ pub mod pallet {
 		}
 	}
 
+	/// A redemption that is executed automatically once the account is no longer bonded.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct RetirementRedemption<Amount: Parameter> {
+		pub amount: RedemptionAmount<Amount>,
+		pub address: EthereumAddress,
+		pub executor: Option<EthereumAddress>,
+		/// Whether the account has been bonded since the redemption was scheduled. The redemption
+		/// is only requested once a bond has been released.
+		pub bonded: bool,
+	}
+
+	/// A redemption of all funds above `floor` that is repeated every `interval` blocks.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct RecurringRedemption<Amount, BlockNumber> {
+		pub floor: Amount,
+		pub interval: BlockNumber,
+		pub address: EthereumAddress,
+		pub executor: Option<EthereumAddress>,
+		pub next_redemption_at: BlockNumber,
+	}
+
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: Chainflip {
@@ -179,6 +204,50 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type RedemptionTax<T: Config> = StorageValue<_, T::Amount, ValueQuery>;
 
+	/// Redemptions to be requested as soon as the account's bond is released.
+	#[pallet::storage]
+	pub type RetirementRedemptions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountId<T>,
+		RetirementRedemption<FlipBalance<T>>,
+		OptionQuery,
+	>;
+
+	/// Recurring redemptions of any funds held above a floor balance.
+	#[pallet::storage]
+	pub type RecurringRedemptions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountId<T>,
+		RecurringRedemption<FlipBalance<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The accounts whose recurring redemption is due at a given block.
+	#[pallet::storage]
+	pub type RecurringRedemptionsDue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<AccountId<T>>, ValueQuery>;
+
+	/// The earliest block whose due recurring redemptions have not all been processed yet.
+	#[pallet::storage]
+	pub type RecurringRedemptionsCursor<T: Config> =
+		StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// If set, retirement redemptions are being checked for released bonds, continuing after the
+	/// given account, or from the first account if `None`.
+	#[pallet::storage]
+	pub type RetirementRedemptionsCursor<T: Config> =
+		StorageValue<_, Option<AccountId<T>>, OptionQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
+			Self::process_recurring_redemptions(current_block)
+				.saturating_add(Self::process_retirement_redemptions())
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -240,6 +309,28 @@ pub mod pallet {
 
 		/// An account has been bound to an executor address.
 		BoundExecutorAddress { account_id: AccountId<T>, address: EthereumAddress },
+
+		/// A redemption has been scheduled for when the account's bond is released.
+		RetirementRedemptionScheduled {
+			account_id: AccountId<T>,
+			amount: RedemptionAmount<FlipBalance<T>>,
+			address: EthereumAddress,
+		},
+
+		/// A recurring redemption of funds above `floor` has been scheduled.
+		RecurringRedemptionScheduled {
+			account_id: AccountId<T>,
+			floor: FlipBalance<T>,
+			interval: BlockNumberFor<T>,
+			address: EthereumAddress,
+		},
+
+		/// All scheduled redemptions for the account have been cancelled.
+		ScheduledRedemptionsCancelled { account_id: AccountId<T> },
+
+		/// A scheduled redemption could not be requested. Retirement redemptions are retried
+		/// at the next epoch expiry, recurring redemptions at their next interval.
+		ScheduledRedemptionFailed { account_id: AccountId<T>, error: DispatchError },
 	}
 
 	#[pallet::error]
@@ -294,6 +385,12 @@ pub mod pallet {
 
 		/// The account is already bound to an executor address.
 		ExecutorAddressAlreadyBound,
+
+		/// The interval between recurring redemptions must be non-zero.
+		InvalidRedemptionInterval,
+
+		/// The account has no scheduled redemptions.
+		NoScheduledRedemption,
 	}
 
 	#[pallet::call]
@@ -360,135 +457,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let account_id = ensure_signed(origin)?;
 
-			ensure!(T::SafeMode::get().redeem_enabled, Error::<T>::RedeemDisabled);
-
-			// Not allowed to redeem if we are an active bidder in the auction phase
-			if T::EpochInfo::is_auction_phase() {
-				ensure!(!ActiveBidder::<T>::get(&account_id), Error::<T>::AuctionPhase);
-			}
-
-			// The redemption must be executed before a new one can be requested.
-			ensure!(
-				!PendingRedemptions::<T>::contains_key(&account_id),
-				Error::<T>::PendingRedemption
-			);
-
-			let mut restricted_balances = RestrictedBalances::<T>::get(&account_id);
-
-			// Ignore executor binding restrictions for withdrawals of restricted funds.
-			if !restricted_balances.contains_key(&address) {
-				if let Some(bound_executor) = BoundExecutorAddress::<T>::get(&account_id) {
-					ensure!(
-						executor == Some(bound_executor),
-						Error::<T>::ExecutorBindingRestrictionViolated
-					);
-				}
-			}
-
-			let redemption_fee = RedemptionTax::<T>::get();
-
-			if let Some(bound_address) = BoundRedeemAddress::<T>::get(&account_id) {
-				ensure!(
-					bound_address == address || restricted_balances.contains_key(&address),
-					Error::<T>::AccountBindingRestrictionViolated
-				);
-			}
-
-			// In case the balance is lower than the sum of restricted addresses we take this
-			// discrepancy into account so that restricted addresses can still redeem.
-			let restricted_deficit: FlipBalance<T> = restricted_balances
-				.values()
-				.copied()
-				.sum::<FlipBalance<T>>()
-				.saturating_sub(T::Flip::balance(&account_id));
-
-			// The available funds are the total balance minus whichever is larger from:
-			// - The bond.
-			// - The total restricted funds that need to remain in the account after the redemption.
-			let liquid_balance = T::Flip::balance(&account_id).saturating_sub(max(
-				T::Flip::bond(&account_id),
-				restricted_balances.values().copied().sum::<FlipBalance<T>>().saturating_sub(
-					restricted_deficit +
-						restricted_balances.get(&address).copied().unwrap_or_default(),
-				),
-			));
-
-			let (debit_amount, redeem_amount) = match amount {
-				RedemptionAmount::Max =>
-					(liquid_balance, liquid_balance.saturating_sub(redemption_fee)),
-				RedemptionAmount::Exact(amount) => (amount.saturating_add(redemption_fee), amount),
-			};
-
-			ensure!(
-				T::Flip::try_burn_fee(&account_id, redemption_fee).is_ok(),
-				Error::<T>::InsufficientBalance
-			);
-
-			let mut total_restricted_balance: FlipBalance<T> = T::Amount::zero();
-
-			// If necessary, update account restrictions.
-			if let Some(restricted_balance) = restricted_balances.get_mut(&address) {
-				// Use the full debit amount here - fees are paid by restricted funds by default.
-				total_restricted_balance = *restricted_balance;
-				restricted_balance.saturating_reduce(debit_amount);
-				if restricted_balance.is_zero() {
-					restricted_balances.remove(&address);
-				}
-				RestrictedBalances::<T>::insert(&account_id, &restricted_balances);
-			}
-
-			let remaining_balance = T::Flip::balance(&account_id)
-				.checked_sub(&redeem_amount)
-				.ok_or(Error::<T>::InsufficientBalance)?;
-
-			ensure!(
-				remaining_balance == Zero::zero() ||
-					remaining_balance >= MinimumFunding::<T>::get(),
-				Error::<T>::BelowMinimumFunding
-			);
-			ensure!(
-				remaining_balance >=
-					restricted_balances.values().copied().sum::<FlipBalance<T>>() -
-						restricted_deficit,
-				Error::<T>::InsufficientUnrestrictedFunds
-			);
-
-			// Update the account balance.
-			if redeem_amount > Zero::zero() {
-				T::Flip::try_initiate_redemption(&account_id, redeem_amount)?;
-
-				// Send the transaction.
-				let contract_expiry =
-					T::TimeSource::now().as_secs() + RedemptionTTLSeconds::<T>::get();
-				let call = T::RegisterRedemption::new_unsigned(
-					<T as Config>::FunderId::from_ref(&account_id).as_ref(),
-					redeem_amount.unique_saturated_into(),
-					address.as_fixed_bytes(),
-					contract_expiry,
-					executor,
-				);
-
-				PendingRedemptions::<T>::insert(
-					&account_id,
-					PendingRedemptionInfo {
-						total: redeem_amount,
-						restricted: min(
-							total_restricted_balance.saturating_sub(redemption_fee),
-							redeem_amount,
-						),
-						redeem_address: address,
-					},
-				);
-
-				Self::deposit_event(Event::RedemptionRequested {
-					account_id,
-					amount: redeem_amount,
-					broadcast_id: T::Broadcaster::threshold_sign_and_broadcast(call),
-					expiry_time: contract_expiry,
-				});
-			} else {
-				Self::deposit_event(Event::RedemptionAmountZero { account_id })
-			}
+			Self::try_redeem(account_id, amount, address, executor)?;
 
 			Ok(().into())
 		}
@@ -759,6 +728,129 @@ pub mod pallet {
 			});
 			Ok(().into())
 		}
+
+		/// Schedules a redemption that is requested automatically at the first epoch expiry at
+		/// which the account is no longer bonded, ie. once the last epoch in which it was an
+		/// authority has expired. Replaces any previously scheduled retirement redemption.
+		///
+		/// If the account is not bonded when the redemption is scheduled, it is only requested
+		/// once the account has become an authority and then retired.
+		///
+		/// The redemption is subject to the same restrictions as [redeem](Call::redeem). If it
+		/// cannot be requested, it is retried at the following epoch expiry.
+		///
+		/// ## Events
+		///
+		/// - [RetirementRedemptionScheduled](Event::RetirementRedemptionScheduled)
+		///
+		/// ## Errors
+		///
+		/// - [AccountBindingRestrictionViolated](Error::AccountBindingRestrictionViolated)
+		/// - [ExecutorBindingRestrictionViolated](Error::ExecutorBindingRestrictionViolated)
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::schedule_retirement_redemption())]
+		pub fn schedule_retirement_redemption(
+			origin: OriginFor<T>,
+			amount: RedemptionAmount<FlipBalance<T>>,
+			address: EthereumAddress,
+			executor: Option<EthereumAddress>,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+			Self::ensure_address_bindings(
+				&account_id,
+				&address,
+				executor,
+				&RestrictedBalances::<T>::get(&account_id),
+			)?;
+
+			RetirementRedemptions::<T>::insert(
+				&account_id,
+				RetirementRedemption {
+					amount,
+					address,
+					executor,
+					bonded: !T::Flip::bond(&account_id).is_zero(),
+				},
+			);
+			Self::deposit_event(Event::RetirementRedemptionScheduled {
+				account_id,
+				amount,
+				address,
+			});
+			Ok(().into())
+		}
+
+		/// Schedules a redemption of all funds above `floor` every `interval` blocks, starting
+		/// `interval` blocks from now. Replaces any previously scheduled recurring redemption.
+		///
+		/// Bonded funds are never redeemed, so for an authority only the funds above the larger
+		/// of `floor` and the bond are redeemed. Each redemption is subject to the same
+		/// restrictions as [redeem](Call::redeem).
+		///
+		/// ## Events
+		///
+		/// - [RecurringRedemptionScheduled](Event::RecurringRedemptionScheduled)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidRedemptionInterval](Error::InvalidRedemptionInterval)
+		/// - [BelowMinimumFunding](Error::BelowMinimumFunding)
+		/// - [AccountBindingRestrictionViolated](Error::AccountBindingRestrictionViolated)
+		/// - [ExecutorBindingRestrictionViolated](Error::ExecutorBindingRestrictionViolated)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::schedule_recurring_redemption())]
+		pub fn schedule_recurring_redemption(
+			origin: OriginFor<T>,
+			floor: FlipBalance<T>,
+			interval: BlockNumberFor<T>,
+			address: EthereumAddress,
+			executor: Option<EthereumAddress>,
+		) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+			ensure!(!interval.is_zero(), Error::<T>::InvalidRedemptionInterval);
+			ensure!(floor >= MinimumFunding::<T>::get(), Error::<T>::BelowMinimumFunding);
+			Self::ensure_address_bindings(
+				&account_id,
+				&address,
+				executor,
+				&RestrictedBalances::<T>::get(&account_id),
+			)?;
+
+			let next_redemption_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(interval);
+			RecurringRedemptionsDue::<T>::append(next_redemption_at, &account_id);
+			RecurringRedemptions::<T>::insert(
+				&account_id,
+				RecurringRedemption { floor, interval, address, executor, next_redemption_at },
+			);
+			Self::deposit_event(Event::RecurringRedemptionScheduled {
+				account_id,
+				floor,
+				interval,
+				address,
+			});
+			Ok(().into())
+		}
+
+		/// Cancels any retirement or recurring redemptions scheduled for the account.
+		///
+		/// ## Events
+		///
+		/// - [ScheduledRedemptionsCancelled](Event::ScheduledRedemptionsCancelled)
+		///
+		/// ## Errors
+		///
+		/// - [NoScheduledRedemption](Error::NoScheduledRedemption)
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::cancel_scheduled_redemptions())]
+		pub fn cancel_scheduled_redemptions(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let account_id = ensure_signed(origin)?;
+			let retirement = RetirementRedemptions::<T>::take(&account_id);
+			let recurring = RecurringRedemptions::<T>::take(&account_id);
+			ensure!(retirement.is_some() || recurring.is_some(), Error::<T>::NoScheduledRedemption);
+			Self::deposit_event(Event::ScheduledRedemptionsCancelled { account_id });
+			Ok(().into())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -827,6 +919,265 @@ impl<T: Config> Pallet<T> {
 			}
 		})
 	}
+
+	/// Requests a redemption on behalf of the account, enforcing all redemption restrictions.
+	fn try_redeem(
+		account_id: AccountId<T>,
+		amount: RedemptionAmount<FlipBalance<T>>,
+		address: EthereumAddress,
+		executor: Option<EthereumAddress>,
+	) -> DispatchResult {
+		ensure!(T::SafeMode::get().redeem_enabled, Error::<T>::RedeemDisabled);
+
+		// Not allowed to redeem if we are an active bidder in the auction phase
+		if T::EpochInfo::is_auction_phase() {
+			ensure!(!ActiveBidder::<T>::get(&account_id), Error::<T>::AuctionPhase);
+		}
+
+		// The redemption must be executed before a new one can be requested.
+		ensure!(!PendingRedemptions::<T>::contains_key(&account_id), Error::<T>::PendingRedemption);
+
+		let mut restricted_balances = RestrictedBalances::<T>::get(&account_id);
+
+		Self::ensure_address_bindings(&account_id, &address, executor, &restricted_balances)?;
+
+		let redemption_fee = RedemptionTax::<T>::get();
+
+		// In case the balance is lower than the sum of restricted addresses we take this
+		// discrepancy into account so that restricted addresses can still redeem.
+		let restricted_deficit: FlipBalance<T> = restricted_balances
+			.values()
+			.copied()
+			.sum::<FlipBalance<T>>()
+			.saturating_sub(T::Flip::balance(&account_id));
+
+		// The available funds are the total balance minus whichever is larger from:
+		// - The bond.
+		// - The total restricted funds that need to remain in the account after the redemption.
+		let liquid_balance = T::Flip::balance(&account_id).saturating_sub(max(
+			T::Flip::bond(&account_id),
+			restricted_balances.values().copied().sum::<FlipBalance<T>>().saturating_sub(
+				restricted_deficit + restricted_balances.get(&address).copied().unwrap_or_default(),
+			),
+		));
+
+		let (debit_amount, redeem_amount) = match amount {
+			RedemptionAmount::Max =>
+				(liquid_balance, liquid_balance.saturating_sub(redemption_fee)),
+			RedemptionAmount::Exact(amount) => (amount.saturating_add(redemption_fee), amount),
+		};
+
+		ensure!(
+			T::Flip::try_burn_fee(&account_id, redemption_fee).is_ok(),
+			Error::<T>::InsufficientBalance
+		);
+
+		let mut total_restricted_balance: FlipBalance<T> = T::Amount::zero();
+
+		// If necessary, update account restrictions.
+		if let Some(restricted_balance) = restricted_balances.get_mut(&address) {
+			// Use the full debit amount here - fees are paid by restricted funds by default.
+			total_restricted_balance = *restricted_balance;
+			restricted_balance.saturating_reduce(debit_amount);
+			if restricted_balance.is_zero() {
+				restricted_balances.remove(&address);
+			}
+			RestrictedBalances::<T>::insert(&account_id, &restricted_balances);
+		}
+
+		let remaining_balance = T::Flip::balance(&account_id)
+			.checked_sub(&redeem_amount)
+			.ok_or(Error::<T>::InsufficientBalance)?;
+
+		ensure!(
+			remaining_balance == Zero::zero() || remaining_balance >= MinimumFunding::<T>::get(),
+			Error::<T>::BelowMinimumFunding
+		);
+		ensure!(
+			remaining_balance >=
+				restricted_balances.values().copied().sum::<FlipBalance<T>>() -
+					restricted_deficit,
+			Error::<T>::InsufficientUnrestrictedFunds
+		);
+
+		// Update the account balance.
+		if redeem_amount > Zero::zero() {
+			T::Flip::try_initiate_redemption(&account_id, redeem_amount)?;
+
+			// Send the transaction.
+			let contract_expiry = T::TimeSource::now().as_secs() + RedemptionTTLSeconds::<T>::get();
+			let call = T::RegisterRedemption::new_unsigned(
+				<T as Config>::FunderId::from_ref(&account_id).as_ref(),
+				redeem_amount.unique_saturated_into(),
+				address.as_fixed_bytes(),
+				contract_expiry,
+				executor,
+			);
+
+			PendingRedemptions::<T>::insert(
+				&account_id,
+				PendingRedemptionInfo {
+					total: redeem_amount,
+					restricted: min(
+						total_restricted_balance.saturating_sub(redemption_fee),
+						redeem_amount,
+					),
+					redeem_address: address,
+				},
+			);
+
+			Self::deposit_event(Event::RedemptionRequested {
+				account_id,
+				amount: redeem_amount,
+				broadcast_id: T::Broadcaster::threshold_sign_and_broadcast(call),
+				expiry_time: contract_expiry,
+			});
+		} else {
+			Self::deposit_event(Event::RedemptionAmountZero { account_id })
+		}
+
+		Ok(())
+	}
+
+	/// Ensures the redemption address and executor comply with the account's bindings. Bindings
+	/// are ignored for withdrawals to restricted addresses.
+	fn ensure_address_bindings(
+		account_id: &AccountId<T>,
+		address: &EthereumAddress,
+		executor: Option<EthereumAddress>,
+		restricted_balances: &BTreeMap<EthereumAddress, FlipBalance<T>>,
+	) -> Result<(), Error<T>> {
+		if restricted_balances.contains_key(address) {
+			return Ok(())
+		}
+		if let Some(bound_executor) = BoundExecutorAddress::<T>::get(account_id) {
+			ensure!(
+				executor == Some(bound_executor),
+				Error::<T>::ExecutorBindingRestrictionViolated
+			);
+		}
+		if let Some(bound_address) = BoundRedeemAddress::<T>::get(account_id) {
+			ensure!(bound_address == *address, Error::<T>::AccountBindingRestrictionViolated);
+		}
+		Ok(())
+	}
+
+	/// Processes the recurring redemptions that are due, up to
+	/// [MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK] per block. Any that remain are processed in the
+	/// following blocks. Both the accounts and the blocks checked count towards the limit.
+	fn process_recurring_redemptions(current_block: BlockNumberFor<T>) -> Weight {
+		let mut weight_used = T::DbWeight::get().reads_writes(1, 1);
+		let mut remaining = MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK;
+		let mut block = RecurringRedemptionsCursor::<T>::get().unwrap_or(current_block);
+
+		while block <= current_block && remaining > 0 {
+			remaining -= 1;
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let mut due = RecurringRedemptionsDue::<T>::take(block);
+			let deferred = due.split_off(min(due.len(), remaining as usize));
+			remaining -= due.len() as u32;
+			weight_used.saturating_accrue(T::WeightInfo::redeem().saturating_mul(due.len() as u64));
+			for account_id in due {
+				Self::process_recurring_redemption(account_id, block, current_block);
+			}
+			if !deferred.is_empty() {
+				RecurringRedemptionsDue::<T>::insert(block, deferred);
+				break
+			}
+			block.saturating_inc();
+		}
+
+		RecurringRedemptionsCursor::<T>::put(block);
+		weight_used
+	}
+
+	/// Requests the retirement redemptions of accounts whose bond has been released, checking up
+	/// to [MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK] accounts per block while a check is in progress.
+	fn process_retirement_redemptions() -> Weight {
+		let mut weight_used = T::DbWeight::get().reads(1);
+		let Some(start_after) = RetirementRedemptionsCursor::<T>::get() else { return weight_used };
+
+		let redemptions = match start_after {
+			Some(account_id) => RetirementRedemptions::<T>::iter_from(
+				RetirementRedemptions::<T>::hashed_key_for(account_id),
+			),
+			None => RetirementRedemptions::<T>::iter(),
+		}
+		.take(MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK as usize)
+		.collect::<Vec<_>>();
+
+		weight_used
+			.saturating_accrue(T::WeightInfo::redeem().saturating_mul(redemptions.len() as u64));
+		if redemptions.len() < MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK as usize {
+			RetirementRedemptionsCursor::<T>::kill();
+		} else {
+			RetirementRedemptionsCursor::<T>::put(redemptions.last().map(|(id, _)| id.clone()));
+		}
+
+		for (account_id, mut redemption) in redemptions {
+			// Only retire accounts that have been seen bonded, ie. that were authorities.
+			if !T::Flip::bond(&account_id).is_zero() {
+				if !redemption.bonded {
+					redemption.bonded = true;
+					RetirementRedemptions::<T>::insert(&account_id, redemption);
+				}
+				continue
+			}
+			if !redemption.bonded {
+				continue
+			}
+			match with_storage_layer(|| {
+				Self::try_redeem(
+					account_id.clone(),
+					redemption.amount,
+					redemption.address,
+					redemption.executor,
+				)
+			}) {
+				Ok(()) => RetirementRedemptions::<T>::remove(&account_id),
+				Err(error) =>
+					Self::deposit_event(Event::ScheduledRedemptionFailed { account_id, error }),
+			}
+		}
+
+		weight_used
+	}
+
+	/// Redeems any funds above the floor and the bond, then schedules the next redemption.
+	fn process_recurring_redemption(
+		account_id: AccountId<T>,
+		due_block: BlockNumberFor<T>,
+		current_block: BlockNumberFor<T>,
+	) {
+		let Some(mut recurring) = RecurringRedemptions::<T>::get(&account_id) else { return };
+		// The entry may have been replaced since this account was queued.
+		if recurring.next_redemption_at != due_block {
+			return
+		}
+
+		let redeemable = T::Flip::balance(&account_id)
+			.saturating_sub(max(recurring.floor, T::Flip::bond(&account_id)))
+			.saturating_sub(RedemptionTax::<T>::get());
+		if !redeemable.is_zero() {
+			if let Err(error) = with_storage_layer(|| {
+				Self::try_redeem(
+					account_id.clone(),
+					RedemptionAmount::Exact(redeemable),
+					recurring.address,
+					recurring.executor,
+				)
+			}) {
+				Self::deposit_event(Event::ScheduledRedemptionFailed {
+					account_id: account_id.clone(),
+					error,
+				});
+			}
+		}
+
+		recurring.next_redemption_at = current_block.saturating_add(recurring.interval);
+		RecurringRedemptionsDue::<T>::append(recurring.next_redemption_at, &account_id);
+		RecurringRedemptions::<T>::insert(&account_id, recurring);
+	}
 }
 
 impl<T: Config> BidderProvider for Pallet<T> {
@@ -855,5 +1206,15 @@ impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
 		RestrictedBalances::<T>::remove(account_id);
 		BoundExecutorAddress::<T>::remove(account_id);
 		BoundRedeemAddress::<T>::remove(account_id);
+		RetirementRedemptions::<T>::remove(account_id);
+		RecurringRedemptions::<T>::remove(account_id);
+	}
+}
+
+/// Starts checking the scheduled retirement redemptions for released bonds. The check restarts
+/// from the first account if one is already in progress, since more bonds may have been released.
+impl<T: Config> EpochTransitionHandler for Pallet<T> {
+	fn on_expired_epoch(_expired: EpochIndex) {
+		RetirementRedemptionsCursor::<T>::put(None::<AccountId<T>>);
 	}
 }
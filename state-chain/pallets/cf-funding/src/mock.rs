@@ -81,6 +81,7 @@ impl pallet_cf_flip::Config for Test {
 	type OnAccountFunded = MockOnAccountFunded;
 	type WeightInfo = ();
 	type WaivedFees = WaivedFeesMock;
+	type ValidatorPerformanceRecorder = ();
 }
 
 cf_traits::impl_mock_ensure_witnessed_for_origin!(RuntimeOrigin);
@@ -185,6 +186,10 @@ impl Broadcaster<Ethereum> for MockBroadcaster {
 	fn clean_up_broadcast_storage(_broadcast_id: BroadcastId) {
 		unimplemented!()
 	}
+
+	fn witness_after_abort(_broadcast_id: BroadcastId) {
+		unimplemented!()
+	}
 }
 
 impl_mock_runtime_safe_mode! { funding: PalletSafeMode }
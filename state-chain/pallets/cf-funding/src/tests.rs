@@ -1,19 +1,21 @@
 use crate::{
 	mock::*, pallet, ActiveBidder, BoundExecutorAddress, Error, EthereumAddress,
-	PendingRedemptions, RedemptionAmount, RedemptionTax, RestrictedAddresses, RestrictedBalances,
+	PendingRedemptions, RecurringRedemptions, RecurringRedemptionsDue, RedemptionAmount,
+	RedemptionTax, RestrictedAddresses, RestrictedBalances, RetirementRedemptions,
+	RetirementRedemptionsCursor, MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK,
 };
 use cf_primitives::FlipBalance;
 use cf_test_utilities::assert_event_sequence;
 use cf_traits::{
 	mocks::account_role_registry::MockAccountRoleRegistry, AccountInfo, AccountRoleRegistry,
-	Bonding, SetSafeMode, Slashing,
+	Bonding, EpochTransitionHandler, SetSafeMode, Slashing,
 };
 use sp_core::H160;
 
 use crate::BoundRedeemAddress;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use pallet_cf_flip::{Bonder, FlipSlasher};
-use sp_runtime::{traits::BadOrigin, DispatchError};
+use sp_runtime::{traits::BadOrigin, AccountId32, DispatchError};
 
 type FlipError = pallet_cf_flip::Error<Test>;
 
@@ -1784,3 +1786,321 @@ fn cannot_redeem_to_non_restricted_address_with_balance_lower_than_restricted_fu
 		);
 	});
 }
+
+#[test]
+fn retirement_redemption_is_requested_once_unbonded() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: u128 = 200;
+		const BOND: u128 = 100;
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT,
+			ETH_ZERO_ADDRESS,
+			TX_HASH
+		));
+		Bonder::<Test>::update_bond(&ALICE, BOND);
+
+		// Bindings are enforced when scheduling.
+		assert_ok!(Funding::bind_redeem_address(RuntimeOrigin::signed(ALICE), ETH_DUMMY_ADDR));
+		assert_noop!(
+			Funding::schedule_retirement_redemption(
+				RuntimeOrigin::signed(ALICE),
+				RedemptionAmount::Max,
+				ETH_ZERO_ADDRESS,
+				Default::default()
+			),
+			Error::<Test>::AccountBindingRestrictionViolated
+		);
+		assert_ok!(Funding::schedule_retirement_redemption(
+			RuntimeOrigin::signed(ALICE),
+			RedemptionAmount::Max,
+			ETH_DUMMY_ADDR,
+			Default::default()
+		));
+
+		// Nothing happens while Alice is still bonded.
+		<Funding as EpochTransitionHandler>::on_expired_epoch(1);
+		Funding::on_initialize(1);
+		assert!(RetirementRedemptions::<Test>::contains_key(ALICE));
+		assert!(!PendingRedemptions::<Test>::contains_key(ALICE));
+
+		// Once the bond is released, the redemption is requested.
+		Bonder::<Test>::update_bond(&ALICE, 0);
+		<Funding as EpochTransitionHandler>::on_expired_epoch(2);
+		Funding::on_initialize(2);
+		assert!(!RetirementRedemptions::<Test>::contains_key(ALICE));
+		assert_eq!(PendingRedemptions::<Test>::get(ALICE).unwrap().total, AMOUNT - REDEMPTION_TAX);
+		assert_eq!(MockBroadcaster::received_requests(), vec![AMOUNT - REDEMPTION_TAX]);
+	});
+}
+
+#[test]
+fn retirement_redemption_is_only_requested_after_being_bonded() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: u128 = 200;
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT,
+			ETH_ZERO_ADDRESS,
+			TX_HASH
+		));
+		assert_ok!(Funding::schedule_retirement_redemption(
+			RuntimeOrigin::signed(ALICE),
+			RedemptionAmount::Max,
+			ETH_DUMMY_ADDR,
+			Default::default()
+		));
+		assert!(!RetirementRedemptions::<Test>::get(ALICE).unwrap().bonded);
+
+		// Alice has never been an authority, so there is nothing to retire from.
+		<Funding as EpochTransitionHandler>::on_expired_epoch(1);
+		Funding::on_initialize(1);
+		assert!(RetirementRedemptions::<Test>::contains_key(ALICE));
+		assert!(!PendingRedemptions::<Test>::contains_key(ALICE));
+
+		// Once she has been bonded, the release of her bond triggers the redemption.
+		Bonder::<Test>::update_bond(&ALICE, AMOUNT);
+		<Funding as EpochTransitionHandler>::on_expired_epoch(2);
+		Funding::on_initialize(2);
+		assert!(RetirementRedemptions::<Test>::get(ALICE).unwrap().bonded);
+		assert!(!PendingRedemptions::<Test>::contains_key(ALICE));
+
+		Bonder::<Test>::update_bond(&ALICE, 0);
+		<Funding as EpochTransitionHandler>::on_expired_epoch(3);
+		Funding::on_initialize(3);
+		assert!(!RetirementRedemptions::<Test>::contains_key(ALICE));
+		assert_eq!(PendingRedemptions::<Test>::get(ALICE).unwrap().total, AMOUNT - REDEMPTION_TAX);
+	});
+}
+
+#[test]
+fn failed_retirement_redemption_is_reverted_and_retried() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: u128 = 100;
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT,
+			ETH_ZERO_ADDRESS,
+			TX_HASH
+		));
+		// The tax is burned before the remaining balance is found to be below the minimum.
+		Bonder::<Test>::update_bond(&ALICE, AMOUNT);
+		assert_ok!(Funding::schedule_retirement_redemption(
+			RuntimeOrigin::signed(ALICE),
+			RedemptionAmount::Exact(AMOUNT - MIN_FUNDING),
+			ETH_DUMMY_ADDR,
+			Default::default()
+		));
+		Bonder::<Test>::update_bond(&ALICE, 0);
+
+		<Funding as EpochTransitionHandler>::on_expired_epoch(1);
+		Funding::on_initialize(1);
+		System::assert_last_event(RuntimeEvent::Funding(crate::Event::ScheduledRedemptionFailed {
+			account_id: ALICE,
+			error: Error::<Test>::BelowMinimumFunding.into(),
+		}));
+		assert_eq!(Flip::balance(&ALICE), AMOUNT);
+		assert!(RetirementRedemptions::<Test>::contains_key(ALICE));
+
+		// Once there are enough funds, the retry succeeds.
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT,
+			ETH_ZERO_ADDRESS,
+			TX_HASH
+		));
+		<Funding as EpochTransitionHandler>::on_expired_epoch(2);
+		Funding::on_initialize(2);
+		assert!(!RetirementRedemptions::<Test>::contains_key(ALICE));
+		assert_eq!(PendingRedemptions::<Test>::get(ALICE).unwrap().total, AMOUNT - MIN_FUNDING);
+	});
+}
+
+#[test]
+fn recurring_redemption_redeems_funds_above_floor() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: u128 = 100;
+		const FLOOR: u128 = 20;
+		const INTERVAL: u64 = 10;
+		assert_noop!(
+			Funding::schedule_recurring_redemption(
+				RuntimeOrigin::signed(ALICE),
+				FLOOR,
+				0,
+				ETH_DUMMY_ADDR,
+				Default::default()
+			),
+			Error::<Test>::InvalidRedemptionInterval
+		);
+		assert_noop!(
+			Funding::schedule_recurring_redemption(
+				RuntimeOrigin::signed(ALICE),
+				MIN_FUNDING - 1,
+				INTERVAL,
+				ETH_DUMMY_ADDR,
+				Default::default()
+			),
+			Error::<Test>::BelowMinimumFunding
+		);
+
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT,
+			ETH_ZERO_ADDRESS,
+			TX_HASH
+		));
+		assert_ok!(Funding::schedule_recurring_redemption(
+			RuntimeOrigin::signed(ALICE),
+			FLOOR,
+			INTERVAL,
+			ETH_DUMMY_ADDR,
+			Default::default()
+		));
+
+		// Everything above the floor is redeemed, net of the redemption tax.
+		Funding::on_initialize(1 + INTERVAL);
+		let first_redemption = AMOUNT - FLOOR - REDEMPTION_TAX;
+		assert_eq!(PendingRedemptions::<Test>::get(ALICE).unwrap().total, first_redemption);
+		assert_ok!(Funding::redeemed(RuntimeOrigin::root(), ALICE, first_redemption, TX_HASH));
+		assert_eq!(Flip::balance(&ALICE), FLOOR);
+
+		// Nothing to redeem, but the redemption is rescheduled.
+		Funding::on_initialize(1 + 2 * INTERVAL);
+		assert!(!PendingRedemptions::<Test>::contains_key(ALICE));
+		assert_eq!(
+			RecurringRedemptions::<Test>::get(ALICE).unwrap().next_redemption_at,
+			1 + 3 * INTERVAL
+		);
+
+		// New rewards are redeemed at the next interval.
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT,
+			ETH_ZERO_ADDRESS,
+			TX_HASH
+		));
+		Funding::on_initialize(1 + 3 * INTERVAL);
+		assert_eq!(PendingRedemptions::<Test>::get(ALICE).unwrap().total, AMOUNT - REDEMPTION_TAX);
+		assert_eq!(
+			MockBroadcaster::received_requests(),
+			vec![first_redemption, AMOUNT - REDEMPTION_TAX]
+		);
+	});
+}
+
+#[test]
+fn can_cancel_scheduled_redemptions() {
+	new_test_ext().execute_with(|| {
+		const INTERVAL: u64 = 10;
+		assert_noop!(
+			Funding::cancel_scheduled_redemptions(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NoScheduledRedemption
+		);
+		assert_ok!(Funding::funded(RuntimeOrigin::root(), ALICE, 100, ETH_ZERO_ADDRESS, TX_HASH));
+		assert_ok!(Funding::schedule_retirement_redemption(
+			RuntimeOrigin::signed(ALICE),
+			RedemptionAmount::Max,
+			ETH_DUMMY_ADDR,
+			Default::default()
+		));
+		assert_ok!(Funding::schedule_recurring_redemption(
+			RuntimeOrigin::signed(ALICE),
+			MIN_FUNDING,
+			INTERVAL,
+			ETH_DUMMY_ADDR,
+			Default::default()
+		));
+
+		assert_ok!(Funding::cancel_scheduled_redemptions(RuntimeOrigin::signed(ALICE)));
+		assert!(!RetirementRedemptions::<Test>::contains_key(ALICE));
+		assert!(!RecurringRedemptions::<Test>::contains_key(ALICE));
+
+		<Funding as EpochTransitionHandler>::on_expired_epoch(1);
+		Funding::on_initialize(1 + INTERVAL);
+		assert!(!PendingRedemptions::<Test>::contains_key(ALICE));
+	});
+}
+
+#[test]
+fn scheduled_redemptions_are_processed_in_batches() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: u128 = 100;
+		const INTERVAL: u64 = 10;
+		let accounts = (0..=MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK)
+			.map(|i| {
+				let mut raw = [0xf0; 32];
+				raw[..4].copy_from_slice(&i.to_le_bytes());
+				AccountId32::new(raw)
+			})
+			.collect::<Vec<_>>();
+		for account_id in &accounts {
+			assert_ok!(Funding::funded(
+				RuntimeOrigin::root(),
+				account_id.clone(),
+				AMOUNT,
+				ETH_ZERO_ADDRESS,
+				TX_HASH
+			));
+			Bonder::<Test>::update_bond(account_id, AMOUNT);
+			assert_ok!(Funding::schedule_retirement_redemption(
+				RuntimeOrigin::signed(account_id.clone()),
+				RedemptionAmount::Max,
+				ETH_DUMMY_ADDR,
+				Default::default()
+			));
+			Bonder::<Test>::update_bond(account_id, 0);
+		}
+
+		// Only a limited number of retirement redemptions are requested per block.
+		<Funding as EpochTransitionHandler>::on_expired_epoch(1);
+		Funding::on_initialize(1);
+		assert_eq!(RetirementRedemptions::<Test>::iter().count(), 1);
+		Funding::on_initialize(2);
+		assert_eq!(RetirementRedemptions::<Test>::iter().count(), 0);
+		assert_eq!(RetirementRedemptionsCursor::<Test>::get(), None);
+		for account_id in &accounts {
+			assert_ok!(Funding::redeemed(
+				RuntimeOrigin::root(),
+				account_id.clone(),
+				AMOUNT - REDEMPTION_TAX,
+				TX_HASH
+			));
+			assert_ok!(Funding::funded(
+				RuntimeOrigin::root(),
+				account_id.clone(),
+				AMOUNT,
+				ETH_ZERO_ADDRESS,
+				TX_HASH
+			));
+			assert_ok!(Funding::schedule_recurring_redemption(
+				RuntimeOrigin::signed(account_id.clone()),
+				MIN_FUNDING,
+				INTERVAL,
+				ETH_DUMMY_ADDR,
+				Default::default()
+			));
+		}
+
+		// Recurring redemptions that don't fit into the block are processed in the next one, and
+		// are then rescheduled relative to the block they were processed in.
+		let due_block = 1 + INTERVAL;
+		for block in 3..=due_block {
+			Funding::on_initialize(block);
+		}
+		assert_eq!(
+			PendingRedemptions::<Test>::iter().count() as u32,
+			MAX_SCHEDULED_REDEMPTIONS_PER_BLOCK - 1
+		);
+		assert_eq!(RecurringRedemptionsDue::<Test>::get(due_block).len(), 2);
+		Funding::on_initialize(due_block + 1);
+		assert_eq!(PendingRedemptions::<Test>::iter().count(), accounts.len());
+		assert!(!RecurringRedemptionsDue::<Test>::contains_key(due_block));
+		assert_eq!(RecurringRedemptionsDue::<Test>::get(due_block + 1 + INTERVAL).len(), 2);
+	});
+}
@@ -44,6 +44,9 @@ pub trait WeightInfo {
 	fn bind_redeem_address() -> Weight;
 	fn update_restricted_addresses(a: u32, b: u32, c: u32, ) -> Weight;
 	fn bind_executor_address() -> Weight;
+	fn schedule_retirement_redemption() -> Weight;
+	fn schedule_recurring_redemption() -> Weight;
+	fn cancel_scheduled_redemptions() -> Weight;
 }
 
 /// Weights for pallet_cf_funding using the Substrate node and recommended hardware.
@@ -375,6 +378,47 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Funding::RestrictedBalances` (r:1 w:0)
+	/// Proof: `Funding::RestrictedBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundExecutorAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundExecutorAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundRedeemAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundRedeemAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:0)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Funding::RetirementRedemptions` (r:0 w:1)
+	/// Proof: `Funding::RetirementRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn schedule_retirement_redemption() -> Weight {
+		Weight::from_parts(22_531_000, 3898)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Funding::MinimumFunding` (r:1 w:0)
+	/// Proof: `Funding::MinimumFunding` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RestrictedBalances` (r:1 w:0)
+	/// Proof: `Funding::RestrictedBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundExecutorAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundExecutorAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundRedeemAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundRedeemAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RecurringRedemptionsDue` (r:1 w:1)
+	/// Proof: `Funding::RecurringRedemptionsDue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RecurringRedemptions` (r:0 w:1)
+	/// Proof: `Funding::RecurringRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn schedule_recurring_redemption() -> Weight {
+		Weight::from_parts(25_047_000, 3777)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Funding::RetirementRedemptions` (r:1 w:1)
+	/// Proof: `Funding::RetirementRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RecurringRedemptions` (r:1 w:1)
+	/// Proof: `Funding::RecurringRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_scheduled_redemptions() -> Weight {
+		Weight::from_parts(17_480_000, 3749)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -705,4 +749,45 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Funding::RestrictedBalances` (r:1 w:0)
+	/// Proof: `Funding::RestrictedBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundExecutorAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundExecutorAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundRedeemAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundRedeemAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:0)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Funding::RetirementRedemptions` (r:0 w:1)
+	/// Proof: `Funding::RetirementRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn schedule_retirement_redemption() -> Weight {
+		Weight::from_parts(22_531_000, 3898)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Funding::MinimumFunding` (r:1 w:0)
+	/// Proof: `Funding::MinimumFunding` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RestrictedBalances` (r:1 w:0)
+	/// Proof: `Funding::RestrictedBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundExecutorAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundExecutorAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::BoundRedeemAddress` (r:1 w:0)
+	/// Proof: `Funding::BoundRedeemAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RecurringRedemptionsDue` (r:1 w:1)
+	/// Proof: `Funding::RecurringRedemptionsDue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RecurringRedemptions` (r:0 w:1)
+	/// Proof: `Funding::RecurringRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn schedule_recurring_redemption() -> Weight {
+		Weight::from_parts(25_047_000, 3777)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Funding::RetirementRedemptions` (r:1 w:1)
+	/// Proof: `Funding::RetirementRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Funding::RecurringRedemptions` (r:1 w:1)
+	/// Proof: `Funding::RecurringRedemptions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_scheduled_redemptions() -> Weight {
+		Weight::from_parts(17_480_000, 3749)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
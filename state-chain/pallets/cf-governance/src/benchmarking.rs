@@ -165,5 +165,51 @@ mod benchmarks {
 		dispatch_whitelisted_call(RawOrigin::Signed(caller.clone()), 1);
 	}
 
+	#[benchmark]
+	fn set_timelock_delay() {
+		let category =
+			T::CallCategoriser::categorise(&frame_system::Call::remark { remark: vec![] }.into());
+		let call = Call::<T>::set_timelock_delay { category: category.clone(), delay: Some(100) };
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin));
+		}
+
+		assert_eq!(TimelockDelays::<T>::get(category), Some(100));
+	}
+
+	#[benchmark]
+	fn veto_timelocked_call() {
+		let caller: T::AccountId = whitelisted_caller();
+		<Members<T>>::put(BTreeSet::from([caller.clone()]));
+		let call: <T as Config>::RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+		TimelockDelays::<T>::insert(T::CallCategoriser::categorise(&call), 100);
+		Pallet::<T>::schedule_execution(call.encode(), 1, ExecutionMode::Automatic).unwrap();
+
+		#[extrinsic_call]
+		veto_timelocked_call(RawOrigin::Signed(caller.clone()), 1);
+
+		assert!(!TimelockedCalls::<T>::contains_key(1));
+	}
+
+	#[benchmark]
+	fn release_timelocked_calls(b: Linear<0, 100>) {
+		let call: <T as Config>::RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+		TimelockDelays::<T>::insert(T::CallCategoriser::categorise(&call), 0);
+		for proposal_id in 0..b {
+			Pallet::<T>::schedule_execution(call.encode(), proposal_id, ExecutionMode::Automatic)
+				.unwrap();
+		}
+
+		#[block]
+		{
+			Pallet::<T>::release_timelocked_calls();
+		}
+
+		assert_eq!(ExecutionPipeline::<T>::decode_len().unwrap_or_default(), b as usize);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
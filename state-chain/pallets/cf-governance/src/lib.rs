@@ -9,10 +9,11 @@ use codec::{Codec, Decode, Encode};
 use frame_support::{
 	dispatch::GetDispatchInfo,
 	ensure,
-	pallet_prelude::{DispatchResultWithPostInfo, Weight},
+	pallet_prelude::{DispatchResultWithPostInfo, Member, Weight},
 	sp_runtime::{DispatchError, Percent, TransactionOutcome},
 	storage::with_transaction,
 	traits::{EnsureOrigin, Get, StorageVersion, UnfilteredDispatchable, UnixTime},
+	Parameter,
 };
 pub use pallet::*;
 use sp_std::{boxed::Box, ops::Add, vec::Vec};
@@ -33,6 +34,18 @@ mod mock;
 mod tests;
 
 pub type ProposalId = u32;
+
+/// The maximum number of calls that can be waiting for their timelock to elapse at once.
+pub const MAX_TIMELOCKED_CALLS: u32 = 100;
+
+/// Assigns governance calls to a category. Each category can be configured with its own minimum
+/// delay before approved calls are executed.
+pub trait CallCategoriser<Call> {
+	type Category: Member + Parameter + Ord;
+
+	fn categorise(call: &Call) -> Self::Category;
+}
+
 /// Implements the functionality of the Chainflip governance.
 #[frame_support::pallet]
 pub mod pallet {
@@ -77,7 +90,31 @@ pub mod pallet {
 		pub execution: ExecutionMode,
 	}
 
+	/// A proposal whose execution has been delayed by the timelock of its call category.
+	#[derive(Encode, Decode, TypeInfo, Clone, RuntimeDebug, PartialEq, Eq)]
+	pub struct TimelockedCall<AccountId, Category> {
+		/// Encoded representation of the approved extrinsic.
+		pub call: OpaqueCall,
+		/// The category the call was assigned to when it was scheduled.
+		pub category: Category,
+		/// Whether the call is executed automatically or pre-authorised for manual dispatch once
+		/// the timelock elapses.
+		pub execution: ExecutionMode,
+		/// The time at which the call becomes executable.
+		pub execute_at: Timestamp,
+		/// Members who have vetoed the call during its timelock.
+		pub vetoed_by: BTreeSet<AccountId>,
+	}
+
+	#[derive(Encode, Decode, TypeInfo, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+	pub struct ScheduledExecution {
+		pub proposal_id: ProposalId,
+		pub execute_at: Timestamp,
+	}
+
 	type AccountId<T> = <T as frame_system::Config>::AccountId;
+	pub type CallCategoryOf<T> =
+		<<T as Config>::CallCategoriser as CallCategoriser<<T as Config>::RuntimeCall>>::Category;
 	type OpaqueCall = Vec<u8>;
 	type Timestamp = u64;
 
@@ -108,6 +145,8 @@ pub mod pallet {
 		type CompatibleCfeVersions: CompatibleCfeVersions;
 		/// For getting current authorities' CFE versions.
 		type AuthoritiesCfeVersions: AuthoritiesCfeVersions;
+		/// Assigns approved calls to a category for the purpose of timelocking their execution.
+		type CallCategoriser: CallCategoriser<<Self as Config>::RuntimeCall>;
 	}
 
 	#[pallet::pallet]
@@ -162,6 +201,34 @@ pub mod pallet {
 	#[pallet::getter(fn members)]
 	pub(super) type Members<T> = StorageValue<_, BTreeSet<AccountId<T>>, ValueQuery>;
 
+	/// Minimum time in seconds between approval and execution of a call, per call category.
+	/// Categories without an entry are executed in the block following approval.
+	#[pallet::storage]
+	#[pallet::getter(fn timelock_delay)]
+	pub type TimelockDelays<T: Config> =
+		StorageMap<_, Blake2_128Concat, CallCategoryOf<T>, Timestamp, OptionQuery>;
+
+	/// Approved calls waiting for their timelock to elapse.
+	#[pallet::storage]
+	pub type TimelockedCalls<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		ProposalId,
+		TimelockedCall<T::AccountId, CallCategoryOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The execution time of each timelocked call, sorted by execution time.
+	#[pallet::storage]
+	#[pallet::getter(fn timelock_schedule)]
+	pub(super) type TimelockSchedule<T> =
+		StorageValue<_, BoundedVec<ScheduledExecution, ConstU32<MAX_TIMELOCKED_CALLS>>, ValueQuery>;
+
+	/// The execution time of the first call in the [TimelockSchedule]. The schedule is only
+	/// decoded once this time has been reached.
+	#[pallet::storage]
+	pub(super) type NextTimelockedExecution<T> = StorageValue<_, Timestamp, OptionQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		/// on_initialize hook - check the ActiveProposals
@@ -169,8 +236,9 @@ pub mod pallet {
 		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
 			// Check expiry and expire the proposals if needed
 			let active_proposal_weight = Self::check_expiry();
+			let timelock_weight = Self::release_timelocked_calls();
 			let execution_weight = Self::execute_pending_proposals();
-			active_proposal_weight + execution_weight
+			active_proposal_weight + timelock_weight + execution_weight
 		}
 	}
 
@@ -195,6 +263,18 @@ pub mod pallet {
 		GovKeyCallHashWhitelisted { call_hash: GovCallHash },
 		/// Failed GovKey call
 		GovKeyCallExecutionFailed { call_hash: GovCallHash, error: DispatchError },
+		/// The timelock delay of a call category has been updated.
+		TimelockDelaySet { category: CallCategoryOf<T>, delay: Option<Timestamp> },
+		/// An approved proposal has been scheduled for execution once its timelock elapses.
+		ExecutionScheduled {
+			proposal_id: ProposalId,
+			category: CallCategoryOf<T>,
+			execute_at: Timestamp,
+		},
+		/// A member vetoed a timelocked call.
+		TimelockedCallVetoed { proposal_id: ProposalId, member: T::AccountId },
+		/// A timelocked call was cancelled by a majority of members before its execution.
+		TimelockedCallCancelled { proposal_id: ProposalId },
 	}
 
 	#[pallet::error]
@@ -215,6 +295,13 @@ pub mod pallet {
 		CallHashNotWhitelisted,
 		/// Insufficient number of CFEs are at the target version to receive the runtime upgrade.
 		NotEnoughAuthoritiesCfesAtTargetVersion,
+		/// There is no timelocked call with this proposal id - it may already have been executed
+		/// or cancelled.
+		TimelockedCallNotFound,
+		/// The member has already vetoed this call.
+		AlreadyVetoed,
+		/// The maximum number of timelocked calls has been reached.
+		TooManyTimelockedCalls,
 	}
 
 	#[pallet::call]
@@ -382,6 +469,10 @@ pub mod pallet {
 		///
 		/// Submit a call to be executed if the gov key has already committed to it.
 		///
+		/// Gov key calls are exempt from the timelock. The gov key is the emergency path for when
+		/// governance members can't act, and its calls must already have been committed to on
+		/// Ethereum and witnessed before they can be submitted here.
+		///
 		/// ## Events
 		///
 		/// - GovKeyCallDispatched
@@ -440,6 +531,78 @@ pub mod pallet {
 				Err(Error::<T>::ProposalNotFound.into())
 			}
 		}
+
+		/// Sets the minimum delay, in seconds, between the approval and the execution of calls in
+		/// the given category. Passing `None` removes the timelock for the category.
+		/// **Can only be called via the Governance Origin**
+		///
+		/// Calls that have already been scheduled keep their original execution time.
+		///
+		/// ## Events
+		///
+		/// - [TimelockDelaySet](Event::TimelockDelaySet)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::set_timelock_delay())]
+		pub fn set_timelock_delay(
+			origin: OriginFor<T>,
+			category: CallCategoryOf<T>,
+			delay: Option<Timestamp>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			TimelockDelays::<T>::set(&category, delay);
+			Self::deposit_event(Event::TimelockDelaySet { category, delay });
+			Ok(())
+		}
+
+		/// Vetoes a call that is waiting for its timelock to elapse. Once more than half of the
+		/// members have vetoed a call, it is cancelled and will not be executed.
+		///
+		/// ## Events
+		///
+		/// - [TimelockedCallVetoed](Event::TimelockedCallVetoed)
+		/// - [TimelockedCallCancelled](Event::TimelockedCallCancelled)
+		///
+		/// ## Errors
+		///
+		/// - [NotMember](Error::NotMember)
+		/// - [TimelockedCallNotFound](Error::TimelockedCallNotFound)
+		/// - [AlreadyVetoed](Error::AlreadyVetoed)
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::veto_timelocked_call())]
+		pub fn veto_timelocked_call(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Members::<T>::get().contains(&who), Error::<T>::NotMember);
+
+			let vetoes = TimelockedCalls::<T>::try_mutate(proposal_id, |timelocked_call| {
+				let timelocked_call =
+					timelocked_call.as_mut().ok_or(Error::<T>::TimelockedCallNotFound)?;
+				ensure!(timelocked_call.vetoed_by.insert(who.clone()), Error::<T>::AlreadyVetoed);
+				Ok::<_, Error<T>>(timelocked_call.vetoed_by.len())
+			})?;
+			Self::deposit_event(Event::TimelockedCallVetoed { proposal_id, member: who });
+
+			if vetoes > (Members::<T>::decode_len().ok_or(Error::<T>::DecodeMembersLenFailed)? / 2)
+			{
+				TimelockedCalls::<T>::remove(proposal_id);
+				TimelockSchedule::<T>::mutate(|schedule| {
+					schedule.retain(|scheduled| scheduled.proposal_id != proposal_id);
+					NextTimelockedExecution::<T>::set(
+						schedule.first().map(|scheduled| scheduled.execute_at),
+					);
+				});
+				Self::deposit_event(Event::TimelockedCallCancelled { proposal_id });
+			}
+
+			// Governance members don't pay transaction fees
+			Ok(Pays::No.into())
+		}
 	}
 
 	/// Genesis definition
@@ -519,11 +682,7 @@ impl<T: Config> Pallet<T> {
 		if proposal.approved.len() >
 			(Members::<T>::decode_len().ok_or(Error::<T>::DecodeMembersLenFailed)? / 2)
 		{
-			if proposal.execution == ExecutionMode::Manual {
-				PreAuthorisedGovCalls::<T>::insert(approved_id, proposal.call);
-			} else {
-				ExecutionPipeline::<T>::append((proposal.call, approved_id));
-			}
+			Self::schedule_execution(proposal.call, approved_id, proposal.execution)?;
 			Proposals::<T>::remove(approved_id);
 			ActiveProposals::<T>::mutate(|proposals| {
 				proposals.retain(|ActiveProposal { proposal_id, .. }| *proposal_id != approved_id)
@@ -555,6 +714,93 @@ impl<T: Config> Pallet<T> {
 		Self::expire_proposals(expired) + T::WeightInfo::on_initialize(num_proposals as u32)
 	}
 
+	/// Releases an approved call for execution, either immediately or, if its category is
+	/// timelocked, once the category's delay has elapsed.
+	fn schedule_execution(
+		call: OpaqueCall,
+		proposal_id: ProposalId,
+		execution: ExecutionMode,
+	) -> Result<(), Error<T>> {
+		let timelock = <T as Config>::RuntimeCall::decode(&mut &(*call)).ok().and_then(|decoded| {
+			let category = T::CallCategoriser::categorise(&decoded);
+			TimelockDelays::<T>::get(&category).map(|delay| (category, delay))
+		});
+
+		match timelock {
+			Some((category, delay)) => {
+				let execute_at = T::TimeSource::now().as_secs().saturating_add(delay);
+				TimelockSchedule::<T>::try_mutate(|schedule| {
+					// The schedule is sorted by execution time.
+					let position =
+						schedule.partition_point(|scheduled| scheduled.execute_at <= execute_at);
+					schedule
+						.try_insert(position, ScheduledExecution { proposal_id, execute_at })
+						.map_err(|_| Error::<T>::TooManyTimelockedCalls)?;
+					NextTimelockedExecution::<T>::set(
+						schedule.first().map(|scheduled| scheduled.execute_at),
+					);
+					Ok::<_, Error<T>>(())
+				})?;
+				TimelockedCalls::<T>::insert(
+					proposal_id,
+					TimelockedCall {
+						call,
+						category: category.clone(),
+						execution,
+						execute_at,
+						vetoed_by: Default::default(),
+					},
+				);
+				Self::deposit_event(Event::ExecutionScheduled {
+					proposal_id,
+					category,
+					execute_at,
+				});
+			},
+			// Undecodable calls are also passed on, so that the failure is reported on execution.
+			None => Self::release_call(call, proposal_id, execution),
+		}
+		Ok(())
+	}
+
+	/// Automatic calls are queued for execution in the next block, manual calls are
+	/// pre-authorised for dispatch via `dispatch_whitelisted_call`.
+	fn release_call(call: OpaqueCall, proposal_id: ProposalId, execution: ExecutionMode) {
+		match execution {
+			ExecutionMode::Automatic => ExecutionPipeline::<T>::append((call, proposal_id)),
+			ExecutionMode::Manual => PreAuthorisedGovCalls::<T>::insert(proposal_id, call),
+		}
+	}
+
+	/// Releases timelocked calls whose delay has elapsed for execution.
+	fn release_timelocked_calls() -> Weight {
+		let now = T::TimeSource::now().as_secs();
+		if !NextTimelockedExecution::<T>::get().is_some_and(|execute_at| execute_at <= now) {
+			return T::WeightInfo::release_timelocked_calls(0)
+		}
+
+		let due = TimelockSchedule::<T>::mutate(|schedule| {
+			let due = schedule
+				.iter()
+				.take_while(|scheduled| scheduled.execute_at <= now)
+				.copied()
+				.collect::<Vec<_>>();
+			schedule.retain(|scheduled| scheduled.execute_at > now);
+			NextTimelockedExecution::<T>::set(
+				schedule.first().map(|scheduled| scheduled.execute_at),
+			);
+			due
+		});
+		for ScheduledExecution { proposal_id, .. } in &due {
+			if let Some(TimelockedCall { call, execution, .. }) =
+				TimelockedCalls::<T>::take(proposal_id)
+			{
+				Self::release_call(call, *proposal_id, execution);
+			}
+		}
+		T::WeightInfo::release_timelocked_calls(due.len() as u32)
+	}
+
 	fn execute_pending_proposals() -> Weight {
 		let mut execution_weight = Weight::zero();
 		for (call, id) in ExecutionPipeline::<T>::take() {
@@ -597,7 +843,9 @@ impl<T: Config> Pallet<T> {
 
 	/// Dispatches a call from the governance origin, with transactional semantics, ie. if the call
 	/// dispatch returns `Err`, rolls back any storage updates.
-	fn dispatch_governance_call(call: <T as Config>::RuntimeCall) -> DispatchResultWithPostInfo {
+	pub fn dispatch_governance_call(
+		call: <T as Config>::RuntimeCall,
+	) -> DispatchResultWithPostInfo {
 		with_transaction(move || {
 			match call.dispatch_bypass_filter(RawOrigin::GovernanceApproval.into()) {
 				r @ Ok(_) => TransactionOutcome::Commit(r),
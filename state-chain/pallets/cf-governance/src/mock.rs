@@ -1,6 +1,6 @@
 use std::cell::RefCell;
 
-use crate::{self as pallet_cf_governance};
+use crate::{self as pallet_cf_governance, CallCategoriser};
 use cf_primitives::SemVer;
 use cf_traits::{
 	impl_mock_chainflip, mocks::time_source, AuthoritiesCfeVersions, CompatibleCfeVersions,
@@ -116,6 +116,23 @@ impl CompatibleCfeVersions for MockCompatibleCfeVersions {
 	}
 }
 
+pub const SYSTEM_CALLS: u8 = 0;
+pub const GOVERNANCE_CALLS: u8 = 1;
+
+pub struct MockCallCategoriser;
+impl CallCategoriser<RuntimeCall> for MockCallCategoriser {
+	type Category = u8;
+
+	fn categorise(call: &RuntimeCall) -> Self::Category {
+		match call {
+			RuntimeCall::Governance(pallet_cf_governance::Call::call_as_sudo { call }) =>
+				Self::categorise(call),
+			RuntimeCall::Governance(_) => GOVERNANCE_CALLS,
+			_ => SYSTEM_CALLS,
+		}
+	}
+}
+
 impl pallet_cf_governance::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
@@ -126,6 +143,7 @@ impl pallet_cf_governance::Config for Test {
 	type RuntimeUpgrade = RuntimeUpgradeMock;
 	type AuthoritiesCfeVersions = MockAuthoritiesCfeVersions;
 	type CompatibleCfeVersions = MockCompatibleCfeVersions;
+	type CallCategoriser = MockCallCategoriser;
 }
 
 pub const ALICE: <Test as frame_system::Config>::AccountId = 123u64;
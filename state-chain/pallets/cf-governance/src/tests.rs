@@ -1,10 +1,12 @@
 use crate::{
 	mock::*, ActiveProposals, Error, ExecutionMode, ExecutionPipeline, ExpiryTime, Members,
-	PreAuthorisedGovCalls, ProposalIdCounter,
+	NextTimelockedExecution, PreAuthorisedGovCalls, ProposalIdCounter, ScheduledExecution,
+	TimelockDelays, TimelockSchedule, TimelockedCalls, MAX_TIMELOCKED_CALLS,
 };
 use cf_primitives::SemVer;
 use cf_test_utilities::last_event;
 use cf_traits::mocks::time_source;
+use codec::Encode;
 use frame_support::{assert_err, assert_noop, assert_ok};
 use sp_runtime::Percent;
 use std::time::Duration;
@@ -318,3 +320,238 @@ fn whitelisted_gov_call() {
 		assert!(!PreAuthorisedGovCalls::<Test>::contains_key(1));
 	});
 }
+
+fn approve_mock_extrinsic() {
+	assert_ok!(Governance::propose_governance_extrinsic(
+		RuntimeOrigin::signed(ALICE),
+		mock_extrinsic(),
+		ExecutionMode::Automatic,
+	));
+	assert_ok!(Governance::approve(RuntimeOrigin::signed(BOB), 1));
+}
+
+#[test]
+fn timelocked_call_is_executed_after_delay() {
+	const START_TIME: Duration = Duration::from_secs(10);
+	const DELAY: u64 = 100;
+
+	new_test_ext()
+		.execute_with(|| {
+			time_source::Mock::reset_to(START_TIME);
+			assert_ok!(Governance::set_timelock_delay(
+				pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+				GOVERNANCE_CALLS,
+				Some(DELAY),
+			));
+			approve_mock_extrinsic();
+			assert_eq!(
+				last_event::<Test>(),
+				RuntimeEvent::Governance(crate::Event::ExecutionScheduled {
+					proposal_id: 1,
+					category: GOVERNANCE_CALLS,
+					execute_at: START_TIME.as_secs() + DELAY,
+				}),
+			);
+			assert!(TimelockedCalls::<Test>::contains_key(1));
+			assert_eq!(
+				TimelockSchedule::<Test>::get(),
+				vec![ScheduledExecution {
+					proposal_id: 1,
+					execute_at: START_TIME.as_secs() + DELAY
+				}]
+			);
+			assert_eq!(NextTimelockedExecution::<Test>::get(), Some(START_TIME.as_secs() + DELAY));
+			assert!(ExecutionPipeline::<Test>::get().is_empty());
+		})
+		.then_execute_at_next_block(|_| {
+			// The call is not executed before the delay has elapsed.
+			assert!(TimelockedCalls::<Test>::contains_key(1));
+			assert!(Members::<Test>::get().contains(&ALICE));
+			time_source::Mock::reset_to(START_TIME + Duration::from_secs(DELAY));
+		})
+		.then_execute_at_next_block(|_| {
+			assert_eq!(last_event::<Test>(), RuntimeEvent::Governance(crate::Event::Executed(1)),);
+			assert!(Members::<Test>::get().contains(&EVE));
+			assert!(!TimelockedCalls::<Test>::contains_key(1));
+			assert!(TimelockSchedule::<Test>::get().is_empty());
+			assert!(NextTimelockedExecution::<Test>::get().is_none());
+		});
+}
+
+#[test]
+fn timelock_schedule_is_sorted_and_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Governance::set_timelock_delay(
+			pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+			GOVERNANCE_CALLS,
+			Some(100),
+		));
+		// Each call is scheduled for earlier execution than the one before.
+		for proposal_id in 0..MAX_TIMELOCKED_CALLS {
+			time_source::Mock::reset_to(Duration::from_secs(
+				(MAX_TIMELOCKED_CALLS - proposal_id).into(),
+			));
+			assert_ok!(Governance::schedule_execution(
+				mock_extrinsic().encode(),
+				proposal_id + 100,
+				ExecutionMode::Automatic,
+			));
+		}
+		let schedule = TimelockSchedule::<Test>::get();
+		assert!(schedule.windows(2).all(|pair| pair[0].execute_at <= pair[1].execute_at));
+		assert_eq!(NextTimelockedExecution::<Test>::get(), Some(101));
+
+		assert_ok!(Governance::propose_governance_extrinsic(
+			RuntimeOrigin::signed(ALICE),
+			mock_extrinsic(),
+			ExecutionMode::Automatic,
+		));
+		assert_err!(
+			Governance::approve(RuntimeOrigin::signed(BOB), 1),
+			Error::<Test>::TooManyTimelockedCalls
+		);
+	});
+}
+
+#[test]
+fn manual_calls_are_only_pre_authorised_after_timelock() {
+	const DELAY: u64 = 100;
+
+	new_test_ext()
+		.execute_with(|| {
+			time_source::Mock::reset_to(Duration::from_secs(10));
+			assert_ok!(Governance::set_timelock_delay(
+				pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+				GOVERNANCE_CALLS,
+				Some(DELAY),
+			));
+			assert_ok!(Governance::propose_governance_extrinsic(
+				RuntimeOrigin::signed(ALICE),
+				mock_extrinsic(),
+				ExecutionMode::Manual,
+			));
+			assert_ok!(Governance::approve(RuntimeOrigin::signed(BOB), 1));
+			assert!(TimelockedCalls::<Test>::contains_key(1));
+			assert!(!PreAuthorisedGovCalls::<Test>::contains_key(1));
+			assert_noop!(
+				Governance::dispatch_whitelisted_call(RuntimeOrigin::signed(CHARLES), 1),
+				Error::<Test>::ProposalNotFound
+			);
+			time_source::Mock::reset_to(Duration::from_secs(10 + DELAY));
+		})
+		.then_execute_at_next_block(|_| {
+			assert!(!TimelockedCalls::<Test>::contains_key(1));
+			assert!(ExecutionPipeline::<Test>::get().is_empty());
+			assert_ok!(Governance::dispatch_whitelisted_call(RuntimeOrigin::signed(CHARLES), 1));
+			assert!(Members::<Test>::get().contains(&EVE));
+		});
+}
+
+#[test]
+fn timelock_only_applies_to_configured_categories() {
+	new_test_ext()
+		.execute_with(|| {
+			assert_ok!(Governance::set_timelock_delay(
+				pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+				SYSTEM_CALLS,
+				Some(100),
+			));
+			approve_mock_extrinsic();
+			assert!(!TimelockedCalls::<Test>::contains_key(1));
+			assert_eq!(ExecutionPipeline::<Test>::get().len(), 1);
+		})
+		.then_execute_at_next_block(|_| {
+			assert!(Members::<Test>::get().contains(&EVE));
+		});
+}
+
+#[test]
+fn sudo_calls_are_timelocked_by_the_category_of_the_inner_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Governance::set_timelock_delay(
+			pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+			SYSTEM_CALLS,
+			Some(100),
+		));
+		assert_ok!(Governance::propose_governance_extrinsic(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(RuntimeCall::Governance(pallet_cf_governance::Call::<Test>::call_as_sudo {
+				call: Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] })),
+			})),
+			ExecutionMode::Automatic,
+		));
+		assert_ok!(Governance::approve(RuntimeOrigin::signed(BOB), 1));
+		assert_eq!(TimelockedCalls::<Test>::get(1).unwrap().category, SYSTEM_CALLS);
+	});
+}
+
+#[test]
+fn timelocked_call_can_be_cancelled_by_majority_veto() {
+	new_test_ext()
+		.execute_with(|| {
+			assert_ok!(Governance::set_timelock_delay(
+				pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+				GOVERNANCE_CALLS,
+				Some(100),
+			));
+			approve_mock_extrinsic();
+
+			assert_noop!(
+				Governance::veto_timelocked_call(RuntimeOrigin::signed(EVE), 1),
+				Error::<Test>::NotMember
+			);
+			assert_noop!(
+				Governance::veto_timelocked_call(RuntimeOrigin::signed(ALICE), 2),
+				Error::<Test>::TimelockedCallNotFound
+			);
+
+			assert_ok!(Governance::veto_timelocked_call(RuntimeOrigin::signed(ALICE), 1));
+			assert_eq!(
+				last_event::<Test>(),
+				RuntimeEvent::Governance(crate::Event::TimelockedCallVetoed {
+					proposal_id: 1,
+					member: ALICE
+				}),
+			);
+			assert_noop!(
+				Governance::veto_timelocked_call(RuntimeOrigin::signed(ALICE), 1),
+				Error::<Test>::AlreadyVetoed
+			);
+			assert!(TimelockedCalls::<Test>::contains_key(1));
+
+			assert_ok!(Governance::veto_timelocked_call(RuntimeOrigin::signed(CHARLES), 1));
+			assert_eq!(
+				last_event::<Test>(),
+				RuntimeEvent::Governance(crate::Event::TimelockedCallCancelled { proposal_id: 1 }),
+			);
+			assert!(!TimelockedCalls::<Test>::contains_key(1));
+			assert!(TimelockSchedule::<Test>::get().is_empty());
+			time_source::Mock::reset_to(Duration::from_secs(1_000));
+		})
+		.then_execute_at_next_block(|_| {
+			assert!(Members::<Test>::get().contains(&ALICE));
+			assert!(ExecutionPipeline::<Test>::get().is_empty());
+		});
+}
+
+#[test]
+fn timelock_delay_can_only_be_set_by_governance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Governance::set_timelock_delay(RuntimeOrigin::signed(ALICE), SYSTEM_CALLS, Some(1)),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(Governance::set_timelock_delay(
+			pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+			SYSTEM_CALLS,
+			Some(1),
+		));
+		assert_eq!(TimelockDelays::<Test>::get(SYSTEM_CALLS), Some(1));
+		assert_ok!(Governance::set_timelock_delay(
+			pallet_cf_governance::RawOrigin::GovernanceApproval.into(),
+			SYSTEM_CALLS,
+			None,
+		));
+		assert_eq!(TimelockDelays::<Test>::get(SYSTEM_CALLS), None);
+	});
+}
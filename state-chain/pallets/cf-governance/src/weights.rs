@@ -42,6 +42,9 @@ pub trait WeightInfo {
 	fn set_whitelisted_call_hash() -> Weight;
 	fn submit_govkey_call() -> Weight;
 	fn dispatch_whitelisted_call() -> Weight;
+	fn set_timelock_delay() -> Weight;
+	fn veto_timelocked_call() -> Weight;
+	fn release_timelocked_calls(b: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_governance using the Substrate node and recommended hardware.
@@ -196,6 +199,44 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `Governance::TimelockDelays` (r:0 w:1)
+	/// Proof: `Governance::TimelockDelays` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_timelock_delay() -> Weight {
+		Weight::from_parts(11_418_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Governance::Members` (r:1 w:0)
+	/// Proof: `Governance::Members` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockedCalls` (r:1 w:1)
+	/// Proof: `Governance::TimelockedCalls` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockSchedule` (r:1 w:1)
+	/// Proof: `Governance::TimelockSchedule` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::NextTimelockedExecution` (r:0 w:1)
+	/// Proof: `Governance::NextTimelockedExecution` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn veto_timelocked_call() -> Weight {
+		Weight::from_parts(25_502_000, 3704)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::NextTimelockedExecution` (r:1 w:1)
+	/// Proof: `Governance::NextTimelockedExecution` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockSchedule` (r:1 w:1)
+	/// Proof: `Governance::TimelockSchedule` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockedCalls` (r:100 w:100)
+	/// Proof: `Governance::TimelockedCalls` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::ExecutionPipeline` (r:1 w:1)
+	/// Proof: `Governance::ExecutionPipeline` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `b` is `[0, 100]`.
+	fn release_timelocked_calls(b: u32, ) -> Weight {
+		Weight::from_parts(7_088_000, 1587)
+			.saturating_add(Weight::from_parts(4_706_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
 }
 
 // For backwards compatibility and tests
@@ -349,4 +390,42 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: `Governance::TimelockDelays` (r:0 w:1)
+	/// Proof: `Governance::TimelockDelays` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_timelock_delay() -> Weight {
+		Weight::from_parts(11_418_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Governance::Members` (r:1 w:0)
+	/// Proof: `Governance::Members` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockedCalls` (r:1 w:1)
+	/// Proof: `Governance::TimelockedCalls` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockSchedule` (r:1 w:1)
+	/// Proof: `Governance::TimelockSchedule` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::NextTimelockedExecution` (r:0 w:1)
+	/// Proof: `Governance::NextTimelockedExecution` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn veto_timelocked_call() -> Weight {
+		Weight::from_parts(25_502_000, 3704)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::NextTimelockedExecution` (r:1 w:1)
+	/// Proof: `Governance::NextTimelockedExecution` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockSchedule` (r:1 w:1)
+	/// Proof: `Governance::TimelockSchedule` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::TimelockedCalls` (r:100 w:100)
+	/// Proof: `Governance::TimelockedCalls` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Governance::ExecutionPipeline` (r:1 w:1)
+	/// Proof: `Governance::ExecutionPipeline` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `b` is `[0, 100]`.
+	fn release_timelocked_calls(b: u32, ) -> Weight {
+		Weight::from_parts(7_088_000, 1587)
+			.saturating_add(Weight::from_parts(4_706_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
 }
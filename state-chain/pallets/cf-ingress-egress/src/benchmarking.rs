@@ -7,7 +7,11 @@ use cf_chains::{
 	DepositChannel,
 };
 use frame_benchmarking::v2::*;
-use frame_support::{assert_ok, traits::OriginTrait};
+use frame_support::{
+	assert_ok,
+	traits::{Hooks, OriginTrait},
+};
+use frame_system::RawOrigin;
 
 pub(crate) type TargetChainBlockNumber<T, I> =
 	<<T as Config<I>>::TargetChain as Chain>::ChainBlockNumber;
@@ -146,6 +150,122 @@ mod benchmarks {
 		);
 	}
 
+	fn insert_broker_channel<T: Config<I>, I: 'static>(broker: &T::AccountId) -> ChannelId {
+		const CHANNEL_ID: ChannelId = 1;
+		let current_height = T::ChainTracking::get_block_height();
+		let deposit_channel = DepositChannel::generate_new::<<T as Config<I>>::AddressDerivation>(
+			CHANNEL_ID,
+			BenchmarkValue::benchmark_value(),
+		)
+		.unwrap();
+		let deposit_address = deposit_channel.address.clone();
+		DepositChannelLifetime::<T, I>::put(TargetChainBlockNumber::<T, I>::from(100u32));
+		DepositChannelRecycleBlocks::<T, I>::append((
+			current_height + 110u32.into(),
+			deposit_address.clone(),
+		));
+		DepositChannelLookup::<T, I>::insert(
+			&deposit_address,
+			DepositChannelDetails {
+				opened_at: current_height,
+				expires_at: current_height + 10u32.into(),
+				deposit_channel,
+				action: ChannelAction::<T::AccountId>::Swap {
+					destination_asset: Asset::Usdc,
+					destination_address: ForeignChainAddress::Eth(Default::default()),
+					broker_id: broker.clone(),
+					broker_commission_bps: 0,
+					affiliates: Default::default(),
+				},
+				boost_fee: 0,
+			},
+		);
+		BrokerDepositChannels::<T, I>::insert(
+			broker,
+			CHANNEL_ID,
+			BrokerDepositChannel {
+				deposit_address: deposit_address.clone(),
+				deposit_count: 0,
+				total_deposited: Zero::zero(),
+			},
+		);
+		DepositChannelBrokers::<T, I>::insert(deposit_address, broker);
+		CHANNEL_ID
+	}
+
+	#[benchmark]
+	fn close_deposit_channel() {
+		let broker: T::AccountId = whitelisted_caller();
+		let channel_id = insert_broker_channel::<T, I>(&broker);
+		// The unused share of the fee is refunded.
+		let fee: T::Amount = 100u32.into();
+		ChannelOpeningFee::<T, I>::put(fee);
+
+		#[extrinsic_call]
+		close_deposit_channel(RawOrigin::Signed(broker), channel_id);
+	}
+
+	#[benchmark]
+	fn extend_deposit_channel() {
+		let broker: T::AccountId = whitelisted_caller();
+		let channel_id = insert_broker_channel::<T, I>(&broker);
+		let fee: T::Amount = 100u32.into();
+		ChannelOpeningFee::<T, I>::put(fee);
+		T::FeePayment::mint_to_account(&broker, fee);
+
+		#[extrinsic_call]
+		extend_deposit_channel(RawOrigin::Signed(broker), channel_id, 10u32.into());
+	}
+
+	#[benchmark]
+	fn make_channel_sticky() {
+		let broker: T::AccountId = whitelisted_caller();
+		let channel_id = insert_broker_channel::<T, I>(&broker);
+		let fee: T::Amount = 100u32.into();
+		StickyChannelFee::<T, I>::put(fee);
+		T::FeePayment::mint_to_account(&broker, fee);
+
+		#[extrinsic_call]
+		make_channel_sticky(RawOrigin::Signed(broker), channel_id);
+	}
+
+	#[benchmark]
+	fn update_sticky_channel_destination() {
+		let broker: T::AccountId = whitelisted_caller();
+		let channel_id = insert_broker_channel::<T, I>(&broker);
+		T::FeePayment::mint_to_account(&broker, StickyChannelFee::<T, I>::get());
+		assert_ok!(Pallet::<T, I>::make_channel_sticky(
+			RawOrigin::Signed(broker.clone()).into(),
+			channel_id
+		));
+
+		#[extrinsic_call]
+		update_sticky_channel_destination(
+			RawOrigin::Signed(broker),
+			channel_id,
+			Asset::Usdc,
+			EncodedAddress::benchmark_value(),
+		);
+	}
+
+	#[benchmark]
+	fn channel_queues(a: Linear<1, 1_000>) {
+		// Nothing in the queue is due, so only the cost of the queue itself is measured. The
+		// sticky channel renewal queue has the same layout.
+		let not_due = T::ChainTracking::get_block_height() + 1u32.into();
+		let queue = (0..a)
+			.map(|_| (not_due, TargetChainAccount::<T, I>::benchmark_value()))
+			.collect::<Vec<_>>();
+		DepositChannelRecycleBlocks::<T, I>::put(queue);
+
+		#[block]
+		{
+			Pallet::<T, I>::on_idle(Default::default(), Weight::MAX);
+		}
+
+		assert_eq!(DepositChannelRecycleBlocks::<T, I>::decode_len(), Some(a as usize));
+	}
+
 	#[cfg(test)]
 	use crate::mock_eth::*;
 
@@ -166,5 +286,20 @@ mod benchmarks {
 		new_test_ext().execute_with(|| {
 			_disable_asset_egress::<Test, ()>(true);
 		});
+		new_test_ext().execute_with(|| {
+			_close_deposit_channel::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_extend_deposit_channel::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_make_channel_sticky::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_update_sticky_channel_destination::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_channel_queues::<Test, ()>(100, true);
+		});
 	}
 }
@@ -19,20 +19,22 @@ use frame_support::{pallet_prelude::OptionQuery, sp_runtime::SaturatedConversion
 pub use weights::WeightInfo;
 
 use cf_chains::{
-	address::{AddressConverter, AddressDerivationApi, AddressDerivationError},
-	AllBatch, AllBatchError, CcmCfParameters, CcmChannelMetadata, CcmDepositMetadata, CcmMessage,
-	Chain, ChannelLifecycleHooks, ConsolidateCall, DepositChannel, ExecutexSwapAndCall,
-	FeeEstimationApi, FetchAssetParams, ForeignChainAddress, SwapOrigin, TransferAssetParams,
+	address::{AddressConverter, AddressDerivationApi, AddressDerivationError, EncodedAddress},
+	AllBatch, AllBatchError, CcmCfParameters, CcmChannelMetadata, CcmDepositMetadata,
+	CcmFailureFallback, CcmMessage, Chain, ChannelLifecycleHooks, ConsolidateCall, DepositChannel,
+	ExecutexSwapAndCall, FeeEstimationApi, FetchAssetParams, ForeignChainAddress, SwapOrigin,
+	TransferAssetParams, TransferFallback,
 };
 use cf_primitives::{
-	Asset, BasisPoints, BroadcastId, ChannelId, EgressCounter, EgressId, EpochIndex, ForeignChain,
-	SwapId, ThresholdSignatureRequestId,
+	AccountRole, Affiliates, Asset, AssetAmount, BasisPoints, BroadcastId, ChannelId,
+	EgressCounter, EgressId, EpochIndex, ForeignChain, SwapId, ThresholdSignatureRequestId,
+	TransactionHash, MAX_AFFILIATES,
 };
 use cf_traits::{
 	liquidity::{LpBalanceApi, LpDepositHandler},
-	AssetConverter, Broadcaster, CcmHandler, CcmSwapIds, Chainflip, DepositApi, DepositHandler,
-	EgressApi, EpochInfo, FeePayment, GetBlockHeight, GetTrackedData, NetworkEnvironmentProvider,
-	ScheduledEgressDetails, SwapDepositHandler,
+	AccountRoleRegistry, AssetConverter, Broadcaster, CcmHandler, CcmSwapIds, Chainflip,
+	DepositApi, DepositHandler, EgressApi, EpochInfo, FeePayment, GetBlockHeight, GetTrackedData,
+	NetworkEnvironmentProvider, ScheduledEgressDetails, SwapDepositHandler,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -40,8 +42,8 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
-use sp_runtime::traits::UniqueSaturatedInto;
-use sp_std::{vec, vec::Vec};
+use sp_runtime::{traits::UniqueSaturatedInto, Permill};
+use sp_std::{boxed::Box, vec, vec::Vec};
 
 /// Enum wrapper for fetch and egress requests.
 #[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo)]
@@ -92,6 +94,8 @@ pub(crate) struct CrossChainMessage<C: Chain> {
 	// Where funds might be returned to if the message fails.
 	pub cf_parameters: CcmCfParameters,
 	pub gas_budget: C::ChainAmount,
+	// Where the principal is refunded to if the message can't be delivered.
+	pub fallback: Option<CcmFailureFallback>,
 }
 
 impl<C: Chain> CrossChainMessage<C> {
@@ -100,7 +104,47 @@ impl<C: Chain> CrossChainMessage<C> {
 	}
 }
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(6);
+/// Tracks a broadcast CCM whose principal is refunded to a fallback address if it is never
+/// delivered.
+#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct CcmFallbackDetails<C: Chain> {
+	pub broadcast_id: BroadcastId,
+	pub asset: C::ChainAsset,
+	pub amount: C::ChainAmount,
+	pub refund_address: C::ChainAccount,
+}
+
+/// How the principal of an undeliverable CCM is sent to its fallback address.
+#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo)]
+pub enum CcmRefundTransfer {
+	/// A `TransferFallback` call made directly from the vault.
+	TransferFallback { broadcast_id: BroadcastId },
+	/// A regular transfer, batched with the other scheduled egresses.
+	Batched { egress_id: EgressId },
+}
+
+/// A deposit channel opened by a broker, with a record of the deposits made into it.
+#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct BrokerDepositChannel<C: Chain> {
+	pub deposit_address: C::ChainAccount,
+	/// The number of deposits that have been accepted into the channel.
+	pub deposit_count: u32,
+	/// The total amount deposited into the channel, before fees.
+	pub total_deposited: C::ChainAmount,
+}
+
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(9);
+
+/// Deposits into sticky channels are only fetched in blocks that are a multiple of this interval,
+/// so that the deposits made in between are fetched together.
+pub const STICKY_CHANNEL_FETCH_INTERVAL: u32 = 10;
+
+/// The maximum number of sticky channels per chain, which bounds the renewal queue.
+pub const MAX_STICKY_CHANNELS: u32 = 1_000;
+
+/// The maximum number of sticky channels renewed in a single block. Renewals are due half a
+/// channel lifetime before expiry, so any left over are safely renewed in the following blocks.
+pub const MAX_STICKY_CHANNEL_RENEWALS_PER_BLOCK: u32 = 20;
 
 /// Calls to the external chains that has failed to be broadcast/accepted by the target chain.
 /// User can use information stored here to query for relevant information to broadcast
@@ -130,12 +174,15 @@ pub enum PalletConfigUpdate<T: Config<I>, I: 'static = ()> {
 	ChannelOpeningFee { fee: T::Amount },
 	/// Set the minimum deposit allowed for a particular asset.
 	SetMinimumDeposit { asset: TargetChainAsset<T, I>, minimum_deposit: TargetChainAmount<T, I> },
+	/// Set the fee that is burned to keep a sticky channel open for another channel lifetime,
+	/// denominated in Flipperinos.
+	StickyChannelFee { fee: T::Amount },
 }
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use cf_chains::{ExecutexSwapAndCall, TransferFallback};
+	use cf_chains::ExecutexSwapAndCall;
 	use cf_primitives::{BroadcastId, EpochIndex};
 	use cf_traits::LpDepositHandler;
 	use core::marker::PhantomData;
@@ -149,6 +196,11 @@ pub mod pallet {
 	pub(crate) type ChannelRecycleQueue<T, I> =
 		Vec<(TargetChainBlockNumber<T, I>, TargetChainAccount<T, I>)>;
 
+	pub(crate) type StickyChannelRenewalQueue<T, I> = BoundedVec<
+		(TargetChainBlockNumber<T, I>, TargetChainAccount<T, I>),
+		ConstU32<MAX_STICKY_CHANNELS>,
+	>;
+
 	pub(crate) type TargetChainAsset<T, I> = <<T as Config<I>>::TargetChain as Chain>::ChainAsset;
 	pub(crate) type TargetChainAccount<T, I> =
 		<<T as Config<I>>::TargetChain as Chain>::ChainAccount;
@@ -164,6 +216,22 @@ pub mod pallet {
 		pub deposit_details: C::DepositDetails,
 	}
 
+	/// A payment made directly into the vault that requests a swap, without a deposit channel.
+	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct VaultSwapWitness<C: Chain, AccountId> {
+		pub vault_address: C::ChainAccount,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub deposit_details: C::DepositDetails,
+		pub destination_asset: Asset,
+		pub destination_address: EncodedAddress,
+		pub min_output: AssetAmount,
+		pub refund_address: C::ChainAccount,
+		pub broker_id: AccountId,
+		pub broker_commission_bps: BasisPoints,
+		pub tx_hash: TransactionHash,
+	}
+
 	#[derive(
 		CloneNoBound, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen,
 	)]
@@ -201,6 +269,7 @@ pub mod pallet {
 			destination_address: ForeignChainAddress,
 			broker_id: AccountId,
 			broker_commission_bps: BasisPoints,
+			affiliates: Affiliates<AccountId>,
 		},
 		LiquidityProvision {
 			lp_account: AccountId,
@@ -433,6 +502,17 @@ pub mod pallet {
 	pub type FailedForeignChainCalls<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, EpochIndex, Vec<FailedForeignChainCall>, ValueQuery>;
 
+	/// CCMs that have been broadcast with a fallback refund address, until they either succeed or
+	/// their principal is refunded.
+	#[pallet::storage]
+	pub type CcmFallbacks<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, EgressId, CcmFallbackDetails<T::TargetChain>, OptionQuery>;
+
+	/// Lookup table from the broadcast of a CCM to its egress id, for CCMs in [CcmFallbacks].
+	#[pallet::storage]
+	pub type CcmFallbackEgressIds<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BroadcastId, EgressId, OptionQuery>;
+
 	#[pallet::storage]
 	pub type DepositBalances<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, DepositTracker<T, I>, ValueQuery>;
@@ -459,6 +539,46 @@ pub mod pallet {
 	pub type ChannelOpeningFee<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, T::Amount, ValueQuery>;
 
+	/// The deposit channels opened by each broker, by channel id.
+	#[pallet::storage]
+	pub type BrokerDepositChannels<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		ChannelId,
+		BrokerDepositChannel<T::TargetChain>,
+		OptionQuery,
+	>;
+
+	/// Lookup table from the address of a deposit channel in [BrokerDepositChannels] to the broker
+	/// that opened it.
+	#[pallet::storage]
+	pub type DepositChannelBrokers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAccount<T, I>, T::AccountId, OptionQuery>;
+
+	/// The fee charged for keeping a sticky channel open for one channel lifetime, in Flipperinos.
+	#[pallet::storage]
+	pub type StickyChannelFee<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::Amount, ValueQuery>;
+
+	/// Deposit channels that are renewed until their broker closes them, by address, with the
+	/// target chain block at which each is next due for renewal.
+	#[pallet::storage]
+	pub type StickyDepositChannels<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		TargetChainBlockNumber<T, I>,
+		OptionQuery,
+	>;
+
+	/// The addresses in [StickyDepositChannels], sorted by the block at which they are due for
+	/// renewal.
+	#[pallet::storage]
+	pub type StickyChannelRenewals<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, StickyChannelRenewalQueue<T, I>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -529,6 +649,15 @@ pub mod pallet {
 		FailedForeignChainCallExpired {
 			broadcast_id: BroadcastId,
 		},
+		/// The principal of a CCM that could not be delivered has been sent to its fallback
+		/// address.
+		CcmFallbackRefunded {
+			egress_id: EgressId,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			refund_address: TargetChainAccount<T, I>,
+			transfer: CcmRefundTransfer,
+		},
 		UtxoConsolidation {
 			broadcast_id: BroadcastId,
 		},
@@ -538,9 +667,77 @@ pub mod pallet {
 		ChannelOpeningFeePaid {
 			fee: T::Amount,
 		},
+		/// The unused share of the fee for a deposit channel has been refunded to its broker.
+		ChannelFeeRefunded {
+			channel_id: ChannelId,
+			fee: T::Amount,
+		},
 		ChannelOpeningFeeSet {
 			fee: T::Amount,
 		},
+		/// Funds paid directly into the vault have been scheduled for swapping.
+		VaultSwapRequested {
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			deposit_details: <T::TargetChain as Chain>::DepositDetails,
+			ingress_fee: TargetChainAmount<T, I>,
+			swap_id: SwapId,
+			broker_id: T::AccountId,
+		},
+		/// The swap parameters of a vault swap were invalid, or its broker is not registered, so
+		/// the funds are refunded.
+		VaultSwapRefunded {
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			deposit_details: <T::TargetChain as Chain>::DepositDetails,
+			refund_address: TargetChainAccount<T, I>,
+			egress_id: Option<EgressId>,
+		},
+		/// A broker has closed one of its deposit channels before it expired.
+		DepositChannelClosed {
+			channel_id: ChannelId,
+			deposit_address: TargetChainAccount<T, I>,
+			expires_at: TargetChainBlockNumber<T, I>,
+		},
+		/// A broker has extended the lifetime of one of its deposit channels.
+		DepositChannelExtended {
+			channel_id: ChannelId,
+			deposit_address: TargetChainAccount<T, I>,
+			expires_at: TargetChainBlockNumber<T, I>,
+		},
+		StickyChannelFeeSet {
+			fee: T::Amount,
+		},
+		StickyChannelFeePaid {
+			channel_id: ChannelId,
+			fee: T::Amount,
+		},
+		/// A deposit channel will be renewed until its broker closes it.
+		DepositChannelMadeSticky {
+			channel_id: ChannelId,
+			deposit_address: TargetChainAccount<T, I>,
+			expires_at: TargetChainBlockNumber<T, I>,
+		},
+		/// A sticky deposit channel has been renewed for another channel lifetime.
+		StickyDepositChannelRenewed {
+			channel_id: ChannelId,
+			deposit_address: TargetChainAccount<T, I>,
+			expires_at: TargetChainBlockNumber<T, I>,
+		},
+		/// A sticky deposit channel could not be renewed, because its broker could not pay for it
+		/// or its address is no longer derived from the current vault key, so the channel
+		/// expires and is recycled like any other.
+		StickyDepositChannelLapsed {
+			channel_id: ChannelId,
+			deposit_address: TargetChainAccount<T, I>,
+			expires_at: TargetChainBlockNumber<T, I>,
+		},
+		/// The destination of a sticky deposit channel has been changed by its broker.
+		StickyChannelDestinationUpdated {
+			channel_id: ChannelId,
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+		},
 	}
 
 	#[derive(CloneNoBound, PartialEqNoBound, EqNoBound)]
@@ -560,14 +757,74 @@ pub mod pallet {
 		BitcoinChannelIdTooLarge,
 		/// The amount is below the minimum egress amount.
 		BelowEgressDustLimit,
+		/// Assethub's Vault Account does not exist in storage.
+		MissingAssethubVault,
+		/// The deposit channel does not exist or was not opened by the caller.
+		NotChannelOwner,
+		/// The deposit channel has already expired.
+		DepositChannelExpired,
+		/// A deposit channel can't be extended to expire more than one channel lifetime ahead.
+		ChannelExtensionTooLong,
+		/// The maximum number of sticky channels has been reached.
+		TooManyStickyChannels,
+		/// Only swap channels can be made sticky.
+		UnsupportedChannelAction,
+		/// The deposit channel is already sticky.
+		ChannelAlreadySticky,
+		/// The deposit channel is not sticky.
+		ChannelNotSticky,
+		/// The destination address is invalid or does not match the destination asset.
+		InvalidDestinationAddress,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// Renew the sticky channels that are due, up to a fixed number per block.
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// The renewal queue has the same layout as the recycle queue, and is bounded by
+			// MAX_STICKY_CHANNELS.
+			let queue_weight = T::WeightInfo::channel_queues(
+				StickyChannelRenewals::<T, I>::decode_len().unwrap_or_default() as u32,
+			);
+			let current_height = T::ChainTracking::get_block_height();
+
+			let due_renewals = StickyChannelRenewals::<T, I>::mutate(|renewal_queue| {
+				let mut queue = sp_std::mem::take(renewal_queue).into_inner();
+				let due_renewals = Self::can_and_cannot_recycle(
+					&mut queue,
+					MAX_STICKY_CHANNEL_RENEWALS_PER_BLOCK as usize,
+					current_height,
+				);
+				*renewal_queue = BoundedVec::truncate_from(queue);
+				due_renewals
+			});
+
+			for address in due_renewals.iter() {
+				Self::renew_sticky_channel(address);
+			}
+
+			// Includes deriving the address of the channel from the current vault key.
+			queue_weight.saturating_add(
+				T::DbWeight::get().reads_writes(9, 5).saturating_mul(due_renewals.len() as u64),
+			)
+		}
+
 		/// Recycle addresses if we can
 		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
-			let read_write_weight =
-				frame_support::weights::constants::RocksDbWeight::get().reads_writes(1, 1);
+			// The queue is decoded and re-encoded in full, whatever is processed.
+			let queue_weight = T::WeightInfo::channel_queues(
+				DepositChannelRecycleBlocks::<T, I>::decode_len().unwrap_or_default() as u32,
+			);
+			if remaining_weight.any_lt(queue_weight) {
+				return T::DbWeight::get().reads(1)
+			}
+			let remaining_weight = remaining_weight.saturating_sub(queue_weight);
+
+			let current_height = T::ChainTracking::get_block_height();
+
+			// Includes the removal of the channel earnings of the broker and each affiliate.
+			let read_write_weight = frame_support::weights::constants::RocksDbWeight::get()
+				.reads_writes(2, 4 + MAX_AFFILIATES as u64);
 
 			let maximum_recycle_number = remaining_weight
 				.ref_time()
@@ -576,15 +833,25 @@ pub mod pallet {
 				.saturated_into::<usize>();
 
 			let can_recycle = DepositChannelRecycleBlocks::<T, I>::mutate(|recycle_queue| {
-				Self::can_and_cannot_recycle(
-					recycle_queue,
-					maximum_recycle_number,
-					T::ChainTracking::get_block_height(),
-				)
+				Self::can_and_cannot_recycle(recycle_queue, maximum_recycle_number, current_height)
 			});
 
 			for address in can_recycle.iter() {
 				if let Some(details) = DepositChannelLookup::<T, I>::take(address) {
+					if let Some(broker) = DepositChannelBrokers::<T, I>::take(address) {
+						BrokerDepositChannels::<T, I>::remove(
+							broker,
+							details.deposit_channel.channel_id,
+						);
+					}
+					if let ChannelAction::Swap { broker_id, affiliates, .. } = details.action {
+						T::SwapDepositHandler::on_channel_recycled(
+							details.deposit_channel.asset.into(),
+							details.deposit_channel.channel_id,
+							broker_id,
+							affiliates,
+						);
+					}
 					if let Some(state) = details.deposit_channel.state.maybe_recycle() {
 						DepositChannelPool::<T, I>::insert(
 							details.deposit_channel.channel_id,
@@ -594,7 +861,7 @@ pub mod pallet {
 				}
 			}
 
-			read_write_weight.saturating_mul(can_recycle.len() as u64)
+			queue_weight.saturating_add(read_write_weight.saturating_mul(can_recycle.len() as u64))
 		}
 
 		/// Take all scheduled Egress and send them out
@@ -640,6 +907,21 @@ pub mod pallet {
 						Self::deposit_event(Event::<T, I>::FailedForeignChainCallExpired {
 							broadcast_id: call.broadcast_id,
 						});
+						// The call can no longer be executed, so the principal is safe to refund.
+						if let Some((egress_id, details)) = CcmFallbackEgressIds::<T, I>::take(
+							call.broadcast_id,
+						)
+						.and_then(|egress_id| {
+							CcmFallbacks::<T, I>::take(egress_id)
+								.map(|details| (egress_id, details))
+						}) {
+							Self::refund_ccm_principal(
+								egress_id,
+								details.asset,
+								details.amount,
+								details.refund_address,
+							);
+						}
 					},
 					// Previous epoch, signature is invalid. Re-sign and store.
 					n if n == 1 => {
@@ -826,7 +1108,8 @@ pub mod pallet {
 			let current_epoch = T::EpochInfo::epoch_index();
 
 			// Stores the broadcast ID, so the user can use it to query for
-			// information such as Threshold Signature etc.
+			// information such as Threshold Signature etc. If the CCM has a fallback address, its
+			// principal is refunded once the failed call expires.
 			FailedForeignChainCalls::<T, I>::append(
 				current_epoch,
 				FailedForeignChainCall { broadcast_id, original_epoch: current_epoch },
@@ -861,11 +1144,294 @@ pub mod pallet {
 							minimum_deposit,
 						});
 					},
+					PalletConfigUpdate::<T, I>::StickyChannelFee { fee } => {
+						StickyChannelFee::<T, I>::set(fee);
+						Self::deposit_event(Event::<T, I>::StickyChannelFeeSet { fee });
+					},
 				}
 			}
 
 			Ok(())
 		}
+
+		/// Called when funds have been paid directly into the vault, together with the parameters
+		/// of the swap they should be used for.
+		///
+		/// Requires `EnsureWitnessed` origin.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::VaultSwapRequested)
+		/// - [On invalid swap parameters](Event::VaultSwapRefunded)
+		#[pallet::call_index(7)]
+		// Does the same storage accesses as a channel deposit, less the channel lookup.
+		#[pallet::weight(T::WeightInfo::process_single_deposit())]
+		pub fn vault_swap_request(
+			origin: OriginFor<T>,
+			vault_swap: Box<VaultSwapWitness<T::TargetChain, T::AccountId>>,
+		) -> DispatchResult {
+			T::EnsureWitnessed::ensure_origin(origin)?;
+
+			Self::process_vault_swap(*vault_swap)
+		}
+
+		/// Callback for when a CCM with a fallback address has been delivered, so its principal
+		/// no longer needs to be refunded.
+		/// Requires Root origin.
+		#[pallet::call_index(8)]
+		// Does the same storage accesses as a failed broadcast.
+		#[pallet::weight(T::WeightInfo::ccm_broadcast_failed())]
+		pub fn ccm_broadcast_succeeded(
+			origin: OriginFor<T>,
+			egress_id: EgressId,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			if let Some(details) = CcmFallbacks::<T, I>::take(egress_id) {
+				CcmFallbackEgressIds::<T, I>::remove(details.broadcast_id);
+			}
+			Ok(())
+		}
+
+		/// Closes one of the caller's deposit channels before it expires. Deposits made after the
+		/// current block of the target chain are no longer witnessed. Closing a sticky channel also
+		/// stops its renewal.
+		///
+		/// The unused share of the fee that paid for the channel lifetime, the channel opening fee
+		/// or the sticky channel fee, is refunded in proportion to the remaining lifetime. The
+		/// recycle period after the new expiry is not refunded, since the address stays reserved
+		/// until then.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::DepositChannelClosed)
+		/// - [Fee refunded](Event::ChannelFeeRefunded)
+		///
+		/// ## Errors
+		///
+		/// - [NotChannelOwner](Error::NotChannelOwner)
+		/// - [DepositChannelExpired](Error::DepositChannelExpired)
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::close_deposit_channel())]
+		pub fn close_deposit_channel(
+			origin: OriginFor<T>,
+			channel_id: ChannelId,
+		) -> DispatchResult {
+			let broker = ensure_signed(origin)?;
+			let deposit_address = Self::open_broker_channel(&broker, channel_id)?;
+
+			let lifetime_fee = if StickyDepositChannels::<T, I>::take(&deposit_address).is_some() {
+				StickyChannelRenewals::<T, I>::mutate(|renewal_queue| {
+					renewal_queue.retain(|(_, address)| *address != deposit_address);
+				});
+				StickyChannelFee::<T, I>::get()
+			} else {
+				ChannelOpeningFee::<T, I>::get()
+			};
+
+			let expires_at = T::ChainTracking::get_block_height();
+			let remaining_lifetime = DepositChannelLookup::<T, I>::get(&deposit_address)
+				.ok_or(Error::<T, I>::InvalidDepositAddress)?
+				.expires_at
+				.saturating_sub(expires_at);
+			let refund = Permill::from_rational::<u64>(
+				remaining_lifetime.into(),
+				DepositChannelLifetime::<T, I>::get().into(),
+			) * lifetime_fee;
+			if !refund.is_zero() {
+				T::FeePayment::refund_fee(&broker, refund);
+				Self::deposit_event(Event::<T, I>::ChannelFeeRefunded { channel_id, fee: refund });
+			}
+
+			Self::update_channel_expiry(&deposit_address, expires_at);
+
+			Self::deposit_event(Event::<T, I>::DepositChannelClosed {
+				channel_id,
+				deposit_address,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Extends the lifetime of one of the caller's deposit channels by the given number of
+		/// target chain blocks. A channel can't be extended to expire more than one channel
+		/// lifetime after the current block. The extension is charged a share of the channel
+		/// opening fee, in proportion to the channel lifetime.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::DepositChannelExtended)
+		///
+		/// ## Errors
+		///
+		/// - [NotChannelOwner](Error::NotChannelOwner)
+		/// - [DepositChannelExpired](Error::DepositChannelExpired)
+		/// - [ChannelAlreadySticky](Error::ChannelAlreadySticky)
+		/// - [ChannelExtensionTooLong](Error::ChannelExtensionTooLong)
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::extend_deposit_channel())]
+		pub fn extend_deposit_channel(
+			origin: OriginFor<T>,
+			channel_id: ChannelId,
+			extension: TargetChainBlockNumber<T, I>,
+		) -> DispatchResult {
+			let broker = ensure_signed(origin)?;
+			let deposit_address = Self::open_broker_channel(&broker, channel_id)?;
+			ensure!(
+				!StickyDepositChannels::<T, I>::contains_key(&deposit_address),
+				Error::<T, I>::ChannelAlreadySticky
+			);
+
+			let lifetime = DepositChannelLifetime::<T, I>::get();
+			let expires_at = DepositChannelLookup::<T, I>::get(&deposit_address)
+				.ok_or(Error::<T, I>::InvalidDepositAddress)?
+				.expires_at
+				.saturating_add(extension);
+			ensure!(
+				expires_at <= T::ChainTracking::get_block_height().saturating_add(lifetime),
+				Error::<T, I>::ChannelExtensionTooLong
+			);
+
+			let fee = Permill::from_rational::<u64>(extension.into(), lifetime.into()) *
+				ChannelOpeningFee::<T, I>::get();
+			T::FeePayment::try_burn_fee(&broker, fee)?;
+			Self::deposit_event(Event::<T, I>::ChannelOpeningFeePaid { fee });
+
+			Self::update_channel_expiry(&deposit_address, expires_at);
+
+			Self::deposit_event(Event::<T, I>::DepositChannelExtended {
+				channel_id,
+				deposit_address,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Makes one of the caller's swap channels sticky: instead of expiring, the channel is
+		/// renewed for another channel lifetime, half a lifetime before it would expire, until
+		/// the broker closes it. The sticky channel fee is charged now and on every renewal. If a
+		/// renewal can't be paid for, the channel expires and is recycled as usual.
+		///
+		/// A channel is only renewed while its address is still the one derived from the current
+		/// vault key. Bitcoin deposits can't be spent once the key of their address is older than
+		/// the previous vault key, so after a rotation a Bitcoin channel lapses instead, and its
+		/// original address keeps being witnessed until its current expiry.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::DepositChannelMadeSticky)
+		///
+		/// ## Errors
+		///
+		/// - [NotChannelOwner](Error::NotChannelOwner)
+		/// - [DepositChannelExpired](Error::DepositChannelExpired)
+		/// - [ChannelAlreadySticky](Error::ChannelAlreadySticky)
+		/// - [UnsupportedChannelAction](Error::UnsupportedChannelAction)
+		/// - [TooManyStickyChannels](Error::TooManyStickyChannels)
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::make_channel_sticky())]
+		pub fn make_channel_sticky(origin: OriginFor<T>, channel_id: ChannelId) -> DispatchResult {
+			let broker = ensure_signed(origin)?;
+			let deposit_address = Self::open_broker_channel(&broker, channel_id)?;
+			ensure!(
+				!StickyDepositChannels::<T, I>::contains_key(&deposit_address),
+				Error::<T, I>::ChannelAlreadySticky
+			);
+			ensure!(
+				matches!(
+					DepositChannelLookup::<T, I>::get(&deposit_address)
+						.ok_or(Error::<T, I>::InvalidDepositAddress)?
+						.action,
+					ChannelAction::Swap { .. }
+				),
+				Error::<T, I>::UnsupportedChannelAction
+			);
+			ensure!(
+				StickyChannelRenewals::<T, I>::decode_len().unwrap_or_default() <
+					MAX_STICKY_CHANNELS as usize,
+				Error::<T, I>::TooManyStickyChannels
+			);
+
+			let fee = StickyChannelFee::<T, I>::get();
+			T::FeePayment::try_burn_fee(&broker, fee)?;
+			Self::deposit_event(Event::<T, I>::StickyChannelFeePaid { channel_id, fee });
+
+			DepositChannelRecycleBlocks::<T, I>::mutate(|recycle_queue| {
+				recycle_queue.retain(|(_, address)| *address != deposit_address);
+			});
+			let expires_at = T::ChainTracking::get_block_height()
+				.saturating_add(DepositChannelLifetime::<T, I>::get());
+			Self::schedule_sticky_channel_renewal(&deposit_address, expires_at);
+
+			Self::deposit_event(Event::<T, I>::DepositChannelMadeSticky {
+				channel_id,
+				deposit_address,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Changes the destination of one of the caller's sticky channels. Deposits witnessed
+		/// after this call are swapped to the new destination.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::StickyChannelDestinationUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [NotChannelOwner](Error::NotChannelOwner)
+		/// - [DepositChannelExpired](Error::DepositChannelExpired)
+		/// - [ChannelNotSticky](Error::ChannelNotSticky)
+		/// - [InvalidDestinationAddress](Error::InvalidDestinationAddress)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::update_sticky_channel_destination())]
+		pub fn update_sticky_channel_destination(
+			origin: OriginFor<T>,
+			channel_id: ChannelId,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+		) -> DispatchResult {
+			let broker = ensure_signed(origin)?;
+			let deposit_address = Self::open_broker_channel(&broker, channel_id)?;
+			ensure!(
+				StickyDepositChannels::<T, I>::contains_key(&deposit_address),
+				Error::<T, I>::ChannelNotSticky
+			);
+
+			let destination_address =
+				T::AddressConverter::try_from_encoded_address(destination_address)
+					.ok()
+					.filter(|address| address.chain() == ForeignChain::from(destination_asset))
+					.ok_or(Error::<T, I>::InvalidDestinationAddress)?;
+
+			DepositChannelLookup::<T, I>::try_mutate(&deposit_address, |details| {
+				let details = details.as_mut().ok_or(Error::<T, I>::InvalidDepositAddress)?;
+				ensure!(
+					destination_asset != Asset::from(details.deposit_channel.asset),
+					Error::<T, I>::InvalidDestinationAddress
+				);
+				match &mut details.action {
+					ChannelAction::Swap {
+						destination_asset: current_asset,
+						destination_address: current_address,
+						..
+					} => {
+						*current_asset = destination_asset;
+						*current_address = destination_address.clone();
+						Ok::<_, DispatchError>(())
+					},
+					_ => Err(Error::<T, I>::UnsupportedChannelAction.into()),
+				}
+			})?;
+
+			Self::deposit_event(Event::<T, I>::StickyChannelDestinationUpdated {
+				channel_id,
+				destination_asset,
+				destination_address,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -885,11 +1451,127 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			.collect()
 	}
 
+	/// Returns the address of a deposit channel opened by the broker, if it has not yet expired.
+	fn open_broker_channel(
+		broker: &T::AccountId,
+		channel_id: ChannelId,
+	) -> Result<TargetChainAccount<T, I>, DispatchError> {
+		let deposit_address = BrokerDepositChannels::<T, I>::get(broker, channel_id)
+			.ok_or(Error::<T, I>::NotChannelOwner)?
+			.deposit_address;
+		let details = DepositChannelLookup::<T, I>::get(&deposit_address)
+			.ok_or(Error::<T, I>::InvalidDepositAddress)?;
+		ensure!(
+			details.expires_at > T::ChainTracking::get_block_height(),
+			Error::<T, I>::DepositChannelExpired
+		);
+		Ok(deposit_address)
+	}
+
+	/// Sets the expiry of a deposit channel and moves its recycling to one channel lifetime after
+	/// the new expiry.
+	fn update_channel_expiry(
+		deposit_address: &TargetChainAccount<T, I>,
+		expires_at: TargetChainBlockNumber<T, I>,
+	) {
+		DepositChannelLookup::<T, I>::mutate(deposit_address, |details| {
+			if let Some(details) = details.as_mut() {
+				details.expires_at = expires_at;
+			}
+		});
+
+		let recycle_height = expires_at.saturating_add(DepositChannelLifetime::<T, I>::get());
+		DepositChannelRecycleBlocks::<T, I>::mutate(|recycle_queue| {
+			recycle_queue.retain(|(_, address)| address != deposit_address);
+			// The queue is sorted by recycle height.
+			let position = recycle_queue.partition_point(|(height, _)| *height <= recycle_height);
+			recycle_queue.insert(position, (recycle_height, deposit_address.clone()));
+		});
+	}
+
+	/// Sets the expiry of a sticky deposit channel and schedules its renewal for half a channel
+	/// lifetime before the new expiry.
+	fn schedule_sticky_channel_renewal(
+		deposit_address: &TargetChainAccount<T, I>,
+		expires_at: TargetChainBlockNumber<T, I>,
+	) {
+		DepositChannelLookup::<T, I>::mutate(deposit_address, |details| {
+			if let Some(details) = details.as_mut() {
+				details.expires_at = expires_at;
+			}
+		});
+
+		let renewal_height =
+			expires_at.saturating_sub(DepositChannelLifetime::<T, I>::get() / 2u32.into());
+		StickyDepositChannels::<T, I>::insert(deposit_address, renewal_height);
+		StickyChannelRenewals::<T, I>::mutate(|renewal_queue| {
+			// The queue is sorted by renewal height. There is room in the queue, since its length
+			// is checked before a channel is made sticky, and a channel is taken out of the queue
+			// before it is renewed.
+			let position = renewal_queue.partition_point(|(height, _)| *height <= renewal_height);
+			if renewal_queue
+				.try_insert(position, (renewal_height, deposit_address.clone()))
+				.is_err()
+			{
+				log_or_panic!("The sticky channel renewal queue is full.");
+			}
+		});
+	}
+
+	/// Charges the broker of a sticky deposit channel for another channel lifetime. If the fee
+	/// can't be paid, or the address of the channel is no longer derived from the current vault
+	/// key, the channel stops being sticky and is recycled after its current expiry.
+	fn renew_sticky_channel(deposit_address: &TargetChainAccount<T, I>) {
+		if !StickyDepositChannels::<T, I>::contains_key(deposit_address) {
+			return
+		}
+		let Some(details) = DepositChannelLookup::<T, I>::get(deposit_address) else {
+			StickyDepositChannels::<T, I>::remove(deposit_address);
+			return
+		};
+		let channel_id = details.deposit_channel.channel_id;
+
+		// Bitcoin deposit addresses are derived from the vault key, so they change on rotation.
+		let derived_from_current_key = matches!(
+			T::AddressDerivation::generate_address(details.deposit_channel.asset, channel_id),
+			Ok(address) if address == *deposit_address
+		);
+
+		let fee = StickyChannelFee::<T, I>::get();
+		if derived_from_current_key &&
+			DepositChannelBrokers::<T, I>::get(deposit_address)
+				.ok_or_else(|| Error::<T, I>::NotChannelOwner.into())
+				.and_then(|broker| T::FeePayment::try_burn_fee(&broker, fee))
+				.is_ok()
+		{
+			Self::deposit_event(Event::<T, I>::StickyChannelFeePaid { channel_id, fee });
+			let expires_at =
+				details.expires_at.saturating_add(DepositChannelLifetime::<T, I>::get());
+			Self::schedule_sticky_channel_renewal(deposit_address, expires_at);
+			Self::deposit_event(Event::<T, I>::StickyDepositChannelRenewed {
+				channel_id,
+				deposit_address: deposit_address.clone(),
+				expires_at,
+			});
+		} else {
+			StickyDepositChannels::<T, I>::remove(deposit_address);
+			Self::update_channel_expiry(deposit_address, details.expires_at);
+			Self::deposit_event(Event::<T, I>::StickyDepositChannelLapsed {
+				channel_id,
+				deposit_address: deposit_address.clone(),
+				expires_at: details.expires_at,
+			});
+		}
+	}
+
 	/// Take all scheduled egress requests and send them out in an `AllBatch` call.
 	///
 	/// Note: Egress transactions with Blacklisted assets are not sent, and kept in storage.
 	#[transactional]
 	fn do_egress_scheduled_fetch_transfer() -> Result<(), AllBatchError> {
+		let fetch_sticky_channels = (frame_system::Pallet::<T>::block_number() %
+			STICKY_CHANNEL_FETCH_INTERVAL.into())
+		.is_zero();
 		let batch_to_send: Vec<_> =
 			ScheduledEgressFetchOrTransfer::<T, I>::mutate(|requests: &mut Vec<_>| {
 				// Filter out disabled assets and requests that are not ready to be egressed.
@@ -901,29 +1583,38 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 									deposit_address,
 									deposit_fetch_id,
 									..
-								} => DepositChannelLookup::<T, I>::mutate(
-									deposit_address,
-									|details| {
-										details
-											.as_mut()
-											.map(|details| {
-												let can_fetch =
-													details.deposit_channel.state.can_fetch();
-
-												if can_fetch {
-													deposit_fetch_id.replace(
-														details.deposit_channel.fetch_id(),
-													);
-													details
-														.deposit_channel
-														.state
-														.on_fetch_scheduled();
-												}
-												can_fetch
-											})
-											.unwrap_or(false)
-									},
-								),
+								} => {
+									// Fetches from sticky channels wait for the next interval.
+									if !fetch_sticky_channels &&
+										StickyDepositChannels::<T, I>::contains_key(
+											&*deposit_address,
+										) {
+										return false
+									}
+									DepositChannelLookup::<T, I>::mutate(
+										deposit_address,
+										|details| {
+											details
+												.as_mut()
+												.map(|details| {
+													let can_fetch =
+														details.deposit_channel.state.can_fetch();
+
+													if can_fetch {
+														deposit_fetch_id.replace(
+															details.deposit_channel.fetch_id(),
+														);
+														details
+															.deposit_channel
+															.state
+															.on_fetch_scheduled();
+													}
+													can_fetch
+												})
+												.unwrap_or(false)
+										},
+									)
+								},
 								FetchOrTransfer::Transfer { .. } => true,
 							}
 					})
@@ -1005,6 +1696,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					.collect()
 			});
 		for ccm in ccms_to_send {
+			let fallback = ccm.fallback.and_then(|CcmFailureFallback { refund_address }| {
+				match TargetChainAccount::<T, I>::try_from(refund_address) {
+					Ok(refund_address) => Some(refund_address),
+					Err(_) => {
+						log::warn!(
+							"CCM fallback address is not on the target chain. Egress Id: {:?}",
+							ccm.egress_id
+						);
+						None
+					},
+				}
+			});
 			match <T::ChainApiCall as ExecutexSwapAndCall<T::TargetChain>>::new_unsigned(
 				TransferAssetParams {
 					asset: ccm.asset,
@@ -1019,22 +1722,90 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				Ok(api_call) => {
 					let broadcast_id = T::Broadcaster::threshold_sign_and_broadcast_with_callback(
 						api_call,
-						None,
+						fallback.is_some().then(|| {
+							Call::ccm_broadcast_succeeded { egress_id: ccm.egress_id }.into()
+						}),
 						|broadcast_id| Some(Call::ccm_broadcast_failed { broadcast_id }.into()),
 					);
+					if let Some(refund_address) = fallback {
+						T::Broadcaster::witness_after_abort(broadcast_id);
+						CcmFallbacks::<T, I>::insert(
+							ccm.egress_id,
+							CcmFallbackDetails {
+								broadcast_id,
+								asset: ccm.asset,
+								amount: ccm.amount,
+								refund_address,
+							},
+						);
+						CcmFallbackEgressIds::<T, I>::insert(broadcast_id, ccm.egress_id);
+					}
 					Self::deposit_event(Event::<T, I>::CcmBroadcastRequested {
 						broadcast_id,
 						egress_id: ccm.egress_id,
 					});
 				},
-				Err(error) => Self::deposit_event(Event::<T, I>::CcmEgressInvalid {
-					egress_id: ccm.egress_id,
-					error,
-				}),
+				Err(error) => {
+					Self::deposit_event(Event::<T, I>::CcmEgressInvalid {
+						egress_id: ccm.egress_id,
+						error,
+					});
+					// Nothing has been signed, so the principal can be refunded straight away.
+					if let Some((refund_address, _)) = fallback {
+						Self::refund_ccm_principal(
+							ccm.egress_id,
+							ccm.asset,
+							ccm.amount,
+							refund_address,
+						);
+					}
+				},
 			};
 		}
 	}
 
+	/// Sends the principal of an undeliverable CCM to its fallback address. Uses `TransferFallback`
+	/// where the chain supports it, or a regular transfer otherwise.
+	///
+	/// The principal has already been accounted for as transferred out of the vault when the CCM
+	/// was scheduled, so the deposit balances are left untouched.
+	fn refund_ccm_principal(
+		egress_id: EgressId,
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+		refund_address: TargetChainAccount<T, I>,
+	) {
+		let transfer = match <T::ChainApiCall as TransferFallback<T::TargetChain>>::new_unsigned(
+			TransferAssetParams { asset, amount, to: refund_address.clone() },
+		) {
+			Ok(api_call) => CcmRefundTransfer::TransferFallback {
+				broadcast_id: T::Broadcaster::threshold_sign_and_broadcast(api_call),
+			},
+			Err(_) => {
+				let refund_egress_id = EgressIdCounter::<T, I>::mutate(|id_counter| {
+					*id_counter = id_counter.saturating_add(1);
+					(<T as Config<I>>::TargetChain::get(), *id_counter)
+				});
+				ScheduledEgressFetchOrTransfer::<T, I>::append(
+					FetchOrTransfer::<T::TargetChain>::Transfer {
+						egress_id: refund_egress_id,
+						asset,
+						destination_address: refund_address.clone(),
+						amount,
+					},
+				);
+				CcmRefundTransfer::Batched { egress_id: refund_egress_id }
+			},
+		};
+		Self::deposit_event(Event::<T, I>::CcmFallbackRefunded {
+			egress_id,
+			asset,
+			amount,
+			refund_address,
+			transfer,
+		});
+	}
+
 	/// Completes a single deposit request.
 	#[transactional]
 	fn process_single_deposit(
@@ -1076,12 +1847,44 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Ok(())
 		}
 
-		ScheduledEgressFetchOrTransfer::<T, I>::append(FetchOrTransfer::<T::TargetChain>::Fetch {
-			asset,
-			deposit_address: deposit_address.clone(),
-			deposit_fetch_id: None,
-			amount: deposit_amount,
-		});
+		if let Some(broker) = DepositChannelBrokers::<T, I>::get(&deposit_address) {
+			BrokerDepositChannels::<T, I>::mutate(broker, channel_id, |channel| {
+				if let Some(channel) = channel.as_mut() {
+					channel.deposit_count.saturating_accrue(1);
+					channel.total_deposited.saturating_accrue(deposit_amount);
+				}
+			});
+		}
+
+		// Sticky channels can receive any number of deposits, so a deposit is added to a fetch
+		// that is already scheduled for the channel, if there is one, rather than being fetched
+		// separately. Such fetches are only sent every STICKY_CHANNEL_FETCH_INTERVAL blocks.
+		let merged_into_scheduled_fetch =
+			StickyDepositChannels::<T, I>::contains_key(&deposit_address) &&
+				ScheduledEgressFetchOrTransfer::<T, I>::mutate(|requests| {
+					requests.iter_mut().any(|request| match request {
+						FetchOrTransfer::Fetch {
+							asset: fetch_asset,
+							deposit_address: fetch_address,
+							amount,
+							..
+						} if *fetch_asset == asset && *fetch_address == deposit_address => {
+							amount.saturating_accrue(deposit_amount);
+							true
+						},
+						_ => false,
+					})
+				});
+		if !merged_into_scheduled_fetch {
+			ScheduledEgressFetchOrTransfer::<T, I>::append(
+				FetchOrTransfer::<T::TargetChain>::Fetch {
+					asset,
+					deposit_address: deposit_address.clone(),
+					deposit_fetch_id: None,
+					amount: deposit_amount,
+				},
+			);
+		}
 		Self::deposit_event(Event::<T, I>::DepositFetchesScheduled { channel_id, asset });
 
 		let AmountAndFeesWithheld { amount_after_fees, fees_withheld } =
@@ -1121,7 +1924,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					destination_asset,
 					broker_id,
 					broker_commission_bps,
-					..
+					affiliates,
 				} => DepositAction::Swap {
 					swap_id: T::SwapDepositHandler::schedule_swap_from_channel(
 						deposit_address.clone().into(),
@@ -1132,6 +1935,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						destination_address,
 						broker_id,
 						broker_commission_bps,
+						affiliates,
 						channel_id,
 					),
 				},
@@ -1180,6 +1984,114 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(())
 	}
 
+	/// Registers funds that were paid directly into the vault and schedules the requested swap.
+	/// If the swap parameters are invalid, or the broker is not registered, the funds are refunded
+	/// instead.
+	#[transactional]
+	fn process_vault_swap(
+		VaultSwapWitness {
+			vault_address,
+			asset,
+			amount,
+			deposit_details,
+			destination_asset,
+			destination_address,
+			min_output,
+			refund_address,
+			broker_id,
+			broker_commission_bps,
+			tx_hash,
+		}: VaultSwapWitness<T::TargetChain, T::AccountId>,
+	) -> DispatchResult {
+		// The funds are already in the vault, so there is nothing to fetch.
+		T::DepositHandler::on_vault_deposit_made(
+			vault_address.clone(),
+			deposit_details.clone(),
+			amount,
+		)
+		.map_err(|_| Error::<T, I>::InvalidDepositAddress)?;
+		DepositBalances::<T, I>::mutate(asset, |deposits| {
+			deposits.register_deposit(amount);
+			deposits.mark_as_fetched(amount);
+		});
+
+		if amount < MinimumDeposit::<T, I>::get(asset) {
+			Self::deposit_event(Event::<T, I>::DepositIgnored {
+				deposit_address: vault_address,
+				asset,
+				amount,
+				deposit_details,
+				reason: DepositIgnoredReason::BelowMinimumDeposit,
+			});
+			return Ok(())
+		}
+
+		let destination_address =
+			T::AddressConverter::try_from_encoded_address(destination_address)
+				.ok()
+				.filter(|address| {
+					address.chain() == ForeignChain::from(destination_asset) &&
+						destination_asset != Asset::from(asset)
+				})
+				.filter(|_| {
+					T::AccountRoleRegistry::has_account_role(&broker_id, AccountRole::Broker)
+				});
+
+		let Some(destination_address) = destination_address else {
+			let egress_id = <Self as EgressApi<T::TargetChain>>::schedule_egress(
+				asset,
+				amount,
+				refund_address.clone(),
+				None,
+			)
+			.map(|ScheduledEgressDetails { egress_id, .. }| egress_id)
+			.ok();
+			Self::deposit_event(Event::<T, I>::VaultSwapRefunded {
+				asset,
+				amount,
+				deposit_details,
+				refund_address,
+				egress_id,
+			});
+			return Ok(())
+		};
+
+		let AmountAndFeesWithheld { amount_after_fees, fees_withheld } =
+			Self::withhold_transaction_fee(IngressOrEgress::Ingress, asset, amount);
+
+		if amount_after_fees.is_zero() {
+			Self::deposit_event(Event::<T, I>::DepositIgnored {
+				deposit_address: vault_address,
+				asset,
+				amount,
+				deposit_details,
+				reason: DepositIgnoredReason::NotEnoughToPayFees,
+			});
+		} else {
+			let swap_id = T::SwapDepositHandler::schedule_vault_swap(
+				asset.into(),
+				destination_asset,
+				amount_after_fees.into(),
+				destination_address,
+				min_output,
+				refund_address.into(),
+				broker_id.clone(),
+				broker_commission_bps,
+				tx_hash,
+			);
+			Self::deposit_event(Event::<T, I>::VaultSwapRequested {
+				asset,
+				amount,
+				deposit_details,
+				ingress_fee: fees_withheld,
+				swap_id,
+				broker_id,
+			});
+		}
+
+		Ok(())
+	}
+
 	fn expiry_and_recycle_block_height(
 	) -> (TargetChainBlockNumber<T, I>, TargetChainBlockNumber<T, I>, TargetChainBlockNumber<T, I>)
 	{
@@ -1228,6 +2140,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							Error::<T, I>::MissingBitcoinVault,
 						AddressDerivationError::BitcoinChannelIdTooLarge =>
 							Error::<T, I>::BitcoinChannelIdTooLarge,
+						AddressDerivationError::MissingAssethubVault =>
+							Error::<T, I>::MissingAssethubVault,
 					})?,
 				next_channel_id,
 			)
@@ -1337,6 +2251,7 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 						source_chain,
 						source_address,
 						gas_budget,
+						fallback: channel_metadata.fallback,
 					});
 
 					// The ccm gas budget is already in terms of the swap asset.
@@ -1416,6 +2331,7 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 		destination_asset: Asset,
 		destination_address: ForeignChainAddress,
 		broker_commission_bps: BasisPoints,
+		affiliates: Affiliates<T::AccountId>,
 		broker_id: T::AccountId,
 		channel_metadata: Option<CcmChannelMetadata>,
 		boost_fee: BasisPoints,
@@ -1436,12 +2352,24 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 					destination_asset,
 					destination_address,
 					broker_commission_bps,
+					affiliates,
 					broker_id: broker_id.clone(),
 				},
 			},
 			boost_fee,
 		)?;
 
+		BrokerDepositChannels::<T, I>::insert(
+			&broker_id,
+			channel_id,
+			BrokerDepositChannel {
+				deposit_address: deposit_address.clone(),
+				deposit_count: 0,
+				total_deposited: Zero::zero(),
+			},
+		);
+		DepositChannelBrokers::<T, I>::insert(&deposit_address, broker_id);
+
 		Ok((channel_id, deposit_address.into(), expiry_height))
 	}
 }
@@ -1,4 +1,7 @@
+pub mod affiliates;
+pub mod broker_deposit_channels;
 pub mod btc_deposit_channels;
+pub mod ccm_fallback;
 pub mod deposit_channels_with_boost_fee;
 pub mod remove_old_storage;
 pub mod set_dust_limit;
@@ -10,4 +13,7 @@ pub type PalletMigration<T, I> = (
 	VersionedMigration<crate::Pallet<T, I>, set_dust_limit::Migration<T, I>, 3, 4>,
 	VersionedMigration<crate::Pallet<T, I>, deposit_channels_with_boost_fee::Migration<T, I>, 4, 5>,
 	VersionedMigration<crate::Pallet<T, I>, remove_old_storage::Migration<T, I>, 5, 6>,
+	VersionedMigration<crate::Pallet<T, I>, ccm_fallback::Migration<T, I>, 6, 7>,
+	VersionedMigration<crate::Pallet<T, I>, affiliates::Migration<T, I>, 7, 8>,
+	VersionedMigration<crate::Pallet<T, I>, broker_deposit_channels::Migration<T, I>, 8, 9>,
 );
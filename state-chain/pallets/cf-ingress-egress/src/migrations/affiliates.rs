@@ -0,0 +1,116 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+use super::ccm_fallback::new as old;
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+impl<AccountId> From<old::ChannelAction<AccountId>> for ChannelAction<AccountId> {
+	fn from(action: old::ChannelAction<AccountId>) -> Self {
+		match action {
+			old::ChannelAction::Swap {
+				destination_asset,
+				destination_address,
+				broker_id,
+				broker_commission_bps,
+			} => Self::Swap {
+				destination_asset,
+				destination_address,
+				broker_id,
+				broker_commission_bps,
+				affiliates: Default::default(),
+			},
+			old::ChannelAction::LiquidityProvision { lp_account } =>
+				Self::LiquidityProvision { lp_account },
+			old::ChannelAction::CcmTransfer {
+				destination_asset,
+				destination_address,
+				channel_metadata,
+			} => Self::CcmTransfer { destination_asset, destination_address, channel_metadata },
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut channels = 0u64;
+		DepositChannelLookup::<T, I>::translate(
+			|_address, old_channel: old::DepositChannelDetails<T, I>| {
+				channels += 1;
+				Some(DepositChannelDetails::<T, I> {
+					deposit_channel: old_channel.deposit_channel,
+					opened_at: old_channel.opened_at,
+					expires_at: old_channel.expires_at,
+					action: old_channel.action.into(),
+					boost_fee: old_channel.boost_fee,
+				})
+			},
+		);
+
+		T::DbWeight::get().reads_writes(channels, channels)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((old::DepositChannelLookup::<T, I>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let channels =
+			<u32>::decode(&mut &state[..]).map_err(|_| "Failed to decode pre-upgrade state.")?;
+		ensure!(
+			DepositChannelLookup::<T, I>::iter_values().count() as u32 == channels,
+			"DepositChannelLookup migration failed."
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::*;
+	use cf_chains::DepositChannel;
+	use sp_core::H160;
+
+	#[test]
+	fn swap_channels_get_no_affiliates() {
+		new_test_ext().execute_with(|| {
+			let address = H160::repeat_byte(1);
+			old::DepositChannelLookup::<Test, ()>::insert(
+				address,
+				old::DepositChannelDetails::<Test, ()> {
+					deposit_channel: DepositChannel {
+						channel_id: 1,
+						address,
+						asset: assets::eth::Asset::Eth,
+						state: Default::default(),
+					},
+					opened_at: 1,
+					expires_at: 2,
+					action: old::ChannelAction::Swap {
+						destination_asset: Asset::Flip,
+						destination_address: ForeignChainAddress::Eth(address),
+						broker_id: BROKER,
+						broker_commission_bps: 10,
+					},
+					boost_fee: 0,
+				},
+			);
+
+			Migration::<Test, ()>::on_runtime_upgrade();
+
+			assert_eq!(
+				DepositChannelLookup::<Test, ()>::get(address).unwrap().action,
+				ChannelAction::Swap {
+					destination_asset: Asset::Flip,
+					destination_address: ForeignChainAddress::Eth(address),
+					broker_id: BROKER,
+					broker_commission_bps: 10,
+					affiliates: Default::default(),
+				}
+			);
+		});
+	}
+}
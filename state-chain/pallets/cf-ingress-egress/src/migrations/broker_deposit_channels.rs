@@ -0,0 +1,113 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+// Indexes the swap channels that were opened before deposit channels were recorded by broker, so
+// that their brokers can manage them. The deposits made into these channels so far are not
+// counted. Ccm channels don't record the broker that opened them, so they can't be indexed.
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut channels = 0u64;
+		let mut indexed = 0u64;
+		for (deposit_address, details) in DepositChannelLookup::<T, I>::iter() {
+			channels += 1;
+			let ChannelAction::Swap { broker_id, .. } = details.action else { continue };
+			if DepositChannelBrokers::<T, I>::contains_key(&deposit_address) {
+				continue
+			}
+			indexed += 1;
+			BrokerDepositChannels::<T, I>::insert(
+				&broker_id,
+				details.deposit_channel.channel_id,
+				BrokerDepositChannel {
+					deposit_address: deposit_address.clone(),
+					deposit_count: 0,
+					total_deposited: Zero::zero(),
+				},
+			);
+			DepositChannelBrokers::<T, I>::insert(deposit_address, broker_id);
+		}
+
+		T::DbWeight::get().reads_writes(channels.saturating_mul(2), indexed.saturating_mul(2))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		let swap_channels = DepositChannelLookup::<T, I>::iter_values()
+			.filter(|details| matches!(details.action, ChannelAction::Swap { .. }))
+			.count() as u32;
+		Ok(swap_channels.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let swap_channels =
+			<u32>::decode(&mut &state[..]).map_err(|_| "Failed to decode pre-upgrade state.")?;
+		ensure!(
+			DepositChannelBrokers::<T, I>::iter_keys().count() as u32 >= swap_channels,
+			"BrokerDepositChannels migration failed."
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::*;
+	use cf_chains::DepositChannel;
+	use sp_core::H160;
+
+	#[test]
+	fn swap_channels_are_indexed_by_broker() {
+		new_test_ext().execute_with(|| {
+			let swap_address = H160::repeat_byte(1);
+			let lp_address = H160::repeat_byte(2);
+			let details = |channel_id, address, action| DepositChannelDetails::<Test, ()> {
+				deposit_channel: DepositChannel {
+					channel_id,
+					address,
+					asset: assets::eth::Asset::Eth,
+					state: Default::default(),
+				},
+				opened_at: 1,
+				expires_at: 2,
+				action,
+				boost_fee: 0,
+			};
+			DepositChannelLookup::<Test, ()>::insert(
+				swap_address,
+				details(
+					1,
+					swap_address,
+					ChannelAction::Swap {
+						destination_asset: Asset::Flip,
+						destination_address: ForeignChainAddress::Eth(swap_address),
+						broker_id: BROKER,
+						broker_commission_bps: 10,
+						affiliates: Default::default(),
+					},
+				),
+			);
+			DepositChannelLookup::<Test, ()>::insert(
+				lp_address,
+				details(2, lp_address, ChannelAction::LiquidityProvision { lp_account: ALICE }),
+			);
+
+			Migration::<Test, ()>::on_runtime_upgrade();
+
+			assert_eq!(
+				BrokerDepositChannels::<Test, ()>::get(BROKER, 1),
+				Some(BrokerDepositChannel {
+					deposit_address: swap_address,
+					deposit_count: 0,
+					total_deposited: 0,
+				})
+			);
+			assert_eq!(DepositChannelBrokers::<Test, ()>::get(swap_address), Some(BROKER));
+			assert!(!DepositChannelBrokers::<Test, ()>::contains_key(lp_address));
+			assert_eq!(BrokerDepositChannels::<Test, ()>::iter().count(), 1);
+		});
+	}
+}
@@ -0,0 +1,271 @@
+use crate::*;
+use cf_chains::DepositChannel;
+use frame_support::traits::OnRuntimeUpgrade;
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+/// Types as they were before CCMs had a fallback address.
+pub(crate) mod old {
+	use super::*;
+
+	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct CcmChannelMetadata {
+		pub message: CcmMessage,
+		pub gas_budget: AssetAmount,
+		pub cf_parameters: CcmCfParameters,
+	}
+
+	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub enum ChannelAction<AccountId> {
+		Swap {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			broker_id: AccountId,
+			broker_commission_bps: BasisPoints,
+		},
+		LiquidityProvision {
+			lp_account: AccountId,
+		},
+		CcmTransfer {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			channel_metadata: CcmChannelMetadata,
+		},
+	}
+
+	#[derive(CloneNoBound, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct DepositChannelDetails<T: Config<I>, I: 'static> {
+		pub deposit_channel: DepositChannel<T::TargetChain>,
+		pub opened_at: TargetChainBlockNumber<T, I>,
+		pub expires_at: TargetChainBlockNumber<T, I>,
+		pub action: ChannelAction<T::AccountId>,
+		pub boost_fee: BasisPoints,
+	}
+
+	#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo)]
+	pub struct CrossChainMessage<C: Chain> {
+		pub egress_id: EgressId,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub destination_address: C::ChainAccount,
+		pub message: CcmMessage,
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub cf_parameters: CcmCfParameters,
+		pub gas_budget: C::ChainAmount,
+	}
+
+	#[frame_support::storage_alias]
+	pub type DepositChannelLookup<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		DepositChannelDetails<T, I>,
+		OptionQuery,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressCcm<T: Config<I>, I: 'static> = StorageValue<
+		Pallet<T, I>,
+		Vec<CrossChainMessage<<T as Config<I>>::TargetChain>>,
+		ValueQuery,
+	>;
+}
+
+/// Types as they are after this migration, at storage version 7.
+pub(crate) mod new {
+	use super::*;
+
+	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub enum ChannelAction<AccountId> {
+		Swap {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			broker_id: AccountId,
+			broker_commission_bps: BasisPoints,
+		},
+		LiquidityProvision {
+			lp_account: AccountId,
+		},
+		CcmTransfer {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			channel_metadata: CcmChannelMetadata,
+		},
+	}
+
+	#[derive(CloneNoBound, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct DepositChannelDetails<T: Config<I>, I: 'static> {
+		pub deposit_channel: DepositChannel<T::TargetChain>,
+		pub opened_at: TargetChainBlockNumber<T, I>,
+		pub expires_at: TargetChainBlockNumber<T, I>,
+		pub action: ChannelAction<T::AccountId>,
+		pub boost_fee: BasisPoints,
+	}
+
+	#[frame_support::storage_alias]
+	pub type DepositChannelLookup<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		DepositChannelDetails<T, I>,
+		OptionQuery,
+	>;
+}
+
+impl<AccountId> From<old::ChannelAction<AccountId>> for new::ChannelAction<AccountId> {
+	fn from(action: old::ChannelAction<AccountId>) -> Self {
+		match action {
+			old::ChannelAction::Swap {
+				destination_asset,
+				destination_address,
+				broker_id,
+				broker_commission_bps,
+			} => Self::Swap {
+				destination_asset,
+				destination_address,
+				broker_id,
+				broker_commission_bps,
+			},
+			old::ChannelAction::LiquidityProvision { lp_account } =>
+				Self::LiquidityProvision { lp_account },
+			old::ChannelAction::CcmTransfer {
+				destination_asset,
+				destination_address,
+				channel_metadata: old::CcmChannelMetadata { message, gas_budget, cf_parameters },
+			} => Self::CcmTransfer {
+				destination_asset,
+				destination_address,
+				channel_metadata: CcmChannelMetadata {
+					message,
+					gas_budget,
+					cf_parameters,
+					fallback: None,
+				},
+			},
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut channels = 0u64;
+		new::DepositChannelLookup::<T, I>::translate(
+			|_address, old_channel: old::DepositChannelDetails<T, I>| {
+				channels += 1;
+				Some(new::DepositChannelDetails::<T, I> {
+					deposit_channel: old_channel.deposit_channel,
+					opened_at: old_channel.opened_at,
+					expires_at: old_channel.expires_at,
+					action: old_channel.action.into(),
+					boost_fee: old_channel.boost_fee,
+				})
+			},
+		);
+
+		let messages = old::ScheduledEgressCcm::<T, I>::take()
+			.into_iter()
+			.map(|message| CrossChainMessage {
+				egress_id: message.egress_id,
+				asset: message.asset,
+				amount: message.amount,
+				destination_address: message.destination_address,
+				message: message.message,
+				source_chain: message.source_chain,
+				source_address: message.source_address,
+				cf_parameters: message.cf_parameters,
+				gas_budget: message.gas_budget,
+				fallback: None,
+			})
+			.collect::<Vec<_>>();
+		ScheduledEgressCcm::<T, I>::put(messages);
+
+		T::DbWeight::get().reads_writes(channels + 1, channels + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::DepositChannelLookup::<T, I>::iter_keys().count() as u32,
+			old::ScheduledEgressCcm::<T, I>::decode_len().unwrap_or_default() as u32,
+		)
+			.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let (channels, messages) = <(u32, u32)>::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode pre-upgrade state.")?;
+		ensure!(
+			new::DepositChannelLookup::<T, I>::iter_values().count() as u32 == channels,
+			"DepositChannelLookup migration failed."
+		);
+		ensure!(
+			ScheduledEgressCcm::<T, I>::get().len() as u32 == messages,
+			"ScheduledEgressCcm migration failed."
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::*;
+	use sp_core::H160;
+
+	#[test]
+	fn ccm_channels_and_messages_get_no_fallback() {
+		new_test_ext().execute_with(|| {
+			let address = H160::repeat_byte(1);
+			let ccm_metadata = old::CcmChannelMetadata {
+				message: vec![0x01].try_into().unwrap(),
+				gas_budget: 1_000,
+				cf_parameters: Default::default(),
+			};
+			old::DepositChannelLookup::<Test, ()>::insert(
+				address,
+				old::DepositChannelDetails::<Test, ()> {
+					deposit_channel: DepositChannel {
+						channel_id: 1,
+						address,
+						asset: assets::eth::Asset::Eth,
+						state: Default::default(),
+					},
+					opened_at: 1,
+					expires_at: 2,
+					action: old::ChannelAction::CcmTransfer {
+						destination_asset: Asset::Flip,
+						destination_address: ForeignChainAddress::Eth(address),
+						channel_metadata: ccm_metadata.clone(),
+					},
+					boost_fee: 0,
+				},
+			);
+			old::ScheduledEgressCcm::<Test, ()>::put(vec![old::CrossChainMessage {
+				egress_id: (ForeignChain::Ethereum, 1),
+				asset: assets::eth::Asset::Flip,
+				amount: 1_000,
+				destination_address: address,
+				message: ccm_metadata.message.clone(),
+				source_chain: ForeignChain::Ethereum,
+				source_address: None,
+				cf_parameters: Default::default(),
+				gas_budget: 1_000,
+			}]);
+
+			Migration::<Test, ()>::on_runtime_upgrade();
+
+			assert!(matches!(
+				new::DepositChannelLookup::<Test, ()>::get(address).unwrap().action,
+				new::ChannelAction::CcmTransfer {
+					channel_metadata: CcmChannelMetadata { fallback: None, .. },
+					..
+				}
+			));
+			assert_eq!(ScheduledEgressCcm::<Test, ()>::get()[0].fallback, None);
+		});
+	}
+}
@@ -84,10 +84,25 @@ impl SwapDepositHandler for MockSwapDepositHandlerBtc {
 		_destination_address: ForeignChainAddress,
 		_broker_id: Self::AccountId,
 		_broker_commission_bps: cf_primitives::BasisPoints,
+		_affiliates: cf_primitives::Affiliates<Self::AccountId>,
 		_channel_id: ChannelId,
 	) -> SwapId {
 		unimplemented!()
 	}
+
+	fn schedule_vault_swap(
+		_from: Asset,
+		_to: Asset,
+		_amount: AssetAmount,
+		_destination_address: ForeignChainAddress,
+		_min_output: AssetAmount,
+		_refund_address: ForeignChainAddress,
+		_broker_id: Self::AccountId,
+		_broker_commission_bps: cf_primitives::BasisPoints,
+		_tx_hash: cf_primitives::TransactionHash,
+	) -> SwapId {
+		unimplemented!()
+	}
 }
 
 pub type MockEgressBroadcaster =
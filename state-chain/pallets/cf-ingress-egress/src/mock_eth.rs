@@ -29,7 +29,7 @@ use cf_traits::{
 };
 use frame_support::traits::{OriginTrait, UnfilteredDispatchable};
 use frame_system as system;
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup, Zero};
 
 type AccountId = u64;
@@ -71,8 +71,23 @@ impl system::Config for Test {
 impl_mock_chainflip!(Test);
 impl_mock_callback!(RuntimeOrigin);
 
+/// Funds paid into this address are treated as vault deposits.
+pub const VAULT_ADDRESS: EthereumAddress = H160([0xaa; 20]);
+
 pub struct MockDepositHandler;
-impl DepositHandler<Ethereum> for MockDepositHandler {}
+impl DepositHandler<Ethereum> for MockDepositHandler {
+	fn on_vault_deposit_made(
+		vault_address: EthereumAddress,
+		_deposit_details: <Ethereum as Chain>::DepositDetails,
+		_amount: <Ethereum as Chain>::ChainAmount,
+	) -> Result<(), ()> {
+		if vault_address == VAULT_ADDRESS {
+			Ok(())
+		} else {
+			Err(())
+		}
+	}
+}
 
 pub type MockEgressBroadcaster =
 	MockBroadcaster<(MockEthereumApiCall<MockEthEnvironment>, RuntimeCall)>;
@@ -246,6 +261,7 @@ impl<Ctx: Clone> RequestAddress for TestExternalities<Test, Ctx> {
 						destination_asset.into(),
 						destination_address.clone(),
 						Default::default(),
+						Default::default(),
 						BROKER,
 						None,
 						0,
@@ -1,20 +1,27 @@
 use crate::{
-	mock_eth::*, Call as PalletCall, ChannelAction, ChannelIdCounter, ChannelOpeningFee,
-	CrossChainMessage, DepositAction, DepositChannelLookup, DepositChannelPool,
-	DepositIgnoredReason, DepositWitness, DisabledEgressAssets, EgressDustLimit,
-	Event as PalletEvent, FailedForeignChainCall, FailedForeignChainCalls, FetchOrTransfer,
-	MinimumDeposit, Pallet, PalletConfigUpdate, ScheduledEgressCcm, ScheduledEgressFetchOrTransfer,
-	TargetChainAccount,
+	mock_eth::*, BrokerDepositChannel, BrokerDepositChannels, Call as PalletCall,
+	CcmFallbackEgressIds, CcmFallbacks, CcmRefundTransfer, ChannelAction, ChannelIdCounter,
+	ChannelOpeningFee, CrossChainMessage, DepositAction, DepositBalances, DepositChannelBrokers,
+	DepositChannelLookup, DepositChannelPool, DepositChannelRecycleBlocks, DepositIgnoredReason,
+	DepositWitness, DisabledEgressAssets, EgressDustLimit, Event as PalletEvent,
+	FailedForeignChainCall, FailedForeignChainCalls, FetchOrTransfer, MinimumDeposit, Pallet,
+	PalletConfigUpdate, ScheduledEgressCcm, ScheduledEgressFetchOrTransfer, StickyChannelRenewals,
+	StickyDepositChannels, TargetChainAccount, VaultSwapWitness,
+	MAX_STICKY_CHANNEL_RENEWALS_PER_BLOCK, STICKY_CHANNEL_FETCH_INTERVAL,
 };
 use cf_chains::{
-	address::AddressConverter, evm::EvmFetchId, mocks::MockEthereum, CcmChannelMetadata,
-	DepositChannel, ExecutexSwapAndCall, SwapOrigin, TransferAssetParams,
+	address::{AddressConverter, EncodedAddress},
+	evm::EvmFetchId,
+	mocks::MockEthereum,
+	CcmChannelMetadata, CcmFailureFallback, DepositChannel, ExecutexSwapAndCall, SwapOrigin,
+	TransferAssetParams,
 };
-use cf_primitives::{chains::assets::eth, ChannelId, ForeignChain};
+use cf_primitives::{chains::assets::eth, ChannelId, EgressId, ForeignChain};
 use cf_test_utilities::assert_has_event;
 use cf_traits::{
 	mocks::{
 		self,
+		account_role_registry::MockAccountRoleRegistry,
 		address_converter::MockAddressConverter,
 		api_call::{MockEthAllBatch, MockEthEnvironment, MockEthereumApiCall},
 		block_height_provider::BlockHeightProvider,
@@ -22,10 +29,11 @@ use cf_traits::{
 		funding_info::MockFundingInfo,
 		tracked_data_provider::TrackedDataProvider,
 	},
-	DepositApi, EgressApi, EpochInfo, FundingInfo, GetBlockHeight, ScheduledEgressDetails,
+	AccountRoleRegistry, DepositApi, EgressApi, EpochInfo, FundingInfo, GetBlockHeight,
+	ScheduledEgressDetails,
 };
 use frame_support::{
-	assert_err, assert_ok,
+	assert_err, assert_noop, assert_ok,
 	traits::{Hooks, OriginTrait},
 	weights::Weight,
 };
@@ -101,6 +109,7 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 				message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
 				gas_budget: 1_000,
 				cf_parameters: vec![].try_into().unwrap(),
+				fallback: None,
 			},
 		};
 
@@ -463,6 +472,7 @@ fn can_process_ccm_deposit() {
 			message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
 			gas_budget: 1_000,
 			cf_parameters: vec![].try_into().unwrap(),
+			fallback: None,
 		};
 		let ccm = CcmDepositMetadata {
 			source_chain: ForeignChain::Ethereum,
@@ -478,6 +488,7 @@ fn can_process_ccm_deposit() {
 			to_asset,
 			destination_address.clone(),
 			0,
+			Default::default(),
 			1,
 			Some(channel_metadata),
 			0,
@@ -532,6 +543,7 @@ fn can_egress_ccm() {
 				message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
 				gas_budget: GAS_BUDGET,
 				cf_parameters: vec![].try_into().unwrap(),
+				fallback: None,
 			}
 		};
 
@@ -555,6 +567,7 @@ fn can_egress_ccm() {
 				source_chain: ForeignChain::Ethereum,
 				source_address: Some(ForeignChainAddress::Eth([0xcf; 20].into())),
 				gas_budget: GAS_BUDGET,
+				fallback: None,
 			}
 		]);
 
@@ -1475,6 +1488,95 @@ fn on_finalize_handles_failed_calls() {
 	});
 }
 
+fn schedule_ccm_with_fallback() -> EgressId {
+	IngressEgress::schedule_egress(
+		ETH_ETH,
+		5_000,
+		ALICE_ETH_ADDRESS,
+		Some((
+			CcmDepositMetadata {
+				source_chain: ForeignChain::Ethereum,
+				source_address: None,
+				channel_metadata: CcmChannelMetadata {
+					message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
+					gas_budget: 1_000,
+					cf_parameters: Default::default(),
+					fallback: Some(CcmFailureFallback {
+						refund_address: ForeignChainAddress::Eth(BOB_ETH_ADDRESS),
+					}),
+				},
+			},
+			1_000,
+		)),
+	)
+	.expect("Egress should succeed")
+	.egress_id
+}
+
+#[test]
+fn failed_ccm_is_refunded_once_expired() {
+	new_test_ext().execute_with(|| {
+		let epoch = 1u32;
+		MockEpochInfo::set_epoch(epoch);
+		let egress_id = schedule_ccm_with_fallback();
+		IngressEgress::on_finalize(1);
+		let broadcast_id = CcmFallbacks::<Test>::get(egress_id).unwrap().broadcast_id;
+		assert_eq!(CcmFallbackEgressIds::<Test>::get(broadcast_id), Some(egress_id));
+		assert!(MockEgressBroadcaster::witnessed_after_abort(broadcast_id));
+
+		// The failure is stored, so the user can broadcast the call until it expires.
+		MockEgressBroadcaster::dispatch_failed_callback(broadcast_id);
+		assert_eq!(
+			FailedForeignChainCalls::<Test>::get(epoch),
+			vec![FailedForeignChainCall { broadcast_id, original_epoch: epoch }]
+		);
+		assert!(CcmFallbacks::<Test>::get(egress_id).is_some());
+
+		// Re-signed in the next epoch, and expired in the one after that.
+		MockEpochInfo::set_epoch(epoch + 1);
+		IngressEgress::on_finalize(3);
+		assert!(CcmFallbacks::<Test>::get(egress_id).is_some());
+		MockEpochInfo::set_epoch(epoch + 2);
+		IngressEgress::on_finalize(4);
+
+		System::assert_has_event(RuntimeEvent::IngressEgress(
+			PalletEvent::FailedForeignChainCallExpired { broadcast_id },
+		));
+		System::assert_last_event(RuntimeEvent::IngressEgress(PalletEvent::CcmFallbackRefunded {
+			egress_id,
+			asset: ETH_ETH,
+			amount: 5_000,
+			refund_address: BOB_ETH_ADDRESS,
+			transfer: CcmRefundTransfer::TransferFallback { broadcast_id: broadcast_id + 1 },
+		}));
+		assert!(CcmFallbacks::<Test>::get(egress_id).is_none());
+		assert!(CcmFallbackEgressIds::<Test>::get(broadcast_id).is_none());
+	});
+}
+
+#[test]
+fn delivered_ccm_is_not_refunded() {
+	new_test_ext().execute_with(|| {
+		let epoch = 1u32;
+		MockEpochInfo::set_epoch(epoch);
+		let egress_id = schedule_ccm_with_fallback();
+		IngressEgress::on_finalize(1);
+		let broadcast_id = CcmFallbacks::<Test>::get(egress_id).unwrap().broadcast_id;
+
+		// Failed calls are left for the user to broadcast.
+		MockEgressBroadcaster::dispatch_failed_callback(broadcast_id);
+		IngressEgress::on_finalize(2);
+		assert_eq!(
+			FailedForeignChainCalls::<Test>::get(epoch),
+			vec![FailedForeignChainCall { broadcast_id, original_epoch: epoch }]
+		);
+
+		MockEgressBroadcaster::dispatch_success_callback(broadcast_id);
+		assert!(CcmFallbacks::<Test>::get(egress_id).is_none());
+		assert!(CcmFallbackEgressIds::<Test>::get(broadcast_id).is_none());
+	});
+}
+
 #[test]
 fn consolidation_tx_gets_broadcasted_on_finalize() {
 	new_test_ext().execute_with(|| {
@@ -1576,3 +1678,554 @@ fn can_update_multiple_items_at_once() {
 		assert_eq!(MinimumDeposit::<Test, _>::get(eth::Asset::Eth), 200);
 	});
 }
+
+fn vault_swap(destination_address: EncodedAddress) -> VaultSwapWitness<Ethereum, u64> {
+	VaultSwapWitness {
+		vault_address: VAULT_ADDRESS,
+		asset: ETH_ETH,
+		amount: 1_000,
+		deposit_details: (),
+		destination_asset: Asset::Flip,
+		destination_address,
+		min_output: 900,
+		refund_address: BOB_ETH_ADDRESS,
+		broker_id: BROKER,
+		broker_commission_bps: 10,
+		tx_hash: [0x11; 32],
+	}
+}
+
+#[test]
+fn can_process_vault_swap() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_broker(
+			&BROKER
+		));
+		assert_ok!(IngressEgress::vault_swap_request(
+			RuntimeOrigin::root(),
+			Box::new(vault_swap(EncodedAddress::Eth(ALICE_ETH_ADDRESS.0))),
+		));
+
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			crate::Event::<Test>::VaultSwapRequested {
+				asset: ETH_ETH,
+				amount: 1_000,
+				deposit_details: (),
+				ingress_fee: 0,
+				swap_id: 1,
+				broker_id: BROKER,
+			},
+		));
+		// The funds are already in the vault, so nothing needs to be fetched.
+		assert_eq!(DepositBalances::<Test, _>::get(ETH_ETH).fetched, 1_000);
+		assert_eq!(
+			ScheduledEgressFetchOrTransfer::<Test, _>::get(),
+			vec![FetchOrTransfer::Transfer {
+				egress_id: (ForeignChain::Ethereum, 1),
+				asset: ETH_FLIP,
+				destination_address: ALICE_ETH_ADDRESS,
+				amount: 1_000,
+			}]
+		);
+	});
+}
+
+#[test]
+fn vault_swap_with_invalid_destination_is_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_broker(
+			&BROKER
+		));
+		assert_ok!(IngressEgress::vault_swap_request(
+			RuntimeOrigin::root(),
+			Box::new(vault_swap(EncodedAddress::Dot([0x01; 32]))),
+		));
+
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			crate::Event::<Test>::VaultSwapRefunded {
+				asset: ETH_ETH,
+				amount: 1_000,
+				deposit_details: (),
+				refund_address: BOB_ETH_ADDRESS,
+				egress_id: Some((ForeignChain::Ethereum, 1)),
+			},
+		));
+		assert_eq!(
+			ScheduledEgressFetchOrTransfer::<Test, _>::get(),
+			vec![FetchOrTransfer::Transfer {
+				egress_id: (ForeignChain::Ethereum, 1),
+				asset: ETH_ETH,
+				destination_address: BOB_ETH_ADDRESS,
+				amount: 1_000,
+			}]
+		);
+	});
+}
+
+#[test]
+fn vault_swap_from_unregistered_broker_is_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IngressEgress::vault_swap_request(
+			RuntimeOrigin::root(),
+			Box::new(vault_swap(EncodedAddress::Eth(ALICE_ETH_ADDRESS.0))),
+		));
+
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			crate::Event::<Test>::VaultSwapRefunded {
+				asset: ETH_ETH,
+				amount: 1_000,
+				deposit_details: (),
+				refund_address: BOB_ETH_ADDRESS,
+				egress_id: Some((ForeignChain::Ethereum, 1)),
+			},
+		));
+	});
+}
+
+#[test]
+fn vault_swaps_into_other_addresses_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			IngressEgress::vault_swap_request(
+				RuntimeOrigin::root(),
+				Box::new(VaultSwapWitness {
+					vault_address: ALICE_ETH_ADDRESS,
+					..vault_swap(EncodedAddress::Eth(ALICE_ETH_ADDRESS.0))
+				}),
+			),
+			crate::Error::<Test, _>::InvalidDepositAddress
+		);
+	});
+}
+
+fn request_broker_channel() -> (ChannelId, <Ethereum as Chain>::ChainAccount) {
+	let (channel_id, deposit_address, ..) = IngressEgress::request_swap_deposit_address(
+		ETH_ETH,
+		eth::Asset::Flip.into(),
+		ForeignChainAddress::Eth(Default::default()),
+		0,
+		Default::default(),
+		BROKER,
+		None,
+		0,
+	)
+	.unwrap();
+	(channel_id, deposit_address.try_into().unwrap())
+}
+
+#[test]
+fn broker_can_close_deposit_channel() {
+	new_test_ext().execute_with(|| {
+		const FEE: u128 = 100;
+		BlockHeightProvider::<MockEthereum>::set_block_height(10);
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::ChannelOpeningFee { fee: FEE }].try_into().unwrap()
+		));
+		MockFundingInfo::<Test>::credit_funds(&BROKER, FEE);
+		let (channel_id, deposit_address) = request_broker_channel();
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 110);
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&BROKER), 0);
+
+		assert_noop!(
+			IngressEgress::close_deposit_channel(RuntimeOrigin::signed(ALICE), channel_id),
+			crate::Error::<Test, _>::NotChannelOwner
+		);
+
+		BlockHeightProvider::<MockEthereum>::set_block_height(20);
+		assert_ok!(IngressEgress::close_deposit_channel(RuntimeOrigin::signed(BROKER), channel_id));
+		System::assert_last_event(RuntimeEvent::IngressEgress(PalletEvent::DepositChannelClosed {
+			channel_id,
+			deposit_address,
+			expires_at: 20,
+		}));
+		// 90 of the 100 blocks of the channel lifetime were unused.
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(PalletEvent::ChannelFeeRefunded {
+			channel_id,
+			fee: 90,
+		}));
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&BROKER), 90);
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 20);
+		assert_eq!(DepositChannelRecycleBlocks::<Test>::get(), vec![(120, deposit_address)]);
+
+		assert_noop!(
+			IngressEgress::close_deposit_channel(RuntimeOrigin::signed(BROKER), channel_id),
+			crate::Error::<Test, _>::DepositChannelExpired
+		);
+	});
+}
+
+#[test]
+fn broker_can_extend_deposit_channel() {
+	new_test_ext().execute_with(|| {
+		const FEE: u128 = 100;
+		BlockHeightProvider::<MockEthereum>::set_block_height(10);
+		let (channel_id, deposit_address) = request_broker_channel();
+		let (_, other_address) = request_broker_channel();
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::ChannelOpeningFee { fee: FEE }].try_into().unwrap()
+		));
+		MockFundingInfo::<Test>::credit_funds(&BROKER, FEE);
+
+		// The channel can expire at most one channel lifetime ahead of the current block.
+		BlockHeightProvider::<MockEthereum>::set_block_height(60);
+		assert_noop!(
+			IngressEgress::extend_deposit_channel(RuntimeOrigin::signed(BROKER), channel_id, 51),
+			crate::Error::<Test, _>::ChannelExtensionTooLong
+		);
+		assert_ok!(IngressEgress::extend_deposit_channel(
+			RuntimeOrigin::signed(BROKER),
+			channel_id,
+			50
+		));
+
+		// Half a channel lifetime costs half the opening fee.
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&BROKER), FEE / 2);
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			PalletEvent::DepositChannelExtended { channel_id, deposit_address, expires_at: 160 },
+		));
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 160);
+		// The recycle queue stays sorted.
+		assert_eq!(
+			DepositChannelRecycleBlocks::<Test>::get(),
+			vec![(210, other_address), (260, deposit_address)]
+		);
+
+		BlockHeightProvider::<MockEthereum>::set_block_height(160);
+		assert_noop!(
+			IngressEgress::extend_deposit_channel(RuntimeOrigin::signed(BROKER), channel_id, 10),
+			crate::Error::<Test, _>::DepositChannelExpired
+		);
+	});
+}
+
+#[test]
+fn broker_channels_track_deposits_until_recycled() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, deposit_address) = request_broker_channel();
+		assert_eq!(DepositChannelBrokers::<Test>::get(deposit_address), Some(BROKER));
+
+		for _ in 0..2 {
+			assert_ok!(IngressEgress::process_single_deposit(
+				deposit_address,
+				ETH_ETH,
+				1_000,
+				(),
+				Default::default()
+			));
+		}
+		assert_eq!(
+			BrokerDepositChannels::<Test>::get(BROKER, channel_id),
+			Some(BrokerDepositChannel {
+				deposit_address,
+				deposit_count: 2,
+				total_deposited: 2_000
+			})
+		);
+
+		let recycle_block = IngressEgress::expiry_and_recycle_block_height().2;
+		BlockHeightProvider::<MockEthereum>::set_block_height(recycle_block);
+		IngressEgress::on_idle(1, Weight::MAX);
+
+		assert!(BrokerDepositChannels::<Test>::get(BROKER, channel_id).is_none());
+		assert!(DepositChannelBrokers::<Test>::get(deposit_address).is_none());
+	});
+}
+
+#[test]
+fn sticky_channels_are_renewed_until_the_fee_cannot_be_paid() {
+	new_test_ext().execute_with(|| {
+		const FEE: u128 = 100;
+		BlockHeightProvider::<MockEthereum>::set_block_height(10);
+		let (channel_id, deposit_address) = request_broker_channel();
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::StickyChannelFee { fee: FEE }].try_into().unwrap()
+		));
+		MockFundingInfo::<Test>::credit_funds(&BROKER, 2 * FEE);
+
+		assert_noop!(
+			IngressEgress::make_channel_sticky(RuntimeOrigin::signed(ALICE), channel_id),
+			crate::Error::<Test, _>::NotChannelOwner
+		);
+		assert_ok!(IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id));
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			PalletEvent::DepositChannelMadeSticky { channel_id, deposit_address, expires_at: 110 },
+		));
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&BROKER), FEE);
+		// The channel is renewed half a channel lifetime before it expires, and is not recycled.
+		assert_eq!(StickyDepositChannels::<Test>::get(deposit_address), Some(60));
+		assert_eq!(StickyChannelRenewals::<Test>::get(), vec![(60, deposit_address)]);
+		assert!(DepositChannelRecycleBlocks::<Test>::get().is_empty());
+
+		assert_noop!(
+			IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id),
+			crate::Error::<Test, _>::ChannelAlreadySticky
+		);
+		assert_noop!(
+			IngressEgress::extend_deposit_channel(RuntimeOrigin::signed(BROKER), channel_id, 10),
+			crate::Error::<Test, _>::ChannelAlreadySticky
+		);
+
+		BlockHeightProvider::<MockEthereum>::set_block_height(60);
+		IngressEgress::on_initialize(1);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			PalletEvent::StickyDepositChannelRenewed {
+				channel_id,
+				deposit_address,
+				expires_at: 210,
+			},
+		));
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&BROKER), 0);
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 210);
+		assert_eq!(StickyChannelRenewals::<Test>::get(), vec![(160, deposit_address)]);
+
+		// The broker can't pay for the next renewal, so the channel expires as scheduled.
+		BlockHeightProvider::<MockEthereum>::set_block_height(160);
+		IngressEgress::on_initialize(1);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			PalletEvent::StickyDepositChannelLapsed {
+				channel_id,
+				deposit_address,
+				expires_at: 210,
+			},
+		));
+		assert!(StickyDepositChannels::<Test>::get(deposit_address).is_none());
+		assert!(StickyChannelRenewals::<Test>::get().is_empty());
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 210);
+		assert_eq!(DepositChannelRecycleBlocks::<Test>::get(), vec![(310, deposit_address)]);
+	});
+}
+
+#[test]
+fn broker_can_update_sticky_channel_destination() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, deposit_address) = request_broker_channel();
+		let destination_address = EncodedAddress::Eth([1; 20]);
+
+		assert_noop!(
+			IngressEgress::update_sticky_channel_destination(
+				RuntimeOrigin::signed(BROKER),
+				channel_id,
+				Asset::Usdc,
+				destination_address.clone(),
+			),
+			crate::Error::<Test, _>::ChannelNotSticky
+		);
+
+		assert_ok!(IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id));
+		assert_noop!(
+			IngressEgress::update_sticky_channel_destination(
+				RuntimeOrigin::signed(BROKER),
+				channel_id,
+				Asset::Btc,
+				destination_address.clone(),
+			),
+			crate::Error::<Test, _>::InvalidDestinationAddress
+		);
+		assert_noop!(
+			IngressEgress::update_sticky_channel_destination(
+				RuntimeOrigin::signed(BROKER),
+				channel_id,
+				ETH_ETH.into(),
+				destination_address.clone(),
+			),
+			crate::Error::<Test, _>::InvalidDestinationAddress
+		);
+
+		assert_ok!(IngressEgress::update_sticky_channel_destination(
+			RuntimeOrigin::signed(BROKER),
+			channel_id,
+			Asset::Usdc,
+			destination_address,
+		));
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			PalletEvent::StickyChannelDestinationUpdated {
+				channel_id,
+				destination_asset: Asset::Usdc,
+				destination_address: ForeignChainAddress::Eth([1; 20].into()),
+			},
+		));
+		assert!(matches!(
+			DepositChannelLookup::<Test>::get(deposit_address).unwrap().action,
+			ChannelAction::Swap {
+				destination_asset: Asset::Usdc,
+				destination_address: ForeignChainAddress::Eth(address),
+				..
+			} if address == H160([1; 20])
+		));
+	});
+}
+
+#[test]
+fn sticky_channel_deposits_share_a_fetch_until_closed() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, deposit_address) = request_broker_channel();
+		assert_ok!(IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id));
+
+		for _ in 0..2 {
+			assert_ok!(IngressEgress::process_single_deposit(
+				deposit_address,
+				ETH_ETH,
+				1_000,
+				(),
+				Default::default()
+			));
+		}
+		// Each deposit is reported, even though it is added to the existing fetch.
+		assert_eq!(
+			System::events()
+				.into_iter()
+				.filter(|record| record.event ==
+					RuntimeEvent::IngressEgress(PalletEvent::DepositFetchesScheduled {
+						channel_id,
+						asset: ETH_ETH
+					}))
+				.count(),
+			2
+		);
+		let scheduled_fetch = vec![FetchOrTransfer::Fetch {
+			asset: ETH_ETH,
+			deposit_address,
+			deposit_fetch_id: None,
+			amount: 2_000,
+		}];
+		assert_eq!(
+			ScheduledEgressFetchOrTransfer::<Test>::get()
+				.into_iter()
+				.filter(|request| matches!(request, FetchOrTransfer::Fetch { .. }))
+				.collect::<Vec<_>>(),
+			scheduled_fetch
+		);
+
+		// The fetch is held back until the next fetch interval.
+		System::set_block_number(STICKY_CHANNEL_FETCH_INTERVAL as u64 + 1);
+		IngressEgress::on_finalize(System::block_number());
+		assert_eq!(ScheduledEgressFetchOrTransfer::<Test>::get(), scheduled_fetch);
+
+		// Once the channel is closed, the fetch is sent without waiting.
+		assert_ok!(IngressEgress::close_deposit_channel(RuntimeOrigin::signed(BROKER), channel_id));
+		assert!(StickyDepositChannels::<Test>::get(deposit_address).is_none());
+		assert!(StickyChannelRenewals::<Test>::get().is_empty());
+		assert_eq!(DepositChannelRecycleBlocks::<Test>::get().len(), 1);
+		IngressEgress::on_finalize(System::block_number());
+		assert!(ScheduledEgressFetchOrTransfer::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn sticky_channel_deposits_are_fetched_every_interval() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, deposit_address) = request_broker_channel();
+		assert_ok!(IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id));
+		assert_ok!(IngressEgress::process_single_deposit(
+			deposit_address,
+			ETH_ETH,
+			1_000,
+			(),
+			Default::default()
+		));
+
+		System::set_block_number(2 * STICKY_CHANNEL_FETCH_INTERVAL as u64);
+		IngressEgress::on_finalize(System::block_number());
+		assert!(ScheduledEgressFetchOrTransfer::<Test>::get().is_empty());
+		assert_eq!(DepositBalances::<Test, _>::get(ETH_ETH).fetched, 1_000);
+	});
+}
+
+#[test]
+fn renewed_sticky_channel_is_witnessed_after_rotation() {
+	new_test_ext().execute_with(|| {
+		BlockHeightProvider::<MockEthereum>::set_block_height(10);
+		let (channel_id, deposit_address) = request_broker_channel();
+		assert_ok!(IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id));
+
+		// The channel is renewed, and the authorities rotate before its original expiry passes.
+		BlockHeightProvider::<MockEthereum>::set_block_height(60);
+		IngressEgress::on_initialize(1);
+		MockEpochInfo::set_epoch(MockEpochInfo::epoch_index() + 1);
+		BlockHeightProvider::<MockEthereum>::set_block_height(150);
+		IngressEgress::on_initialize(2);
+
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 210);
+		assert!(DepositChannelRecycleBlocks::<Test>::get().is_empty());
+
+		assert_ok!(IngressEgress::process_single_deposit(deposit_address, ETH_ETH, 1_000, (), 150));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			PalletEvent::DepositFetchesScheduled { channel_id, asset: ETH_ETH },
+		));
+		assert_eq!(
+			BrokerDepositChannels::<Test>::get(BROKER, channel_id).unwrap().deposit_count,
+			1
+		);
+	});
+}
+
+#[test]
+fn sticky_channel_renewals_are_bounded_per_block() {
+	new_test_ext().execute_with(|| {
+		BlockHeightProvider::<MockEthereum>::set_block_height(10);
+		let channel_count = MAX_STICKY_CHANNEL_RENEWALS_PER_BLOCK as usize + 1;
+		for _ in 0..channel_count {
+			let (channel_id, _) = request_broker_channel();
+			assert_ok!(IngressEgress::make_channel_sticky(
+				RuntimeOrigin::signed(BROKER),
+				channel_id
+			));
+		}
+		assert_eq!(StickyChannelRenewals::<Test>::decode_len(), Some(channel_count));
+
+		BlockHeightProvider::<MockEthereum>::set_block_height(60);
+		IngressEgress::on_initialize(1);
+		assert_eq!(
+			StickyChannelRenewals::<Test>::get()
+				.into_iter()
+				.filter(|(renewal_height, _)| *renewal_height == 60)
+				.count(),
+			1
+		);
+
+		// The remaining renewal is processed in the next block.
+		IngressEgress::on_initialize(2);
+		assert!(StickyChannelRenewals::<Test>::get()
+			.into_iter()
+			.all(|(renewal_height, _)| renewal_height == 160));
+	});
+}
+
+#[test]
+fn sticky_channel_lapses_once_its_address_is_no_longer_derived() {
+	new_test_ext().execute_with(|| {
+		const CHANNEL_ID: ChannelId = 7;
+		BlockHeightProvider::<MockEthereum>::set_block_height(10);
+		// A channel whose address was derived from a previous vault key.
+		let deposit_address: <Ethereum as Chain>::ChainAccount = [0xcf; 20].into();
+		DepositChannelPool::<Test, _>::insert(
+			CHANNEL_ID,
+			DepositChannel {
+				address: deposit_address,
+				..DepositChannel::<Ethereum>::generate_new::<
+					<Test as crate::Config>::AddressDerivation,
+				>(CHANNEL_ID, eth::Asset::Eth)
+				.unwrap()
+			},
+		);
+		let (channel_id, _) = request_broker_channel();
+		assert_eq!(channel_id, CHANNEL_ID);
+		assert_ok!(IngressEgress::make_channel_sticky(RuntimeOrigin::signed(BROKER), channel_id));
+
+		BlockHeightProvider::<MockEthereum>::set_block_height(60);
+		IngressEgress::on_initialize(1);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			PalletEvent::StickyDepositChannelLapsed {
+				channel_id,
+				deposit_address,
+				expires_at: 110,
+			},
+		));
+		assert!(StickyDepositChannels::<Test>::get(deposit_address).is_none());
+		// The channel keeps being witnessed at its original address until it expires.
+		assert_eq!(DepositChannelLookup::<Test>::get(deposit_address).unwrap().expires_at, 110);
+	});
+}
@@ -37,6 +37,11 @@ pub trait WeightInfo {
 	fn finalise_ingress(a: u32, ) -> Weight;
 	fn vault_transfer_failed() -> Weight;
 	fn ccm_broadcast_failed() -> Weight;
+	fn close_deposit_channel() -> Weight;
+	fn extend_deposit_channel() -> Weight;
+	fn make_channel_sticky() -> Weight;
+	fn update_sticky_channel_destination() -> Weight;
+	fn channel_queues(a: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_ingress_egress using the Substrate node and recommended hardware.
@@ -103,6 +108,98 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	fn ccm_broadcast_failed() -> Weight {
 		Weight::from_parts(1_000_000, 1_000)
 	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyChannelRenewals` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyChannelRenewals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelOpeningFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::ChannelOpeningFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn close_deposit_channel() -> Weight {
+		Weight::from_parts(32_758_000, 4246)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelOpeningFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::ChannelOpeningFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn extend_deposit_channel() -> Weight {
+		Weight::from_parts(44_102_000, 4359)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyChannelFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::StickyChannelFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyChannelRenewals` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyChannelRenewals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn make_channel_sticky() -> Weight {
+		Weight::from_parts(50_114_000, 4428)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_sticky_channel_destination() -> Weight {
+		Weight::from_parts(25_213_000, 4312)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[1, 1000]`.
+	fn channel_queues(a: u32, ) -> Weight {
+		Weight::from_parts(10_036_142, 1726)
+			.saturating_add(Weight::from_parts(61_574, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 56).saturating_mul(a.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -167,4 +264,96 @@ impl WeightInfo for () {
 	fn ccm_broadcast_failed() -> Weight {
 		Weight::from_parts(1_000_000, 1_000)
 	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyChannelRenewals` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyChannelRenewals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelOpeningFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::ChannelOpeningFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	fn close_deposit_channel() -> Weight {
+		Weight::from_parts(32_758_000, 4246)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelOpeningFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::ChannelOpeningFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn extend_deposit_channel() -> Weight {
+		Weight::from_parts(44_102_000, 4359)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyChannelFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::StickyChannelFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyChannelRenewals` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::StickyChannelRenewals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn make_channel_sticky() -> Weight {
+		Weight::from_parts(50_114_000, 4428)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `EthereumIngressEgress::BrokerDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::BrokerDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::StickyDepositChannels` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::StickyDepositChannels` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_sticky_channel_destination() -> Weight {
+		Weight::from_parts(25_213_000, 4312)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[1, 1000]`.
+	fn channel_queues(a: u32, ) -> Weight {
+		Weight::from_parts(10_036_142, 1726)
+			.saturating_add(Weight::from_parts(61_574, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 56).saturating_mul(a.into()))
+	}
 }
@@ -132,8 +132,9 @@ mod benchmarks {
 		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Eth, 1_000_000,));
 		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Usdc, 1_000_000,));
 
+		// Worst case: the order is given an expiry.
 		#[extrinsic_call]
-		update_limit_order(
+		update_limit_order_with_expiry(
 			RawOrigin::Signed(caller.clone()),
 			Asset::Eth,
 			Asset::Usdc,
@@ -141,6 +142,7 @@ mod benchmarks {
 			0,
 			Some(100),
 			IncreaseOrDecrease::Increase(1_000_000),
+			Some(BlockNumberFor::<T>::from(5u32)),
 		);
 	}
 
@@ -157,8 +159,9 @@ mod benchmarks {
 		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Eth, 1_000_000,));
 		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Usdc, 1_000_000,));
 
+		// Worst case: the order is given an expiry.
 		#[extrinsic_call]
-		set_limit_order(
+		set_limit_order_with_expiry(
 			RawOrigin::Signed(caller.clone()),
 			Asset::Eth,
 			Asset::Usdc,
@@ -166,6 +169,7 @@ mod benchmarks {
 			0,
 			Some(100),
 			1_000,
+			Some(BlockNumberFor::<T>::from(5u32)),
 		);
 	}
 
@@ -219,6 +223,7 @@ mod benchmarks {
 			Ok(PoolInfo {
 				limit_order_fee_hundredth_pips: fee,
 				range_order_fee_hundredth_pips: fee,
+				dynamic_fee: None,
 			})
 		);
 	}
@@ -252,5 +257,70 @@ mod benchmarks {
 		assert!(!ScheduledLimitOrderUpdates::<T>::get(BlockNumberFor::<T>::from(5u32)).is_empty());
 	}
 
+	#[benchmark]
+	fn set_dynamic_pool_fees() {
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		let dynamic_fee = DynamicFeeConfig {
+			min_fee_hundredth_pips: 500,
+			max_fee_hundredth_pips: 5_000,
+			window: 10,
+			max_fee_volatility: 100,
+		};
+		let call = Call::<T>::set_dynamic_pool_fees {
+			base_asset: Asset::Eth,
+			quote_asset: Asset::Usdc,
+			dynamic_fee: Some(dynamic_fee),
+		};
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert_eq!(
+			Pallet::<T>::pool_info(Asset::Eth, STABLE_ASSET).unwrap().dynamic_fee,
+			Some(dynamic_fee)
+		);
+	}
+
+	#[benchmark]
+	fn set_range_order_policy() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Eth, 1_000_000,));
+		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Usdc, 1_000_000,));
+		assert_ok!(Pallet::<T>::set_range_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000 },
+		));
+
+		#[extrinsic_call]
+		set_range_order_policy(
+			RawOrigin::Signed(caller.clone()),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			Some(RangeOrderPolicy { auto_compound: true, rebalance_width: Some(200) }),
+		);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
@@ -2,15 +2,20 @@
 use core::ops::Range;
 
 use cf_amm::{
-	common::{Amount, Order, Price, Side, SideMap, SqrtPriceQ64F96, Tick},
+	common::{
+		mul_div_floor, price_at_tick, Amount, Order, Price, Side, SideMap, SqrtPriceQ64F96, Tick,
+		MAX_TICK, MIN_TICK, PRICE_FRACTIONAL_BITS,
+	},
 	limit_orders,
 	limit_orders::{Collected, PositionInfo},
 	range_orders,
 	range_orders::Liquidity,
-	PoolState,
+	PoolState, SwapFees,
 };
 use cf_primitives::{chains::assets::any, Asset, AssetAmount, SwapOutput, STABLE_ASSET};
-use cf_traits::{impl_pallet_safe_mode, Chainflip, LpBalanceApi, PoolApi, SwappingApi};
+use cf_traits::{
+	impl_pallet_safe_mode, Chainflip, LpBalanceApi, PoolApi, PriceOracle, SwappingApi,
+};
 use frame_support::{
 	dispatch::GetDispatchInfo,
 	pallet_prelude::*,
@@ -20,9 +25,12 @@ use frame_support::{
 	transactional,
 };
 
-use frame_system::pallet_prelude::OriginFor;
+use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
 use serde::{Deserialize, Serialize};
-use sp_arithmetic::traits::{AtLeast32BitUnsigned, UniqueSaturatedInto, Zero};
+use sp_arithmetic::traits::{
+	AtLeast32BitUnsigned, CheckedSub, IntegerSquareRoot, UniqueSaturatedInto, Zero,
+};
+use sp_core::U256;
 use sp_std::{boxed::Box, collections::btree_set::BTreeSet, vec::Vec};
 
 pub use pallet::*;
@@ -251,6 +259,9 @@ impl<T> From<AssetsMap<T>> for SideMap<T> {
 
 pub const PALLET_VERSION: StorageVersion = StorageVersion::new(2);
 
+/// The maximum number of [DeferredLimitOrderCollections] credited in a block.
+pub const MAX_DEFERRED_COLLECTIONS_PER_BLOCK: u32 = 100;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use cf_amm::{
@@ -273,6 +284,23 @@ pub mod pallet {
 		pub call: Call<T>,
 	}
 
+	/// Identifies a limit order, so that it can be closed once it expires.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct LimitOrderKey<AccountId> {
+		pub lp: AccountId,
+		pub asset_pair: AssetPair,
+		pub side: Order,
+		pub id: OrderId,
+	}
+
+	/// Identifies a range order, so that its [RangeOrderPolicy] can be applied.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct RangeOrderKey<AccountId> {
+		pub lp: AccountId,
+		pub asset_pair: AssetPair,
+		pub id: OrderId,
+	}
+
 	#[derive(Clone, Debug, Encode, Decode, TypeInfo)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Pool<T: Config> {
@@ -287,6 +315,70 @@ pub mod pallet {
 
 	pub type OrderId = u64;
 
+	/// A snapshot of a pool's price oracle accumulators. At most one observation is made per block.
+	#[derive(
+		Copy,
+		Clone,
+		Debug,
+		Encode,
+		Decode,
+		TypeInfo,
+		MaxEncodedLen,
+		PartialEq,
+		Eq,
+		Deserialize,
+		Serialize,
+	)]
+	pub struct PriceObservation<BlockNumber> {
+		/// The block in which the observation was made.
+		pub block_number: BlockNumber,
+		/// The pool's mid price tick summed over every block since the pool's first observation.
+		pub tick_cumulative: i64,
+		/// One divided by the in-range range order liquidity, summed over every block since the
+		/// pool's first observation, as a fixed point number with 128 fractional bits. This is
+		/// allowed to wrap, only differences between observations are meaningful.
+		pub blocks_per_liquidity_cumulative: U256,
+		/// The pool's mid price tick when the observation was made. This applies until the next
+		/// observation.
+		pub tick: Tick,
+		/// The in-range range order liquidity when the observation was made. This applies until
+		/// the next observation.
+		pub liquidity: Liquidity,
+	}
+
+	impl<BlockNumber: AtLeast32BitUnsigned + Copy> PriceObservation<BlockNumber> {
+		/// Returns the observation that would be made at `block_number`, assuming the tick and
+		/// liquidity don't change in the meantime.
+		pub fn advanced_to(&self, block_number: BlockNumber) -> Self {
+			let blocks: u32 =
+				block_number.saturating_sub(self.block_number).unique_saturated_into();
+			Self {
+				block_number,
+				tick_cumulative: self
+					.tick_cumulative
+					.wrapping_add(i64::from(self.tick) * i64::from(blocks)),
+				blocks_per_liquidity_cumulative: self
+					.blocks_per_liquidity_cumulative
+					.overflowing_add(
+						(U256::from(blocks) << 128) /
+							U256::from(sp_std::cmp::max(self.liquidity, 1)),
+					)
+					.0,
+				..*self
+			}
+		}
+	}
+
+	/// The position of a pool's latest price observation in its ring buffer, and the number of
+	/// observations the ring buffer holds.
+	#[derive(
+		Copy, Clone, Debug, Default, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq,
+	)]
+	pub struct PriceObservationRing {
+		pub latest: u32,
+		pub len: u32,
+	}
+
 	pub type AssetAmounts = AssetsMap<AssetAmount>;
 
 	/// Represents an amount of liquidity, either as an exact amount, or through maximum and minimum
@@ -376,6 +468,55 @@ pub mod pallet {
 		}
 	}
 
+	/// A single limit or range order operation, applied as part of a batch. Each variant has the
+	/// same parameters and behaviour as the extrinsic of the same name.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub enum OrderOperation<BlockNumber> {
+		UpdateRangeOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			option_tick_range: Option<core::ops::Range<Tick>>,
+			size_change: IncreaseOrDecrease<RangeOrderSize>,
+		},
+		SetRangeOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			option_tick_range: Option<core::ops::Range<Tick>>,
+			size: RangeOrderSize,
+		},
+		UpdateLimitOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Order,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			amount_change: IncreaseOrDecrease<AssetAmount>,
+			expiry_block: Option<BlockNumber>,
+		},
+		SetLimitOrder {
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Order,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			sell_amount: AssetAmount,
+			expiry_block: Option<BlockNumber>,
+		},
+	}
+
+	impl<BlockNumber> OrderOperation<BlockNumber> {
+		pub fn weight<T: Config>(&self) -> Weight {
+			match self {
+				OrderOperation::UpdateRangeOrder { .. } => T::WeightInfo::update_range_order(),
+				OrderOperation::SetRangeOrder { .. } => T::WeightInfo::set_range_order(),
+				OrderOperation::UpdateLimitOrder { .. } => T::WeightInfo::update_limit_order(),
+				OrderOperation::SetLimitOrder { .. } => T::WeightInfo::set_limit_order(),
+			}
+		}
+	}
+
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: Chainflip {
@@ -388,6 +529,26 @@ pub mod pallet {
 		#[pallet::constant]
 		type NetworkFee: Get<Permill>;
 
+		/// The maximum number of price observations kept for each pool. As at most one
+		/// observation is made per block, this is the longest window a TWAP can be computed over.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
+		/// The maximum number of order operations that can be applied in a single batch.
+		#[pallet::constant]
+		type MaxOrdersPerBatch: Get<u32>;
+
+		/// The maximum number of expired limit orders that are closed in a single block. Any
+		/// further expired orders are closed in the following blocks.
+		#[pallet::constant]
+		type MaxExpiredLimitOrdersPerBlock: Get<u32>;
+
+		/// The maximum number of range orders whose [RangeOrderPolicy] is applied in a single
+		/// block. Orders are visited in turn, so every order is visited once the pallet has
+		/// cycled through all of them.
+		#[pallet::constant]
+		type MaxAutomatedRangeOrdersPerBlock: Get<u32>;
+
 		/// Safe Mode access.
 		type SafeMode: Get<PalletSafeMode>;
 
@@ -421,6 +582,85 @@ pub mod pallet {
 	pub(super) type ScheduledLimitOrderUpdates<T: Config> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<LimitOrderUpdate<T>>, ValueQuery>;
 
+	/// Pools whose fees are adjusted every block according to their recent price volatility.
+	#[pallet::storage]
+	pub(super) type DynamicPoolFees<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, DynamicFeeConfig, OptionQuery>;
+
+	/// The earnings of limit orders that were collected when a dynamic fee changed the fee of the
+	/// pool, waiting to be credited to their LPs over the following blocks.
+	#[pallet::storage]
+	pub(super) type DeferredLimitOrderCollections<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		AssetPair,
+		Vec<DeferredLimitOrderCollection<T::AccountId>>,
+		OptionQuery,
+	>;
+
+	/// Running totals of the swap volume and fees of each pool.
+	#[pallet::storage]
+	pub(super) type PoolStatistics<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, PoolStats, ValueQuery>;
+
+	/// The fees each LP has earned over the lifetime of each of their positions, including
+	/// positions that have since been closed.
+	#[pallet::storage]
+	pub(super) type LpPositionFeeEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(AssetPair, LpPosition),
+		AssetAmounts,
+		ValueQuery,
+	>;
+
+	/// The block at which each limit order with an expiry should be closed.
+	#[pallet::storage]
+	pub(super) type LimitOrderExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, LimitOrderKey<T::AccountId>, BlockNumberFor<T>, OptionQuery>;
+
+	/// Index of the limit orders due to expire at each block. Entries may be stale if the order's
+	/// expiry has since changed, see [LimitOrderExpiries].
+	#[pallet::storage]
+	pub(super) type LimitOrderExpiryQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Vec<LimitOrderKey<T::AccountId>>,
+		ValueQuery,
+	>;
+
+	/// The automation the pallet applies to each range order that has a policy.
+	#[pallet::storage]
+	pub(super) type RangeOrderPolicies<T: Config> =
+		StorageMap<_, Twox64Concat, RangeOrderKey<T::AccountId>, RangeOrderPolicy, OptionQuery>;
+
+	/// The last range order whose policy was applied. The next block continues from the order
+	/// after it.
+	#[pallet::storage]
+	pub(super) type RangeOrderPolicyCursor<T: Config> =
+		StorageValue<_, RangeOrderKey<T::AccountId>, OptionQuery>;
+
+	/// The position of each pool's latest price observation, and the number of observations held.
+	#[pallet::storage]
+	pub(super) type PriceObservationRings<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, PriceObservationRing, OptionQuery>;
+
+	/// Bounded ring buffers of price observations for each pool, used to compute time-weighted
+	/// average prices.
+	#[pallet::storage]
+	pub(super) type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		AssetPair,
+		Twox64Concat,
+		u32,
+		PriceObservation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub flip_buy_interval: BlockNumberFor<T>,
@@ -469,6 +709,21 @@ pub mod pallet {
 				}
 			}
 
+			// Collect to avoid undefined behaviour (See StorsgeMap::iter documentation)
+			for (asset_pair, mut pool) in Pools::<T>::iter().collect::<Vec<_>>() {
+				weight_used.saturating_accrue(T::DbWeight::get().reads_writes(3, 2));
+				Self::observe_pool_price(asset_pair, &mut pool);
+			}
+
+			weight_used.saturating_accrue(Self::credit_deferred_limit_order_collections());
+			for (asset_pair, dynamic_fee) in DynamicPoolFees::<T>::iter().collect::<Vec<_>>() {
+				weight_used.saturating_accrue(Self::update_dynamic_pool_fee(
+					asset_pair,
+					dynamic_fee,
+					current_block,
+				));
+			}
+
 			weight_used.saturating_accrue(T::DbWeight::get().reads(1));
 			for LimitOrderUpdate { ref lp, id, call } in
 				ScheduledLimitOrderUpdates::<T>::take(current_block)
@@ -492,6 +747,9 @@ pub mod pallet {
 				});
 				weight_used.saturating_accrue(call_weight);
 			}
+
+			weight_used.saturating_accrue(Self::close_expired_limit_orders(current_block));
+			weight_used.saturating_accrue(Self::apply_range_order_policies());
 			weight_used
 		}
 	}
@@ -528,6 +786,8 @@ pub mod pallet {
 		InsufficientLiquidity,
 		/// The swap output is past the maximum allowed amount.
 		OutputOverflow,
+		/// The input required for the swap is past the maximum allowed amount.
+		InputOverflow,
 		/// There are no amounts between the specified maximum and minimum that match the required
 		/// ratio of assets
 		AssetRatioUnachieveable,
@@ -539,6 +799,17 @@ pub mod pallet {
 		UnsupportedCall,
 		/// The update can't be scheduled because it has expired (dispatch_at is in the past).
 		LimitOrderUpdateExpired,
+		/// The pool doesn't have enough price observations to cover the requested window.
+		InsufficientPriceHistory,
+		/// The TWAP window must be at least one block.
+		InvalidTwapWindow,
+		/// The expiry block of a limit order must be in the future.
+		InvalidExpiryBlock,
+		/// The dynamic fee parameters are invalid.
+		InvalidDynamicFeeConfig,
+		/// The rebalance width of a range order policy must be non-zero and no wider than the
+		/// full range of ticks.
+		InvalidRebalanceWidth,
 	}
 
 	#[pallet::event]
@@ -581,6 +852,16 @@ pub mod pallet {
 			collected_fees: AssetAmount,
 			bought_amount: AssetAmount,
 		},
+		/// A limit order reached its expiry block and was closed. Its assets have been returned to
+		/// the LP's free balance.
+		LimitOrderExpired {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Order,
+			id: OrderId,
+			tick: Tick,
+		},
 		NetworkFeeTaken {
 			fee_amount: AssetAmount,
 		},
@@ -595,6 +876,47 @@ pub mod pallet {
 			quote_asset: Asset,
 			fee_hundredth_pips: u32,
 		},
+		/// Dynamic fees have been enabled, updated or disabled (if `None`) for a pool.
+		DynamicPoolFeeConfigSet {
+			base_asset: Asset,
+			quote_asset: Asset,
+			dynamic_fee: Option<DynamicFeeConfig>,
+		},
+		/// A pool's fee has been adjusted according to its recent price volatility.
+		DynamicPoolFeeSet {
+			base_asset: Asset,
+			quote_asset: Asset,
+			fee_hundredth_pips: u32,
+			realised_variance: u64,
+		},
+		/// A range order's policy has been set, or removed (if `None`).
+		RangeOrderPolicySet {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			policy: Option<RangeOrderPolicy>,
+		},
+		/// The fees collected from an auto-compounding range order were added back to it as
+		/// liquidity. Any part of the fees that couldn't be added is left in the LP's free
+		/// balance.
+		RangeOrderCompounded {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			amounts: AssetAmounts,
+		},
+		/// The price moved too far from a range order's range, so the order was moved next to the
+		/// current price.
+		RangeOrderRebalanced {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			previous_tick_range: core::ops::Range<Tick>,
+			new_tick_range: core::ops::Range<Tick>,
+		},
 		/// A scheduled update to a limit order succeeded.
 		ScheduledLimitOrderUpdateDispatchSuccess {
 			lp: T::AccountId,
@@ -668,7 +990,7 @@ pub mod pallet {
 			Pools::<T>::try_mutate(asset_pair, |maybe_pool| {
 				ensure!(maybe_pool.is_none(), Error::<T>::PoolAlreadyExists);
 
-				*maybe_pool = Some(Pool {
+				let mut pool = Pool {
 					range_orders_cache: Default::default(),
 					limit_orders_cache: Default::default(),
 					pool_state: PoolState::new(fee_hundredth_pips, initial_price).map_err(|e| {
@@ -681,7 +1003,9 @@ pub mod pallet {
 								Error::<T>::InvalidInitialPrice,
 						}
 					})?,
-				});
+				};
+				Self::observe_pool_price(asset_pair, &mut pool);
+				*maybe_pool = Some(pool);
 
 				Ok::<_, Error<T>>(())
 			})?;
@@ -866,61 +1190,16 @@ pub mod pallet {
 			option_tick: Option<Tick>,
 			amount_change: IncreaseOrDecrease<AssetAmount>,
 		) -> DispatchResult {
-			ensure!(
-				T::SafeMode::get().limit_order_update_enabled,
-				Error::<T>::UpdatingLimitOrdersDisabled
-			);
-			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
-			Self::try_mutate_order(&lp, base_asset, quote_asset, |asset_pair, pool| {
-				let tick = match (
-					pool.limit_orders_cache[side.to_sold_side().into()]
-						.get(&lp)
-						.and_then(|limit_orders| limit_orders.get(&id))
-						.cloned(),
-					option_tick,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick)) | (Some(tick), None) => Ok(tick),
-					(Some(previous_tick), Some(new_tick)) => {
-						if previous_tick != new_tick {
-							let withdrawn_asset_amount = Self::inner_update_limit_order(
-								pool,
-								&lp,
-								asset_pair,
-								side,
-								id,
-								previous_tick,
-								IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
-								/* allow_noop */ false,
-							)?;
-							Self::inner_update_limit_order(
-								pool,
-								&lp,
-								asset_pair,
-								side,
-								id,
-								new_tick,
-								IncreaseOrDecrease::Increase(withdrawn_asset_amount.into()),
-								/* allow_noop */ true,
-							)?;
-						}
-
-						Ok(new_tick)
-					},
-				}?;
-				Self::inner_update_limit_order(
-					pool,
-					&lp,
-					asset_pair,
-					side,
-					id,
-					tick,
-					amount_change.map(|amount| amount.into()),
-					/* allow_noop */ false,
-				)?;
-
-				Ok(())
-			})
+			Self::update_limit_order_with_expiry(
+				origin,
+				base_asset,
+				quote_asset,
+				side,
+				id,
+				option_tick,
+				amount_change,
+				None,
+			)
 		}
 
 		/// Optionally move the order to a different tick and then set its amount of liquidity. The
@@ -942,49 +1221,16 @@ pub mod pallet {
 			option_tick: Option<Tick>,
 			sell_amount: AssetAmount,
 		) -> DispatchResult {
-			ensure!(
-				T::SafeMode::get().limit_order_update_enabled,
-				Error::<T>::UpdatingLimitOrdersDisabled
-			);
-			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
-			Self::try_mutate_order(&lp, base_asset, quote_asset, |asset_pair, pool| {
-				let tick = match (
-					pool.limit_orders_cache[side.to_sold_side().into()]
-						.get(&lp)
-						.and_then(|limit_orders| limit_orders.get(&id))
-						.cloned(),
-					option_tick,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick)) => Ok(tick),
-					(Some(previous_tick), option_new_tick) => {
-						Self::inner_update_limit_order(
-							pool,
-							&lp,
-							asset_pair,
-							side,
-							id,
-							previous_tick,
-							IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
-							/* allow noop */ false,
-						)?;
-
-						Ok(option_new_tick.unwrap_or(previous_tick))
-					},
-				}?;
-				Self::inner_update_limit_order(
-					pool,
-					&lp,
-					asset_pair,
-					side,
-					id,
-					tick,
-					IncreaseOrDecrease::Increase(sell_amount.into()),
-					/* allow noop */ true,
-				)?;
-
-				Ok(())
-			})
+			Self::set_limit_order_with_expiry(
+				origin,
+				base_asset,
+				quote_asset,
+				side,
+				id,
+				option_tick,
+				sell_amount,
+				None,
+			)
 		}
 
 		/// Sets the Liquidity Pool fees. Also collect earned fees and bought amount for
@@ -1012,29 +1258,10 @@ pub mod pallet {
 				PoolState::<(T::AccountId, OrderId)>::validate_fees(fee_hundredth_pips),
 				Error::<T>::InvalidFeeAmount
 			);
-			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
-			Self::try_mutate_pool(asset_pair, |asset_pair: &AssetPair, pool| {
-				pool.pool_state
-					.set_fees(fee_hundredth_pips)
-					.map_err(|_| Error::<T>::InvalidFeeAmount)?
-					.try_map(|side, collected_fees| {
-						for ((lp, id), tick, collected, position_info) in collected_fees.into_iter()
-						{
-							Self::process_limit_order_update(
-								pool,
-								asset_pair,
-								&lp,
-								Assets::from(side).sell_order(),
-								id,
-								tick,
-								collected,
-								position_info,
-								IncreaseOrDecrease::Increase(0),
-							)?;
-						}
-						Result::<(), DispatchError>::Ok(())
-					})
-			})?;
+			Self::inner_set_pool_fees(
+				AssetPair::try_new::<T>(base_asset, quote_asset)?,
+				fee_hundredth_pips,
+			)?;
 
 			Self::deposit_event(Event::<T>::PoolFeeSet {
 				base_asset,
@@ -1048,9 +1275,9 @@ pub mod pallet {
 		/// Schedules a limit order update to be executed at a later block.
 		///
 		/// The update is defined by the passed call, which can be one either `set_limit_order` or
-		/// `update_limit_order` extrinsic at a later block. The call is executed at the specified
-		/// block number, and the validity of the order is checked at the block number it enters
-		/// the state-chain.
+		/// `update_limit_order` extrinsic, or their `_with_expiry` variants, at a later block. The
+		/// call is executed at the specified block number, and the validity of the order is checked
+		/// at the block number it enters the state-chain.
 		///
 		/// `dispatch_at` specifies the block at which to schedule the update. If the
 		///
@@ -1087,23 +1314,380 @@ pub mod pallet {
 			};
 
 			match *call {
-				Call::update_limit_order { id, .. } => schedule_or_dispatch(*call, id),
-				Call::set_limit_order { id, .. } => schedule_or_dispatch(*call, id),
+				Call::update_limit_order { id, .. } |
+				Call::set_limit_order { id, .. } |
+				Call::update_limit_order_with_expiry { id, .. } |
+				Call::set_limit_order_with_expiry { id, .. } => schedule_or_dispatch(*call, id),
 				_ => Err(Error::<T>::UnsupportedCall)?,
 			}
 		}
-	}
-}
 
-impl<T: Config> SwappingApi for Pallet<T> {
-	fn take_network_fee(input: AssetAmount) -> AssetAmount {
-		if input.is_zero() {
-			return input
-		}
+		/// Applies a list of limit and range order operations, across any number of pools, in
+		/// order. Either all of the operations succeed, or none of them are applied, so that
+		/// orders can be moved together without leaving stale orders exposed to swaps in between.
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - Any error returned by the individual order operations.
+		#[pallet::call_index(9)]
+		#[pallet::weight(
+			orders.iter().fold(Weight::zero(), |total, order| total.saturating_add(order.weight::<T>()))
+		)]
+		pub fn batch_orders(
+			origin: OriginFor<T>,
+			orders: BoundedVec<OrderOperation<BlockNumberFor<T>>, T::MaxOrdersPerBatch>,
+		) -> DispatchResult {
+			T::AccountRoleRegistry::ensure_liquidity_provider(origin.clone())?;
+
+			for order in orders {
+				match order {
+					OrderOperation::UpdateRangeOrder {
+						base_asset,
+						quote_asset,
+						id,
+						option_tick_range,
+						size_change,
+					} => Self::update_range_order(
+						origin.clone(),
+						base_asset,
+						quote_asset,
+						id,
+						option_tick_range,
+						size_change,
+					),
+					OrderOperation::SetRangeOrder {
+						base_asset,
+						quote_asset,
+						id,
+						option_tick_range,
+						size,
+					} => Self::set_range_order(
+						origin.clone(),
+						base_asset,
+						quote_asset,
+						id,
+						option_tick_range,
+						size,
+					),
+					OrderOperation::UpdateLimitOrder {
+						base_asset,
+						quote_asset,
+						side,
+						id,
+						option_tick,
+						amount_change,
+						expiry_block,
+					} => Self::update_limit_order_with_expiry(
+						origin.clone(),
+						base_asset,
+						quote_asset,
+						side,
+						id,
+						option_tick,
+						amount_change,
+						expiry_block,
+					),
+					OrderOperation::SetLimitOrder {
+						base_asset,
+						quote_asset,
+						side,
+						id,
+						option_tick,
+						sell_amount,
+						expiry_block,
+					} => Self::set_limit_order_with_expiry(
+						origin.clone(),
+						base_asset,
+						quote_asset,
+						side,
+						id,
+						option_tick,
+						sell_amount,
+						expiry_block,
+					),
+				}?;
+			}
+
+			Ok(())
+		}
+
+		/// Enables dynamic fees for a pool, or disables them if `dynamic_fee` is `None`. While
+		/// enabled, the pool's fees are set every block according to its realised price volatility
+		/// over the last window, overriding any fee set via `set_pool_fees`. When disabled, the
+		/// pool keeps its current fee. Requires governance origin.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::DynamicPoolFeeConfigSet)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [PoolDoesNotExist](pallet_cf_pools::Error::PoolDoesNotExist)
+		/// - [InvalidDynamicFeeConfig](pallet_cf_pools::Error::InvalidDynamicFeeConfig)
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::set_dynamic_pool_fees())]
+		pub fn set_dynamic_pool_fees(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			dynamic_fee: Option<DynamicFeeConfig>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+
+			if let Some(config) = dynamic_fee {
+				ensure!(
+					config.min_fee_hundredth_pips <= config.max_fee_hundredth_pips &&
+						PoolState::<(T::AccountId, OrderId)>::validate_fees(
+							config.max_fee_hundredth_pips
+						) && config.window > 0 && config.window < T::MaxPriceObservations::get() &&
+						config.max_fee_volatility > 0,
+					Error::<T>::InvalidDynamicFeeConfig
+				);
+				DynamicPoolFees::<T>::insert(asset_pair, config);
+			} else {
+				DynamicPoolFees::<T>::remove(asset_pair);
+			}
+
+			Self::deposit_event(Event::<T>::DynamicPoolFeeConfigSet {
+				base_asset,
+				quote_asset,
+				dynamic_fee,
+			});
+
+			Ok(())
+		}
+
+		/// Attaches a policy to one of your range orders, or removes it if `policy` is `None`.
+		/// The pallet applies the policy to the order periodically: auto-compounding orders have
+		/// their collected fees added back as liquidity, and orders with a rebalance width have
+		/// their assets moved to a range of that width next to the current price once the price is
+		/// more than that width outside their range. The policy is removed once the order is
+		/// closed.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::RangeOrderPolicySet)
+		///
+		/// ## Errors
+		///
+		/// - [UpdatingRangeOrdersDisabled](pallet_cf_pools::Error::UpdatingRangeOrdersDisabled)
+		/// - [PoolDoesNotExist](pallet_cf_pools::Error::PoolDoesNotExist)
+		/// - [OrderDoesNotExist](pallet_cf_pools::Error::OrderDoesNotExist)
+		/// - [InvalidRebalanceWidth](pallet_cf_pools::Error::InvalidRebalanceWidth)
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_range_order_policy())]
+		pub fn set_range_order_policy(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			policy: Option<RangeOrderPolicy>,
+		) -> DispatchResult {
+			ensure!(
+				T::SafeMode::get().range_order_update_enabled,
+				Error::<T>::UpdatingRangeOrdersDisabled
+			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			let order_key = RangeOrderKey { lp: lp.clone(), asset_pair, id };
+
+			if let Some(policy) = policy {
+				let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+				ensure!(
+					pool.range_orders_cache
+						.get(&lp)
+						.is_some_and(|range_orders| range_orders.contains_key(&id)),
+					Error::<T>::OrderDoesNotExist
+				);
+				ensure!(
+					policy
+						.rebalance_width
+						.map_or(true, |width| width > 0 && width <= MAX_TICK.abs_diff(MIN_TICK)),
+					Error::<T>::InvalidRebalanceWidth
+				);
+				RangeOrderPolicies::<T>::insert(&order_key, policy);
+			} else {
+				RangeOrderPolicies::<T>::remove(&order_key);
+			}
+
+			Self::deposit_event(Event::<T>::RangeOrderPolicySet {
+				lp,
+				base_asset,
+				quote_asset,
+				id,
+				policy,
+			});
+
+			Ok(())
+		}
+
+		/// Same as [update_limit_order](Pallet::update_limit_order), but the order can be given an
+		/// `expiry_block`, at which it will be closed automatically and its assets returned to your
+		/// balance. If no `expiry_block` is given, any existing expiry is kept.
+		///
+		/// ## Errors
+		///
+		/// - [InvalidExpiryBlock](pallet_cf_pools::Error::InvalidExpiryBlock)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::update_limit_order())]
+		pub fn update_limit_order_with_expiry(
+			origin: OriginFor<T>,
+			base_asset: any::Asset,
+			quote_asset: any::Asset,
+			side: Order,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			amount_change: IncreaseOrDecrease<AssetAmount>,
+			expiry_block: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure!(
+				T::SafeMode::get().limit_order_update_enabled,
+				Error::<T>::UpdatingLimitOrdersDisabled
+			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			Self::ensure_valid_expiry_block(expiry_block)?;
+			Self::try_mutate_order(&lp, base_asset, quote_asset, |asset_pair, pool| {
+				let order_key = LimitOrderKey { lp: lp.clone(), asset_pair: *asset_pair, side, id };
+				let expiry_block =
+					expiry_block.or_else(|| LimitOrderExpiries::<T>::get(&order_key));
+				let tick = match (
+					pool.limit_orders_cache[side.to_sold_side().into()]
+						.get(&lp)
+						.and_then(|limit_orders| limit_orders.get(&id))
+						.cloned(),
+					option_tick,
+				) {
+					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+					(None, Some(tick)) | (Some(tick), None) => Ok(tick),
+					(Some(previous_tick), Some(new_tick)) => {
+						if previous_tick != new_tick {
+							let withdrawn_asset_amount = Self::inner_update_limit_order(
+								pool,
+								&lp,
+								asset_pair,
+								side,
+								id,
+								previous_tick,
+								IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
+								/* allow_noop */ false,
+							)?;
+							Self::inner_update_limit_order(
+								pool,
+								&lp,
+								asset_pair,
+								side,
+								id,
+								new_tick,
+								IncreaseOrDecrease::Increase(withdrawn_asset_amount.into()),
+								/* allow_noop */ true,
+							)?;
+						}
+
+						Ok(new_tick)
+					},
+				}?;
+				Self::inner_update_limit_order(
+					pool,
+					&lp,
+					asset_pair,
+					side,
+					id,
+					tick,
+					amount_change.map(|amount| amount.into()),
+					/* allow_noop */ false,
+				)?;
+				Self::update_limit_order_expiry(pool, order_key, expiry_block);
+
+				Ok(())
+			})
+		}
+
+		/// Same as [set_limit_order](Pallet::set_limit_order), but the order can be given an
+		/// `expiry_block`, at which it will be closed automatically and its assets returned to your
+		/// balance. If no `expiry_block` is given, the order has no expiry.
+		///
+		/// ## Errors
+		///
+		/// - [InvalidExpiryBlock](pallet_cf_pools::Error::InvalidExpiryBlock)
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_limit_order())]
+		pub fn set_limit_order_with_expiry(
+			origin: OriginFor<T>,
+			base_asset: any::Asset,
+			quote_asset: any::Asset,
+			side: Order,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			sell_amount: AssetAmount,
+			expiry_block: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure!(
+				T::SafeMode::get().limit_order_update_enabled,
+				Error::<T>::UpdatingLimitOrdersDisabled
+			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			Self::ensure_valid_expiry_block(expiry_block)?;
+			Self::try_mutate_order(&lp, base_asset, quote_asset, |asset_pair, pool| {
+				let tick = match (
+					pool.limit_orders_cache[side.to_sold_side().into()]
+						.get(&lp)
+						.and_then(|limit_orders| limit_orders.get(&id))
+						.cloned(),
+					option_tick,
+				) {
+					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+					(None, Some(tick)) => Ok(tick),
+					(Some(previous_tick), option_new_tick) => {
+						Self::inner_update_limit_order(
+							pool,
+							&lp,
+							asset_pair,
+							side,
+							id,
+							previous_tick,
+							IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
+							/* allow noop */ false,
+						)?;
+
+						Ok(option_new_tick.unwrap_or(previous_tick))
+					},
+				}?;
+				Self::inner_update_limit_order(
+					pool,
+					&lp,
+					asset_pair,
+					side,
+					id,
+					tick,
+					IncreaseOrDecrease::Increase(sell_amount.into()),
+					/* allow noop */ true,
+				)?;
+				Self::update_limit_order_expiry(
+					pool,
+					LimitOrderKey { lp: lp.clone(), asset_pair: *asset_pair, side, id },
+					expiry_block,
+				);
+
+				Ok(())
+			})
+		}
+	}
+}
+
+impl<T: Config> SwappingApi for Pallet<T> {
+	fn take_network_fee(from: any::Asset, to: any::Asset, input: AssetAmount) -> AssetAmount {
+		if input.is_zero() {
+			return input
+		}
 		let (remaining, fee) = utilities::calculate_network_fee(T::NetworkFee::get(), input);
 		CollectedNetworkFee::<T>::mutate(|total| {
 			total.saturating_accrue(fee);
 		});
+		Self::record_network_fee(from, to, fee);
 		Self::deposit_event(Event::<T>::NetworkFeeTaken { fee_amount: fee });
 		remaining
 	}
@@ -1116,18 +1700,61 @@ impl<T: Config> SwappingApi for Pallet<T> {
 	) -> Result<AssetAmount, DispatchError> {
 		let (asset_pair, order) =
 			AssetPair::from_swap(from, to).ok_or(Error::<T>::PoolDoesNotExist)?;
-		Self::try_mutate_pool(asset_pair, |_asset_pair, pool| {
-			let (output_amount, remaining_amount) =
-				pool.pool_state.swap(order, input_amount.into(), None);
+		Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
+			Self::observe_pool_price(*asset_pair, pool);
+			let (output_amount, remaining_amount, fees) =
+				pool.pool_state.swap_with_fees(order, input_amount.into(), None);
 			remaining_amount
 				.is_zero()
 				.then_some(())
 				.ok_or(Error::<T>::InsufficientLiquidity)?;
 			let output_amount = output_amount.try_into().map_err(|_| Error::<T>::OutputOverflow)?;
+			Self::record_swap(*asset_pair, order, input_amount, fees);
 			Self::deposit_event(Event::<T>::AssetSwapped { from, to, input_amount, output_amount });
 			Ok(output_amount)
 		})
 	}
+
+	fn take_network_fee_exact_output(
+		from: any::Asset,
+		to: any::Asset,
+		output: AssetAmount,
+	) -> AssetAmount {
+		if output.is_zero() {
+			return output
+		}
+		let (input, fee) =
+			utilities::calculate_network_fee_exact_output(T::NetworkFee::get(), output);
+		CollectedNetworkFee::<T>::mutate(|total| {
+			total.saturating_accrue(fee);
+		});
+		Self::record_network_fee(from, to, fee);
+		Self::deposit_event(Event::<T>::NetworkFeeTaken { fee_amount: fee });
+		input
+	}
+
+	#[transactional]
+	fn swap_single_leg_exact_output(
+		from: any::Asset,
+		to: any::Asset,
+		output_amount: AssetAmount,
+	) -> Result<(AssetAmount, AssetAmount), DispatchError> {
+		let (asset_pair, order) =
+			AssetPair::from_swap(from, to).ok_or(Error::<T>::PoolDoesNotExist)?;
+		Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
+			Self::observe_pool_price(*asset_pair, pool);
+			let (input_amount, bought_amount, fees) =
+				pool.pool_state.swap_exact_output_with_fees(order, output_amount.into(), None);
+			(bought_amount >= output_amount.into())
+				.then_some(())
+				.ok_or(Error::<T>::InsufficientLiquidity)?;
+			let input_amount = input_amount.try_into().map_err(|_| Error::<T>::InputOverflow)?;
+			let output_amount = bought_amount.try_into().map_err(|_| Error::<T>::OutputOverflow)?;
+			Self::record_swap(*asset_pair, order, input_amount, fees);
+			Self::deposit_event(Event::<T>::AssetSwapped { from, to, input_amount, output_amount });
+			Ok((input_amount, output_amount))
+		})
+	}
 }
 
 impl<T: Config> PoolApi for Pallet<T> {
@@ -1138,6 +1765,43 @@ impl<T: Config> PoolApi for Pallet<T> {
 	}
 }
 
+impl<T: Config> PriceOracle for Pallet<T> {
+	type BlockNumber = BlockNumberFor<T>;
+
+	fn convert_at_twap(
+		from: any::Asset,
+		to: any::Asset,
+		amount: AssetAmount,
+		window: BlockNumberFor<T>,
+	) -> Option<AssetAmount> {
+		let convert_single_leg = |from, to, amount: AssetAmount| -> Option<AssetAmount> {
+			let (asset_pair, order) = AssetPair::from_swap(from, to)?;
+			let AssetsMap { base, quote } = asset_pair.assets();
+			let price = Self::pool_twap(base, quote, window).ok()?.price;
+			if price.is_zero() {
+				return None
+			}
+			let one = U256::one() << PRICE_FRACTIONAL_BITS;
+			match order {
+				Order::Sell => mul_div_floor(amount.into(), price, one),
+				Order::Buy => mul_div_floor(amount.into(), one, price),
+			}
+			.try_into()
+			.ok()
+		};
+
+		match (from, to) {
+			_ if from == to => Some(amount),
+			(_, STABLE_ASSET) | (STABLE_ASSET, _) => convert_single_leg(from, to, amount),
+			_ => convert_single_leg(
+				STABLE_ASSET,
+				to,
+				convert_single_leg(from, STABLE_ASSET, amount)?,
+			),
+		}
+	}
+}
+
 impl<T: Config> cf_traits::FlipBurnInfo for Pallet<T> {
 	fn take_flip_to_burn() -> AssetAmount {
 		FlipToBurn::<T>::take()
@@ -1164,6 +1828,110 @@ pub struct PoolInfo {
 	/// The fee taken, when range orders are used, from swap inputs that contributes to liquidity
 	/// provider earnings
 	pub range_order_fee_hundredth_pips: u32,
+	/// If set, the pool's fees are adjusted every `window` blocks according to recent price
+	/// volatility.
+	pub dynamic_fee: Option<DynamicFeeConfig>,
+}
+
+/// Parameters for adjusting a pool's fees according to its recent price volatility. Every `window`
+/// blocks, the realised volatility over the last `window` blocks is measured as the square root of
+/// the sum of the squared per-block tick moves. The fee scales linearly from
+/// `min_fee_hundredth_pips`, when the price hasn't moved, to `max_fee_hundredth_pips` when the
+/// realised volatility is `max_fee_volatility` ticks or more.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Deserialize,
+	Serialize,
+)]
+pub struct DynamicFeeConfig {
+	pub min_fee_hundredth_pips: u32,
+	pub max_fee_hundredth_pips: u32,
+	/// The number of blocks over which the price volatility is measured.
+	pub window: u32,
+	/// The realised volatility, in ticks, at and above which the maximum fee is charged.
+	pub max_fee_volatility: u32,
+}
+
+impl DynamicFeeConfig {
+	pub fn fee_for_realised_variance(&self, realised_variance: u64) -> u32 {
+		let volatility =
+			core::cmp::min(realised_variance.integer_sqrt(), u64::from(self.max_fee_volatility));
+		let fee_range = self.max_fee_hundredth_pips.saturating_sub(self.min_fee_hundredth_pips);
+		let scaled_fee = u64::from(fee_range) * volatility /
+			u64::from(core::cmp::max(self.max_fee_volatility, 1));
+		// The scaled fee is at most `fee_range`, so it always fits in a u32.
+		self.min_fee_hundredth_pips.saturating_add(scaled_fee as u32)
+	}
+}
+
+/// Automation that the pallet applies to a range order on the LP's behalf.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Deserialize,
+	Serialize,
+)]
+pub struct RangeOrderPolicy {
+	/// If set, the order's fees are collected and added back to it as liquidity.
+	pub auto_compound: bool,
+	/// If set, once the price is more than this many ticks outside the order's range, the order
+	/// is withdrawn and its assets are minted into a range of this many ticks next to the current
+	/// tick. As the price has left the range, the withdrawn assets are all in one asset, so the
+	/// new range lies above the current tick if they are in the base asset, or below it if they
+	/// are in the quote asset.
+	pub rebalance_width: Option<u32>,
+}
+
+/// The earnings of a limit order that were collected when a dynamic fee changed the fee of its
+/// pool, and are yet to be credited to the LP.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct DeferredLimitOrderCollection<AccountId> {
+	pub lp: AccountId,
+	pub side: Order,
+	pub id: OrderId,
+	pub tick: Tick,
+	pub collected_fees: AssetAmount,
+	pub bought_amount: AssetAmount,
+	pub sell_amount_total: AssetAmount,
+}
+
+impl RangeOrderPolicy {
+	/// Returns true if `current_tick` is more than `width` ticks outside of `tick_range`.
+	pub fn needs_rebalancing(tick_range: &Range<Tick>, current_tick: Tick, width: u32) -> bool {
+		let width = width as Tick;
+		current_tick < tick_range.start.saturating_sub(width) ||
+			current_tick >= tick_range.end.saturating_add(width)
+	}
+
+	/// Returns the range of `width` ticks directly above `current_tick`, which can be funded with
+	/// only the base asset, or directly below it, which can be funded with only the quote asset.
+	/// The range is truncated if necessary to lie within the valid ticks.
+	pub fn adjacent_tick_range(current_tick: Tick, width: u32, above: bool) -> Range<Tick> {
+		let width = width as Tick;
+		if above {
+			let lower_tick = current_tick.saturating_add(1).min(MAX_TICK - 1);
+			lower_tick..lower_tick.saturating_add(width).min(MAX_TICK)
+		} else {
+			let upper_tick = current_tick.max(MIN_TICK + 1);
+			upper_tick.saturating_sub(width).max(MIN_TICK)..upper_tick
+		}
+	}
 }
 
 #[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
@@ -1175,6 +1943,8 @@ pub struct LimitOrder<T: Config> {
 	pub sell_amount: Amount,
 	pub fees_earned: Amount,
 	pub original_sell_amount: Amount,
+	/// The block at which the order will be closed, if it has an expiry.
+	pub expiry_block: Option<BlockNumberFor<T>>,
 }
 
 #[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
@@ -1264,6 +2034,71 @@ pub struct PoolPriceV2 {
 	pub buy: Option<SqrtPriceQ64F96>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Encode, Decode, TypeInfo, PartialEq, Eq, Debug)]
+pub struct PoolTwap {
+	/// The average mid price tick over the window, i.e. the tick of the geometric mean price.
+	pub tick: Tick,
+	/// The price at `tick`, in units of the quote asset per unit of the base asset.
+	pub price: Price,
+	/// The harmonic mean of the in-range range order liquidity over the window.
+	pub harmonic_mean_liquidity: Liquidity,
+}
+
+/// Running totals of the swaps that have gone through a pool.
+#[derive(
+	Serialize,
+	Deserialize,
+	Clone,
+	Default,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Debug,
+)]
+pub struct PoolStats {
+	/// The total amount of each asset sold into the pool by swaps.
+	pub volume: AssetAmounts,
+	/// The total fees paid to limit orders, in each asset.
+	pub limit_order_fees: AssetAmounts,
+	/// The total fees paid to range orders, in each asset.
+	pub range_order_fees: AssetAmounts,
+	/// The total network fee, in STABLE_ASSET, taken from swaps through the pool. The fee of a
+	/// swap through two pools is split evenly between them.
+	pub network_fee: AssetAmount,
+}
+
+/// Identifies one of an LP's positions within a pool.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LpPosition {
+	LimitOrder { side: Order, id: OrderId },
+	RangeOrder { id: OrderId },
+}
+
+#[derive(Serialize, Deserialize, Clone, Encode, Decode, TypeInfo, PartialEq, Eq, Debug)]
+pub struct LpPositionStats {
+	pub base_asset: Asset,
+	pub quote_asset: Asset,
+	pub position: LpPosition,
+	/// The fees earned by the position over its lifetime, in each asset.
+	pub fees_earned: AssetAmounts,
+}
+
 impl<T: Config> Pallet<T> {
 	fn inner_sweep(lp: &T::AccountId) -> DispatchResult {
 		// Collect to avoid undefined behaviour (See StorsgeMap::iter_keys documentation)
@@ -1525,81 +2360,582 @@ impl<T: Config> Pallet<T> {
 						},
 					)
 				})?;
+		Self::record_lp_fees(lp, asset_pair, LpPosition::RangeOrder { id }, collected_fees);
+
+		if position_info.liquidity == 0 {
+			if let Some(range_orders) = pool.range_orders_cache.get_mut(lp) {
+				range_orders.remove(&id);
+				if range_orders.is_empty() {
+					pool.range_orders_cache.remove(lp);
+				}
+			}
+		} else {
+			let range_orders = pool.range_orders_cache.entry(lp.clone()).or_default();
+			range_orders.insert(id, tick_range.clone());
+		}
+
+		let zero_change = *liquidity_change.abs() == 0;
+
+		if !zero_change || collected_fees != Default::default() {
+			Self::deposit_event(Event::<T>::RangeOrderUpdated {
+				lp: lp.clone(),
+				base_asset: asset_pair.assets().base,
+				quote_asset: asset_pair.assets().quote,
+				id,
+				tick_range,
+				size_change: {
+					if zero_change {
+						None
+					} else {
+						Some(liquidity_change.map(|liquidity| RangeOrderChange {
+							liquidity,
+							amounts: assets_change,
+						}))
+					}
+				},
+				liquidity_total: position_info.liquidity,
+				collected_fees,
+			});
+		}
+
+		Ok(assets_change)
+	}
+
+	#[transactional]
+	pub fn swap_with_network_fee(
+		from: any::Asset,
+		to: any::Asset,
+		input_amount: AssetAmount,
+	) -> Result<SwapOutput, DispatchError> {
+		Ok(match (from, to) {
+			(_, STABLE_ASSET) => {
+				let output = Self::take_network_fee(
+					from,
+					to,
+					Self::swap_single_leg(from, to, input_amount)?,
+				);
+				SwapOutput { intermediary: None, output }
+			},
+			(STABLE_ASSET, _) => {
+				let output = Self::swap_single_leg(
+					from,
+					to,
+					Self::take_network_fee(from, to, input_amount),
+				)?;
+				SwapOutput { intermediary: None, output }
+			},
+			_ => {
+				let intermediary = Self::swap_single_leg(from, STABLE_ASSET, input_amount)?;
+				let output = Self::swap_single_leg(
+					STABLE_ASSET,
+					to,
+					Self::take_network_fee(from, to, intermediary),
+				)?;
+				SwapOutput { intermediary: Some(intermediary), output }
+			},
+		})
+	}
+
+	fn try_mutate_pool<
+		R,
+		E: From<pallet::Error<T>>,
+		F: FnOnce(&AssetPair, &mut Pool<T>) -> Result<R, E>,
+	>(
+		asset_pair: AssetPair,
+		f: F,
+	) -> Result<R, E> {
+		Pools::<T>::try_mutate(asset_pair, |maybe_pool| {
+			let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
+			f(&asset_pair, pool)
+		})
+	}
+
+	/// Adds a swap's input, and the fees it paid to liquidity providers, to the pool's running
+	/// totals.
+	fn record_swap(asset_pair: AssetPair, order: Order, input_amount: AssetAmount, fees: SwapFees) {
+		let sold_asset: Assets = order.to_sold_side().into();
+		PoolStatistics::<T>::mutate(asset_pair, |stats| {
+			stats.volume[sold_asset].saturating_accrue(input_amount);
+			// The fees are a fraction of the input amount, so they always fit in an AssetAmount.
+			stats.limit_order_fees[sold_asset]
+				.saturating_accrue(fees.limit_orders.try_into().unwrap_or(AssetAmount::MAX));
+			stats.range_order_fees[sold_asset]
+				.saturating_accrue(fees.range_orders.try_into().unwrap_or(AssetAmount::MAX));
+		});
+	}
+
+	/// Adds the network fee of a swap from `from` to `to` to the running totals of the pools it
+	/// goes through. A swap between two non-stable assets goes through two pools, and the fee is
+	/// split evenly between them.
+	fn record_network_fee(from: any::Asset, to: any::Asset, fee: AssetAmount) {
+		let (from_pool_fee, to_pool_fee) = match (from, to) {
+			(_, STABLE_ASSET) => (fee, 0),
+			(STABLE_ASSET, _) => (0, fee),
+			_ => (fee - fee / 2, fee / 2),
+		};
+		for (pool_asset, fee) in [(from, from_pool_fee), (to, to_pool_fee)] {
+			if let Some((asset_pair, _)) = AssetPair::from_swap(pool_asset, STABLE_ASSET) {
+				PoolStatistics::<T>::mutate(asset_pair, |stats| {
+					stats.network_fee.saturating_accrue(fee);
+				});
+			}
+		}
+	}
+
+	/// Adds fees collected by one of an LP's positions to its lifetime earnings.
+	fn record_lp_fees(
+		lp: &T::AccountId,
+		asset_pair: &AssetPair,
+		position: LpPosition,
+		fees: AssetAmounts,
+	) {
+		if fees != Default::default() {
+			LpPositionFeeEarnings::<T>::mutate(lp, (*asset_pair, position), |earned| {
+				earned.base.saturating_accrue(fees.base);
+				earned.quote.saturating_accrue(fees.quote);
+			});
+		}
+	}
+
+	/// Sets the fees of a pool, collecting earned fees and bought amounts for all limit orders.
+	/// If `defer_collections` is set, the collected earnings are queued in
+	/// [DeferredLimitOrderCollections] instead of being credited immediately.
+	fn inner_set_pool_fees(
+		asset_pair: AssetPair,
+		fee_hundredth_pips: u32,
+		defer_collections: bool,
+	) -> DispatchResult {
+		let mut deferred_collections = Vec::new();
+		Self::try_mutate_pool(asset_pair, |asset_pair: &AssetPair, pool| {
+			pool.pool_state
+				.set_fees(fee_hundredth_pips)
+				.map_err(|_| Error::<T>::InvalidFeeAmount)?
+				.try_map(|side, collected_fees| {
+					for ((lp, id), tick, collected, position_info) in collected_fees.into_iter() {
+						let side = Assets::from(side).sell_order();
+						if !defer_collections {
+							Self::process_limit_order_update(
+								pool,
+								asset_pair,
+								&lp,
+								side,
+								id,
+								tick,
+								collected,
+								position_info,
+								IncreaseOrDecrease::Increase(0),
+							)?;
+							continue
+						}
+						Self::update_limit_orders_cache(pool, &lp, side, id, tick, &position_info);
+						if !collected.fees.is_zero() || !collected.bought_amount.is_zero() {
+							deferred_collections.push(DeferredLimitOrderCollection {
+								lp,
+								side,
+								id,
+								tick,
+								collected_fees: collected.fees.try_into()?,
+								bought_amount: collected.bought_amount.try_into()?,
+								sell_amount_total: position_info.amount.try_into()?,
+							});
+						}
+					}
+					Result::<(), DispatchError>::Ok(())
+				})
+		})?;
+		if !deferred_collections.is_empty() {
+			DeferredLimitOrderCollections::<T>::insert(asset_pair, deferred_collections);
+		}
+		Ok(())
+	}
+
+	/// Credits the earnings in [DeferredLimitOrderCollections] to their LPs, at most
+	/// [MAX_DEFERRED_COLLECTIONS_PER_BLOCK] in total.
+	fn credit_deferred_limit_order_collections() -> Weight {
+		let mut credited = 0u32;
+		let mut weight_used = T::DbWeight::get().reads(1);
+		for asset_pair in DeferredLimitOrderCollections::<T>::iter_keys().collect::<Vec<_>>() {
+			let limit = MAX_DEFERRED_COLLECTIONS_PER_BLOCK - credited;
+			if limit == 0 {
+				break
+			}
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			DeferredLimitOrderCollections::<T>::mutate_exists(asset_pair, |maybe_collections| {
+				let Some(collections) = maybe_collections.as_mut() else { return };
+				let due = collections.len().min(limit as usize);
+				for DeferredLimitOrderCollection {
+					lp,
+					side,
+					id,
+					tick,
+					collected_fees,
+					bought_amount,
+					sell_amount_total,
+				} in collections.drain(..due)
+				{
+					if let Err(error) = Self::credit_limit_order_earnings(
+						&asset_pair,
+						&lp,
+						side,
+						id,
+						tick,
+						collected_fees,
+						bought_amount,
+						IncreaseOrDecrease::Increase(0),
+						sell_amount_total,
+					) {
+						log::error!(
+							"Unable to credit deferred limit order earnings to {lp:?}: {error:?}"
+						);
+					}
+				}
+				credited += due as u32;
+				if collections.is_empty() {
+					*maybe_collections = None;
+				}
+			});
+		}
+		weight_used
+			.saturating_add(T::WeightInfo::update_limit_order().saturating_mul(credited.into()))
+	}
+
+	/// Sets the pool's fee according to the realised variance of its price over the last `window`
+	/// blocks. The pool is left unchanged until it has enough price history to cover the window,
+	/// and while the earnings collected by its previous fee change are still being credited.
+	fn update_dynamic_pool_fee(
+		asset_pair: AssetPair,
+		dynamic_fee: DynamicFeeConfig,
+		current_block: BlockNumberFor<T>,
+	) -> Weight {
+		let mut weight_used = T::DbWeight::get().reads(1);
+		if DeferredLimitOrderCollections::<T>::contains_key(asset_pair) {
+			return weight_used
+		}
+
+		// At most one observation is made per block, so at most `window + 1` are read.
+		weight_used.saturating_accrue(T::DbWeight::get().reads(u64::from(dynamic_fee.window) + 2));
+		let realised_variance = match current_block
+			.checked_sub(&dynamic_fee.window.into())
+			.ok_or_else(|| DispatchError::from(Error::<T>::InsufficientPriceHistory))
+			.and_then(|window_start| Self::realised_price_variance(asset_pair, window_start))
+		{
+			Ok(realised_variance) => realised_variance,
+			Err(_) => return weight_used,
+		};
+
+		let fee_hundredth_pips = dynamic_fee.fee_for_realised_variance(realised_variance);
+		weight_used.saturating_accrue(T::DbWeight::get().reads(1));
+		let Some(pool) = Pools::<T>::get(asset_pair) else { return weight_used };
+		if pool.pool_state.limit_order_fee() == fee_hundredth_pips &&
+			pool.pool_state.range_order_fee() == fee_hundredth_pips
+		{
+			return weight_used
+		}
 
-		if position_info.liquidity == 0 {
-			if let Some(range_orders) = pool.range_orders_cache.get_mut(lp) {
-				range_orders.remove(&id);
-				if range_orders.is_empty() {
-					pool.range_orders_cache.remove(lp);
-				}
+		// The earnings of the limit orders are collected, but credited over the following blocks.
+		weight_used.saturating_accrue(
+			T::WeightInfo::set_pool_fees().saturating_add(T::DbWeight::get().writes(1)),
+		);
+		match with_storage_layer(|| Self::inner_set_pool_fees(asset_pair, fee_hundredth_pips, true))
+		{
+			Ok(()) => {
+				let assets = asset_pair.assets();
+				Self::deposit_event(Event::<T>::DynamicPoolFeeSet {
+					base_asset: assets.base,
+					quote_asset: assets.quote,
+					fee_hundredth_pips,
+					realised_variance,
+				});
+			},
+			Err(error) =>
+				log::warn!("Unable to set dynamic fee for pool {asset_pair:?}: {error:?}"),
+		}
+
+		weight_used
+	}
+
+	/// Returns the sum of the squared tick moves between consecutive price observations since
+	/// `window_start`, including the move away from the tick in effect at `window_start`.
+	fn realised_price_variance(
+		asset_pair: AssetPair,
+		window_start: BlockNumberFor<T>,
+	) -> Result<u64, DispatchError> {
+		let ring = PriceObservationRings::<T>::get(asset_pair)
+			.ok_or(Error::<T>::InsufficientPriceHistory)?;
+
+		let mut realised_variance = 0u64;
+		let mut later_tick = None;
+		// Walk back from the latest observation until the one in effect at the window start.
+		for index in (0..ring.len).rev() {
+			let observation =
+				PriceObservations::<T>::get(asset_pair, (ring.latest + 1 + index) % ring.len)
+					.ok_or(Error::<T>::InsufficientPriceHistory)?;
+			if let Some(later_tick) = later_tick {
+				let tick_move = u64::from(observation.tick.abs_diff(later_tick));
+				realised_variance = realised_variance.saturating_add(tick_move * tick_move);
 			}
-		} else {
-			let range_orders = pool.range_orders_cache.entry(lp.clone()).or_default();
-			range_orders.insert(id, tick_range.clone());
+			if observation.block_number <= window_start {
+				return Ok(realised_variance)
+			}
+			later_tick = Some(observation.tick);
 		}
+		Err(Error::<T>::InsufficientPriceHistory.into())
+	}
 
-		let zero_change = *liquidity_change.abs() == 0;
+	fn ensure_valid_expiry_block(expiry_block: Option<BlockNumberFor<T>>) -> DispatchResult {
+		let current_block = frame_system::Pallet::<T>::block_number();
+		ensure!(
+			expiry_block.map_or(true, |expiry_block| expiry_block > current_block),
+			Error::<T>::InvalidExpiryBlock
+		);
+		Ok(())
+	}
 
-		if !zero_change || collected_fees != Default::default() {
-			Self::deposit_event(Event::<T>::RangeOrderUpdated {
-				lp: lp.clone(),
-				base_asset: asset_pair.assets().base,
-				quote_asset: asset_pair.assets().quote,
-				id,
-				tick_range,
-				size_change: {
-					if zero_change {
-						None
-					} else {
-						Some(liquidity_change.map(|liquidity| RangeOrderChange {
-							liquidity,
-							amounts: assets_change,
-						}))
-					}
+	/// Sets the expiry of a limit order after it has been updated. If the order no longer exists,
+	/// or no expiry is given, any existing expiry is removed.
+	fn update_limit_order_expiry(
+		pool: &Pool<T>,
+		order_key: LimitOrderKey<T::AccountId>,
+		expiry_block: Option<BlockNumberFor<T>>,
+	) {
+		let order_exists = pool.limit_orders_cache[order_key.side.to_sold_side().into()]
+			.get(&order_key.lp)
+			.is_some_and(|limit_orders| limit_orders.contains_key(&order_key.id));
+		match expiry_block.filter(|_| order_exists) {
+			Some(expiry_block) =>
+				if LimitOrderExpiries::<T>::get(&order_key) != Some(expiry_block) {
+					LimitOrderExpiryQueue::<T>::append(expiry_block, order_key.clone());
+					LimitOrderExpiries::<T>::insert(order_key, expiry_block);
 				},
-				liquidity_total: position_info.liquidity,
-				collected_fees,
-			});
+			None => LimitOrderExpiries::<T>::remove(order_key),
 		}
+	}
 
-		Ok(assets_change)
+	/// Closes the limit orders that expire at the current block, returning their assets to the
+	/// LPs. At most `MaxExpiredLimitOrdersPerBlock` orders are closed, the rest are deferred to the
+	/// next block.
+	fn close_expired_limit_orders(current_block: BlockNumberFor<T>) -> Weight {
+		let mut weight_used = T::DbWeight::get().reads_writes(1, 1);
+		let mut expiring_orders = LimitOrderExpiryQueue::<T>::take(current_block);
+		if expiring_orders.is_empty() {
+			return weight_used
+		}
+
+		let max_expired_orders = if T::SafeMode::get().limit_order_update_enabled {
+			T::MaxExpiredLimitOrdersPerBlock::get() as usize
+		} else {
+			0
+		};
+		if expiring_orders.len() > max_expired_orders {
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let mut deferred_orders = expiring_orders.split_off(max_expired_orders);
+			let next_block = current_block.saturating_add(1u32.into());
+			deferred_orders.extend(LimitOrderExpiryQueue::<T>::take(next_block));
+			LimitOrderExpiryQueue::<T>::insert(next_block, deferred_orders);
+		}
+
+		for order_key in expiring_orders {
+			weight_used.saturating_accrue(T::DbWeight::get().reads(1));
+			// The order's expiry may have been changed or removed since it was queued.
+			if LimitOrderExpiries::<T>::get(&order_key)
+				.is_some_and(|expiry_block| expiry_block <= current_block)
+			{
+				weight_used.saturating_accrue(T::WeightInfo::update_limit_order());
+				LimitOrderExpiries::<T>::remove(&order_key);
+				if let Err(error) = Self::close_limit_order(&order_key) {
+					log::warn!("Unable to close expired limit order {order_key:?}: {error:?}");
+				}
+			}
+		}
+
+		weight_used
 	}
 
 	#[transactional]
-	pub fn swap_with_network_fee(
-		from: any::Asset,
-		to: any::Asset,
-		input_amount: AssetAmount,
-	) -> Result<SwapOutput, DispatchError> {
-		Ok(match (from, to) {
-			(_, STABLE_ASSET) => {
-				let output = Self::take_network_fee(Self::swap_single_leg(from, to, input_amount)?);
-				SwapOutput { intermediary: None, output }
-			},
-			(STABLE_ASSET, _) => {
-				let output = Self::swap_single_leg(from, to, Self::take_network_fee(input_amount))?;
-				SwapOutput { intermediary: None, output }
-			},
-			_ => {
-				let intermediary = Self::swap_single_leg(from, STABLE_ASSET, input_amount)?;
-				let output =
-					Self::swap_single_leg(STABLE_ASSET, to, Self::take_network_fee(intermediary))?;
-				SwapOutput { intermediary: Some(intermediary), output }
-			},
+	fn close_limit_order(order_key: &LimitOrderKey<T::AccountId>) -> DispatchResult {
+		let LimitOrderKey { lp, asset_pair, side, id } = order_key;
+		Self::try_mutate_pool(*asset_pair, |asset_pair, pool| {
+			if let Some(tick) = pool.limit_orders_cache[side.to_sold_side().into()]
+				.get(lp)
+				.and_then(|limit_orders| limit_orders.get(id))
+				.cloned()
+			{
+				Self::inner_update_limit_order(
+					pool,
+					lp,
+					asset_pair,
+					*side,
+					*id,
+					tick,
+					IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
+					/* allow_noop */ false,
+				)?;
+				let assets = asset_pair.assets();
+				Self::deposit_event(Event::<T>::LimitOrderExpired {
+					lp: lp.clone(),
+					base_asset: assets.base,
+					quote_asset: assets.quote,
+					side: *side,
+					id: *id,
+					tick,
+				});
+			}
+			Ok(())
 		})
 	}
 
-	fn try_mutate_pool<
-		R,
-		E: From<pallet::Error<T>>,
-		F: FnOnce(&AssetPair, &mut Pool<T>) -> Result<R, E>,
-	>(
-		asset_pair: AssetPair,
-		f: F,
-	) -> Result<R, E> {
-		Pools::<T>::try_mutate(asset_pair, |maybe_pool| {
-			let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
-			f(&asset_pair, pool)
+	/// Applies the policies of up to `MaxAutomatedRangeOrdersPerBlock` range orders, continuing
+	/// from where the previous block stopped.
+	fn apply_range_order_policies() -> Weight {
+		let mut weight_used = T::DbWeight::get().reads(1);
+		if !T::SafeMode::get().range_order_update_enabled {
+			return weight_used
+		}
+
+		let max_orders = T::MaxAutomatedRangeOrdersPerBlock::get() as usize;
+		weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+		let policies = match RangeOrderPolicyCursor::<T>::get() {
+			Some(cursor) =>
+				RangeOrderPolicies::<T>::iter_from(RangeOrderPolicies::<T>::hashed_key_for(cursor)),
+			None => RangeOrderPolicies::<T>::iter(),
+		}
+		.take(max_orders)
+		.collect::<Vec<_>>();
+
+		// Once the end of the policies is reached, start again from the beginning next block.
+		RangeOrderPolicyCursor::<T>::set(if policies.len() < max_orders {
+			None
+		} else {
+			policies.last().map(|(order_key, _)| order_key.clone())
+		});
+
+		for (order_key, policy) in policies {
+			// Rebalancing burns and mints the order, and compounding mints into it again.
+			weight_used.saturating_accrue(T::WeightInfo::update_range_order().saturating_mul(3));
+			if let Err(error) = Self::apply_range_order_policy(&order_key, policy) {
+				log::warn!("Unable to apply policy to range order {order_key:?}: {error:?}");
+			}
+		}
+
+		weight_used
+	}
+
+	#[transactional]
+	fn apply_range_order_policy(
+		order_key: &RangeOrderKey<T::AccountId>,
+		policy: RangeOrderPolicy,
+	) -> DispatchResult {
+		let RangeOrderKey { lp, asset_pair, id } = order_key;
+		Self::try_mutate_pool(*asset_pair, |asset_pair, pool| {
+			let tick_range = if let Some(tick_range) = pool
+				.range_orders_cache
+				.get(lp)
+				.and_then(|range_orders| range_orders.get(id))
+				.cloned()
+			{
+				tick_range
+			} else {
+				RangeOrderPolicies::<T>::remove(order_key);
+				return Ok(())
+			};
+
+			let (collected, position_info) = pool
+				.pool_state
+				.range_order(&(lp.clone(), *id), tick_range.clone())
+				.map_err(|_| Error::<T>::OrderDoesNotExist)?;
+			let fees: AssetAmounts =
+				AssetsMap::<Amount>::from(collected.fees).try_map(AssetAmount::try_from)?;
+			let current_tick = pool.pool_state.current_range_order_tick();
+			let assets = asset_pair.assets();
+
+			let tick_range = match policy.rebalance_width {
+				Some(width)
+					if RangeOrderPolicy::needs_rebalancing(&tick_range, current_tick, width) =>
+				{
+					// The withdrawn assets and fees are credited to the LP, and only the withdrawn
+					// assets are then minted back, so the LP's other funds are never used.
+					let withdrawn = Self::inner_update_range_order(
+						pool,
+						lp,
+						asset_pair,
+						*id,
+						tick_range.clone(),
+						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+							liquidity: position_info.liquidity,
+						}),
+						/* allow_noop */ false,
+					)?;
+					let new_tick_range = RangeOrderPolicy::adjacent_tick_range(
+						current_tick,
+						width,
+						/* above */ withdrawn.base > 0,
+					);
+					Self::inner_update_range_order(
+						pool,
+						lp,
+						asset_pair,
+						*id,
+						new_tick_range.clone(),
+						IncreaseOrDecrease::Increase(range_orders::Size::Amount {
+							maximum: withdrawn.map(Into::into).into(),
+							minimum: Default::default(),
+						}),
+						/* allow_noop */ false,
+					)?;
+					Self::deposit_event(Event::<T>::RangeOrderRebalanced {
+						lp: lp.clone(),
+						base_asset: assets.base,
+						quote_asset: assets.quote,
+						id: *id,
+						previous_tick_range: tick_range,
+						new_tick_range: new_tick_range.clone(),
+					});
+					new_tick_range
+				},
+				_ => {
+					if policy.auto_compound && fees != Default::default() {
+						// Collect the fees into the LP's balance, so they can be minted back.
+						Self::inner_update_range_order(
+							pool,
+							lp,
+							asset_pair,
+							*id,
+							tick_range.clone(),
+							IncreaseOrDecrease::Increase(range_orders::Size::Liquidity {
+								liquidity: 0,
+							}),
+							/* allow_noop */ false,
+						)?;
+					}
+					tick_range
+				},
+			};
+
+			if policy.auto_compound && fees != Default::default() {
+				let amounts = Self::inner_update_range_order(
+					pool,
+					lp,
+					asset_pair,
+					*id,
+					tick_range,
+					IncreaseOrDecrease::Increase(range_orders::Size::Amount {
+						maximum: fees.map(Into::into).into(),
+						minimum: Default::default(),
+					}),
+					/* allow_noop */ false,
+				)?;
+				if amounts != Default::default() {
+					Self::deposit_event(Event::<T>::RangeOrderCompounded {
+						lp: lp.clone(),
+						base_asset: assets.base,
+						quote_asset: assets.quote,
+						id: *id,
+						amounts,
+					});
+				}
+			}
+
+			Ok(())
 		})
 	}
 
@@ -1632,6 +2968,129 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Returns the time-weighted average price of the pool over the last `window` blocks.
+	pub fn pool_twap(
+		base_asset: Asset,
+		quote_asset: Asset,
+		window: BlockNumberFor<T>,
+	) -> Result<PoolTwap, DispatchError> {
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+		ensure!(!window.is_zero(), Error::<T>::InvalidTwapWindow);
+
+		let current_block = frame_system::Pallet::<T>::block_number();
+		let start = Self::price_observation_at(
+			asset_pair,
+			current_block.checked_sub(&window).ok_or(Error::<T>::InsufficientPriceHistory)?,
+		)?;
+		let end = Self::price_observation_at(asset_pair, current_block)?;
+
+		let blocks: u32 = window.unique_saturated_into();
+		let tick = end
+			.tick_cumulative
+			.wrapping_sub(start.tick_cumulative)
+			.div_euclid(i64::from(blocks))
+			.clamp(i64::from(MIN_TICK), i64::from(MAX_TICK)) as Tick;
+
+		Ok(PoolTwap {
+			tick,
+			price: price_at_tick(tick).ok_or(Error::<T>::InvalidTick)?,
+			harmonic_mean_liquidity: (U256::from(blocks) << 128)
+				.checked_div(
+					end.blocks_per_liquidity_cumulative
+						.overflowing_sub(start.blocks_per_liquidity_cumulative)
+						.0,
+				)
+				.and_then(|liquidity| liquidity.try_into().ok())
+				.unwrap_or(Liquidity::MAX),
+		})
+	}
+
+	/// Returns the accumulator values as of `block_number`, derived from the latest observation
+	/// made at or before it.
+	fn price_observation_at(
+		asset_pair: AssetPair,
+		block_number: BlockNumberFor<T>,
+	) -> Result<PriceObservation<BlockNumberFor<T>>, DispatchError> {
+		let ring = PriceObservationRings::<T>::get(asset_pair)
+			.ok_or(Error::<T>::InsufficientPriceHistory)?;
+		// Observations are indexed from the oldest to the latest.
+		let observation = |index: u32| {
+			PriceObservations::<T>::get(asset_pair, (ring.latest + 1 + index) % ring.len)
+				.ok_or(Error::<T>::InsufficientPriceHistory)
+		};
+
+		let latest = observation(ring.len - 1)?;
+		if latest.block_number <= block_number {
+			return Ok(latest.advanced_to(block_number))
+		}
+		ensure!(observation(0)?.block_number <= block_number, Error::<T>::InsufficientPriceHistory);
+
+		// Binary search for the latest observation made at or before `block_number`.
+		let (mut low, mut high) = (0, ring.len - 1);
+		while high - low > 1 {
+			let middle = low + (high - low) / 2;
+			if observation(middle)?.block_number <= block_number {
+				low = middle;
+			} else {
+				high = middle;
+			}
+		}
+		Ok(observation(low)?.advanced_to(block_number))
+	}
+
+	/// Records the pool's current mid price tick and liquidity in its price oracle, unless an
+	/// observation has already been made this block.
+	fn observe_pool_price(asset_pair: AssetPair, pool: &mut Pool<T>) {
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let maybe_tick = match (
+			pool.pool_state.current_price(Order::Sell),
+			pool.pool_state.current_price(Order::Buy),
+		) {
+			(Some((_, _, sell_tick)), Some((_, _, buy_tick))) =>
+				Some((sell_tick + buy_tick).div_euclid(2)),
+			(Some((_, _, tick)), None) | (None, Some((_, _, tick))) => Some(tick),
+			(None, None) => None,
+		};
+		let liquidity = pool.pool_state.current_range_order_liquidity();
+
+		PriceObservationRings::<T>::mutate(asset_pair, |maybe_ring| {
+			let observation = match maybe_ring
+				.and_then(|ring| PriceObservations::<T>::get(asset_pair, ring.latest))
+			{
+				Some(latest) if latest.block_number >= block_number => return,
+				Some(latest) => PriceObservation {
+					tick: maybe_tick.unwrap_or(latest.tick),
+					liquidity,
+					..latest.advanced_to(block_number)
+				},
+				None => match maybe_tick {
+					Some(tick) => PriceObservation {
+						block_number,
+						tick_cumulative: 0,
+						blocks_per_liquidity_cumulative: U256::zero(),
+						tick,
+						liquidity,
+					},
+					None => return,
+				},
+			};
+
+			let ring = match maybe_ring {
+				Some(ring) => {
+					let capacity = sp_std::cmp::max(T::MaxPriceObservations::get(), 1);
+					PriceObservationRing {
+						latest: (ring.latest + 1) % capacity,
+						len: sp_std::cmp::min(ring.len + 1, capacity),
+					}
+				},
+				None => PriceObservationRing { latest: 0, len: 1 },
+			};
+			PriceObservations::<T>::insert(asset_pair, ring.latest, observation);
+			*maybe_ring = Some(ring);
+		});
+	}
+
 	pub fn required_asset_ratio_for_range_order(
 		base_asset: any::Asset,
 		quote_asset: any::Asset,
@@ -1799,14 +3258,34 @@ impl<T: Config> Pallet<T> {
 		base_asset: any::Asset,
 		quote_asset: any::Asset,
 	) -> Result<PoolInfo, DispatchError> {
-		let pool = Pools::<T>::get(AssetPair::try_new::<T>(base_asset, quote_asset)?)
-			.ok_or(Error::<T>::PoolDoesNotExist)?;
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
 		Ok(PoolInfo {
 			limit_order_fee_hundredth_pips: pool.pool_state.limit_order_fee(),
 			range_order_fee_hundredth_pips: pool.pool_state.range_order_fee(),
+			dynamic_fee: DynamicPoolFees::<T>::get(asset_pair),
 		})
 	}
 
+	pub fn pool_stats(
+		base_asset: any::Asset,
+		quote_asset: any::Asset,
+	) -> Result<PoolStats, DispatchError> {
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+		Ok(PoolStatistics::<T>::get(asset_pair))
+	}
+
+	/// Returns the lifetime fee earnings of each of the LP's positions, open or closed.
+	pub fn lp_stats(lp: &T::AccountId) -> Vec<LpPositionStats> {
+		LpPositionFeeEarnings::<T>::iter_prefix(lp)
+			.map(|((asset_pair, position), fees_earned)| {
+				let AssetsMap { base: base_asset, quote: quote_asset } = asset_pair.assets();
+				LpPositionStats { base_asset, quote_asset, position, fees_earned }
+			})
+			.collect()
+	}
+
 	pub fn pool_liquidity(
 		base_asset: any::Asset,
 		quote_asset: any::Asset,
@@ -1836,8 +3315,8 @@ impl<T: Config> Pallet<T> {
 		quote_asset: any::Asset,
 		option_lp: Option<T::AccountId>,
 	) -> Result<PoolOrders<T>, DispatchError> {
-		let pool = Pools::<T>::get(AssetPair::try_new::<T>(base_asset, quote_asset)?)
-			.ok_or(Error::<T>::PoolDoesNotExist)?;
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
 		let option_lp = option_lp.as_ref();
 		Ok(PoolOrders {
 			limit_orders: AskBidMap::from_sell_map(
@@ -1875,6 +3354,12 @@ impl<T: Config> Pallet<T> {
 								sell_amount: position_info.amount,
 								fees_earned: collected.accumulative_fees,
 								original_sell_amount: collected.original_amount,
+								expiry_block: LimitOrderExpiries::<T>::get(LimitOrderKey {
+									lp: lp.clone(),
+									asset_pair,
+									side: asset.sell_order(),
+									id,
+								}),
 							})
 						}
 					})
@@ -1952,32 +3437,54 @@ impl<T: Config> Pallet<T> {
 		position_info: PositionInfo,
 		amount_change: IncreaseOrDecrease<AssetAmount>,
 	) -> DispatchResult {
-		let collected_fees: AssetAmount = collected.fees.try_into()?;
+		Self::credit_limit_order_earnings(
+			asset_pair,
+			lp,
+			order,
+			id,
+			tick,
+			collected.fees.try_into()?,
+			collected.bought_amount.try_into()?,
+			amount_change,
+			position_info.amount.try_into()?,
+		)?;
+		Self::update_limit_orders_cache(pool, lp, order, id, tick, &position_info);
+		Ok(())
+	}
+
+	/// Credits the fees and bought amount collected from a limit order to its LP.
+	#[allow(clippy::too_many_arguments)]
+	fn credit_limit_order_earnings(
+		asset_pair: &AssetPair,
+		lp: &T::AccountId,
+		order: Order,
+		id: OrderId,
+		tick: Tick,
+		collected_fees: AssetAmount,
+		bought_amount: AssetAmount,
+		amount_change: IncreaseOrDecrease<AssetAmount>,
+		sell_amount_total: AssetAmount,
+	) -> DispatchResult {
 		T::LpBalance::try_credit_account(
 			lp,
 			asset_pair.assets()[(!order.to_sold_side()).into()],
 			collected_fees,
 		)?;
+		let mut fees_earned = AssetAmounts::default();
+		fees_earned[(!order.to_sold_side()).into()] = collected_fees;
+		Self::record_lp_fees(
+			lp,
+			asset_pair,
+			LpPosition::LimitOrder { side: order, id },
+			fees_earned,
+		);
 
-		let bought_amount: AssetAmount = collected.bought_amount.try_into()?;
 		T::LpBalance::try_credit_account(
 			lp,
 			asset_pair.assets()[(!order.to_sold_side()).into()],
 			bought_amount,
 		)?;
 
-		let limit_orders = &mut pool.limit_orders_cache[order.to_sold_side().into()];
-		if position_info.amount.is_zero() {
-			if let Some(lp_limit_orders) = limit_orders.get_mut(lp) {
-				lp_limit_orders.remove(&id);
-				if lp_limit_orders.is_empty() {
-					limit_orders.remove(lp);
-				}
-			}
-		} else {
-			limit_orders.entry(lp.clone()).or_default().insert(id, tick);
-		}
-
 		let zero_change = *amount_change.abs() == 0;
 
 		if !zero_change ||
@@ -1998,13 +3505,35 @@ impl<T: Config> Pallet<T> {
 						Some(amount_change)
 					}
 				},
-				sell_amount_total: position_info.amount.try_into()?,
+				sell_amount_total,
 				collected_fees,
 				bought_amount,
 			});
 		}
 		Ok(())
 	}
+
+	/// Records an open limit order in the pool's cache, or removes it if it has been closed.
+	fn update_limit_orders_cache(
+		pool: &mut Pool<T>,
+		lp: &T::AccountId,
+		order: Order,
+		id: OrderId,
+		tick: Tick,
+		position_info: &PositionInfo,
+	) {
+		let limit_orders = &mut pool.limit_orders_cache[order.to_sold_side().into()];
+		if position_info.amount.is_zero() {
+			if let Some(lp_limit_orders) = limit_orders.get_mut(lp) {
+				lp_limit_orders.remove(&id);
+				if lp_limit_orders.is_empty() {
+					limit_orders.remove(lp);
+				}
+			}
+		} else {
+			limit_orders.entry(lp.clone()).or_default().insert(id, tick);
+		}
+	}
 }
 
 impl<T: Config> cf_traits::AssetConverter for Pallet<T> {
@@ -2110,6 +3639,7 @@ impl<T: Config> cf_traits::AssetConverter for Pallet<T> {
 
 pub mod utilities {
 	use super::*;
+	use frame_support::sp_runtime::PerThing;
 
 	pub fn calculate_network_fee(
 		fee_percentage: Permill,
@@ -2118,4 +3648,14 @@ pub mod utilities {
 		let fee = fee_percentage * input;
 		(input - fee, fee)
 	}
+
+	/// Returns the input amount required for `output` to remain after the network fee is taken,
+	/// along with the fee.
+	pub fn calculate_network_fee_exact_output(
+		fee_percentage: Permill,
+		output: AssetAmount,
+	) -> (AssetAmount, AssetAmount) {
+		let input = fee_percentage.left_from_one().saturating_reciprocal_mul_ceil(output);
+		(input, input.saturating_sub(output))
+	}
 }
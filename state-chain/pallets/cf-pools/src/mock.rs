@@ -3,13 +3,14 @@ use cf_primitives::{Asset, AssetAmount};
 use cf_traits::{
 	impl_mock_chainflip, impl_mock_runtime_safe_mode, AccountRoleRegistry, LpBalanceApi,
 };
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::ConstU32};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	DispatchResult, Permill,
+	DispatchError, DispatchResult, Permill,
 };
+use sp_std::collections::btree_map::BTreeMap;
 
 type AccountId = u64;
 
@@ -70,7 +71,21 @@ parameter_types! {
 	pub static AliceDebitedUsdc: AssetAmount = Default::default();
 	pub static BobDebitedEth: AssetAmount = Default::default();
 	pub static BobDebitedUsdc: AssetAmount = Default::default();
+	pub static LpBalances: BTreeMap<(AccountId, Asset), AssetAmount> = [ALICE, BOB]
+		.into_iter()
+		.flat_map(|lp| [(lp, Asset::Eth), (lp, Asset::Usdc)])
+		.map(|key| (key, INITIAL_LP_BALANCE))
+		.collect();
 }
+
+/// The balance of each asset that ALICE and BOB start with. Other accounts' balances aren't
+/// tracked.
+pub const INITIAL_LP_BALANCE: AssetAmount = AssetAmount::MAX / 2;
+
+pub fn lp_balance(who: AccountId, asset: Asset) -> AssetAmount {
+	LpBalances::get().get(&(who, asset)).copied().unwrap_or_default()
+}
+
 pub struct MockBalance;
 impl LpBalanceApi for MockBalance {
 	type AccountId = AccountId;
@@ -102,6 +117,11 @@ impl LpBalanceApi for MockBalance {
 			(BOB, Asset::Usdc) => BobCollectedUsdc::set(BobCollectedUsdc::get() + amount),
 			_ => (),
 		}
+		LpBalances::mutate(|balances| {
+			if let Some(balance) = balances.get_mut(&(*who, asset)) {
+				*balance = balance.saturating_add(amount);
+			}
+		});
 		Ok(())
 	}
 
@@ -110,6 +130,14 @@ impl LpBalanceApi for MockBalance {
 		asset: cf_primitives::Asset,
 		amount: cf_primitives::AssetAmount,
 	) -> sp_runtime::DispatchResult {
+		LpBalances::mutate(|balances| {
+			if let Some(balance) = balances.get_mut(&(*who, asset)) {
+				*balance = balance
+					.checked_sub(amount)
+					.ok_or(DispatchError::Other("Insufficient balance"))?;
+			}
+			Ok::<_, DispatchError>(())
+		})?;
 		match (*who, asset) {
 			(ALICE, Asset::Eth) => AliceDebitedEth::set(AliceDebitedEth::get() + amount),
 			(ALICE, Asset::Usdc) => AliceDebitedUsdc::set(AliceDebitedUsdc::get() + amount),
@@ -130,6 +158,10 @@ impl pallet_cf_pools::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type LpBalance = MockBalance;
 	type NetworkFee = NetworkFee;
+	type MaxPriceObservations = ConstU32<100>;
+	type MaxOrdersPerBatch = ConstU32<10>;
+	type MaxExpiredLimitOrdersPerBlock = ConstU32<2>;
+	type MaxAutomatedRangeOrdersPerBlock = ConstU32<2>;
 	type SafeMode = MockRuntimeSafeMode;
 	type WeightInfo = ();
 }
@@ -1,13 +1,21 @@
 use crate::{
 	self as pallet_cf_pools, mock::*, utilities, AskBidMap, AssetAmounts, AssetPair, AssetsMap,
-	CollectedNetworkFee, Error, Event, FlipBuyInterval, FlipToBurn, LimitOrder, PoolInfo,
-	PoolOrders, Pools, RangeOrder, RangeOrderSize, ScheduledLimitOrderUpdates, STABLE_ASSET,
+	CollectedNetworkFee, DeferredLimitOrderCollections, DynamicFeeConfig, Error, Event,
+	FlipBuyInterval, FlipToBurn, IncreaseOrDecrease, LimitOrder, LpPosition, LpPositionStats,
+	OrderOperation, PoolInfo, PoolOrders, PoolStats, Pools, PriceObservationRings,
+	PriceObservations, RangeOrder, RangeOrderPolicies, RangeOrderPolicy, RangeOrderSize,
+	ScheduledLimitOrderUpdates, STABLE_ASSET,
+};
+use cf_amm::common::{
+	price_at_tick, tick_at_price, Order, Tick, MAX_TICK, MIN_TICK, PRICE_FRACTIONAL_BITS,
 };
-use cf_amm::common::{price_at_tick, tick_at_price, Order, Tick, PRICE_FRACTIONAL_BITS};
 use cf_primitives::{chains::assets::any::Asset, AssetAmount, SwapOutput};
 use cf_test_utilities::{assert_events_match, assert_has_event, last_event};
-use cf_traits::AssetConverter;
-use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use cf_traits::{AssetConverter, PriceOracle, SwappingApi};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Get, Hooks},
+};
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_core::U256;
 use sp_runtime::Permill;
@@ -271,6 +279,68 @@ fn test_network_fee_calculation() {
 	});
 }
 
+#[test]
+fn test_network_fee_calculation_exact_output() {
+	new_test_ext().execute_with(|| {
+		// Show we can never overflow and panic
+		utilities::calculate_network_fee_exact_output(Permill::from_percent(100), AssetAmount::MAX);
+		// 200 bps (2%) of 100 = 2
+		assert_eq!(
+			utilities::calculate_network_fee_exact_output(Permill::from_percent(2u32), 98),
+			(100, 2)
+		);
+		// 10 bps = 0,1% of 3000 = 3
+		assert_eq!(
+			utilities::calculate_network_fee_exact_output(
+				Permill::from_rational(1u32, 1000u32),
+				2997
+			),
+			(3000, 3)
+		);
+	});
+}
+
+#[test]
+fn can_swap_exact_output() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			Order::Sell,
+			0,
+			Some(0),
+			1_000,
+		));
+
+		// At a price of 1 with no fees, the required input equals the output.
+		assert_eq!(
+			LiquidityPools::swap_single_leg_exact_output(STABLE_ASSET, FLIP, 400),
+			Ok((400, 400))
+		);
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::AssetSwapped {
+			from: STABLE_ASSET,
+			to: FLIP,
+			input_amount: 400,
+			output_amount: 400,
+		}));
+
+		// Only 600 FLIP remain in the pool.
+		assert_noop!(
+			LiquidityPools::swap_single_leg_exact_output(STABLE_ASSET, FLIP, 601),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
+
 #[test]
 fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 	new_test_ext().execute_with(|| {
@@ -289,6 +359,7 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 			Ok(PoolInfo {
 				limit_order_fee_hundredth_pips: old_fee,
 				range_order_fee_hundredth_pips: old_fee,
+				dynamic_fee: None,
 			})
 		);
 
@@ -339,7 +410,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 5000u128.into(),
 						fees_earned: 0.into(),
-						original_sell_amount: 5000u128.into()
+						original_sell_amount: 5000u128.into(),
+						expiry_block: None,
 					}],
 					bids: vec![LimitOrder {
 						lp: ALICE,
@@ -347,7 +419,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 1000.into(),
 						fees_earned: 0.into(),
-						original_sell_amount: 1000u128.into()
+						original_sell_amount: 1000u128.into(),
+						expiry_block: None,
 					}]
 				},
 				range_orders: vec![]
@@ -363,7 +436,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 10000u128.into(),
 						fees_earned: 0.into(),
-						original_sell_amount: 10000u128.into()
+						original_sell_amount: 10000u128.into(),
+						expiry_block: None,
 					}],
 					bids: vec![LimitOrder {
 						lp: BOB,
@@ -371,7 +445,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 10000.into(),
 						fees_earned: 0.into(),
-						original_sell_amount: 10000u128.into()
+						original_sell_amount: 10000u128.into(),
+						expiry_block: None,
 					}]
 				},
 				range_orders: vec![]
@@ -409,6 +484,7 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 			Ok(PoolInfo {
 				limit_order_fee_hundredth_pips: new_fee,
 				range_order_fee_hundredth_pips: new_fee,
+				dynamic_fee: None,
 			})
 		);
 		System::assert_has_event(RuntimeEvent::LiquidityPools(Event::<Test>::PoolFeeSet {
@@ -430,7 +506,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 3004.into(),
 						fees_earned: 1330.into(),
-						original_sell_amount: 5000.into()
+						original_sell_amount: 5000.into(),
+						expiry_block: None,
 					}],
 					bids: vec![LimitOrder {
 						lp: ALICE,
@@ -438,7 +515,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 454.into(),
 						fees_earned: 363.into(),
-						original_sell_amount: 1000.into()
+						original_sell_amount: 1000.into(),
+						expiry_block: None,
 					}]
 				},
 				range_orders: vec![]
@@ -454,7 +532,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 6_008u128.into(),
 						fees_earned: 2660.into(),
-						original_sell_amount: 10000.into()
+						original_sell_amount: 10000.into(),
+						expiry_block: None,
 					}],
 					bids: vec![LimitOrder {
 						lp: BOB,
@@ -462,7 +541,8 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 						tick: 0,
 						sell_amount: 4_545.into(),
 						fees_earned: 3636.into(),
-						original_sell_amount: 10000u128.into()
+						original_sell_amount: 10000u128.into(),
+						expiry_block: None,
 					}]
 				},
 				range_orders: vec![]
@@ -543,7 +623,8 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 						tick: 0,
 						sell_amount: 100.into(),
 						fees_earned: 0.into(),
-						original_sell_amount: 100.into()
+						original_sell_amount: 100.into(),
+						expiry_block: None,
 					}],
 					bids: vec![]
 				},
@@ -639,6 +720,7 @@ fn update_pool_liquidity_fee_collects_fees_for_range_order() {
 			Ok(PoolInfo {
 				limit_order_fee_hundredth_pips: old_fee,
 				range_order_fee_hundredth_pips: old_fee,
+				dynamic_fee: None,
 			})
 		);
 
@@ -926,6 +1008,7 @@ fn can_get_all_pool_orders() {
 							sell_amount: 500_000.into(),
 							fees_earned: 0.into(),
 							original_sell_amount: 500_000.into(),
+							expiry_block: None,
 						},
 						LimitOrder {
 							lp: ALICE,
@@ -934,6 +1017,7 @@ fn can_get_all_pool_orders() {
 							sell_amount: 600_000.into(),
 							fees_earned: 0.into(),
 							original_sell_amount: 600_000.into(),
+							expiry_block: None,
 						},
 						LimitOrder {
 							lp: ALICE,
@@ -942,6 +1026,7 @@ fn can_get_all_pool_orders() {
 							sell_amount: 700_000.into(),
 							fees_earned: 0.into(),
 							original_sell_amount: 700_000.into(),
+							expiry_block: None,
 						}
 					],
 					bids: vec![LimitOrder {
@@ -951,6 +1036,7 @@ fn can_get_all_pool_orders() {
 						sell_amount: 800_000.into(),
 						fees_earned: 0.into(),
 						original_sell_amount: 800_000.into(),
+						expiry_block: None,
 					}]
 				},
 				range_orders: vec![
@@ -1062,3 +1148,833 @@ fn asset_conversion() {
 		);
 	});
 }
+
+#[test]
+fn pool_twap_resists_intra_block_price_changes() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			0,
+			Some(-100_000..100_000),
+			RangeOrderSize::AssetAmounts {
+				maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+				minimum: AssetAmounts { base: 900_000, quote: 900_000 },
+			}
+		));
+
+		// There is no history before the pool was created.
+		assert_noop!(
+			LiquidityPools::pool_twap(FLIP, STABLE_ASSET, 1),
+			Error::<Test>::InsufficientPriceHistory
+		);
+		assert_noop!(
+			LiquidityPools::pool_twap(FLIP, STABLE_ASSET, 0),
+			Error::<Test>::InvalidTwapWindow
+		);
+
+		for block in 2..=10 {
+			System::set_block_number(block);
+			LiquidityPools::on_initialize(block);
+		}
+		let twap = LiquidityPools::pool_twap(FLIP, STABLE_ASSET, 5).unwrap();
+		assert_eq!(twap.tick, 0);
+		assert_eq!(twap.price, price_at_tick(0).unwrap());
+		let liquidity = Pools::<Test>::get(AssetPair::new(FLIP, STABLE_ASSET).unwrap())
+			.unwrap()
+			.pool_state
+			.current_range_order_liquidity();
+		assert!((liquidity..=liquidity + 1).contains(&twap.harmonic_mean_liquidity));
+
+		// Moving the price has no effect on the TWAP within the same block.
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, FLIP, 500_000));
+		let moved_tick = LiquidityPools::current_price(FLIP, STABLE_ASSET).unwrap().tick;
+		assert!(moved_tick > 0);
+		assert_eq!(LiquidityPools::pool_twap(FLIP, STABLE_ASSET, 5).unwrap().tick, 0);
+
+		// In later blocks, the new price is weighted by the number of blocks it has held for.
+		for block in 11..=15 {
+			System::set_block_number(block);
+			LiquidityPools::on_initialize(block);
+		}
+		assert_eq!(
+			LiquidityPools::pool_twap(FLIP, STABLE_ASSET, 5).unwrap().tick,
+			(4 * moved_tick).div_euclid(5)
+		);
+		assert_eq!(LiquidityPools::pool_twap(FLIP, STABLE_ASSET, 4).unwrap().tick, moved_tick);
+
+		// Prices can be converted using the TWAP.
+		assert_eq!(LiquidityPools::convert_at_twap(FLIP, FLIP, 1_000, 4), Some(1_000));
+		assert_eq!(
+			LiquidityPools::convert_at_twap(FLIP, STABLE_ASSET, 1_000_000, 4),
+			Some(
+				cf_amm::common::mul_div_floor(
+					U256::from(1_000_000u128),
+					price_at_tick(moved_tick).unwrap(),
+					U256::one() << PRICE_FRACTIONAL_BITS
+				)
+				.as_u128()
+			)
+		);
+		assert_eq!(LiquidityPools::convert_at_twap(Asset::Eth, STABLE_ASSET, 1_000, 4), None);
+	});
+}
+
+#[test]
+fn price_observations_are_bounded() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		let max_observations: u32 = <Test as crate::Config>::MaxPriceObservations::get();
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(100).unwrap(),
+		));
+		let tick = LiquidityPools::current_price(FLIP, STABLE_ASSET).unwrap().tick;
+
+		let last_block = BlockNumberFor::<Test>::from(max_observations) + 50;
+		for block in 2..=last_block {
+			System::set_block_number(block);
+			LiquidityPools::on_initialize(block);
+		}
+
+		let asset_pair = AssetPair::new(FLIP, STABLE_ASSET).unwrap();
+		assert_eq!(
+			PriceObservations::<Test>::iter_prefix(asset_pair).count(),
+			max_observations as usize
+		);
+		assert_eq!(PriceObservationRings::<Test>::get(asset_pair).unwrap().len, max_observations);
+
+		// Only the most recent observations are available.
+		let window = BlockNumberFor::<Test>::from(max_observations) - 1;
+		assert_eq!(LiquidityPools::pool_twap(FLIP, STABLE_ASSET, window).unwrap().tick, tick);
+		assert_noop!(
+			LiquidityPools::pool_twap(FLIP, STABLE_ASSET, window + 1),
+			Error::<Test>::InsufficientPriceHistory
+		);
+	});
+}
+
+#[test]
+fn can_batch_orders_across_pools() {
+	new_test_ext().execute_with(|| {
+		for base_asset in [Asset::Eth, Asset::Flip] {
+			assert_ok!(LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				base_asset,
+				STABLE_ASSET,
+				Default::default(),
+				price_at_tick(0).unwrap(),
+			));
+		}
+
+		assert_ok!(LiquidityPools::batch_orders(
+			RuntimeOrigin::signed(ALICE),
+			vec![
+				OrderOperation::SetRangeOrder {
+					base_asset: Asset::Eth,
+					quote_asset: STABLE_ASSET,
+					id: 0,
+					option_tick_range: Some(-100..100),
+					size: RangeOrderSize::Liquidity { liquidity: 100_000 },
+				},
+				OrderOperation::SetLimitOrder {
+					base_asset: Asset::Flip,
+					quote_asset: STABLE_ASSET,
+					side: Order::Sell,
+					id: 0,
+					option_tick: Some(100),
+					sell_amount: 1_000,
+					expiry_block: None,
+				},
+				// Operations are applied in order, so later operations can move earlier orders.
+				OrderOperation::UpdateLimitOrder {
+					base_asset: Asset::Flip,
+					quote_asset: STABLE_ASSET,
+					side: Order::Sell,
+					id: 0,
+					option_tick: Some(200),
+					amount_change: IncreaseOrDecrease::Decrease(500),
+					expiry_block: None,
+				},
+			]
+			.try_into()
+			.unwrap(),
+		));
+
+		let eth_pool =
+			Pools::<Test>::get(AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap()).unwrap();
+		assert_eq!(eth_pool.range_orders_cache.get(&ALICE).unwrap().get(&0), Some(&(-100..100)));
+		let flip_orders = LiquidityPools::pool_orders(Asset::Flip, STABLE_ASSET, Some(ALICE))
+			.unwrap()
+			.limit_orders
+			.asks;
+		assert_eq!(flip_orders.len(), 1);
+		assert_eq!((flip_orders[0].tick, flip_orders[0].sell_amount), (200, 500.into()));
+	});
+}
+
+#[test]
+fn batched_orders_are_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+
+		// The second operation fails as the pool doesn't exist, so the first is not applied either.
+		assert_noop!(
+			LiquidityPools::batch_orders(
+				RuntimeOrigin::signed(ALICE),
+				vec![
+					OrderOperation::SetLimitOrder {
+						base_asset: Asset::Flip,
+						quote_asset: STABLE_ASSET,
+						side: Order::Sell,
+						id: 0,
+						option_tick: Some(100),
+						sell_amount: 1_000,
+						expiry_block: None,
+					},
+					OrderOperation::SetLimitOrder {
+						base_asset: Asset::Eth,
+						quote_asset: STABLE_ASSET,
+						side: Order::Sell,
+						id: 0,
+						option_tick: Some(100),
+						sell_amount: 1_000,
+						expiry_block: None,
+					},
+				]
+				.try_into()
+				.unwrap(),
+			),
+			Error::<Test>::PoolDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn limit_orders_are_closed_at_their_expiry_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+
+		assert_noop!(
+			LiquidityPools::set_limit_order_with_expiry(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				Order::Sell,
+				0,
+				Some(0),
+				1_000,
+				Some(System::block_number()),
+			),
+			Error::<Test>::InvalidExpiryBlock
+		);
+
+		assert_ok!(LiquidityPools::set_limit_order_with_expiry(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			Order::Sell,
+			0,
+			Some(0),
+			1_000,
+			Some(5),
+		));
+		let asks = LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE))
+			.unwrap()
+			.limit_orders
+			.asks;
+		assert_eq!(asks.len(), 1);
+		assert_eq!(asks[0].expiry_block, Some(5));
+		assert_eq!(AliceCollectedEth::get(), 0);
+
+		LiquidityPools::on_initialize(4);
+		assert_eq!(
+			LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE))
+				.unwrap()
+				.limit_orders
+				.asks
+				.len(),
+			1
+		);
+
+		LiquidityPools::on_initialize(5);
+		assert!(LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE))
+			.unwrap()
+			.limit_orders
+			.asks
+			.is_empty());
+		assert_eq!(AliceCollectedEth::get(), 1_000);
+		assert_has_event::<Test>(RuntimeEvent::LiquidityPools(Event::<Test>::LimitOrderExpired {
+			lp: ALICE,
+			base_asset: Asset::Eth,
+			quote_asset: STABLE_ASSET,
+			side: Order::Sell,
+			id: 0,
+			tick: 0,
+		}));
+	});
+}
+
+#[test]
+fn limit_order_expiries_are_updated_and_rate_limited() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+		for id in 0..4 {
+			assert_ok!(LiquidityPools::set_limit_order_with_expiry(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				Order::Sell,
+				id,
+				Some(0),
+				100,
+				Some(5),
+			));
+		}
+
+		// Updating an order without an expiry keeps its existing expiry, even if it moves tick.
+		assert_ok!(LiquidityPools::update_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			Order::Sell,
+			0,
+			Some(10),
+			IncreaseOrDecrease::Increase(100),
+		));
+		// Setting an order without an expiry removes its expiry.
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			Order::Sell,
+			3,
+			Some(0),
+			100,
+		));
+
+		let open_order_ids = || {
+			LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE))
+				.unwrap()
+				.limit_orders
+				.asks
+				.into_iter()
+				.map(|order| order.id.as_u64())
+				.collect::<Vec<_>>()
+		};
+
+		// At most two expired orders are closed per block, the rest are closed in the next block.
+		LiquidityPools::on_initialize(5);
+		assert_eq!(open_order_ids().len(), 2);
+		LiquidityPools::on_initialize(6);
+		assert_eq!(open_order_ids(), vec![3]);
+	});
+}
+
+#[test]
+fn dynamic_fee_scales_with_realised_volatility() {
+	let dynamic_fee = DynamicFeeConfig {
+		min_fee_hundredth_pips: 100,
+		max_fee_hundredth_pips: 1_100,
+		window: 5,
+		max_fee_volatility: 10,
+	};
+	assert_eq!(dynamic_fee.fee_for_realised_variance(0), 100);
+	assert_eq!(dynamic_fee.fee_for_realised_variance(25), 600);
+	assert_eq!(dynamic_fee.fee_for_realised_variance(100), 1_100);
+	assert_eq!(dynamic_fee.fee_for_realised_variance(1_000_000), 1_100);
+}
+
+#[test]
+fn pool_fees_follow_price_volatility() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			0,
+			Some(-100_000..100_000),
+			RangeOrderSize::AssetAmounts {
+				maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+				minimum: AssetAmounts { base: 900_000, quote: 900_000 },
+			}
+		));
+
+		let dynamic_fee = DynamicFeeConfig {
+			min_fee_hundredth_pips: 1_000,
+			max_fee_hundredth_pips: 101_000,
+			window: 5,
+			max_fee_volatility: 100_000,
+		};
+		for invalid_dynamic_fee in [
+			DynamicFeeConfig { min_fee_hundredth_pips: 200_000, ..dynamic_fee },
+			DynamicFeeConfig { window: 0, ..dynamic_fee },
+			DynamicFeeConfig { max_fee_volatility: 0, ..dynamic_fee },
+		] {
+			assert_noop!(
+				LiquidityPools::set_dynamic_pool_fees(
+					RuntimeOrigin::root(),
+					FLIP,
+					STABLE_ASSET,
+					Some(invalid_dynamic_fee),
+				),
+				Error::<Test>::InvalidDynamicFeeConfig
+			);
+		}
+		assert_ok!(LiquidityPools::set_dynamic_pool_fees(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			Some(dynamic_fee),
+		));
+		let asset_pair = AssetPair::new(FLIP, STABLE_ASSET).unwrap();
+		let pool_fee = || LiquidityPools::pool_info(FLIP, STABLE_ASSET).unwrap();
+		let go_to_block = |block| {
+			System::set_block_number(block);
+			LiquidityPools::on_initialize(block);
+		};
+
+		// The fee is unchanged until there is enough price history to cover a window, then set to
+		// the minimum as the price hasn't moved.
+		for block in 2..=5 {
+			go_to_block(block);
+		}
+		assert_eq!(pool_fee().limit_order_fee_hundredth_pips, 0);
+		go_to_block(6);
+		go_to_block(7);
+		assert_eq!(
+			pool_fee(),
+			PoolInfo {
+				limit_order_fee_hundredth_pips: 1_000,
+				range_order_fee_hundredth_pips: 1_000,
+				dynamic_fee: Some(dynamic_fee),
+			}
+		);
+		System::assert_has_event(RuntimeEvent::LiquidityPools(Event::DynamicPoolFeeSet {
+			base_asset: FLIP,
+			quote_asset: STABLE_ASSET,
+			fee_hundredth_pips: 1_000,
+			realised_variance: 0,
+		}));
+
+		// Once the price moves, the fee rises in the next block. The earnings of the limit order
+		// are collected, and credited in the following block.
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(BOB),
+			FLIP,
+			STABLE_ASSET,
+			Order::Sell,
+			0,
+			Some(0),
+			100_000,
+		));
+		let bob_balance = lp_balance(BOB, STABLE_ASSET);
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, FLIP, 500_000));
+		go_to_block(8);
+		let volatile_fee = pool_fee().limit_order_fee_hundredth_pips;
+		assert!(volatile_fee > 1_000 && volatile_fee < 101_000);
+		assert_eq!(pool_fee().range_order_fee_hundredth_pips, volatile_fee);
+		assert!(DeferredLimitOrderCollections::<Test>::contains_key(asset_pair));
+		assert_eq!(lp_balance(BOB, STABLE_ASSET), bob_balance);
+		go_to_block(9);
+		assert!(!DeferredLimitOrderCollections::<Test>::contains_key(asset_pair));
+		assert!(lp_balance(BOB, STABLE_ASSET) > bob_balance);
+
+		// And falls back once the price has been stable for the whole window.
+		go_to_block(12);
+		assert_eq!(pool_fee().limit_order_fee_hundredth_pips, volatile_fee);
+		go_to_block(13);
+		assert_eq!(pool_fee().limit_order_fee_hundredth_pips, 1_000);
+
+		// Price moves that cancel out within the window still count towards the volatility.
+		go_to_block(14);
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, FLIP, 500_000));
+		go_to_block(15);
+		assert_ok!(LiquidityPools::swap_single_leg(FLIP, STABLE_ASSET, 500_000));
+		go_to_block(16);
+		assert!(pool_fee().limit_order_fee_hundredth_pips > 1_000);
+
+		// Disabling dynamic fees keeps the current fee.
+		let current_fee = pool_fee().limit_order_fee_hundredth_pips;
+		assert_ok!(LiquidityPools::set_dynamic_pool_fees(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			None,
+		));
+		assert_ok!(LiquidityPools::swap_single_leg(FLIP, STABLE_ASSET, 500_000));
+		for block in 17..=22 {
+			go_to_block(block);
+		}
+		assert_eq!(
+			pool_fee(),
+			PoolInfo {
+				limit_order_fee_hundredth_pips: current_fee,
+				range_order_fee_hundredth_pips: current_fee,
+				dynamic_fee: None,
+			}
+		);
+	});
+}
+
+#[test]
+fn pool_and_lp_stats_accumulate_swap_volume_and_fees() {
+	new_test_ext().execute_with(|| {
+		let range = -100..100;
+		assert_noop!(
+			LiquidityPools::pool_stats(Asset::Eth, STABLE_ASSET),
+			Error::<Test>::PoolDoesNotExist
+		);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			400_000,
+			price_at_tick(0).unwrap(),
+		));
+		assert_eq!(LiquidityPools::pool_stats(Asset::Eth, STABLE_ASSET), Ok(Default::default()));
+
+		for lp in [ALICE, BOB] {
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(lp),
+				Asset::Eth,
+				STABLE_ASSET,
+				0,
+				Some(range.clone()),
+				RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+			));
+		}
+
+		assert_ok!(LiquidityPools::swap_with_network_fee(STABLE_ASSET, Asset::Eth, 5_000));
+		assert_ok!(LiquidityPools::swap_with_network_fee(Asset::Eth, STABLE_ASSET, 5_000));
+
+		// The network fee is taken from the input of the first swap, and the output of the second.
+		assert_eq!(
+			LiquidityPools::pool_stats(Asset::Eth, STABLE_ASSET),
+			Ok(PoolStats {
+				volume: AssetsMap { base: 5_000, quote: 4_990 },
+				limit_order_fees: AssetsMap { base: 0, quote: 0 },
+				range_order_fees: AssetsMap { base: 2_000, quote: 1_996 },
+				network_fee: CollectedNetworkFee::<Test>::get(),
+			})
+		);
+
+		// Fee earnings are recorded as they are collected, and kept once the position is closed.
+		assert!(LiquidityPools::lp_stats(&ALICE).is_empty());
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(range),
+			RangeOrderSize::Liquidity { liquidity: 0 },
+		));
+		assert_eq!(
+			LiquidityPools::lp_stats(&ALICE),
+			vec![LpPositionStats {
+				base_asset: Asset::Eth,
+				quote_asset: STABLE_ASSET,
+				position: LpPosition::RangeOrder { id: 0 },
+				fees_earned: AssetsMap { base: 999, quote: 997 },
+			}]
+		);
+		assert!(LiquidityPools::lp_stats(&BOB).is_empty());
+	});
+}
+
+#[test]
+fn network_fee_of_two_leg_swaps_is_split_between_both_pools() {
+	new_test_ext().execute_with(|| {
+		for asset in [Asset::Eth, Asset::Flip] {
+			assert_ok!(LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				asset,
+				STABLE_ASSET,
+				0,
+				price_at_tick(0).unwrap(),
+			));
+		}
+
+		assert_eq!(LiquidityPools::take_network_fee(Asset::Flip, Asset::Eth, 1_500), 1_497);
+		assert_eq!(LiquidityPools::pool_stats(Asset::Flip, STABLE_ASSET).unwrap().network_fee, 2);
+		assert_eq!(LiquidityPools::pool_stats(Asset::Eth, STABLE_ASSET).unwrap().network_fee, 1);
+
+		assert_eq!(LiquidityPools::take_network_fee(Asset::Eth, STABLE_ASSET, 1_500), 1_497);
+		assert_eq!(LiquidityPools::pool_stats(Asset::Flip, STABLE_ASSET).unwrap().network_fee, 2);
+		assert_eq!(LiquidityPools::pool_stats(Asset::Eth, STABLE_ASSET).unwrap().network_fee, 4);
+	});
+}
+
+#[test]
+fn range_order_policies_are_validated() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		let policy = RangeOrderPolicy { auto_compound: true, rebalance_width: Some(200) };
+
+		assert_noop!(
+			LiquidityPools::set_range_order_policy(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				0,
+				Some(policy),
+			),
+			Error::<Test>::OrderDoesNotExist
+		);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		for rebalance_width in [0, MAX_TICK.abs_diff(MIN_TICK) + 1] {
+			assert_noop!(
+				LiquidityPools::set_range_order_policy(
+					RuntimeOrigin::signed(ALICE),
+					Asset::Eth,
+					STABLE_ASSET,
+					0,
+					Some(RangeOrderPolicy { rebalance_width: Some(rebalance_width), ..policy }),
+				),
+				Error::<Test>::InvalidRebalanceWidth
+			);
+		}
+
+		assert_ok!(LiquidityPools::set_range_order_policy(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(policy),
+		));
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::RangeOrderPolicySet {
+			lp: ALICE,
+			base_asset: Asset::Eth,
+			quote_asset: STABLE_ASSET,
+			id: 0,
+			policy: Some(policy),
+		}));
+
+		// Orders are only rebalanced once the price is more than the width outside their range.
+		assert!(!RangeOrderPolicy::needs_rebalancing(&(100..200), 0, 100));
+		assert!(RangeOrderPolicy::needs_rebalancing(&(100..200), -1, 100));
+		assert!(!RangeOrderPolicy::needs_rebalancing(&(100..200), 299, 100));
+		assert!(RangeOrderPolicy::needs_rebalancing(&(100..200), 300, 100));
+
+		// Ranges lie next to the current tick, within the valid ticks.
+		assert_eq!(RangeOrderPolicy::adjacent_tick_range(0, 200, true), 1..201);
+		assert_eq!(RangeOrderPolicy::adjacent_tick_range(0, 200, false), -200..0);
+		assert_eq!(
+			RangeOrderPolicy::adjacent_tick_range(MAX_TICK, 200, true),
+			MAX_TICK - 1..MAX_TICK
+		);
+		assert_eq!(
+			RangeOrderPolicy::adjacent_tick_range(MIN_TICK, 200, false),
+			MIN_TICK..MIN_TICK + 1
+		);
+	});
+}
+
+#[test]
+fn auto_compounding_range_orders_reinvest_fees() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			400_000,
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		assert_ok!(LiquidityPools::set_range_order_policy(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(RangeOrderPolicy { auto_compound: true, rebalance_width: None }),
+		));
+
+		assert_ok!(LiquidityPools::swap_with_network_fee(STABLE_ASSET, Asset::Eth, 5_000));
+		assert_ok!(LiquidityPools::swap_with_network_fee(Asset::Eth, STABLE_ASSET, 5_000));
+		System::set_block_number(2);
+		LiquidityPools::on_initialize(2);
+
+		assert_events_match!(
+			Test,
+			RuntimeEvent::LiquidityPools(Event::RangeOrderCompounded { lp: ALICE, id: 0, .. }) => ()
+		);
+		let range_orders = LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE))
+			.unwrap()
+			.range_orders;
+		assert_eq!(range_orders.len(), 1);
+		assert_eq!(range_orders[0].range, -100..100);
+		assert!(range_orders[0].liquidity > 1_000_000);
+
+		// Removing the policy stops the compounding.
+		assert_ok!(LiquidityPools::set_range_order_policy(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			None,
+		));
+		assert_ok!(LiquidityPools::swap_with_network_fee(STABLE_ASSET, Asset::Eth, 5_000));
+		assert_ok!(LiquidityPools::swap_with_network_fee(Asset::Eth, STABLE_ASSET, 5_000));
+		System::reset_events();
+		System::set_block_number(3);
+		LiquidityPools::on_initialize(3);
+		assert!(!System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::LiquidityPools(Event::RangeOrderCompounded { .. })
+		)));
+	});
+}
+
+#[test]
+fn out_of_range_orders_are_rebalanced_with_bounded_work_per_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		for id in 0..3 {
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				id,
+				Some(100..200),
+				RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+			));
+			assert_ok!(LiquidityPools::set_range_order_policy(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				id,
+				Some(RangeOrderPolicy { auto_compound: false, rebalance_width: Some(50) }),
+			));
+		}
+		let balances = || (lp_balance(ALICE, Asset::Eth), lp_balance(ALICE, STABLE_ASSET));
+		let balances_before = balances();
+		let rebalanced_orders = || {
+			System::events()
+				.into_iter()
+				.filter(|record| {
+					matches!(
+						record.event,
+						RuntimeEvent::LiquidityPools(Event::RangeOrderRebalanced {
+							previous_tick_range: core::ops::Range { start: 100, end: 200 },
+							new_tick_range: core::ops::Range { start: 1, end: 51 },
+							..
+						})
+					)
+				})
+				.count()
+		};
+
+		// At most two orders are rebalanced per block in the mock.
+		System::reset_events();
+		System::set_block_number(2);
+		LiquidityPools::on_initialize(2);
+		assert_eq!(rebalanced_orders(), 2);
+
+		System::reset_events();
+		System::set_block_number(3);
+		LiquidityPools::on_initialize(3);
+		assert_eq!(rebalanced_orders(), 1);
+
+		// The withdrawn Eth is minted back into the new range, which is half as wide, so the orders
+		// have about twice the liquidity. Only the withdrawn assets are used, so none of the LP's
+		// other funds are taken, and any rounding dust is left in their balance.
+		let range_orders = LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE))
+			.unwrap()
+			.range_orders;
+		assert_eq!(range_orders.len(), 3);
+		assert!(range_orders.iter().all(|order| order.range == (1..51) &&
+			order.liquidity > 1_900_000 &&
+			order.liquidity < 2_100_000));
+		let balances_after = balances();
+		assert!(balances_after.0 >= balances_before.0 && balances_after.0 - balances_before.0 < 10);
+		assert_eq!(balances_after.1, balances_before.1);
+		System::reset_events();
+		System::set_block_number(4);
+		LiquidityPools::on_initialize(4);
+		assert_eq!(rebalanced_orders(), 0);
+
+		// Policies are removed once their order is closed.
+		for id in 0..3 {
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				id,
+				None,
+				RangeOrderSize::Liquidity { liquidity: 0 },
+			));
+		}
+		for block in 5..=6 {
+			System::set_block_number(block);
+			LiquidityPools::on_initialize(block);
+		}
+		assert_eq!(RangeOrderPolicies::<Test>::iter().count(), 0);
+	});
+}
@@ -41,6 +41,8 @@ pub trait WeightInfo {
 	fn set_limit_order() -> Weight;
 	fn set_pool_fees() -> Weight;
 	fn schedule() -> Weight;
+	fn set_dynamic_pool_fees() -> Weight;
+	fn set_range_order_policy() -> Weight;
 }
 
 /// Weights for pallet_cf_pools using the Substrate node and recommended hardware.
@@ -180,6 +182,28 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `LiquidityPools::Pools` (r:1 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::DynamicPoolFees` (r:0 w:1)
+	/// Proof: `LiquidityPools::DynamicPoolFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_dynamic_pool_fees() -> Weight {
+		Weight::from_parts(20_000_000, 4550)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `LiquidityPools::Pools` (r:1 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::RangeOrderPolicies` (r:0 w:1)
+	/// Proof: `LiquidityPools::RangeOrderPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_range_order_policy() -> Weight {
+		Weight::from_parts(25_000_000, 4977)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -318,4 +342,26 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `LiquidityPools::Pools` (r:1 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::DynamicPoolFees` (r:0 w:1)
+	/// Proof: `LiquidityPools::DynamicPoolFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_dynamic_pool_fees() -> Weight {
+		Weight::from_parts(20_000_000, 4550)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `LiquidityPools::Pools` (r:1 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::RangeOrderPolicies` (r:0 w:1)
+	/// Proof: `LiquidityPools::RangeOrderPolicies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_range_order_policy() -> Weight {
+		Weight::from_parts(25_000_000, 4977)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
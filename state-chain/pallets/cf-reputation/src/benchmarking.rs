@@ -110,5 +110,88 @@ mod benchmarks {
 		}
 	}
 
+	#[benchmark]
+	fn submit_appeal(e: Linear<0, { MAX_APPEAL_EVIDENCE_BYTES as u32 }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+		T::AccountRoleRegistry::register_as_validator(&caller).unwrap();
+		let validator_id: T::ValidatorId = caller.clone().into();
+		OffenceRecords::<T>::insert(
+			0,
+			OffenceRecord {
+				offender: validator_id,
+				offence: PalletOffence::MissedHeartbeat.into(),
+				reputation_penalty: 10,
+				slashed: 0,
+				epoch: T::EpochInfo::epoch_index(),
+				suspended_until: None,
+				appealable_until: T::AppealWindow::get(),
+				appeal_evidence: None,
+			},
+		);
+
+		#[extrinsic_call]
+		submit_appeal(RawOrigin::Signed(caller), 0, vec![0u8; e as usize]);
+
+		assert!(OffenceRecords::<T>::get(0).unwrap().appeal_evidence.is_some());
+	}
+
+	#[benchmark]
+	fn resolve_appeal() {
+		let validator_id: T::ValidatorId = account("validator", 0, 0);
+		let offence: T::Offence = PalletOffence::MissedHeartbeat.into();
+		OffenceRecords::<T>::insert(
+			0,
+			OffenceRecord {
+				offender: validator_id.clone(),
+				offence,
+				reputation_penalty: 10,
+				slashed: 1_000,
+				epoch: T::EpochInfo::epoch_index(),
+				suspended_until: Some(T::AppealWindow::get()),
+				appealable_until: T::AppealWindow::get(),
+				appeal_evidence: Some(vec![0u8; MAX_APPEAL_EVIDENCE_BYTES]),
+			},
+		);
+		LatestOffenceRecord::<T>::insert(&validator_id, offence, 0);
+		Suspensions::<T>::insert(
+			offence,
+			VecDeque::from([(T::AppealWindow::get(), validator_id.clone())]),
+		);
+		ValidatorHistory::<T>::mutate(T::EpochInfo::epoch_index(), &validator_id, |performance| {
+			performance.slashes = 1_000u32.into()
+		});
+		let call = Call::<T>::resolve_appeal { record_id: 0, successful: true };
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert!(!OffenceRecords::<T>::contains_key(0));
+	}
+
+	#[benchmark]
+	fn expire_offence_records(n: Linear<0, 100>) {
+		let expiry_block = T::AppealWindow::get();
+		for i in 0..n {
+			let validator_id: T::ValidatorId = account("validator", i, i);
+			Pallet::<T>::record_appealable_offence(
+				validator_id,
+				PalletOffence::MissedHeartbeat.into(),
+				&Penalty { reputation: 10, suspension: Zero::zero() },
+			);
+		}
+
+		#[block]
+		{
+			Pallet::<T>::expire_offence_records(expiry_block);
+		}
+
+		assert_eq!(OffenceRecords::<T>::iter().count(), 0);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
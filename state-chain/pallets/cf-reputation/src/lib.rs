@@ -18,13 +18,16 @@ pub use reputation::*;
 pub mod weights;
 pub use weights::WeightInfo;
 
+use cf_primitives::{EpochIndex, FlipBalance};
 use cf_traits::{
-	impl_pallet_safe_mode, offence_reporting::*, Chainflip, Heartbeat, NetworkState, QualifyNode,
-	ReputationResetter, Slashing,
+	impl_pallet_safe_mode, offence_reporting::*, Chainflip, EpochTransitionHandler, Heartbeat,
+	NetworkState, QualifyNode, ReputationResetter, Slashing, ValidatorPerformanceRecorder,
 };
 use frame_support::{
 	pallet_prelude::*,
-	sp_runtime::traits::{BlockNumberProvider, Saturating, Zero},
+	sp_runtime::traits::{
+		BlockNumberProvider, SaturatedConversion, Saturating, UniqueSaturatedInto, Zero,
+	},
 	traits::{Get, OnKilledAccount},
 };
 use frame_system::pallet_prelude::*;
@@ -76,6 +79,97 @@ impl<T: Config> Default for Penalty<T> {
 	}
 }
 
+/// The maximum number of different offences recorded in a validator's performance for an epoch.
+/// This is more than the number of offences in the runtime, so in practice nothing is dropped.
+pub const MAX_RECORDED_OFFENCES: u32 = 32;
+
+/// A record of a validator's performance during an epoch.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct EpochPerformance<Offence, Amount, BlockNumber> {
+	/// The total rewards paid out to the validator.
+	pub rewards: Amount,
+	/// The total amount slashed from the validator.
+	pub slashes: Amount,
+	/// The number of times the validator was reported for each offence.
+	pub offences: BoundedVec<(Offence, u32), ConstU32<MAX_RECORDED_OFFENCES>>,
+	/// The number of times the validator was suspended for each offence, and the block at which
+	/// the latest of these suspensions ends.
+	pub suspensions: BoundedVec<(Offence, u32, BlockNumber), ConstU32<MAX_RECORDED_OFFENCES>>,
+	/// The validator's reputation at the end of the epoch. Only recorded for authorities.
+	pub reputation_at_epoch_end: Option<ReputationPoints>,
+}
+
+impl<Offence, Amount: Default, BlockNumber> Default
+	for EpochPerformance<Offence, Amount, BlockNumber>
+{
+	fn default() -> Self {
+		Self {
+			rewards: Default::default(),
+			slashes: Default::default(),
+			offences: Default::default(),
+			suspensions: Default::default(),
+			reputation_at_epoch_end: None,
+		}
+	}
+}
+
+impl<Offence: PartialEq, Amount, BlockNumber: Ord + Copy>
+	EpochPerformance<Offence, Amount, BlockNumber>
+{
+	fn record_offence(&mut self, offence: Offence) {
+		match self.offences.iter_mut().find(|(recorded, _)| *recorded == offence) {
+			Some((_, count)) => *count = count.saturating_add(1),
+			None =>
+				if self.offences.try_push((offence, 1)).is_err() {
+					log::warn!("Too many different offences to record in the validator history.");
+				},
+		}
+	}
+
+	fn record_suspension(&mut self, offence: Offence, suspended_until: BlockNumber) {
+		match self.suspensions.iter_mut().find(|(recorded, ..)| *recorded == offence) {
+			Some((_, count, latest)) => {
+				*count = count.saturating_add(1);
+				*latest = (*latest).max(suspended_until);
+			},
+			None =>
+				if self.suspensions.try_push((offence, 1, suspended_until)).is_err() {
+					log::warn!(
+						"Too many different suspensions to record in the validator history."
+					);
+				},
+		}
+	}
+}
+
+/// Identifies a recorded offence that can be appealed.
+pub type OffenceRecordId = u64;
+
+/// The maximum size of the evidence that can be submitted with an appeal.
+pub const MAX_APPEAL_EVIDENCE_BYTES: usize = 16 * 1024;
+
+/// The penalties applied to a validator for an appealable offence.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct OffenceRecord<ValidatorId, Offence, BlockNumber> {
+	pub offender: ValidatorId,
+	pub offence: Offence,
+	/// The reputation deducted for the offence.
+	pub reputation_penalty: ReputationPoints,
+	/// The amount of FLIP slashed for the offence.
+	pub slashed: FlipBalance,
+	/// The epoch in which the offence was reported.
+	pub epoch: EpochIndex,
+	/// The block at which the offender's suspension for the offence ends, if they were suspended.
+	pub suspended_until: Option<BlockNumber>,
+	/// The last block at which the offender can appeal.
+	pub appealable_until: BlockNumber,
+	/// The evidence submitted by the offender, if the offence has been appealed.
+	pub appeal_evidence: Option<Vec<u8>>,
+}
+
+pub type OffenceRecordFor<T> =
+	OffenceRecord<<T as Chainflip>::ValidatorId, <T as Config>::Offence, BlockNumberFor<T>>;
+
 #[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum PalletOffence {
 	MissedHeartbeat,
@@ -131,11 +225,24 @@ pub mod pallet {
 
 		/// Safe mode access
 		type SafeMode: Get<PalletSafeMode>;
+
+		/// The number of epochs for which validator performance records are kept after their
+		/// epoch has expired.
+		#[pallet::constant]
+		type ValidatorHistoryRetention: Get<EpochIndex>;
+
+		/// Offences that the offender can appeal against.
+		type AppealableOffences: OffenceList<Self>;
+
+		/// The number of blocks after an offence is reported during which it can be appealed.
+		#[pallet::constant]
+		type AppealWindow: Get<BlockNumberFor<Self>>;
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
+			let expiry_weight = Self::expire_offence_records(current_block);
 			if current_block % T::HeartbeatBlockInterval::get() == Zero::zero() {
 				T::Heartbeat::on_heartbeat_interval();
 				if T::SafeMode::get().reporting_enabled {
@@ -144,9 +251,39 @@ pub mod pallet {
 					let num_offline_authorities = offline_authorities.len() as u32;
 					Self::penalise_offline_authorities(offline_authorities);
 					return T::WeightInfo::submit_network_state(num_offline_authorities)
+						.saturating_add(expiry_weight)
 				}
 			}
-			T::WeightInfo::on_initialize_no_action()
+			T::WeightInfo::on_initialize_no_action().saturating_add(expiry_weight)
+		}
+
+		/// Prunes the validator history of the oldest epoch once it is past the retention period.
+		fn on_idle(_current_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let db_weight = frame_support::weights::constants::RocksDbWeight::get();
+			let mut weight_used = db_weight.reads(2);
+			let oldest_epoch = OldestValidatorHistoryEpoch::<T>::get();
+			if oldest_epoch.saturating_add(T::ValidatorHistoryRetention::get()) >
+				T::EpochInfo::last_expired_epoch()
+			{
+				return weight_used
+			}
+			let limit = remaining_weight
+				.saturating_sub(weight_used)
+				.saturating_sub(db_weight.writes(1))
+				.ref_time()
+				.checked_div(db_weight.writes(1).ref_time())
+				.unwrap_or_default()
+				.saturated_into::<u32>();
+			if limit == 0 {
+				return weight_used
+			}
+			let result = ValidatorHistory::<T>::clear_prefix(oldest_epoch, limit, None);
+			weight_used.saturating_accrue(db_weight.writes(result.unique.into()));
+			if result.maybe_cursor.is_none() {
+				OldestValidatorHistoryEpoch::<T>::put(oldest_epoch.saturating_add(1));
+				weight_used.saturating_accrue(db_weight.writes(1));
+			}
+			weight_used
 		}
 	}
 
@@ -190,6 +327,49 @@ pub mod pallet {
 	pub type LastHeartbeat<T: Config> =
 		StorageMap<_, Twox64Concat, T::ValidatorId, BlockNumberFor<T>, OptionQuery>;
 
+	/// Per-epoch performance records of validators.
+	#[pallet::storage]
+	pub type ValidatorHistory<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EpochIndex,
+		Blake2_128Concat,
+		T::ValidatorId,
+		EpochPerformance<T::Offence, T::Amount, BlockNumberFor<T>>,
+		ValueQuery,
+	>;
+
+	/// The oldest epoch whose [ValidatorHistory] has not been pruned yet.
+	#[pallet::storage]
+	pub type OldestValidatorHistoryEpoch<T: Config> = StorageValue<_, EpochIndex, ValueQuery>;
+
+	/// The id of the next appealable offence record.
+	#[pallet::storage]
+	pub type NextOffenceRecordId<T: Config> = StorageValue<_, OffenceRecordId, ValueQuery>;
+
+	/// Records of appealable offences that are still within their appeal window, or that have
+	/// been appealed and are awaiting a decision.
+	#[pallet::storage]
+	pub type OffenceRecords<T: Config> =
+		StorageMap<_, Twox64Concat, OffenceRecordId, OffenceRecordFor<T>, OptionQuery>;
+
+	/// The most recent offence record for each offender and offence, used to attribute slashes.
+	#[pallet::storage]
+	pub type LatestOffenceRecord<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::ValidatorId,
+		Twox64Concat,
+		T::Offence,
+		OffenceRecordId,
+		OptionQuery,
+	>;
+
+	/// The offence records whose appeal window closes at a given block.
+	#[pallet::storage]
+	pub type OffenceRecordExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<OffenceRecordId>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -204,12 +384,35 @@ pub mod pallet {
 		MissedHeartbeatPenaltyUpdated { new_reputation_penalty: ReputationPoints },
 		/// The penalty for some offence has been updated.
 		PenaltyUpdated { offence: T::Offence, old_penalty: Penalty<T>, new_penalty: Penalty<T> },
+		/// An offence has been recorded that the offender can appeal against.
+		AppealableOffenceRecorded {
+			record_id: OffenceRecordId,
+			offender: T::ValidatorId,
+			offence: T::Offence,
+			appealable_until: BlockNumberFor<T>,
+		},
+		/// An offender has appealed against an offence.
+		AppealSubmitted { record_id: OffenceRecordId, offender: T::ValidatorId },
+		/// An appeal has been decided. If successful, the offence's penalties have been reversed.
+		AppealResolved { record_id: OffenceRecordId, offender: T::ValidatorId, successful: bool },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Tried to set the accrual ration to something invalid.
 		InvalidAccrualRatio,
+		/// The offence record does not exist or can no longer be appealed.
+		UnknownOffenceRecord,
+		/// Only the offender can appeal against an offence.
+		NotOffender,
+		/// The appeal window for this offence has closed.
+		AppealWindowClosed,
+		/// The offence has already been appealed.
+		AlreadyAppealed,
+		/// The submitted evidence exceeds the maximum size.
+		EvidenceTooLarge,
+		/// The offence has not been appealed.
+		NoPendingAppeal,
 	}
 
 	#[pallet::call]
@@ -324,6 +527,107 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Appeal against an offence, submitting evidence to support the appeal. Can only be
+		/// called by the offender, within the appeal window of the offence.
+		///
+		/// ## Events
+		///
+		/// - [AppealSubmitted](Event::AppealSubmitted)
+		///
+		/// ## Errors
+		///
+		/// - [UnknownOffenceRecord](Error::UnknownOffenceRecord)
+		/// - [NotOffender](Error::NotOffender)
+		/// - [AppealWindowClosed](Error::AppealWindowClosed)
+		/// - [AlreadyAppealed](Error::AlreadyAppealed)
+		/// - [EvidenceTooLarge](Error::EvidenceTooLarge)
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::submit_appeal(evidence.len() as u32))]
+		pub fn submit_appeal(
+			origin: OriginFor<T>,
+			record_id: OffenceRecordId,
+			evidence: Vec<u8>,
+		) -> DispatchResult {
+			let validator_id: T::ValidatorId =
+				T::AccountRoleRegistry::ensure_validator(origin)?.into();
+			ensure!(evidence.len() <= MAX_APPEAL_EVIDENCE_BYTES, Error::<T>::EvidenceTooLarge);
+
+			OffenceRecords::<T>::try_mutate(record_id, |maybe_record| {
+				let record = maybe_record.as_mut().ok_or(Error::<T>::UnknownOffenceRecord)?;
+				ensure!(record.offender == validator_id, Error::<T>::NotOffender);
+				ensure!(
+					frame_system::Pallet::<T>::current_block_number() <= record.appealable_until,
+					Error::<T>::AppealWindowClosed
+				);
+				ensure!(record.appeal_evidence.is_none(), Error::<T>::AlreadyAppealed);
+				record.appeal_evidence = Some(evidence);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::AppealSubmitted { record_id, offender: validator_id });
+			Ok(())
+		}
+
+		/// Decide an appeal. A successful appeal restores the deducted reputation, lifts the
+		/// suspension imposed for the offence and refunds any FLIP that was slashed.
+		///
+		/// ## Events
+		///
+		/// - [AppealResolved](Event::AppealResolved)
+		///
+		/// ## Errors
+		///
+		/// - [NoPendingAppeal](Error::NoPendingAppeal)
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::resolve_appeal())]
+		pub fn resolve_appeal(
+			origin: OriginFor<T>,
+			record_id: OffenceRecordId,
+			successful: bool,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			let record = OffenceRecords::<T>::get(record_id)
+				.filter(|record| record.appeal_evidence.is_some())
+				.ok_or(Error::<T>::NoPendingAppeal)?;
+			Self::remove_offence_record(record_id, &record);
+
+			if successful {
+				Reputations::<T>::mutate(&record.offender, |rep| {
+					rep.restore_reputation(record.reputation_penalty);
+				});
+				// Only the suspension imposed for this offence is lifted, not any later ones.
+				if let Some(suspended_until) = record.suspended_until {
+					Suspensions::<T>::mutate(record.offence, |suspensions| {
+						if let Some(index) = suspensions.iter().position(|(block, id)| {
+							*block == suspended_until && *id == record.offender
+						}) {
+							suspensions.remove(index);
+						}
+					});
+				}
+				T::Slasher::refund_slash(&record.offender, record.slashed);
+				ValidatorHistory::<T>::mutate_exists(
+					record.epoch,
+					&record.offender,
+					|performance| {
+						if let Some(performance) = performance {
+							performance.slashes = performance
+								.slashes
+								.saturating_sub(record.slashed.unique_saturated_into());
+						}
+					},
+				);
+			}
+
+			Self::deposit_event(Event::AppealResolved {
+				record_id,
+				offender: record.offender,
+				successful,
+			});
+			Ok(())
+		}
 	}
 
 	impl<T: Config> QualifyNode<T::ValidatorId> for Pallet<T> {
@@ -400,6 +704,13 @@ impl<T: Config> OffenceReporter for Pallet<T> {
 		let offence = offence.into();
 		let penalty = Self::resolve_penalty_for(offence);
 
+		let current_epoch = T::EpochInfo::epoch_index();
+		validators.clone().into_iter().for_each(|validator_id| {
+			ValidatorHistory::<T>::mutate(current_epoch, &validator_id, |performance| {
+				performance.record_offence(offence)
+			});
+		});
+
 		if penalty.reputation > 0 {
 			validators.clone().into_iter().for_each(|validator_id| {
 				Reputations::<T>::mutate(&validator_id, |rep| {
@@ -413,6 +724,12 @@ impl<T: Config> OffenceReporter for Pallet<T> {
 			});
 		}
 
+		if T::AppealableOffences::OFFENCES.contains(&offence) {
+			validators.clone().into_iter().for_each(|validator_id| {
+				Self::record_appealable_offence(validator_id, offence, &penalty);
+			});
+		}
+
 		if penalty.suspension > Zero::zero() {
 			Self::suspend_all(validators, &offence, penalty.suspension);
 		}
@@ -421,6 +738,16 @@ impl<T: Config> OffenceReporter for Pallet<T> {
 	fn forgive_all(offence: impl Into<Self::Offence>) {
 		Suspensions::<T>::remove(offence.into());
 	}
+
+	fn report_slash(offence: impl Into<Self::Offence>, node: &T::ValidatorId, amount: FlipBalance) {
+		if let Some(record_id) = LatestOffenceRecord::<T>::get(node, offence.into()) {
+			OffenceRecords::<T>::mutate(record_id, |maybe_record| {
+				if let Some(record) = maybe_record {
+					record.slashed.saturating_accrue(amount);
+				}
+			});
+		}
+	}
 }
 
 pub trait OffenceList<T: Config> {
@@ -472,7 +799,13 @@ impl<T: Config> Pallet<T> {
 		let current_block = frame_system::Pallet::<T>::current_block_number();
 		let mut suspensions = Suspensions::<T>::get(offence);
 		let suspend_until = current_block.saturating_add(suspension);
-		suspensions.extend(iter::repeat(suspend_until).zip(validators));
+		let current_epoch = T::EpochInfo::epoch_index();
+		for validator_id in validators {
+			ValidatorHistory::<T>::mutate(current_epoch, &validator_id, |performance| {
+				performance.record_suspension(*offence, suspend_until)
+			});
+			suspensions.push_back((suspend_until, validator_id));
+		}
 		suspensions.make_contiguous().sort_unstable_by_key(|(block, _)| *block);
 		while matches!(suspensions.front(), Some((block, _)) if *block < current_block) {
 			suspensions.pop_front();
@@ -505,6 +838,107 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	fn record_appealable_offence(
+		offender: T::ValidatorId,
+		offence: T::Offence,
+		penalty: &Penalty<T>,
+	) {
+		let record_id = NextOffenceRecordId::<T>::mutate(|id| {
+			let record_id = *id;
+			*id = id.saturating_add(1);
+			record_id
+		});
+		let current_block = frame_system::Pallet::<T>::current_block_number();
+		let appealable_until = current_block.saturating_add(T::AppealWindow::get());
+
+		OffenceRecords::<T>::insert(
+			record_id,
+			OffenceRecord {
+				offender: offender.clone(),
+				offence,
+				reputation_penalty: penalty.reputation.max(0),
+				slashed: Zero::zero(),
+				epoch: T::EpochInfo::epoch_index(),
+				suspended_until: (penalty.suspension > Zero::zero())
+					.then(|| current_block.saturating_add(penalty.suspension)),
+				appealable_until,
+				appeal_evidence: None,
+			},
+		);
+		LatestOffenceRecord::<T>::insert(&offender, offence, record_id);
+		OffenceRecordExpiries::<T>::append(appealable_until, record_id);
+
+		Self::deposit_event(Event::AppealableOffenceRecorded {
+			record_id,
+			offender,
+			offence,
+			appealable_until,
+		});
+	}
+
+	fn remove_offence_record(record_id: OffenceRecordId, record: &OffenceRecordFor<T>) {
+		OffenceRecords::<T>::remove(record_id);
+		LatestOffenceRecord::<T>::mutate_exists(&record.offender, record.offence, |latest| {
+			if *latest == Some(record_id) {
+				*latest = None;
+			}
+		});
+	}
+
+	/// Removes the records of offences whose appeal window has closed without an appeal.
+	/// Appealed offences are kept until the appeal is decided.
+	fn expire_offence_records(current_block: BlockNumberFor<T>) -> Weight {
+		let expiring = OffenceRecordExpiries::<T>::take(current_block);
+		let weight = T::WeightInfo::expire_offence_records(expiring.len() as u32);
+		for record_id in expiring {
+			if let Some(record) = OffenceRecords::<T>::get(record_id) {
+				if record.appeal_evidence.is_none() {
+					Self::remove_offence_record(record_id, &record);
+				}
+			}
+		}
+		weight
+	}
+
+	/// Returns the performance records of a validator for all epochs that have not been pruned,
+	/// in ascending order of epoch.
+	pub fn validator_history(
+		validator_id: &T::ValidatorId,
+	) -> Vec<(EpochIndex, EpochPerformance<T::Offence, T::Amount, BlockNumberFor<T>>)> {
+		(T::EpochInfo::last_expired_epoch().saturating_sub(T::ValidatorHistoryRetention::get())..=
+			T::EpochInfo::epoch_index())
+			.filter(|epoch| ValidatorHistory::<T>::contains_key(epoch, validator_id))
+			.map(|epoch| (epoch, ValidatorHistory::<T>::get(epoch, validator_id)))
+			.collect()
+	}
+}
+
+impl<T: Config> ValidatorPerformanceRecorder<T::ValidatorId, T::Amount> for Pallet<T> {
+	fn record_reward(validator_id: &T::ValidatorId, amount: T::Amount) {
+		ValidatorHistory::<T>::mutate(T::EpochInfo::epoch_index(), validator_id, |performance| {
+			performance.rewards = performance.rewards.saturating_add(amount)
+		});
+	}
+
+	fn record_slash(validator_id: &T::ValidatorId, amount: T::Amount) {
+		ValidatorHistory::<T>::mutate(T::EpochInfo::epoch_index(), validator_id, |performance| {
+			performance.slashes = performance.slashes.saturating_add(amount)
+		});
+	}
+}
+
+impl<T: Config> EpochTransitionHandler for Pallet<T> {
+	fn on_new_epoch(previous_epoch: EpochIndex) {
+		for validator_id in T::EpochInfo::authorities_at_epoch(previous_epoch) {
+			let reputation_points = Reputations::<T>::get(&validator_id).reputation_points;
+			ValidatorHistory::<T>::mutate(previous_epoch, &validator_id, |performance| {
+				performance.reputation_at_epoch_end = Some(reputation_points)
+			});
+		}
+	}
+}
+
 impl<T: Config> ReputationResetter for Pallet<T> {
 	type ValidatorId = T::ValidatorId;
 
@@ -14,6 +14,7 @@ type ValidatorId = u64;
 
 thread_local! {
 	pub static SLASHES: RefCell<Vec<u64>> = RefCell::new(Default::default());
+	pub static REFUNDS: RefCell<Vec<(u64, FlipBalance)>> = RefCell::new(Default::default());
 	pub static HEARTBEATS: RefCell<u32> = RefCell::new(Default::default());
 }
 
@@ -66,14 +67,20 @@ pub const ACCRUAL_RATIO: (i32, u64) = (REPUTATION_PER_HEARTBEAT, HEARTBEAT_BLOCK
 
 pub const MAX_ACCRUABLE_REPUTATION: ReputationPoints = 25;
 
+pub const VALIDATOR_HISTORY_RETENTION: EpochIndex = 2;
+
 pub const MISSED_HEARTBEAT_PENALTY_POINTS: ReputationPoints = 2;
 pub const GRANDPA_EQUIVOCATION_PENALTY_POINTS: ReputationPoints = 50;
 pub const GRANDPA_SUSPENSION_DURATION: u64 = HEARTBEAT_BLOCK_INTERVAL * 10;
 
+pub const APPEAL_WINDOW: u64 = 100;
+
 parameter_types! {
 	pub const HeartbeatBlockInterval: u64 = HEARTBEAT_BLOCK_INTERVAL;
 	pub const ReputationPointFloorAndCeiling: (i32, i32) = (-2880, 2880);
 	pub const MaximumAccruableReputation: ReputationPoints = MAX_ACCRUABLE_REPUTATION;
+	pub const ValidatorHistoryRetention: EpochIndex = VALIDATOR_HISTORY_RETENTION;
+	pub const AppealWindow: u64 = APPEAL_WINDOW;
 }
 
 // Mocking the `Slasher` trait
@@ -83,6 +90,10 @@ impl MockSlasher {
 	pub fn slash_count(validator_id: ValidatorId) -> usize {
 		SLASHES.with(|slashes| slashes.borrow().iter().filter(|id| **id == validator_id).count())
 	}
+
+	pub fn refunds() -> Vec<(u64, FlipBalance)> {
+		REFUNDS.with(|refunds| refunds.borrow().clone())
+	}
 }
 
 impl Slashing for MockSlasher {
@@ -97,11 +108,18 @@ impl Slashing for MockSlasher {
 		});
 	}
 
-	fn slash_balance(account_id: &Self::AccountId, _amount: FlipBalance) {
+	fn slash_balance(account_id: &Self::AccountId, amount: FlipBalance) -> FlipBalance {
 		// Count those slashes
 		SLASHES.with(|count| {
 			count.borrow_mut().push(*account_id);
 		});
+		amount
+	}
+
+	fn refund_slash(account_id: &Self::AccountId, amount: FlipBalance) {
+		REFUNDS.with(|refunds| {
+			refunds.borrow_mut().push((*account_id, amount));
+		});
 	}
 
 	fn calculate_slash_amount(
@@ -161,6 +179,12 @@ impl From<PalletOffence> for AllOffences {
 	}
 }
 
+pub struct MockAppealableOffences;
+
+impl OffenceList<Test> for MockAppealableOffences {
+	const OFFENCES: &'static [AllOffences] = &[AllOffences::ForgettingYourYubiKey];
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Offence = AllOffences;
@@ -171,6 +195,9 @@ impl Config for Test {
 	type WeightInfo = ();
 	type MaximumAccruableReputation = MaximumAccruableReputation;
 	type SafeMode = MockRuntimeSafeMode;
+	type ValidatorHistoryRetention = ValidatorHistoryRetention;
+	type AppealableOffences = MockAppealableOffences;
+	type AppealWindow = AppealWindow;
 }
 
 cf_test_utilities::impl_test_helpers! {
@@ -54,6 +54,12 @@ impl<P: ReputationParameters> ReputationTracker<P> {
 		self.clamp();
 	}
 
+	/// Restores reputation that was deducted for an offence, eg. after a successful appeal.
+	pub fn restore_reputation(&mut self, points: ReputationPoints) {
+		self.reputation_points.saturating_accrue(points);
+		self.clamp();
+	}
+
 	/// Clamp the reputation points to the given bounds.
 	fn clamp(&mut self) {
 		let (floor, ceiling) = P::bounds();
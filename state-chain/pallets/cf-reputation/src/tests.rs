@@ -1,6 +1,9 @@
 use crate::{mock::*, *};
 use cf_traits::{offence_reporting::*, EpochInfo, QualifyNode, SafeMode, SetSafeMode};
-use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, OnInitialize},
+};
 
 fn reputation_points(who: &<Test as frame_system::Config>::AccountId) -> ReputationPoints {
 	ReputationPallet::reputation(who).reputation_points
@@ -498,3 +501,256 @@ fn in_safe_mode_you_dont_lose_reputation_for_being_offline() {
 		assert_eq!(reputation, reputation_points(&BOB));
 	});
 }
+
+#[test]
+fn validator_performance_is_recorded_per_epoch() {
+	new_test_ext().execute_with(|| {
+		let first_epoch = MockEpochInfo::epoch_index();
+		let suspended_until = System::block_number() + HEARTBEAT_BLOCK_INTERVAL;
+
+		<ReputationPallet as OffenceReporter>::report_many(
+			AllOffences::ForgettingYourYubiKey,
+			vec![ALICE, BOB],
+		);
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+		<ReputationPallet as OffenceReporter>::report(AllOffences::MissedHeartbeat, ALICE);
+		ReputationPallet::record_reward(&ALICE, 100);
+		ReputationPallet::record_reward(&ALICE, 50);
+		ReputationPallet::record_slash(&ALICE, 10);
+
+		MockEpochInfo::set_past_authorities(BTreeSet::from([ALICE]));
+		let second_epoch = MockEpochInfo::next_epoch(BTreeSet::from([ALICE, BOB]));
+		<ReputationPallet as EpochTransitionHandler>::on_new_epoch(first_epoch);
+		ReputationPallet::record_reward(&BOB, 20);
+
+		assert_eq!(
+			ReputationPallet::validator_history(&ALICE),
+			vec![(
+				first_epoch,
+				EpochPerformance {
+					rewards: 150,
+					slashes: 10,
+					offences: vec![
+						(AllOffences::ForgettingYourYubiKey, 2),
+						(AllOffences::MissedHeartbeat, 1)
+					]
+					.try_into()
+					.unwrap(),
+					suspensions: vec![(AllOffences::ForgettingYourYubiKey, 2, suspended_until)]
+						.try_into()
+						.unwrap(),
+					reputation_at_epoch_end: Some(reputation_points(&ALICE)),
+				}
+			)]
+		);
+		assert_eq!(
+			ReputationPallet::validator_history(&BOB),
+			vec![
+				(
+					first_epoch,
+					EpochPerformance {
+						rewards: 0,
+						slashes: 0,
+						offences: vec![(AllOffences::ForgettingYourYubiKey, 1)].try_into().unwrap(),
+						suspensions: vec![(AllOffences::ForgettingYourYubiKey, 1, suspended_until)]
+							.try_into()
+							.unwrap(),
+						// Bob was not an authority in the first epoch.
+						reputation_at_epoch_end: None,
+					}
+				),
+				(second_epoch, EpochPerformance { rewards: 20, ..Default::default() })
+			]
+		);
+	});
+}
+
+#[test]
+fn validator_history_is_pruned_after_retention_period() {
+	new_test_ext().execute_with(|| {
+		let first_epoch = MockEpochInfo::epoch_index();
+		ReputationPallet::record_reward(&ALICE, 100);
+
+		for _ in 0..VALIDATOR_HISTORY_RETENTION + 1 {
+			MockEpochInfo::next_epoch(BTreeSet::from([ALICE]));
+			ReputationPallet::record_reward(&ALICE, 100);
+		}
+
+		let prune_epochs = |count: u32| {
+			for _ in 0..count {
+				ReputationPallet::on_idle(System::block_number(), Weight::MAX);
+			}
+		};
+
+		// The expiry of the first epoch doesn't prune its history.
+		MockEpochInfo::set_last_expired_epoch(first_epoch);
+		prune_epochs(first_epoch + 1);
+		assert!(ValidatorHistory::<Test>::contains_key(first_epoch, ALICE));
+
+		// Once the retention period has elapsed, it is pruned when there is weight to spare.
+		MockEpochInfo::set_last_expired_epoch(first_epoch + VALIDATOR_HISTORY_RETENTION);
+		ReputationPallet::on_idle(System::block_number(), Weight::zero());
+		assert_eq!(OldestValidatorHistoryEpoch::<Test>::get(), 0);
+		prune_epochs(first_epoch + 1);
+		assert_eq!(OldestValidatorHistoryEpoch::<Test>::get(), first_epoch + 1);
+		assert!(!ValidatorHistory::<Test>::contains_key(first_epoch, ALICE));
+		assert_eq!(
+			ReputationPallet::validator_history(&ALICE)
+				.into_iter()
+				.map(|(epoch, _)| epoch)
+				.collect::<Vec<_>>(),
+			(first_epoch + 1..=MockEpochInfo::epoch_index()).collect::<Vec<_>>()
+		);
+	});
+}
+
+#[test]
+fn appealable_offences_are_recorded_with_slashes() {
+	new_test_ext().execute_with(|| {
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+		<ReputationPallet as OffenceReporter>::report_slash(
+			AllOffences::ForgettingYourYubiKey,
+			&ALICE,
+			1_000,
+		);
+
+		let record = OffenceRecords::<Test>::get(0).expect("Offence should be recorded");
+		assert_eq!(record.offender, ALICE);
+		assert_eq!(record.reputation_penalty, 15);
+		assert_eq!(record.slashed, 1_000);
+		assert_eq!(record.epoch, MockEpochInfo::epoch_index());
+		assert_eq!(record.suspended_until, Some(System::block_number() + HEARTBEAT_BLOCK_INTERVAL));
+		assert_eq!(record.appealable_until, System::block_number() + APPEAL_WINDOW);
+
+		// Non-appealable offences are not recorded.
+		<ReputationPallet as OffenceReporter>::report(AllOffences::MissedHeartbeat, ALICE);
+		assert_eq!(OffenceRecords::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn appeals_can_only_be_submitted_by_the_offender_within_the_window() {
+	new_test_ext().execute_with(|| {
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+
+		assert_noop!(
+			ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 1, vec![]),
+			Error::<Test>::UnknownOffenceRecord
+		);
+		assert_noop!(
+			ReputationPallet::submit_appeal(RuntimeOrigin::signed(BOB), 0, vec![]),
+			Error::<Test>::NotOffender
+		);
+		assert_noop!(
+			ReputationPallet::submit_appeal(
+				RuntimeOrigin::signed(ALICE),
+				0,
+				vec![0; MAX_APPEAL_EVIDENCE_BYTES + 1]
+			),
+			Error::<Test>::EvidenceTooLarge
+		);
+		assert_ok!(ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 0, vec![1, 2]));
+		assert_noop!(
+			ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 0, vec![3]),
+			Error::<Test>::AlreadyAppealed
+		);
+
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, BOB);
+		System::set_block_number(System::block_number() + APPEAL_WINDOW + 1);
+		assert_noop!(
+			ReputationPallet::submit_appeal(RuntimeOrigin::signed(BOB), 1, vec![]),
+			Error::<Test>::AppealWindowClosed
+		);
+	});
+}
+
+#[test]
+fn successful_appeal_reverses_penalties() {
+	new_test_ext().execute_with(|| {
+		const SLASHED: FlipBalance = 1_000;
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+		<ReputationPallet as OffenceReporter>::report_slash(
+			AllOffences::ForgettingYourYubiKey,
+			&ALICE,
+			SLASHED,
+		);
+		ReputationPallet::record_slash(&ALICE, SLASHED);
+		assert_reputation!(ALICE, -15);
+		assert!(ReputationPallet::validators_suspended_for(&[AllOffences::ForgettingYourYubiKey])
+			.contains(&ALICE));
+
+		assert_noop!(
+			ReputationPallet::resolve_appeal(RuntimeOrigin::root(), 0, true),
+			Error::<Test>::NoPendingAppeal
+		);
+		assert_ok!(ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 0, vec![1]));
+		assert_ok!(ReputationPallet::resolve_appeal(RuntimeOrigin::root(), 0, true));
+
+		assert_reputation!(ALICE, 0);
+		assert!(ReputationPallet::validators_suspended_for(&[AllOffences::ForgettingYourYubiKey])
+			.is_empty());
+		assert_eq!(MockSlasher::refunds(), vec![(ALICE, SLASHED)]);
+		assert_eq!(ValidatorHistory::<Test>::get(MockEpochInfo::epoch_index(), ALICE).slashes, 0);
+		assert!(!OffenceRecords::<Test>::contains_key(0));
+		System::assert_last_event(RuntimeEvent::ReputationPallet(Event::AppealResolved {
+			record_id: 0,
+			offender: ALICE,
+			successful: true,
+		}));
+	});
+}
+
+#[test]
+fn successful_appeal_only_lifts_its_own_suspension() {
+	new_test_ext().execute_with(|| {
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+		advance_by_block();
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+		let later_suspension = OffenceRecords::<Test>::get(1).unwrap().suspended_until.unwrap();
+
+		assert_ok!(ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 0, vec![1]));
+		assert_ok!(ReputationPallet::resolve_appeal(RuntimeOrigin::root(), 0, true));
+
+		assert_eq!(
+			ReputationPallet::suspensions(AllOffences::ForgettingYourYubiKey),
+			VecDeque::from([(later_suspension, ALICE)])
+		);
+		assert!(ReputationPallet::validators_suspended_for(&[AllOffences::ForgettingYourYubiKey])
+			.contains(&ALICE));
+	});
+}
+
+#[test]
+fn rejected_appeal_keeps_penalties() {
+	new_test_ext().execute_with(|| {
+		<ReputationPallet as OffenceReporter>::report(AllOffences::ForgettingYourYubiKey, ALICE);
+		assert_ok!(ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 0, vec![1]));
+		assert_ok!(ReputationPallet::resolve_appeal(RuntimeOrigin::root(), 0, false));
+
+		assert_reputation!(ALICE, -15);
+		assert!(ReputationPallet::validators_suspended_for(&[AllOffences::ForgettingYourYubiKey])
+			.contains(&ALICE));
+		assert!(MockSlasher::refunds().is_empty());
+		assert!(!OffenceRecords::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn unappealed_offence_records_expire() {
+	new_test_ext().execute_with(|| {
+		<ReputationPallet as OffenceReporter>::report_many(
+			AllOffences::ForgettingYourYubiKey,
+			[ALICE, BOB],
+		);
+		assert_ok!(ReputationPallet::submit_appeal(RuntimeOrigin::signed(ALICE), 0, vec![1]));
+
+		for _ in 0..APPEAL_WINDOW {
+			advance_by_block();
+		}
+
+		// Only the pending appeal remains.
+		assert!(OffenceRecords::<Test>::contains_key(0));
+		assert!(!OffenceRecords::<Test>::contains_key(1));
+		assert_eq!(LatestOffenceRecord::<Test>::get(BOB, AllOffences::ForgettingYourYubiKey), None);
+	});
+}
@@ -38,6 +38,9 @@ pub trait WeightInfo {
 	fn heartbeat() -> Weight;
 	fn submit_network_state(o: u32) -> Weight;
 	fn on_initialize_no_action() -> Weight;
+	fn submit_appeal(e: u32, ) -> Weight;
+	fn resolve_appeal() -> Weight;
+	fn expire_offence_records(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_reputation using the Substrate node and recommended hardware.
@@ -132,6 +135,51 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		Weight::from_parts(4_025_000, 1820)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Reputation::OffenceRecords` (r:1 w:1)
+	/// Proof: `Reputation::OffenceRecords` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `e` is `[0, 16384]`.
+	fn submit_appeal(e: u32, ) -> Weight {
+		Weight::from_parts(24_873_000, 3877)
+			.saturating_add(Weight::from_parts(1_104, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Reputation::OffenceRecords` (r:1 w:1)
+	/// Proof: `Reputation::OffenceRecords` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::LatestOffenceRecord` (r:1 w:1)
+	/// Proof: `Reputation::LatestOffenceRecord` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::Reputations` (r:1 w:1)
+	/// Proof: `Reputation::Reputations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::Suspensions` (r:1 w:1)
+	/// Proof: `Reputation::Suspensions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::TotalIssuance` (r:1 w:1)
+	/// Proof: `Flip::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Reputation::ValidatorHistory` (r:1 w:1)
+	/// Proof: `Reputation::ValidatorHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn resolve_appeal() -> Weight {
+		Weight::from_parts(42_355_000, 4154)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `Reputation::OffenceRecordExpiries` (r:1 w:1)
+	/// Proof: `Reputation::OffenceRecordExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::OffenceRecords` (r:100 w:100)
+	/// Proof: `Reputation::OffenceRecords` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::LatestOffenceRecord` (r:100 w:100)
+	/// Proof: `Reputation::LatestOffenceRecord` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 100]`.
+	fn expire_offence_records(n: u32, ) -> Weight {
+		Weight::from_parts(3_517_000, 3632)
+			.saturating_add(Weight::from_parts(9_822_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
 }
 
 // For backwards compatibility and tests
@@ -225,4 +273,49 @@ impl WeightInfo for () {
 		Weight::from_parts(4_025_000, 1820)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Reputation::OffenceRecords` (r:1 w:1)
+	/// Proof: `Reputation::OffenceRecords` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `e` is `[0, 16384]`.
+	fn submit_appeal(e: u32, ) -> Weight {
+		Weight::from_parts(24_873_000, 3877)
+			.saturating_add(Weight::from_parts(1_104, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Reputation::OffenceRecords` (r:1 w:1)
+	/// Proof: `Reputation::OffenceRecords` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::LatestOffenceRecord` (r:1 w:1)
+	/// Proof: `Reputation::LatestOffenceRecord` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::Reputations` (r:1 w:1)
+	/// Proof: `Reputation::Reputations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::Suspensions` (r:1 w:1)
+	/// Proof: `Reputation::Suspensions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::TotalIssuance` (r:1 w:1)
+	/// Proof: `Flip::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Reputation::ValidatorHistory` (r:1 w:1)
+	/// Proof: `Reputation::ValidatorHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn resolve_appeal() -> Weight {
+		Weight::from_parts(42_355_000, 4154)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `Reputation::OffenceRecordExpiries` (r:1 w:1)
+	/// Proof: `Reputation::OffenceRecordExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::OffenceRecords` (r:100 w:100)
+	/// Proof: `Reputation::OffenceRecords` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::LatestOffenceRecord` (r:100 w:100)
+	/// Proof: `Reputation::LatestOffenceRecord` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[0, 100]`.
+	fn expire_offence_records(n: u32, ) -> Weight {
+		Weight::from_parts(3_517_000, 3632)
+			.saturating_add(Weight::from_parts(9_822_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
 }
@@ -37,6 +37,39 @@ mod benchmarks {
 		}
 	}
 
+	#[benchmark]
+	fn request_swap_deposit_address_with_affiliates(
+		a: Linear<0, { cf_primitives::MAX_AFFILIATES }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+		assert_ok!(T::AccountRoleRegistry::register_as_broker(&caller));
+
+		let affiliates = (0..a)
+			.map(|i| {
+				let affiliate = account::<T::AccountId>("affiliate", i, 0);
+				<T as frame_system::Config>::OnNewAccount::on_new_account(&affiliate);
+				assert_ok!(T::AccountRoleRegistry::register_as_broker(&affiliate));
+				Affiliate { account: affiliate, bps: 10 }
+			})
+			.collect::<Vec<_>>();
+
+		let origin = RawOrigin::Signed(caller);
+		let call = Call::<T>::request_swap_deposit_address_with_affiliates {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_address: EncodedAddress::benchmark_value(),
+			broker_commission_bps: 0,
+			affiliates: affiliates.try_into().unwrap(),
+			boost_fee: 0,
+			channel_metadata: None,
+		};
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+	}
+
 	#[benchmark]
 	fn withdraw() {
 		let caller: T::AccountId = whitelisted_caller();
@@ -92,6 +125,91 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn internal_swap() {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+		assert_ok!(T::AccountRoleRegistry::register_as_liquidity_provider(&caller));
+		assert_ok!(T::LpBalance::try_credit_account(&caller, Asset::Eth, 1_000));
+
+		let call = Call::<T>::internal_swap {
+			from: Asset::Eth,
+			to: Asset::Usdc,
+			amount: 1_000,
+			min_output: 900,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into()));
+		}
+
+		assert_eq!(
+			PendingInternalSwaps::<T>::get(1),
+			Some(InternalSwapDetails { account_id: caller, min_output: 900 })
+		);
+	}
+
+	#[benchmark]
+	fn schedule_exact_output_swap_from_contract() {
+		let witness_origin = T::EnsureWitnessed::try_successful_origin().unwrap();
+		let call = Call::<T>::schedule_exact_output_swap_from_contract {
+			from: Asset::Eth,
+			to: Asset::Usdc,
+			max_input: 1_000,
+			output_amount: 900,
+			destination_address: EncodedAddress::benchmark_value(),
+			refund_address: EncodedAddress::benchmark_value(),
+			tx_hash: [0; 32],
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(witness_origin));
+		}
+
+		assert_eq!(
+			ExactOutputSwapQueue::<T>::get(
+				<frame_system::Pallet<T>>::block_number() + SWAP_DELAY_BLOCKS.into()
+			),
+			vec![ExactOutputSwap {
+				swap_id: 1,
+				from: Asset::Eth,
+				to: Asset::Usdc,
+				max_input: 1_000,
+				output_amount: 900,
+				destination_address: ForeignChainAddress::benchmark_value(),
+				refund_address: ForeignChainAddress::benchmark_value(),
+			}]
+		);
+	}
+
+	#[benchmark]
+	fn process_exact_output_swaps(a: Linear<1, 100>) {
+		let block = <frame_system::Pallet<T>>::block_number();
+		for swap_id in 0..a {
+			ExactOutputSwapQueue::<T>::append(
+				block,
+				ExactOutputSwap {
+					swap_id: swap_id.into(),
+					from: Asset::Eth,
+					to: Asset::Flip,
+					max_input: 1_000,
+					output_amount: 900,
+					destination_address: ForeignChainAddress::benchmark_value(),
+					refund_address: ForeignChainAddress::benchmark_value(),
+				},
+			);
+		}
+
+		#[block]
+		{
+			Pallet::<T>::process_exact_output_swaps_for_block(block);
+		}
+
+		assert!(ExactOutputSwapQueue::<T>::get(block).is_empty());
+	}
+
 	#[benchmark]
 	fn ccm_deposit() {
 		let origin = T::EnsureWitnessed::try_successful_origin().unwrap();
@@ -102,6 +220,7 @@ mod benchmarks {
 				message: vec![0x00].try_into().unwrap(),
 				gas_budget: 1,
 				cf_parameters: Default::default(),
+				fallback: None,
 			},
 		};
 		let call = Call::<T>::ccm_deposit {
@@ -129,5 +248,43 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn register_withdrawal_address() {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+		assert_ok!(T::AccountRoleRegistry::register_as_broker(&caller));
+
+		#[extrinsic_call]
+		register_withdrawal_address(
+			RawOrigin::Signed(caller.clone()),
+			EncodedAddress::benchmark_value(),
+		);
+
+		assert!(BrokerWithdrawalAddress::<T>::contains_key(caller, ForeignChain::Ethereum));
+	}
+
+	#[benchmark]
+	fn withdraw_all() {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+		assert_ok!(T::AccountRoleRegistry::register_as_broker(&caller));
+		BrokerWithdrawalAddress::<T>::insert(
+			caller.clone(),
+			ForeignChain::Ethereum,
+			ForeignChainAddress::benchmark_value(),
+		);
+		// Ethereum has the most assets, so withdrawing all of them is the worst case.
+		for asset in
+			Asset::all().filter(|asset| ForeignChain::from(*asset) == ForeignChain::Ethereum)
+		{
+			EarnedBrokerFees::<T>::insert(caller.clone(), asset, 200);
+		}
+
+		#[extrinsic_call]
+		withdraw_all(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(EarnedBrokerFees::<T>::iter_prefix(caller).count(), 0);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
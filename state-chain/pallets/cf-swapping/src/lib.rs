@@ -5,14 +5,19 @@ use cf_chains::{
 	CcmChannelMetadata, CcmDepositMetadata, SwapOrigin,
 };
 use cf_primitives::{
-	Asset, AssetAmount, ChannelId, ForeignChain, SwapId, SwapLeg, TransactionHash, STABLE_ASSET,
+	Affiliate, Affiliates, Asset, AssetAmount, BasisPoints, ChannelId, EpochIndex, ForeignChain,
+	SwapId, SwapLeg, TransactionHash, STABLE_ASSET,
 };
 use cf_runtime_utilities::log_or_panic;
-use cf_traits::{impl_pallet_safe_mode, liquidity::SwappingApi, CcmHandler, DepositApi};
+use cf_traits::{
+	impl_pallet_safe_mode,
+	liquidity::{LpBalanceApi, SwappingApi},
+	CcmHandler, DepositApi,
+};
 use frame_support::{
 	pallet_prelude::*,
 	sp_runtime::{
-		traits::{Get, Saturating},
+		traits::{Get, SaturatedConversion, Saturating},
 		DispatchError, Permill,
 	},
 	transactional,
@@ -33,17 +38,25 @@ pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(2);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(3);
 
 const BASIS_POINTS_PER_MILLION: u32 = 100;
 
+/// The maximum total commission of a deposit channel, including the shares of any affiliates.
+pub const MAX_BROKER_COMMISSION_BPS: BasisPoints = 1000;
+
 pub const SWAP_DELAY_BLOCKS: u32 = 2;
 
+/// The number of epochs, including the current one, for which [BrokerEpochEarnings] are kept.
+pub const BROKER_EPOCH_EARNINGS_RETENTION: EpochIndex = 30;
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum SwapType {
 	Swap(ForeignChainAddress),
 	CcmPrincipal(SwapId),
 	CcmGas(SwapId),
+	/// A swap out of a Liquidity Provider's free balance, see [PendingInternalSwaps].
+	InternalSwap,
 }
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct Swap {
@@ -114,6 +127,36 @@ impl Swap {
 	}
 }
 
+/// A swap that buys an exact amount of the destination asset. Any part of the input that is not
+/// required to buy the output is refunded.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ExactOutputSwap {
+	pub swap_id: SwapId,
+	pub from: Asset,
+	pub to: Asset,
+	/// The maximum amount of the source asset that can be sold.
+	pub max_input: AssetAmount,
+	pub output_amount: AssetAmount,
+	pub destination_address: ForeignChainAddress,
+	pub refund_address: ForeignChainAddress,
+}
+
+/// The owner of a swap out of a Liquidity Provider's free balance, and the minimum output they are
+/// willing to accept.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct InternalSwapDetails<AccountId> {
+	pub account_id: AccountId,
+	pub min_output: AssetAmount,
+}
+
+/// The minimum output of a swap of funds paid directly into the vault, and where to refund the
+/// funds if it can't be met.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct VaultSwapDetails {
+	pub min_output: AssetAmount,
+	pub refund_address: ForeignChainAddress,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CcmSwapLeg {
 	Principal,
@@ -130,6 +173,7 @@ pub(crate) struct CcmSwapOutput {
 enum BatchExecutionError {
 	SwapLegFailed { asset: Asset, direction: SwapLeg, amount: AssetAmount },
 	DispatchError { error: DispatchError },
+	MinimumOutputNotMet { swap_ids: Vec<SwapId> },
 }
 
 /// This impl is never used. This is purely used to satisfy trait requirement
@@ -174,6 +218,8 @@ pub struct CcmSwapAmounts {
 pub enum CcmFailReason {
 	UnsupportedForTargetChain,
 	InsufficientDepositAmount,
+	InvalidFallbackAddress,
+	GasBudgetUnsupportedForTargetChain,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
@@ -190,7 +236,7 @@ impl_pallet_safe_mode! {
 pub mod pallet {
 
 	use cf_chains::{address::EncodedAddress, AnyChain, Chain};
-	use cf_primitives::{Asset, AssetAmount, BasisPoints, EgressId, SwapId};
+	use cf_primitives::{AccountRole, Asset, AssetAmount, BasisPoints, EgressId, SwapId};
 	use cf_traits::{
 		AccountRoleRegistry, CcmSwapIds, Chainflip, EgressApi, ScheduledEgressDetails,
 		SwapDepositHandler,
@@ -217,6 +263,9 @@ pub mod pallet {
 		/// An interface to the AMM api implementation.
 		type SwappingApi: SwappingApi;
 
+		/// API for crediting and debiting the free balances of Liquidity Providers.
+		type LpBalance: LpBalanceApi<AccountId = Self::AccountId>;
+
 		/// A converter to convert address to and from human readable to internal address
 		/// representation.
 		type AddressConverter: AddressConverter;
@@ -238,6 +287,12 @@ pub mod pallet {
 	pub(crate) type SwapQueue<T: Config> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<Swap>, ValueQuery>;
 
+	/// Scheduled exact output swaps. These are executed individually, after the batched swaps for
+	/// the same block.
+	#[pallet::storage]
+	pub(crate) type ExactOutputSwapQueue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<ExactOutputSwap>, ValueQuery>;
+
 	/// The first block for which swaps haven't yet been processed
 	#[pallet::storage]
 	pub(crate) type FirstUnprocessedBlock<T: Config> =
@@ -249,9 +304,58 @@ pub mod pallet {
 
 	/// Earned Fees by Brokers
 	#[pallet::storage]
-	pub(crate) type EarnedBrokerFees<T: Config> =
+	pub type EarnedBrokerFees<T: Config> =
+		StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, Asset, AssetAmount, ValueQuery>;
+
+	/// The total commission each broker has earned in each asset. Unlike [EarnedBrokerFees], this
+	/// is not reduced by withdrawals.
+	#[pallet::storage]
+	pub type TotalBrokerEarnings<T: Config> =
 		StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, Asset, AssetAmount, ValueQuery>;
 
+	/// The total commission each broker has earned from each of their deposit channels, keyed by
+	/// the channel's source asset and id since channel ids are only unique per chain. Removed when
+	/// the channel is recycled.
+	#[pallet::storage]
+	pub type BrokerChannelEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Twox64Concat,
+		(Asset, ChannelId),
+		AssetAmount,
+		ValueQuery,
+	>;
+
+	/// The total commission each broker has earned in each asset during each epoch. Only the last
+	/// [BROKER_EPOCH_EARNINGS_RETENTION] epochs are kept.
+	#[pallet::storage]
+	pub type BrokerEpochEarnings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EpochIndex,
+		Identity,
+		T::AccountId,
+		BTreeMap<Asset, AssetAmount>,
+		ValueQuery,
+	>;
+
+	/// The oldest epoch whose [BrokerEpochEarnings] have not been pruned yet.
+	#[pallet::storage]
+	pub type OldestBrokerEpochEarnings<T: Config> = StorageValue<_, EpochIndex, ValueQuery>;
+
+	/// The address on each chain that a broker's fees are sent to by
+	/// [withdraw_all](Pallet::withdraw_all).
+	#[pallet::storage]
+	pub type BrokerWithdrawalAddress<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Twox64Concat,
+		ForeignChain,
+		ForeignChainAddress,
+	>;
+
 	/// Cross chain messages Counter
 	#[pallet::storage]
 	pub type CcmIdCounter<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -264,6 +368,16 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type CcmOutputs<T: Config> = StorageMap<_, Twox64Concat, u64, CcmSwapOutput>;
 
+	/// Swaps out of Liquidity Providers' free balances that are waiting to be executed.
+	#[pallet::storage]
+	pub(crate) type PendingInternalSwaps<T: Config> =
+		StorageMap<_, Twox64Concat, SwapId, InternalSwapDetails<T::AccountId>>;
+
+	/// Swaps of funds paid directly into the vault that are waiting to be executed.
+	#[pallet::storage]
+	pub(crate) type PendingVaultSwaps<T: Config> =
+		StorageMap<_, Twox64Concat, SwapId, VaultSwapDetails>;
+
 	/// Fund accrued from rejected swap and CCM calls.
 	#[pallet::storage]
 	pub type CollectedRejectedFunds<T: Config> =
@@ -285,11 +399,21 @@ pub mod pallet {
 			destination_asset: Asset,
 			channel_id: ChannelId,
 			broker_commission_rate: BasisPoints,
+			affiliates: Affiliates<T::AccountId>,
 			channel_metadata: Option<CcmChannelMetadata>,
 			source_chain_expiry_block: <AnyChain as Chain>::ChainBlockNumber,
 			boost_fee: BasisPoints,
 		},
-		/// A swap deposit has been received.
+		/// An affiliate has been credited with their share of the commission on a swap deposit.
+		AffiliateCommissionCredited {
+			swap_id: SwapId,
+			broker_id: T::AccountId,
+			affiliate_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		/// A swap deposit has been received. The broker commission includes the shares of any
+		/// affiliates.
 		SwapScheduled {
 			swap_id: SwapId,
 			source_asset: Asset,
@@ -329,6 +453,20 @@ pub mod pallet {
 			egress_fee: AssetAmount,
 			destination_address: EncodedAddress,
 		},
+		/// A broker's balance of an asset was not withdrawn by
+		/// [withdraw_all](Pallet::withdraw_all) and remains available.
+		WithdrawalSkipped {
+			broker_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+			reason: DispatchError,
+		},
+		/// A broker has registered the address that their fees on a chain are withdrawn to.
+		BrokerWithdrawalAddressRegistered {
+			broker_id: T::AccountId,
+			chain: ForeignChain,
+			address: EncodedAddress,
+		},
 		/// Most likely cause of this error is that there are insufficient
 		/// liquidity in the Pool. Also this could happen if the result overflowed u128::MAX
 		BatchSwapFailed {
@@ -370,6 +508,66 @@ pub mod pallet {
 			amount: AssetAmount,
 			reason: DispatchError,
 		},
+		/// An exact output swap has been scheduled.
+		ExactOutputSwapScheduled {
+			swap_id: SwapId,
+			source_asset: Asset,
+			max_input: AssetAmount,
+			destination_asset: Asset,
+			output_amount: AssetAmount,
+			destination_address: EncodedAddress,
+			refund_address: EncodedAddress,
+			origin: SwapOrigin,
+			execute_at: BlockNumberFor<T>,
+		},
+		/// An exact output swap has been executed. The unused input will be refunded.
+		ExactOutputSwapExecuted {
+			swap_id: SwapId,
+			source_asset: Asset,
+			swap_input: AssetAmount,
+			destination_asset: Asset,
+			swap_output: AssetAmount,
+			intermediate_amount: Option<AssetAmount>,
+		},
+		/// An exact output swap could not be executed. The full input will be refunded.
+		ExactOutputSwapFailed {
+			swap_id: SwapId,
+			reason: DispatchError,
+		},
+		/// The egress of a swap refund has been scheduled.
+		SwapRefundEgressScheduled {
+			swap_id: SwapId,
+			egress_id: EgressId,
+			asset: Asset,
+			amount: AssetAmount,
+			fee: AssetAmount,
+		},
+		/// A swap out of a Liquidity Provider's free balance has been scheduled.
+		InternalSwapScheduled {
+			swap_id: SwapId,
+			account_id: T::AccountId,
+			source_asset: Asset,
+			amount: AssetAmount,
+			destination_asset: Asset,
+			min_output: AssetAmount,
+			execute_at: BlockNumberFor<T>,
+		},
+		/// The output of a swap out of a Liquidity Provider's free balance has been credited to
+		/// their free balance.
+		InternalSwapCredited {
+			swap_id: SwapId,
+			account_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		/// A swap out of a Liquidity Provider's free balance would have returned less than the
+		/// minimum output, so it was removed from its batch and the input was refunded.
+		InternalSwapRefunded {
+			swap_id: SwapId,
+			account_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
 	}
 	#[pallet::error]
 	pub enum Error<T> {
@@ -385,6 +583,12 @@ pub mod pallet {
 		CcmInsufficientDepositAmount,
 		/// The provided address could not be decoded.
 		InvalidDestinationAddress,
+		/// The CCM fallback address is not on the destination chain.
+		InvalidCcmFallbackAddress,
+		/// The target chain does not accept a gas budget for CCM.
+		CcmGasBudgetUnsupportedForTargetChain,
+		/// The broker has not registered a withdrawal address for the asset's chain.
+		NoWithdrawalAddressRegistered,
 
 		/// Withdrawals are disabled due to Safe Mode.
 		WithdrawalsDisabled,
@@ -392,12 +596,70 @@ pub mod pallet {
 		DepositsDisabled,
 		/// Broker registration is disabled due to Safe Mode.
 		BrokerRegistrationDisabled,
-		/// Broker commission bps is limited to 1000 points.
+		/// Broker commission bps, including the shares of any affiliates, is limited to 1000
+		/// points.
 		BrokerCommissionBpsTooHigh,
+		/// Affiliates must be registered as brokers so that they can withdraw their commission.
+		AffiliateNotRegisteredAsBroker,
+		/// The maximum input of an exact output swap is insufficient to buy the output amount.
+		InsufficientSwapInput,
+		/// Swaps are disabled due to Safe Mode.
+		SwapsDisabled,
+		/// The source and destination assets of a swap must be different.
+		SameSourceAndDestinationAsset,
+		/// The swap amount must be greater than zero.
+		ZeroSwapAmount,
+		/// The swap amount is greater than the maximum swap amount for the source asset.
+		MaximumSwapAmountExceeded,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Reserves the weight of the exact output swaps that will be executed in `on_finalize`.
+		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
+			let mut weight_used = T::DbWeight::get().reads(1);
+			let mut block = FirstUnprocessedBlock::<T>::get();
+			let mut exact_output_swaps = 0u32;
+			while block <= current_block {
+				weight_used.saturating_accrue(T::DbWeight::get().reads(1));
+				exact_output_swaps.saturating_accrue(
+					ExactOutputSwapQueue::<T>::decode_len(block).unwrap_or_default() as u32,
+				);
+				block += 1u32.into();
+			}
+			weight_used
+				.saturating_add(T::WeightInfo::process_exact_output_swaps(exact_output_swaps))
+		}
+
+		/// Prunes the broker earnings of the oldest epoch once it is past the retention period.
+		fn on_idle(_current_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let db_weight = frame_support::weights::constants::RocksDbWeight::get();
+			let mut weight_used = db_weight.reads(2);
+			let oldest_epoch = OldestBrokerEpochEarnings::<T>::get();
+			if oldest_epoch.saturating_add(BROKER_EPOCH_EARNINGS_RETENTION) >
+				T::EpochInfo::epoch_index()
+			{
+				return weight_used
+			}
+			let limit = remaining_weight
+				.saturating_sub(weight_used)
+				.saturating_sub(db_weight.writes(1))
+				.ref_time()
+				.checked_div(db_weight.writes(1).ref_time())
+				.unwrap_or_default()
+				.saturated_into::<u32>();
+			if limit == 0 {
+				return weight_used
+			}
+			let result = BrokerEpochEarnings::<T>::clear_prefix(oldest_epoch, limit, None);
+			weight_used.saturating_accrue(db_weight.writes(result.unique.into()));
+			if result.maybe_cursor.is_none() {
+				OldestBrokerEpochEarnings::<T>::put(oldest_epoch.saturating_add(1));
+				weight_used.saturating_accrue(db_weight.writes(1));
+			}
+			weight_used
+		}
+
 		/// Execute all swaps in the SwapQueue
 		fn on_finalize(current_block: BlockNumberFor<T>) {
 			if !T::SafeMode::get().swaps_enabled {
@@ -426,7 +688,12 @@ pub mod pallet {
 						);
 						break
 					},
+					Err(BatchExecutionError::MinimumOutputNotMet { swap_ids }) => {
+						// The batch is retried without the refunded swaps.
+						Self::refund_swaps_below_minimum_output(block_to_process, swap_ids);
+					},
 					Ok(()) => {
+						Self::process_exact_output_swaps_for_block(block_to_process);
 						block_to_process += 1u32.into();
 					},
 				}
@@ -453,45 +720,16 @@ pub mod pallet {
 			channel_metadata: Option<CcmChannelMetadata>,
 			boost_fee: BasisPoints,
 		) -> DispatchResult {
-			ensure!(T::SafeMode::get().deposits_enabled, Error::<T>::DepositsDisabled);
-			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
-			ensure!(broker_commission_bps <= 1000, Error::<T>::BrokerCommissionBpsTooHigh);
-
-			let destination_address_internal =
-				Self::validate_destination_address(&destination_address, destination_asset)?;
-
-			if channel_metadata.is_some() {
-				// Currently only Ethereum supports CCM.
-				ensure!(
-					ForeignChain::Ethereum == destination_asset.into(),
-					Error::<T>::CcmUnsupportedForTargetChain
-				);
-			}
-
-			let (channel_id, deposit_address, expiry_height) =
-				T::DepositHandler::request_swap_deposit_address(
-					source_asset,
-					destination_asset,
-					destination_address_internal,
-					broker_commission_bps,
-					broker,
-					channel_metadata.clone(),
-					boost_fee,
-				)?;
-
-			Self::deposit_event(Event::<T>::SwapDepositAddressReady {
-				deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
-				destination_address,
+			Self::request_swap_deposit_address_with_affiliates(
+				origin,
 				source_asset,
 				destination_asset,
-				channel_id,
-				broker_commission_rate: broker_commission_bps,
+				destination_address,
+				broker_commission_bps,
+				Default::default(),
 				channel_metadata,
-				source_chain_expiry_block: expiry_height,
 				boost_fee,
-			});
-
-			Ok(())
+			)
 		}
 
 		/// Brokers can withdraw their collected fees.
@@ -649,6 +887,285 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Allow Witnessers to submit an exact output swap request on the behalf of someone else.
+		/// Requires Witnesser origin.
+		///
+		/// The swap buys exactly `output_amount` of the destination asset, selling at most
+		/// `max_input` of the source asset. Any unused input is refunded to the `refund_address`.
+		///
+		/// ## Events
+		///
+		/// - [ExactOutputSwapScheduled](Event::ExactOutputSwapScheduled)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidDestinationAddress](Error::InvalidDestinationAddress)
+		/// - [IncompatibleAssetAndAddress](Error::IncompatibleAssetAndAddress)
+		/// - [SameSourceAndDestinationAsset](Error::SameSourceAndDestinationAsset)
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::schedule_exact_output_swap_from_contract())]
+		pub fn schedule_exact_output_swap_from_contract(
+			origin: OriginFor<T>,
+			from: Asset,
+			to: Asset,
+			max_input: AssetAmount,
+			output_amount: AssetAmount,
+			destination_address: EncodedAddress,
+			refund_address: EncodedAddress,
+			tx_hash: TransactionHash,
+		) -> DispatchResult {
+			T::EnsureWitnessed::ensure_origin(origin)?;
+			ensure!(from != to, Error::<T>::SameSourceAndDestinationAsset);
+
+			let destination_address_internal =
+				Self::validate_destination_address(&destination_address, to)?;
+			let refund_address_internal =
+				Self::validate_destination_address(&refund_address, from)?;
+
+			let (swap_id, execute_at) = Self::schedule_exact_output_swap_internal(
+				from,
+				to,
+				max_input,
+				output_amount,
+				destination_address_internal,
+				refund_address_internal,
+			);
+
+			Self::deposit_event(Event::<T>::ExactOutputSwapScheduled {
+				swap_id,
+				source_asset: from,
+				max_input,
+				destination_asset: to,
+				output_amount,
+				destination_address,
+				refund_address,
+				origin: SwapOrigin::Vault { tx_hash },
+				execute_at,
+			});
+
+			Ok(())
+		}
+
+		/// Swap an amount of an asset from the Liquidity Provider's free balance into another
+		/// asset. The swap is batched with other swaps and the network fee is taken as normal. The
+		/// output is credited to the Liquidity Provider's free balance. If the output would be
+		/// less than `min_output`, the swap is removed from its batch and the input is refunded.
+		///
+		/// ## Events
+		///
+		/// - [InternalSwapScheduled](Event::InternalSwapScheduled)
+		///
+		/// ## Errors
+		///
+		/// - [SwapsDisabled](Error::SwapsDisabled)
+		/// - [SameSourceAndDestinationAsset](Error::SameSourceAndDestinationAsset)
+		/// - [ZeroSwapAmount](Error::ZeroSwapAmount)
+		/// - [MaximumSwapAmountExceeded](Error::MaximumSwapAmountExceeded)
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::internal_swap())]
+		pub fn internal_swap(
+			origin: OriginFor<T>,
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+			min_output: AssetAmount,
+		) -> DispatchResult {
+			ensure!(T::SafeMode::get().swaps_enabled, Error::<T>::SwapsDisabled);
+			let account_id = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			ensure!(from != to, Error::<T>::SameSourceAndDestinationAsset);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroSwapAmount);
+			ensure!(
+				MaximumSwapAmount::<T>::get(from).map_or(true, |max| amount <= max),
+				Error::<T>::MaximumSwapAmountExceeded
+			);
+
+			T::LpBalance::try_debit_account(&account_id, from, amount)?;
+
+			let (swap_id, execute_at) =
+				Self::schedule_swap_internal(from, to, amount, SwapType::InternalSwap);
+			PendingInternalSwaps::<T>::insert(
+				swap_id,
+				InternalSwapDetails { account_id: account_id.clone(), min_output },
+			);
+
+			Self::deposit_event(Event::<T>::InternalSwapScheduled {
+				swap_id,
+				account_id,
+				source_asset: from,
+				amount,
+				destination_asset: to,
+				min_output,
+				execute_at,
+			});
+
+			Ok(())
+		}
+
+		/// Register the address that the broker's fees on the address's chain are withdrawn to by
+		/// [withdraw_all](Pallet::withdraw_all). Replaces any previously registered address for the
+		/// chain.
+		///
+		/// ## Events
+		///
+		/// - [BrokerWithdrawalAddressRegistered](Event::BrokerWithdrawalAddressRegistered)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidDestinationAddress](Error::InvalidDestinationAddress)
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::register_withdrawal_address())]
+		pub fn register_withdrawal_address(
+			origin: OriginFor<T>,
+			address: EncodedAddress,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let withdrawal_address = T::AddressConverter::try_from_encoded_address(address.clone())
+				.map_err(|_| Error::<T>::InvalidDestinationAddress)?;
+			let chain = withdrawal_address.chain();
+
+			BrokerWithdrawalAddress::<T>::insert(&broker_id, chain, withdrawal_address);
+
+			Self::deposit_event(Event::<T>::BrokerWithdrawalAddressRegistered {
+				broker_id,
+				chain,
+				address,
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw the broker's whole balance of every asset to their registered withdrawal
+		/// address for the asset's chain. Balances that can't be egressed, for example because
+		/// they are below the egress dust limit or no address is registered for their chain, are
+		/// left in place.
+		///
+		/// ## Events
+		///
+		/// - [WithdrawalRequested](Event::WithdrawalRequested)
+		/// - [WithdrawalSkipped](Event::WithdrawalSkipped)
+		///
+		/// ## Errors
+		///
+		/// - [WithdrawalsDisabled](Error::WithdrawalsDisabled)
+		/// - [NoFundsAvailable](Error::NoFundsAvailable)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::withdraw_all())]
+		pub fn withdraw_all(origin: OriginFor<T>) -> DispatchResult {
+			ensure!(T::SafeMode::get().withdrawals_enabled, Error::<T>::WithdrawalsDisabled);
+
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let mut any_withdrawn = false;
+			for (asset, amount) in EarnedBrokerFees::<T>::iter_prefix(&broker_id)
+				.filter(|(_, amount)| !amount.is_zero())
+				.collect::<Vec<_>>()
+			{
+				match Self::withdraw_to_registered_address(&broker_id, asset) {
+					Ok(()) => any_withdrawn = true,
+					Err(reason) => Self::deposit_event(Event::<T>::WithdrawalSkipped {
+						broker_id: broker_id.clone(),
+						asset,
+						amount,
+						reason,
+					}),
+				}
+			}
+			ensure!(any_withdrawn, Error::<T>::NoFundsAvailable);
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address, sharing the commission with up to
+		/// [cf_primitives::MAX_AFFILIATES] affiliates. Each of the `affiliates` receives a share of
+		/// the commission on deposits into the channel, in addition to the broker's own
+		/// `broker_commission_bps`.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		///
+		/// ## Errors
+		///
+		/// - [BrokerCommissionBpsTooHigh](Error::BrokerCommissionBpsTooHigh)
+		/// - [AffiliateNotRegisteredAsBroker](Error::AffiliateNotRegisteredAsBroker)
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::request_swap_deposit_address_with_affiliates(affiliates.len() as u32))]
+		pub fn request_swap_deposit_address_with_affiliates(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission_bps: BasisPoints,
+			affiliates: Affiliates<T::AccountId>,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+		) -> DispatchResult {
+			ensure!(T::SafeMode::get().deposits_enabled, Error::<T>::DepositsDisabled);
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+			let total_commission_bps =
+				affiliates.iter().fold(broker_commission_bps as u32, |total, affiliate| {
+					total + affiliate.bps as u32
+				});
+			ensure!(
+				total_commission_bps <= MAX_BROKER_COMMISSION_BPS as u32,
+				Error::<T>::BrokerCommissionBpsTooHigh
+			);
+			ensure!(
+				affiliates.iter().all(|affiliate| T::AccountRoleRegistry::has_account_role(
+					&affiliate.account,
+					AccountRole::Broker
+				)),
+				Error::<T>::AffiliateNotRegisteredAsBroker
+			);
+
+			let destination_address_internal =
+				Self::validate_destination_address(&destination_address, destination_asset)?;
+
+			if let Some(channel_metadata) = &channel_metadata {
+				ensure!(
+					ForeignChain::from(destination_asset).supports_ccm(),
+					Error::<T>::CcmUnsupportedForTargetChain
+				);
+				ensure!(
+					channel_metadata.gas_budget.is_zero() ||
+						ForeignChain::from(destination_asset).supports_ccm_gas_budget(),
+					Error::<T>::CcmGasBudgetUnsupportedForTargetChain
+				);
+				ensure!(
+					Self::is_valid_ccm_fallback(channel_metadata, destination_asset),
+					Error::<T>::InvalidCcmFallbackAddress
+				);
+			}
+
+			let (channel_id, deposit_address, expiry_height) =
+				T::DepositHandler::request_swap_deposit_address(
+					source_asset,
+					destination_asset,
+					destination_address_internal,
+					broker_commission_bps,
+					affiliates.clone(),
+					broker,
+					channel_metadata.clone(),
+					boost_fee,
+				)?;
+
+			Self::deposit_event(Event::<T>::SwapDepositAddressReady {
+				deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
+				destination_address,
+				source_asset,
+				destination_asset,
+				channel_id,
+				broker_commission_rate: broker_commission_bps,
+				affiliates,
+				channel_metadata,
+				source_chain_expiry_block: expiry_height,
+				boost_fee,
+			});
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -671,12 +1188,35 @@ pub mod pallet {
 					"All swaps should have Stable amount set here"
 				);
 				let stable_amount = swap.stable_amount.get_or_insert_with(Default::default);
-				*stable_amount = T::SwappingApi::take_network_fee(*stable_amount);
+				*stable_amount =
+					T::SwappingApi::take_network_fee(swap.from, swap.to, *stable_amount);
 			}
 
 			// Swap from Stable asset, and complete the swap logic.
 			Self::do_group_and_swap(&mut swaps, SwapLeg::FromStable)?;
 
+			// Internal and vault swaps must not return less than their minimum output. If any
+			// would, the batch is reverted so it can be retried without them.
+			let swap_ids = swaps
+				.iter()
+				.filter(|swap| {
+					let min_output = match swap.swap_type {
+						SwapType::InternalSwap => PendingInternalSwaps::<T>::get(swap.swap_id)
+							.map(|details| details.min_output),
+						SwapType::Swap(_) => PendingVaultSwaps::<T>::get(swap.swap_id)
+							.map(|details| details.min_output),
+						_ => None,
+					};
+					min_output.is_some_and(|min_output| {
+						swap.final_output.unwrap_or_default() < min_output
+					})
+				})
+				.map(|swap| swap.swap_id)
+				.collect::<Vec<_>>();
+			if !swap_ids.is_empty() {
+				return Err(BatchExecutionError::MinimumOutputNotMet { swap_ids })
+			}
+
 			for swap in swaps {
 				if let Some(swap_output) = swap.final_output {
 					Self::deposit_event(Event::<T>::SwapExecuted {
@@ -691,35 +1231,16 @@ pub mod pallet {
 					});
 					// Handle swap completion logic.
 					match &swap.swap_type {
-						SwapType::Swap(destination_address) =>
-							match T::EgressHandler::schedule_egress(
+						SwapType::Swap(destination_address) => {
+							PendingVaultSwaps::<T>::remove(swap.swap_id);
+							Self::schedule_swap_egress(
+								swap.swap_id,
 								swap.to,
 								swap_output,
 								destination_address.clone(),
-								None,
-							) {
-								Ok(ScheduledEgressDetails {
-									egress_id,
-									egress_amount,
-									fee_withheld,
-								}) => {
-									Self::deposit_event(Event::<T>::SwapEgressScheduled {
-										swap_id: swap.swap_id,
-										egress_id,
-										asset: swap.to,
-										amount: egress_amount,
-										fee: fee_withheld,
-									});
-								},
-								Err(err) => {
-									Self::deposit_event(Event::<T>::SwapEgressIgnored {
-										swap_id: swap.swap_id,
-										asset: swap.to,
-										amount: swap_output,
-										reason: err.into(),
-									});
-								},
-							},
+								false,
+							)
+						},
 						SwapType::CcmPrincipal(ccm_id) => {
 							Self::handle_ccm_swap_result(
 								*ccm_id,
@@ -730,6 +1251,9 @@ pub mod pallet {
 						SwapType::CcmGas(ccm_id) => {
 							Self::handle_ccm_swap_result(*ccm_id, swap_output, CcmSwapLeg::Gas);
 						},
+						SwapType::InternalSwap => {
+							Self::credit_internal_swap_output(swap.swap_id, swap.to, swap_output);
+						},
 					};
 				} else {
 					debug_assert!(false, "Swap is not completed yet!");
@@ -739,6 +1263,212 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Executes the exact output swaps scheduled for the given block. Each swap is executed on
+		/// its own, so that a failed swap only refunds its own input.
+		pub(crate) fn process_exact_output_swaps_for_block(block: BlockNumberFor<T>) {
+			for swap in ExactOutputSwapQueue::<T>::take(block) {
+				match Self::execute_exact_output_swap(&swap) {
+					Ok((swap_input, swap_output, intermediate_amount)) => {
+						Self::deposit_event(Event::<T>::ExactOutputSwapExecuted {
+							swap_id: swap.swap_id,
+							source_asset: swap.from,
+							swap_input,
+							destination_asset: swap.to,
+							swap_output,
+							intermediate_amount,
+						});
+						Self::schedule_swap_egress(
+							swap.swap_id,
+							swap.to,
+							swap_output,
+							swap.destination_address,
+							false,
+						);
+						let refund_amount = swap.max_input.saturating_sub(swap_input);
+						if !refund_amount.is_zero() {
+							Self::schedule_swap_egress(
+								swap.swap_id,
+								swap.from,
+								refund_amount,
+								swap.refund_address,
+								true,
+							);
+						}
+					},
+					Err(reason) => {
+						Self::deposit_event(Event::<T>::ExactOutputSwapFailed {
+							swap_id: swap.swap_id,
+							reason,
+						});
+						Self::schedule_swap_egress(
+							swap.swap_id,
+							swap.from,
+							swap.max_input,
+							swap.refund_address,
+							true,
+						);
+					},
+				}
+			}
+		}
+
+		/// Buys the output of an exact output swap, working backwards from the destination asset.
+		/// Returns the required input, the output bought and the intermediate amount of
+		/// STABLE_ASSET, if any. The output is at least the requested amount: anything bought in
+		/// excess of it, from partially filled limit orders, is passed on to the swapper.
+		#[transactional]
+		fn execute_exact_output_swap(
+			swap: &ExactOutputSwap,
+		) -> Result<(AssetAmount, AssetAmount, Option<AssetAmount>), DispatchError> {
+			let (stable_amount, mut swap_output) = if swap.to == STABLE_ASSET {
+				(swap.output_amount, swap.output_amount)
+			} else {
+				T::SwappingApi::swap_single_leg_exact_output(
+					STABLE_ASSET,
+					swap.to,
+					swap.output_amount,
+				)?
+			};
+
+			let stable_amount =
+				T::SwappingApi::take_network_fee_exact_output(swap.from, swap.to, stable_amount);
+
+			let swap_input = if swap.from == STABLE_ASSET {
+				stable_amount
+			} else {
+				let (swap_input, stable_bought) = T::SwappingApi::swap_single_leg_exact_output(
+					swap.from,
+					STABLE_ASSET,
+					stable_amount,
+				)?;
+				let stable_excess = stable_bought.saturating_sub(stable_amount);
+				if !stable_excess.is_zero() {
+					swap_output.saturating_accrue(if swap.to == STABLE_ASSET {
+						stable_excess
+					} else {
+						T::SwappingApi::swap_single_leg(STABLE_ASSET, swap.to, stable_excess)?
+					});
+				}
+				swap_input
+			};
+
+			ensure!(swap_input <= swap.max_input, Error::<T>::InsufficientSwapInput);
+
+			Ok((
+				swap_input,
+				swap_output,
+				(swap.from != STABLE_ASSET && swap.to != STABLE_ASSET).then_some(stable_amount),
+			))
+		}
+
+		fn credit_internal_swap_output(swap_id: SwapId, asset: Asset, amount: AssetAmount) {
+			if let Some(InternalSwapDetails { account_id, .. }) =
+				PendingInternalSwaps::<T>::take(swap_id)
+			{
+				match T::LpBalance::try_credit_account(&account_id, asset, amount) {
+					Ok(()) => Self::deposit_event(Event::<T>::InternalSwapCredited {
+						swap_id,
+						account_id,
+						asset,
+						amount,
+					}),
+					Err(error) => log::error!(
+						"Failed to credit output of internal swap {swap_id} to {account_id:?}: {error:?}"
+					),
+				}
+			} else {
+				log_or_panic!("Internal swap {swap_id} must have pending details.");
+			}
+		}
+
+		/// Removes the given swaps from the swap queue for the block, and refunds their input.
+		/// Internal swaps are refunded to the Liquidity Providers' free balances, vault swaps to
+		/// their refund address.
+		fn refund_swaps_below_minimum_output(block: BlockNumberFor<T>, swap_ids: Vec<SwapId>) {
+			SwapQueue::<T>::mutate(block, |swaps| {
+				swaps.retain(|swap| {
+					if !swap_ids.contains(&swap.swap_id) {
+						return true
+					}
+					if let Some(InternalSwapDetails { account_id, .. }) =
+						PendingInternalSwaps::<T>::take(swap.swap_id)
+					{
+						match T::LpBalance::try_credit_account(&account_id, swap.from, swap.amount)
+						{
+							Ok(()) => Self::deposit_event(Event::<T>::InternalSwapRefunded {
+								swap_id: swap.swap_id,
+								account_id,
+								asset: swap.from,
+								amount: swap.amount,
+							}),
+							Err(error) => log::error!(
+								"Failed to refund internal swap {} to {account_id:?}: {error:?}",
+								swap.swap_id
+							),
+						}
+					} else if let Some(VaultSwapDetails { refund_address, .. }) =
+						PendingVaultSwaps::<T>::take(swap.swap_id)
+					{
+						Self::schedule_swap_egress(
+							swap.swap_id,
+							swap.from,
+							swap.amount,
+							refund_address,
+							true,
+						);
+					}
+					false
+				})
+			});
+		}
+
+		fn schedule_swap_egress(
+			swap_id: SwapId,
+			asset: Asset,
+			amount: AssetAmount,
+			destination_address: ForeignChainAddress,
+			is_refund: bool,
+		) {
+			match T::EgressHandler::schedule_egress(asset, amount, destination_address, None) {
+				Ok(ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }) =>
+					Self::deposit_event(if is_refund {
+						Event::<T>::SwapRefundEgressScheduled {
+							swap_id,
+							egress_id,
+							asset,
+							amount: egress_amount,
+							fee: fee_withheld,
+						}
+					} else {
+						Event::<T>::SwapEgressScheduled {
+							swap_id,
+							egress_id,
+							asset,
+							amount: egress_amount,
+							fee: fee_withheld,
+						}
+					}),
+				Err(err) => {
+					Self::deposit_event(Event::<T>::SwapEgressIgnored {
+						swap_id,
+						asset,
+						amount,
+						reason: err.into(),
+					});
+				},
+			}
+		}
+
+		/// The principal of a CCM can only be refunded on the chain it is egressed to.
+		fn is_valid_ccm_fallback(
+			channel_metadata: &CcmChannelMetadata,
+			destination_asset: Asset,
+		) -> bool {
+			channel_metadata.fallback.as_ref().map_or(true, |fallback| {
+				fallback.refund_address.chain() == ForeignChain::from(destination_asset)
+			})
+		}
+
 		pub fn principal_and_gas_amounts(
 			deposit_amount: AssetAmount,
 			channel_metadata: &CcmChannelMetadata,
@@ -748,10 +1478,16 @@ pub mod pallet {
 			let gas_budget = channel_metadata.gas_budget;
 			let principal_swap_amount = deposit_amount.saturating_sub(gas_budget);
 
-			if ForeignChain::Ethereum != destination_asset.into() {
+			if !ForeignChain::from(destination_asset).supports_ccm() {
 				return Err(CcmFailReason::UnsupportedForTargetChain)
+			} else if !gas_budget.is_zero() &&
+				!ForeignChain::from(destination_asset).supports_ccm_gas_budget()
+			{
+				return Err(CcmFailReason::GasBudgetUnsupportedForTargetChain)
 			} else if deposit_amount < gas_budget {
 				return Err(CcmFailReason::InsufficientDepositAmount)
+			} else if !Self::is_valid_ccm_fallback(channel_metadata, destination_asset) {
+				return Err(CcmFailReason::InvalidFallbackAddress)
 			}
 
 			// if the gas asset is different.
@@ -780,6 +1516,64 @@ pub mod pallet {
 			Ok(destination_address_internal)
 		}
 
+		/// Egresses the broker's whole balance of the asset to their registered withdrawal address.
+		/// Transactional so that the balance is kept if the egress can't be scheduled.
+		#[transactional]
+		fn withdraw_to_registered_address(
+			broker_id: &T::AccountId,
+			asset: Asset,
+		) -> DispatchResult {
+			let destination_address =
+				BrokerWithdrawalAddress::<T>::get(broker_id, ForeignChain::from(asset))
+					.ok_or(Error::<T>::NoWithdrawalAddressRegistered)?;
+
+			let ScheduledEgressDetails { egress_id, egress_amount, fee_withheld } =
+				T::EgressHandler::schedule_egress(
+					asset,
+					EarnedBrokerFees::<T>::take(broker_id, asset),
+					destination_address.clone(),
+					None,
+				)
+				.map_err(Into::into)?;
+
+			Self::deposit_event(Event::<T>::WithdrawalRequested {
+				egress_amount,
+				egress_fee: fee_withheld,
+				destination_address: T::AddressConverter::to_encoded_address(destination_address),
+				egress_id,
+			});
+
+			Ok(())
+		}
+
+		fn credit_broker_fee(
+			account_id: &T::AccountId,
+			asset: Asset,
+			channel_id: Option<ChannelId>,
+			fee: AssetAmount,
+		) {
+			EarnedBrokerFees::<T>::mutate(account_id, asset, |earned_fees| {
+				earned_fees.saturating_accrue(fee)
+			});
+			if !fee.is_zero() {
+				TotalBrokerEarnings::<T>::mutate(account_id, asset, |earnings| {
+					earnings.saturating_accrue(fee)
+				});
+				BrokerEpochEarnings::<T>::mutate(
+					T::EpochInfo::epoch_index(),
+					account_id,
+					|earnings| earnings.entry(asset).or_default().saturating_accrue(fee),
+				);
+				if let Some(channel_id) = channel_id {
+					BrokerChannelEarnings::<T>::mutate(
+						account_id,
+						(asset, channel_id),
+						|earnings| earnings.saturating_accrue(fee),
+					);
+				}
+			}
+		}
+
 		// Helper function that splits swaps of a given direction, group them by asset
 		// and do the swaps of a given direction. Processed and unprocessed swaps are
 		// returned.
@@ -935,10 +1729,65 @@ pub mod pallet {
 			amount: AssetAmount,
 			swap_type: SwapType,
 		) -> (u64, BlockNumberFor<T>) {
-			let swap_id = SwapIdCounter::<T>::mutate(|id| {
+			let swap_id = Self::next_swap_id();
+			let swap_amount = Self::confiscate_excess_swap_amount(swap_id, from, to, amount);
+
+			let execute_at = frame_system::Pallet::<T>::block_number() + SWAP_DELAY_BLOCKS.into();
+
+			SwapQueue::<T>::append(
+				execute_at,
+				Swap::new(swap_id, from, to, swap_amount, swap_type),
+			);
+
+			(swap_id, execute_at)
+		}
+
+		/// Schedule an exact output swap, assuming all checks already passed. Return swap_id along
+		/// with the block at which the swap is scheduled to be executed.
+		fn schedule_exact_output_swap_internal(
+			from: Asset,
+			to: Asset,
+			max_input: AssetAmount,
+			output_amount: AssetAmount,
+			destination_address: ForeignChainAddress,
+			refund_address: ForeignChainAddress,
+		) -> (u64, BlockNumberFor<T>) {
+			let swap_id = Self::next_swap_id();
+			let max_input = Self::confiscate_excess_swap_amount(swap_id, from, to, max_input);
+
+			let execute_at = frame_system::Pallet::<T>::block_number() + SWAP_DELAY_BLOCKS.into();
+
+			ExactOutputSwapQueue::<T>::append(
+				execute_at,
+				ExactOutputSwap {
+					swap_id,
+					from,
+					to,
+					max_input,
+					output_amount,
+					destination_address,
+					refund_address,
+				},
+			);
+
+			(swap_id, execute_at)
+		}
+
+		fn next_swap_id() -> SwapId {
+			SwapIdCounter::<T>::mutate(|id| {
 				id.saturating_accrue(1);
 				*id
-			});
+			})
+		}
+
+		/// Confiscates any amount above the MaximumSwapAmount of the source asset, and returns
+		/// the amount that can be swapped.
+		fn confiscate_excess_swap_amount(
+			swap_id: SwapId,
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+		) -> AssetAmount {
 			let (swap_amount, confiscated_amount) = match MaximumSwapAmount::<T>::get(from) {
 				Some(max) => (sp_std::cmp::min(amount, max), amount.saturating_sub(max)),
 				None => (amount, Zero::zero()),
@@ -955,15 +1804,7 @@ pub mod pallet {
 					confiscated_amount,
 				});
 			}
-
-			let execute_at = frame_system::Pallet::<T>::block_number() + SWAP_DELAY_BLOCKS.into();
-
-			SwapQueue::<T>::append(
-				execute_at,
-				Swap::new(swap_id, from, to, swap_amount, swap_type),
-			);
-
-			(swap_id, execute_at)
+			swap_amount
 		}
 	}
 
@@ -980,11 +1821,21 @@ pub mod pallet {
 			destination_address: ForeignChainAddress,
 			broker_id: Self::AccountId,
 			broker_commission_bps: BasisPoints,
+			affiliates: Affiliates<Self::AccountId>,
 			channel_id: ChannelId,
 		) -> SwapId {
 			// Permill maxes out at 100% so this is safe.
-			let fee = Permill::from_parts(broker_commission_bps as u32 * BASIS_POINTS_PER_MILLION) *
-				amount;
+			let commission = |bps: BasisPoints| {
+				Permill::from_parts(bps as u32 * BASIS_POINTS_PER_MILLION) * amount
+			};
+			let broker_fee = commission(broker_commission_bps);
+			let affiliate_fees = affiliates
+				.into_iter()
+				.map(|Affiliate { account, bps }| (account, commission(bps)))
+				.collect::<Vec<_>>();
+			let fee = affiliate_fees
+				.iter()
+				.fold(broker_fee, |total, (_, affiliate_fee)| total.saturating_add(*affiliate_fee));
 			assert!(fee <= amount, "Broker fee cannot be more than the amount");
 
 			let net_amount = amount.saturating_sub(fee);
@@ -1003,9 +1854,6 @@ pub mod pallet {
 				net_amount,
 				SwapType::Swap(destination_address.clone()),
 			);
-			EarnedBrokerFees::<T>::mutate(&broker_id, from, |earned_fees| {
-				earned_fees.saturating_accrue(fee)
-			});
 			Self::deposit_event(Event::<T>::SwapScheduled {
 				swap_id,
 				source_asset: from,
@@ -1017,6 +1865,73 @@ pub mod pallet {
 				broker_commission: Some(fee),
 				execute_at,
 			});
+			Self::credit_broker_fee(&broker_id, from, Some(channel_id), broker_fee);
+			for (affiliate_id, affiliate_fee) in affiliate_fees {
+				Self::credit_broker_fee(&affiliate_id, from, Some(channel_id), affiliate_fee);
+				Self::deposit_event(Event::<T>::AffiliateCommissionCredited {
+					swap_id,
+					broker_id: broker_id.clone(),
+					affiliate_id,
+					asset: from,
+					amount: affiliate_fee,
+				});
+			}
+
+			swap_id
+		}
+
+		fn on_channel_recycled(
+			from: Asset,
+			channel_id: ChannelId,
+			broker_id: Self::AccountId,
+			affiliates: Affiliates<Self::AccountId>,
+		) {
+			BrokerChannelEarnings::<T>::remove(&broker_id, (from, channel_id));
+			for Affiliate { account, .. } in affiliates {
+				BrokerChannelEarnings::<T>::remove(&account, (from, channel_id));
+			}
+		}
+
+		fn schedule_vault_swap(
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+			destination_address: ForeignChainAddress,
+			min_output: AssetAmount,
+			refund_address: ForeignChainAddress,
+			broker_id: Self::AccountId,
+			broker_commission_bps: BasisPoints,
+			tx_hash: TransactionHash,
+		) -> SwapId {
+			// The commission is capped, since it is not validated before the funds are sent.
+			let broker_fee = Permill::from_parts(
+				broker_commission_bps.min(MAX_BROKER_COMMISSION_BPS) as u32 *
+					BASIS_POINTS_PER_MILLION,
+			) * amount;
+			let (swap_id, execute_at) = Self::schedule_swap_internal(
+				from,
+				to,
+				amount.saturating_sub(broker_fee),
+				SwapType::Swap(destination_address.clone()),
+			);
+			PendingVaultSwaps::<T>::insert(
+				swap_id,
+				VaultSwapDetails { min_output, refund_address },
+			);
+			Self::deposit_event(Event::<T>::SwapScheduled {
+				swap_id,
+				source_asset: from,
+				deposit_amount: amount,
+				destination_asset: to,
+				destination_address: T::AddressConverter::to_encoded_address(
+					destination_address.clone(),
+				),
+				origin: SwapOrigin::Vault { tx_hash },
+				swap_type: SwapType::Swap(destination_address),
+				broker_commission: Some(broker_fee),
+				execute_at,
+			});
+			Self::credit_broker_fee(&broker_id, from, None, broker_fee);
 
 			swap_id
 		}
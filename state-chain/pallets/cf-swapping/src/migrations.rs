@@ -1,6 +1,9 @@
+mod ccm_fallback;
 mod schedule_swaps;
 
 use cf_runtime_upgrade_utilities::VersionedMigration;
 
-pub type PalletMigration<T> =
-	(VersionedMigration<crate::Pallet<T>, schedule_swaps::Migration<T>, 1, 2>,);
+pub type PalletMigration<T> = (
+	VersionedMigration<crate::Pallet<T>, schedule_swaps::Migration<T>, 1, 2>,
+	VersionedMigration<crate::Pallet<T>, ccm_fallback::Migration<T>, 2, 3>,
+);
@@ -0,0 +1,131 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config>(PhantomData<T>);
+
+/// Types as they were before CCMs had a fallback address.
+mod old {
+	use super::*;
+	use cf_chains::{CcmCfParameters, CcmMessage};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct CcmChannelMetadata {
+		pub message: CcmMessage,
+		pub gas_budget: AssetAmount,
+		pub cf_parameters: CcmCfParameters,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct CcmDepositMetadata {
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub channel_metadata: CcmChannelMetadata,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct CcmSwap {
+		pub source_asset: Asset,
+		pub deposit_amount: AssetAmount,
+		pub destination_asset: Asset,
+		pub destination_address: ForeignChainAddress,
+		pub deposit_metadata: CcmDepositMetadata,
+		pub principal_swap_id: Option<SwapId>,
+		pub gas_swap_id: Option<SwapId>,
+	}
+
+	#[frame_support::storage_alias]
+	pub type PendingCcms<T: Config> = StorageMap<Pallet<T>, Twox64Concat, u64, CcmSwap>;
+}
+
+impl From<old::CcmSwap> for CcmSwap {
+	fn from(ccm: old::CcmSwap) -> Self {
+		let old::CcmChannelMetadata { message, gas_budget, cf_parameters } =
+			ccm.deposit_metadata.channel_metadata;
+		CcmSwap {
+			source_asset: ccm.source_asset,
+			deposit_amount: ccm.deposit_amount,
+			destination_asset: ccm.destination_asset,
+			destination_address: ccm.destination_address,
+			deposit_metadata: CcmDepositMetadata {
+				source_chain: ccm.deposit_metadata.source_chain,
+				source_address: ccm.deposit_metadata.source_address,
+				channel_metadata: CcmChannelMetadata {
+					message,
+					gas_budget,
+					cf_parameters,
+					fallback: None,
+				},
+			},
+			principal_swap_id: ccm.principal_swap_id,
+			gas_swap_id: ccm.gas_swap_id,
+		}
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let mut ccms = 0u64;
+		PendingCcms::<T>::translate(|_ccm_id, ccm: old::CcmSwap| {
+			ccms += 1;
+			Some(ccm.into())
+		});
+
+		T::DbWeight::get().reads_writes(ccms, ccms)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((old::PendingCcms::<T>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+		let pre_upgrade_count =
+			<u32>::decode(&mut &state[..]).map_err(|_| "Failed to decode pre-upgrade state.")?;
+		ensure!(
+			PendingCcms::<T>::iter_values().count() as u32 == pre_upgrade_count,
+			"PendingCcms count mismatch!"
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock::*;
+
+	#[test]
+	fn pending_ccms_get_no_fallback() {
+		new_test_ext().execute_with(|| {
+			old::PendingCcms::<Test>::insert(
+				1,
+				old::CcmSwap {
+					source_asset: Asset::Eth,
+					deposit_amount: 10_000,
+					destination_asset: Asset::Usdc,
+					destination_address: ForeignChainAddress::Eth(Default::default()),
+					deposit_metadata: old::CcmDepositMetadata {
+						source_chain: ForeignChain::Ethereum,
+						source_address: None,
+						channel_metadata: old::CcmChannelMetadata {
+							message: vec![0x01].try_into().unwrap(),
+							gas_budget: 1_000,
+							cf_parameters: Default::default(),
+						},
+					},
+					principal_swap_id: Some(1),
+					gas_swap_id: None,
+				},
+			);
+
+			Migration::<Test>::on_runtime_upgrade();
+
+			let ccm = PendingCcms::<Test>::get(1).unwrap();
+			assert_eq!(ccm.deposit_metadata.channel_metadata.fallback, None);
+			assert_eq!(ccm.deposit_metadata.channel_metadata.gas_budget, 1_000);
+			assert_eq!(ccm.principal_swap_id, Some(1));
+		});
+	}
+}
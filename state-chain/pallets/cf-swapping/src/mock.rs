@@ -1,4 +1,5 @@
 use core::cell::Cell;
+use std::collections::BTreeMap;
 
 use crate::{self as pallet_cf_swapping, PalletSafeMode, WeightInfo};
 use cf_chains::AnyChain;
@@ -9,14 +10,18 @@ use cf_traits::{
 		address_converter::MockAddressConverter, deposit_handler::MockDepositHandler,
 		egress_handler::MockEgressHandler,
 	},
-	AccountRoleRegistry, SwappingApi,
+	AccountRoleRegistry, LpBalanceApi, SwappingApi,
+};
+use frame_support::{
+	pallet_prelude::{DispatchError, DispatchResult},
+	parameter_types,
+	weights::Weight,
 };
-use frame_support::{pallet_prelude::DispatchError, parameter_types, weights::Weight};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	Percent,
+	PerThing, Percent,
 };
 
 type AccountId = u64;
@@ -68,6 +73,7 @@ parameter_types! {
 	pub static NetworkFee: Percent = Percent::from_percent(0);
 	pub static Swaps: Vec<(Asset, Asset, AssetAmount)> = vec![];
 	pub static SwapRate: f64 = 1f64;
+	pub static LpBalances: BTreeMap<(AccountId, Asset), AssetAmount> = BTreeMap::new();
 }
 
 thread_local! {
@@ -87,7 +93,7 @@ impl MockSwappingApi {
 }
 
 impl SwappingApi for MockSwappingApi {
-	fn take_network_fee(input_amount: AssetAmount) -> AssetAmount {
+	fn take_network_fee(_from: Asset, _to: Asset, input_amount: AssetAmount) -> AssetAmount {
 		input_amount - NetworkFee::get() * input_amount
 	}
 
@@ -105,6 +111,89 @@ impl SwappingApi for MockSwappingApi {
 		Swaps::set(swaps);
 		Ok((input_amount as f64 * SwapRate::get()) as AssetAmount)
 	}
+
+	fn take_network_fee_exact_output(
+		_from: Asset,
+		_to: Asset,
+		output_amount: AssetAmount,
+	) -> AssetAmount {
+		NetworkFee::get().left_from_one().saturating_reciprocal_mul_ceil(output_amount)
+	}
+
+	fn swap_single_leg_exact_output(
+		from: Asset,
+		to: Asset,
+		output_amount: AssetAmount,
+	) -> Result<(AssetAmount, AssetAmount), DispatchError> {
+		if Self::swaps_should_fail() {
+			return Err(DispatchError::from("Test swap failed"))
+		}
+
+		let input_amount = (output_amount as f64 / SwapRate::get()) as AssetAmount;
+		let mut swaps = Swaps::get();
+		swaps.push((from, to, input_amount));
+		Swaps::set(swaps);
+		Ok((input_amount, output_amount))
+	}
+}
+
+pub struct MockLpBalance;
+
+impl MockLpBalance {
+	pub fn balance(who: AccountId, asset: Asset) -> AssetAmount {
+		LpBalances::get().get(&(who, asset)).copied().unwrap_or_default()
+	}
+}
+
+impl LpBalanceApi for MockLpBalance {
+	type AccountId = AccountId;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn register_liquidity_refund_address(
+		_who: &Self::AccountId,
+		_address: cf_chains::ForeignChainAddress,
+	) {
+	}
+
+	fn ensure_has_refund_address_for_pair(
+		_who: &Self::AccountId,
+		_base_asset: Asset,
+		_quote_asset: Asset,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn try_credit_account(
+		who: &Self::AccountId,
+		asset: Asset,
+		amount: AssetAmount,
+	) -> DispatchResult {
+		LpBalances::mutate(|balances| {
+			*balances.entry((*who, asset)).or_default() += amount;
+		});
+		Ok(())
+	}
+
+	fn try_debit_account(
+		who: &Self::AccountId,
+		asset: Asset,
+		amount: AssetAmount,
+	) -> DispatchResult {
+		LpBalances::mutate(|balances| {
+			let balance = balances.entry((*who, asset)).or_default();
+			*balance =
+				balance.checked_sub(amount).ok_or(DispatchError::from("Insufficient balance"))?;
+			Ok(())
+		})
+	}
+
+	fn asset_balances(who: &Self::AccountId) -> Vec<(Asset, AssetAmount)> {
+		LpBalances::get()
+			.into_iter()
+			.filter(|((account_id, _), _)| account_id == who)
+			.map(|((_, asset), amount)| (asset, amount))
+			.collect()
+	}
 }
 
 pub struct MockWeightInfo;
@@ -129,6 +218,30 @@ impl WeightInfo for MockWeightInfo {
 	fn register_as_broker() -> Weight {
 		Weight::from_parts(100, 0)
 	}
+
+	fn schedule_exact_output_swap_from_contract() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn internal_swap() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn register_withdrawal_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn withdraw_all() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_swap_deposit_address_with_affiliates(_a: u32) -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn process_exact_output_swaps(a: u32) -> Weight {
+		Weight::from_parts(100, 0).saturating_mul(a.into())
+	}
 }
 
 impl pallet_cf_swapping::Config for Test {
@@ -137,11 +250,13 @@ impl pallet_cf_swapping::Config for Test {
 	type EgressHandler = MockEgressHandler<AnyChain>;
 	type AddressConverter = MockAddressConverter;
 	type SwappingApi = MockSwappingApi;
+	type LpBalance = MockLpBalance;
 	type SafeMode = MockRuntimeSafeMode;
 	type WeightInfo = MockWeightInfo;
 }
 
 pub const ALICE: <Test as frame_system::Config>::AccountId = 123u64;
+pub const LP: <Test as frame_system::Config>::AccountId = 124u64;
 
 cf_test_utilities::impl_test_helpers! {
 	Test,
@@ -150,5 +265,7 @@ cf_test_utilities::impl_test_helpers! {
 	},
 	|| {
 		<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_broker(&ALICE).unwrap();
+		<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_liquidity_provider(&LP)
+			.unwrap();
 	},
 }
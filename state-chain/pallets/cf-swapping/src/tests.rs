@@ -1,24 +1,28 @@
 use super::*;
 use crate::{
 	mock::{RuntimeEvent, *},
-	CcmFailReason, CcmIdCounter, CcmOutputs, CcmSwap, CcmSwapOutput, CollectedRejectedFunds,
-	EarnedBrokerFees, Error, Event, MaximumSwapAmount, Pallet, PendingCcms, Swap, SwapOrigin,
-	SwapQueue, SwapType,
+	BrokerChannelEarnings, BrokerEpochEarnings, BrokerWithdrawalAddress, CcmFailReason,
+	CcmIdCounter, CcmOutputs, CcmSwap, CcmSwapOutput, CollectedRejectedFunds, EarnedBrokerFees,
+	Error, Event, ExactOutputSwap, ExactOutputSwapQueue, MaximumSwapAmount,
+	OldestBrokerEpochEarnings, Pallet, PendingCcms, PendingInternalSwaps, PendingVaultSwaps, Swap,
+	SwapOrigin, SwapQueue, SwapType, TotalBrokerEarnings, BROKER_EPOCH_EARNINGS_RETENTION,
+	MAX_BROKER_COMMISSION_BPS,
 };
 use cf_chains::{
 	address::{to_encoded_address, AddressConverter, EncodedAddress, ForeignChainAddress},
 	btc::{BitcoinNetwork, ScriptPubkey},
 	dot::PolkadotAccountId,
-	AnyChain, CcmChannelMetadata, CcmDepositMetadata,
+	AnyChain, CcmChannelMetadata, CcmDepositMetadata, CcmFailureFallback,
 };
-use cf_primitives::{Asset, AssetAmount, BasisPoints, ForeignChain, NetworkEnvironment};
+use cf_primitives::{Affiliate, Asset, AssetAmount, BasisPoints, ForeignChain, NetworkEnvironment};
 use cf_test_utilities::assert_event_sequence;
 use cf_traits::{
 	mocks::{
+		account_role_registry::MockAccountRoleRegistry,
 		address_converter::MockAddressConverter,
 		egress_handler::{MockEgressHandler, MockEgressParameter},
 	},
-	CcmHandler, SetSafeMode, SwapDepositHandler, SwappingApi,
+	AccountRoleRegistry, CcmHandler, LpBalanceApi, SetSafeMode, SwapDepositHandler, SwappingApi,
 };
 use frame_support::{
 	assert_err, assert_noop, assert_ok,
@@ -115,6 +119,7 @@ fn insert_swaps(swaps: &[Swap]) {
 				destination_address.clone(),
 				broker_id as u64,
 				2,
+				Default::default(),
 				1,
 			);
 		}
@@ -126,6 +131,7 @@ fn generate_ccm_channel() -> CcmChannelMetadata {
 		message: vec![0x01].try_into().unwrap(),
 		gas_budget: GAS_BUDGET,
 		cf_parameters: Default::default(),
+		fallback: None,
 	}
 }
 fn generate_ccm_deposit() -> CcmDepositMetadata {
@@ -190,6 +196,7 @@ fn process_all_swaps() {
 fn expect_earned_fees_to_be_recorded() {
 	new_test_ext().execute_with(|| {
 		const ALICE: u64 = 2_u64;
+		MockEpochInfo::set_epoch(1);
 		<Pallet<Test> as SwapDepositHandler>::schedule_swap_from_channel(
 			ForeignChainAddress::Eth([2; 20].into()),
 			Default::default(),
@@ -199,6 +206,7 @@ fn expect_earned_fees_to_be_recorded() {
 			ForeignChainAddress::Eth([2; 20].into()),
 			ALICE,
 			200,
+			Default::default(),
 			1,
 		);
 		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, cf_primitives::Asset::Flip), 2);
@@ -211,9 +219,69 @@ fn expect_earned_fees_to_be_recorded() {
 			ForeignChainAddress::Eth([2; 20].into()),
 			ALICE,
 			200,
+			Default::default(),
 			1,
 		);
 		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, cf_primitives::Asset::Flip), 4);
+		MockEpochInfo::set_epoch(2);
+		<Pallet<Test> as SwapDepositHandler>::schedule_swap_from_channel(
+			ForeignChainAddress::Eth([2; 20].into()),
+			Default::default(),
+			Asset::Flip,
+			Asset::Usdc,
+			100,
+			ForeignChainAddress::Eth([2; 20].into()),
+			ALICE,
+			200,
+			Default::default(),
+			2,
+		);
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, cf_primitives::Asset::Flip), 6);
+
+		// Withdrawals reduce the balance but not the earnings.
+		EarnedBrokerFees::<Test>::remove(ALICE, Asset::Flip);
+		assert_eq!(TotalBrokerEarnings::<Test>::get(ALICE, Asset::Flip), 6);
+		assert_eq!(BrokerChannelEarnings::<Test>::get(ALICE, (Asset::Flip, 1)), 4);
+		assert_eq!(BrokerChannelEarnings::<Test>::get(ALICE, (Asset::Flip, 2)), 2);
+		assert_eq!(BrokerEpochEarnings::<Test>::get(1, ALICE), BTreeMap::from([(Asset::Flip, 4)]));
+		assert_eq!(BrokerEpochEarnings::<Test>::get(2, ALICE), BTreeMap::from([(Asset::Flip, 2)]));
+
+		// Channel earnings are removed when the channel is recycled.
+		<Pallet<Test> as SwapDepositHandler>::on_channel_recycled(
+			Asset::Flip,
+			1,
+			ALICE,
+			Default::default(),
+		);
+		assert!(!BrokerChannelEarnings::<Test>::contains_key(ALICE, (Asset::Flip, 1)));
+		assert_eq!(BrokerChannelEarnings::<Test>::get(ALICE, (Asset::Flip, 2)), 2);
+	});
+}
+
+#[test]
+fn broker_epoch_earnings_are_pruned_after_the_retention_period() {
+	new_test_ext().execute_with(|| {
+		const ALICE: u64 = 2_u64;
+		for epoch in 0..3 {
+			BrokerEpochEarnings::<Test>::insert(epoch, ALICE, BTreeMap::from([(Asset::Flip, 1)]));
+		}
+
+		MockEpochInfo::set_epoch(BROKER_EPOCH_EARNINGS_RETENTION - 1);
+		Swapping::on_idle(1, Weight::MAX);
+		assert_eq!(OldestBrokerEpochEarnings::<Test>::get(), 0);
+		assert!(BrokerEpochEarnings::<Test>::contains_key(0, ALICE));
+
+		MockEpochInfo::set_epoch(BROKER_EPOCH_EARNINGS_RETENTION + 1);
+		Swapping::on_idle(2, Weight::MAX);
+		assert_eq!(OldestBrokerEpochEarnings::<Test>::get(), 1);
+		assert!(!BrokerEpochEarnings::<Test>::contains_key(0, ALICE));
+		assert!(BrokerEpochEarnings::<Test>::contains_key(1, ALICE));
+
+		Swapping::on_idle(3, Weight::MAX);
+		Swapping::on_idle(4, Weight::MAX);
+		assert_eq!(OldestBrokerEpochEarnings::<Test>::get(), 2);
+		assert!(!BrokerEpochEarnings::<Test>::contains_key(1, ALICE));
+		assert!(BrokerEpochEarnings::<Test>::contains_key(2, ALICE));
 	});
 }
 
@@ -231,6 +299,7 @@ fn cannot_swap_with_incorrect_destination_address_type() {
 			ForeignChainAddress::Eth([2; 20].into()),
 			ALICE,
 			2,
+			Default::default(),
 			1,
 		);
 
@@ -264,6 +333,7 @@ fn expect_swap_id_to_be_emitted() {
 				ForeignChainAddress::Eth(Default::default()),
 				ALICE,
 				0,
+				Default::default(),
 				1,
 			);
 			// 3. Process swaps -> SwapExecuted, SwapEgressScheduled
@@ -340,6 +410,60 @@ fn withdraw_broker_fees() {
 	});
 }
 
+#[test]
+fn withdraw_all_broker_fees() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Swapping::withdraw_all(RuntimeOrigin::signed(ALICE)),
+			<Error<Test>>::NoFundsAvailable
+		);
+
+		assert_noop!(
+			Swapping::register_withdrawal_address(
+				RuntimeOrigin::signed(ALICE),
+				EncodedAddress::Btc(vec![0x00]),
+			),
+			<Error<Test>>::InvalidDestinationAddress
+		);
+		assert_ok!(Swapping::register_withdrawal_address(
+			RuntimeOrigin::signed(ALICE),
+			EncodedAddress::Eth([1; 20]),
+		));
+		assert_eq!(
+			BrokerWithdrawalAddress::<Test>::get(ALICE, ForeignChain::Ethereum),
+			Some(ForeignChainAddress::Eth([1; 20].into()))
+		);
+
+		EarnedBrokerFees::<Test>::insert(ALICE, Asset::Eth, 200);
+		EarnedBrokerFees::<Test>::insert(ALICE, Asset::Flip, 300);
+		// No withdrawal address is registered for Polkadot.
+		EarnedBrokerFees::<Test>::insert(ALICE, Asset::Dot, 400);
+
+		assert_ok!(Swapping::withdraw_all(RuntimeOrigin::signed(ALICE)));
+
+		let egresses = MockEgressHandler::<AnyChain>::get_scheduled_egresses();
+		assert_eq!(
+			egresses.iter().map(MockEgressParameter::amount).sorted().collect::<Vec<_>>(),
+			vec![200, 300]
+		);
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Eth), 0);
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Flip), 0);
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Dot), 400);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::WithdrawalSkipped {
+			broker_id: ALICE,
+			asset: Asset::Dot,
+			amount: 400,
+			reason: Error::<Test>::NoWithdrawalAddressRegistered.into(),
+		}));
+
+		// Only the skipped balance remains, so there is nothing left to withdraw.
+		assert_noop!(
+			Swapping::withdraw_all(RuntimeOrigin::signed(ALICE)),
+			<Error<Test>>::NoFundsAvailable
+		);
+	});
+}
+
 #[test]
 fn can_swap_using_witness_origin() {
 	new_test_ext().execute_with(|| {
@@ -402,15 +526,6 @@ fn reject_invalid_ccm_deposit() {
 			Error::<Test>::IncompatibleAssetAndAddress
 		);
 
-		assert_failed_ccm(
-			Asset::Eth,
-			1_000_000,
-			Asset::Dot,
-			ForeignChainAddress::Dot(Default::default()),
-			ccm.clone(),
-			CcmFailReason::UnsupportedForTargetChain,
-		);
-
 		assert_failed_ccm(
 			Asset::Eth,
 			1_000_000,
@@ -424,9 +539,25 @@ fn reject_invalid_ccm_deposit() {
 			gas_budget - 1,
 			Asset::Eth,
 			ForeignChainAddress::Eth(Default::default()),
-			ccm,
+			ccm.clone(),
 			CcmFailReason::InsufficientDepositAmount,
 		);
+		assert_failed_ccm(
+			Asset::Eth,
+			1_000_000,
+			Asset::Eth,
+			ForeignChainAddress::Eth(Default::default()),
+			CcmDepositMetadata {
+				channel_metadata: CcmChannelMetadata {
+					fallback: Some(CcmFailureFallback {
+						refund_address: ForeignChainAddress::Dot(Default::default()),
+					}),
+					..ccm.channel_metadata.clone()
+				},
+				..ccm
+			},
+			CcmFailReason::InvalidFallbackAddress,
+		);
 	});
 }
 
@@ -452,14 +583,41 @@ fn rejects_invalid_swap_deposit() {
 			Swapping::request_swap_deposit_address(
 				RuntimeOrigin::signed(ALICE),
 				Asset::Eth,
-				Asset::Dot,
-				EncodedAddress::Dot(Default::default()),
+				Asset::Btc,
+				MockAddressConverter::to_encoded_address(ForeignChainAddress::Btc(
+					ScriptPubkey::try_from_address(
+						"BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+						&BitcoinNetwork::Mainnet,
+					)
+					.unwrap()
+				)),
 				0,
-				Some(ccm),
+				Default::default(),
+				Some(ccm.clone()),
 				0
 			),
 			Error::<Test>::CcmUnsupportedForTargetChain
 		);
+
+		assert_noop!(
+			Swapping::request_swap_deposit_address(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				Asset::Usdc,
+				EncodedAddress::Eth(Default::default()),
+				0,
+				Some(CcmChannelMetadata {
+					fallback: Some(CcmFailureFallback {
+						refund_address: ForeignChainAddress::Btc(ScriptPubkey::P2PKH(
+							Default::default()
+						)),
+					}),
+					..ccm
+				}),
+				0
+			),
+			Error::<Test>::InvalidCcmFallbackAddress
+		);
 	});
 }
 
@@ -943,6 +1101,7 @@ fn swap_by_deposit_happy_path() {
 			ForeignChainAddress::Eth(Default::default()),
 			Default::default(),
 			Default::default(),
+			Default::default(),
 			1,
 		);
 
@@ -1052,6 +1211,78 @@ fn ccm_without_principal_swaps_are_accepted() {
 	});
 }
 
+#[test]
+fn ccm_to_polkadot_is_accepted() {
+	new_test_ext().execute_with(|| {
+		let ccm = CcmDepositMetadata {
+			channel_metadata: CcmChannelMetadata { gas_budget: 0, ..generate_ccm_channel() },
+			..generate_ccm_deposit()
+		};
+
+		assert_ok!(Swapping::request_swap_deposit_address(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			Asset::Dot,
+			EncodedAddress::Dot(Default::default()),
+			0,
+			Some(ccm.channel_metadata.clone()),
+			0
+		));
+
+		assert_ok!(Swapping::on_ccm_deposit(
+			Asset::Dot,
+			10_000,
+			Asset::Dot,
+			ForeignChainAddress::Dot(Default::default()),
+			ccm,
+			SwapOrigin::Vault { tx_hash: Default::default() },
+		));
+
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady { .. }),
+			RuntimeEvent::Swapping(Event::CcmDepositReceived {
+				ccm_id: 1,
+				principal_swap_id: None,
+				gas_swap_id: None,
+				destination_address: EncodedAddress::Dot(..),
+				..
+			}),
+			RuntimeEvent::Swapping(Event::CcmEgressScheduled {
+				ccm_id: 1,
+				egress_id: (ForeignChain::Polkadot, _),
+			})
+		);
+	});
+}
+
+#[test]
+fn ccm_gas_budget_to_polkadot_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Swapping::request_swap_deposit_address(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				Asset::Dot,
+				EncodedAddress::Dot(Default::default()),
+				0,
+				Some(generate_ccm_channel()),
+				0
+			),
+			Error::<Test>::CcmGasBudgetUnsupportedForTargetChain
+		);
+
+		assert_failed_ccm(
+			Asset::Dot,
+			GAS_BUDGET + 10_000,
+			Asset::Dot,
+			ForeignChainAddress::Dot(Default::default()),
+			generate_ccm_deposit(),
+			CcmFailReason::GasBudgetUnsupportedForTargetChain,
+		);
+	});
+}
+
 #[test]
 fn process_all_into_stable_swaps_first() {
 	new_test_ext().execute_with(|| {
@@ -1109,7 +1340,8 @@ fn process_all_into_stable_swaps_first() {
 		assert_swaps_queue_is_empty();
 
 		// Network fee should only be taken once.
-		let total_amount_after_network_fee = MockSwappingApi::take_network_fee(amount * 4);
+		let total_amount_after_network_fee =
+			MockSwappingApi::take_network_fee(Asset::Flip, Asset::Eth, amount * 4);
 		let output_amount = total_amount_after_network_fee / 4;
 		// Verify swap "from" -> STABLE_ASSET, then "to" -> Output Asset
 		assert_eq!(
@@ -1213,6 +1445,13 @@ fn cannot_withdraw_in_safe_mode() {
 			EncodedAddress::Eth(Default::default()),
 		));
 		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Eth), 0);
+
+		EarnedBrokerFees::<Test>::insert(ALICE, Asset::Eth, 200);
+		<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_red();
+		assert_noop!(
+			Swapping::withdraw_all(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::WithdrawalsDisabled
+		);
 	});
 }
 
@@ -1398,6 +1637,7 @@ fn can_handle_swaps_with_zero_outputs() {
 				eth_address.clone(),
 				Default::default(),
 				0,
+				Default::default(),
 				0,
 			);
 			Swapping::schedule_swap_from_channel(
@@ -1409,6 +1649,7 @@ fn can_handle_swaps_with_zero_outputs() {
 				eth_address,
 				Default::default(),
 				0,
+				Default::default(),
 				0,
 			);
 
@@ -1688,6 +1929,7 @@ fn swap_excess_are_confiscated_for_swap_via_deposit() {
 			ForeignChainAddress::Eth(Default::default()),
 			ALICE,
 			0,
+			Default::default(),
 			0,
 		);
 
@@ -1893,6 +2135,7 @@ fn swap_with_custom_broker_fee(
 		ForeignChainAddress::Eth([2; 20].into()),
 		ALICE,
 		broker_fee,
+		Default::default(),
 		1,
 	);
 }
@@ -2000,6 +2243,96 @@ fn broker_bps_is_limited() {
 	});
 }
 
+#[test]
+fn affiliate_commission_is_validated() {
+	const AFFILIATE: u64 = 456;
+	new_test_ext().execute_with(|| {
+		let request_with_affiliate_bps = |broker_commission_bps: BasisPoints, bps: BasisPoints| {
+			Swapping::request_swap_deposit_address_with_affiliates(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				Asset::Usdc,
+				EncodedAddress::Eth(Default::default()),
+				broker_commission_bps,
+				vec![Affiliate { account: AFFILIATE, bps }].try_into().unwrap(),
+				None,
+				0,
+			)
+		};
+
+		assert_noop!(
+			request_with_affiliate_bps(10, 10),
+			Error::<Test>::AffiliateNotRegisteredAsBroker
+		);
+
+		<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_broker(&AFFILIATE)
+			.unwrap();
+
+		assert_noop!(
+			request_with_affiliate_bps(900, 101),
+			Error::<Test>::BrokerCommissionBpsTooHigh
+		);
+		assert_ok!(request_with_affiliate_bps(900, 100));
+	});
+}
+
+#[test]
+fn broker_commission_is_split_with_affiliates() {
+	const AFFILIATE_1: u64 = 456;
+	const AFFILIATE_2: u64 = 789;
+	const AMOUNT: AssetAmount = 10_000;
+	new_test_ext().execute_with(|| {
+		<Pallet<Test> as SwapDepositHandler>::schedule_swap_from_channel(
+			ForeignChainAddress::Eth([2; 20].into()),
+			Default::default(),
+			Asset::Flip,
+			Asset::Usdc,
+			AMOUNT,
+			ForeignChainAddress::Eth([2; 20].into()),
+			ALICE,
+			100,
+			vec![
+				Affiliate { account: AFFILIATE_1, bps: 50 },
+				Affiliate { account: AFFILIATE_2, bps: 10 },
+			]
+			.try_into()
+			.unwrap(),
+			1,
+		);
+
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Flip), 100);
+		assert_eq!(EarnedBrokerFees::<Test>::get(AFFILIATE_1, Asset::Flip), 50);
+		assert_eq!(EarnedBrokerFees::<Test>::get(AFFILIATE_2, Asset::Flip), 10);
+		assert_eq!(BrokerChannelEarnings::<Test>::get(AFFILIATE_1, (Asset::Flip, 1)), 50);
+		assert_eq!(
+			SwapQueue::<Test>::get(System::block_number() + u64::from(SWAP_DELAY_BLOCKS))[0].amount,
+			AMOUNT - 160
+		);
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapScheduled {
+				swap_id: 1,
+				broker_commission: Some(160),
+				..
+			}),
+			RuntimeEvent::Swapping(Event::AffiliateCommissionCredited {
+				swap_id: 1,
+				broker_id: ALICE,
+				affiliate_id: AFFILIATE_1,
+				asset: Asset::Flip,
+				amount: 50,
+			}),
+			RuntimeEvent::Swapping(Event::AffiliateCommissionCredited {
+				swap_id: 1,
+				broker_id: ALICE,
+				affiliate_id: AFFILIATE_2,
+				asset: Asset::Flip,
+				amount: 10,
+			}),
+		);
+	});
+}
+
 #[test]
 fn swaps_are_executed_according_to_execute_at_field() {
 	let mut swaps = generate_test_swaps();
@@ -2159,3 +2492,411 @@ fn can_update_multiple_items_at_once() {
 		assert_eq!(MaximumSwapAmount::<Test>::get(Asset::Dot), Some(200));
 	});
 }
+
+#[test]
+fn can_swap_exact_output_and_refund_unused_input() {
+	new_test_ext().execute_with(|| {
+		const MAX_INPUT: AssetAmount = 1_000;
+		const OUTPUT_AMOUNT: AssetAmount = 400;
+		let destination_address = ForeignChainAddress::Eth([1; 20].into());
+		let refund_address = ForeignChainAddress::Eth([2; 20].into());
+
+		assert_ok!(Swapping::schedule_exact_output_swap_from_contract(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			Asset::Eth,
+			MAX_INPUT,
+			OUTPUT_AMOUNT,
+			MockAddressConverter::to_encoded_address(destination_address.clone()),
+			MockAddressConverter::to_encoded_address(refund_address.clone()),
+			Default::default(),
+		));
+
+		let execute_at = System::block_number() + u64::from(SWAP_DELAY_BLOCKS);
+		assert_eq!(
+			ExactOutputSwapQueue::<Test>::get(execute_at),
+			vec![ExactOutputSwap {
+				swap_id: 1,
+				from: Asset::Flip,
+				to: Asset::Eth,
+				max_input: MAX_INPUT,
+				output_amount: OUTPUT_AMOUNT,
+				destination_address: destination_address.clone(),
+				refund_address: refund_address.clone(),
+			}]
+		);
+
+		// The output is bought working backwards from the destination asset.
+		Swapping::on_finalize(execute_at);
+		assert_eq!(
+			Swaps::get(),
+			vec![
+				(Asset::Usdc, Asset::Eth, OUTPUT_AMOUNT),
+				(Asset::Flip, Asset::Usdc, OUTPUT_AMOUNT)
+			]
+		);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::ExactOutputSwapExecuted {
+			swap_id: 1,
+			source_asset: Asset::Flip,
+			swap_input: OUTPUT_AMOUNT,
+			destination_asset: Asset::Eth,
+			swap_output: OUTPUT_AMOUNT,
+			intermediate_amount: Some(OUTPUT_AMOUNT),
+		}));
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![
+				MockEgressParameter::Swap {
+					asset: Asset::Eth,
+					amount: OUTPUT_AMOUNT,
+					fee: 0,
+					destination_address,
+				},
+				MockEgressParameter::Swap {
+					asset: Asset::Flip,
+					amount: MAX_INPUT - OUTPUT_AMOUNT,
+					fee: 0,
+					destination_address: refund_address,
+				},
+			]
+		);
+		assert!(ExactOutputSwapQueue::<Test>::get(execute_at).is_empty());
+	});
+}
+
+#[test]
+fn exact_output_swap_refunds_full_input_on_failure() {
+	new_test_ext().execute_with(|| {
+		const MAX_INPUT: AssetAmount = 1_000;
+		let refund_address = ForeignChainAddress::Eth([2; 20].into());
+
+		// At this rate, buying 400 Eth requires 1_600 Flip.
+		SwapRate::set(0.5f64);
+		assert_ok!(Swapping::schedule_exact_output_swap_from_contract(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			Asset::Eth,
+			MAX_INPUT,
+			400,
+			EncodedAddress::Eth([1; 20]),
+			MockAddressConverter::to_encoded_address(refund_address.clone()),
+			Default::default(),
+		));
+
+		let execute_at = System::block_number() + u64::from(SWAP_DELAY_BLOCKS);
+		Swapping::on_finalize(execute_at);
+
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::ExactOutputSwapFailed {
+			swap_id: 1,
+			reason: Error::<Test>::InsufficientSwapInput.into(),
+		}));
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Flip,
+				amount: MAX_INPUT,
+				fee: 0,
+				destination_address: refund_address,
+			}]
+		);
+	});
+}
+
+#[test]
+fn exact_output_swap_refund_address_must_match_source_asset() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Swapping::schedule_exact_output_swap_from_contract(
+				RuntimeOrigin::root(),
+				Asset::Flip,
+				Asset::Eth,
+				1_000,
+				400,
+				EncodedAddress::Eth(Default::default()),
+				EncodedAddress::Dot(Default::default()),
+				Default::default(),
+			),
+			Error::<Test>::IncompatibleAssetAndAddress
+		);
+		assert_noop!(
+			Swapping::schedule_exact_output_swap_from_contract(
+				RuntimeOrigin::root(),
+				Asset::Eth,
+				Asset::Eth,
+				1_000,
+				400,
+				EncodedAddress::Eth(Default::default()),
+				EncodedAddress::Eth(Default::default()),
+				Default::default(),
+			),
+			Error::<Test>::SameSourceAndDestinationAsset
+		);
+	});
+}
+
+#[test]
+fn weight_of_exact_output_swaps_is_reserved_in_on_initialize() {
+	new_test_ext().execute_with(|| {
+		let execute_at = System::block_number() + u64::from(SWAP_DELAY_BLOCKS);
+		let weight_without_swaps = Swapping::on_initialize(execute_at);
+
+		for _ in 0..3 {
+			assert_ok!(Swapping::schedule_exact_output_swap_from_contract(
+				RuntimeOrigin::root(),
+				Asset::Flip,
+				Asset::Eth,
+				1_000,
+				400,
+				EncodedAddress::Eth([1; 20]),
+				EncodedAddress::Eth([2; 20]),
+				Default::default(),
+			));
+		}
+
+		assert_eq!(
+			Swapping::on_initialize(execute_at),
+			weight_without_swaps + MockWeightInfo::process_exact_output_swaps(3)
+		);
+	});
+}
+
+#[test]
+fn lp_can_swap_from_free_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MockLpBalance::try_credit_account(&LP, Asset::Flip, 1_000));
+
+		assert_noop!(
+			Swapping::internal_swap(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Flip,
+				Asset::Eth,
+				1_000,
+				0
+			),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			Swapping::internal_swap(RuntimeOrigin::signed(LP), Asset::Flip, Asset::Eth, 1_001, 0),
+			DispatchError::from("Insufficient balance")
+		);
+
+		assert_ok!(Swapping::internal_swap(
+			RuntimeOrigin::signed(LP),
+			Asset::Flip,
+			Asset::Eth,
+			1_000,
+			900
+		));
+		let execute_at = System::block_number() + u64::from(SWAP_DELAY_BLOCKS);
+		System::assert_last_event(RuntimeEvent::Swapping(Event::<Test>::InternalSwapScheduled {
+			swap_id: 1,
+			account_id: LP,
+			source_asset: Asset::Flip,
+			amount: 1_000,
+			destination_asset: Asset::Eth,
+			min_output: 900,
+			execute_at,
+		}));
+		assert_eq!(MockLpBalance::balance(LP, Asset::Flip), 0);
+		assert_eq!(
+			SwapQueue::<Test>::get(execute_at),
+			vec![Swap::new(1, Asset::Flip, Asset::Eth, 1_000, SwapType::InternalSwap)]
+		);
+
+		// The output is credited to the free balance, and nothing is egressed.
+		Swapping::on_finalize(execute_at);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::InternalSwapCredited {
+			swap_id: 1,
+			account_id: LP,
+			asset: Asset::Eth,
+			amount: 1_000,
+		}));
+		assert_eq!(MockLpBalance::balance(LP, Asset::Eth), 1_000);
+		assert!(PendingInternalSwaps::<Test>::get(1).is_none());
+		assert!(MockEgressHandler::<AnyChain>::get_scheduled_egresses().is_empty());
+	});
+}
+
+#[test]
+fn internal_swap_below_minimum_output_is_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MockLpBalance::try_credit_account(&LP, Asset::Flip, 1_000));
+		assert_ok!(Swapping::internal_swap(
+			RuntimeOrigin::signed(LP),
+			Asset::Flip,
+			Asset::Eth,
+			1_000,
+			900
+		));
+		assert_ok!(Swapping::schedule_swap_from_contract(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			Asset::Eth,
+			500,
+			EncodedAddress::Eth([1; 20]),
+			Default::default(),
+		));
+
+		// At this rate, the internal swap only returns 250 Eth.
+		SwapRate::set(0.5f64);
+		let execute_at = System::block_number() + u64::from(SWAP_DELAY_BLOCKS);
+		Swapping::on_finalize(execute_at);
+
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::InternalSwapRefunded {
+			swap_id: 1,
+			account_id: LP,
+			asset: Asset::Flip,
+			amount: 1_000,
+		}));
+		assert_eq!(MockLpBalance::balance(LP, Asset::Flip), 1_000);
+		assert_eq!(MockLpBalance::balance(LP, Asset::Eth), 0);
+		assert!(PendingInternalSwaps::<Test>::get(1).is_none());
+
+		// The rest of the batch is executed without the refunded swap.
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Swapping(Event::<Test>::SwapExecuted {
+				swap_id: 2,
+				swap_output: 125,
+				intermediate_amount: Some(250),
+				..
+			})
+		)));
+		assert!(SwapQueue::<Test>::get(execute_at).is_empty());
+		assert_eq!(FirstUnprocessedBlock::<Test>::get(), execute_at + 1);
+	});
+}
+
+#[test]
+fn internal_swap_parameters_are_validated() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MockLpBalance::try_credit_account(&LP, Asset::Flip, 1_000));
+
+		assert_noop!(
+			Swapping::internal_swap(RuntimeOrigin::signed(LP), Asset::Flip, Asset::Flip, 1_000, 0),
+			Error::<Test>::SameSourceAndDestinationAsset
+		);
+		assert_noop!(
+			Swapping::internal_swap(RuntimeOrigin::signed(LP), Asset::Flip, Asset::Eth, 0, 0),
+			Error::<Test>::ZeroSwapAmount
+		);
+
+		set_maximum_swap_amount(Asset::Flip, Some(500));
+		assert_noop!(
+			Swapping::internal_swap(RuntimeOrigin::signed(LP), Asset::Flip, Asset::Eth, 1_000, 0),
+			Error::<Test>::MaximumSwapAmountExceeded
+		);
+
+		<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_red();
+		assert_noop!(
+			Swapping::internal_swap(RuntimeOrigin::signed(LP), Asset::Flip, Asset::Eth, 500, 0),
+			Error::<Test>::SwapsDisabled
+		);
+	});
+}
+
+#[test]
+fn vault_swap_below_minimum_output_is_refunded() {
+	new_test_ext().execute_with(|| {
+		let refund_address = ForeignChainAddress::Btc(ScriptPubkey::Taproot([0x02; 32]));
+		let refunded_swap_id = Swapping::schedule_vault_swap(
+			Asset::Btc,
+			Asset::Eth,
+			1_000,
+			ForeignChainAddress::Eth([0x01; 20].into()),
+			900,
+			refund_address.clone(),
+			ALICE,
+			0,
+			[0x11; 32],
+		);
+		let executed_swap_id = Swapping::schedule_vault_swap(
+			Asset::Btc,
+			Asset::Eth,
+			1_000,
+			ForeignChainAddress::Eth([0x01; 20].into()),
+			100,
+			refund_address.clone(),
+			ALICE,
+			0,
+			[0x22; 32],
+		);
+		let execute_at = System::block_number() + u64::from(SWAP_DELAY_BLOCKS);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::SwapScheduled {
+			swap_id: refunded_swap_id,
+			source_asset: Asset::Btc,
+			deposit_amount: 1_000,
+			destination_asset: Asset::Eth,
+			destination_address: EncodedAddress::Eth([0x01; 20]),
+			origin: SwapOrigin::Vault { tx_hash: [0x11; 32] },
+			swap_type: SwapType::Swap(ForeignChainAddress::Eth([0x01; 20].into())),
+			broker_commission: Some(0),
+			execute_at,
+		}));
+
+		// At this rate, both swaps return 250 Eth.
+		SwapRate::set(0.5f64);
+		Swapping::on_finalize(execute_at);
+
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Swapping(Event::<Test>::SwapRefundEgressScheduled {
+				swap_id,
+				asset: Asset::Btc,
+				amount: 1_000,
+				..
+			}) if swap_id == refunded_swap_id
+		)));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Swapping(Event::<Test>::SwapEgressScheduled {
+				swap_id,
+				asset: Asset::Eth,
+				amount: 250,
+				..
+			}) if swap_id == executed_swap_id
+		)));
+		assert!(PendingVaultSwaps::<Test>::get(refunded_swap_id).is_none());
+		assert!(PendingVaultSwaps::<Test>::get(executed_swap_id).is_none());
+		assert_eq!(FirstUnprocessedBlock::<Test>::get(), execute_at + 1);
+	});
+}
+
+#[test]
+fn vault_swap_credits_capped_broker_commission() {
+	new_test_ext().execute_with(|| {
+		let refund_address = ForeignChainAddress::Btc(ScriptPubkey::Taproot([0x02; 32]));
+		let schedule_vault_swap = |broker_commission_bps| {
+			Swapping::schedule_vault_swap(
+				Asset::Btc,
+				Asset::Eth,
+				1_000,
+				ForeignChainAddress::Eth([0x01; 20].into()),
+				0,
+				refund_address.clone(),
+				ALICE,
+				broker_commission_bps,
+				[0x11; 32],
+			)
+		};
+
+		let swap_id = schedule_vault_swap(100);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::SwapScheduled {
+			swap_id,
+			source_asset: Asset::Btc,
+			deposit_amount: 1_000,
+			destination_asset: Asset::Eth,
+			destination_address: EncodedAddress::Eth([0x01; 20]),
+			origin: SwapOrigin::Vault { tx_hash: [0x11; 32] },
+			swap_type: SwapType::Swap(ForeignChainAddress::Eth([0x01; 20].into())),
+			broker_commission: Some(10),
+			execute_at: System::block_number() + u64::from(SWAP_DELAY_BLOCKS),
+		}));
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Btc), 10);
+
+		// The commission is capped at the maximum a broker may charge.
+		schedule_vault_swap(MAX_BROKER_COMMISSION_BPS + 1);
+		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, Asset::Btc), 110);
+		assert_eq!(TotalBrokerEarnings::<Test>::get(ALICE, Asset::Btc), 110);
+	});
+}
@@ -37,6 +37,12 @@ pub trait WeightInfo {
 	fn register_as_broker() -> Weight;
 	fn schedule_swap_from_contract() -> Weight;
 	fn ccm_deposit() -> Weight;
+	fn schedule_exact_output_swap_from_contract() -> Weight;
+	fn internal_swap() -> Weight;
+	fn register_withdrawal_address() -> Weight;
+	fn withdraw_all() -> Weight;
+	fn request_swap_deposit_address_with_affiliates(a: u32, ) -> Weight;
+	fn process_exact_output_swaps(a: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_swapping using the Substrate node and recommended hardware.
@@ -139,6 +145,113 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
+	/// Storage: `Swapping::SwapIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaximumSwapAmount` (r:1 w:0)
+	/// Proof: `Swapping::MaximumSwapAmount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::ExactOutputSwapQueue` (r:1 w:1)
+	/// Proof: `Swapping::ExactOutputSwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn schedule_exact_output_swap_from_contract() -> Weight {
+		Weight::from_parts(20_912_000, 3650)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Swapping::MaximumSwapAmount` (r:1 w:0)
+	/// Proof: `Swapping::MaximumSwapAmount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
+	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapQueue` (r:1 w:1)
+	/// Proof: `Swapping::SwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::PendingInternalSwaps` (r:0 w:1)
+	/// Proof: `Swapping::PendingInternalSwaps` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn internal_swap() -> Weight {
+		Weight::from_parts(28_214_000, 3977)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Swapping::BrokerWithdrawalAddress` (r:0 w:1)
+	/// Proof: `Swapping::BrokerWithdrawalAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn register_withdrawal_address() -> Weight {
+		Weight::from_parts(14_180_000, 3498)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Swapping::EarnedBrokerFees` (r:5 w:4)
+	/// Proof: `Swapping::EarnedBrokerFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::BrokerWithdrawalAddress` (r:4 w:0)
+	/// Proof: `Swapping::BrokerWithdrawalAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn withdraw_all() -> Weight {
+		Weight::from_parts(120_937_000, 14719)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:6 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelPool` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ChannelIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumVaultAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[0, 5]`.
+	fn request_swap_deposit_address_with_affiliates(a: u32, ) -> Weight {
+		Weight::from_parts(54_112_000, 4358)
+			.saturating_add(Weight::from_parts(3_862_415, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(a.into()))
+	}
+	/// Storage: `Swapping::ExactOutputSwapQueue` (r:1 w:1)
+	/// Proof: `Swapping::ExactOutputSwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:2)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PriceObservationRings` (r:2 w:2)
+	/// Proof: `LiquidityPools::PriceObservationRings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PriceObservations` (r:2 w:2)
+	/// Proof: `LiquidityPools::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::CollectedNetworkFee` (r:1 w:1)
+	/// Proof: `LiquidityPools::CollectedNetworkFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[1, 100]`.
+	fn process_exact_output_swaps(a: u32, ) -> Weight {
+		Weight::from_parts(37_220_000, 12436)
+			.saturating_add(Weight::from_parts(102_640_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((9_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 98).saturating_mul(a.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -240,4 +353,111 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
+	/// Storage: `Swapping::SwapIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaximumSwapAmount` (r:1 w:0)
+	/// Proof: `Swapping::MaximumSwapAmount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::ExactOutputSwapQueue` (r:1 w:1)
+	/// Proof: `Swapping::ExactOutputSwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn schedule_exact_output_swap_from_contract() -> Weight {
+		Weight::from_parts(20_912_000, 3650)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Swapping::MaximumSwapAmount` (r:1 w:0)
+	/// Proof: `Swapping::MaximumSwapAmount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
+	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapQueue` (r:1 w:1)
+	/// Proof: `Swapping::SwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::PendingInternalSwaps` (r:0 w:1)
+	/// Proof: `Swapping::PendingInternalSwaps` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn internal_swap() -> Weight {
+		Weight::from_parts(28_214_000, 3977)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Swapping::BrokerWithdrawalAddress` (r:0 w:1)
+	/// Proof: `Swapping::BrokerWithdrawalAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn register_withdrawal_address() -> Weight {
+		Weight::from_parts(14_180_000, 3498)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Swapping::EarnedBrokerFees` (r:5 w:4)
+	/// Proof: `Swapping::EarnedBrokerFees` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::BrokerWithdrawalAddress` (r:4 w:0)
+	/// Proof: `Swapping::BrokerWithdrawalAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn withdraw_all() -> Weight {
+		Weight::from_parts(120_937_000, 14719)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:6 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelPool` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ChannelIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumVaultAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[0, 5]`.
+	fn request_swap_deposit_address_with_affiliates(a: u32, ) -> Weight {
+		Weight::from_parts(54_112_000, 4358)
+			.saturating_add(Weight::from_parts(3_862_415, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 2508).saturating_mul(a.into()))
+	}
+	/// Storage: `Swapping::ExactOutputSwapQueue` (r:1 w:1)
+	/// Proof: `Swapping::ExactOutputSwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:2)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PriceObservationRings` (r:2 w:2)
+	/// Proof: `LiquidityPools::PriceObservationRings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PriceObservations` (r:2 w:2)
+	/// Proof: `LiquidityPools::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::CollectedNetworkFee` (r:1 w:1)
+	/// Proof: `LiquidityPools::CollectedNetworkFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[1, 100]`.
+	fn process_exact_output_swaps(a: u32, ) -> Weight {
+		Weight::from_parts(37_220_000, 12436)
+			.saturating_add(Weight::from_parts(102_640_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((9_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((9_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 98).saturating_mul(a.into()))
+	}
 }
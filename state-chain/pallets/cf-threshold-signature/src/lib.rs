@@ -1424,7 +1424,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::OffenceReporter::report_many(PalletOffence::FailedKeygen, offenders.clone());
 		if T::SafeMode::get().slashing_enabled {
 			offenders.clone().into_iter().for_each(|offender| {
-				T::Slasher::slash_balance(&offender, KeygenSlashAmount::<T, I>::get());
+				let slashed =
+					T::Slasher::slash_balance(&offender, KeygenSlashAmount::<T, I>::get());
+				T::OffenceReporter::report_slash(PalletOffence::FailedKeygen, &offender, slashed);
 			});
 		}
 		PendingKeyRotation::<T, I>::put(KeyRotationStatus::<T, I>::Failed {
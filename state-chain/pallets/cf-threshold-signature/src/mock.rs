@@ -224,13 +224,16 @@ impl Slashing for MockSlasher {
 		});
 	}
 
-	fn slash_balance(account_id: &Self::AccountId, _amount: FlipBalance) {
+	fn slash_balance(account_id: &Self::AccountId, amount: FlipBalance) -> FlipBalance {
 		// Count those slashes
 		SLASHES.with(|count| {
 			count.borrow_mut().push(*account_id);
 		});
+		amount
 	}
 
+	fn refund_slash(_account_id: &Self::AccountId, _amount: FlipBalance) {}
+
 	fn calculate_slash_amount(
 		_account_id: &Self::AccountId,
 		_blocks: Self::BlockNumber,
@@ -4,9 +4,8 @@ use super::*;
 
 use cf_traits::{Chainflip, FeePayment};
 use frame_benchmarking::v2::*;
-use frame_support::sp_runtime::traits::UniqueSaturatedFrom;
+use frame_support::{assert_ok, sp_runtime::traits::UniqueSaturatedFrom};
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
-use sp_std::collections::btree_set::BTreeSet;
 
 fn generate_proposal() -> Proposal {
 	Proposal::SetGovernanceKey(ForeignChain::Ethereum, vec![1; 32])
@@ -18,19 +17,21 @@ mod benchmarks {
 	use sp_std::vec;
 
 	#[benchmark]
-	fn on_initialize_resolve_votes(a: Linear<10, 1_000>) {
-		// a: Number of backers
-
+	fn on_initialize_resolve_votes() {
 		let proposal = generate_proposal();
 		Proposals::<T>::insert(BlockNumberFor::<T>::from(1u32), proposal.clone());
-		let backers = (0..a).map(|i| account("doogle", i, 0)).collect::<BTreeSet<_>>();
-		for account in &backers {
-			T::FeePayment::mint_to_account(
-				account,
-				<T as Chainflip>::Amount::unique_saturated_from(50_000_000_000_000_000_000_000u128),
-			);
-		}
-		Backers::<T>::insert(proposal, backers);
+		T::FeePayment::mint_to_account(
+			&whitelisted_caller(),
+			<T as Chainflip>::Amount::unique_saturated_from(50_000_000_000_000_000_000_000u128),
+		);
+		Pallet::<T>::start_voting_power_snapshot(&proposal);
+		Tallies::<T>::insert(
+			&proposal,
+			VoteTally {
+				ayes: TotalFundsSnapshots::<T>::get(&proposal).unwrap_or_default(),
+				..Default::default()
+			},
+		);
 
 		#[block]
 		{
@@ -40,6 +41,30 @@ mod benchmarks {
 		assert!(GovKeyUpdateAwaitingEnactment::<T>::get().is_some());
 	}
 
+	#[benchmark]
+	fn on_initialize_clear_votes(a: Linear<0, 1_000>) {
+		// a: Number of cleared votes and snapshots
+
+		let proposal = generate_proposal();
+		for i in 0..a / 2 {
+			let voter = account::<T::AccountId>("doogle", i, 0);
+			VotingPowerSnapshots::<T>::insert(
+				&proposal,
+				&voter,
+				<T as Chainflip>::Amount::unique_saturated_from(1_000u128),
+			);
+			Votes::<T>::insert(&proposal, &voter, Vote::Aye);
+		}
+		PendingVoteCleanups::<T>::append((proposal, T::FundingInfo::create_balance_checkpoint()));
+
+		#[block]
+		{
+			Pallet::<T>::process_pending_cleanups();
+		}
+
+		assert!(PendingVoteCleanups::<T>::get().is_empty());
+	}
+
 	#[benchmark]
 	fn on_initialize_execute_proposal() {
 		GovKeyUpdateAwaitingEnactment::<T>::set(Some((
@@ -58,10 +83,11 @@ mod benchmarks {
 	#[benchmark]
 	fn submit_proposal() {
 		let caller: T::AccountId = whitelisted_caller();
-		T::FeePayment::mint_to_account(&caller, T::ProposalFee::get());
+		// The caller keeps some funds, so that their vote is snapshotted.
+		T::FeePayment::mint_to_account(&caller, T::ProposalFee::get() + T::ProposalFee::get());
 
 		#[extrinsic_call]
-		submit_proposal(RawOrigin::Signed(whitelisted_caller()), generate_proposal());
+		submit_proposal(RawOrigin::Signed(caller), generate_proposal());
 
 		assert!(Proposals::<T>::contains_key(
 			<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get()
@@ -69,22 +95,43 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn back_proposal(a: Linear<1, 1_000>) {
+	fn vote() {
 		let caller: T::AccountId = whitelisted_caller();
 		let proposal = generate_proposal();
-		let backers = (0..a)
-			.map(|i| account::<T::AccountId>("signers", i, 0))
-			.collect::<BTreeSet<_>>();
+		T::FeePayment::mint_to_account(&caller, T::ProposalFee::get());
 		Proposals::<T>::insert(
 			<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get(),
 			proposal.clone(),
 		);
-		Backers::<T>::insert(proposal.clone(), backers);
+		// Worst case: the caller's voting power is snapshotted on their first vote.
+		Pallet::<T>::start_voting_power_snapshot(&proposal);
+
+		#[extrinsic_call]
+		vote(RawOrigin::Signed(caller.clone()), proposal.clone(), Vote::Aye);
+
+		assert_eq!(Votes::<T>::get(proposal, caller), Some(Vote::Aye));
+	}
+
+	#[benchmark]
+	fn withdraw_vote() {
+		let caller: T::AccountId = whitelisted_caller();
+		let proposal = generate_proposal();
+		T::FeePayment::mint_to_account(&caller, T::ProposalFee::get());
+		Proposals::<T>::insert(
+			<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get(),
+			proposal.clone(),
+		);
+		Pallet::<T>::start_voting_power_snapshot(&proposal);
+		assert_ok!(Pallet::<T>::vote(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal.clone(),
+			Vote::Aye
+		));
 
 		#[extrinsic_call]
-		back_proposal(RawOrigin::Signed(caller.clone()), proposal.clone());
+		withdraw_vote(RawOrigin::Signed(caller.clone()), proposal.clone());
 
-		assert!(Backers::<T>::get(proposal).contains(&caller));
+		assert!(Votes::<T>::get(proposal, caller).is_none());
 	}
 
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -1,8 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use cf_chains::{eth::Address, ForeignChain};
-use cf_traits::{BroadcastAnyChainGovKey, Chainflip, CommKeyBroadcaster, FeePayment, FundingInfo};
+use cf_primitives::{Asset, FlipBalance};
+use cf_traits::{
+	BalanceCheckpointId, BroadcastAnyChainGovKey, Chainflip, CommKeyBroadcaster, FeePayment,
+	FundingInfo,
+};
 use codec::{Decode, Encode};
-use frame_support::{pallet_prelude::*, traits::StorageVersion, RuntimeDebugNoBound};
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::{
+		traits::{Saturating, Zero},
+		DispatchError,
+	},
+	traits::StorageVersion,
+	RuntimeDebugNoBound,
+};
+use serde::{Deserialize, Serialize};
 use sp_std::{cmp::PartialEq, vec, vec::Vec};
 
 pub use pallet::*;
@@ -17,14 +30,88 @@ mod tests;
 pub mod weights;
 pub use weights::WeightInfo;
 
-#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebugNoBound)]
+#[derive(
+	Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebugNoBound, Serialize, Deserialize,
+)]
 #[scale_info(skip_type_params(T))]
 pub enum Proposal {
 	SetGovernanceKey(ForeignChain, Vec<u8>),
 	SetCommunityKey(Address),
+	UpdateRuntimeParameter(RuntimeParameterUpdate),
 }
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(1);
+/// A change to a runtime parameter that would otherwise require a governance call. If the
+/// proposal passes, the update is dispatched with governance approval after the enactment delay.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug, Serialize, Deserialize)]
+pub enum RuntimeParameterUpdate {
+	/// The minimum amount of FLIP (in FLIPPERINOS) required to fund an account.
+	MinimumFunding(FlipBalance),
+	/// The annual emission inflation rate for current authorities.
+	CurrentAuthorityEmissionInflation(u32),
+	/// The annual emission inflation rate for backup nodes.
+	BackupNodeEmissionInflation(u32),
+	/// The fee of the pool for the given asset pair.
+	PoolFees { base_asset: Asset, quote_asset: Asset, fee_hundredth_pips: u32 },
+}
+
+/// Dispatches runtime parameter updates that have been approved by the tokenholders.
+pub trait RuntimeParameterDispatcher {
+	fn dispatch_update(update: RuntimeParameterUpdate) -> Result<(), DispatchError>;
+}
+
+#[derive(
+	Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug, Serialize, Deserialize,
+)]
+pub enum Vote {
+	Aye,
+	Nay,
+	Abstain,
+}
+
+/// The voting power behind each option of a proposal.
+#[derive(
+	Copy,
+	Clone,
+	Default,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	RuntimeDebug,
+	Serialize,
+	Deserialize,
+)]
+pub struct VoteTally<Amount> {
+	pub ayes: Amount,
+	pub nays: Amount,
+	pub abstentions: Amount,
+}
+
+impl<Amount: Saturating + Copy> VoteTally<Amount> {
+	fn add(&mut self, vote: Vote, voting_power: Amount) {
+		let total = self.total_for(vote);
+		*total = total.saturating_add(voting_power);
+	}
+
+	fn remove(&mut self, vote: Vote, voting_power: Amount) {
+		let total = self.total_for(vote);
+		*total = total.saturating_sub(voting_power);
+	}
+
+	fn total_for(&mut self, vote: Vote) -> &mut Amount {
+		match vote {
+			Vote::Aye => &mut self.ayes,
+			Vote::Nay => &mut self.nays,
+			Vote::Abstain => &mut self.abstentions,
+		}
+	}
+}
+
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(2);
+
+/// The maximum number of votes, voting power snapshots and balance checkpoints cleared in a block.
+pub const MAX_ACCOUNTS_PER_BLOCK: u32 = 1_000;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -36,7 +123,6 @@ pub mod pallet {
 	pub struct Pallet<T>(_);
 
 	use frame_system::pallet_prelude::*;
-	use sp_std::collections::btree_set::BTreeSet;
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: Chainflip {
@@ -48,6 +134,8 @@ pub mod pallet {
 		type CommKeyBroadcaster: CommKeyBroadcaster;
 		/// Broadcasts the gov key to any supported chain.
 		type AnyChainGovKeyBroadcaster: BroadcastAnyChainGovKey;
+		/// Dispatches approved runtime parameter updates.
+		type RuntimeParameterDispatcher: RuntimeParameterDispatcher;
 		/// Benchmarking weights.
 		type WeightInfo: WeightInfo;
 		/// Voting period of a proposal in blocks.
@@ -61,17 +149,56 @@ pub mod pallet {
 		type EnactmentDelay: Get<BlockNumberFor<Self>>;
 	}
 
-	/// All unresolved proposals that are open for backing, indexed by the block at which the vote
+	/// All unresolved proposals that are open for voting, indexed by the block at which the vote
 	/// will be resolved.
 	#[pallet::storage]
 	#[pallet::getter(fn proposals)]
 	pub type Proposals<T: Config> = StorageMap<_, Twox64Concat, BlockNumberFor<T>, Proposal>;
 
-	/// The accounts currently backing each proposal.
+	/// The vote of each account that has voted on a proposal.
+	#[pallet::storage]
+	pub type Votes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		Proposal,
+		Blake2_128Concat,
+		T::AccountId,
+		Vote,
+		OptionQuery,
+	>;
+
+	/// The running tally of votes for each open proposal.
+	#[pallet::storage]
+	pub type Tallies<T: Config> =
+		StorageMap<_, Twox64Concat, Proposal, VoteTally<T::Amount>, ValueQuery>;
+
+	/// The voting power of each account that has voted, as of the submission of the proposal.
 	#[pallet::storage]
-	#[pallet::getter(fn backers)]
-	pub type Backers<T: Config> =
-		StorageMap<_, Twox64Concat, Proposal, BTreeSet<T::AccountId>, ValueQuery>;
+	pub type VotingPowerSnapshots<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		Proposal,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Amount,
+		OptionQuery,
+	>;
+
+	/// The total on-chain funds at the time each open proposal was submitted.
+	#[pallet::storage]
+	pub type TotalFundsSnapshots<T: Config> =
+		StorageMap<_, Twox64Concat, Proposal, T::Amount, OptionQuery>;
+
+	/// The funding balance checkpoint taken when each open proposal was submitted.
+	#[pallet::storage]
+	pub type VotingPowerCheckpoints<T: Config> =
+		StorageMap<_, Twox64Concat, Proposal, BalanceCheckpointId, OptionQuery>;
+
+	/// Resolved proposals whose votes, voting power snapshots and balance checkpoint are still
+	/// being cleared.
+	#[pallet::storage]
+	pub type PendingVoteCleanups<T> =
+		StorageValue<_, Vec<(Proposal, BalanceCheckpointId)>, ValueQuery>;
 
 	/// The Government key proposal currently awaiting enactment, if any. Indexed by the block
 	/// number we will attempt to enact this update.
@@ -85,6 +212,12 @@ pub mod pallet {
 	pub type CommKeyUpdateAwaitingEnactment<T> =
 		StorageValue<_, (BlockNumberFor<T>, Address), OptionQuery>;
 
+	/// Runtime parameter updates awaiting enactment, indexed by the block number at which they
+	/// will be dispatched.
+	#[pallet::storage]
+	pub type RuntimeParameterUpdatesAwaitingEnactment<T> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<RuntimeParameterUpdate>, ValueQuery>;
+
 	/// Current Governance keys for foreign chains.
 	#[pallet::storage]
 	pub type GovKeys<T> = StorageMap<_, Twox64Concat, ForeignChain, Vec<u8>>;
@@ -104,6 +237,12 @@ pub mod pallet {
 		GovKeyUpdatedHasFailed { chain: ForeignChain, key: Vec<u8> },
 		/// Update of GOV key was successful.
 		GovKeyUpdatedWasSuccessful { chain: ForeignChain, key: Vec<u8> },
+		/// A vote was cast, or changed, on a proposal.
+		VoteCast { proposal: Proposal, voter: T::AccountId, vote: Vote, voting_power: T::Amount },
+		/// A vote on a proposal was withdrawn.
+		VoteWithdrawn { proposal: Proposal, voter: T::AccountId },
+		/// The dispatch of an approved runtime parameter update has failed.
+		RuntimeParameterUpdateFailed { update: RuntimeParameterUpdate, error: DispatchError },
 	}
 
 	#[pallet::error]
@@ -114,6 +253,12 @@ pub mod pallet {
 		ProposalDoesntExist,
 		/// The proposed governance key is incompatible with the proposed chain.
 		IncompatibleGovkey,
+		/// An identical proposal is already open for voting.
+		ProposalAlreadyExists,
+		/// The account held no funds when the proposal was submitted.
+		NoVotingPower,
+		/// The account has not voted on this proposal.
+		NotVoted,
 	}
 
 	#[pallet::hooks]
@@ -121,10 +266,10 @@ pub mod pallet {
 		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
 			let mut weight = Weight::zero();
 			if let Some(proposal) = Proposals::<T>::take(current_block) {
-				weight = T::WeightInfo::on_initialize_resolve_votes(
-					Self::resolve_vote(proposal).try_into().unwrap(),
-				);
+				Self::resolve_vote(proposal);
+				weight.saturating_accrue(T::WeightInfo::on_initialize_resolve_votes());
 			}
+			weight.saturating_accrue(Self::process_pending_cleanups());
 			if let Some((enactment_block, (chain, new_key))) =
 				GovKeyUpdateAwaitingEnactment::<T>::get()
 			{
@@ -164,6 +309,18 @@ pub mod pallet {
 					weight.saturating_accrue(T::WeightInfo::on_initialize_execute_proposal());
 				}
 			}
+			for update in RuntimeParameterUpdatesAwaitingEnactment::<T>::take(current_block) {
+				if let Err(error) = T::RuntimeParameterDispatcher::dispatch_update(update.clone()) {
+					Self::deposit_event(Event::<T>::RuntimeParameterUpdateFailed {
+						update: update.clone(),
+						error,
+					});
+				}
+				Self::deposit_event(Event::<T>::ProposalEnacted {
+					proposal: Proposal::UpdateRuntimeParameter(update),
+				});
+				weight.saturating_accrue(T::WeightInfo::on_initialize_execute_proposal());
+			}
 			weight
 		}
 	}
@@ -171,7 +328,9 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Submit a proposal. The caller will be charged a proposal fee equal to
-		/// [Config::ProposalFee].
+		/// [Config::ProposalFee]. The funding balances of all accounts are checkpointed at
+		/// submission and used as voting power for the duration of the vote. The proposer votes in
+		/// favour of the proposal.
 		///
 		/// ## Events
 		///
@@ -181,6 +340,8 @@ pub mod pallet {
 		///
 		/// - [BadOrigin](frame_system::BadOrigin)
 		/// - [InsufficientLiquidity](pallet_cf_flip::Error::InsufficientLiquidity)
+		/// - [IncompatibleGovkey](Error::IncompatibleGovkey)
+		/// - [ProposalAlreadyExists](Error::ProposalAlreadyExists)
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::submit_proposal())]
 		pub fn submit_proposal(
@@ -194,49 +355,127 @@ pub mod pallet {
 					Error::<T>::IncompatibleGovkey
 				);
 			}
+			// A resolved proposal can only be submitted again once its votes have been cleared.
+			ensure!(
+				!TotalFundsSnapshots::<T>::contains_key(&proposal) &&
+					!PendingVoteCleanups::<T>::get()
+						.iter()
+						.any(|(pending_proposal, _)| *pending_proposal == proposal),
+				Error::<T>::ProposalAlreadyExists
+			);
 			T::FeePayment::try_burn_fee(&proposer, T::ProposalFee::get())?;
 			Proposals::<T>::insert(
 				<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get(),
 				proposal.clone(),
 			);
-			Backers::<T>::insert(proposal.clone(), BTreeSet::from([proposer]));
-			Self::deposit_event(Event::<T>::ProposalSubmitted { proposal });
+			Self::start_voting_power_snapshot(&proposal);
+			Self::deposit_event(Event::<T>::ProposalSubmitted { proposal: proposal.clone() });
+			// The proposer may have spent all their funds on the proposal fee.
+			let _ = Self::cast_vote(proposer, proposal, Vote::Aye);
 			Ok(().into())
 		}
 
-		/// Backs a proposal. The caller signals their support for a proposal.
+		/// Backs a proposal. The caller signals their support for a proposal. This is equivalent
+		/// to voting [Aye](Vote::Aye).
+		///
+		/// ## Events
+		///
+		/// - [VoteCast](Event::VoteCast)
 		///
 		/// ## Errors
 		///
 		/// - [BadOrigin](frame_system::BadOrigin)
 		/// - [ProposalDoesntExist](Error::ProposalDoesntExist)
 		/// - [AlreadyBacked](Error::AlreadyBacked)
+		/// - [NoVotingPower](Error::NoVotingPower)
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::back_proposal(Backers::<T>::decode_len(proposal).unwrap_or_default() as u32))]
+		#[pallet::weight(T::WeightInfo::vote())]
 		pub fn back_proposal(
 			origin: OriginFor<T>,
 			proposal: Proposal,
 		) -> DispatchResultWithPostInfo {
 			let backer = ensure_signed(origin)?;
-			Backers::<T>::try_mutate_exists(proposal, |maybe_backers| match maybe_backers {
-				Some(backers) => {
-					if !backers.insert(backer) {
-						return Err(Error::<T>::AlreadyBacked)
-					}
-					Ok(())
-				},
-				None => Err(Error::<T>::ProposalDoesntExist),
-			})?;
+			ensure!(
+				Votes::<T>::get(&proposal, &backer) != Some(Vote::Aye),
+				Error::<T>::AlreadyBacked
+			);
+			Self::cast_vote(backer, proposal, Vote::Aye)?;
+			Ok(().into())
+		}
+
+		/// Votes on a proposal with the voting power the caller had when the proposal was
+		/// submitted. A previous vote by the caller on the same proposal is replaced.
+		///
+		/// ## Events
+		///
+		/// - [VoteCast](Event::VoteCast)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [ProposalDoesntExist](Error::ProposalDoesntExist)
+		/// - [NoVotingPower](Error::NoVotingPower)
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(
+			origin: OriginFor<T>,
+			proposal: Proposal,
+			vote: Vote,
+		) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+			Self::cast_vote(voter, proposal, vote)?;
+			Ok(().into())
+		}
+
+		/// Withdraws the caller's vote on a proposal.
+		///
+		/// ## Events
+		///
+		/// - [VoteWithdrawn](Event::VoteWithdrawn)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [ProposalDoesntExist](Error::ProposalDoesntExist)
+		/// - [NotVoted](Error::NotVoted)
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::withdraw_vote())]
+		pub fn withdraw_vote(
+			origin: OriginFor<T>,
+			proposal: Proposal,
+		) -> DispatchResultWithPostInfo {
+			let voter = ensure_signed(origin)?;
+			ensure!(
+				TotalFundsSnapshots::<T>::contains_key(&proposal),
+				Error::<T>::ProposalDoesntExist
+			);
+			let previous_vote = Votes::<T>::take(&proposal, &voter).ok_or(Error::<T>::NotVoted)?;
+			Tallies::<T>::mutate(&proposal, |tally| {
+				tally.remove(
+					previous_vote,
+					VotingPowerSnapshots::<T>::get(&proposal, &voter).unwrap_or_default(),
+				)
+			});
+			Self::deposit_event(Event::<T>::VoteWithdrawn { proposal, voter });
 			Ok(().into())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
-		pub fn resolve_vote(proposal: Proposal) -> usize {
-			let backers = Backers::<T>::take(&proposal);
+		/// Resolves the vote on a proposal. Its votes and voting power snapshots are cleared over
+		/// the following blocks.
+		///
+		/// A proposal passes if the voting power in favour is more than two thirds of the
+		/// snapshotted on-chain funds, excluding any abstentions.
+		pub fn resolve_vote(proposal: Proposal) {
+			let tally = Tallies::<T>::take(&proposal);
+			let total_funds = TotalFundsSnapshots::<T>::take(&proposal).unwrap_or_default();
+			if let Some(checkpoint) = VotingPowerCheckpoints::<T>::take(&proposal) {
+				PendingVoteCleanups::<T>::append((proposal.clone(), checkpoint));
+			}
 			Self::deposit_event(
-				if backers.iter().map(T::FundingInfo::total_balance_of).sum::<T::Amount>() >
-					(T::FundingInfo::total_onchain_funds() / 3u32.into()) * 2u32.into()
+				if tally.ayes >
+					(total_funds.saturating_sub(tally.abstentions) / 3u32.into()) * 2u32.into()
 				{
 					let enactment_block =
 						<frame_system::Pallet<T>>::block_number() + T::EnactmentDelay::get();
@@ -250,13 +489,102 @@ pub mod pallet {
 						Proposal::SetCommunityKey(key) => {
 							CommKeyUpdateAwaitingEnactment::<T>::put((enactment_block, key));
 						},
+						Proposal::UpdateRuntimeParameter(update) => {
+							RuntimeParameterUpdatesAwaitingEnactment::<T>::append(
+								enactment_block,
+								update,
+							);
+						},
 					}
 					Event::<T>::ProposalPassed { proposal }
 				} else {
 					Event::<T>::ProposalRejected { proposal }
 				},
 			);
-			backers.len()
+		}
+
+		/// Records the total on-chain funds for the proposal, and checkpoints the funding balance
+		/// of every account as its voting power.
+		pub(crate) fn start_voting_power_snapshot(proposal: &Proposal) {
+			TotalFundsSnapshots::<T>::insert(proposal, T::FundingInfo::total_onchain_funds());
+			VotingPowerCheckpoints::<T>::insert(
+				proposal,
+				T::FundingInfo::create_balance_checkpoint(),
+			);
+		}
+
+		/// Clears the votes, voting power snapshots and balance checkpoints of resolved proposals,
+		/// at most [MAX_ACCOUNTS_PER_BLOCK] entries in total.
+		pub(crate) fn process_pending_cleanups() -> Weight {
+			let mut cleared = 0u32;
+			let mut pending_cleanups = PendingVoteCleanups::<T>::get();
+			let cleanups_pending = !pending_cleanups.is_empty();
+			while let Some((proposal, checkpoint)) = pending_cleanups.first() {
+				let limit = MAX_ACCOUNTS_PER_BLOCK.saturating_sub(cleared);
+				if limit == 0 {
+					break
+				}
+				// Votes are cleared first: there can only be a vote where there is a snapshot.
+				let votes = Votes::<T>::clear_prefix(proposal, limit, None);
+				cleared += votes.unique;
+				if votes.maybe_cursor.is_some() || votes.unique == limit {
+					break
+				}
+				let snapshots =
+					VotingPowerSnapshots::<T>::clear_prefix(proposal, limit - votes.unique, None);
+				cleared += snapshots.unique;
+				if snapshots.maybe_cursor.is_some() || cleared == MAX_ACCOUNTS_PER_BLOCK {
+					break
+				}
+				let (released, complete) = T::FundingInfo::release_balance_checkpoint(
+					*checkpoint,
+					MAX_ACCOUNTS_PER_BLOCK - cleared,
+				);
+				cleared += released;
+				if !complete {
+					break
+				}
+				pending_cleanups.remove(0);
+			}
+			if cleanups_pending {
+				PendingVoteCleanups::<T>::put(pending_cleanups);
+			}
+
+			T::WeightInfo::on_initialize_clear_votes(cleared)
+		}
+
+		/// The voting power of an account: the funding balance it held when the proposal was
+		/// submitted.
+		fn voting_power(proposal: &Proposal, voter: &T::AccountId) -> Option<T::Amount> {
+			VotingPowerSnapshots::<T>::get(proposal, voter).or_else(|| {
+				let balance = T::FundingInfo::balance_at_checkpoint(
+					VotingPowerCheckpoints::<T>::get(proposal)?,
+					voter,
+				);
+				if balance.is_zero() {
+					return None
+				}
+				VotingPowerSnapshots::<T>::insert(proposal, voter, balance);
+				Some(balance)
+			})
+		}
+
+		fn cast_vote(voter: T::AccountId, proposal: Proposal, vote: Vote) -> DispatchResult {
+			ensure!(
+				TotalFundsSnapshots::<T>::contains_key(&proposal),
+				Error::<T>::ProposalDoesntExist
+			);
+			let voting_power =
+				Self::voting_power(&proposal, &voter).ok_or(Error::<T>::NoVotingPower)?;
+			Tallies::<T>::mutate(&proposal, |tally| {
+				if let Some(previous_vote) = Votes::<T>::get(&proposal, &voter) {
+					tally.remove(previous_vote, voting_power);
+				}
+				tally.add(vote, voting_power);
+			});
+			Votes::<T>::insert(&proposal, &voter, vote);
+			Self::deposit_event(Event::<T>::VoteCast { proposal, voter, vote, voting_power });
+			Ok(())
 		}
 	}
 }
@@ -1,5 +1,7 @@
+pub mod v2;
+
 use crate::Pallet;
 
-use cf_runtime_upgrade_utilities::{migration_template::Migration, VersionedMigration};
+use cf_runtime_upgrade_utilities::VersionedMigration;
 
-pub type PalletMigration<T> = VersionedMigration<Pallet<T>, Migration<T>, 0, 1>;
+pub type PalletMigration<T> = (VersionedMigration<Pallet<T>, v2::Migration<T>, 1, 2>,);
@@ -0,0 +1,106 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
+
+#[cfg(feature = "try-runtime")]
+use frame_support::pallet_prelude::DispatchError;
+
+/// Runtime Migration for migrating from V1 to V2: replaces the set of backers of each open
+/// proposal with a voting power snapshot and an `Aye` vote for each of the previous backers.
+pub struct Migration<T: Config>(PhantomData<T>);
+
+mod old {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub type Backers<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		Proposal,
+		BTreeSet<<T as frame_system::Config>::AccountId>,
+		ValueQuery,
+	>;
+}
+
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let mut reads_writes = 0u64;
+		for (proposal, backers) in old::Backers::<T>::drain() {
+			// Balances at the time of submission are unknown, so the balances at the time of the
+			// upgrade are used. The backers are snapshotted here, and all other accounts are
+			// checkpointed.
+			Pallet::<T>::start_voting_power_snapshot(&proposal);
+			reads_writes += 4;
+			for backer in backers {
+				let voting_power = T::FundingInfo::total_balance_of(&backer);
+				reads_writes += 1;
+				if !voting_power.is_zero() {
+					VotingPowerSnapshots::<T>::insert(&proposal, &backer, voting_power);
+					Votes::<T>::insert(&proposal, &backer, Vote::Aye);
+					Tallies::<T>::mutate(&proposal, |tally| {
+						tally.ayes = tally.ayes.saturating_add(voting_power)
+					});
+					reads_writes += 3;
+				}
+			}
+		}
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((old::Backers::<T>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let number_of_proposals =
+			<u32>::decode(&mut &state[..]).map_err(|_| "Failed to decode pre-upgrade state.")?;
+		ensure!(
+			number_of_proposals == TotalFundsSnapshots::<T>::iter_keys().count() as u32,
+			"Proposal snapshots mismatch!"
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test_runtime_upgrade {
+	use super::*;
+	use mock::{Test, TokenholderGovernance, ALICE, BOB, EVE};
+
+	#[test]
+	fn test() {
+		mock::new_test_ext().execute_with(|| {
+			let proposal = Proposal::SetCommunityKey(Address::repeat_byte(1));
+			// pre upgrade
+			Proposals::<Test>::insert(10, proposal.clone());
+			old::Backers::<Test>::insert(&proposal, BTreeSet::from([ALICE, BOB]));
+
+			#[cfg(feature = "try-runtime")]
+			let state = Migration::<Test>::pre_upgrade().unwrap();
+
+			// upgrade
+			Migration::<Test>::on_runtime_upgrade();
+			TokenholderGovernance::on_initialize(2);
+
+			// post upgrade
+			#[cfg(feature = "try-runtime")]
+			Migration::<Test>::post_upgrade(state).unwrap();
+
+			assert!(!old::Backers::<Test>::contains_key(&proposal));
+			assert_eq!(Votes::<Test>::get(&proposal, ALICE), Some(Vote::Aye));
+			assert_eq!(Votes::<Test>::get(&proposal, BOB), Some(Vote::Aye));
+			assert_eq!(Votes::<Test>::get(&proposal, EVE), None);
+			assert_eq!(VotingPowerSnapshots::<Test>::get(&proposal, EVE), None);
+			assert_eq!(
+				<Test as Chainflip>::FundingInfo::balance_at_checkpoint(
+					VotingPowerCheckpoints::<Test>::get(&proposal).unwrap(),
+					&EVE
+				),
+				200
+			);
+			assert_eq!(Tallies::<Test>::get(&proposal).ayes, 700);
+		});
+	}
+}
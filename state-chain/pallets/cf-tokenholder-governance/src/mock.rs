@@ -1,4 +1,6 @@
-use crate::{self as pallet_cf_tokenholder_governance};
+use crate::{
+	self as pallet_cf_tokenholder_governance, RuntimeParameterDispatcher, RuntimeParameterUpdate,
+};
 use cf_chains::{Chain, ChainCrypto, Ethereum, ForeignChain};
 use cf_traits::{
 	impl_mock_chainflip, impl_mock_ensure_witnessed_for_origin, impl_mock_on_account_funded,
@@ -9,7 +11,10 @@ use codec::{Decode, Encode};
 use frame_support::{parameter_types, traits::HandleLifetime};
 use frame_system as system;
 use sp_core::H256;
-use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
+};
 
 use system::pallet_prelude::BlockNumberFor;
 
@@ -134,11 +139,31 @@ impl CommKeyBroadcaster for MockBroadcaster {
 	}
 }
 
+pub struct MockRuntimeParameterDispatcher;
+
+#[frame_support::storage_alias]
+type DispatchedRuntimeParameterUpdates =
+	StorageValue<Mock, Vec<RuntimeParameterUpdate>, frame_support::pallet_prelude::ValueQuery>;
+
+impl MockRuntimeParameterDispatcher {
+	pub fn dispatched_updates() -> Vec<RuntimeParameterUpdate> {
+		DispatchedRuntimeParameterUpdates::get()
+	}
+}
+
+impl RuntimeParameterDispatcher for MockRuntimeParameterDispatcher {
+	fn dispatch_update(update: RuntimeParameterUpdate) -> Result<(), DispatchError> {
+		DispatchedRuntimeParameterUpdates::append(update);
+		Ok(())
+	}
+}
+
 impl pallet_cf_tokenholder_governance::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type FeePayment = MockFeePayment<Self>;
 	type CommKeyBroadcaster = MockBroadcaster;
 	type AnyChainGovKeyBroadcaster = MockBroadcaster;
+	type RuntimeParameterDispatcher = MockRuntimeParameterDispatcher;
 	type WeightInfo = ();
 	type VotingPeriod = VotingPeriod;
 	type EnactmentDelay = EnactmentDelay;
@@ -1,5 +1,6 @@
 use crate::tests::TokenholderGovernance;
 use cf_test_utilities::last_event;
+use cf_traits::{mocks::funding_info::MockFundingInfo, FundingInfo};
 use frame_support::{assert_noop, assert_ok};
 
 use crate::{mock::*, *};
@@ -176,7 +177,9 @@ fn not_enough_backed_liquidity_for_proposal_enactment() {
 			<frame_system::Pallet<Test>>::block_number() +
 				<mock::Test as Config>::VotingPeriod::get()
 		));
-		assert!(!Backers::<Test>::contains_key(&proposal));
+		assert!(!Tallies::<Test>::contains_key(&proposal));
+		assert!(!TotalFundsSnapshots::<Test>::contains_key(&proposal));
+		assert_eq!(VotingPowerSnapshots::<Test>::iter_prefix(&proposal).count(), 0);
 		assert!(GovKeyUpdateAwaitingEnactment::<Test>::get().is_none());
 		assert_eq!(
 			last_event::<Test>(),
@@ -255,3 +258,281 @@ fn govkey_broadcast_to_correct_chain() {
 		);
 	});
 }
+
+fn resolve_proposal_submitted_now() {
+	TokenholderGovernance::on_initialize(
+		<frame_system::Pallet<Test>>::block_number() + <mock::Test as Config>::VotingPeriod::get(),
+	);
+}
+
+#[test]
+fn can_vote_against_and_change_vote() {
+	new_test_ext().execute_with(|| {
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+		assert_ok!(TokenholderGovernance::vote(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone(),
+			Vote::Nay
+		));
+		assert_eq!(
+			last_event::<Test>(),
+			mock::RuntimeEvent::TokenholderGovernance(crate::Event::VoteCast {
+				proposal: proposal.clone(),
+				voter: BOB,
+				vote: Vote::Nay,
+				voting_power: 200,
+			}),
+		);
+		assert_eq!(
+			Tallies::<Test>::get(&proposal),
+			VoteTally { ayes: 400, nays: 200, abstentions: 0 }
+		);
+
+		// Changing the vote moves the voting power to the new option.
+		assert_ok!(TokenholderGovernance::vote(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone(),
+			Vote::Aye
+		));
+		assert_ok!(TokenholderGovernance::vote(
+			RuntimeOrigin::signed(CHARLES),
+			proposal.clone(),
+			Vote::Aye
+		));
+		assert_eq!(
+			Tallies::<Test>::get(&proposal),
+			VoteTally { ayes: 700, nays: 0, abstentions: 0 }
+		);
+
+		resolve_proposal_submitted_now();
+		assert_eq!(
+			last_event::<Test>(),
+			mock::RuntimeEvent::TokenholderGovernance(crate::Event::ProposalPassed { proposal }),
+		);
+	});
+}
+
+#[test]
+fn can_withdraw_vote() {
+	new_test_ext().execute_with(|| {
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+		assert_noop!(
+			TokenholderGovernance::withdraw_vote(RuntimeOrigin::signed(BOB), proposal.clone()),
+			Error::<Test>::NotVoted
+		);
+		assert_ok!(TokenholderGovernance::back_proposal(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone()
+		));
+		assert_ok!(TokenholderGovernance::back_proposal(
+			RuntimeOrigin::signed(CHARLES),
+			proposal.clone()
+		));
+		assert_ok!(TokenholderGovernance::withdraw_vote(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone()
+		));
+		assert_eq!(Votes::<Test>::get(&proposal, BOB), None);
+		assert_eq!(Tallies::<Test>::get(&proposal).ayes, 500);
+
+		resolve_proposal_submitted_now();
+		assert_eq!(
+			last_event::<Test>(),
+			mock::RuntimeEvent::TokenholderGovernance(crate::Event::ProposalRejected { proposal }),
+		);
+	});
+}
+
+#[test]
+fn abstentions_are_excluded_from_the_threshold() {
+	new_test_ext().execute_with(|| {
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+		assert_ok!(TokenholderGovernance::back_proposal(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone()
+		));
+		// 600 of 999 is not enough on its own, but it is once EVE abstains.
+		assert_ok!(TokenholderGovernance::vote(
+			RuntimeOrigin::signed(EVE),
+			proposal.clone(),
+			Vote::Abstain
+		));
+
+		resolve_proposal_submitted_now();
+		assert_eq!(
+			last_event::<Test>(),
+			mock::RuntimeEvent::TokenholderGovernance(crate::Event::ProposalPassed { proposal }),
+		);
+	});
+}
+
+#[test]
+fn voting_power_is_snapshotted_at_submission() {
+	new_test_ext().execute_with(|| {
+		const NEW_ACCOUNT: u64 = 2024;
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+		go_to_block(2);
+
+		MockFundingInfo::<Test>::credit_funds(&BOB, 1_000);
+		MockFundingInfo::<Test>::credit_funds(&NEW_ACCOUNT, 1_000);
+
+		assert_ok!(TokenholderGovernance::back_proposal(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone()
+		));
+		assert_eq!(Tallies::<Test>::get(&proposal).ayes, 600);
+		assert_noop!(
+			TokenholderGovernance::back_proposal(RuntimeOrigin::signed(NEW_ACCOUNT), proposal),
+			Error::<Test>::NoVotingPower
+		);
+	});
+}
+
+#[test]
+fn cannot_submit_the_same_proposal_twice() {
+	new_test_ext().execute_with(|| {
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+		assert_noop!(
+			TokenholderGovernance::submit_proposal(RuntimeOrigin::signed(BOB), proposal),
+			Error::<Test>::ProposalAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn update_runtime_parameter_via_onchain_proposal() {
+	new_test_ext().execute_with(|| {
+		let update = RuntimeParameterUpdate::PoolFees {
+			base_asset: Asset::Eth,
+			quote_asset: Asset::Usdc,
+			fee_hundredth_pips: 500,
+		};
+		let proposal = Proposal::UpdateRuntimeParameter(update.clone());
+		submit_and_pass_proposal(proposal.clone());
+
+		let enactment_block = <frame_system::Pallet<Test>>::block_number() +
+			<mock::Test as Config>::EnactmentDelay::get();
+		assert_eq!(
+			RuntimeParameterUpdatesAwaitingEnactment::<Test>::get(enactment_block),
+			vec![update.clone()]
+		);
+		TokenholderGovernance::on_initialize(enactment_block);
+		assert!(!RuntimeParameterUpdatesAwaitingEnactment::<Test>::contains_key(enactment_block));
+		assert_eq!(MockRuntimeParameterDispatcher::dispatched_updates(), vec![update]);
+		assert_eq!(
+			last_event::<Test>(),
+			mock::RuntimeEvent::TokenholderGovernance(crate::Event::ProposalEnacted { proposal }),
+		);
+	});
+}
+
+#[test]
+fn vote_cleanup_is_spread_over_blocks() {
+	new_test_ext().execute_with(|| {
+		const NEW_ACCOUNTS: u64 = MAX_ACCOUNTS_PER_BLOCK as u64 + 500;
+		for account_id in 0..NEW_ACCOUNTS {
+			MockFundingInfo::<Test>::credit_funds(&(10_000 + account_id), 1);
+		}
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+
+		go_to_block(1);
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+		let checkpoint = VotingPowerCheckpoints::<Test>::get(&proposal).unwrap();
+		for account_id in 0..NEW_ACCOUNTS {
+			assert_ok!(TokenholderGovernance::vote(
+				RuntimeOrigin::signed(10_000 + account_id),
+				proposal.clone(),
+				Vote::Nay
+			));
+		}
+		let voters = NEW_ACCOUNTS as usize + 1;
+		assert_eq!(VotingPowerSnapshots::<Test>::iter_prefix(&proposal).count(), voters);
+
+		go_to_block(1 + <mock::Test as Config>::VotingPeriod::get());
+		assert_eq!(
+			last_event::<Test>(),
+			mock::RuntimeEvent::TokenholderGovernance(crate::Event::ProposalRejected {
+				proposal: proposal.clone()
+			}),
+		);
+		assert!(!VotingPowerCheckpoints::<Test>::contains_key(&proposal));
+		assert_eq!(PendingVoteCleanups::<Test>::get(), vec![(proposal.clone(), checkpoint)]);
+		assert_eq!(
+			Votes::<Test>::iter_prefix(&proposal).count(),
+			voters - MAX_ACCOUNTS_PER_BLOCK as usize
+		);
+		assert_eq!(VotingPowerSnapshots::<Test>::iter_prefix(&proposal).count(), voters);
+		assert_noop!(
+			TokenholderGovernance::submit_proposal(RuntimeOrigin::signed(ALICE), proposal.clone()),
+			Error::<Test>::ProposalAlreadyExists
+		);
+
+		go_to_block(2 + <mock::Test as Config>::VotingPeriod::get());
+		assert_eq!(Votes::<Test>::iter_prefix(&proposal).count(), 0);
+		assert_eq!(
+			VotingPowerSnapshots::<Test>::iter_prefix(&proposal).count(),
+			2 * voters - 2 * MAX_ACCOUNTS_PER_BLOCK as usize
+		);
+
+		go_to_block(3 + <mock::Test as Config>::VotingPeriod::get());
+		assert_eq!(
+			VotingPowerSnapshots::<Test>::iter_prefix(&proposal).count(),
+			2 * voters - 3 * MAX_ACCOUNTS_PER_BLOCK as usize
+		);
+
+		go_to_block(4 + <mock::Test as Config>::VotingPeriod::get());
+		assert!(PendingVoteCleanups::<Test>::get().is_empty());
+		assert_eq!(VotingPowerSnapshots::<Test>::iter_prefix(&proposal).count(), 0);
+		assert_eq!(MockFundingInfo::<Test>::balance_at_checkpoint(checkpoint, &ALICE), 0);
+	});
+}
+
+#[test]
+fn funds_added_after_submission_do_not_count() {
+	new_test_ext().execute_with(|| {
+		const NEW_ACCOUNT: u64 = 2024;
+		let proposal = Proposal::SetCommunityKey(sp_core::H160::from_slice(&[1; 20]));
+		assert_ok!(TokenholderGovernance::submit_proposal(
+			RuntimeOrigin::signed(ALICE),
+			proposal.clone()
+		));
+
+		// Neither voter has voted, or been snapshotted, before funding themselves.
+		MockFundingInfo::<Test>::credit_funds(&BOB, 1_000);
+		MockFundingInfo::<Test>::credit_funds(&NEW_ACCOUNT, 1_000);
+		assert_ok!(TokenholderGovernance::vote(
+			RuntimeOrigin::signed(BOB),
+			proposal.clone(),
+			Vote::Nay
+		));
+		assert_eq!(Tallies::<Test>::get(&proposal).nays, 200);
+		assert_eq!(VotingPowerSnapshots::<Test>::get(&proposal, BOB), Some(200));
+		assert_noop!(
+			TokenholderGovernance::vote(RuntimeOrigin::signed(NEW_ACCOUNT), proposal, Vote::Nay),
+			Error::<Test>::NoVotingPower
+		);
+	});
+}
@@ -32,10 +32,12 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_cf_tokenholder_governance.
 pub trait WeightInfo {
-	fn on_initialize_resolve_votes(a: u32, ) -> Weight;
+	fn on_initialize_resolve_votes() -> Weight;
+	fn on_initialize_clear_votes(a: u32, ) -> Weight;
 	fn on_initialize_execute_proposal() -> Weight;
 	fn submit_proposal() -> Weight;
-	fn back_proposal(a: u32, ) -> Weight;
+	fn vote() -> Weight;
+	fn withdraw_vote() -> Weight;
 }
 
 /// Weights for pallet_cf_tokenholder_governance using the Substrate node and recommended hardware.
@@ -43,31 +45,37 @@ pub struct PalletWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Storage: `TokenholderGovernance::Proposals` (r:1 w:1)
 	/// Proof: `TokenholderGovernance::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `TokenholderGovernance::Backers` (r:1 w:1)
-	/// Proof: `TokenholderGovernance::Backers` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Flip::Account` (r:1000 w:0)
-	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
-	/// Storage: `Flip::TotalIssuance` (r:1 w:0)
-	/// Proof: `Flip::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `Flip::OffchainFunds` (r:1 w:0)
-	/// Proof: `Flip::OffchainFunds` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerCheckpoints` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerCheckpoints` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::PendingVoteCleanups` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::PendingVoteCleanups` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `TokenholderGovernance::CommKeyUpdateAwaitingEnactment` (r:1 w:0)
 	/// Proof: `TokenholderGovernance::CommKeyUpdateAwaitingEnactment` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `TokenholderGovernance::GovKeyUpdateAwaitingEnactment` (r:0 w:1)
 	/// Proof: `TokenholderGovernance::GovKeyUpdateAwaitingEnactment` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// The range of component `a` is `[10, 1000]`.
-	fn on_initialize_resolve_votes(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `644 + a * (118 ±0)`
-		//  Estimated: `4118 + a * (2555 ±0)`
-		// Minimum execution time: 67_862_000 picoseconds.
-		Weight::from_parts(68_101_000, 4118)
-			// Standard Error: 18_101
-			.saturating_add(Weight::from_parts(4_134_028, 0).saturating_mul(a.into()))
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
-			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(a.into()))
+	fn on_initialize_resolve_votes() -> Weight {
+		Weight::from_parts(32_115_000, 4118)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `TokenholderGovernance::PendingVoteCleanups` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::PendingVoteCleanups` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:0 w:1000)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:0 w:1000)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[0, 1000]`.
+	fn on_initialize_clear_votes(a: u32, ) -> Weight {
+		Weight::from_parts(3_914_000, 3877)
+			.saturating_add(Weight::from_parts(2_106_437, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2545).saturating_mul(a.into()))
 	}
 	/// Storage: `TokenholderGovernance::Proposals` (r:1 w:0)
 	/// Proof: `TokenholderGovernance::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -118,37 +126,60 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(20_u64))
 			.saturating_add(T::DbWeight::get().writes(10_u64))
 	}
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::PendingVoteCleanups` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::PendingVoteCleanups` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Flip::Account` (r:1 w:1)
 	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
 	/// Storage: `Flip::TotalIssuance` (r:1 w:1)
 	/// Proof: `Flip::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `TokenholderGovernance::Backers` (r:0 w:1)
-	/// Proof: `TokenholderGovernance::Backers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::OffchainFunds` (r:1 w:0)
+	/// Proof: `Flip::OffchainFunds` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `TokenholderGovernance::VotingPowerCheckpoints` (r:0 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerCheckpoints` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `TokenholderGovernance::Proposals` (r:0 w:1)
 	/// Proof: `TokenholderGovernance::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn submit_proposal() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `479`
-		//  Estimated: `3545`
-		// Minimum execution time: 26_813_000 picoseconds.
-		Weight::from_parts(27_369_000, 3545)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+		Weight::from_parts(49_306_000, 3996)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
-	/// Storage: `TokenholderGovernance::Backers` (r:1 w:1)
-	/// Proof: `TokenholderGovernance::Backers` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// The range of component `a` is `[1, 1000]`.
-	fn back_proposal(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `185 + a * (32 ±0)`
-		//  Estimated: `3650 + a * (32 ±0)`
-		// Minimum execution time: 10_990_000 picoseconds.
-		Weight::from_parts(13_089_416, 3650)
-			// Standard Error: 559
-			.saturating_add(Weight::from_parts(71_329, 0).saturating_mul(a.into()))
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
-			.saturating_add(Weight::from_parts(0, 32).saturating_mul(a.into()))
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerCheckpoints` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::VotingPowerCheckpoints` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:0)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn vote() -> Weight {
+		Weight::from_parts(28_203_000, 3995)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn withdraw_vote() -> Weight {
+		Weight::from_parts(19_377_000, 3877)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 }
 
@@ -156,31 +187,37 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 impl WeightInfo for () {
 	/// Storage: `TokenholderGovernance::Proposals` (r:1 w:1)
 	/// Proof: `TokenholderGovernance::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `TokenholderGovernance::Backers` (r:1 w:1)
-	/// Proof: `TokenholderGovernance::Backers` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Flip::Account` (r:1000 w:0)
-	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
-	/// Storage: `Flip::TotalIssuance` (r:1 w:0)
-	/// Proof: `Flip::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `Flip::OffchainFunds` (r:1 w:0)
-	/// Proof: `Flip::OffchainFunds` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerCheckpoints` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerCheckpoints` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::PendingVoteCleanups` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::PendingVoteCleanups` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `TokenholderGovernance::CommKeyUpdateAwaitingEnactment` (r:1 w:0)
 	/// Proof: `TokenholderGovernance::CommKeyUpdateAwaitingEnactment` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `TokenholderGovernance::GovKeyUpdateAwaitingEnactment` (r:0 w:1)
 	/// Proof: `TokenholderGovernance::GovKeyUpdateAwaitingEnactment` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// The range of component `a` is `[10, 1000]`.
-	fn on_initialize_resolve_votes(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `644 + a * (118 ±0)`
-		//  Estimated: `4118 + a * (2555 ±0)`
-		// Minimum execution time: 67_862_000 picoseconds.
-		Weight::from_parts(68_101_000, 4118)
-			// Standard Error: 18_101
-			.saturating_add(Weight::from_parts(4_134_028, 0).saturating_mul(a.into()))
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
-			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
-			.saturating_add(Weight::from_parts(0, 2555).saturating_mul(a.into()))
+	fn on_initialize_resolve_votes() -> Weight {
+		Weight::from_parts(32_115_000, 4118)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `TokenholderGovernance::PendingVoteCleanups` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::PendingVoteCleanups` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:0 w:1000)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:0 w:1000)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `a` is `[0, 1000]`.
+	fn on_initialize_clear_votes(a: u32, ) -> Weight {
+		Weight::from_parts(3_914_000, 3877)
+			.saturating_add(Weight::from_parts(2_106_437, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2545).saturating_mul(a.into()))
 	}
 	/// Storage: `TokenholderGovernance::Proposals` (r:1 w:0)
 	/// Proof: `TokenholderGovernance::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -231,36 +268,59 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(20_u64))
 			.saturating_add(RocksDbWeight::get().writes(10_u64))
 	}
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::PendingVoteCleanups` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::PendingVoteCleanups` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Flip::Account` (r:1 w:1)
 	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
 	/// Storage: `Flip::TotalIssuance` (r:1 w:1)
 	/// Proof: `Flip::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
-	/// Storage: `TokenholderGovernance::Backers` (r:0 w:1)
-	/// Proof: `TokenholderGovernance::Backers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::OffchainFunds` (r:1 w:0)
+	/// Proof: `Flip::OffchainFunds` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `TokenholderGovernance::VotingPowerCheckpoints` (r:0 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerCheckpoints` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `TokenholderGovernance::Proposals` (r:0 w:1)
 	/// Proof: `TokenholderGovernance::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn submit_proposal() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `479`
-		//  Estimated: `3545`
-		// Minimum execution time: 26_813_000 picoseconds.
-		Weight::from_parts(27_369_000, 3545)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+		Weight::from_parts(49_306_000, 3996)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
-	/// Storage: `TokenholderGovernance::Backers` (r:1 w:1)
-	/// Proof: `TokenholderGovernance::Backers` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// The range of component `a` is `[1, 1000]`.
-	fn back_proposal(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `185 + a * (32 ±0)`
-		//  Estimated: `3650 + a * (32 ±0)`
-		// Minimum execution time: 10_990_000 picoseconds.
-		Weight::from_parts(13_089_416, 3650)
-			// Standard Error: 559
-			.saturating_add(Weight::from_parts(71_329, 0).saturating_mul(a.into()))
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
-			.saturating_add(Weight::from_parts(0, 32).saturating_mul(a.into()))
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerCheckpoints` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::VotingPowerCheckpoints` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:0)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn vote() -> Weight {
+		Weight::from_parts(28_203_000, 3995)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `TokenholderGovernance::TotalFundsSnapshots` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::TotalFundsSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Votes` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::VotingPowerSnapshots` (r:1 w:0)
+	/// Proof: `TokenholderGovernance::VotingPowerSnapshots` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TokenholderGovernance::Tallies` (r:1 w:1)
+	/// Proof: `TokenholderGovernance::Tallies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn withdraw_vote() -> Weight {
+		Weight::from_parts(19_377_000, 3877)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 }
@@ -963,6 +963,8 @@ impl<T: Config> Pallet<T> {
 				.collect(),
 		);
 
+		T::EpochTransitionHandler::on_new_epoch(old_epoch);
+
 		Self::deposit_event(Event::NewEpoch(new_epoch));
 	}
 
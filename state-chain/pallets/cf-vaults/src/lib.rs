@@ -198,6 +198,15 @@ pub mod pallet {
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Bootstraps the vault: the key is activated once governance has witnessed the creation of
+	/// the vault on the external chain.
+	pub fn await_governance_activation(new_public_key: AggKeyFor<T, I>) {
+		PendingVaultActivation::<T, I>::put(VaultActivationStatus::<T, I>::AwaitingActivation {
+			new_public_key,
+		});
+		Self::deposit_event(Event::<T, I>::AwaitingGovernanceActivation { new_public_key });
+	}
+
 	fn activate_new_key_for_chain(block_number: ChainBlockNumberFor<T, I>) {
 		PendingVaultActivation::<T, I>::put(VaultActivationStatus::<T, I>::Complete);
 		VaultStartBlockNumbers::<T, I>::insert(
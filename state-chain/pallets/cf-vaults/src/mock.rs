@@ -21,6 +21,7 @@ use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
 
 thread_local! {
 	pub static SET_AGG_KEY_WITH_AGG_KEY_REQUIRED: RefCell<bool> = RefCell::new(true);
+	pub static VAULT_CREATED: RefCell<bool> = RefCell::new(true);
 }
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -77,6 +78,12 @@ impl MockSetAggKeyWithAggKey {
 			*cell.borrow_mut() = required;
 		});
 	}
+
+	pub fn set_vault_created(created: bool) {
+		VAULT_CREATED.with(|cell| {
+			*cell.borrow_mut() = created;
+		});
+	}
 }
 
 impl SetAggKeyWithAggKey<MockEthereumChainCrypto> for MockSetAggKeyWithAggKey {
@@ -87,6 +94,9 @@ impl SetAggKeyWithAggKey<MockEthereumChainCrypto> for MockSetAggKeyWithAggKey {
 		if !SET_AGG_KEY_WITH_AGG_KEY_REQUIRED.with(|cell| *cell.borrow()) {
 			return Err(SetAggKeyWithAggKeyError::NotRequired)
 		}
+		if !VAULT_CREATED.with(|cell| *cell.borrow()) {
+			return Err(SetAggKeyWithAggKeyError::VaultNotCreated)
+		}
 
 		Ok(Self { old_key: old_key.ok_or(SetAggKeyWithAggKeyError::Failed)?, new_key })
 	}
@@ -166,6 +176,10 @@ impl Broadcaster<MockEthereum> for MockBroadcaster {
 	fn clean_up_broadcast_storage(_broadcast_id: BroadcastId) {
 		unimplemented!()
 	}
+
+	fn witness_after_abort(_broadcast_id: BroadcastId) {
+		unimplemented!()
+	}
 }
 
 parameter_types! {
@@ -66,6 +66,18 @@ fn when_set_agg_key_with_agg_key_not_required_we_skip_to_completion() {
 	});
 }
 
+#[test]
+fn vault_not_created_on_activate_returns_governance_event() {
+	new_test_ext().execute_with(|| {
+		MockSetAggKeyWithAggKey::set_vault_created(false);
+
+		VaultsPallet::activate(NEW_AGG_PUBKEY, Some(Default::default()));
+
+		assert_last_event!(crate::Event::AwaitingGovernanceActivation { .. });
+		assert_eq!(VaultsPallet::status(), AsyncResult::Pending);
+	});
+}
+
 #[test]
 fn vault_start_block_number_is_set_correctly() {
 	new_test_ext_no_key().execute_with(|| {
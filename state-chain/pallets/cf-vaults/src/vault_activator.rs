@@ -33,6 +33,11 @@ impl<T: Config<I>, I: 'static> VaultActivator<<T::Chain as Chain>::ChainCrypto>
 					// need to be swept.
 					Self::activate_new_key_for_chain(T::ChainTracking::get_block_height());
 				},
+				Err(SetAggKeyWithAggKeyError::VaultNotCreated) => {
+					// The vault has to be created and witnessed by governance, as if there was no
+					// active key.
+					Self::await_governance_activation(new_public_key);
+				},
 				Err(SetAggKeyWithAggKeyError::Failed) => {
 					log_or_panic!(
 						"Unexpected failure during {} vault activation.",
@@ -42,10 +47,7 @@ impl<T: Config<I>, I: 'static> VaultActivator<<T::Chain as Chain>::ChainCrypto>
 			}
 		} else {
 			// No active key means we are bootstrapping the vault.
-			PendingVaultActivation::<T, I>::put(
-				VaultActivationStatus::<T, I>::AwaitingActivation { new_public_key },
-			);
-			Self::deposit_event(Event::<T, I>::AwaitingGovernanceActivation { new_public_key });
+			Self::await_governance_activation(new_public_key);
 		}
 	}
 
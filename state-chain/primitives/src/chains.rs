@@ -90,7 +90,8 @@ macro_rules! chains {
 chains! {
 	Ethereum = 1,
 	Polkadot = 2,
-	Bitcoin = 3
+	Bitcoin = 3,
+	Assethub = 4
 }
 
 /// Can be any Chain.
@@ -115,6 +116,24 @@ impl ForeignChain {
 			ForeignChain::Ethereum => assets::any::Asset::Eth,
 			ForeignChain::Polkadot => assets::any::Asset::Dot,
 			ForeignChain::Bitcoin => assets::any::Asset::Btc,
+			ForeignChain::Assethub => assets::any::Asset::HubDot,
+		}
+	}
+
+	/// Whether cross-chain messages can be egressed to this chain.
+	pub const fn supports_ccm(self) -> bool {
+		match self {
+			ForeignChain::Ethereum | ForeignChain::Polkadot => true,
+			ForeignChain::Bitcoin | ForeignChain::Assethub => false,
+		}
+	}
+
+	/// Whether cross-chain messages to this chain can carry a gas budget. Messages to Polkadot are
+	/// delivered as a remark, so there is no call for a gas budget to pay for.
+	pub const fn supports_ccm_gas_budget(self) -> bool {
+		match self {
+			ForeignChain::Ethereum => true,
+			ForeignChain::Polkadot | ForeignChain::Bitcoin | ForeignChain::Assethub => false,
 		}
 	}
 }
@@ -124,6 +143,7 @@ fn chain_as_u32() {
 	assert_eq!(ForeignChain::Ethereum as u32, 1);
 	assert_eq!(ForeignChain::Polkadot as u32, 2);
 	assert_eq!(ForeignChain::Bitcoin as u32, 3);
+	assert_eq!(ForeignChain::Assethub as u32, 4);
 }
 
 #[test]
@@ -131,7 +151,8 @@ fn chain_id_to_chain() {
 	assert_eq!(ForeignChain::try_from(1), Ok(ForeignChain::Ethereum));
 	assert_eq!(ForeignChain::try_from(2), Ok(ForeignChain::Polkadot));
 	assert_eq!(ForeignChain::try_from(3), Ok(ForeignChain::Bitcoin));
-	assert!(ForeignChain::try_from(4).is_err());
+	assert_eq!(ForeignChain::try_from(4), Ok(ForeignChain::Assethub));
+	assert!(ForeignChain::try_from(5).is_err());
 }
 
 #[test]
@@ -139,6 +160,7 @@ fn test_chains() {
 	assert_eq!(Ethereum.as_ref(), &ForeignChain::Ethereum);
 	assert_eq!(Polkadot.as_ref(), &ForeignChain::Polkadot);
 	assert_eq!(Bitcoin.as_ref(), &ForeignChain::Bitcoin);
+	assert_eq!(Assethub.as_ref(), &ForeignChain::Assethub);
 }
 
 #[test]
@@ -146,6 +168,7 @@ fn test_get_chain_identifier() {
 	assert_eq!(Ethereum::get(), ForeignChain::Ethereum);
 	assert_eq!(Polkadot::get(), ForeignChain::Polkadot);
 	assert_eq!(Bitcoin::get(), ForeignChain::Bitcoin);
+	assert_eq!(Assethub::get(), ForeignChain::Assethub);
 }
 
 #[test]
@@ -162,4 +185,8 @@ fn test_chain_to_and_from_str() {
 		ForeignChain::from_str(ForeignChain::Bitcoin.to_string().as_str()).unwrap(),
 		ForeignChain::Bitcoin
 	);
+	assert_eq!(
+		ForeignChain::from_str(ForeignChain::Assethub.to_string().as_str()).unwrap(),
+		ForeignChain::Assethub
+	);
 }
@@ -201,10 +201,12 @@ macro_rules! assets {
 						assert_eq!(assert_ok!(serde_json::to_string(&Asset::Eth)), "{\"chain\":\"Ethereum\",\"asset\":\"ETH\"}");
 						assert_eq!(assert_ok!(serde_json::to_string(&Asset::Dot)), "{\"chain\":\"Polkadot\",\"asset\":\"DOT\"}");
 						assert_eq!(assert_ok!(serde_json::to_string(&Asset::Btc)), "{\"chain\":\"Bitcoin\",\"asset\":\"BTC\"}");
+						assert_eq!(assert_ok!(serde_json::to_string(&Asset::HubUsdt)), "{\"chain\":\"Assethub\",\"asset\":\"HUBUSDT\"}");
 
 						assert_eq!(assert_ok!(serde_json::from_str::<Asset>("{\"chain\":\"Ethereum\",\"asset\":\"ETH\"}")), Asset::Eth);
 						assert_eq!(assert_ok!(serde_json::from_str::<Asset>("{\"chain\":\"Polkadot\",\"asset\":\"DOT\"}")), Asset::Dot);
 						assert_eq!(assert_ok!(serde_json::from_str::<Asset>("{\"chain\":\"Bitcoin\",\"asset\":\"BTC\"}")), Asset::Btc);
+						assert_eq!(assert_ok!(serde_json::from_str::<Asset>("{\"chain\":\"Assethub\",\"asset\":\"HUBUSDT\"}")), Asset::HubUsdt);
 
 						assert_eq!(assert_ok!(serde_json::from_str::<Asset>("{\"asset\":\"ETH\"}")), Asset::Eth);
 						assert_eq!(assert_ok!(serde_json::from_str::<Asset>("{\"asset\":\"DOT\"}")), Asset::Dot);
@@ -388,6 +390,11 @@ assets!(pub enum Asset {
 	(btc, Bitcoin, "Bitcoin") => {
 		(Btc, btc) = 5u32 (GAS_ASSET),
 	},
+	(hub, Assethub, "Assethub") => {
+		(HubDot, hubdot) = 6u32 (GAS_ASSET),
+		(HubUsdt, hubusdt) = 7u32,
+		(HubUsdc, hubusdc) = 8u32,
+	},
 });
 
 #[cfg(test)]
@@ -415,6 +422,10 @@ mod test_assets {
 		assert_eq!(any::Asset::try_from(3).unwrap(), any::Asset::Usdc);
 		assert_eq!(any::Asset::try_from(4).unwrap(), any::Asset::Dot);
 		assert_eq!(any::Asset::try_from(5).unwrap(), any::Asset::Btc);
+		assert_eq!(any::Asset::try_from(6).unwrap(), any::Asset::HubDot);
+		assert_eq!(any::Asset::try_from(7).unwrap(), any::Asset::HubUsdt);
+		assert_eq!(any::Asset::try_from(8).unwrap(), any::Asset::HubUsdc);
+		assert!(any::Asset::try_from(9).is_err());
 	}
 
 	#[test]
@@ -424,11 +435,17 @@ mod test_assets {
 		assert_conversion!(eth, Usdc);
 		assert_conversion!(dot, Dot);
 		assert_conversion!(btc, Btc);
+		assert_conversion!(hub, HubDot);
+		assert_conversion!(hub, HubUsdt);
+		assert_conversion!(hub, HubUsdc);
 
 		assert_incompatible!(eth, Dot);
 		assert_incompatible!(dot, Eth);
 		assert_incompatible!(dot, Flip);
 		assert_incompatible!(dot, Usdc);
 		assert_incompatible!(btc, Usdc);
+		assert_incompatible!(hub, Dot);
+		assert_incompatible!(hub, Usdc);
+		assert_incompatible!(dot, HubDot);
 	}
 }
@@ -187,6 +187,24 @@ pub enum AccountRole {
 
 pub type EgressBatch<Amount, EgressAddress> = Vec<(Amount, EgressAddress)>;
 
+/// The maximum number of affiliates that can share in the commission of a deposit channel.
+pub const MAX_AFFILIATES: u32 = 5;
+
+/// An account that receives a share of the commission on deposits into a broker's channel, in
+/// addition to the broker's own commission.
+#[derive(
+	Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Serialize, Deserialize,
+)]
+pub struct Affiliate<AccountId> {
+	pub account: AccountId,
+	pub bps: BasisPoints,
+}
+
+pub type Affiliates<AccountId> = frame_support::BoundedVec<
+	Affiliate<AccountId>,
+	frame_support::traits::ConstU32<MAX_AFFILIATES>,
+>;
+
 /// Struct that represents the estimated output of a Swap.
 #[derive(
 	PartialEq, Default, Eq, Copy, Clone, Debug, Encode, Decode, TypeInfo, Serialize, Deserialize,
@@ -9,11 +9,12 @@ mod missed_authorship_slots;
 mod offences;
 mod signer_nomination;
 use crate::{
-	AccountId, AccountRoles, Authorship, BitcoinChainTracking, BitcoinIngressEgress,
-	BitcoinThresholdSigner, BlockNumber, Emissions, Environment, EthereumBroadcaster,
-	EthereumChainTracking, EthereumIngressEgress, Flip, FlipBalance, Hash, PolkadotBroadcaster,
-	PolkadotChainTracking, PolkadotIngressEgress, PolkadotThresholdSigner, Runtime, RuntimeCall,
-	System, Validator, YEAR,
+	AccountId, AccountRoles, AssethubChainTracking, AssethubIngressEgress, Authorship,
+	BitcoinChainTracking, BitcoinIngressEgress, BitcoinThresholdSigner, BlockNumber, Emissions,
+	Environment, EthereumBroadcaster, EthereumChainTracking, EthereumIngressEgress, Flip,
+	FlipBalance, Governance, Hash, PolkadotBroadcaster, PolkadotChainTracking,
+	PolkadotIngressEgress, PolkadotThresholdSigner, Reputation, Runtime, RuntimeCall, System,
+	Validator, YEAR,
 };
 use backup_node_rewards::calculate_backup_rewards;
 use cf_chains::{
@@ -24,7 +25,9 @@ use cf_chains::{
 	assets::any::ForeignChainAndAsset,
 	btc::{
 		api::{BitcoinApi, SelectedUtxosAndChangeAmount, UtxoSelectionType},
-		Bitcoin, BitcoinCrypto, BitcoinFeeInfo, BitcoinTransactionData, UtxoId,
+		deposit_address::DepositAddress,
+		AggKey, Bitcoin, BitcoinCrypto, BitcoinFeeInfo, BitcoinTransactionData, UtxoId,
+		CHANGE_ADDRESS_SALT,
 	},
 	dot::{
 		api::PolkadotApi, Polkadot, PolkadotAccountId, PolkadotCrypto, PolkadotReplayProtection,
@@ -40,16 +43,18 @@ use cf_chains::{
 		api::{EthEnvironmentProvider, EvmReplayProtection},
 		EvmCrypto, Transaction,
 	},
+	hub::{api::AssethubApi, Assethub, AssethubAccountId},
 	AnyChain, ApiCall, CcmChannelMetadata, CcmDepositMetadata, Chain, ChainCrypto,
 	ChainEnvironment, ChainState, DepositChannel, ForeignChain, ReplayProtectionProvider,
 	SetCommKeyWithAggKey, SetGovKeyWithAggKey, TransactionBuilder,
 };
-use cf_primitives::{chains::assets, AccountRole, Asset, BasisPoints, ChannelId};
+use cf_primitives::{chains::assets, AccountRole, Affiliates, Asset, BasisPoints, ChannelId};
 use cf_traits::{
 	AccountInfo, AccountRoleRegistry, BackupRewardsNotifier, BlockEmissions,
 	BroadcastAnyChainGovKey, Broadcaster, Chainflip, CommKeyBroadcaster, DepositApi,
 	DepositHandler, EgressApi, EpochInfo, Heartbeat, Issuance, KeyProvider, OnBroadcastReady,
 	QualifyNode, RewardsDistribution, RuntimeUpgrade, ScheduledEgressDetails,
+	ValidatorPerformanceRecorder,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -63,6 +68,8 @@ use frame_support::{
 };
 pub use missed_authorship_slots::MissedAuraSlots;
 pub use offences::*;
+use pallet_cf_governance::CallCategoriser;
+use pallet_cf_tokenholder_governance::{RuntimeParameterDispatcher, RuntimeParameterUpdate};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 pub use signer_nomination::RandomSignerNomination;
@@ -108,6 +115,7 @@ impl RewardsDistribution for BackupNodeEmissions {
 			Self::Balance::unique_saturated_from(Validator::current_authority_count()),
 		) {
 			Flip::settle(&validator_id, Self::Issuance::mint(reward).into());
+			Reputation::record_reward(&validator_id, reward);
 			<Emissions as BackupRewardsNotifier>::emit_event(&validator_id, reward);
 		}
 	}
@@ -227,6 +235,27 @@ impl TransactionBuilder<Polkadot, PolkadotApi<DotEnvironment>> for DotTransactio
 	}
 }
 
+pub struct HubTransactionBuilder;
+impl TransactionBuilder<Assethub, AssethubApi<HubEnvironment>> for HubTransactionBuilder {
+	fn build_transaction(
+		signed_call: &AssethubApi<HubEnvironment>,
+	) -> <Assethub as Chain>::Transaction {
+		PolkadotTransactionData { encoded_extrinsic: signed_call.chain_encoded() }
+	}
+
+	fn refresh_unsigned_data(_unsigned_tx: &mut <Assethub as Chain>::Transaction) {
+		// As for Polkadot, there is nothing to refresh.
+	}
+
+	fn requires_signature_refresh(
+		call: &AssethubApi<HubEnvironment>,
+		payload: &<<Assethub as Chain>::ChainCrypto as ChainCrypto>::Payload,
+	) -> bool {
+		// As for Polkadot, the payload only changes on an Asset Hub runtime version update.
+		&call.threshold_signature_payload() != payload
+	}
+}
+
 pub struct BtcTransactionBuilder;
 impl TransactionBuilder<Bitcoin, BitcoinApi<BtcEnvironment>> for BtcTransactionBuilder {
 	fn build_transaction(
@@ -266,6 +295,7 @@ impl RewardsDistribution for BlockAuthorRewardDistribution {
 		if reward_amount != 0 {
 			if let Some(current_block_author) = Authorship::author() {
 				Flip::settle(&current_block_author, Self::Issuance::mint(reward_amount).into());
+				Reputation::record_reward(&current_block_author, reward_amount);
 			} else {
 				log::warn!("No block author for block {}.", System::current_block_number());
 			}
@@ -349,6 +379,35 @@ impl ChainEnvironment<cf_chains::dot::api::VaultAccount, PolkadotAccountId> for
 	}
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct HubEnvironment;
+
+impl ReplayProtectionProvider<Assethub> for HubEnvironment {
+	// Asset Hub transactions are signed by the Polkadot AggKey but have their own genesis hash and
+	// proxy account nonce.
+	fn replay_protection(reset_nonce: ResetProxyAccountNonce) -> PolkadotReplayProtection {
+		PolkadotReplayProtection {
+			genesis_hash: Environment::assethub_genesis_hash(),
+			signer: <PolkadotThresholdSigner as KeyProvider<PolkadotCrypto>>::active_epoch_key()
+				.map(|epoch_key| epoch_key.key)
+				.defensive_unwrap_or_default(),
+			nonce: Environment::next_assethub_proxy_account_nonce(reset_nonce),
+		}
+	}
+}
+
+impl Get<RuntimeVersion> for HubEnvironment {
+	fn get() -> RuntimeVersion {
+		AssethubChainTracking::chain_state().unwrap().tracked_data.runtime_version
+	}
+}
+
+impl ChainEnvironment<cf_chains::hub::api::VaultAccount, AssethubAccountId> for HubEnvironment {
+	fn lookup(_: cf_chains::hub::api::VaultAccount) -> Option<AssethubAccountId> {
+		Environment::assethub_vault_account()
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 pub struct BtcEnvironment;
 
@@ -407,7 +466,7 @@ impl BroadcastAnyChainGovKey for TokenholderGovernanceBroadcaster {
 				Self::broadcast_gov_key::<Ethereum, EthereumBroadcaster>(maybe_old_key, new_key),
 			ForeignChain::Polkadot =>
 				Self::broadcast_gov_key::<Polkadot, PolkadotBroadcaster>(maybe_old_key, new_key),
-			ForeignChain::Bitcoin => Err(()),
+			ForeignChain::Bitcoin | ForeignChain::Assethub => Err(()),
 		}
 	}
 
@@ -417,7 +476,7 @@ impl BroadcastAnyChainGovKey for TokenholderGovernanceBroadcaster {
 				Self::is_govkey_compatible::<<Ethereum as Chain>::ChainCrypto>(key),
 			ForeignChain::Polkadot =>
 				Self::is_govkey_compatible::<<Polkadot as Chain>::ChainCrypto>(key),
-			ForeignChain::Bitcoin => false,
+			ForeignChain::Bitcoin | ForeignChain::Assethub => false,
 		}
 	}
 }
@@ -430,6 +489,113 @@ impl CommKeyBroadcaster for TokenholderGovernanceBroadcaster {
 	}
 }
 
+pub struct TokenholderGovernanceParameterDispatcher;
+
+impl RuntimeParameterDispatcher for TokenholderGovernanceParameterDispatcher {
+	fn dispatch_update(update: RuntimeParameterUpdate) -> Result<(), DispatchError> {
+		Governance::dispatch_governance_call(match update {
+			RuntimeParameterUpdate::MinimumFunding(minimum_funding) =>
+				RuntimeCall::Funding(pallet_cf_funding::Call::update_minimum_funding {
+					minimum_funding,
+				}),
+			RuntimeParameterUpdate::CurrentAuthorityEmissionInflation(inflation) =>
+				RuntimeCall::Emissions(
+					pallet_cf_emissions::Call::update_current_authority_emission_inflation {
+						inflation,
+					},
+				),
+			RuntimeParameterUpdate::BackupNodeEmissionInflation(inflation) =>
+				RuntimeCall::Emissions(
+					pallet_cf_emissions::Call::update_backup_node_emission_inflation { inflation },
+				),
+			RuntimeParameterUpdate::PoolFees { base_asset, quote_asset, fee_hundredth_pips } =>
+				RuntimeCall::LiquidityPools(pallet_cf_pools::Call::set_pool_fees {
+					base_asset,
+					quote_asset,
+					fee_hundredth_pips,
+				}),
+		})
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+}
+
+/// Categories of governance calls that can be subjected to an execution timelock.
+#[derive(
+	Encode,
+	Decode,
+	TypeInfo,
+	Clone,
+	Copy,
+	Debug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Serialize,
+	Deserialize,
+)]
+pub enum GovernanceCallCategory {
+	RuntimeUpgrade,
+	Governance,
+	Funding,
+	Validator,
+	Liquidity,
+	Chains,
+	Other,
+}
+
+pub struct GovernanceCallCategoriser;
+
+impl CallCategoriser<RuntimeCall> for GovernanceCallCategoriser {
+	type Category = GovernanceCallCategory;
+
+	fn categorise(call: &RuntimeCall) -> Self::Category {
+		match call {
+			// Calls executed as sudo are categorised by the wrapped call.
+			RuntimeCall::Governance(pallet_cf_governance::Call::call_as_sudo { call }) =>
+				Self::categorise(call),
+			RuntimeCall::Governance(pallet_cf_governance::Call::chainflip_runtime_upgrade {
+				..
+			}) |
+			RuntimeCall::System(
+				frame_system::Call::set_code { .. } |
+				frame_system::Call::set_code_without_checks { .. },
+			) => GovernanceCallCategory::RuntimeUpgrade,
+			RuntimeCall::Governance(_) | RuntimeCall::TokenholderGovernance(_) =>
+				GovernanceCallCategory::Governance,
+			RuntimeCall::Flip(_) | RuntimeCall::Emissions(_) | RuntimeCall::Funding(_) =>
+				GovernanceCallCategory::Funding,
+			RuntimeCall::Validator(_) | RuntimeCall::Reputation(_) =>
+				GovernanceCallCategory::Validator,
+			RuntimeCall::LiquidityPools(_) |
+			RuntimeCall::LiquidityProvider(_) |
+			RuntimeCall::Swapping(_) => GovernanceCallCategory::Liquidity,
+			RuntimeCall::Environment(_) |
+			RuntimeCall::EthereumChainTracking(_) |
+			RuntimeCall::PolkadotChainTracking(_) |
+			RuntimeCall::BitcoinChainTracking(_) |
+			RuntimeCall::AssethubChainTracking(_) |
+			RuntimeCall::EthereumVault(_) |
+			RuntimeCall::PolkadotVault(_) |
+			RuntimeCall::BitcoinVault(_) |
+			RuntimeCall::AssethubVault(_) |
+			RuntimeCall::EthereumThresholdSigner(_) |
+			RuntimeCall::PolkadotThresholdSigner(_) |
+			RuntimeCall::BitcoinThresholdSigner(_) |
+			RuntimeCall::EthereumBroadcaster(_) |
+			RuntimeCall::PolkadotBroadcaster(_) |
+			RuntimeCall::BitcoinBroadcaster(_) |
+			RuntimeCall::AssethubBroadcaster(_) |
+			RuntimeCall::EthereumIngressEgress(_) |
+			RuntimeCall::PolkadotIngressEgress(_) |
+			RuntimeCall::BitcoinIngressEgress(_) |
+			RuntimeCall::AssethubIngressEgress(_) => GovernanceCallCategory::Chains,
+			_ => GovernanceCallCategory::Other,
+		}
+	}
+}
+
 #[macro_export]
 macro_rules! impl_deposit_api_for_anychain {
 	( $t: ident, $(($chain: ident, $pallet: ident)),+ ) => {
@@ -458,6 +624,7 @@ macro_rules! impl_deposit_api_for_anychain {
 				destination_asset: Asset,
 				destination_address: ForeignChainAddress,
 				broker_commission_bps: BasisPoints,
+				affiliates: Affiliates<Self::AccountId>,
 				broker_id: Self::AccountId,
 				channel_metadata: Option<CcmChannelMetadata>,
 				boost_fee: BasisPoints
@@ -469,6 +636,7 @@ macro_rules! impl_deposit_api_for_anychain {
 							destination_asset,
 							destination_address,
 							broker_commission_bps,
+							affiliates,
 							broker_id,
 							channel_metadata,
 							boost_fee
@@ -516,14 +684,16 @@ impl_deposit_api_for_anychain!(
 	AnyChainIngressEgressHandler,
 	(Ethereum, EthereumIngressEgress),
 	(Polkadot, PolkadotIngressEgress),
-	(Bitcoin, BitcoinIngressEgress)
+	(Bitcoin, BitcoinIngressEgress),
+	(Assethub, AssethubIngressEgress)
 );
 
 impl_egress_api_for_anychain!(
 	AnyChainIngressEgressHandler,
 	(Ethereum, EthereumIngressEgress),
 	(Polkadot, PolkadotIngressEgress),
-	(Bitcoin, BitcoinIngressEgress)
+	(Bitcoin, BitcoinIngressEgress),
+	(Assethub, AssethubIngressEgress)
 );
 
 pub struct EthDepositHandler;
@@ -532,6 +702,9 @@ impl DepositHandler<Ethereum> for EthDepositHandler {}
 pub struct DotDepositHandler;
 impl DepositHandler<Polkadot> for DotDepositHandler {}
 
+pub struct HubDepositHandler;
+impl DepositHandler<Assethub> for HubDepositHandler {}
+
 pub struct BtcDepositHandler;
 impl DepositHandler<Bitcoin> for BtcDepositHandler {
 	fn on_deposit_made(
@@ -541,6 +714,25 @@ impl DepositHandler<Bitcoin> for BtcDepositHandler {
 	) {
 		Environment::add_bitcoin_utxo_to_list(amount, utxo_id, channel.state)
 	}
+
+	fn on_vault_deposit_made(
+		vault_address: <Bitcoin as Chain>::ChainAccount,
+		utxo_id: <Bitcoin as Chain>::DepositDetails,
+		amount: <Bitcoin as Chain>::ChainAmount,
+	) -> Result<(), ()> {
+		// Payments into the vault of the previous epoch can still be spent during the handover.
+		let AggKey { previous, current } =
+			<BtcEnvironment as ChainEnvironment<(), AggKey>>::lookup(()).ok_or(())?;
+		let vault_key = [Some(current), previous]
+			.into_iter()
+			.flatten()
+			.find(|key| {
+				DepositAddress::new(*key, CHANGE_ADDRESS_SALT).script_pubkey() == vault_address
+			})
+			.ok_or(())?;
+		Environment::add_bitcoin_change_utxo(amount, utxo_id, vault_key);
+		Ok(())
+	}
 }
 
 pub struct ChainAddressConverter;
@@ -564,6 +756,9 @@ impl OnBroadcastReady<Ethereum> for BroadcastReadyProvider {
 impl OnBroadcastReady<Polkadot> for BroadcastReadyProvider {
 	type ApiCall = PolkadotApi<DotEnvironment>;
 }
+impl OnBroadcastReady<Assethub> for BroadcastReadyProvider {
+	type ApiCall = AssethubApi<HubEnvironment>;
+}
 impl OnBroadcastReady<Bitcoin> for BroadcastReadyProvider {
 	type ApiCall = BitcoinApi<BtcEnvironment>;
 
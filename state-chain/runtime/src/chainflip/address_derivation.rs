@@ -1,4 +1,5 @@
 pub mod btc;
 pub mod dot;
 pub mod eth;
+pub mod hub;
 pub struct AddressDerivation;
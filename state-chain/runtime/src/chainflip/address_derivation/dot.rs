@@ -18,37 +18,13 @@ impl AddressDerivationApi<Polkadot> for AddressDerivation {
 		_source_asset: <Polkadot as Chain>::ChainAsset,
 		channel_id: ChannelId,
 	) -> Result<<Polkadot as Chain>::ChainAccount, AddressDerivationError> {
-		const PREFIX: &[u8; 16] = b"modlpy/utilisuba";
-		const RAW_PUBLIC_KEY_SIZE: usize = 32;
-		const PAYLOAD_LENGTH: usize = PREFIX.len() + RAW_PUBLIC_KEY_SIZE + size_of::<u16>();
-
 		let master_account = Environment::polkadot_vault_account()
 			.ok_or(AddressDerivationError::MissingPolkadotVault)?;
 
-		let mut layers = channel_id
-			.to_be_bytes()
-			.chunks(2)
-			.map(|chunk| u16::from_be_bytes(chunk.as_array::<2>()))
-			.skip_while(|layer| *layer == 0u16)
-			.collect::<Vec<u16>>();
-
-		layers.reverse();
-
-		let payload_hash =
-			layers.into_iter().fold(*master_account.aliased_ref(), |sub_account, salt| {
-				let mut payload = Vec::with_capacity(PAYLOAD_LENGTH);
-				// Fill the first slots with the derivation prefix.
-				payload.extend(PREFIX);
-				// Then add the 32-byte public key.
-				payload.extend(sub_account);
-				// Finally, add the index to the end of the payload.
-				payload.extend(&salt.to_le_bytes());
-
-				// Hash the whole thing
-				BlakeTwo256::hash(&payload).to_fixed_bytes()
-			});
-
-		Ok(PolkadotAccountId::from_aliased(payload_hash))
+		Ok(PolkadotAccountId::from_aliased(derive_sub_account(
+			*master_account.aliased_ref(),
+			channel_id,
+		)))
 	}
 
 	fn generate_address_and_state(
@@ -65,6 +41,37 @@ impl AddressDerivationApi<Polkadot> for AddressDerivation {
 	}
 }
 
+/// Derives the account that `utility.as_derivative` dispatches from, for the given channel id.
+///
+/// The channel id is split into 16-bit indices, one per derivation layer, skipping leading zeroes.
+pub(super) fn derive_sub_account(master_account: [u8; 32], channel_id: ChannelId) -> [u8; 32] {
+	const PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+	const RAW_PUBLIC_KEY_SIZE: usize = 32;
+	const PAYLOAD_LENGTH: usize = PREFIX.len() + RAW_PUBLIC_KEY_SIZE + size_of::<u16>();
+
+	let mut layers = channel_id
+		.to_be_bytes()
+		.chunks(2)
+		.map(|chunk| u16::from_be_bytes(chunk.as_array::<2>()))
+		.skip_while(|layer| *layer == 0u16)
+		.collect::<Vec<u16>>();
+
+	layers.reverse();
+
+	layers.into_iter().fold(master_account, |sub_account, salt| {
+		let mut payload = Vec::with_capacity(PAYLOAD_LENGTH);
+		// Fill the first slots with the derivation prefix.
+		payload.extend(PREFIX);
+		// Then add the 32-byte public key.
+		payload.extend(sub_account);
+		// Finally, add the index to the end of the payload.
+		payload.extend(&salt.to_le_bytes());
+
+		// Hash the whole thing
+		BlakeTwo256::hash(&payload).to_fixed_bytes()
+	})
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -0,0 +1,85 @@
+use super::{dot::derive_sub_account, AddressDerivation};
+use crate::Environment;
+use cf_chains::{
+	address::{AddressDerivationApi, AddressDerivationError},
+	hub::{Assethub, AssethubAccountId},
+	Chain,
+};
+use cf_primitives::ChannelId;
+
+impl AddressDerivationApi<Assethub> for AddressDerivation {
+	fn generate_address(
+		_source_asset: <Assethub as Chain>::ChainAsset,
+		channel_id: ChannelId,
+	) -> Result<<Assethub as Chain>::ChainAccount, AddressDerivationError> {
+		let master_account = Environment::assethub_vault_account()
+			.ok_or(AddressDerivationError::MissingAssethubVault)?;
+
+		Ok(AssethubAccountId::from_aliased(derive_sub_account(
+			*master_account.aliased_ref(),
+			channel_id,
+		)))
+	}
+
+	fn generate_address_and_state(
+		source_asset: <Assethub as Chain>::ChainAsset,
+		channel_id: ChannelId,
+	) -> Result<
+		(<Assethub as Chain>::ChainAccount, <Assethub as Chain>::DepositChannelState),
+		AddressDerivationError,
+	> {
+		Ok((
+			<Self as AddressDerivationApi<Assethub>>::generate_address(source_asset, channel_id)?,
+			Default::default(),
+		))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Runtime;
+	use cf_chains::Polkadot;
+	use cf_primitives::chains::assets::{dot, hub};
+	use pallet_cf_environment::{AssethubVaultAccountId, PolkadotVaultAccountId};
+
+	#[test]
+	fn derivation_matches_polkadot_for_the_same_vault() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			PolkadotVaultAccountId::<Runtime>::put(
+				cf_chains::dot::PolkadotAccountId::from_aliased([7u8; 32]),
+			);
+			AssethubVaultAccountId::<Runtime>::put(AssethubAccountId::from_aliased([7u8; 32]));
+
+			for channel_id in [1, 6259, 0x0004_0003_0002_0001] {
+				assert_eq!(
+					<AddressDerivation as AddressDerivationApi<Assethub>>::generate_address(
+						hub::Asset::HubUsdt,
+						channel_id
+					)
+					.unwrap()
+					.aliased_ref(),
+					<AddressDerivation as AddressDerivationApi<Polkadot>>::generate_address(
+						dot::Asset::Dot,
+						channel_id
+					)
+					.unwrap()
+					.aliased_ref(),
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn missing_vault() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			assert_eq!(
+				<AddressDerivation as AddressDerivationApi<Assethub>>::generate_address(
+					hub::Asset::HubDot,
+					1
+				),
+				Err(AddressDerivationError::MissingAssethubVault),
+			);
+		});
+	}
+}
@@ -27,3 +27,31 @@ impl PalletInstanceAlias for cf_chains::btc::Bitcoin {
 }
 
 pub type BitcoinInstance = <cf_chains::btc::Bitcoin as PalletInstanceAlias>::Instance;
+
+impl PalletInstanceAlias for cf_chains::hub::Assethub {
+	type Instance = Instance4;
+}
+
+pub type AssethubInstance = <cf_chains::hub::Assethub as PalletInstanceAlias>::Instance;
+
+/// The threshold signer instance that signs for a chain. Chains that share a crypto (Polkadot and
+/// Asset Hub) share a threshold signer, so this is not always the chain's own instance.
+pub trait ThresholdSignerInstanceAlias {
+	type Instance: Send + Sync + 'static;
+}
+
+impl ThresholdSignerInstanceAlias for cf_chains::eth::Ethereum {
+	type Instance = EthereumInstance;
+}
+
+impl ThresholdSignerInstanceAlias for cf_chains::dot::Polkadot {
+	type Instance = PolkadotInstance;
+}
+
+impl ThresholdSignerInstanceAlias for cf_chains::btc::Bitcoin {
+	type Instance = BitcoinInstance;
+}
+
+impl ThresholdSignerInstanceAlias for cf_chains::hub::Assethub {
+	type Instance = PolkadotInstance;
+}
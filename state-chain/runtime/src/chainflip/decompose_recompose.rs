@@ -1,4 +1,6 @@
-use crate::{BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall};
+use crate::{
+	AssethubInstance, BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall,
+};
 use cf_chains::btc::BitcoinFeeInfo;
 use codec::{Decode, Encode};
 use pallet_cf_witnesser::WitnessDataExtraction;
@@ -34,6 +36,15 @@ impl WitnessDataExtraction for RuntimeCall {
 				let fee_info = mem::take(&mut new_chain_state.tracked_data.median_tip);
 				Some(fee_info.encode())
 			},
+			RuntimeCall::AssethubChainTracking(pallet_cf_chain_tracking::Call::<
+				Runtime,
+				AssethubInstance,
+			>::update_chain_state {
+				ref mut new_chain_state,
+			}) => {
+				let fee_info = mem::take(&mut new_chain_state.tracked_data.median_tip);
+				Some(fee_info.encode())
+			},
 			_ => None,
 		}
 	}
@@ -68,6 +79,16 @@ impl WitnessDataExtraction for RuntimeCall {
 					new_chain_state.tracked_data.median_tip = median;
 				};
 			},
+			RuntimeCall::AssethubChainTracking(pallet_cf_chain_tracking::Call::<
+				Runtime,
+				AssethubInstance,
+			>::update_chain_state {
+				new_chain_state,
+			}) => {
+				if let Some(median) = decode_and_select(data, select_median) {
+					new_chain_state.tracked_data.median_tip = median;
+				};
+			},
 			_ => {
 				log::warn!("No witness data injection for call {:?}", self);
 			},
@@ -125,6 +146,7 @@ mod tests {
 		btc::{BitcoinFeeInfo, BitcoinTrackedData},
 		dot::PolkadotTrackedData,
 		eth::EthereumTrackedData,
+		hub::Assethub,
 		Bitcoin, Chain, ChainState, Ethereum, Polkadot,
 	};
 	use cf_primitives::{AccountRole, ForeignChain};
@@ -177,6 +199,19 @@ mod tests {
 						},
 					},
 				}),
+			ForeignChain::Assethub =>
+				RuntimeCall::AssethubChainTracking(pallet_cf_chain_tracking::Call::<
+					Runtime,
+					AssethubInstance,
+				>::update_chain_state {
+					new_chain_state: ChainState {
+						block_height: BLOCK_HEIGHT as u32,
+						tracked_data: PolkadotTrackedData {
+							median_tip: fee.into(),
+							runtime_version: Default::default(),
+						},
+					},
+				}),
 		}
 	}
 
@@ -185,6 +220,7 @@ mod tests {
 		test_medians::<Ethereum>();
 		test_medians::<Bitcoin>();
 		test_medians::<Polkadot>();
+		test_medians::<Assethub>();
 	}
 
 	#[track_caller]
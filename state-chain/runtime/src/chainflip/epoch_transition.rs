@@ -1,12 +1,18 @@
 use cf_primitives::EpochIndex;
 use cf_traits::EpochTransitionHandler;
 
-use crate::Witnesser;
+use crate::{Funding, Reputation, Witnesser};
 
 pub struct ChainflipEpochTransitions;
 
 impl EpochTransitionHandler for ChainflipEpochTransitions {
+	fn on_new_epoch(previous_epoch: EpochIndex) {
+		<Reputation as EpochTransitionHandler>::on_new_epoch(previous_epoch);
+	}
+
 	fn on_expired_epoch(expired: EpochIndex) {
 		<Witnesser as EpochTransitionHandler>::on_expired_epoch(expired);
+		<Reputation as EpochTransitionHandler>::on_expired_epoch(expired);
+		<Funding as EpochTransitionHandler>::on_expired_epoch(expired);
 	}
 }
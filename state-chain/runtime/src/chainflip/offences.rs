@@ -47,6 +47,18 @@ impl OffenceList<Runtime> for KeygenExclusionOffences {
 		&[Offence::MissedAuthorshipSlot, Offence::GrandpaEquivocation];
 }
 
+/// Offences reported by the threshold signature ceremonies, for which the accused node can
+/// submit an appeal.
+pub struct AppealableOffences;
+
+impl OffenceList<Runtime> for AppealableOffences {
+	const OFFENCES: &'static [Offence] = &[
+		Offence::ParticipateSigningFailed,
+		Offence::ParticipateKeygenFailed,
+		Offence::ParticipateKeyHandoverFailed,
+	];
+}
+
 // Boilerplate
 impl From<pallet_cf_broadcast::PalletOffence> for Offence {
 	fn from(offences: pallet_cf_broadcast::PalletOffence) -> Self {
@@ -12,8 +12,10 @@ mod weights;
 use crate::{
 	chainflip::{calculate_account_apy, Offence},
 	runtime_apis::{
-		AuctionState, DispatchErrorWithMessage, FailingWitnessValidators, LiquidityProviderInfo,
-		RuntimeApiAccountInfoV2, RuntimeApiPenalty,
+		AuctionState, BrokerChannel, BrokerEarnings, DispatchErrorWithMessage,
+		FailingWitnessValidators, LiquidityProviderInfo, RuntimeApiAccountInfoV2,
+		RuntimeApiPenalty, TimelockedGovernanceCall, TokenholderProposalInfo, TokenholderVoteInfo,
+		ValidatorEpochPerformance,
 	},
 };
 use cf_amm::{
@@ -26,18 +28,19 @@ use cf_chains::{
 	dot::{self, PolkadotCrypto},
 	eth::{self, api::EthereumApi, Address as EthereumAddress, Ethereum},
 	evm::EvmCrypto,
+	hub::{self, Assethub},
 	Bitcoin, CcmChannelMetadata, DefaultRetryPolicy, FeeEstimationApi, ForeignChain, Polkadot,
 	TransactionBuilder,
 };
 use cf_primitives::{BroadcastId, NetworkEnvironment};
-use cf_traits::{AssetConverter, GetTrackedData, LpBalanceApi};
+use cf_traits::{AssetConverter, GetBlockHeight, GetTrackedData, LpBalanceApi};
 use core::ops::Range;
 pub use frame_system::Call as SystemCall;
 use pallet_cf_governance::GovCallHash;
 use pallet_cf_ingress_egress::{ChannelAction, DepositWitness};
 use pallet_cf_pools::{
-	AskBidMap, AssetPair, AssetsMap, PoolLiquidity, PoolOrderbook, PoolPriceV1, PoolPriceV2,
-	UnidirectionalPoolDepth,
+	AskBidMap, AssetPair, AssetsMap, LpPositionStats, PoolLiquidity, PoolOrderbook, PoolPriceV1,
+	PoolPriceV2, PoolStats, PoolTwap, UnidirectionalPoolDepth,
 };
 use pallet_cf_reputation::ExclusionList;
 use pallet_cf_swapping::CcmSwapAmounts;
@@ -48,7 +51,7 @@ use sp_std::collections::btree_map::BTreeMap;
 
 pub use frame_support::{
 	construct_runtime, debug,
-	instances::{Instance1, Instance2, Instance3},
+	instances::{Instance1, Instance2, Instance3, Instance4},
 	parameter_types,
 	traits::{
 		ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, Get, KeyOwnerProofSystem,
@@ -105,7 +108,7 @@ pub use chainflip::chain_instances::*;
 use chainflip::{
 	all_keys_rotator::AllKeyRotator, epoch_transition::ChainflipEpochTransitions,
 	BroadcastReadyProvider, BtcEnvironment, ChainAddressConverter, ChainflipHeartbeat,
-	DotEnvironment, EthEnvironment, TokenholderGovernanceBroadcaster,
+	DotEnvironment, EthEnvironment, HubEnvironment, TokenholderGovernanceBroadcaster,
 };
 use safe_mode::{RuntimeSafeMode, WitnesserCallPermission};
 
@@ -220,6 +223,7 @@ impl pallet_cf_environment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PolkadotVaultKeyWitnessedHandler = PolkadotVault;
 	type BitcoinVaultKeyWitnessedHandler = BitcoinVault;
+	type AssethubVaultKeyWitnessedHandler = AssethubVault;
 	type BitcoinFeeInfo = chainflip::BitcoinFeeGetter;
 	type RuntimeSafeMode = RuntimeSafeMode;
 	type CurrentReleaseVersion = CurrentReleaseVersion;
@@ -231,6 +235,7 @@ impl pallet_cf_swapping::Config for Runtime {
 	type DepositHandler = chainflip::AnyChainIngressEgressHandler;
 	type EgressHandler = chainflip::AnyChainIngressEgressHandler;
 	type SwappingApi = LiquidityPools;
+	type LpBalance = LiquidityProvider;
 	type AddressConverter = ChainAddressConverter;
 	type SafeMode = RuntimeSafeMode;
 	type WeightInfo = pallet_cf_swapping::weights::PalletWeight<Runtime>;
@@ -269,6 +274,17 @@ impl pallet_cf_vaults::Config<BitcoinInstance> for Runtime {
 	type CfeMultisigRequest = CfeInterface;
 }
 
+impl pallet_cf_vaults::Config<AssethubInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Chain = Assethub;
+	type SetAggKeyWithAggKey = hub::api::AssethubApi<HubEnvironment>;
+	type Broadcaster = AssethubBroadcaster;
+	type WeightInfo = pallet_cf_vaults::weights::PalletWeight<Runtime>;
+	type ChainTracking = AssethubChainTracking;
+	type SafeMode = RuntimeSafeMode;
+	type CfeMultisigRequest = CfeInterface;
+}
+
 use chainflip::address_derivation::AddressDerivation;
 
 impl pallet_cf_ingress_egress::Config<EthereumInstance> for Runtime {
@@ -328,6 +344,25 @@ impl pallet_cf_ingress_egress::Config<BitcoinInstance> for Runtime {
 	type FeePayment = Flip;
 }
 
+impl pallet_cf_ingress_egress::Config<AssethubInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type TargetChain = Assethub;
+	type AddressDerivation = AddressDerivation;
+	type AddressConverter = ChainAddressConverter;
+	type LpBalance = LiquidityProvider;
+	type SwapDepositHandler = Swapping;
+	type ChainApiCall = hub::api::AssethubApi<chainflip::HubEnvironment>;
+	type Broadcaster = AssethubBroadcaster;
+	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
+	type DepositHandler = chainflip::HubDepositHandler;
+	type ChainTracking = AssethubChainTracking;
+	type CcmHandler = Swapping;
+	type NetworkEnvironment = Environment;
+	type AssetConverter = LiquidityPools;
+	type FeePayment = Flip;
+}
+
 parameter_types! {
 	pub const NetworkFee: Permill = Permill::from_perthousand(1);
 }
@@ -336,6 +371,10 @@ impl pallet_cf_pools::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type LpBalance = LiquidityProvider;
 	type NetworkFee = NetworkFee;
+	type MaxPriceObservations = ConstU32<{ 4 * HOURS }>;
+	type MaxOrdersPerBatch = ConstU32<32>;
+	type MaxExpiredLimitOrdersPerBlock = ConstU32<100>;
+	type MaxAutomatedRangeOrdersPerBlock = ConstU32<50>;
 	type SafeMode = RuntimeSafeMode;
 	type WeightInfo = ();
 }
@@ -516,6 +555,7 @@ impl pallet_cf_flip::Config for Runtime {
 	type OnAccountFunded = pallet_cf_validator::UpdateBackupMapping<Self>;
 	type WeightInfo = pallet_cf_flip::weights::PalletWeight<Runtime>;
 	type WaivedFees = chainflip::WaivedFees;
+	type ValidatorPerformanceRecorder = Reputation;
 }
 
 impl pallet_cf_witnesser::Config for Runtime {
@@ -551,6 +591,7 @@ impl pallet_cf_tokenholder_governance::Config for Runtime {
 	type VotingPeriod = ConstU32<{ 14 * DAYS }>;
 	type AnyChainGovKeyBroadcaster = TokenholderGovernanceBroadcaster;
 	type CommKeyBroadcaster = TokenholderGovernanceBroadcaster;
+	type RuntimeParameterDispatcher = chainflip::TokenholderGovernanceParameterDispatcher;
 	type ProposalFee = ConstU128<{ 1_000 * FLIPPERINOS_PER_FLIP }>;
 	type EnactmentDelay = ConstU32<{ 7 * DAYS }>;
 }
@@ -565,6 +606,7 @@ impl pallet_cf_governance::Config for Runtime {
 	type RuntimeUpgrade = chainflip::RuntimeUpgradeManager;
 	type CompatibleCfeVersions = Environment;
 	type AuthoritiesCfeVersions = Validator;
+	type CallCategoriser = chainflip::GovernanceCallCategoriser;
 }
 
 impl pallet_cf_emissions::Config for Runtime {
@@ -600,6 +642,7 @@ impl pallet_transaction_payment::Config for Runtime {
 parameter_types! {
 	pub const ReputationPointFloorAndCeiling: (i32, i32) = (-2880, 2880);
 	pub const MaximumAccruableReputation: pallet_cf_reputation::ReputationPoints = 15;
+	pub const ValidatorHistoryRetention: cf_primitives::EpochIndex = 26;
 }
 
 impl pallet_cf_cfe_interface::Config for Runtime {
@@ -616,6 +659,9 @@ impl pallet_cf_reputation::Config for Runtime {
 	type WeightInfo = pallet_cf_reputation::weights::PalletWeight<Runtime>;
 	type MaximumAccruableReputation = MaximumAccruableReputation;
 	type SafeMode = RuntimeSafeMode;
+	type ValidatorHistoryRetention = ValidatorHistoryRetention;
+	type AppealableOffences = chainflip::AppealableOffences;
+	type AppealWindow = ConstU32<{ 3 * DAYS }>;
 }
 
 impl pallet_cf_threshold_signature::Config<EthereumInstance> for Runtime {
@@ -641,7 +687,8 @@ impl pallet_cf_threshold_signature::Config<PolkadotInstance> for Runtime {
 	type ThresholdCallable = RuntimeCall;
 	type ThresholdSignerNomination = chainflip::RandomSignerNomination;
 	type TargetChainCrypto = PolkadotCrypto;
-	type VaultActivator = PolkadotVault;
+	// Polkadot and Asset Hub share the same AggKey, so a rotation activates both vaults.
+	type VaultActivator = (PolkadotVault, AssethubVault);
 	type OffenceReporter = Reputation;
 	type CeremonyRetryDelay = ConstU32<1>;
 	type SafeMode = RuntimeSafeMode;
@@ -738,6 +785,31 @@ impl pallet_cf_broadcast::Config<BitcoinInstance> for Runtime {
 	type CfeBroadcastRequest = CfeInterface;
 }
 
+impl pallet_cf_broadcast::Config<AssethubInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeOrigin = RuntimeOrigin;
+	type BroadcastCallable = RuntimeCall;
+	type Offence = chainflip::Offence;
+	type TargetChain = Assethub;
+	type ApiCall = hub::api::AssethubApi<HubEnvironment>;
+	// Asset Hub transactions are signed with the Polkadot AggKey.
+	type ThresholdSigner = PolkadotThresholdSigner;
+	type TransactionBuilder = chainflip::HubTransactionBuilder;
+	type BroadcastSignerNomination = chainflip::RandomSignerNomination;
+	type OffenceReporter = Reputation;
+	type EnsureThresholdSigned =
+		pallet_cf_threshold_signature::EnsureThresholdSigned<Self, PolkadotInstance>;
+	type BroadcastReadyProvider = BroadcastReadyProvider;
+	type BroadcastTimeout = ConstU32<{ 10 * MINUTES }>;
+	type WeightInfo = pallet_cf_broadcast::weights::PalletWeight<Runtime>;
+	type SafeMode = RuntimeSafeMode;
+	type SafeModeBlockMargin = ConstU32<10>;
+	type ChainTracking = AssethubChainTracking;
+	type RetryPolicy = DefaultRetryPolicy;
+	type CfeBroadcastRequest = CfeInterface;
+}
+
 impl pallet_cf_chain_tracking::Config<EthereumInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type TargetChain = Ethereum;
@@ -756,6 +828,12 @@ impl pallet_cf_chain_tracking::Config<BitcoinInstance> for Runtime {
 	type WeightInfo = pallet_cf_chain_tracking::weights::PalletWeight<Runtime>;
 }
 
+impl pallet_cf_chain_tracking::Config<AssethubInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TargetChain = Assethub;
+	type WeightInfo = pallet_cf_chain_tracking::weights::PalletWeight<Runtime>;
+}
+
 construct_runtime!(
 	pub struct Runtime
 	{
@@ -782,10 +860,12 @@ construct_runtime!(
 		EthereumChainTracking: pallet_cf_chain_tracking::<Instance1>,
 		PolkadotChainTracking: pallet_cf_chain_tracking::<Instance2>,
 		BitcoinChainTracking: pallet_cf_chain_tracking::<Instance3>,
+		AssethubChainTracking: pallet_cf_chain_tracking::<Instance4>,
 
 		EthereumVault: pallet_cf_vaults::<Instance1>,
 		PolkadotVault: pallet_cf_vaults::<Instance2>,
 		BitcoinVault: pallet_cf_vaults::<Instance3>,
+		AssethubVault: pallet_cf_vaults::<Instance4>,
 
 		EthereumThresholdSigner: pallet_cf_threshold_signature::<Instance1>,
 		PolkadotThresholdSigner: pallet_cf_threshold_signature::<Instance2>,
@@ -794,6 +874,7 @@ construct_runtime!(
 		EthereumBroadcaster: pallet_cf_broadcast::<Instance1>,
 		PolkadotBroadcaster: pallet_cf_broadcast::<Instance2>,
 		BitcoinBroadcaster: pallet_cf_broadcast::<Instance3>,
+		AssethubBroadcaster: pallet_cf_broadcast::<Instance4>,
 
 		Swapping: pallet_cf_swapping,
 		LiquidityProvider: pallet_cf_lp,
@@ -801,6 +882,7 @@ construct_runtime!(
 		EthereumIngressEgress: pallet_cf_ingress_egress::<Instance1>,
 		PolkadotIngressEgress: pallet_cf_ingress_egress::<Instance2>,
 		BitcoinIngressEgress: pallet_cf_ingress_egress::<Instance3>,
+		AssethubIngressEgress: pallet_cf_ingress_egress::<Instance4>,
 
 		LiquidityPools: pallet_cf_pools,
 
@@ -867,20 +949,24 @@ pub type PalletExecutionOrder = (
 	EthereumChainTracking,
 	PolkadotChainTracking,
 	BitcoinChainTracking,
+	AssethubChainTracking,
 	EthereumVault,
 	PolkadotVault,
 	BitcoinVault,
+	AssethubVault,
 	EthereumThresholdSigner,
 	PolkadotThresholdSigner,
 	BitcoinThresholdSigner,
 	EthereumBroadcaster,
 	PolkadotBroadcaster,
 	BitcoinBroadcaster,
+	AssethubBroadcaster,
 	Swapping,
 	LiquidityProvider,
 	EthereumIngressEgress,
 	PolkadotIngressEgress,
 	BitcoinIngressEgress,
+	AssethubIngressEgress,
 	LiquidityPools,
 );
 
@@ -891,17 +977,22 @@ type PalletMigrations = (
 	// DO NOT REMOVE `VersionUpdate`. THIS IS REQUIRED TO UPDATE THE VERSION FOR THE CFES EVERY
 	// UPGRADE
 	pallet_cf_environment::migrations::VersionUpdate<Runtime>,
+	// Must run before the Assethub instance migrations below.
+	migrations::assethub::AssethubMigration,
+	migrations::VersionedMigration<migrations::assethub::AssethubSafeModeMigration, 130>,
 	pallet_cf_environment::migrations::PalletMigration<Runtime>,
 	pallet_cf_funding::migrations::PalletMigration<Runtime>,
 	// pallet_cf_validator::migrations::PalletMigration<Runtime>,
 	// pallet_cf_governance::migrations::PalletMigration<Runtime>,
-	// pallet_cf_tokenholder_governance::migrations::PalletMigration<Runtime>,
+	pallet_cf_tokenholder_governance::migrations::PalletMigration<Runtime>,
 	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance4>,
 	// TODO: Remove this after version 1.3 release.
 	ThresholdSignatureRefactorMigration,
 	pallet_cf_threshold_signature::migrations::PalletMigration<Runtime, Instance1>,
@@ -910,11 +1001,13 @@ type PalletMigrations = (
 	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_swapping::migrations::PalletMigration<Runtime>,
 	// pallet_cf_lp::migrations::PalletMigration<Runtime>,
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance4>,
 	// pallet_cf_pools::migrations::PalletMigration<Runtime>,
 );
 
@@ -1151,6 +1244,18 @@ impl_runtime_apis! {
 			LiquidityPools::pool_price(base_asset, quote_asset).map_err(Into::into)
 		}
 
+		fn cf_pool_twap(base_asset: Asset, quote_asset: Asset, window: BlockNumber) -> Result<PoolTwap, DispatchErrorWithMessage> {
+			LiquidityPools::pool_twap(base_asset, quote_asset, window).map_err(Into::into)
+		}
+
+		fn cf_pool_stats(base_asset: Asset, quote_asset: Asset) -> Result<PoolStats, DispatchErrorWithMessage> {
+			LiquidityPools::pool_stats(base_asset, quote_asset).map_err(Into::into)
+		}
+
+		fn cf_lp_stats(lp: AccountId) -> Vec<LpPositionStats> {
+			LiquidityPools::lp_stats(&lp)
+		}
+
 		/// Simulates a swap and return the intermediate (if any) and final output.
 		///
 		/// If no swap rate can be calculated, returns None. This can happen if the pools are not
@@ -1222,6 +1327,7 @@ impl_runtime_apis! {
 				ForeignChainAndAsset::Ethereum(asset) => MinimumDeposit::<Runtime, EthereumInstance>::get(asset),
 				ForeignChainAndAsset::Polkadot(asset) => MinimumDeposit::<Runtime, PolkadotInstance>::get(asset),
 				ForeignChainAndAsset::Bitcoin(asset) => MinimumDeposit::<Runtime, BitcoinInstance>::get(asset).into(),
+				ForeignChainAndAsset::Assethub(asset) => MinimumDeposit::<Runtime, AssethubInstance>::get(asset),
 			}
 		}
 
@@ -1232,6 +1338,7 @@ impl_runtime_apis! {
 				ForeignChainAndAsset::Ethereum(asset) => EgressDustLimit::<Runtime, EthereumInstance>::get(asset),
 				ForeignChainAndAsset::Polkadot(asset) => EgressDustLimit::<Runtime, PolkadotInstance>::get(asset),
 				ForeignChainAndAsset::Bitcoin(asset) => EgressDustLimit::<Runtime, BitcoinInstance>::get(asset),
+				ForeignChainAndAsset::Assethub(asset) => EgressDustLimit::<Runtime, AssethubInstance>::get(asset),
 			}
 		}
 
@@ -1249,6 +1356,14 @@ impl_runtime_apis! {
 					.estimate_ingress_fee(asset)),
 				ForeignChainAndAsset::Bitcoin(asset) => Some(pallet_cf_chain_tracking::Pallet::<Runtime, BitcoinInstance>::get_tracked_data()
 					.estimate_ingress_fee(asset).into()),
+				ForeignChainAndAsset::Assethub(asset) => {
+					pallet_cf_pools::Pallet::<Runtime>::estimate_swap_input_for_desired_output(
+						generic_asset,
+						Asset::HubDot,
+						pallet_cf_chain_tracking::Pallet::<Runtime, AssethubInstance>::get_tracked_data()
+							.estimate_ingress_fee(asset)
+					)
+				},
 			}
 		}
 
@@ -1266,6 +1381,14 @@ impl_runtime_apis! {
 					.estimate_egress_fee(asset)),
 				ForeignChainAndAsset::Bitcoin(asset) => Some(pallet_cf_chain_tracking::Pallet::<Runtime, BitcoinInstance>::get_tracked_data()
 					.estimate_egress_fee(asset).into()),
+				ForeignChainAndAsset::Assethub(asset) => {
+					pallet_cf_pools::Pallet::<Runtime>::estimate_swap_input_for_desired_output(
+						generic_asset,
+						Asset::HubDot,
+						pallet_cf_chain_tracking::Pallet::<Runtime, AssethubInstance>::get_tracked_data()
+							.estimate_egress_fee(asset)
+					)
+				},
 			}
 		}
 
@@ -1274,6 +1397,7 @@ impl_runtime_apis! {
 				ForeignChain::Bitcoin => pallet_cf_ingress_egress::Pallet::<Runtime, BitcoinInstance>::witness_safety_margin(),
 				ForeignChain::Ethereum => pallet_cf_ingress_egress::Pallet::<Runtime, EthereumInstance>::witness_safety_margin(),
 				ForeignChain::Polkadot => pallet_cf_ingress_egress::Pallet::<Runtime, PolkadotInstance>::witness_safety_margin().map(Into::into),
+				ForeignChain::Assethub => pallet_cf_ingress_egress::Pallet::<Runtime, AssethubInstance>::witness_safety_margin().map(Into::into),
 			}
 		}
 
@@ -1300,6 +1424,73 @@ impl_runtime_apis! {
 			})
 		}
 
+		fn cf_broker_earnings(account_id: AccountId) -> Option<BrokerEarnings> {
+			if Self::cf_account_role(account_id.clone())? != AccountRole::Broker {
+				return None;
+			}
+
+			Some(BrokerEarnings {
+				withdrawal_addresses: ForeignChain::iter().map(|chain| {
+					(chain, pallet_cf_swapping::BrokerWithdrawalAddress::<Runtime>::get(&account_id, chain))
+				}).collect(),
+				balances: Asset::all().map(|asset|
+					(asset, pallet_cf_swapping::EarnedBrokerFees::<Runtime>::get(&account_id, asset))
+				).collect(),
+				total_earnings: Asset::all().map(|asset|
+					(asset, pallet_cf_swapping::TotalBrokerEarnings::<Runtime>::get(&account_id, asset))
+				).collect(),
+				channel_earnings: pallet_cf_swapping::BrokerChannelEarnings::<Runtime>::iter_prefix(&account_id)
+					.map(|((asset, channel_id), earned)| (asset, channel_id, earned))
+					.collect(),
+				epoch_earnings: (Validator::current_epoch().saturating_sub(pallet_cf_swapping::BROKER_EPOCH_EARNINGS_RETENTION - 1)..=Validator::current_epoch())
+					.flat_map(|epoch| {
+						pallet_cf_swapping::BrokerEpochEarnings::<Runtime>::get(epoch, &account_id)
+							.into_iter()
+							.map(move |(asset, earned)| (epoch, asset, earned))
+					})
+					.collect(),
+			})
+		}
+
+		fn cf_broker_channels(account_id: AccountId) -> Vec<BrokerChannel> {
+			fn broker_channels<I: 'static>(account_id: &AccountId) -> Vec<BrokerChannel>
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				let current_height = <Runtime as pallet_cf_ingress_egress::Config<I>>::ChainTracking::get_block_height();
+				pallet_cf_ingress_egress::BrokerDepositChannels::<Runtime, I>::iter_prefix(account_id)
+					.filter_map(|(channel_id, channel)| {
+						let details = pallet_cf_ingress_egress::DepositChannelLookup::<Runtime, I>::get(&channel.deposit_address)?;
+						let destination_asset = match details.action {
+							ChannelAction::Swap { destination_asset, .. } |
+							ChannelAction::CcmTransfer { destination_asset, .. } => destination_asset,
+							ChannelAction::LiquidityProvision { .. } => return None,
+						};
+						let source_asset: Asset = details.deposit_channel.asset.into();
+						Some(BrokerChannel {
+							chain: source_asset.into(),
+							channel_id,
+							deposit_address: channel.deposit_address.into(),
+							source_asset,
+							destination_asset,
+							opened_at: details.opened_at.into(),
+							expires_at: details.expires_at.into(),
+							is_open: details.expires_at > current_height,
+							is_sticky: pallet_cf_ingress_egress::StickyDepositChannels::<Runtime, I>::contains_key(&channel.deposit_address),
+							deposit_count: channel.deposit_count,
+							total_deposited: channel.total_deposited.into(),
+						})
+					})
+					.collect()
+			}
+
+			broker_channels::<EthereumInstance>(&account_id)
+				.into_iter()
+				.chain(broker_channels::<BitcoinInstance>(&account_id))
+				.chain(broker_channels::<PolkadotInstance>(&account_id))
+				.chain(broker_channels::<AssethubInstance>(&account_id))
+				.collect()
+		}
+
 		fn cf_account_role(account_id: AccountId) -> Option<AccountRole> {
 			pallet_cf_account_roles::AccountRoles::<Runtime>::get(account_id)
 		}
@@ -1382,6 +1573,11 @@ impl_runtime_apis! {
 							}) if from == swap_from && to == swap_to => {
 								all_prewitnessed_swaps.push(deposit_amount);
 							},
+							RuntimeCall::Swapping(pallet_cf_swapping::Call::schedule_exact_output_swap_from_contract {
+								from: swap_from, to: swap_to, max_input, ..
+							}) if from == swap_from && to == swap_to => {
+								all_prewitnessed_swaps.push(max_input);
+							},
 							RuntimeCall::EthereumIngressEgress(pallet_cf_ingress_egress::Call::process_deposits {
 								deposit_witnesses, ..
 							}) => {
@@ -1397,6 +1593,11 @@ impl_runtime_apis! {
 							}) => {
 								all_prewitnessed_swaps.extend(filter_deposit_swaps::<Polkadot, PolkadotInstance>(from, to, deposit_witnesses));
 							},
+							RuntimeCall::AssethubIngressEgress(pallet_cf_ingress_egress::Call::process_deposits {
+								deposit_witnesses, ..
+							}) => {
+								all_prewitnessed_swaps.extend(filter_deposit_swaps::<Assethub, AssethubInstance>(from, to, deposit_witnesses));
+							},
 							RuntimeCall::Swapping(pallet_cf_swapping::Call::ccm_deposit {
 								source_asset, deposit_amount, destination_asset, deposit_metadata, ..
 							}) => {
@@ -1453,8 +1654,59 @@ impl_runtime_apis! {
 				ForeignChain::Ethereum => pallet_cf_ingress_egress::Pallet::<Runtime, EthereumInstance>::channel_opening_fee(),
 				ForeignChain::Polkadot => pallet_cf_ingress_egress::Pallet::<Runtime, PolkadotInstance>::channel_opening_fee(),
 				ForeignChain::Bitcoin => pallet_cf_ingress_egress::Pallet::<Runtime, BitcoinInstance>::channel_opening_fee(),
+				ForeignChain::Assethub => pallet_cf_ingress_egress::Pallet::<Runtime, AssethubInstance>::channel_opening_fee(),
 			}
 		}
+
+		fn cf_tokenholder_proposals() -> Vec<TokenholderProposalInfo> {
+			pallet_cf_tokenholder_governance::Proposals::<Runtime>::iter()
+				.map(|(resolution_block, proposal)| TokenholderProposalInfo {
+					total_funds_snapshot: pallet_cf_tokenholder_governance::TotalFundsSnapshots::<Runtime>::get(&proposal).unwrap_or_default(),
+					tally: pallet_cf_tokenholder_governance::Tallies::<Runtime>::get(&proposal),
+					proposal,
+					resolution_block,
+				})
+				.collect()
+		}
+
+		fn cf_tokenholder_votes(account_id: AccountId) -> Vec<TokenholderVoteInfo> {
+			pallet_cf_tokenholder_governance::Proposals::<Runtime>::iter_values()
+				.filter_map(|proposal| {
+					let voting_power = pallet_cf_tokenholder_governance::VotingPowerSnapshots::<Runtime>::get(&proposal, &account_id)?;
+					Some(TokenholderVoteInfo {
+						vote: pallet_cf_tokenholder_governance::Votes::<Runtime>::get(&proposal, &account_id),
+						proposal,
+						voting_power,
+					})
+				})
+				.collect()
+		}
+
+		fn cf_timelocked_governance_calls() -> Vec<TimelockedGovernanceCall> {
+			pallet_cf_governance::TimelockedCalls::<Runtime>::iter()
+				.map(|(proposal_id, timelocked_call)| TimelockedGovernanceCall {
+					proposal_id,
+					call: <RuntimeCall as codec::Decode>::decode(&mut &timelocked_call.call[..]).ok(),
+					category: timelocked_call.category,
+					execute_at: timelocked_call.execute_at,
+					vetoed_by: timelocked_call.vetoed_by.into_iter().collect(),
+				})
+				.collect()
+		}
+
+		fn cf_validator_history(account_id: AccountId) -> Vec<ValidatorEpochPerformance> {
+			Reputation::validator_history(&account_id)
+				.into_iter()
+				.map(|(epoch_index, performance)| ValidatorEpochPerformance {
+					epoch_index,
+					rewards: performance.rewards,
+					slashes: performance.slashes,
+					offences: performance.offences.into_inner(),
+					suspensions: performance.suspensions.into_inner(),
+					reputation_at_epoch_end: performance.reputation_at_epoch_end,
+				})
+				.collect()
+		}
 	}
 
 	// END custom runtime APIs
@@ -1,4 +1,6 @@
 //! Chainflip runtime storage migrations.
+pub mod assethub;
+
 use crate::System;
 use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
 use sp_std::marker::PhantomData;
@@ -0,0 +1,210 @@
+//! Storage migrations for adding Assethub to a running chain.
+use crate::{
+	safe_mode::{RuntimeSafeMode, WitnesserCallPermission},
+	AssethubBroadcaster, AssethubChainTracking, AssethubIngressEgress, AssethubInstance,
+	AssethubVault, BitcoinInstance, EthereumInstance, PolkadotInstance, PolkadotThresholdSigner,
+	Runtime, Validator,
+};
+use cf_traits::SafeMode;
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::{RuntimeDbWeight, Weight},
+};
+
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Initialises the storage versions of the Assethub pallet instances, which have no genesis on a
+/// running chain, and bootstraps the Assethub vault with the current Polkadot key.
+pub struct AssethubMigration;
+
+fn initialise_storage_version<P>() -> Weight
+where
+	P: PalletInfoAccess + GetStorageVersion<CurrentStorageVersion = StorageVersion>,
+{
+	if <P as GetStorageVersion>::on_chain_storage_version() == StorageVersion::new(0) {
+		let version = <P as GetStorageVersion>::current_storage_version();
+		version.put::<P>();
+		log::info!("✅ {}: Initialised storage version to {:?}.", P::name(), version);
+		RuntimeDbWeight::default().reads_writes(1, 1)
+	} else {
+		RuntimeDbWeight::default().reads(1)
+	}
+}
+
+impl OnRuntimeUpgrade for AssethubMigration {
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight = initialise_storage_version::<AssethubChainTracking>()
+			.saturating_add(initialise_storage_version::<AssethubVault>())
+			.saturating_add(initialise_storage_version::<AssethubBroadcaster>())
+			.saturating_add(initialise_storage_version::<AssethubIngressEgress>());
+
+		// The Assethub vault shares the Polkadot key, so it is activated with the current one once
+		// governance has witnessed its creation. Like any newly activated vault, it is witnessed
+		// from the next epoch onwards.
+		if pallet_cf_environment::AssethubVaultAccountId::<Runtime>::get().is_none() &&
+			pallet_cf_vaults::PendingVaultActivation::<Runtime, AssethubInstance>::get()
+				.is_none()
+		{
+			if let Some(current_key) = PolkadotThresholdSigner::keys(Validator::current_epoch()) {
+				log::info!("✅ Bootstrapping the Assethub vault with the current Polkadot key.");
+				AssethubVault::await_governance_activation(current_key);
+			}
+		}
+		weight.saturating_accrue(RuntimeDbWeight::default().reads_writes(4, 2));
+
+		weight
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		frame_support::ensure!(
+			<AssethubIngressEgress as GetStorageVersion>::on_chain_storage_version() ==
+				<AssethubIngressEgress as GetStorageVersion>::current_storage_version(),
+			"Assethub ingress-egress storage version not initialised."
+		);
+		frame_support::ensure!(
+			pallet_cf_environment::AssethubVaultAccountId::<Runtime>::get().is_some() ||
+				pallet_cf_vaults::PendingVaultActivation::<Runtime, AssethubInstance>::get()
+					.is_some(),
+			"Assethub vault not bootstrapped."
+		);
+		Ok(())
+	}
+}
+
+/// The runtime safe mode as it was before Assethub was added.
+mod old {
+	use super::*;
+
+	#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq)]
+	pub struct WitnesserCallPermission {
+		pub governance: bool,
+		pub funding: bool,
+		pub swapping: bool,
+		pub ethereum_broadcast: bool,
+		pub ethereum_chain_tracking: bool,
+		pub ethereum_ingress_egress: bool,
+		pub ethereum_vault: bool,
+		pub polkadot_broadcast: bool,
+		pub polkadot_chain_tracking: bool,
+		pub polkadot_ingress_egress: bool,
+		pub polkadot_vault: bool,
+		pub bitcoin_broadcast: bool,
+		pub bitcoin_chain_tracking: bool,
+		pub bitcoin_ingress_egress: bool,
+		pub bitcoin_vault: bool,
+	}
+
+	#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+	pub struct RuntimeSafeMode {
+		pub emissions: pallet_cf_emissions::PalletSafeMode,
+		pub funding: pallet_cf_funding::PalletSafeMode,
+		pub swapping: pallet_cf_swapping::PalletSafeMode,
+		pub liquidity_provider: pallet_cf_lp::PalletSafeMode,
+		pub validator: pallet_cf_validator::PalletSafeMode,
+		pub pools: pallet_cf_pools::PalletSafeMode,
+		pub reputation: pallet_cf_reputation::PalletSafeMode,
+		pub threshold_signature_ethereum:
+			pallet_cf_threshold_signature::PalletSafeMode<EthereumInstance>,
+		pub threshold_signature_bitcoin:
+			pallet_cf_threshold_signature::PalletSafeMode<BitcoinInstance>,
+		pub threshold_signature_polkadot:
+			pallet_cf_threshold_signature::PalletSafeMode<PolkadotInstance>,
+		pub broadcast_ethereum: pallet_cf_broadcast::PalletSafeMode<EthereumInstance>,
+		pub broadcast_bitcoin: pallet_cf_broadcast::PalletSafeMode<BitcoinInstance>,
+		pub broadcast_polkadot: pallet_cf_broadcast::PalletSafeMode<PolkadotInstance>,
+		pub witnesser: pallet_cf_witnesser::PalletSafeMode<WitnesserCallPermission>,
+	}
+}
+
+/// Adds the Assethub settings to the runtime safe mode. They are copied from the Polkadot ones,
+/// since Assethub is secured by the same key.
+pub struct AssethubSafeModeMigration;
+
+impl OnRuntimeUpgrade for AssethubSafeModeMigration {
+	fn on_runtime_upgrade() -> Weight {
+		let _ = pallet_cf_environment::RuntimeSafeMode::<Runtime>::translate::<
+			old::RuntimeSafeMode,
+			_,
+		>(|maybe_old| {
+			maybe_old.map(|old| RuntimeSafeMode {
+				emissions: old.emissions,
+				funding: old.funding,
+				swapping: old.swapping,
+				liquidity_provider: old.liquidity_provider,
+				validator: old.validator,
+				pools: old.pools,
+				reputation: old.reputation,
+				threshold_signature_ethereum: old.threshold_signature_ethereum,
+				threshold_signature_bitcoin: old.threshold_signature_bitcoin,
+				threshold_signature_polkadot: old.threshold_signature_polkadot,
+				broadcast_ethereum: old.broadcast_ethereum,
+				broadcast_bitcoin: old.broadcast_bitcoin,
+				broadcast_assethub: if old.broadcast_polkadot.retry_enabled {
+					<pallet_cf_broadcast::PalletSafeMode<AssethubInstance> as SafeMode>::CODE_GREEN
+				} else {
+					<pallet_cf_broadcast::PalletSafeMode<AssethubInstance> as SafeMode>::CODE_RED
+				},
+				broadcast_polkadot: old.broadcast_polkadot,
+				witnesser: match old.witnesser {
+					pallet_cf_witnesser::PalletSafeMode::CodeGreen =>
+						pallet_cf_witnesser::PalletSafeMode::CodeGreen,
+					pallet_cf_witnesser::PalletSafeMode::CodeRed =>
+						pallet_cf_witnesser::PalletSafeMode::CodeRed,
+					pallet_cf_witnesser::PalletSafeMode::CodeAmber(permission) =>
+						pallet_cf_witnesser::PalletSafeMode::CodeAmber(WitnesserCallPermission {
+							governance: permission.governance,
+							funding: permission.funding,
+							swapping: permission.swapping,
+							ethereum_broadcast: permission.ethereum_broadcast,
+							ethereum_chain_tracking: permission.ethereum_chain_tracking,
+							ethereum_ingress_egress: permission.ethereum_ingress_egress,
+							ethereum_vault: permission.ethereum_vault,
+							polkadot_broadcast: permission.polkadot_broadcast,
+							polkadot_chain_tracking: permission.polkadot_chain_tracking,
+							polkadot_ingress_egress: permission.polkadot_ingress_egress,
+							polkadot_vault: permission.polkadot_vault,
+							bitcoin_broadcast: permission.bitcoin_broadcast,
+							bitcoin_chain_tracking: permission.bitcoin_chain_tracking,
+							bitcoin_ingress_egress: permission.bitcoin_ingress_egress,
+							bitcoin_vault: permission.bitcoin_vault,
+							assethub_broadcast: permission.polkadot_broadcast,
+							assethub_chain_tracking: permission.polkadot_chain_tracking,
+							assethub_ingress_egress: permission.polkadot_ingress_egress,
+							assethub_vault: permission.polkadot_vault,
+						}),
+				},
+			})
+		})
+		.map_err(|_| log::error!("Failed to decode the old runtime safe mode."));
+
+		RuntimeDbWeight::default().reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		let old = frame_support::storage::unhashed::get::<old::RuntimeSafeMode>(
+			&pallet_cf_environment::RuntimeSafeMode::<Runtime>::hashed_key(),
+		);
+		Ok(old.map(|old| old.broadcast_polkadot.retry_enabled).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		if let Some(polkadot_retry_enabled) = <Option<bool>>::decode(&mut &state[..])
+			.map_err(|_| "Failed to decode pre-upgrade state.")?
+		{
+			let safe_mode = pallet_cf_environment::RuntimeSafeMode::<Runtime>::get();
+			frame_support::ensure!(
+				safe_mode.broadcast_polkadot.retry_enabled == polkadot_retry_enabled &&
+					safe_mode.broadcast_assethub.retry_enabled == polkadot_retry_enabled,
+				"Runtime safe mode not migrated."
+			);
+		}
+		Ok(())
+	}
+}
@@ -1,20 +1,26 @@
-use crate::chainflip::Offence;
+use crate::{
+	chainflip::{GovernanceCallCategory, Offence},
+	RuntimeCall,
+};
 use cf_amm::{
 	common::{Amount, Order, Tick},
 	range_orders::Liquidity,
 };
 use cf_chains::{eth::Address as EthereumAddress, Chain, ForeignChainAddress};
 use cf_primitives::{
-	AccountRole, Asset, AssetAmount, BroadcastId, EpochIndex, FlipBalance, ForeignChain,
+	AccountRole, Asset, AssetAmount, BroadcastId, ChannelId, EpochIndex, FlipBalance, ForeignChain,
 	NetworkEnvironment, SemVer, SwapOutput,
 };
 use codec::{Decode, Encode};
 use core::ops::Range;
 use frame_support::sp_runtime::AccountId32;
-use pallet_cf_governance::GovCallHash;
+use pallet_cf_governance::{GovCallHash, ProposalId};
 use pallet_cf_pools::{
-	AskBidMap, AssetsMap, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders, PoolPriceV1,
-	PoolPriceV2, UnidirectionalPoolDepth,
+	AskBidMap, AssetsMap, LpPositionStats, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders,
+	PoolPriceV1, PoolPriceV2, PoolStats, PoolTwap, UnidirectionalPoolDepth,
+};
+use pallet_cf_tokenholder_governance::{
+	Proposal as TokenholderProposal, Vote as TokenholderVote, VoteTally,
 };
 use pallet_cf_witnesser::CallHash;
 use scale_info::{prelude::string::String, TypeInfo};
@@ -77,6 +83,38 @@ pub struct LiquidityProviderInfo {
 	pub balances: Vec<(Asset, AssetAmount)>,
 }
 
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct BrokerEarnings {
+	pub withdrawal_addresses: Vec<(ForeignChain, Option<ForeignChainAddress>)>,
+	/// Commission that has been earned but not yet withdrawn.
+	pub balances: Vec<(Asset, AssetAmount)>,
+	/// Commission earned over the broker's lifetime, including withdrawn amounts.
+	pub total_earnings: Vec<(Asset, AssetAmount)>,
+	/// Lifetime commission earned from each deposit channel, by source asset and channel id.
+	pub channel_earnings: Vec<(Asset, ChannelId, AssetAmount)>,
+	/// Commission earned in each epoch, by epoch index and source asset.
+	pub epoch_earnings: Vec<(EpochIndex, Asset, AssetAmount)>,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct BrokerChannel {
+	pub chain: ForeignChain,
+	pub channel_id: ChannelId,
+	pub deposit_address: ForeignChainAddress,
+	pub source_asset: Asset,
+	pub destination_asset: Asset,
+	/// The range of source chain blocks in which deposits into the channel are witnessed.
+	pub opened_at: u64,
+	pub expires_at: u64,
+	/// False once the source chain has passed `expires_at`, or the channel has been closed.
+	pub is_open: bool,
+	/// Whether the channel is renewed until its broker closes it.
+	pub is_sticky: bool,
+	pub deposit_count: u32,
+	/// The total amount deposited into the channel, before fees.
+	pub total_deposited: AssetAmount,
+}
+
 #[derive(Debug, Decode, Encode, TypeInfo)]
 pub enum DispatchErrorWithMessage {
 	Module(Vec<u8>),
@@ -97,6 +135,41 @@ pub struct FailingWitnessValidators {
 	pub validators: Vec<(cf_primitives::AccountId, String, bool)>,
 }
 
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct TokenholderProposalInfo {
+	pub proposal: TokenholderProposal,
+	pub resolution_block: u32,
+	pub total_funds_snapshot: FlipBalance,
+	pub tally: VoteTally<FlipBalance>,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct TokenholderVoteInfo {
+	pub proposal: TokenholderProposal,
+	pub vote: Option<TokenholderVote>,
+	pub voting_power: FlipBalance,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct TimelockedGovernanceCall {
+	pub proposal_id: ProposalId,
+	/// `None` if the call can no longer be decoded, eg. after a runtime upgrade.
+	pub call: Option<RuntimeCall>,
+	pub category: GovernanceCallCategory,
+	pub execute_at: u64,
+	pub vetoed_by: Vec<AccountId32>,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct ValidatorEpochPerformance {
+	pub epoch_index: EpochIndex,
+	pub rewards: FlipBalance,
+	pub slashes: FlipBalance,
+	pub offences: Vec<(Offence, u32)>,
+	pub suspensions: Vec<(Offence, u32, u32)>,
+	pub reputation_at_epoch_end: Option<i32>,
+}
+
 decl_runtime_apis!(
 	/// Definition for all runtime API interfaces.
 	pub trait CustomRuntimeApi {
@@ -132,6 +205,19 @@ decl_runtime_apis!(
 			base_asset: Asset,
 			quote_asset: Asset,
 		) -> Result<PoolPriceV2, DispatchErrorWithMessage>;
+		/// Returns the time-weighted average price of the pool over the last `window` blocks.
+		fn cf_pool_twap(
+			base_asset: Asset,
+			quote_asset: Asset,
+			window: u32,
+		) -> Result<PoolTwap, DispatchErrorWithMessage>;
+		/// Returns the running totals of the pool's swap volume and fees.
+		fn cf_pool_stats(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<PoolStats, DispatchErrorWithMessage>;
+		/// Returns the lifetime fee earnings of each of the LP's positions.
+		fn cf_lp_stats(lp: AccountId32) -> Vec<LpPositionStats>;
 		fn cf_pool_simulate_swap(
 			from: Asset,
 			to: Asset,
@@ -181,6 +267,9 @@ decl_runtime_apis!(
 			side: Order,
 		) -> Vec<AssetAmount>;
 		fn cf_liquidity_provider_info(account_id: AccountId32) -> Option<LiquidityProviderInfo>;
+		/// Returns None if the account is not a broker.
+		fn cf_broker_earnings(account_id: AccountId32) -> Option<BrokerEarnings>;
+		fn cf_broker_channels(account_id: AccountId32) -> Vec<BrokerChannel>;
 		fn cf_account_role(account_id: AccountId32) -> Option<AccountRole>;
 		fn cf_asset_balances(account_id: AccountId32) -> Vec<(Asset, AssetAmount)>;
 		fn cf_redemption_tax() -> AssetAmount;
@@ -193,5 +282,9 @@ decl_runtime_apis!(
 		fn cf_witness_count(hash: CallHash) -> Option<FailingWitnessValidators>;
 		fn cf_witness_safety_margin(chain: ForeignChain) -> Option<u64>;
 		fn cf_channel_opening_fee(chain: ForeignChain) -> FlipBalance;
+		fn cf_tokenholder_proposals() -> Vec<TokenholderProposalInfo>;
+		fn cf_tokenholder_votes(account_id: AccountId32) -> Vec<TokenholderVoteInfo>;
+		fn cf_timelocked_governance_calls() -> Vec<TimelockedGovernanceCall>;
+		fn cf_validator_history(account_id: AccountId32) -> Vec<ValidatorEpochPerformance>;
 	}
 );
@@ -1,6 +1,8 @@
 //! For filtering runtime calls and other related utilities.
 
-use crate::{BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall};
+use crate::{
+	AssethubInstance, BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall,
+};
 use cf_traits::{impl_runtime_safe_mode, CallDispatchFilter};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -21,6 +23,7 @@ impl_runtime_safe_mode! {
 	broadcast_ethereum: pallet_cf_broadcast::PalletSafeMode<EthereumInstance>,
 	broadcast_bitcoin: pallet_cf_broadcast::PalletSafeMode<BitcoinInstance>,
 	broadcast_polkadot: pallet_cf_broadcast::PalletSafeMode<PolkadotInstance>,
+	broadcast_assethub: pallet_cf_broadcast::PalletSafeMode<AssethubInstance>,
 	witnesser: pallet_cf_witnesser::PalletSafeMode<WitnesserCallPermission>,
 }
 
@@ -62,6 +65,12 @@ pub struct WitnesserCallPermission {
 	pub bitcoin_chain_tracking: bool,
 	pub bitcoin_ingress_egress: bool,
 	pub bitcoin_vault: bool,
+
+	// Assethub pallets
+	pub assethub_broadcast: bool,
+	pub assethub_chain_tracking: bool,
+	pub assethub_ingress_egress: bool,
+	pub assethub_vault: bool,
 }
 
 impl WitnesserCallPermission {
@@ -82,6 +91,10 @@ impl WitnesserCallPermission {
 			bitcoin_chain_tracking: true,
 			bitcoin_ingress_egress: true,
 			bitcoin_vault: true,
+			assethub_broadcast: true,
+			assethub_chain_tracking: true,
+			assethub_ingress_egress: true,
+			assethub_vault: true,
 		}
 	}
 }
@@ -108,6 +121,11 @@ impl CallDispatchFilter<RuntimeCall> for WitnesserCallPermission {
 			RuntimeCall::BitcoinIngressEgress(..) => self.bitcoin_ingress_egress,
 			RuntimeCall::BitcoinVault(..) => self.bitcoin_vault,
 
+			RuntimeCall::AssethubBroadcaster(..) => self.assethub_broadcast,
+			RuntimeCall::AssethubChainTracking(..) => self.assethub_chain_tracking,
+			RuntimeCall::AssethubIngressEgress(..) => self.assethub_ingress_egress,
+			RuntimeCall::AssethubVault(..) => self.assethub_vault,
+
 			_ => {
 				cf_runtime_utilities::log_or_panic!(
 					"All witnesser calls must be controllable through `WitnesserCallPermission`. Call: {:?}",
@@ -19,9 +19,9 @@ use cf_chains::{
 	ChainCrypto, DepositChannel, Ethereum, SwapOrigin,
 };
 use cf_primitives::{
-	AccountRole, Asset, AssetAmount, AuthorityCount, BasisPoints, BroadcastId, ChannelId,
-	Ed25519PublicKey, EgressCounter, EgressId, EpochIndex, FlipBalance, ForeignChain, Ipv6Addr,
-	NetworkEnvironment, SemVer, SwapId, ThresholdSignatureRequestId,
+	AccountRole, Affiliates, Asset, AssetAmount, AuthorityCount, BasisPoints, BroadcastId,
+	ChannelId, Ed25519PublicKey, EgressCounter, EgressId, EpochIndex, FlipBalance, ForeignChain,
+	Ipv6Addr, NetworkEnvironment, SemVer, SwapId, ThresholdSignatureRequestId,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
@@ -201,8 +201,41 @@ pub trait VaultActivator<C: ChainCrypto> {
 	fn set_status(_outcome: AsyncResult<()>);
 }
 
+/// Activates the same key on two vaults, for chains that share a `ChainCrypto` and therefore a
+/// single threshold signer (for example Polkadot and Asset Hub).
+impl<C: ChainCrypto, A, B> VaultActivator<C> for (A, B)
+where
+	A: VaultActivator<C>,
+	B: VaultActivator<C, ValidatorId = A::ValidatorId>,
+{
+	type ValidatorId = A::ValidatorId;
+
+	fn status() -> AsyncResult<()> {
+		match (A::status(), B::status()) {
+			(AsyncResult::Ready(()), AsyncResult::Ready(())) => AsyncResult::Ready(()),
+			(AsyncResult::Void, _) | (_, AsyncResult::Void) => AsyncResult::Void,
+			_ => AsyncResult::Pending,
+		}
+	}
+
+	fn activate(new_key: C::AggKey, maybe_old_key: Option<C::AggKey>) {
+		A::activate(new_key, maybe_old_key);
+		B::activate(new_key, maybe_old_key);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_status(outcome: AsyncResult<()>) {
+		A::set_status(outcome);
+		B::set_status(outcome);
+	}
+}
+
 /// Handler for Epoch life cycle events.
 pub trait EpochTransitionHandler {
+	/// When a new epoch has started, after the authorities of the previous epoch have been rotated
+	/// out.
+	fn on_new_epoch(_previous_epoch: EpochIndex) {}
+
 	/// When an epoch has been expired.
 	fn on_expired_epoch(_expired: EpochIndex) {}
 }
@@ -351,8 +384,11 @@ pub trait Slashing {
 	/// Slashes a validator for the equivalent of some number of blocks offline.
 	fn slash(validator_id: &Self::AccountId, blocks_offline: Self::BlockNumber);
 
-	/// Slashes a validator by some fixed amount.
-	fn slash_balance(account_id: &Self::AccountId, slash_amount: FlipBalance);
+	/// Slashes a validator by some fixed amount. Returns the amount that was actually slashed.
+	fn slash_balance(account_id: &Self::AccountId, slash_amount: FlipBalance) -> FlipBalance;
+
+	/// Returns previously slashed funds to a validator, eg. after a successful appeal.
+	fn refund_slash(account_id: &Self::AccountId, amount: FlipBalance);
 
 	/// Calculate the amount of FLIP to slash
 	fn calculate_slash_amount(
@@ -361,6 +397,17 @@ pub trait Slashing {
 	) -> Self::Balance;
 }
 
+/// Keeps track of the rewards and slashes of validators over the course of each epoch.
+pub trait ValidatorPerformanceRecorder<ValidatorId, Amount> {
+	/// Records a reward paid out to a validator in the current epoch.
+	fn record_reward(_validator_id: &ValidatorId, _amount: Amount) {}
+
+	/// Records an amount slashed from a validator in the current epoch.
+	fn record_slash(_validator_id: &ValidatorId, _amount: Amount) {}
+}
+
+impl<ValidatorId, Amount> ValidatorPerformanceRecorder<ValidatorId, Amount> for () {}
+
 /// Nominate a single account for transaction broadcasting.
 pub trait BroadcastNomination {
 	/// The id type of the broadcaster.
@@ -520,6 +567,10 @@ pub trait Broadcaster<C: Chain> {
 
 	/// Clean up storage data related to a broadcast ID.
 	fn clean_up_broadcast_storage(broadcast_id: BroadcastId);
+
+	/// Keep witnessing a broadcast after it has been aborted or re-signed, so that its success
+	/// callback is still dispatched if someone else broadcasts the signed call.
+	fn witness_after_abort(broadcast_id: BroadcastId);
 }
 
 /// The heartbeat of the network
@@ -651,6 +702,9 @@ pub trait FeePayment {
 
 	/// Burns an amount of tokens, if the account has enough. Otherwise fails.
 	fn try_burn_fee(account_id: &Self::AccountId, amount: Self::Amount) -> DispatchResult;
+
+	/// Mints an amount of tokens to the account, to give back part of a fee that was burned.
+	fn refund_fee(account_id: &Self::AccountId, amount: Self::Amount);
 }
 
 /// Provides information about on-chain funds.
@@ -661,8 +715,23 @@ pub trait FundingInfo {
 	fn total_balance_of(account_id: &Self::AccountId) -> Self::Balance;
 	/// Returns the total amount of funds held on-chain.
 	fn total_onchain_funds() -> Self::Balance;
+	/// Starts recording the funding balance of each account as it is now. Until the checkpoint is
+	/// released, the balance at the checkpoint can be looked up via
+	/// [balance_at_checkpoint](Self::balance_at_checkpoint).
+	fn create_balance_checkpoint() -> BalanceCheckpointId;
+	/// Returns the funding balance an account had when the checkpoint was created.
+	fn balance_at_checkpoint(
+		checkpoint: BalanceCheckpointId,
+		account_id: &Self::AccountId,
+	) -> Self::Balance;
+	/// Stops recording balances for the checkpoint and removes up to `limit` of its records.
+	/// Returns the number of records removed, and whether all of them have been removed.
+	fn release_balance_checkpoint(checkpoint: BalanceCheckpointId, limit: u32) -> (u32, bool);
 }
 
+/// Identifies a point in time at which funding balances are checkpointed.
+pub type BalanceCheckpointId = u32;
+
 /// Allow pallets to open and expire deposit addresses.
 pub trait DepositApi<C: Chain> {
 	type AccountId;
@@ -680,6 +749,7 @@ pub trait DepositApi<C: Chain> {
 		destination_asset: Asset,
 		destination_address: ForeignChainAddress,
 		broker_commission_bps: BasisPoints,
+		affiliates: Affiliates<Self::AccountId>,
 		broker_id: Self::AccountId,
 		channel_metadata: Option<CcmChannelMetadata>,
 		boost_fee: BasisPoints,
@@ -816,6 +886,18 @@ pub trait DepositHandler<C: Chain> {
 		_channel: DepositChannel<C>,
 	) {
 	}
+
+	/// Called when funds are paid directly into the vault rather than into a deposit channel.
+	/// Returns an error if the address does not belong to the vault, or if the chain does not
+	/// support vault deposits.
+	#[allow(clippy::result_unit_err)]
+	fn on_vault_deposit_made(
+		_vault_address: C::ChainAccount,
+		_deposit_details: C::DepositDetails,
+		_amount: C::ChainAmount,
+	) -> Result<(), ()> {
+		Err(())
+	}
 }
 
 pub trait NetworkEnvironmentProvider {
@@ -1,5 +1,7 @@
 use cf_chains::address::ForeignChainAddress;
-use cf_primitives::{Asset, AssetAmount, BasisPoints, ChannelId, SwapId};
+use cf_primitives::{
+	Affiliates, Asset, AssetAmount, BasisPoints, ChannelId, SwapId, TransactionHash,
+};
 use frame_support::pallet_prelude::{DispatchError, DispatchResult};
 use sp_std::vec::Vec;
 
@@ -16,8 +18,35 @@ pub trait SwapDepositHandler {
 		destination_address: ForeignChainAddress,
 		broker_id: Self::AccountId,
 		broker_commission_bps: BasisPoints,
+		affiliates: Affiliates<Self::AccountId>,
 		channel_id: ChannelId,
 	) -> SwapId;
+
+	/// Called when a swap deposit channel is recycled, so that any records kept for the channel
+	/// can be removed.
+	fn on_channel_recycled(
+		_from: Asset,
+		_channel_id: ChannelId,
+		_broker_id: Self::AccountId,
+		_affiliates: Affiliates<Self::AccountId>,
+	) {
+	}
+
+	/// Schedules a swap of funds that were paid directly into the vault, crediting the broker's
+	/// commission. If the swap would return less than `min_output`, the input is refunded to
+	/// `refund_address` instead.
+	#[allow(clippy::too_many_arguments)]
+	fn schedule_vault_swap(
+		from: Asset,
+		to: Asset,
+		amount: AssetAmount,
+		destination_address: ForeignChainAddress,
+		min_output: AssetAmount,
+		refund_address: ForeignChainAddress,
+		broker_id: Self::AccountId,
+		broker_commission_bps: BasisPoints,
+		tx_hash: TransactionHash,
+	) -> SwapId;
 }
 
 pub trait LpDepositHandler {
@@ -76,8 +105,9 @@ impl<T: frame_system::Config> PoolApi for T {
 
 pub trait SwappingApi {
 	/// Takes the swap amount in STABLE_ASSET, collect network fee from it
-	/// and return the remaining value
-	fn take_network_fee(input_amount: AssetAmount) -> AssetAmount;
+	/// and return the remaining value. The fee is accounted to the pools the swap from `from` to
+	/// `to` goes through, split evenly if there are two.
+	fn take_network_fee(from: Asset, to: Asset, input_amount: AssetAmount) -> AssetAmount;
 
 	/// Process a single leg of a swap, into or from Stable asset. No network fee is taken.
 	fn swap_single_leg(
@@ -85,10 +115,29 @@ pub trait SwappingApi {
 		to: Asset,
 		input_amount: AssetAmount,
 	) -> Result<AssetAmount, DispatchError>;
+
+	/// Takes the network fee such that `output_amount` of STABLE_ASSET remains after the fee is
+	/// collected, and returns the amount required before the fee. The fee is accounted like in
+	/// [Self::take_network_fee].
+	fn take_network_fee_exact_output(
+		from: Asset,
+		to: Asset,
+		output_amount: AssetAmount,
+	) -> AssetAmount;
+
+	/// Process a single leg of a swap, into or from Stable asset, that buys at least
+	/// `output_amount`. Returns the input amount required and the amount bought, which may
+	/// slightly exceed `output_amount` when limit orders are partially filled. No network fee is
+	/// taken.
+	fn swap_single_leg_exact_output(
+		from: Asset,
+		to: Asset,
+		output_amount: AssetAmount,
+	) -> Result<(AssetAmount, AssetAmount), DispatchError>;
 }
 
 impl<T: frame_system::Config> SwappingApi for T {
-	fn take_network_fee(input_amount: AssetAmount) -> AssetAmount {
+	fn take_network_fee(_from: Asset, _to: Asset, input_amount: AssetAmount) -> AssetAmount {
 		input_amount
 	}
 
@@ -99,4 +148,49 @@ impl<T: frame_system::Config> SwappingApi for T {
 	) -> Result<AssetAmount, DispatchError> {
 		Ok(input_amount)
 	}
+
+	fn take_network_fee_exact_output(
+		_from: Asset,
+		_to: Asset,
+		output_amount: AssetAmount,
+	) -> AssetAmount {
+		output_amount
+	}
+
+	fn swap_single_leg_exact_output(
+		_from: Asset,
+		_to: Asset,
+		output_amount: AssetAmount,
+	) -> Result<(AssetAmount, AssetAmount), DispatchError> {
+		Ok((output_amount, output_amount))
+	}
+}
+
+/// Provides time-weighted average prices which, unlike the instantaneous pool price, cannot be
+/// moved cheaply within a single block.
+pub trait PriceOracle {
+	type BlockNumber;
+
+	/// Converts `amount` of `from` into `to` at the time-weighted average price over the last
+	/// `window` blocks. Conversions between two non-stable assets are routed via the Stable asset.
+	/// Returns `None` if a required pool doesn't exist, or doesn't have enough price history.
+	fn convert_at_twap(
+		from: Asset,
+		to: Asset,
+		amount: AssetAmount,
+		window: Self::BlockNumber,
+	) -> Option<AssetAmount>;
+}
+
+impl<T: frame_system::Config> PriceOracle for T {
+	type BlockNumber = frame_system::pallet_prelude::BlockNumberFor<T>;
+
+	fn convert_at_twap(
+		_from: Asset,
+		_to: Asset,
+		amount: AssetAmount,
+		_window: Self::BlockNumber,
+	) -> Option<AssetAmount> {
+		Some(amount)
+	}
 }
@@ -74,6 +74,10 @@ impl<
 	/// Clean up storage data related to a broadcast ID.
 	fn clean_up_broadcast_storage(_broadcast_id: BroadcastId) {}
 
+	fn witness_after_abort(broadcast_id: BroadcastId) {
+		Self::put_storage(b"WITNESSED_AFTER_ABORT", broadcast_id, ());
+	}
+
 	fn threshold_sign_and_broadcast_rotation_tx(api_call: Self::ApiCall) -> BroadcastId {
 		<Self as Broadcaster<Api>>::threshold_sign_and_broadcast(api_call)
 	}
@@ -137,6 +141,10 @@ impl<
 		Self::pending_failed_callbacks(Self::get_storage)
 	}
 
+	pub fn witnessed_after_abort(id: BroadcastId) -> bool {
+		Self::get_storage::<_, ()>(b"WITNESSED_AFTER_ABORT", id).is_some()
+	}
+
 	pub fn pending_success_callbacks(
 		mut f: impl FnMut(&[u8], u32) -> Option<C> + 'static,
 	) -> Vec<C> {
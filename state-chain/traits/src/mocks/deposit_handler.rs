@@ -1,8 +1,9 @@
 use crate::{Chainflip, DepositApi};
 use cf_chains::{
-	address::ForeignChainAddress, dot::PolkadotAccountId, CcmChannelMetadata, Chain, ForeignChain,
+	address::ForeignChainAddress, dot::PolkadotAccountId, hub::AssethubAccountId,
+	CcmChannelMetadata, Chain, ForeignChain,
 };
-use cf_primitives::{chains::assets::any, BasisPoints, ChannelId};
+use cf_primitives::{chains::assets::any, Affiliates, BasisPoints, ChannelId};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use sp_std::marker::PhantomData;
@@ -27,6 +28,7 @@ pub struct SwapChannel<C: Chain, T: Chainflip> {
 	pub destination_asset: any::Asset,
 	pub destination_address: ForeignChainAddress,
 	pub broker_commission_bps: BasisPoints,
+	pub affiliates: Affiliates<<T as frame_system::Config>::AccountId>,
 	pub broker_id: <T as frame_system::Config>::AccountId,
 	pub channel_metadata: Option<CcmChannelMetadata>,
 	pub boost_fee: BasisPoints,
@@ -64,6 +66,9 @@ impl<C: Chain, T: Chainflip> MockDepositHandler<C, T> {
 					PolkadotAccountId::from_aliased([channel_id as u8; 32]),
 				),
 				ForeignChain::Bitcoin => todo!("Bitcoin address"),
+				ForeignChain::Assethub => ForeignChainAddress::Hub(
+					AssethubAccountId::from_aliased([channel_id as u8; 32]),
+				),
 			},
 		)
 	}
@@ -111,6 +116,7 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 		destination_asset: cf_primitives::Asset,
 		destination_address: ForeignChainAddress,
 		broker_commission_bps: BasisPoints,
+		affiliates: Affiliates<Self::AccountId>,
 		broker_id: Self::AccountId,
 		channel_metadata: Option<CcmChannelMetadata>,
 		boost_fee: BasisPoints,
@@ -131,6 +137,7 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 					destination_asset,
 					destination_address,
 					broker_commission_bps,
+					affiliates,
 					broker_id,
 					channel_metadata,
 					boost_fee,
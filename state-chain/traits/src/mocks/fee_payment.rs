@@ -22,6 +22,10 @@ impl<T: Chainflip<FundingInfo = MockFundingInfo<T>>> FeePayment for MockFeePayme
 			.ok_or(ERROR_INSUFFICIENT_LIQUIDITY)
 	}
 
+	fn refund_fee(account_id: &Self::AccountId, amount: Self::Amount) {
+		MockFundingInfo::<T>::credit_funds(account_id, amount);
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	fn mint_to_account(account_id: &Self::AccountId, amount: Self::Amount) {
 		MockFundingInfo::<T>::credit_funds(account_id, amount);
@@ -1,10 +1,10 @@
-use crate::{Chainflip, FundingInfo};
+use crate::{BalanceCheckpointId, Chainflip, FundingInfo};
 use frame_support::Never;
 use sp_runtime::{
 	traits::{CheckedSub, Zero},
 	Saturating,
 };
-use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
 
 use super::{MockPallet, MockPalletStorage};
 
@@ -15,6 +15,7 @@ impl<T> MockPallet for MockFundingInfo<T> {
 }
 
 const BALANCES: &[u8] = b"BALANCES";
+const CHECKPOINTS: &[u8] = b"CHECKPOINTS";
 
 impl<T: Chainflip> MockFundingInfo<T> {
 	pub fn credit_funds(account_id: &T::AccountId, amount: T::Amount) {
@@ -79,4 +80,48 @@ impl<T: Chainflip> FundingInfo for MockFundingInfo<T> {
 			})
 			.unwrap_or_default()
 	}
+
+	fn create_balance_checkpoint() -> BalanceCheckpointId {
+		let balances: BTreeMap<T::AccountId, T::Amount> =
+			<Self as MockPalletStorage>::get_value(BALANCES).unwrap_or_default();
+		<Self as MockPalletStorage>::mutate_value(
+			CHECKPOINTS,
+			|storage: &mut Option<Vec<Option<BTreeMap<T::AccountId, T::Amount>>>>| {
+				let checkpoints = storage.get_or_insert_with(Default::default);
+				checkpoints.push(Some(balances));
+				Ok::<_, Never>((checkpoints.len() - 1) as BalanceCheckpointId)
+			},
+		)
+		.unwrap()
+	}
+
+	fn balance_at_checkpoint(
+		checkpoint: BalanceCheckpointId,
+		account_id: &Self::AccountId,
+	) -> Self::Balance {
+		<Self as MockPalletStorage>::get_value(CHECKPOINTS)
+			.and_then(|checkpoints: Vec<Option<BTreeMap<T::AccountId, T::Amount>>>| {
+				checkpoints
+					.get(checkpoint as usize)
+					.cloned()
+					.flatten()
+					.and_then(|balances| balances.get(account_id).cloned())
+			})
+			.unwrap_or_default()
+	}
+
+	fn release_balance_checkpoint(checkpoint: BalanceCheckpointId, _limit: u32) -> (u32, bool) {
+		<Self as MockPalletStorage>::mutate_value(
+			CHECKPOINTS,
+			|storage: &mut Option<Vec<Option<BTreeMap<T::AccountId, T::Amount>>>>| {
+				let removed = storage
+					.as_mut()
+					.and_then(|checkpoints| checkpoints.get_mut(checkpoint as usize))
+					.and_then(Option::take)
+					.map_or(0, |balances| balances.len() as u32);
+				Ok::<_, Never>((removed, true))
+			},
+		)
+		.unwrap()
+	}
 }
@@ -21,6 +21,7 @@ where
 		destination_address: cf_chains::ForeignChainAddress,
 		_broker_id: Self::AccountId,
 		_broker_commission_bps: cf_primitives::BasisPoints,
+		_affiliates: cf_primitives::Affiliates<Self::AccountId>,
 		_channel_id: cf_primitives::ChannelId,
 	) -> SwapId {
 		let _ = E::schedule_egress(
@@ -31,4 +32,24 @@ where
 		);
 		1
 	}
+
+	fn schedule_vault_swap(
+		_from: cf_primitives::Asset,
+		to: cf_primitives::Asset,
+		amount: cf_primitives::AssetAmount,
+		destination_address: cf_chains::ForeignChainAddress,
+		_min_output: cf_primitives::AssetAmount,
+		_refund_address: cf_chains::ForeignChainAddress,
+		_broker_id: Self::AccountId,
+		_broker_commission_bps: cf_primitives::BasisPoints,
+		_tx_hash: cf_primitives::TransactionHash,
+	) -> SwapId {
+		let _ = E::schedule_egress(
+			to.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
+			amount.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
+			destination_address.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
+			None,
+		);
+		1
+	}
 }
@@ -1,3 +1,5 @@
+use cf_primitives::FlipBalance;
+
 /// For reporting offences.
 pub trait OffenceReporter {
 	type ValidatorId: Clone;
@@ -16,4 +18,13 @@ pub trait OffenceReporter {
 
 	/// Forgive all nodes
 	fn forgive_all(offence: impl Into<Self::Offence>);
+
+	/// Records an amount slashed from a node for an offence it has just been reported for, so
+	/// that the slash can be refunded if the offence is successfully appealed.
+	fn report_slash(
+		_offence: impl Into<Self::Offence>,
+		_node: &Self::ValidatorId,
+		_amount: FlipBalance,
+	) {
+	}
 }